@@ -21,6 +21,7 @@
  * - 权限相关: PERMISSION_DENIED
  * - 存储相关: STORAGE_LIMIT, CACHE_ERROR
  * - 版本相关: INCOMPATIBLE_API_VERSION
+ * - 用户输入相关: INPUT_CANCELLED, INPUT_UNAVAILABLE
  * - 其他: UNKNOWN
  */
 export enum PluginErrorType {
@@ -101,6 +102,20 @@ export enum PluginErrorType {
    */
   INCOMPATIBLE_API_VERSION = 'INCOMPATIBLE_API_VERSION',
 
+  /**
+   * 用户取消输入
+   * 触发条件: context.requestInput() 的对话框被用户取消
+   * 可重试: 否 (需用户手动刷新后重新输入)
+   */
+  INPUT_CANCELLED = 'INPUT_CANCELLED',
+
+  /**
+   * 输入对话框不可用
+   * 触发条件: 宿主无法打开输入对话框
+   * 可重试: 否
+   */
+  INPUT_UNAVAILABLE = 'INPUT_UNAVAILABLE',
+
   /**
    * 未知错误
    * 触发条件: 未分类的其他错误
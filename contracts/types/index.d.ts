@@ -28,7 +28,12 @@
 export {
   LogLevel,
   PluginStorage,
+  PluginSecrets,
+  InputFieldOptions,
+  InputRequestOptions,
   PluginCache,
+  TimerScheduleOptions,
+  PluginTimers,
   PluginContext,
   ExposedMethods,
 } from './plugin-context';
@@ -53,6 +58,7 @@ export {
   // 基础类型
   Result,
   AppError,
+  IpcErrorCode,
 
   // 插件类型
   PluginType,
@@ -60,12 +66,30 @@ export {
   PluginInfo,
   UpdateInfo,
   ValidationResult,
+  FieldValidationError,
   HealthStatus,
   PluginHealth,
+  HealthTransition,
+  PluginResourceStats,
+  SubscriberMetrics,
+  EventBusStats,
+  DeadLetter,
+  PluginLogLevel,
+  PluginLogEntry,
+  DiagnosticsFile,
+  AppLogLevel,
+  AppLogEntry,
+  IpcCommandMetrics,
+  IpcMetricsReport,
+  AppUpdatePhase,
+  AppRelease,
+  AppUpdateStatus,
+  PluginAccountData,
 
   // 插件数据类型
   PluginDataBase,
   UsageDimension,
+  TokenBreakdown,
   UsageData,
   BalanceItem,
   BalanceData,
@@ -74,11 +98,143 @@ export {
   CustomData,
   PluginData,
 
+  // 插件输入请求
+  InputField,
+  InputRequest,
+
+  // 网络状态
+  NetworkStatus,
+
+  // 系统健康与管理员策略
+  AdminPolicy,
+  PolicyStatus,
+  SystemHealth,
+
+  // 完整性校验
+  IntegrityReport,
+  AuditEventKind,
+  AuditEvent,
+
+  // 插件市场
+  RegistryEntry,
+  RegistryManifest,
+  PluginBackup,
+  Incompatibility,
+  IncompatiblePlugin,
+  PluginSandboxLimits,
+  PluginDescription,
+  BulkOp,
+  BulkItemResult,
+  BulkResult,
+  KeyState,
+  TrustedKeyInfo,
+  PublisherKeyStatus,
+  UpdateSettings,
+  UsageAlertSettings,
+  MissedAlert,
+  MissedAlertSummary,
+  AlertType,
+  AlertSeverity,
+  AlertRecord,
+  AlertHistoryFilter,
+  WatchdogPolicy,
+  ProxySettings,
+  TlsSettings,
+  CustomRootCertificate,
+
+  // 隐私报告
+  HostReport,
+  PrivacyReport,
+  PluginStorageStats,
+
+  // 预算
+  Budget,
+  BudgetLine,
+  BudgetStatus,
+  ModelPrice,
+  PriceTable,
+  CostRange,
+  ModelCost,
+  CostEstimate,
+  EstimatedSpend,
+
+  // 开发者模式
+  TrustLevel,
+  DeveloperSettings,
+  ImportedKey,
+  SkippedKey,
+  EnvImportReport,
+
+  // 数据推送
+  PushSettings,
+
+  // 弹窗摘要
+  PopupSummary,
+  UsageTrend,
+
+  // 数据摘要
+  DataSummary,
+
+  // 批量查询
+  BatchQuery,
+  BatchResult,
+  BatchResponse,
+
+  // 网关
+  GatewayAuth,
+  GatewayProfile,
+  GatewayConfig,
+
+  // 旧版命令
+  DeprecatedCommand,
+  LegacyCommandReport,
+
+  // 应用设置
+  AppTheme,
+  TrayDisplayMode,
+  AppSettings,
+  AppSettingsPatch,
+  ThemeChangedPayload,
+
+  // 首次设置向导
+  WizardStep,
+  WizardApiKey,
+  WizardStepInput,
+  ConfiguredSecret,
+  WizardProgress,
+  WizardPluginOption,
+  WizardState,
+
   // Commands
   PluginManagementCommands,
   DataCommands,
   ConfigCommands,
   MonitoringCommands,
+  InputCommands,
+  NetworkCommands,
+  IntegrityCommands,
+  RegistryCommands,
+  UpdateCommands,
+  UsageAlertCommands,
+  AlertSilenceCommands,
+  AlertHistoryCommands,
+  PluginBackupCommands,
+  PublisherKeyCommands,
+  MissedAlertCommands,
+  PrivacyCommands,
+  PluginLogCommands,
+  BudgetCommands,
+  CostCommands,
+  DeveloperCommands,
+  PushCommands,
+  PopupCommands,
+  GatewayCommands,
+  LegacyCommands,
+  WizardCommands,
+  SettingsCommands,
+  IpcMetricsCommands,
+  AppUpdateCommands,
+  PluginAccountCommands,
   IPCCommands,
   InvokeCommand,
 } from './ipc-commands';
@@ -91,10 +247,30 @@ export {
   IPCEventName,
   PluginInstalledEvent,
   PluginUninstalledEvent,
+  PluginBulkCompletedEvent,
   PluginUpdatedEvent,
   PluginDataUpdatedEvent,
+  CrossingDirection,
+  BoundaryCrossing,
+  PercentageChange,
+  PluginDataChange,
+  PluginDataChangedEvent,
   PluginErrorEvent,
   PluginHealthChangedEvent,
+  PluginHealthTransitionEvent,
+  InputRequestedEvent,
+  NetworkStatusChangedEvent,
+  UpdateAvailableEvent,
+  PluginReloadedEvent,
+  PluginDataBroadcastEvent,
+  PluginLogEvent,
+  InstallStage,
+  InstallProgress,
+  InstallProgressEvent,
+  BudgetWarningEvent,
+  DeprecatedCommandEvent,
+  WizardFinishedEvent,
+  AppUpdateStatusEvent,
   IPCEvent,
   IPCEventHandlers,
   IPCEventListener,
@@ -121,6 +297,9 @@ export {
   DataType as ModuleDataType,
   PluginMetadata,
   ConfigFieldSchema,
+  RetryPolicy,
+  ResourceLimits,
+  MaintenanceWindow,
   PluginDataBase as ModulePluginDataBase,
   UsageData as ModuleUsageData,
   BalanceData as ModuleBalanceData,
@@ -130,6 +309,8 @@ export {
   ConfigValidationResult,
   DataPlugin,
   EventPlugin,
+  EventSubscription,
+  FilteredEventSubscription,
   HybridPlugin,
   PluginModule,
 } from './plugin-module';
@@ -12,6 +12,8 @@
  * - internal-commands.d.ts: 应用核心功能的 commands（Phase 1 已实现）
  */
 
+import type { Result } from './ipc-commands';
+
 // ============================================================================
 // 系统 Commands (2个)
 // ============================================================================
@@ -41,7 +43,8 @@ export interface SystemCommands {
  * Keychain 命令接口
  *
  * 使用 macOS Keychain 安全存储敏感数据（如 API 密钥）。
- * 在非 macOS 平台上这些命令会返回错误。
+ * 只有设置和仪表盘窗口可以调用，其他窗口返回 COMMAND_NOT_ALLOWED；
+ * 读写失败（含非 macOS 平台）返回 SECRET_STORE_FAILED。
  */
 export interface KeychainCommands {
   /**
@@ -49,36 +52,34 @@ export interface KeychainCommands {
    * @param args.service - 服务名称（如 "com.cuk.app"）
    * @param args.key - 键名（如 "claude_session_key"）
    * @param args.value - 要存储的值
-   * @throws 存储失败时返回错误信息
    */
   keychain_set(args: {
     service: string;
     key: string;
     value: string;
-  }): Promise<void>;
+  }): Promise<Result<void>>;
 
   /**
    * 获取 Keychain 项
    * @param args.service - 服务名称
    * @param args.key - 键名
    * @returns 存储的值，如果不存在返回 null
-   * @throws 读取失败时返回错误信息
    */
   keychain_get(args: {
     service: string;
     key: string;
-  }): Promise<string | null>;
+  }): Promise<Result<string | null>>;
 
   /**
    * 删除 Keychain 项
    * @param args.service - 服务名称
    * @param args.key - 键名
-   * @throws 删除失败时返回错误信息（不存在的项不会报错）
+   * 不存在的项不会报错
    */
   keychain_delete(args: {
     service: string;
     key: string;
-  }): Promise<void>;
+  }): Promise<Result<void>>;
 }
 
 // ============================================================================
@@ -13,7 +13,7 @@
 // 注意: PluginErrorType 在 errors.d.ts 中定义，此处不导入以避免循环依赖
 // 如需使用，请单独导入: import { PluginErrorType } from './errors';
 
-import type { ConfigFieldSchema } from './plugin-module';
+import type { ConfigFieldSchema, EventSubscription, PluginMetadata } from './plugin-module';
 
 // ============================================================================
 // 基础类型
@@ -28,12 +28,119 @@ export interface Result<T = void> {
   error?: AppError;
 }
 
+/**
+ * IPC 错误码
+ *
+ * 与 src-tauri/src/commands/error.rs 中的 codes 常量保持一致，前端应按 code 分支，
+ * 不要依赖 message 文案。插件生命周期错误会先按种类归类（如 PLUGIN_NOT_FOUND、
+ * CONFIG_INVALID），无法归类时返回对应操作的失败码（如 PLUGIN_ENABLE_FAILED）。
+ * 插件执行结果中的错误使用 PluginErrorType 的错误码（见 errors.d.ts），因此 AppError.code 仍为 string。
+ */
+export type IpcErrorCode =
+  // 通用
+  | 'INVALID_ARGUMENT'
+  | 'INVALID_INPUT'
+  | 'INVALID_JSON'
+  | 'BATCH_TOO_LARGE'
+  | 'NOT_SUPPORTED'
+  | 'COMMAND_NOT_ALLOWED'
+  | 'IO_ERROR'
+  // 插件生命周期
+  | 'PLUGIN_NOT_FOUND'
+  | 'PLUGIN_DISABLED'
+  | 'MANIFEST_INVALID'
+  /** details 为 IncompatiblePlugin（支持范围和处理建议） */
+  | 'INCOMPATIBLE_API_VERSION'
+  /** details.fieldErrors 为字段错误列表 */
+  | 'CONFIG_INVALID'
+  | 'SECRET_STORE_FAILED'
+  | 'RESOURCE_LIMIT_EXCEEDED'
+  | 'PLUGIN_ENABLE_FAILED'
+  | 'PLUGIN_DISABLE_FAILED'
+  | 'PLUGIN_UNINSTALL_FAILED'
+  | 'PLUGIN_RELOAD_FAILED'
+  | 'PLUGIN_UPDATE_FAILED'
+  | 'PLUGIN_REFRESH_FAILED'
+  | 'PLUGIN_TEST_RUN_FAILED'
+  | 'PLUGIN_ACCOUNT_FAILED'
+  | 'CONFIG_SET_FAILED'
+  | 'INPUT_NOT_FOUND'
+  | 'DIAGNOSTICS_FAILED'
+  | 'INTEGRITY_CHECK_FAILED'
+  | 'PLUGIN_DISCOVER_FAILED'
+  | 'PLUGIN_TAMPERED'
+  | 'PLUGIN_AUTO_DISABLED'
+  | 'PLUGIN_UPDATE_ROLLED_BACK'
+  | 'LEGACY_COMMAND_DISABLED'
+  // 安全校验
+  | 'SIGNATURE_INVALID'
+  | 'INTEGRITY_FAILED'
+  | 'UNSAFE_ARCHIVE'
+  | 'EXTRACT_FAILED'
+  | 'INSTALL_CANCELLED'
+  | 'BACKUP_NOT_FOUND'
+  | 'POLICY_DENIED'
+  | 'DEVELOPER_MODE_REQUIRED'
+  // 插件安装与 Registry
+  | 'INVALID_SOURCE'
+  | 'DOWNLOAD_FAILED'
+  | 'MANIFEST_PARSE_FAILED'
+  | 'INSTALL_FAILED'
+  | 'REGISTRY_FETCH_FAILED'
+  | 'REGISTRY_INVALID'
+  | 'REGISTRY_SIGNATURE_INVALID'
+  | 'MANIFEST_FETCH_FAILED'
+  | 'ASSET_NOT_FOUND'
+  | 'ASSET_INVALID'
+  // 沙盒运行时
+  | 'RUNTIME_INIT_FAILED'
+  | 'EXECUTION_TIMEOUT'
+  | 'MEMORY_EXCEEDED'
+  | 'EXECUTION_INTERRUPTED'
+  | 'JS_EXECUTION_FAILED'
+  // 设置与窗口
+  | 'ALERT_SILENCE_SAVE_FAILED'
+  | 'ALERT_NOT_FOUND'
+  | 'INVALID_BUDGET'
+  | 'BUDGET_SAVE_FAILED'
+  | 'INVALID_GATEWAY_CONFIG'
+  | 'DEVELOPER_SETTINGS_SAVE_FAILED'
+  | 'LEGACY_SETTINGS_SAVE_FAILED'
+  | 'UPDATE_SETTINGS_SAVE_FAILED'
+  | 'USAGE_ALERT_SETTINGS_SAVE_FAILED'
+  | 'SETTINGS_SAVE_FAILED'
+  | 'WIZARD_FINISHED'
+  | 'WIZARD_STEP_OUT_OF_ORDER'
+  | 'WIZARD_INCOMPLETE'
+  | 'WIZARD_SAVE_FAILED'
+  | 'INVALID_ENV_FILE'
+  | 'ENV_IMPORT_FAILED'
+  | 'PUSH_SERVER_START_FAILED'
+  | 'PUSH_SETTINGS_SAVE_FAILED'
+  | 'PLUGIN_PUSH_NOT_SUPPORTED'
+  | 'WINDOW_OPEN_FAILED'
+  // 签名公钥列表与价格表
+  | 'KEY_LIST_FETCH_FAILED'
+  | 'INVALID_KEY_LIST'
+  | 'KEY_LIST_OUTDATED'
+  | 'KEY_LIST_SAVE_FAILED'
+  | 'PRICING_FETCH_FAILED'
+  | 'INVALID_PRICING_TABLE'
+  | 'PRICING_SAVE_FAILED'
+  // 应用更新
+  | 'APP_UPDATE_CHECK_FAILED'
+  | 'APP_UPDATE_NOT_AVAILABLE'
+  | 'APP_UPDATE_BUSY'
+  | 'APP_UPDATE_DOWNLOAD_FAILED'
+  | 'APP_UPDATE_NOT_READY'
+  | 'APP_UPDATE_INSTALL_FAILED';
+
 /**
  * 应用错误
  */
 export interface AppError {
-  /** 错误码 */
-  code: string;
+  /** 错误码（IPC 命令的错误码见 IpcErrorCode） */
+  code: IpcErrorCode | (string & {});
   /** 用户可读消息 */
   message: string;
   /** 详细信息 (用于调试) */
@@ -60,6 +167,10 @@ export type DataType = 'usage' | 'balance' | 'status' | 'custom';
 export interface PluginInfo {
   /** 插件 ID */
   id: string;
+  /** 插件实例 ID（IPC 调用和 PluginData.pluginId 均使用实例 ID；默认账户与 id 相同） */
+  instanceId: string;
+  /** 账户名称（同一插件添加的其他账户，默认账户不返回） */
+  accountLabel?: string;
   /** 显示名称 */
   name: string;
   /** 版本号 */
@@ -80,8 +191,33 @@ export interface PluginInfo {
   icon?: string;
   /** 配置 Schema */
   configSchema?: Record<string, ConfigFieldSchema>;
+  /** 数据源可能已失效（连续多天 404/410 或域名不存在，停止调度） */
+  possiblyDefunct?: boolean;
+  /** 开始持续失效的时间 (ISO 8601) */
+  defunctSince?: string;
+  /** 已因完整性校验失败被隔离（需重新校验通过才能启用） */
+  quarantined?: boolean;
+  /** 信任级别（developer 为开发者模式加载的未签名插件） */
+  trustLevel?: TrustLevel;
+  /** 已声明但未授予的权限（未受信任的开发者插件） */
+  restrictedPermissions?: string[];
+  /** 接受外部推送数据（本地推送端点） */
+  acceptsPush?: boolean;
+  /** 服务商计划维护截止时间 (ISO 8601)，维护期间暂停刷新，数据停留在维护前 */
+  maintenanceUntil?: string;
+  /** 被看门狗自动禁用的时间 (ISO 8601)，重新启用 (plugin_enable) 后清除 */
+  autoDisabledAt?: string;
 }
 
+/**
+ * 插件信任级别（builtin > signed > installed > developer）
+ * - builtin: 应用内置的第一方数据源，随应用更新，不能卸载
+ * - signed: 已安装且 manifest 签名通过校验，可使用完整沙盒上限和 secrets API
+ * - installed: 已安装但未签名，沙盒上限减半、最多 4 个并发请求、不可使用 secrets API
+ * - developer: 开发者模式下从 dev-plugins 目录加载，沙盒上限减半、最多 2 个并发请求、不可使用 secrets API
+ */
+export type TrustLevel = 'installed' | 'signed' | 'developer' | 'builtin';
+
 /**
  * 更新信息
  */
@@ -110,6 +246,159 @@ export interface UpdateInfo {
   signature: string;
 }
 
+/**
+ * 插件版本备份
+ * 每次更新或覆盖安装前自动保存旧版本，每个插件保留最近 3 个版本
+ */
+export interface PluginBackup {
+  /** 插件 ID */
+  pluginId: string;
+  /** 备份的版本号 */
+  version: string;
+  /** 该版本的安装时间 (ISO 8601) */
+  installedAt: string;
+  /** 备份时间 (ISO 8601) */
+  backedUpAt: string;
+  /** 内容哈希（各文件 SHA-256 按路径排序后的 SHA-256），恢复前校验 */
+  hash: string;
+  /** 文件数 */
+  files: number;
+  /** 总字节数 */
+  sizeBytes: number;
+}
+
+/**
+ * 插件运行时实际生效的限制（manifest 声明、管理员上限和信任级别共同决定）
+ */
+export interface PluginSandboxLimits {
+  /** 沙盒内存上限 (字节) */
+  memoryLimitBytes: number;
+  /** 单次执行超时 (ms) */
+  timeoutMs: number;
+  /** 单次执行内最大并发 fetch 请求数 */
+  maxConcurrentFetches: number;
+  /** 每分钟最多发起的 fetch 请求数 */
+  maxRequestsPerMinute: number;
+}
+
+/**
+ * 插件能力描述（设置界面"插件能做什么"面板）
+ */
+export interface PluginDescription {
+  pluginId: string;
+  instanceId: string;
+  /** 解析后的完整 manifest（旧版本格式已迁移到当前格式） */
+  manifest: PluginMetadata;
+  trustLevel: TrustLevel;
+  /** 实际授予的权限（已去除开发者插件未信任和信任级别不允许的权限） */
+  grantedPermissions: string[];
+  /** 已声明但未授予的权限 */
+  restrictedPermissions?: string[];
+  /**
+   * 沙盒中注入的 API（内置数据源不在沙盒中执行，为空）
+   * 基础 API: console, encoding, errors；按权限: fetch, timer, storage, secrets, input, cache, pluginData
+   */
+  injectedApis: string[];
+  /** 暴露给其他插件调用的方法 */
+  exposedMethods: string[];
+  subscribedEvents: EventSubscription[];
+  /** 允许访问的主机白名单（未声明时可访问任意公网主机） */
+  allowedHosts?: string[];
+  limits: PluginSandboxLimits;
+}
+
+/**
+ * 批量操作类型
+ */
+export type BulkOp = 'enable' | 'disable' | 'refresh' | 'uninstall';
+
+/**
+ * 批量操作中单个插件的结果
+ */
+export interface BulkItemResult {
+  /** 插件实例 ID */
+  id: string;
+  success: boolean;
+  /** 离线时刷新已排队，恢复连接后执行（仅 refresh） */
+  queued?: boolean;
+  error?: AppError;
+}
+
+/**
+ * 批量操作结果（同时作为 ipc:plugin_bulk_completed 事件的 payload）
+ */
+export interface BulkResult {
+  op: BulkOp;
+  /** 各插件的结果（与去重后的 ID 顺序一致） */
+  results: BulkItemResult[];
+  /** 成功数（含已排队） */
+  succeeded: number;
+  /** 失败数 */
+  failed: number;
+}
+
+/**
+ * API 版本不兼容原因
+ * - invalid: apiVersion 缺失或格式无效
+ * - tooOld: 低于可迁移的最低版本（需更新插件）
+ * - tooNew: 高于当前应用支持的版本（需更新应用）
+ */
+export type Incompatibility = 'invalid' | 'tooOld' | 'tooNew';
+
+/**
+ * API 版本不兼容、未加载的插件
+ * 支持范围内的旧版本 manifest 在加载时自动迁移，不会出现在此列表
+ */
+export interface IncompatiblePlugin {
+  /** 插件 ID（manifest 中缺失时为空） */
+  pluginId: string;
+  /** 显示名称 */
+  name?: string;
+  /** manifest 声明的 apiVersion（缺失时为空） */
+  apiVersion: string;
+  /** 应用支持的版本范围 */
+  supported: string;
+  reason: Incompatibility;
+  /** 处理建议 */
+  guidance: string;
+}
+
+/**
+ * 签名密钥状态
+ * - active: 可用于校验签名
+ * - expired: 已过期
+ * - revoked: 已被密钥列表吊销
+ */
+export type KeyState = 'active' | 'expired' | 'revoked';
+
+/**
+ * 签名信任库中的密钥
+ */
+export interface TrustedKeyInfo {
+  /** 密钥 ID（manifest 签名 ed25519:{keyId}:{base64} 中的 keyId） */
+  keyId: string;
+  /** 发布者名称（根密钥省略） */
+  publisher?: string;
+  /** 是否为应用内嵌的根密钥（不能被吊销） */
+  root: boolean;
+  state: KeyState;
+  /** 过期时间 (ISO 8601) */
+  expiresAt?: string;
+}
+
+/**
+ * 签名信任库状态
+ * 第三方发布者公钥通过根密钥签名的密钥列表分发，列表版本单调递增
+ */
+export interface PublisherKeyStatus {
+  /** 当前密钥列表版本（未加载列表时省略） */
+  version?: number;
+  /** 当前密钥列表签发时间 (ISO 8601) */
+  issuedAt?: string;
+  /** 根密钥和发布者密钥 */
+  keys: TrustedKeyInfo[];
+}
+
 /**
  * 配置验证结果
  */
@@ -123,316 +412,2221 @@ export interface ValidationResult {
 }
 
 /**
- * 健康状态
+ * 字段校验错误（set_plugin_config 返回 CONFIG_INVALID 时位于 error.details.fieldErrors）
  */
-export type HealthStatus = 'healthy' | 'degraded' | 'unhealthy';
+export interface FieldValidationError {
+  /** 字段名 */
+  field: string;
+  /** 错误消息 */
+  message: string;
+  /** 错误类型 */
+  errorType: 'required' | 'type_mismatch' | 'out_of_range' | 'invalid_option' | 'unknown_field';
+}
 
 /**
- * 插件健康信息
+ * 解析回放结果
  */
-export interface PluginHealth {
-  /** 插件 ID */
-  pluginId: string;
-  /** 健康状态 */
-  status: HealthStatus;
-  /** 最后成功时间 (ISO 8601) */
-  lastSuccess?: string;
-  /** 最后错误信息 */
-  lastError?: string;
-  /** 错误计数 */
-  errorCount: number;
-  /** 平均延迟 (ms) */
-  avgLatencyMs: number;
-  /** P99 延迟 (ms) */
-  p99LatencyMs: number;
-  /** 成功率 (0-1) */
-  successRate: number;
-  /** 总调用次数 */
-  totalCalls: number;
-  /** 连续失败次数 */
-  consecutiveFailures: number;
+export interface ReplayParseResult {
+  /** 是否无错误 */
+  valid: boolean;
+  /** 解析得到的数据 */
+  data?: PluginData;
+  /** 错误（字段类型不符、解析失败等） */
+  errors: string[];
+  /** 警告（字段缺失使用默认值、dataType 不一致等） */
+  warnings: string[];
 }
 
-// ============================================================================
-// 插件数据类型
-// ============================================================================
+/**
+ * 插件输入字段
+ */
+export interface InputField {
+  /** 字段名（作为返回对象的键） */
+  name: string;
+  /** 显示标签 */
+  label?: string;
+  /** 字段类型，password 以掩码显示 */
+  type: 'text' | 'password' | 'code';
+  /** 占位提示 */
+  placeholder?: string;
+  /** 是否必填 */
+  required: boolean;
+}
 
 /**
- * 插件数据基础接口
+ * 插件输入请求
  */
-export interface PluginDataBase {
-  /** 数据来源插件 */
+export interface InputRequest {
+  /** 请求 ID */
+  requestId: string;
+  /** 发起请求的插件 ID */
   pluginId: string;
-  /** 最后更新时间 (ISO 8601) */
-  lastUpdated: string;
+  /** 对话框标题 */
+  title: string;
+  /** 说明文字 */
+  message?: string;
+  /** 字段列表 */
+  fields: InputField[];
+  /** 过期时间 (ISO 8601) */
+  expiresAt: string;
 }
 
 /**
- * Usage 维度
+ * 网络状态（离线模式）
  */
-export interface UsageDimension {
-  /** 维度 ID */
-  id: string;
-  /** 显示标签 */
-  label: string;
-  /** 使用百分比 */
-  percentage: number;
-  /** 已用量 */
-  used: number;
-  /** 限额 */
-  limit: number;
-  /** 重置时间 */
-  resetTime?: string;
+export interface NetworkStatus {
+  /** 是否在线 */
+  online: boolean;
+  /** 离线起始时间 (ISO 8601)，在线时不存在 */
+  offlineSince?: string;
+  /** 离线期间排队、恢复连接后刷新的插件 ID */
+  queuedRefreshes: string[];
 }
 
 /**
- * 使用量数据
+ * 管理员策略（机器级 policy.json，MDM 下发，只读）
+ * macOS: /Library/Application Support/CUK/policy.json
  */
-export interface UsageData extends PluginDataBase {
-  dataType: 'usage';
-  /** 使用百分比 (0-100) */
-  percentage: number;
-  /** 已用量 */
-  used: number;
-  /** 限额 */
-  limit: number;
-  /** 单位 */
-  unit: string;
-  /** 重置时间 (ISO 8601) */
-  resetTime?: string;
-  /** 重置标签 */
-  resetLabel?: string;
-  /** 多维度使用量 */
-  dimensions?: UsageDimension[];
+export interface AdminPolicy {
+  /** 固定的插件市场 registry.json 地址 */
+  registryUrl?: string;
+  /** 禁止安装新插件（已安装插件的更新不受影响） */
+  disablePluginInstalls: boolean;
+  /** 强制验证插件签名（禁止跳过签名、不加载开发者插件） */
+  requireSignatures: boolean;
+  /** 插件网络访问白名单（与 manifest allowedHosts 同时生效） */
+  networkAllowlist?: string[];
+  /** 插件 manifest `resources.memoryLimitMb` 的上限（默认 64） */
+  maxPluginMemoryMb?: number;
+  /** 插件 manifest `resources.timeoutMs` 的上限（默认 120000） */
+  maxPluginTimeoutMs?: number;
 }
 
 /**
- * 余额子项（用于多配额场景，如 PAYGO/PLUS/FREE）
+ * 管理员策略状态
  */
-export interface BalanceItem {
-  /** 子项名称（如 PAYGO, PLUS, FREE） */
-  name: string;
-  /** 已用额度 */
-  used: number;
-  /** 总额度 */
-  quota: number;
-  /** 使用百分比 (0-100) */
-  percentage: number;
-  /** 货币/单位 */
-  currency?: string;
-  /** 重置时间 (ISO 8601) */
-  resetTime?: string;
-  /** 重置标签（如 "今日已用完"、"12-31 04:43 可重置"） */
-  resetLabel?: string;
-  /** 到期时间 (ISO 8601) */
-  expiresAt?: string;
-  /** 剩余天数 */
-  remainingDays?: number;
-  /** 是否可刷新 */
-  refreshable?: boolean;
+export interface PolicyStatus {
+  /** 策略文件路径 */
+  path: string;
+  /** 是否已加载策略文件 */
+  loaded: boolean;
+  /** 策略文件解析错误（此时按最严格的策略处理） */
+  error?: string;
+  /** 生效的策略 */
+  policy: AdminPolicy;
+  /** 生效策略的名称（如 registryUrl、requireSignatures） */
+  active: string[];
 }
 
 /**
- * 余额数据
+ * 系统健康状态
  */
-export interface BalanceData extends PluginDataBase {
-  dataType: 'balance';
-  /** 余额 */
-  balance: number;
-  /** 货币 */
-  currency: string;
-  /** 总额度 */
-  quota?: number;
-  /** 已用额度 */
-  usedQuota?: number;
-  /** 到期时间 (ISO 8601) */
-  expiresAt?: string;
-  /** 多配额子项（如 PAYGO/PLUS/FREE） */
-  items?: BalanceItem[];
-  /** 是否显示总额度（默认 false） */
-  showTotal?: boolean;
+export interface SystemHealth {
+  /** 应用版本 */
+  appVersion: string;
+  /** 已加载插件数 */
+  pluginCount: number;
+  /** 已启用插件数 */
+  enabledCount: number;
+  /** 已启用但不健康的插件数 */
+  unhealthyCount: number;
+  /** 网络状态 */
+  network: NetworkStatus;
+  /** 管理员策略 */
+  policy: PolicyStatus;
 }
 
 /**
- * 状态指示器
+ * 插件完整性校验报告
  */
-export type StatusIndicator = 'none' | 'minor' | 'major' | 'critical' | 'unknown';
+export interface IntegrityReport {
+  /** 插件 ID */
+  pluginId: string;
+  /** 校验时间 (ISO 8601) */
+  checkedAt: string;
+  /** manifest 中是否声明了文件哈希（未声明时无法校验） */
+  hasHashes: boolean;
+  /** 校验的文件数 */
+  filesChecked: number;
+  /** 校验失败的文件描述 */
+  issues: string[];
+  /** 校验后是否处于隔离状态 */
+  quarantined: boolean;
+}
 
 /**
- * 状态数据
+ * 安全审计事件类型
  */
-export interface StatusData extends PluginDataBase {
-  dataType: 'status';
-  /** 状态指示 */
-  indicator: StatusIndicator;
-  /** 状态描述 */
+export type AuditEventKind = 'integrity_failed' | 'plugin_quarantined' | 'quarantine_released';
+
+/**
+ * 安全审计事件
+ */
+export interface AuditEvent {
+  /** 事件时间 (ISO 8601) */
+  timestamp: string;
+  /** 事件类型 */
+  kind: AuditEventKind;
+  /** 相关插件 ID */
+  pluginId?: string;
+  /** 事件描述 */
+  message: string;
+  /** 详细信息 */
+  details?: string[];
+}
+
+/**
+ * registry 中的插件条目（来自已验证签名的索引）
+ */
+export interface RegistryEntry {
+  id: string;
+  name: string;
   description: string;
+  author: string;
+  version: string;
+  downloads: number;
+  verified: boolean;
+  icon?: string;
+  /** 截图地址 */
+  screenshots?: string[];
+  pluginType?: PluginType;
+  dataType?: DataType;
+  downloadUrl?: string;
+  /** manifest.json 地址（缺省时由 downloadUrl 目录推导） */
+  manifestUrl?: string;
+  tags: string[];
+  updatedAt?: string;
+  repository?: string;
 }
 
 /**
- * 自定义数据
+ * registry 中插件的 manifest
  */
-export interface CustomData extends PluginDataBase {
-  dataType: 'custom';
-  /** 自定义渲染 HTML */
-  renderHtml?: string;
-  /** 自定义数据 */
-  payload: Record<string, unknown>;
-  /** 卡片标题 */
-  title?: string;
-  /** 卡片副标题 */
-  subtitle?: string;
+export interface RegistryManifest {
+  /** registry 条目 */
+  entry: RegistryEntry;
+  /** manifest.json 内容 */
+  manifest: Record<string, unknown>;
+  /** manifest 签名是否有效 */
+  signatureValid: boolean;
+  /** 签名验证失败原因 */
+  signatureError?: string;
 }
 
 /**
- * 插件数据联合类型
+ * 插件自动更新设置
  */
-export type PluginData = UsageData | BalanceData | StatusData | CustomData;
+export interface UpdateSettings {
+  /** 是否启用后台检查 */
+  enabled: boolean;
+  /** 检查间隔（小时，最小 1） */
+  intervalHours: number;
+  /** 允许自动安装更新的插件 ID（更新后健康检查失败时自动回滚） */
+  trusted: string[];
+  /** registry.json URL（为空时使用默认地址） */
+  registryUrl?: string;
+}
 
-// ============================================================================
-// IPC Commands 定义
-// ============================================================================
+/**
+ * 用量提醒设置
+ * 每次刷新后检查使用量（含多维度），同一阈值在一个重置周期内只提醒一次
+ */
+export interface UsageAlertSettings {
+  /** 是否启用用量提醒 */
+  enabled: boolean;
+  /** 默认阈值（百分比），默认 [80, 95] */
+  defaultThresholds: number[];
+  /** 按插件配置的阈值（覆盖默认阈值，空数组表示不提醒） */
+  plugins: Record<string, number[]>;
+}
 
 /**
- * 插件管理 Commands (9个)
+ * 未送达的告警（通知权限未授予或系统繁忙时发送失败，等待重投）
  */
-export interface PluginManagementCommands {
-  /**
-   * 获取所有插件列表
-   */
-  plugin_list(): Promise<Result<PluginInfo[]>>;
+export interface MissedAlert {
+  /** 队列内唯一 ID */
+  id: number;
+  /** 通知标题 */
+  title: string;
+  /** 通知内容 */
+  body: string;
+  /** 是否为严重告警 */
+  critical: boolean;
+  /** 首次发送失败的时间 (ISO 8601) */
+  createdAt: string;
+  /** 已重投次数 */
+  attempts: number;
+  /** 下次重投时间 (ISO 8601)，30 秒起按 2 倍退避，最长 1 小时 */
+  nextAttemptAt: string;
+}
 
-  /**
-   * 启用插件
-   */
-  plugin_enable(args: { id: string }): Promise<Result>;
+/**
+ * 错过的告警摘要
+ */
+export interface MissedAlertSummary {
+  /** 待重投的告警数（应用内"错过的告警"计数） */
+  count: number;
+  /** 过期时间（小时），超过后未送达的告警被丢弃 */
+  expiryHours: number;
+  /** 待重投的告警（按时间升序） */
+  alerts: MissedAlert[];
+}
 
-  /**
-   * 禁用插件
-   */
-  plugin_disable(args: { id: string }): Promise<Result>;
+/**
+ * 告警类型
+ */
+export type AlertType =
+  | 'consecutive_failures'
+  | 'high_latency'
+  | 'low_success_rate'
+  | 'anomaly_detected'
+  | 'auto_disabled';
 
-  /**
-   * 安装插件
-   */
-  plugin_install(args: {
-    /** 插件来源 (URL 或本地路径) */
-    source: string;
-    /** 是否跳过签名验证 */
-    skipSignature?: boolean;
-  }): Promise<Result<PluginInfo>>;
+/**
+ * 告警级别
+ */
+export type AlertSeverity = 'warning' | 'critical';
 
-  /**
-   * 卸载插件
-   */
-  plugin_uninstall(args: { id: string }): Promise<Result>;
+/**
+ * 告警历史记录（最多保留 1000 条、30 天）
+ */
+export interface AlertRecord {
+  /** 历史内唯一 ID */
+  id: number;
+  /** 告警类型 */
+  alertType: AlertType;
+  /** 告警级别 */
+  severity: AlertSeverity;
+  /** 插件 ID */
+  pluginId: string;
+  /** 告警消息 */
+  message: string;
+  /** 触发时间 (ISO 8601) */
+  timestamp: string;
+  /** 告警条件恢复的时间 (ISO 8601)，插件恢复健康或用量恢复正常时记录 */
+  resolvedAt?: string;
+  /** 用户确认的时间 (ISO 8601) */
+  acknowledgedAt?: string;
+}
 
+/**
+ * 告警历史查询条件（均为可选）
+ */
+export interface AlertHistoryFilter {
+  pluginId?: string;
+  alertType?: AlertType;
+  severity?: AlertSeverity;
+  /** 只返回该时间之后触发的告警 (ISO 8601) */
+  since?: string;
+  /** 只返回未恢复的告警 */
+  unresolvedOnly?: boolean;
+  /** 只返回未确认的告警 */
+  unacknowledgedOnly?: boolean;
+  /** 最多返回条数，默认 100 */
+  limit?: number;
+}
+
+/**
+ * 隐私报告中单个主机的统计
+ */
+export interface HostReport {
+  /** 主机名 */
+  host: string;
+  /** 请求数 */
+  requests: number;
+  /** 失败数（未收到响应） */
+  failures: number;
+  /** 发送字节数（请求头 + 请求体） */
+  bytesSent: number;
+  /** 接收字节数（响应体） */
+  bytesReceived: number;
+  /** 作为请求头发送的配置字段名（不含值） */
+  headerFields: string[];
+  /** 报告期内首次访问日期 (YYYY-MM-DD) */
+  firstSeen: string;
+  /** 报告期内最后访问日期 (YYYY-MM-DD) */
+  lastSeen: string;
+}
+
+/**
+ * 插件隐私报告：报告期内离开本机的数据
+ * 由网络访问统计（保留 30 天）和安全审计日志汇总
+ */
+export interface PrivacyReport {
+  pluginId: string;
+  /** 报告天数 */
+  days: number;
+  /** 报告起始时间 (ISO 8601) */
+  since: string;
+  /** 生成时间 (ISO 8601) */
+  generatedAt: string;
+  /** manifest 声明的主机白名单（缺省表示未限制） */
+  declaredHosts?: string[];
+  /** 访问过的主机（按请求数降序） */
+  hosts: HostReport[];
+  /** 总请求数 */
+  totalRequests: number;
+  /** 总发送字节数 */
+  totalBytesSent: number;
+  /** 总接收字节数 */
+  totalBytesReceived: number;
+  /** 作为请求头发送过的配置字段名（不含值） */
+  headerFields: string[];
+  /** 报告期内与该插件相关的审计事件 */
+  auditEvents: AuditEvent[];
+}
+
+/**
+ * 插件存储占用（storage 文件 + cache 目录）
+ */
+export interface PluginStorageStats {
+  pluginId: string;
+  /** storage 已用字节数 */
+  storageBytes: number;
+  /** storage 配额（字节） */
+  storageQuota: number;
+  /** cache 条目数 */
+  cacheEntries: number;
+  /** cache 已用字节数 */
+  cacheBytes: number;
+  /** cache 配额（字节） */
+  cacheQuota: number;
+}
+
+/**
+ * 月度预算
+ */
+export interface Budget {
+  /** 月度上限 */
+  limit: number;
+  /** 货币（ISO 4217，如 USD、CNY） */
+  currency: string;
+}
+
+/**
+ * 单条预算状态（插件或全局）
+ * 花费来自余额下降和货币单位用量的增长，充值/重置不计入
+ */
+export interface BudgetLine {
+  /** 插件 ID（全局预算省略） */
+  pluginId?: string;
+  /** 货币 */
+  currency: string;
+  /** 本月已花费 */
+  spent: number;
+  /** 按本月平均速率推算的月底花费（观测不足 1 小时时省略） */
+  projected?: number;
+  /** 月度上限（未设置预算时省略） */
+  limit?: number;
+  /** 预计超出预算 */
+  overBudget: boolean;
+}
+
+/**
+ * 本月预算状态
+ */
+export interface BudgetStatus {
+  /** 统计月份 (YYYY-MM, UTC) */
+  month: string;
+  /** 全局预算（所有插件同币种花费之和） */
+  global?: BudgetLine;
+  /** 有花费或设置了预算的插件（按插件 ID 排序） */
+  plugins: BudgetLine[];
+}
+
+/**
+ * 模型价格（USD / 百万 token）
+ */
+export interface ModelPrice {
+  /** 输入 */
+  input: number;
+  /** 输出 */
+  output: number;
+  /** 写入缓存（省略时为输入价格的 1.25 倍） */
+  cacheWrite?: number;
+  /** 读取缓存（省略时为输入价格的 0.1 倍） */
+  cacheRead?: number;
+}
+
+/**
+ * 模型价格表
+ * 内置一份，远程价格表按模型覆盖内置价格
+ */
+export interface PriceTable {
+  /** 版本 */
+  version: string;
+  /** 货币（目前只支持 USD） */
+  currency: string;
+  /** 模型名（不含日期后缀）-> 价格 */
+  models: Record<string, ModelPrice>;
+}
+
+/**
+ * 费用估算区间（含今天，按 UTC 日期）
+ * - day: 今天
+ * - week: 最近 7 天
+ * - month: 最近 30 天
+ */
+export type CostRange = 'day' | 'week' | 'month';
+
+/**
+ * 单个模型的预估花费
+ */
+export interface ModelCost {
+  /** 模型名（插件上报） */
+  model: string;
+  /** 匹配到的价格表模型名（无价格时省略） */
+  pricedAs?: string;
+  /** 区间内 token 用量 */
+  tokens: TokenBreakdown;
+  /** 预估花费（无价格时省略） */
+  cost?: number;
+}
+
+/**
+ * 插件费用估算
+ * 只有上报了 token 明细的使用量维度（维度 ID 为模型名）参与估算
+ */
+export interface CostEstimate {
+  pluginId: string;
+  range: CostRange;
+  /** 区间起始时间 (ISO 8601) */
+  start: string;
+  /** 区间结束时间 (ISO 8601) */
+  end: string;
+  currency: string;
+  /** 预估总花费（不含无价格的模型） */
+  total: number;
+  /** 各模型花费（按花费降序） */
+  models: ModelCost[];
+  /** 无价格模型的 token 数 */
+  unpricedTokens: number;
+  /** 使用的价格表版本 */
+  pricingVersion: string;
+}
+
+/**
+ * 摘要中的预估花费
+ */
+export interface EstimatedSpend {
+  range: CostRange;
+  currency: string;
+  /** 所有插件的预估花费之和 */
+  total: number;
+}
+
+/**
+ * 开发者模式设置
+ * 开启后从 ~/.config/cuk/dev-plugins/ 加载未签名插件
+ */
+export interface DeveloperSettings {
+  /** 是否启用开发者模式 */
+  enabled: boolean;
+  /** 用户显式信任的开发者插件 ID（授予全部声明权限，否则仅 timer/storage/cache） */
+  trusted: string[];
+}
+
+/**
+ * .env 导入的密钥（不含值）
+ * 值写入 Keychain（服务名 cuk.plugin.{pluginId}），插件配置中对应字段为 `{ secretRef: secretName }`
+ */
+export interface ImportedKey {
+  /** 环境变量名（如 ANTHROPIC_API_KEY） */
+  variable: string;
+  /** 目标插件 ID */
+  pluginId: string;
+  /** 插件密钥名称（插件通过 context.secrets.get(secretName) 读取） */
+  secretName: string;
+}
+
+/**
+ * 已识别但未导入的变量
+ */
+export interface SkippedKey {
+  /** 环境变量名 */
+  variable: string;
+  /** 目标插件 ID（写入失败时） */
+  pluginId?: string;
+  /** 原因 */
+  reason: string;
+}
+
+/**
+ * .env 导入报告
+ */
+export interface EnvImportReport {
+  /** 已导入 */
+  imported: ImportedKey[];
+  /** 已识别但未导入 */
+  skipped: SkippedKey[];
+  /** 未识别的变量数量 */
+  ignored: number;
+}
+
+/**
+ * 本地推送端点设置
+ * 开启后监听 127.0.0.1:{port}，接收 `POST /v1/push/{pluginId}`
+ */
+export interface PushSettings {
+  /** 是否启用本地推送端点 */
+  enabled: boolean;
+  /** 监听端口（默认 47821） */
+  port: number;
+  /** 插件 ID -> token 的 SHA-256 哈希 */
+  tokens: Record<string, string>;
+}
+
+/**
+ * 弹窗摘要
+ * 延迟超出预算（150ms）时返回上次结果，snapshotOnly 为 true，直到竞争消退
+ */
+export interface PopupSummary {
+  /** 插件列表 */
+  plugins: PluginInfo[];
+  /** 插件缓存数据 */
+  data: PluginData[];
+  /** 插件健康状态 */
+  health: PluginHealth[];
+  /** 网络状态 */
+  network: NetworkStatus;
+  /** 是否为快照 */
+  snapshotOnly: boolean;
+  /** 生成时间 (ISO 8601) */
+  generatedAt: string;
+}
+
+/**
+ * 使用率趋势（弹窗迷你折线图）
+ * 最近 24 小时按 points 等分，每个点为该时间段内的峰值使用百分比
+ */
+export interface UsageTrend {
+  /** 插件 ID */
+  pluginId: string;
+  /** 窗口起始时间 (ISO 8601) */
+  start: string;
+  /** 窗口结束时间 (ISO 8601) */
+  end: string;
+  /** 每个点覆盖的时长（毫秒） */
+  bucketMs: number;
+  /** 各时间段峰值使用百分比 (0-100)，无采样的时间段为 null */
+  points: (number | null)[];
+}
+
+/**
+ * 多插件数据摘要
+ * 聚合所有已启用插件的缓存数据，在 Rust 端计算以保证各窗口展示一致
+ */
+export interface DataSummary {
+  /** 参与聚合的插件数 */
+  pluginCount: number;
+  /** 最高使用率（含多维度） */
+  maxUsage?: {
+    pluginId: string;
+    percentage: number;
+    /** 维度标签（来自多维度使用量时） */
+    dimension?: string;
+  };
+  /** 最低余额（不做货币换算） */
+  lowestBalance?: {
+    pluginId: string;
+    balance: number;
+    currency: string;
+  };
+  /** 最差状态（critical > major > minor > unknown > none） */
+  worstStatus?: {
+    pluginId: string;
+    indicator: StatusIndicator;
+    description: string;
+  };
+  /** 最近的未来重置时间 */
+  soonestReset?: {
+    pluginId: string;
+    /** 重置时间 (ISO 8601) */
+    resetTime: string;
+    /** 重置标签或维度标签 */
+    label?: string;
+  };
+  /** 最近 30 天按模型价格估算的花费（没有 token 用量记录时省略） */
+  estimatedSpend?: EstimatedSpend;
+  /** 生成时间 (ISO 8601) */
+  generatedAt: string;
+}
+
+/**
+ * 批量查询请求（command 与对应的独立命令同名，仅支持只读命令）
+ */
+export type BatchQuery =
+  | { command: 'plugin_list' }
+  | { command: 'get_all_data' }
+  | { command: 'get_all_health' }
+  | { command: 'get_summary' }
+  | { command: 'get_plugin_data'; id: string }
+  | { command: 'get_plugin_health'; id: string };
+
+/**
+ * 批量查询结果（与请求顺序一一对应）
+ */
+export type BatchResult =
+  | { command: 'plugin_list'; result: PluginInfo[] }
+  | { command: 'get_all_data'; result: PluginData[] }
+  | { command: 'get_all_health'; result: PluginHealth[] }
+  | { command: 'get_summary'; result: DataSummary }
+  | { command: 'get_plugin_data'; result: PluginData | null }
+  | { command: 'get_plugin_health'; result: PluginHealth | null };
+
+/**
+ * 批量查询响应
+ * 所有结果来自同一个插件表快照，不会出现列表与数据/健康状态不一致
+ */
+export interface BatchResponse {
+  /** 各请求的结果（与请求顺序一致） */
+  results: BatchResult[];
+  /** 快照时间 (ISO 8601) */
+  snapshotAt: string;
+}
+
+/**
+ * 网关鉴权方案
+ * - apiKey: `x-api-key: <key>`
+ * - bearer: `Authorization: Bearer <key>`
+ * - header: 自定义请求头，值为 `<prefix><key>`
+ * - none: 网关自行鉴权，丢弃插件提供的凭证
+ */
+export type GatewayAuth =
+  | { scheme: 'apiKey' }
+  | { scheme: 'bearer' }
+  | { scheme: 'header'; name: string; prefix?: string }
+  | { scheme: 'none' };
+
+/**
+ * 单个网关配置
+ */
+export interface GatewayProfile {
+  /** 基础地址（替换插件 URL 中的 `{{gateway.base}}`，必须为 https） */
+  baseUrl: string;
+  /** 鉴权方案（改写插件请求头中的 `{{gateway.auth}}`） */
+  auth: GatewayAuth;
+  /** 附加请求头（插件未设置同名请求头时添加） */
+  headers: Record<string, string>;
+}
+
+/**
+ * 宿主网关配置
+ */
+export interface GatewayConfig {
+  /** 默认网关名称 */
+  default?: string;
+  /** 已配置的网关 */
+  gateways: Record<string, GatewayProfile>;
+  /** 插件 ID -> 网关名称（覆盖默认网关） */
+  plugins: Record<string, string>;
+}
+
+/**
+ * Phase 2 旧版命令的调用统计
+ */
+export interface DeprecatedCommand {
+  /** 旧命令名（如 list_plugins） */
+  command: string;
+  /** 替代命令（无替代时省略） */
+  replacement?: string;
+  /** 累计调用次数 */
+  count: number;
+  /** 最后调用时间 (ISO 8601) */
+  lastUsed?: string;
+}
+
+/**
+ * 旧版命令统计报告（仅本地统计，不上报）
+ */
+export interface LegacyCommandReport {
+  /** 是否允许调用旧版命令（停用后调用直接返回错误） */
+  enabled: boolean;
+  /** 所有旧命令（含未调用过的） */
+  commands: DeprecatedCommand[];
+}
+
+/**
+ * 界面主题
+ */
+export type AppTheme = 'light' | 'dark' | 'system';
+
+/**
+ * 左键点击托盘时的显示方式（popup: 托盘弹窗，hud: 只显示百分比的置顶小窗）
+ */
+export type TrayDisplayMode = 'popup' | 'hud';
+
+/**
+ * 托盘点击动作
+ */
+export type TrayClickAction = 'toggle_popup' | 'refresh_all' | 'open_dashboard' | 'none';
+
+/**
+ * 托盘点击配置（按修饰键路由；refresh_all 直接强制刷新所有插件）
+ */
+export interface TrayClickConfig {
+  /** 普通点击 */
+  click: TrayClickAction;
+  /** Option/Alt + 点击 */
+  optionClick: TrayClickAction;
+  /** Shift + 点击 */
+  shiftClick: TrayClickAction;
+}
+
+/**
+ * 应用设置（app_settings.json，带版本号，加载时自动迁移）
+ */
+export interface AppSettings {
+  /** 设置版本 */
+  version: number;
+  /** 界面主题 */
+  theme: AppTheme;
+  /** 左键点击托盘时的显示方式 */
+  trayDisplay: TrayDisplayMode;
+  /** 托盘点击动作 */
+  trayClick: TrayClickConfig;
+  /** 刷新节奏 */
+  refresh: {
+    /** 自动刷新间隔 (毫秒，1 分钟 ~ 24 小时) */
+    intervalMs: number;
+    /** 启动时立即刷新 */
+    onLaunch: boolean;
+  };
+  /** 通知偏好 */
+  notifications: {
+    /** 是否发送系统通知 */
+    enabled: boolean;
+    /** 通知是否播放提示音 */
+    sound: boolean;
+  };
+  /** 启动行为 */
+  startup: {
+    /** 开机自启动 */
+    launchAtLogin: boolean;
+    /** 显示菜单栏图标 */
+    showInMenuBar: boolean;
+  };
+  /** 失控插件看门狗策略 */
+  watchdog: WatchdogPolicy;
+  /** 插件请求代理 */
+  proxy: ProxySettings;
+  /** 自定义根证书与证书固定 */
+  tls: TlsSettings;
+}
+
+/**
+ * 失控插件看门狗策略
+ * 插件连续执行超时或内存超限达到阈值时自动禁用，触发 auto_disabled 告警并发送 PLUGIN_AUTO_DISABLED 错误事件
+ */
+export interface WatchdogPolicy {
+  /** 是否自动禁用失控插件，默认 true */
+  enabled: boolean;
+  /** 连续多少次执行超时/内存超限后禁用 (1 ~ 20)，默认 3 */
+  maxConsecutiveRunaways: number;
+}
+
+/**
+ * 插件请求代理（默认关闭）
+ * 密码通过 keychain_set 保存在 Keychain（service 为 `cuk.proxy`，key 为用户名），修改密码后需重新保存代理设置。
+ * 代理地址需通过私网检查（不允许 localhost、私有 IP、内部域名），否则更新设置返回 INVALID_ARGUMENT。
+ * 启用后插件请求的目标主机由代理解析，本地 DNS 私网检查只作用于代理地址。
+ */
+export interface ProxySettings {
+  /** 是否对插件请求使用代理 */
+  enabled: boolean;
+  /** 代理主机 */
+  host: string;
+  /** 代理端口 */
+  port: number;
+  /** 代理用户名（可选） */
+  username?: string;
+}
+
+/**
+ * 插件请求的 TLS 设置（用于经 TLS 解密网关访问外网的企业网络）
+ * 证书或指纹格式无效时更新设置返回 INVALID_ARGUMENT。
+ */
+export interface TlsSettings {
+  /** 在内置根证书之外额外信任的根证书（最多 16 个） */
+  rootCertificates: CustomRootCertificate[];
+  /**
+   * 主机 -> 允许的叶子证书 SHA-256 指纹（十六进制，可带冒号）
+   * 服务器证书不匹配时 TLS 握手失败，请求头和请求体不会发送，插件收到 ok=false 的响应
+   */
+  pinnedHosts: Record<string, string[]>;
+}
+
+/**
+ * 自定义根证书
+ */
+export interface CustomRootCertificate {
+  /** 显示名称 */
+  name: string;
+  /** PEM 编码的证书（可包含多个证书） */
+  pem: string;
+}
+
+/**
+ * 当前主题（window:theme_changed 事件 Payload）
+ */
+export interface ThemeChangedPayload {
+  /** 主题模式 */
+  theme: AppTheme;
+  /** 解析后的实际外观（system 模式下为当前系统外观） */
+  resolved: 'light' | 'dark';
+}
+
+/**
+ * 应用设置修改（JSON merge patch：只包含要修改的字段，null 恢复默认值）
+ */
+export type AppSettingsPatch = {
+  [K in keyof Omit<AppSettings, 'version'>]?: AppSettings[K] extends object
+    ? { [F in keyof AppSettings[K]]?: AppSettings[K][F] | null }
+    : AppSettings[K] | null;
+};
+
+/**
+ * 首次设置向导步骤（按顺序）
+ */
+export type WizardStep = 'welcome' | 'plugins' | 'apiKeys' | 'refreshInterval';
+
+/**
+ * 向导中填写的 API Key（明文只写入 Keychain）
+ */
+export interface WizardApiKey {
+  /** 插件 ID */
+  pluginId: string;
+  /** 密钥名称（见 WizardPluginOption.secretNames） */
+  name: string;
+  /** 明文值 */
+  value: string;
+}
+
+/**
+ * 完成向导步骤时提交的内容
+ */
+export type WizardStepInput =
+  | { step: 'welcome' }
+  | { step: 'plugins'; pluginIds: string[] }
+  | { step: 'apiKeys'; keys: WizardApiKey[] }
+  | { step: 'refreshInterval'; intervalMs: number };
+
+/**
+ * 已写入 Keychain 的密钥（不含值）
+ */
+export interface ConfiguredSecret {
+  /** 插件 ID */
+  pluginId: string;
+  /** 密钥名称 */
+  name: string;
+}
+
+/**
+ * 首次设置向导进度（wizard:finished 事件 payload）
+ */
+export interface WizardProgress {
+  /** 已完成的步骤 */
+  completedSteps: WizardStep[];
+  /** 选择启用的插件 */
+  selectedPlugins: string[];
+  /** 已写入 Keychain 的密钥 */
+  configuredSecrets: ConfiguredSecret[];
+  /** 选择的刷新间隔 (毫秒，1 分钟 ~ 24 小时，默认 5 分钟) */
+  refreshIntervalMs: number;
+  /** 完成时间 (ISO 8601)，未完成时为 null */
+  finishedAt: string | null;
+}
+
+/**
+ * 向导中可选择的插件
+ */
+export interface WizardPluginOption {
+  /** 插件 ID */
+  id: string;
+  /** 插件名称 */
+  name: string;
+  /** 当前是否启用 */
+  enabled: boolean;
+  /** 可在向导中填写的密钥名称 */
+  secretNames: string[];
+}
+
+/**
+ * 首次设置向导状态
+ */
+export interface WizardState extends WizardProgress {
+  /** 首次设置是否已完成 */
+  completed: boolean;
+  /** 当前步骤（全部完成、等待 wizard_finish 时为 null） */
+  currentStep: WizardStep | null;
+  /** 可选择的插件 */
+  plugins: WizardPluginOption[];
+}
+
+/**
+ * 健康状态
+ *
+ * stale: 网络离线，数据暂停刷新（不视为插件故障，恢复连接后自动刷新）
+ */
+export type HealthStatus = 'healthy' | 'degraded' | 'unhealthy' | 'stale';
+
+/**
+ * 插件健康信息
+ */
+export interface PluginHealth {
+  /** 插件 ID */
+  pluginId: string;
+  /** 健康状态 */
+  status: HealthStatus;
+  /** 最后成功时间 (ISO 8601) */
+  lastSuccess?: string;
+  /** 距最后成功的时长 (ms)，不受睡眠/唤醒影响 */
+  lastSuccessAgeMs?: number;
+  /** 最后错误信息（从服务商错误体中提取的摘要，最多 300 字符） */
+  lastError?: string;
+  /** 错误计数 */
+  errorCount: number;
+  /** 平均延迟 (ms) */
+  avgLatencyMs: number;
+  /** P99 延迟 (ms) */
+  p99LatencyMs: number;
+  /** 成功率 (0-1) */
+  successRate: number;
+  /** 总调用次数 */
+  totalCalls: number;
+  /** 连续失败次数 */
+  consecutiveFailures: number;
+  /** fetchData 结果缓存命中次数（缓存有效期为刷新间隔的一半） */
+  cacheHits: number;
+  /** fetchData 结果缓存未命中次数（含 force 强制刷新） */
+  cacheMisses: number;
+  /** 超出请求预算（manifest maxRequestsPerMinute）被拒绝的请求数 */
+  throttledRequests: number;
+  /** 因未声明 fetch:<host> 权限被拒绝访问的主机 */
+  blockedHosts?: string[];
+  /** 沙盒执行平均 CPU 时间 (ms) - 基于滑动窗口 */
+  avgCpuTimeMs: number;
+  /** 沙盒执行期间的峰值内存 (字节) - 基于滑动窗口 */
+  peakMemoryBytes: number;
+  /** 建议的恢复操作（根据最后错误类型生成） */
+  suggestedAction?: string;
+  /** 告警是否已静音 */
+  alertsMuted: boolean;
+  /** 告警暂停截止时间 (ISO 8601)，含全局暂停 */
+  alertsSnoozedUntil?: string;
+}
+
+/**
+ * 插件健康状态转换（plugin:health_changed 事件 payload）
+ */
+export interface HealthTransition {
+  /** 插件 ID */
+  pluginId: string;
+  /** 变化前的状态 */
+  previous: HealthStatus;
+  /** 变化后的状态 */
+  current: HealthStatus;
+  /** 变化时间 (ISO 8601) */
+  changedAt: string;
+  /** 变化前的状态开始时间 (ISO 8601)，插件加载后首次变化时省略 */
+  previousSince?: string;
+  /** 变化后的健康快照 */
+  health: PluginHealth;
+}
+
+/**
+ * 插件沙盒资源占用（基于最近 100 次沙盒执行，含重试和生命周期钩子）
+ */
+export interface PluginResourceStats {
+  pluginId: string;
+  /** 统计窗口内的沙盒执行次数 */
+  executions: number;
+  /** 最近一次执行的 CPU 时间 (ms) */
+  lastCpuTimeMs: number;
+  /** 平均 CPU 时间 (ms) */
+  avgCpuTimeMs: number;
+  /** 最大 CPU 时间 (ms) */
+  maxCpuTimeMs: number;
+  /** 最近一次执行期间的峰值内存 (字节) */
+  lastMemoryBytes: number;
+  /** 统计窗口内的峰值内存 (字节) */
+  peakMemoryBytes: number;
+  /** 沙盒内存上限 (字节) */
+  memoryLimitBytes: number;
+}
+
+/**
+ * 单个订阅者的事件投递指标
+ */
+export interface SubscriberMetrics {
+  /** 投递次数（含失败） */
+  deliveries: number;
+  /** 失败次数（含超时） */
+  failures: number;
+  /** 超时次数 */
+  timeouts: number;
+  /** 最近一次投递延迟 (ms) */
+  lastLatencyMs: number;
+  /** 平均投递延迟 (ms) */
+  avgLatencyMs: number;
+  /** 最大投递延迟 (ms) */
+  maxLatencyMs: number;
+}
+
+/**
+ * 事件总线统计
+ */
+export interface EventBusStats {
+  /** 已发布事件总数 */
+  eventsPublished: number;
+  /** 已分发事件总数 */
+  eventsDispatched: number;
+  /** 分发失败次数 */
+  dispatchFailures: number;
+  /** 当前订阅者总数 */
+  totalSubscriptions: number;
+  /** 系统通道积压事件数 */
+  systemLaneDepth: number;
+  /** 插件通道积压事件数 */
+  pluginLaneDepth: number;
+  /** 因防饥饿保护提前分发的插件事件数 */
+  starvationYields: number;
+  /** 进入死信缓冲区的事件总数（含已被丢弃的） */
+  deadLetters: number;
+  /** 死信缓冲区当前大小（最多保留 100 条） */
+  deadLetterDepth: number;
+  /** 各订阅者的投递指标，key 为插件 ID */
+  subscribers: Record<string, SubscriberMetrics>;
+}
+
+/**
+ * 死信：投递失败（onEvent 报错或超时）的事件
+ */
+export interface DeadLetter {
+  /** 完整事件名称 */
+  eventName: string;
+  /** 事件数据 */
+  data: unknown;
+  /** 事件来源插件 ID（系统事件为 null） */
+  sourcePlugin: string | null;
+  /** 投递失败的订阅者插件 ID */
+  subscriber: string;
+  /** 失败原因 */
+  error: string;
+  /** 是否因超时失败 */
+  timedOut: boolean;
+  /** 事件发布时间 (ISO 8601) */
+  publishedAt: string;
+  /** 投递失败时间 (ISO 8601) */
+  failedAt: string;
+}
+
+/**
+ * 插件日志级别（按严重程度递增）
+ */
+export type PluginLogLevel = 'trace' | 'debug' | 'info' | 'warn' | 'error';
+
+/**
+ * 插件 console 日志（每个插件在内存中保留最近 500 条）
+ */
+export interface PluginLogEntry {
+  /** 插件 ID */
+  pluginId: string;
+  /** 插件内单调递增的序号（清除后继续递增） */
+  seq: number;
+  /** 记录时间 (ISO 8601) */
+  timestamp: string;
+  /** 日志级别（console.log 记为 info） */
+  level: PluginLogLevel;
+  /** 日志内容（超过 10KB 截断） */
+  message: string;
+}
+
+/**
+ * 已生成的插件诊断包
+ * zip 内含 report.json（版本、插件信息、健康快照、最后错误、资源占用）、manifest.json、
+ * config.json（凭据字段已替换为 [REDACTED]）和 logs.txt；每个插件保留最近 5 个
+ */
+export interface DiagnosticsFile {
+  /** zip 文件路径 */
+  path: string;
+  /** 文件大小 (字节) */
+  sizeBytes: number;
+  /** 生成时间 (ISO 8601) */
+  generatedAt: string;
+}
+
+/**
+ * 应用日志级别（get_app_logs 参数，不区分大小写）
+ */
+export type AppLogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+/**
+ * 单条应用日志（来自日志目录，macOS 为 ~/Library/Logs/CUK）
+ */
+export interface AppLogEntry {
+  /** 时间 (RFC3339) */
+  timestamp: string;
+  /** 级别 (ERROR/WARN/INFO/DEBUG/TRACE) */
+  level: string;
+  /** 日志来源模块 */
+  target: string;
+  /** 日志内容（可能含多行） */
+  message: string;
+}
+
+/**
+ * 单个 IPC 命令的调用统计
+ */
+export interface IpcCommandMetrics {
+  /** 命令名 */
+  command: string;
+  /** 已完成的调用次数 */
+  invocations: number;
+  /** 失败次数（返回 success = false 或 invoke 被拒绝） */
+  errors: number;
+  /** 失败率 (0-1) */
+  errorRate: number;
+  /** 正在执行的调用数 */
+  inFlight: number;
+  /** 平均耗时 (毫秒) */
+  avgDurationMs: number;
+  /** 最大耗时 (毫秒) */
+  maxDurationMs: number;
+  /** 最近一次失败的错误码 */
+  lastErrorCode?: string;
+}
+
+/**
+ * IPC 调用统计（仅内存，应用重启后清零）
+ */
+export interface IpcMetricsReport {
+  /** 统计开始时间 (ISO 8601) */
+  since: string;
+  /** 总调用次数 */
+  totalInvocations: number;
+  /** 总失败次数 */
+  totalErrors: number;
+  /** 各命令统计（按命令名排序） */
+  commands: IpcCommandMetrics[];
+}
+
+/**
+ * 应用更新阶段
+ * - idle: 尚未检查
+ * - upToDate: 已是最新版本
+ * - available: 有新版本，尚未下载
+ * - downloading: 正在下载
+ * - ready: 已下载并校验，等待用户确认安装
+ * - installing: 正在安装（完成后应用自动重启）
+ */
+export type AppUpdatePhase = 'idle' | 'upToDate' | 'available' | 'downloading' | 'ready' | 'installing';
+
+/**
+ * 可用的新版本（来自已签名且 type 为 "app-release" 的 latest.json）
+ */
+export interface AppRelease {
+  /** 版本号 */
+  version: string;
+  /** 更新说明 */
+  notes?: string;
+  /** 发布时间 (ISO 8601) */
+  pubDate?: string;
+  /** 安装包大小（字节） */
+  size?: number;
+  /** 当前平台是否可以自动安装（否则只能手动下载） */
+  installable: boolean;
+}
+
+/**
+ * 应用更新状态（app_update:status 事件 payload）
+ */
+export interface AppUpdateStatus {
+  /** 当前版本 */
+  currentVersion: string;
+  /** 更新阶段 */
+  phase: AppUpdatePhase;
+  /** 新版本信息（available / downloading / ready / installing 时存在） */
+  release?: AppRelease;
+  /** 已下载字节数 */
+  downloadedBytes: number;
+  /** 最近一次检查时间 (ISO 8601) */
+  lastChecked?: string;
+  /** 最近一次检查、下载或安装的错误 */
+  lastError?: string;
+}
+
+/**
+ * 插件单个账户的数据（get_plugin_accounts 按账户聚合）
+ */
+export interface PluginAccountData {
+  /** 插件实例 ID */
+  instanceId: string;
+  /** 账户名称（默认账户不返回） */
+  accountLabel?: string;
+  /** 是否启用 */
+  enabled: boolean;
+  /** 最近一次数据 */
+  data?: PluginData;
+}
+
+// ============================================================================
+// 插件数据类型
+// ============================================================================
+
+/**
+ * 插件数据基础接口
+ */
+export interface PluginDataBase {
+  /** 数据来源插件 */
+  pluginId: string;
+  /** 最后更新时间 (ISO 8601) */
+  lastUpdated: string;
+}
+
+/**
+ * Usage 维度
+ */
+export interface UsageDimension {
+  /** 维度 ID */
+  id: string;
+  /** 显示标签 */
+  label: string;
+  /** 使用百分比 */
+  percentage: number;
+  /** 已用量 */
+  used: number;
+  /** 限额 */
+  limit: number;
+  /** 重置时间 */
+  resetTime?: string;
+  /** token 用量明细（维度 ID 为模型名时用于费用估算） */
+  tokens?: TokenBreakdown;
+}
+
+/**
+ * token 用量明细
+ */
+export interface TokenBreakdown {
+  /** 输入 token */
+  input: number;
+  /** 输出 token */
+  output: number;
+  /** 写入缓存的 token */
+  cacheCreation: number;
+  /** 读取缓存的 token */
+  cacheRead: number;
+}
+
+/**
+ * 使用量数据
+ */
+export interface UsageData extends PluginDataBase {
+  dataType: 'usage';
+  /** 使用百分比 (0-100) */
+  percentage: number;
+  /** 已用量 */
+  used: number;
+  /** 限额 */
+  limit: number;
+  /** 单位 */
+  unit: string;
+  /** 重置时间 (ISO 8601) */
+  resetTime?: string;
+  /** 重置标签 */
+  resetLabel?: string;
+  /** 多维度使用量 */
+  dimensions?: UsageDimension[];
+}
+
+/**
+ * 余额子项（用于多配额场景，如 PAYGO/PLUS/FREE）
+ */
+export interface BalanceItem {
+  /** 子项名称（如 PAYGO, PLUS, FREE） */
+  name: string;
+  /** 已用额度 */
+  used: number;
+  /** 总额度 */
+  quota: number;
+  /** 使用百分比 (0-100) */
+  percentage: number;
+  /** 货币/单位 */
+  currency?: string;
+  /** 重置时间 (ISO 8601) */
+  resetTime?: string;
+  /** 重置标签（如 "今日已用完"、"12-31 04:43 可重置"） */
+  resetLabel?: string;
+  /** 到期时间 (ISO 8601) */
+  expiresAt?: string;
+  /** 剩余天数 */
+  remainingDays?: number;
+  /** 是否可刷新 */
+  refreshable?: boolean;
+}
+
+/**
+ * 余额数据
+ */
+export interface BalanceData extends PluginDataBase {
+  dataType: 'balance';
+  /** 余额 */
+  balance: number;
+  /** 货币 */
+  currency: string;
+  /** 总额度 */
+  quota?: number;
+  /** 已用额度 */
+  usedQuota?: number;
+  /** 到期时间 (ISO 8601) */
+  expiresAt?: string;
+  /** 多配额子项（如 PAYGO/PLUS/FREE） */
+  items?: BalanceItem[];
+  /** 是否显示总额度（默认 false） */
+  showTotal?: boolean;
+}
+
+/**
+ * 状态指示器
+ */
+export type StatusIndicator = 'none' | 'minor' | 'major' | 'critical' | 'unknown';
+
+/**
+ * 状态数据
+ */
+export interface StatusData extends PluginDataBase {
+  dataType: 'status';
+  /** 状态指示 */
+  indicator: StatusIndicator;
+  /** 状态描述 */
+  description: string;
+}
+
+/**
+ * 自定义数据
+ */
+export interface CustomData extends PluginDataBase {
+  dataType: 'custom';
+  /** 自定义渲染 HTML */
+  renderHtml?: string;
+  /** 自定义数据 */
+  payload: Record<string, unknown>;
+  /** 卡片标题 */
+  title?: string;
+  /** 卡片副标题 */
+  subtitle?: string;
+}
+
+/**
+ * 插件数据联合类型
+ */
+export type PluginData = UsageData | BalanceData | StatusData | CustomData;
+
+// ============================================================================
+// IPC Commands 定义
+// ============================================================================
+
+/**
+ * 插件管理 Commands (14个)
+ *
+ * 安装/卸载/更新/回滚只允许设置窗口和仪表盘窗口调用，
+ * 其他窗口（托盘弹窗、HUD 等）调用返回 COMMAND_NOT_ALLOWED
+ */
+export interface PluginManagementCommands {
+  /**
+   * 获取所有插件列表
+   */
+  plugin_list(): Promise<Result<PluginInfo[]>>;
+
+  /**
+   * 获取 API 版本不兼容、未加载的插件（最近一次发现的结果）
+   */
+  plugin_list_incompatible(): Promise<Result<IncompatiblePlugin[]>>;
+
+  /**
+   * 获取插件能力描述（manifest 与实际生效的权限、注入的 API、订阅和沙盒限制）
+   * @errors PLUGIN_NOT_FOUND
+   */
+  plugin_describe(args: { pluginId: string }): Promise<Result<PluginDescription>>;
+
+  /**
+   * 启用插件
+   */
+  plugin_enable(args: { id: string }): Promise<Result>;
+
+  /**
+   * 禁用插件
+   */
+  plugin_disable(args: { id: string }): Promise<Result>;
+
+  /**
+   * 安装插件
+   * 管理员策略禁止安装新插件或要求签名时，新安装/跳过签名的请求被拒绝
+   * apiVersion 超出支持范围时拒绝安装，details 为 IncompatiblePlugin
+   * @errors POLICY_DENIED, INCOMPATIBLE_API_VERSION, COMMAND_NOT_ALLOWED
+   */
+  plugin_install(args: {
+    /** 插件来源 (URL 或本地路径) */
+    source: string;
+    /** 是否跳过签名验证 */
+    skipSignature?: boolean;
+  }): Promise<Result<PluginInfo>>;
+
+  /**
+   * 从本地 .zip 文件安装插件（离线安装 / 拖拽安装）
+   * 经安全解压、签名与完整性校验后原子放入插件目录
+   * @errors INCOMPATIBLE_API_VERSION, COMMAND_NOT_ALLOWED
+   */
+  plugin_install_from_file(args: {
+    /** 本地 .zip 文件路径 */
+    path: string;
+    /** 是否跳过签名验证 */
+    skipSignature?: boolean;
+  }): Promise<Result<PluginInfo>>;
+
+  /**
+   * 取消进行中的插件安装
+   * 解压在下一个条目前中止并删除已写出的临时文件，对应安装返回 INSTALL_CANCELLED
+   * 返回是否找到进行中的安装
+   * @errors COMMAND_NOT_ALLOWED
+   */
+  plugin_install_cancel(args: {
+    /** 发起安装时的 source / 文件路径 / registry ID */
+    key: string;
+  }): Promise<Result<boolean>>;
+
+  /**
+   * 卸载插件
+   * @errors COMMAND_NOT_ALLOWED
+   */
+  plugin_uninstall(args: { id: string }): Promise<Result>;
+
+  /**
+   * 批量操作选中的插件（启用/禁用/刷新/卸载）
+   * 逐个执行，单个插件失败不影响其余插件；完成后发送一个 ipc:plugin_bulk_completed 汇总事件，
+   * 不逐个发送卸载/错误事件。重复 ID 只执行一次，每批最多 64 个
+   * @errors BATCH_TOO_LARGE, COMMAND_NOT_ALLOWED (uninstall)
+   */
+  plugin_bulk(args: { op: BulkOp; ids: string[] }): Promise<Result<BulkResult>>;
+
+  /**
+   * 重载插件
+   */
+  plugin_reload(args: { id: string }): Promise<Result>;
+
+  /**
+   * 检查插件更新（与 registry 索引比对版本）
+   */
+  plugin_check_updates(): Promise<Result<UpdateInfo[]>>;
+
+  /**
+   * 更新插件
+   * registry 中有新版本时安装（更新后健康检查失败返回 PLUGIN_UPDATE_ROLLED_BACK），否则重载
+   * @errors COMMAND_NOT_ALLOWED
+   */
+  plugin_update(args: { id: string }): Promise<Result<PluginInfo>>;
+
+  /**
+   * 回滚插件到备份的版本（同 rollback_to，不返回插件信息）
+   * @errors BACKUP_NOT_FOUND, INTEGRITY_FAILED, COMMAND_NOT_ALLOWED
+   */
+  plugin_rollback(args: {
+    id: string;
+    /** 目标版本 */
+    version: string;
+  }): Promise<Result>;
+}
+
+/**
+ * 数据 Commands (4个)
+ */
+export interface DataCommands {
+  /**
+   * 获取所有插件数据
+   */
+  get_all_data(): Promise<Result<PluginData[]>>;
+
+  /**
+   * 获取单个插件数据
+   */
+  get_plugin_data(args: { id: string }): Promise<Result<PluginData>>;
+
+  /**
+   * 获取所有已启用插件数据的聚合摘要
+   */
+  get_summary(): Promise<Result<DataSummary>>;
+
+  /**
+   * 在同一快照上批量执行只读查询（单批最多 32 条）
+   * @errors BATCH_TOO_LARGE
+   */
+  batch_query(args: { requests: BatchQuery[] }): Promise<Result<BatchResponse>>;
+
+  /**
+   * 刷新单个插件
+   */
+  refresh_plugin(args: {
+    id: string;
+    /** 是否强制刷新 (绕过缓存) */
+    force?: boolean;
+  }): Promise<Result<PluginData>>;
+
+  /**
+   * 刷新所有插件
+   */
+  refresh_all(args?: {
+    /** 是否强制刷新 (绕过缓存) */
+    force?: boolean;
+  }): Promise<Result<PluginData[]>>;
+
+  /**
+   * 回放解析：将 fetchData 原始结果重新解析并校验（不执行插件代码）
+   */
+  replay_parse(args: {
+    pluginId: string;
+    /** fetchData 原始返回值的 JSON 文本 */
+    rawJson: string;
+  }): Promise<Result<ReplayParseResult>>;
+
+  /**
+   * 试运行：执行 fetchData 并按 dataType 和 manifest payloadSchema 校验结果
+   * 不更新缓存和健康统计，未启用的插件也可试运行
+   */
+  plugin_test_run(args: { id: string }): Promise<Result<ReplayParseResult>>;
+}
+
+/**
+ * 配置 Commands (3个)
+ */
+export interface ConfigCommands {
+  /**
+   * 获取插件配置
+   * 敏感字段（configSchema 中 secret: true）只返回 { secretRef } 引用，不返回明文
+   */
+  get_plugin_config(args: { id: string }): Promise<Result<Record<string, unknown>>>;
+
+  /**
+   * 设置插件配置
+   * 按 configSchema 校验通过后才保存并通知插件；失败时 details 为 { fieldErrors: FieldValidationError[] }
+   * 敏感字段的字符串值写入钥匙串，传回 { secretRef } 引用时保持原值
+   * @errors CONFIG_INVALID, CONFIG_SET_FAILED
+   */
+  set_plugin_config(args: {
+    id: string;
+    config: Record<string, unknown>;
+  }): Promise<Result>;
+
+  /**
+   * 验证插件配置
+   */
+  validate_plugin_config(args: {
+    id: string;
+    config: Record<string, unknown>;
+  }): Promise<Result<ValidationResult>>;
+}
+
+/**
+ * 监控 Commands (6个)
+ */
+export interface MonitoringCommands {
+  /**
+   * 获取所有插件健康状态
+   */
+  get_all_health(): Promise<Result<PluginHealth[]>>;
+
+  /**
+   * 获取单个插件健康状态
+   */
+  get_plugin_health(args: { id: string }): Promise<Result<PluginHealth>>;
+
+  /**
+   * 获取插件沙盒资源占用（CPU 时间、内存），用于资源面板
+   * @errors PLUGIN_NOT_FOUND
+   */
+  get_plugin_resource_stats(args: { id: string }): Promise<Result<PluginResourceStats>>;
+
+  /**
+   * 获取系统健康状态（插件数量、网络状态、生效的管理员策略）
+   */
+  get_system_health(): Promise<Result<SystemHealth>>;
+
+  /**
+   * 获取事件总线统计（队列积压、死信数量、各订阅者投递延迟）
+   */
+  get_event_bus_stats(): Promise<Result<EventBusStats>>;
+
+  /**
+   * 获取投递失败的事件（最新的在前）
+   * @param limit 返回数量，默认 50
+   */
+  get_dead_letters(args?: { limit?: number }): Promise<Result<DeadLetter[]>>;
+}
+
+/**
+ * 插件输入请求 Commands (2个)
+ */
+export interface InputCommands {
+  /**
+   * 获取待处理的插件输入请求
+   */
+  get_pending_inputs(): Promise<Result<InputRequest[]>>;
+
+  /**
+   * 提交输入请求的响应
+   * 没有剩余待处理请求时自动关闭输入对话框
+   */
+  submit_input(args: {
+    requestId: string;
+    /** 字段值，null 表示用户取消 */
+    values: Record<string, string> | null;
+  }): Promise<Result>;
+}
+
+/**
+ * 网络状态 Commands (1个)
+ */
+export interface NetworkCommands {
+  /**
+   * 获取网络状态
+   * 离线时 refresh_plugin/refresh_all 不执行插件，返回缓存数据并排队刷新
+   */
+  get_network_status(): Promise<Result<NetworkStatus>>;
+}
+
+/**
+ * 完整性校验 Commands (2个)
+ */
+export interface IntegrityCommands {
   /**
-   * 重载插件
+   * 校验插件文件与 manifest 哈希是否一致
+   * 不一致时禁用并隔离插件，并发送 PLUGIN_TAMPERED 错误事件
    */
-  plugin_reload(args: { id: string }): Promise<Result>;
+  verify_plugin_integrity(args: { id: string }): Promise<Result<IntegrityReport>>;
 
   /**
-   * 检查插件更新
+   * 获取最近的安全审计事件（默认 100 条）
    */
-  plugin_check_updates(): Promise<Result<UpdateInfo[]>>;
+  get_audit_events(args?: { limit?: number }): Promise<Result<AuditEvent[]>>;
+}
 
+/**
+ * 发布者密钥 Commands (2个)
+ * 第三方插件作者用自己的密钥签名插件，公钥由根密钥签名的密钥列表分发
+ */
+export interface PublisherKeyCommands {
   /**
-   * 更新插件
+   * 获取签名信任库（根密钥和发布者密钥）
    */
-  plugin_update(args: { id: string }): Promise<Result<PluginInfo>>;
+  get_publisher_keys(): Promise<Result<PublisherKeyStatus>>;
 
   /**
-   * 回滚插件
+   * 下载并应用发布者密钥列表（密钥轮换与吊销），默认使用官方地址
+   * 列表必须由根密钥签名且版本高于当前版本；应用后重新评估已安装插件的信任级别
+   * @errors KEY_LIST_FETCH_FAILED, SIGNATURE_INVALID, INVALID_KEY_LIST, KEY_LIST_OUTDATED, KEY_LIST_SAVE_FAILED, COMMAND_NOT_ALLOWED
    */
-  plugin_rollback(args: {
-    id: string;
-    /** 目标版本 */
-    version: string;
-  }): Promise<Result>;
+  update_publisher_keys(args?: { url?: string }): Promise<Result<PublisherKeyStatus>>;
 }
 
 /**
- * 数据 Commands (4个)
+ * 插件市场 Commands (3个)
+ * registry 索引必须带有效签名（规则与 manifest 签名相同），否则返回 REGISTRY_SIGNATURE_INVALID
+ * 管理员策略固定了 registryUrl 时，未指定地址使用固定地址，指定其他地址返回 POLICY_DENIED
  */
-export interface DataCommands {
+export interface RegistryCommands {
   /**
-   * 获取所有插件数据
+   * 搜索插件（关键字匹配 id/名称/描述/作者/标签，为空时返回全部）
+   * registryUrl 为空时使用默认地址
    */
-  get_all_data(): Promise<Result<PluginData[]>>;
+  registry_search(args?: { query?: string; registryUrl?: string }): Promise<Result<RegistryEntry[]>>;
 
   /**
-   * 获取单个插件数据
+   * 获取插件 manifest（附签名验证结果，用于安装前预览）
    */
-  get_plugin_data(args: { id: string }): Promise<Result<PluginData>>;
+  registry_get_manifest(args: { id: string; registryUrl?: string }): Promise<Result<RegistryManifest>>;
 
   /**
-   * 刷新单个插件
+   * 从 registry 安装插件（强制验证 manifest 签名）
+   * @errors COMMAND_NOT_ALLOWED
    */
-  refresh_plugin(args: {
-    id: string;
-    /** 是否强制刷新 (绕过缓存) */
-    force?: boolean;
-  }): Promise<Result<PluginData>>;
+  registry_install(args: { id: string; registryUrl?: string }): Promise<Result<PluginInfo>>;
+}
 
+/**
+ * 插件更新 Commands (2个)
+ * 后台检查发现新版本时发送 update:available 事件
+ */
+export interface UpdateCommands {
   /**
-   * 刷新所有插件
+   * 获取插件自动更新设置
    */
-  refresh_all(args?: {
-    /** 是否强制刷新 (绕过缓存) */
-    force?: boolean;
-  }): Promise<Result<PluginData[]>>;
+  get_update_settings(): Promise<Result<UpdateSettings>>;
+
+  /**
+   * 保存插件自动更新设置（下一轮检查生效）
+   */
+  set_update_settings(args: { settings: UpdateSettings }): Promise<Result<void>>;
 }
 
 /**
- * 配置 Commands (3个)
+ * 用量提醒 Commands (2个)
+ * 越过阈值时通过系统通知中心提醒
  */
-export interface ConfigCommands {
+export interface UsageAlertCommands {
   /**
-   * 获取插件配置
+   * 获取用量提醒设置
    */
-  get_plugin_config(args: { id: string }): Promise<Result<Record<string, unknown>>>;
+  get_usage_alert_settings(): Promise<Result<UsageAlertSettings>>;
 
   /**
-   * 设置插件配置
+   * 保存用量提醒设置（下次刷新时生效）
    */
-  set_plugin_config(args: {
-    id: string;
-    config: Record<string, unknown>;
-  }): Promise<Result>;
+  set_usage_alert_settings(args: { settings: UsageAlertSettings }): Promise<Result<void>>;
+}
+
+/**
+ * 告警静默 Commands (3个)
+ * 静默期间仍记录告警，只是不发送系统通知；状态跨重启保留
+ */
+export interface AlertSilenceCommands {
+  /**
+   * 暂停插件提醒（pluginId 为 '*' 时暂停全部插件），返回截止时间 (ISO 8601)
+   * @param duration 暂停时长（秒）
+   */
+  alert_snooze(args: { pluginId: string; duration: number }): Promise<Result<string>>;
 
   /**
-   * 验证插件配置
+   * 静音插件提醒（直到取消静音）
    */
-  validate_plugin_config(args: {
-    id: string;
-    config: Record<string, unknown>;
-  }): Promise<Result<ValidationResult>>;
+  alert_mute(args: { pluginId: string }): Promise<Result<void>>;
+
+  /**
+   * 取消插件提醒的静音和暂停
+   */
+  alert_unmute(args: { pluginId: string }): Promise<Result<void>>;
 }
 
 /**
- * 监控 Commands (2个)
+ * 插件备份 Commands (2个)
+ * 备份保存在与插件目录同级的 plugin_backups/ 中
  */
-export interface MonitoringCommands {
+export interface PluginBackupCommands {
   /**
-   * 获取所有插件健康状态
+   * 列出插件的版本备份（不传 pluginId 时列出所有插件，同一插件最新在前）
    */
-  get_all_health(): Promise<Result<PluginHealth[]>>;
+  list_plugin_backups(args: { pluginId?: string }): Promise<Result<PluginBackup[]>>;
 
   /**
-   * 获取单个插件健康状态
+   * 回滚插件到备份的指定版本，保留当前配置和启用状态
+   * 成功后发送 ipc:plugin_updated 事件
+   * @errors BACKUP_NOT_FOUND, INTEGRITY_FAILED, COMMAND_NOT_ALLOWED
    */
-  get_plugin_health(args: { id: string }): Promise<Result<PluginHealth>>;
+  rollback_to(args: { pluginId: string; version: string }): Promise<Result<PluginInfo>>;
+}
+
+/**
+ * 告警历史 Commands (2个)
+ * 触发的告警持久化到 alert_history.json，跨重启保留
+ */
+export interface AlertHistoryCommands {
+  /**
+   * 查询告警历史（按触发时间倒序）
+   */
+  get_alert_history(args: { filter?: AlertHistoryFilter }): Promise<Result<AlertRecord[]>>;
+
+  /**
+   * 确认告警，返回更新后的记录（重复确认保持首次确认时间）
+   * @errors ALERT_NOT_FOUND
+   */
+  acknowledge_alert(args: { id: number }): Promise<Result<AlertRecord>>;
+}
+
+/**
+ * 未送达告警 Commands (3个)
+ * 通知发送失败的告警持久化到磁盘并在后台重投，直到送达或过期
+ */
+export interface MissedAlertCommands {
+  /**
+   * 获取错过的告警
+   */
+  get_missed_alerts(): Promise<Result<MissedAlertSummary>>;
+
+  /**
+   * 清空错过的告警（用户已在应用内查看）
+   */
+  clear_missed_alerts(): Promise<Result<void>>;
+
+  /**
+   * 设置未送达告警的过期时间，返回实际生效值
+   * @param hours 过期时间（小时），默认 24，最长 168
+   * @errors INVALID_ARGUMENT
+   */
+  set_missed_alert_expiry(args: { hours: number }): Promise<Result<number>>;
+}
+
+/**
+ * 隐私报告与存储占用 Commands (2个)
+ */
+export interface PrivacyCommands {
+  /**
+   * 获取插件隐私报告：访问过的主机、请求数、流量及作为请求头发送的配置字段名
+   * @param days 报告天数，默认且最多 30
+   */
+  get_privacy_report(args: { pluginId: string; days?: number }): Promise<Result<PrivacyReport>>;
+
+  /**
+   * 获取各插件的存储占用（storage + cache），按插件 ID 排序
+   */
+  get_storage_stats(): Promise<Result<PluginStorageStats[]>>;
+}
+
+/**
+ * 插件日志与诊断 Commands (4个)
+ * 新日志同时以 plugin:log 事件实时推送
+ */
+export interface PluginLogCommands {
+  /**
+   * 获取插件 console 日志（按时间顺序）
+   * @param since 只返回该时间 (ISO 8601) 之后的日志
+   * @param level 最低日志级别
+   * @errors INVALID_ARGUMENT
+   */
+  get_plugin_logs(args: {
+    pluginId: string;
+    since?: string;
+    level?: PluginLogLevel;
+  }): Promise<Result<PluginLogEntry[]>>;
+
+  /**
+   * 清除插件 console 日志，返回清除的条数
+   */
+  clear_plugin_logs(args: { pluginId: string }): Promise<Result<number>>;
+
+  /**
+   * 生成插件诊断包（zip），用于附在问题报告中
+   * @errors PLUGIN_NOT_FOUND, DIAGNOSTICS_FAILED
+   */
+  generate_diagnostics(args: { pluginId: string }): Promise<Result<DiagnosticsFile>>;
+
+  /**
+   * 获取最近的应用日志（按时间顺序，含已轮转的旧文件）
+   * @param lines 返回条数，默认 200，最多 2000
+   * @param level 最低日志级别，默认全部
+   * @errors INVALID_ARGUMENT
+   */
+  get_app_logs(args?: { lines?: number; level?: AppLogLevel }): Promise<Result<AppLogEntry[]>>;
+}
+
+/**
+ * 预算 Commands (2个)
+ * 预计超出预算时发送 budget:warning 事件和系统通知（每个预算每月一次）
+ */
+export interface BudgetCommands {
+  /**
+   * 获取本月预算状态
+   */
+  get_budget_status(): Promise<Result<BudgetStatus>>;
+
+  /**
+   * 设置或清除月度预算
+   * @param pluginId 插件 ID，省略时为全局预算
+   * @param budget 预算，省略时清除
+   */
+  set_budget(args: { pluginId?: string; budget?: Budget }): Promise<Result<void>>;
+}
+
+/**
+ * 费用估算 Commands (3个)
+ */
+export interface CostCommands {
+  /**
+   * 按模型价格估算插件在区间内的花费
+   * @param range 缺省为 month
+   * @errors PLUGIN_NOT_FOUND
+   */
+  estimate_cost(args: { pluginId: string; range?: CostRange }): Promise<Result<CostEstimate>>;
+
+  /**
+   * 获取当前生效的模型价格表
+   */
+  get_price_table(): Promise<Result<PriceTable>>;
+
+  /**
+   * 从远程 JSON 更新模型价格表，返回合并后的价格表
+   * @param url 仅支持 HTTPS，缺省为随发布附带的 pricing.json
+   * @errors PRICING_FETCH_FAILED, INVALID_PRICING_TABLE, PRICING_SAVE_FAILED
+   */
+  update_price_table(args: { url?: string }): Promise<Result<PriceTable>>;
+}
+
+/**
+ * 开发者模式 Commands (3个)
+ */
+export interface DeveloperCommands {
+  /**
+   * 获取开发者模式设置
+   */
+  get_developer_settings(): Promise<Result<DeveloperSettings>>;
+
+  /**
+   * 保存开发者模式设置，返回当前加载的开发者插件
+   * 信任插件前应向用户展示其 restrictedPermissions 并取得明确确认
+   */
+  set_developer_settings(args: { settings: DeveloperSettings }): Promise<Result<PluginInfo[]>>;
+
+  /**
+   * 从 .env 文件导入已知 API Key（ANTHROPIC_API_KEY / OPENAI_API_KEY）
+   * 仅开发者模式可用；path 为用户选择的文件绝对路径
+   * 按 `secrets:<name>` 权限映射到插件（anthropic_api_key / openai_api_key）
+   * @errors DEVELOPER_MODE_REQUIRED, INVALID_ENV_FILE, ENV_IMPORT_FAILED, COMMAND_NOT_ALLOWED
+   */
+  import_keys_from_env(args: { path: string }): Promise<Result<EnvImportReport>>;
+}
+
+/**
+ * 数据推送 Commands (4个)
+ */
+export interface PushCommands {
+  /**
+   * 获取本地推送端点设置
+   */
+  get_push_settings(): Promise<Result<PushSettings>>;
+
+  /**
+   * 开启/关闭本地推送端点（可同时修改端口）
+   */
+  set_push_settings(args: { enabled: boolean; port?: number }): Promise<Result<PushSettings>>;
+
+  /**
+   * 为声明 acceptsPush 的插件签发推送 token（明文仅返回这一次，旧 token 失效）
+   */
+  issue_push_token(args: { id: string }): Promise<Result<string>>;
+
+  /**
+   * 吊销插件的推送 token
+   */
+  revoke_push_token(args: { id: string }): Promise<Result<boolean>>;
+}
+
+/**
+ * 弹窗 Commands (2个)
+ */
+export interface PopupCommands {
+  /**
+   * 获取弹窗摘要（插件列表、数据、健康状态、网络状态）
+   */
+  get_popup_summary(): Promise<Result<PopupSummary>>;
+
+  /**
+   * 获取插件最近 24 小时的使用率趋势（仅 usage 类型插件有数据）
+   * @param points 点数，默认 24，最多 96
+   * @errors PLUGIN_NOT_FOUND
+   */
+  get_usage_trend(args: { pluginId: string; points?: number }): Promise<Result<UsageTrend>>;
+}
+
+/**
+ * 网关 Commands (2个)
+ */
+export interface GatewayCommands {
+  /**
+   * 获取宿主网关配置
+   */
+  get_gateway_config(): Promise<Result<GatewayConfig>>;
+
+  /**
+   * 保存宿主网关配置（校验后持久化，下次执行插件时生效；仅设置/仪表盘窗口）
+   * @errors INVALID_GATEWAY_CONFIG, COMMAND_NOT_ALLOWED
+   */
+  set_gateway_config(args: { config: GatewayConfig }): Promise<Result<void>>;
+}
+
+/**
+ * 旧版命令 Commands (2个)
+ *
+ * Phase 2 的 list_plugins/get_plugin/enable_plugin/disable_plugin/discover_plugins/get_plugins_dir
+ * 已弃用，调用时映射到对应的 7.3 命令并发送 ipc:deprecated_command 事件。
+ * 返回值与 7.3 命令相同为 Result<T> 信封，停用后返回 LEGACY_COMMAND_DISABLED
+ */
+export interface LegacyCommands {
+  /**
+   * 获取旧版命令的调用统计
+   */
+  get_legacy_command_report(): Promise<Result<LegacyCommandReport>>;
+
+  /**
+   * 允许/停用旧版命令
+   * @errors LEGACY_SETTINGS_SAVE_FAILED
+   */
+  set_legacy_commands_enabled(args: { enabled: boolean }): Promise<Result<void>>;
+}
+
+/**
+ * 首次设置向导 Commands (3个)
+ *
+ * 步骤必须按顺序完成（可重做已完成的步骤），全部完成后调用 wizard_finish
+ */
+export interface WizardCommands {
+  /**
+   * 获取向导状态
+   */
+  wizard_get_state(): Promise<Result<WizardState>>;
+
+  /**
+   * 完成一个步骤: plugins 启用选中插件并停用其余插件，apiKeys 写入 Keychain
+   * @errors WIZARD_FINISHED, WIZARD_STEP_OUT_OF_ORDER, INVALID_ARGUMENT, WIZARD_SAVE_FAILED
+   */
+  wizard_complete_step(args: { input: WizardStepInput }): Promise<Result<WizardState>>;
+
+  /**
+   * 结束向导：写入完成标记、将选择的刷新间隔写入应用设置、发送 wizard:finished 事件并关闭向导窗口
+   * @errors WIZARD_FINISHED, WIZARD_INCOMPLETE, WIZARD_SAVE_FAILED
+   */
+  wizard_finish(): Promise<Result<WizardProgress>>;
+}
+
+/**
+ * 应用设置 Commands (6个)
+ *
+ * 修改成功后通过 window:state_changed（stateType = "settings"）同步到所有窗口，
+ * 主题变化或 system 模式下系统外观切换时额外发送 window:theme_changed
+ */
+export interface SettingsCommands {
+  /**
+   * 获取应用设置
+   */
+  get_app_settings(): Promise<Result<AppSettings>>;
+
+  /**
+   * 修改应用设置，返回修改后的完整设置
+   * @errors INVALID_ARGUMENT, SETTINGS_SAVE_FAILED
+   */
+  set_app_settings(args: { patch: AppSettingsPatch }): Promise<Result<AppSettings>>;
+
+  /**
+   * 恢复默认应用设置
+   * @errors SETTINGS_SAVE_FAILED
+   */
+  reset_app_settings(): Promise<Result<AppSettings>>;
+
+  /**
+   * 获取当前主题（模式 + 解析后的实际外观）
+   */
+  get_theme(): Promise<Result<ThemeChangedPayload>>;
+
+  /**
+   * 获取托盘点击配置（即 AppSettings.trayClick）
+   */
+  get_tray_click_config(): Promise<Result<TrayClickConfig>>;
+
+  /**
+   * 设置托盘点击配置，保存到应用设置并更新托盘工具提示
+   * @errors INVALID_ARGUMENT, SETTINGS_SAVE_FAILED
+   */
+  set_tray_click_config(args: { config: TrayClickConfig }): Promise<Result<void>>;
+}
+
+/**
+ * IPC 追踪 Commands (1个)
+ *
+ * 每次调用分配请求 ID 并在应用日志中记录耗时（debug 级别，超过 1 秒为 warn）
+ */
+export interface IpcMetricsCommands {
+  /**
+   * 获取各命令的调用次数、失败率和耗时（调试面板，自身不计入统计）
+   */
+  get_ipc_metrics(): Promise<Result<IpcMetricsReport>>;
+}
+
+/**
+ * 应用更新 Commands (4个)
+ *
+ * 后台每天检查一次，状态变化通过 app_update:status 事件通知；
+ * 下载和安装都需要用户在界面上确认，安装完成后应用自动重启
+ */
+export interface AppUpdateCommands {
+  /**
+   * 获取当前更新状态（不发起网络请求）
+   */
+  get_app_update_status(): Promise<Result<AppUpdateStatus>>;
+
+  /**
+   * 立即检查新版本
+   * @errors APP_UPDATE_CHECK_FAILED, SIGNATURE_INVALID
+   */
+  check_for_app_updates(): Promise<Result<AppUpdateStatus>>;
+
+  /**
+   * 下载新版本安装包并校验 sha256（进度通过 app_update:status 事件通知；仅设置/仪表盘/关于窗口）
+   * @errors APP_UPDATE_NOT_AVAILABLE, APP_UPDATE_BUSY, APP_UPDATE_DOWNLOAD_FAILED, INTEGRITY_FAILED, NOT_SUPPORTED, COMMAND_NOT_ALLOWED
+   */
+  download_app_update(): Promise<Result<AppUpdateStatus>>;
+
+  /**
+   * 安装已下载的更新并重启应用（仅 macOS；仅设置/仪表盘/关于窗口）
+   * @errors APP_UPDATE_NOT_READY, APP_UPDATE_BUSY, APP_UPDATE_INSTALL_FAILED, NOT_SUPPORTED, COMMAND_NOT_ALLOWED
+   */
+  install_app_update(): Promise<Result<void>>;
+}
+
+/**
+ * 多账户 Commands (3个)
+ *
+ * 同一数据插件可以添加多个账户，每个账户是独立的插件实例（实例 ID 为 `插件ID@序号`），
+ * 配置、Keychain 密钥、数据和健康统计互不影响；其余命令的 id 参数传实例 ID
+ */
+export interface PluginAccountCommands {
+  /**
+   * 为数据插件添加账户（新账户默认禁用，配置凭证后启用）
+   * @errors PLUGIN_NOT_FOUND, PLUGIN_ACCOUNT_FAILED, COMMAND_NOT_ALLOWED
+   */
+  add_plugin_account(args: { pluginId: string; label: string }): Promise<Result<PluginInfo>>;
+
+  /**
+   * 移除账户及其配置和数据（插件本身保留；卸载插件时所有账户一并移除）
+   * @errors PLUGIN_NOT_FOUND, PLUGIN_ACCOUNT_FAILED, COMMAND_NOT_ALLOWED
+   */
+  remove_plugin_account(args: { instanceId: string }): Promise<Result<void>>;
+
+  /**
+   * 获取插件所有账户的数据（默认账户在前）
+   * @errors PLUGIN_NOT_FOUND
+   */
+  get_plugin_accounts(args: { pluginId: string }): Promise<Result<PluginAccountData[]>>;
 }
 
 /**
- * 所有 IPC Commands (18个)
+ * 所有 IPC Commands (93个)
  */
 export interface IPCCommands
   extends PluginManagementCommands,
     DataCommands,
     ConfigCommands,
-    MonitoringCommands {}
+    MonitoringCommands,
+    InputCommands,
+    NetworkCommands,
+    IntegrityCommands,
+    PublisherKeyCommands,
+    RegistryCommands,
+    UpdateCommands,
+    UsageAlertCommands,
+    AlertSilenceCommands,
+    AlertHistoryCommands,
+    PluginBackupCommands,
+    MissedAlertCommands,
+    PrivacyCommands,
+    PluginLogCommands,
+    BudgetCommands,
+    CostCommands,
+    DeveloperCommands,
+    PushCommands,
+    PopupCommands,
+    GatewayCommands,
+    LegacyCommands,
+    WizardCommands,
+    SettingsCommands,
+    IpcMetricsCommands,
+    AppUpdateCommands,
+    PluginAccountCommands {}
 
 // ============================================================================
 // Tauri invoke 类型辅助
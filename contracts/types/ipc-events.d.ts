@@ -9,7 +9,7 @@
  * 共 6 个 IPC Events。
  */
 
-import { PluginInfo, PluginData, PluginHealth, AppError } from './ipc-commands';
+import { PluginInfo, BulkResult, PluginData, PluginHealth, HealthTransition, AppError, InputRequest, NetworkStatus, UpdateInfo, BudgetLine, DeprecatedCommand, PluginLogEntry, WizardProgress, AppUpdateStatus } from './ipc-commands';
 
 // ============================================================================
 // IPC Events 定义
@@ -22,10 +22,24 @@ import { PluginInfo, PluginData, PluginHealth, AppError } from './ipc-commands';
 export type IPCEventName =
   | 'ipc:plugin_installed'
   | 'ipc:plugin_uninstalled'
+  | 'ipc:plugin_bulk_completed'
   | 'ipc:plugin_updated'
   | 'ipc:plugin_data_updated'
+  | 'ipc:plugin_data_changed'
   | 'ipc:plugin_error'
-  | 'ipc:plugin_health_changed';
+  | 'ipc:plugin_health_changed'
+  | 'plugin:health_changed'
+  | 'ipc:input_requested'
+  | 'ipc:network_status_changed'
+  | 'update:available'
+  | 'plugin:reloaded'
+  | 'plugin:data_updated'
+  | 'plugin:log'
+  | 'install:progress'
+  | 'budget:warning'
+  | 'ipc:deprecated_command'
+  | 'wizard:finished'
+  | 'app_update:status';
 
 /**
  * 插件安装完成事件
@@ -75,6 +89,63 @@ export interface PluginDataUpdatedEvent {
   };
 }
 
+/**
+ * 越界方向
+ * - up: 从边界以下升到边界及以上
+ * - down: 从边界及以上回落到边界以下
+ */
+export type CrossingDirection = 'up' | 'down';
+
+/**
+ * 越过的关键边界（50/80/90%）
+ */
+export interface BoundaryCrossing {
+  /** 边界百分比 */
+  boundary: number;
+  /** 越界方向 */
+  direction: CrossingDirection;
+}
+
+/**
+ * 单个用量计量（主用量或维度）的变化
+ */
+export interface PercentageChange {
+  /** 维度 ID（主用量省略） */
+  dimensionId?: string;
+  /** 维度标签（主用量省略） */
+  label?: string;
+  /** 上一次的使用百分比（首次刷新为 null） */
+  previousPercentage: number | null;
+  /** 当前使用百分比 */
+  percentage: number;
+  /** 变化量（首次刷新为 0） */
+  delta: number;
+  /** 越过的关键边界（按越过顺序） */
+  crossings: BoundaryCrossing[];
+}
+
+/**
+ * 刷新结果差异
+ * 主用量的变化字段直接展开在顶层
+ */
+export interface PluginDataChange extends PercentageChange {
+  /** 插件 ID */
+  id: string;
+  /** 各维度变化 */
+  dimensions: PercentageChange[];
+}
+
+/**
+ * 插件数据变化事件
+ * 使用量数据刷新后紧随 ipc:plugin_data_updated 发送，供界面播放变化动画
+ */
+export interface PluginDataChangedEvent {
+  /** 事件名称 */
+  event: 'ipc:plugin_data_changed';
+  /** 事件数据 */
+  payload: PluginDataChange;
+}
+
 /**
  * 插件错误事件
  */
@@ -100,16 +171,203 @@ export interface PluginHealthChangedEvent {
   payload: PluginHealth;
 }
 
+/**
+ * 插件健康状态转换事件
+ * 插件在 healthy / degraded / unhealthy 之间变化时推送（与 ipc:plugin_health_changed 同时发送），
+ * 宿主同时按所有已启用插件中最差的健康状态更新托盘图标
+ */
+export interface PluginHealthTransitionEvent {
+  /** 事件名称 */
+  event: 'plugin:health_changed';
+  /** 事件数据 */
+  payload: HealthTransition;
+}
+
+/**
+ * 插件请求用户输入事件
+ * 宿主同时打开输入对话框窗口 (label: input)
+ */
+export interface InputRequestedEvent {
+  /** 事件名称 */
+  event: 'ipc:input_requested';
+  /** 事件数据 */
+  payload: InputRequest;
+}
+
+/**
+ * 网络状态变化事件
+ * 进入离线、恢复连接或排队刷新变化时发送
+ */
+export interface NetworkStatusChangedEvent {
+  /** 事件名称 */
+  event: 'ipc:network_status_changed';
+  /** 事件数据 */
+  payload: NetworkStatus;
+}
+
+/**
+ * 插件更新可用事件
+ * 后台更新检查发现新版本时发送（信任的插件随后自动安装）
+ */
+export interface UpdateAvailableEvent {
+  /** 事件名称 */
+  event: 'update:available';
+  /** 事件数据 */
+  payload: UpdateInfo[];
+}
+
+/**
+ * 插件热重载完成事件
+ * 开发者模式下 dev-plugins 中插件的 manifest 或入口文件变更后自动重载时发送
+ * （重载失败通过 ipc:plugin_error 发送，错误码 PLUGIN_RELOAD_FAILED）
+ */
+export interface PluginReloadedEvent {
+  /** 事件名称 */
+  event: 'plugin:reloaded';
+  /** 事件数据 */
+  payload: PluginInfo;
+}
+
+/**
+ * 插件数据广播事件
+ * 插件缓存数据每次更新（刷新、推送、定时器、事件等任何来源）后推送到所有窗口，
+ * 前端据此实时更新，无需轮询 get_all_data
+ */
+export interface PluginDataBroadcastEvent {
+  /** 事件名称 */
+  event: 'plugin:data_updated';
+  /** 事件数据 */
+  payload: PluginDataUpdatedEvent['payload'];
+}
+
+/**
+ * 插件日志事件
+ * 插件沙盒中的 console 输出实时推送，供日志查看器显示（推送队列满时丢弃，可用 get_plugin_logs 补齐）
+ */
+export interface PluginLogEvent {
+  /** 事件名称 */
+  event: 'plugin:log';
+  /** 事件数据 */
+  payload: PluginLogEntry;
+}
+
+/**
+ * 安装阶段
+ * - extracting: 正在解压插件包（每个条目发送一次）
+ * - verified: 文件哈希校验完成
+ */
+export type InstallStage = 'extracting' | 'verified';
+
+/**
+ * 插件安装进度
+ */
+export interface InstallProgress {
+  /** 插件 ID（extracting 阶段为发起安装时的 source / 文件路径 / registry ID） */
+  pluginId: string;
+  /** 安装阶段 */
+  stage: InstallStage;
+  /** 已计算哈希的文件数（含解压时边写边算的文件）；extracting 阶段为已处理的条目数 */
+  files: number;
+  /** extracting 阶段的条目总数 */
+  totalFiles?: number;
+  /** 已计算哈希的字节数；extracting 阶段为已写出的字节数 */
+  bytes: number;
+  /** 哈希计算耗时 (毫秒)；extracting 阶段为已解压耗时 */
+  elapsedMs: number;
+  /** 校验吞吐量 (MB/s)，耗时不可计量时省略 */
+  throughputMbPerSec?: number;
+}
+
+/**
+ * 插件安装进度事件
+ * plugin_install / plugin_install_from_file / registry_install 过程中发送
+ */
+export interface InstallProgressEvent {
+  /** 事件名称 */
+  event: 'install:progress';
+  /** 事件数据 */
+  payload: InstallProgress;
+}
+
+/**
+ * 预算提醒事件
+ * 预计本月花费超出预算时发送，每个预算每月一次
+ */
+export interface BudgetWarningEvent {
+  /** 事件名称 */
+  event: 'budget:warning';
+  /** 超出预算的预算状态 */
+  payload: BudgetLine;
+}
+
+/**
+ * 批量操作完成事件
+ * plugin_bulk 执行完成后发送一次，payload 汇总各插件结果
+ */
+export interface PluginBulkCompletedEvent {
+  /** 事件名称 */
+  event: 'ipc:plugin_bulk_completed';
+  /** 批量操作结果 */
+  payload: BulkResult;
+}
+
+/**
+ * 旧版命令弃用事件
+ * 每次调用 Phase 2 旧版命令时发送，payload 为该命令的累计调用统计
+ */
+export interface DeprecatedCommandEvent {
+  /** 事件名称 */
+  event: 'ipc:deprecated_command';
+  /** 旧命令及替代命令 */
+  payload: DeprecatedCommand;
+}
+
+/**
+ * 首次设置完成事件
+ * wizard_finish 成功后发送，各窗口据此应用选择的刷新间隔
+ */
+export interface WizardFinishedEvent {
+  /** 事件名称 */
+  event: 'wizard:finished';
+  /** 向导最终进度 */
+  payload: WizardProgress;
+}
+
+/**
+ * 应用更新状态事件
+ * 检查完成、下载进度变化、下载完成或失败时发送
+ */
+export interface AppUpdateStatusEvent {
+  /** 事件名称 */
+  event: 'app_update:status';
+  /** 更新状态 */
+  payload: AppUpdateStatus;
+}
+
 /**
  * 所有 IPC 事件联合类型
  */
 export type IPCEvent =
   | PluginInstalledEvent
   | PluginUninstalledEvent
+  | PluginBulkCompletedEvent
   | PluginUpdatedEvent
   | PluginDataUpdatedEvent
+  | PluginDataChangedEvent
   | PluginErrorEvent
-  | PluginHealthChangedEvent;
+  | PluginHealthChangedEvent
+  | PluginHealthTransitionEvent
+  | InputRequestedEvent
+  | NetworkStatusChangedEvent
+  | UpdateAvailableEvent
+  | PluginReloadedEvent
+  | PluginDataBroadcastEvent
+  | PluginLogEvent
+  | InstallProgressEvent
+  | BudgetWarningEvent
+  | DeprecatedCommandEvent
+  | WizardFinishedEvent
+  | AppUpdateStatusEvent;
 
 // ============================================================================
 // 事件监听器类型
@@ -121,10 +379,24 @@ export type IPCEvent =
 export interface IPCEventHandlers {
   'ipc:plugin_installed': (payload: PluginInstalledEvent['payload']) => void;
   'ipc:plugin_uninstalled': (payload: PluginUninstalledEvent['payload']) => void;
+  'ipc:plugin_bulk_completed': (payload: PluginBulkCompletedEvent['payload']) => void;
   'ipc:plugin_updated': (payload: PluginUpdatedEvent['payload']) => void;
   'ipc:plugin_data_updated': (payload: PluginDataUpdatedEvent['payload']) => void;
+  'ipc:plugin_data_changed': (payload: PluginDataChangedEvent['payload']) => void;
   'ipc:plugin_error': (payload: PluginErrorEvent['payload']) => void;
   'ipc:plugin_health_changed': (payload: PluginHealthChangedEvent['payload']) => void;
+  'plugin:health_changed': (payload: PluginHealthTransitionEvent['payload']) => void;
+  'ipc:input_requested': (payload: InputRequestedEvent['payload']) => void;
+  'ipc:network_status_changed': (payload: NetworkStatusChangedEvent['payload']) => void;
+  'update:available': (payload: UpdateAvailableEvent['payload']) => void;
+  'plugin:reloaded': (payload: PluginReloadedEvent['payload']) => void;
+  'plugin:data_updated': (payload: PluginDataBroadcastEvent['payload']) => void;
+  'plugin:log': (payload: PluginLogEvent['payload']) => void;
+  'install:progress': (payload: InstallProgressEvent['payload']) => void;
+  'budget:warning': (payload: BudgetWarningEvent['payload']) => void;
+  'ipc:deprecated_command': (payload: DeprecatedCommandEvent['payload']) => void;
+  'wizard:finished': (payload: WizardFinishedEvent['payload']) => void;
+  'app_update:status': (payload: AppUpdateStatusEvent['payload']) => void;
 }
 
 /**
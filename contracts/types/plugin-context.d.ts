@@ -58,17 +58,71 @@ export interface PluginStorage {
   clear(): Promise<void>;
 }
 
+/**
+ * 插件密钥 API（只读）
+ *
+ * 存储位置: macOS Keychain，服务名 `cuk.plugin.{pluginId}`，账户名为密钥名称
+ * 权限: 每个名称需在 manifest permissions 中声明 `secrets:{name}`
+ * 写入: 由宿主设置界面通过 keychain_set 完成，插件不可写入或枚举
+ */
+export interface PluginSecrets {
+  /**
+   * 读取密钥
+   * @param name 密钥名称，只能包含字母、数字、下划线和连字符
+   * @returns 密钥值，不存在则返回 undefined
+   * @throws PERMISSION_DENIED 如果未声明 `secrets:{name}` 权限
+   */
+  get(name: string): Promise<string | undefined>;
+}
+
+/**
+ * 输入请求字段
+ */
+export interface InputFieldOptions {
+  /** 字段名，只能包含字母、数字、下划线和连字符，作为返回对象的键 */
+  name: string;
+  /** 显示标签，默认使用 name */
+  label?: string;
+  /** 字段类型，默认 text；password 以掩码显示 */
+  type?: 'text' | 'password' | 'code';
+  /** 占位提示 */
+  placeholder?: string;
+  /** 是否必填，默认 true */
+  required?: boolean;
+}
+
+/**
+ * 输入请求参数
+ */
+export interface InputRequestOptions {
+  /** 对话框标题 (1-100 字符) */
+  title: string;
+  /** 说明文字 */
+  message?: string;
+  /** 字段列表 (1-8 个) */
+  fields: InputFieldOptions[];
+  /** 等待时间 (毫秒)，默认 120000，范围 5000-300000，且不超过本次执行的剩余时间 */
+  timeoutMs?: number;
+}
+
 /**
  * 插件缓存 API
  *
- * 键空间: 每个插件独立，键名格式为 `cache:{pluginId}:{key}`
- * 并发语义: 线程安全，支持并发读写
- * 存储位置: 内存 (moka::future::Cache)
- * 过期策略: TTL (Time-To-Live) + TTI (Time-To-Idle)
- * 默认 TTL: 5 分钟 (300000ms)
- * 默认 TTI: 2 分钟 (120000ms)
+ * 权限: manifest permissions 中声明 `cache`
+ * 键空间: 每个插件独立目录，键名只能包含字母、数字、下划线和连字符
+ * 存储位置: ~/Library/Caches/cuk/plugins/{pluginId}/，卸载插件时删除
+ * 配额: 单插件 10MB，超出时先清理过期条目，再按最近最少使用 (LRU) 淘汰
+ * 过期策略: set() 默认 TTL 5 分钟 (300000ms)，put() 写入的条目不过期
  */
 export interface PluginCache {
+  /**
+   * 写入缓存（不过期，仅受配额淘汰）
+   * @param key 缓存键
+   * @param value 字节 (Uint8Array/ArrayBuffer) 或可 JSON 序列化的值，读取时保持原类型
+   * @throws CACHE_ERROR 如果单个值超出配额
+   */
+  put(key: string, value: Uint8Array | ArrayBuffer | unknown): Promise<void>;
+
   /**
    * 获取缓存值
    * @param key 缓存键
@@ -95,6 +149,62 @@ export interface PluginCache {
    * @param key 缓存键
    */
   has(key: string): Promise<boolean>;
+
+  /**
+   * 列出所有缓存键
+   */
+  keys(): Promise<string[]>;
+}
+
+/**
+ * 持久定时器参数（delayMs 与 intervalMs 至少指定一个）
+ */
+export interface TimerScheduleOptions {
+  /** 首次触发延时 (毫秒)，默认等于 intervalMs，最大 86400000 (24小时) */
+  delayMs?: number;
+  /** 重复间隔 (毫秒)，省略则只触发一次，范围 1000-86400000 */
+  intervalMs?: number;
+  /** 触发时传给 onTimer 的数据，必须可 JSON 序列化 (最大 16KB) */
+  data?: unknown;
+}
+
+/**
+ * 持久定时器 API
+ *
+ * 权限: manifest permissions 中声明 `timer`
+ * 沙盒在每次执行结束后回收，setTimeout/setInterval 只在本次执行期间有效；
+ * 需要周期性工作时通过本 API 登记，由宿主计时，到期时在新的沙盒中调用插件导出的 onTimer(name, data, context)
+ *
+ * 语义:
+ * - 登记在本次执行成功结束后生效，且只对已启用的插件生效
+ * - 同名定时器重新登记时替换旧定时器；一次性定时器触发后自动注销
+ * - 每个插件最多 10 个；禁用、卸载插件时全部取消
+ * - 不持久化，应用重启后需在 onLoad 中重新登记
+ * - 上次触发的 onTimer 仍在执行时跳过本次触发
+ */
+export interface PluginTimers {
+  /**
+   * 登记持久定时器
+   * @param name 定时器名称 (1-64 字符，字母、数字和 `-_.:`)
+   * @throws PERMISSION_DENIED 如果未声明 `timer` 权限
+   * @throws Error 如果参数无效或单次执行登记超过 32 次
+   *
+   * @example
+   * export async function onLoad(context) {
+   *   context.timers.schedule('poll', { intervalMs: 10 * 60 * 1000 });
+   * }
+   * export async function onTimer(name, data, context) {
+   *   if (name === 'poll') context.emit('refreshed', await check(context));
+   * }
+   */
+  schedule(name: string, options: TimerScheduleOptions): void;
+
+  /**
+   * 取消持久定时器（不存在时忽略）
+   * @param name 定时器名称
+   * @throws PERMISSION_DENIED 如果未声明 `timer` 权限
+   */
+  cancel(name: string): void;
 }
 
 /**
@@ -150,10 +260,59 @@ export interface PluginContext {
   readonly storage: PluginStorage;
 
   /**
-   * 插件专属内存缓存
+   * 插件专属密钥（只读，Keychain）
+   */
+  readonly secrets: PluginSecrets;
+
+  /**
+   * 运行时请求用户输入（如一次性验证码）
+   *
+   * 权限: manifest permissions 中声明 `input`
+   * 宿主打开输入对话框，用户提交后返回字段值；每个插件同时只能有一个待处理请求
+   * 等待时间不超过本次执行的剩余时间（执行超时后对话框随之失效）
+   *
+   * @returns 字段名到输入值的映射
+   * @throws PERMISSION_DENIED 如果未声明 `input` 权限
+   * @throws TIMEOUT 如果用户在等待时间内未响应，或剩余执行时间不足 5 秒
+   * @throws INPUT_CANCELLED 如果用户取消输入
+   * @throws INPUT_UNAVAILABLE 如果输入对话框无法打开
+   * @throws UNKNOWN 如果参数无效
+   * @throws RATE_LIMIT 如果已有待处理的输入请求
+   *
+   * @example
+   * const { code } = await context.requestInput({
+   *   title: '输入两步验证码',
+   *   fields: [{ name: 'code', type: 'code', label: '验证码' }],
+   * });
+   */
+  requestInput(options: InputRequestOptions): Promise<Record<string, string>>;
+
+  /**
+   * 读取其他插件最近一次缓存的数据（只读快照，比 context.call 更轻量）
+   *
+   * 权限: manifest permissions 中声明 `read:{pluginId}`
+   * 快照在本次执行开始时获取，返回的是副本，修改不影响宿主缓存
+   *
+   * @param pluginId 目标插件 ID
+   * @returns 目标插件的 PluginData，尚无数据时为 null
+   * @throws PERMISSION_DENIED 如果未声明 `read:{pluginId}` 权限
+   *
+   * @example
+   * const usage = await context.getData('claude-usage');
+   * if (usage && usage.dataType === 'usage') { ... }
+   */
+  getData(pluginId: string): Promise<Record<string, unknown> | null>;
+
+  /**
+   * 插件专属磁盘缓存
    */
   readonly cache: PluginCache;
 
+  /**
+   * 宿主调度的持久定时器（触发时调用插件导出的 onTimer）
+   */
+  readonly timers: PluginTimers;
+
   /**
    * 输出日志
    * @param level 日志级别
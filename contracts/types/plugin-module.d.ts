@@ -41,7 +41,10 @@ export interface PluginMetadata {
   /** 版本号 (必填) */
   version: string;
 
-  /** API 版本 (必填) */
+  /**
+   * API 版本 (必填)，格式 "主版本.次版本"
+   * 当前支持 0.9 - 1.0，旧版本 manifest 加载时自动迁移；超出范围的插件不加载
+   */
   apiVersion: string;
 
   /** 插件类型 (必填) */
@@ -65,11 +68,45 @@ export interface PluginMetadata {
   /** 刷新间隔 (毫秒) */
   refreshIntervalMs?: number;
 
-  /** 权限声明 */
+  /** 每分钟最多发起的 fetch 请求数（默认 60，上限 600），超出的请求以 RateLimit 错误失败 */
+  maxRequestsPerMinute?: number;
+
+  /** fetchData 瞬时失败（网络错误/超时）的重试策略，未声明时使用默认策略 */
+  retryPolicy?: RetryPolicy;
+
+  /** 服务商计划维护窗口：维护期间不自动刷新，强制刷新的失败不计入健康统计 */
+  maintenanceWindows?: MaintenanceWindow[];
+
+  /**
+   * 沙盒资源限制（未声明时内存 16MB、超时 30 秒）
+   * 超过管理员策略上限（默认 64MB / 120 秒）的插件不会被加载
+   */
+  resources?: ResourceLimits;
+
+  /**
+   * 权限声明
+   * 网络访问可声明 `network`（不限主机，受 allowedHosts 约束）或逐个声明
+   * `fetch:<host-pattern>`（如 `fetch:api.openai.com`、`fetch:*.anthropic.com`），
+   * 声明后只允许访问匹配的主机；`read:<pluginId>` 允许通过 context.getData 读取该插件的缓存数据
+   */
   permissions?: string[];
 
   /** 配置项 Schema */
   configSchema?: Record<string, ConfigFieldSchema>;
+
+  /**
+   * custom 数据的 JSON Schema（dataType 为 custom 时生效）
+   * 校验 fetchData 返回的完整对象；不符合时数据不会进入缓存，plugin_test_run 会列出不符项。
+   * 支持 type/enum/const/properties/required/additionalProperties/items/
+   * minItems/maxItems/minimum/maximum/minLength/maxLength
+   */
+  payloadSchema?: Record<string, unknown>;
+  /**
+   * 是否接受外部推送数据
+   * 宿主开启本地推送端点后，外部系统可经 `POST /v1/push/{id}`（Bearer token）推送
+   * 与 fetchData 返回值格式相同的数据
+   */
+  acceptsPush?: boolean;
 }
 
 /**
@@ -78,6 +115,10 @@ export interface PluginMetadata {
 export interface ConfigFieldSchema {
   type: 'string' | 'number' | 'boolean' | 'select';
   required?: boolean;
+  /**
+   * 敏感字段：值存入 macOS 钥匙串，磁盘和 get_plugin_config 中只保留
+   * `{ secretRef: 字段名 }` 引用，执行插件时才替换为明文
+   */
   secret?: boolean;
   label?: string;
   description?: string;
@@ -87,6 +128,53 @@ export interface ConfigFieldSchema {
   options?: Array<{ value: string; label: string }>;
 }
 
+/**
+ * fetchData 重试策略（仅网络错误和超时会重试）
+ */
+export interface RetryPolicy {
+  /** 最大重试次数（不含首次执行，0-5，默认 2） */
+  maxRetries?: number;
+  /** 首次重试前的退避时间 (毫秒，默认 500)，之后按 2 倍递增 */
+  initialDelayMs?: number;
+  /** 退避时间上限 (毫秒，默认 5000) */
+  maxDelayMs?: number;
+}
+
+/**
+ * 沙盒资源限制
+ */
+export interface ResourceLimits {
+  /** 内存上限 (MB，大于 0) */
+  memoryLimitMb?: number;
+  /** 单次执行超时 (毫秒，不小于 100) */
+  timeoutMs?: number;
+}
+
+/**
+ * 服务商维护窗口
+ * - 一次性窗口: `{ start, end }`（RFC 3339）
+ * - 每周重复窗口（UTC）: `{ weekdays, startTime, durationMinutes }`
+ */
+export type MaintenanceWindow =
+  | {
+      /** 开始时间 (RFC 3339) */
+      start: string;
+      /** 结束时间 (RFC 3339) */
+      end: string;
+      /** 维护说明 */
+      reason?: string;
+    }
+  | {
+      /** 星期（如 "mon"、"sunday"） */
+      weekdays: string[];
+      /** 开始时间 (UTC, HH:MM) */
+      startTime: string;
+      /** 持续时长（分钟，最长 7 天） */
+      durationMinutes: number;
+      /** 维护说明 */
+      reason?: string;
+    };
+
 // ============================================================================
 // 插件数据类型
 // ============================================================================
@@ -169,6 +257,8 @@ export type PluginData = UsageData | BalanceData | StatusData | CustomData;
 export interface ConfigValidationResult {
   valid: boolean;
   message?: string;
+  /** 字段错误 (字段名 -> 错误消息)，存在时视为无效 */
+  fieldErrors?: Record<string, string>;
 }
 
 /**
@@ -195,10 +285,46 @@ export interface DataPlugin {
   /** 插件卸载时调用 */
   onUnload?(context: PluginContext): Promise<void>;
 
-  /** 验证配置 */
-  validateConfig?(config: Record<string, unknown>): Promise<ConfigValidationResult>;
+  /**
+   * 持久定时器到期时调用（见 PluginContext.timers）
+   * @param name 定时器名称
+   * @param data 登记时附带的数据，未指定时为 null
+   * @param context 运行时上下文
+   */
+  onTimer?(name: string, data: unknown, context: PluginContext): Promise<void>;
+
+  /**
+   * 验证配置 (在沙盒中以待保存的配置调用，超时 5 秒；未实现时按 configSchema 校验)
+   * @param config 待保存的配置
+   * @param context 运行时上下文
+   */
+  validateConfig?(
+    config: Record<string, unknown>,
+    context: PluginContext
+  ): Promise<ConfigValidationResult>;
 }
 
+/**
+ * 带负载过滤器的事件订阅
+ *
+ * @example
+ * { event: 'plugin:*:data_updated', filter: { dataType: ['usage', 'balance'] } }
+ */
+export interface FilteredEventSubscription {
+  /** 事件名称或通配符模式（段内 `*` 匹配任意字符，不跨越 `:`） */
+  event: string;
+  /**
+   * 负载过滤器：字段路径（嵌套字段用 `.`）-> 期望值
+   * 全部字段相等时才投递；期望值为数组时匹配其中任一值
+   */
+  filter?: Record<string, unknown>;
+}
+
+/**
+ * 事件订阅：事件名称、通配符模式（如 plugin:*:data_updated）或带过滤器的订阅
+ */
+export type EventSubscription = string | FilteredEventSubscription;
+
 /**
  * EventPlugin 接口
  * 必须实现 onEvent 和 subscribedEvents
@@ -208,11 +334,15 @@ export interface EventPlugin {
   metadata: PluginMetadata;
 
   /** 订阅的事件列表 (必须导出) */
-  subscribedEvents: string[];
+  subscribedEvents: EventSubscription[];
 
   /**
    * 事件处理函数 (必须实现)
-   * @param event 事件名称
+   *
+   * 订阅的事件出队时在插件沙盒中调用，超时 5 秒；抛出异常或超时计入插件健康统计。
+   * 插件 fetchData 成功或收到推送数据后，宿主发布 `plugin:{pluginId}:data_updated`，数据为新的 PluginData。
+   *
+   * @param event 完整事件名称 (如 plugin:claude-usage:data_updated)
    * @param data 事件数据
    * @param context 运行时上下文
    */
@@ -230,6 +360,14 @@ export interface EventPlugin {
 
   /** 插件卸载时调用 */
   onUnload?(context: PluginContext): Promise<void>;
+
+  /**
+   * 持久定时器到期时调用（见 PluginContext.timers）
+   * @param name 定时器名称
+   * @param data 登记时附带的数据，未指定时为 null
+   * @param context 运行时上下文
+   */
+  onTimer?(name: string, data: unknown, context: PluginContext): Promise<void>;
 }
 
 /**
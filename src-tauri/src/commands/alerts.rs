@@ -0,0 +1,24 @@
+// Phase 6.6: 告警暂停与静音
+// 应用级告警管理器，静默设置持久化到 alert_silences.json（与插件目录同级）
+// Phase 6.16: 告警历史持久化到 alert_history.json（与插件目录同级）
+
+use std::sync::Arc;
+
+use tauri::AppHandle;
+
+use crate::plugin::monitoring::AlertHistory;
+use crate::plugin::{create_alert_manager_with_notifications, AlertManager, AlertSilences, PluginManager};
+
+/// 告警管理器状态
+pub struct AlertManagerState(pub Arc<AlertManager>);
+
+/// 创建告警管理器（使用系统通知，加载静默设置和告警历史）
+pub fn create_alert_manager(app: AppHandle, manager: &PluginManager) -> AlertManagerState {
+    let silences_path = AlertSilences::file_path(manager.plugins_dir());
+    let history_path = AlertHistory::file_path(manager.plugins_dir());
+    AlertManagerState(Arc::new(
+        create_alert_manager_with_notifications(app)
+            .with_silences_file(silences_path)
+            .with_history_file(history_path),
+    ))
+}
@@ -0,0 +1,64 @@
+// Phase 6.8: 月度预算提醒
+// 每次刷新得到新数据后记录花费，预计超出预算时发送 budget:warning 事件和系统通知
+//
+// 预算设置保存在 budgets.json，本月花费账本保存在 budget_ledger.json（均与插件目录同级）
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::alerts::AlertManagerState;
+use crate::commands::missed_alerts::deliver_notification;
+use crate::commands::events::emitter;
+use crate::plugin::budget::{BudgetLine, BudgetTracker};
+use crate::plugin::monitoring::ALL_PLUGINS;
+use crate::plugin::types::PluginData;
+use crate::plugin::PluginManager;
+
+/// 预算跟踪器状态
+pub struct BudgetState(pub Arc<BudgetTracker>);
+
+/// 创建预算跟踪器（加载预算设置和本月账本）
+pub fn create_budget_tracker(manager: &PluginManager) -> BudgetState {
+    BudgetState(Arc::new(BudgetTracker::load(manager.plugins_dir())))
+}
+
+/// 发送预算提醒通知
+fn notify_budget_warning(app: &AppHandle, warning: &BudgetLine) {
+    let target = warning.plugin_id.as_deref().unwrap_or("全部插件");
+    let title = format!("{} 本月预计超出预算", target);
+    let body = format!(
+        "已花费 {:.2} {}，预计月底 {:.2}，预算 {:.2}",
+        warning.spent,
+        warning.currency,
+        warning.projected.unwrap_or(warning.spent),
+        warning.limit.unwrap_or_default()
+    );
+
+    log::info!("预算提醒: target={}, {}", target, body);
+    deliver_notification(app, &title, &body, false);
+}
+
+/// 记录刷新后的插件数据，预计超出预算时提醒
+pub fn check_budget(app: &AppHandle, data: &PluginData) {
+    let Some(state) = app.try_state::<BudgetState>() else {
+        return;
+    };
+    let warnings = state.0.record(data, Utc::now());
+
+    for warning in &warnings {
+        if let Err(e) = emitter(app).emit_budget_warning(warning) {
+            log::warn!("发送预算提醒事件失败: emit_error={}", e);
+        }
+        // 暂停/静音期间仍记为已提醒，只是不发送系统通知
+        let silenced = app.try_state::<AlertManagerState>().is_some_and(|alert_manager| {
+            alert_manager
+                .0
+                .is_silenced(warning.plugin_id.as_deref().unwrap_or(ALL_PLUGINS))
+        });
+        if !silenced {
+            notify_budget_warning(app, warning);
+        }
+    }
+}
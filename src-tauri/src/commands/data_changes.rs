@@ -0,0 +1,95 @@
+// Phase 6.11: 刷新结果发布
+// 刷新（手动、批量、恢复连接后补刷、本地推送）得到新数据后统一经 publish_plugin_data 发布：
+// 发送 ipc:plugin_data_updated，计算与上一次结果的差异并发送 ipc:plugin_data_changed，
+// 数据有变化时才检查用量提醒和用量异常，最后记录预算花费。
+//
+// 此外，插件缓存数据的每次更新（包括定时器、事件、更新后的健康检查等非刷新路径）
+// 都经 spawn_data_update_broadcaster 以 plugin:data_updated 和 window:data_updated 推送到所有窗口，
+// 前端无需轮询 get_all_data。
+
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager};
+
+use crate::commands::alerts::AlertManagerState;
+use crate::commands::budget::check_budget;
+use crate::commands::events::emitter;
+use crate::commands::usage_alerts::check_usage_alerts;
+use crate::plugin::monitoring::DataDiffTracker;
+use crate::plugin::types::PluginData;
+use crate::plugin::{AlertType, PluginManager};
+use crate::window::broadcast_data_update;
+
+/// 刷新结果差异跟踪器状态
+pub struct DataDiffState(pub Arc<DataDiffTracker>);
+
+/// 创建刷新结果差异跟踪器
+pub fn create_data_diff_tracker() -> DataDiffState {
+    DataDiffState(Arc::new(DataDiffTracker::new()))
+}
+
+/// 发布插件刷新结果
+pub async fn publish_plugin_data(app: &AppHandle, manager: &PluginManager, id: &str, data: &PluginData) {
+    if let Err(e) = emitter(app).emit_plugin_data_updated(id, data) {
+        log::warn!("发送插件数据更新事件失败: plugin={}, emit_error={}", id, e);
+    }
+
+    let change = app
+        .try_state::<DataDiffState>()
+        .and_then(|state| state.0.record(data));
+    if let Some(change) = &change {
+        if change.has_crossings() {
+            log::info!(
+                "[{}] 使用量越过关键边界: {:.1}% -> {:.1}%",
+                id,
+                change.primary.previous_percentage.unwrap_or_default(),
+                change.primary.percentage
+            );
+        }
+        if let Err(e) = emitter(app).emit_plugin_data_changed(change) {
+            log::warn!("发送插件数据变化事件失败: plugin={}, emit_error={}", id, e);
+        }
+    }
+
+    // 数据与上一次相同时不会越过新的阈值，跳过用量提醒检查
+    if change.as_ref().map_or(true, |c| c.is_changed()) {
+        check_usage_alerts(app, manager, data).await;
+        check_usage_anomaly(app, manager, data).await;
+    }
+    check_budget(app, data);
+}
+
+/// 启动数据更新广播：消费 PluginManager 的数据更新通道，推送到所有窗口
+pub fn spawn_data_update_broadcaster(app: AppHandle, manager: Arc<PluginManager>) {
+    let Some(mut updates) = manager.take_data_update_receiver() else {
+        log::warn!("数据更新广播已启动，跳过");
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        while let Some(update) = updates.recv().await {
+            let id = &update.plugin_id;
+            if let Err(e) = emitter(&app).emit_plugin_data_broadcast(id, &update.data) {
+                log::warn!("广播插件数据更新失败: plugin={}, emit_error={}", id, e);
+            }
+            let payload = serde_json::json!({ "id": id, "data": update.data });
+            if let Err(e) = broadcast_data_update(&app, payload) {
+                log::warn!("窗口数据同步失败: plugin={}, emit_error={}", id, e);
+            }
+        }
+    });
+}
+
+/// 检查用量异常（最近一小时的消耗明显高于基线时触发 AnomalyDetected 告警，恢复正常时标记已恢复）
+async fn check_usage_anomaly(app: &AppHandle, manager: &PluginManager, data: &PluginData) {
+    let PluginData::Usage(usage) = data else {
+        return;
+    };
+    let Some(alert_manager) = app.try_state::<AlertManagerState>() else {
+        return;
+    };
+    match manager.detect_usage_anomaly(&usage.base.plugin_id) {
+        Some(anomaly) => alert_manager.0.check_usage_anomaly(&anomaly).await,
+        // 用量恢复正常，标记未恢复的异常告警
+        None => alert_manager.0.resolve(&usage.base.plugin_id, &[AlertType::AnomalyDetected]),
+    }
+}
@@ -0,0 +1,445 @@
+// Phase 7.3.1: IPC 错误信封
+// 所有 IPC 命令的失败结果统一为 { code, message, details }:
+// - code 为稳定的错误码（见 codes），前端据此分支处理，不依赖 message 文案
+// - message 为用户可读的中文描述，可随版本调整
+// - details 为可选的结构化信息（如配置校验的字段错误）
+// - LifecycleError / SecurityError / RuntimeError 按错误种类映射到固定错误码，
+//   无法归类的生命周期错误使用调用方传入的操作失败码（如 PLUGIN_ENABLE_FAILED）
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::plugin::lifecycle::LifecycleError;
+use crate::plugin::types::AppError;
+use crate::plugin::RuntimeError;
+use crate::security::SecurityError;
+
+/// 错误码常量（与 contracts/types/ipc-commands.d.ts 中的 IpcErrorCode 保持一致）
+pub mod codes {
+    // ------------------------------------------------------------------------
+    // 通用
+    // ------------------------------------------------------------------------
+
+    /// 参数不合法
+    pub const INVALID_ARGUMENT: &str = "INVALID_ARGUMENT";
+    /// 输入内容不合法
+    pub const INVALID_INPUT: &str = "INVALID_INPUT";
+    /// JSON 解析失败
+    pub const INVALID_JSON: &str = "INVALID_JSON";
+    /// 批量请求超过上限
+    pub const BATCH_TOO_LARGE: &str = "BATCH_TOO_LARGE";
+    /// 当前平台或插件不支持该操作
+    pub const NOT_SUPPORTED: &str = "NOT_SUPPORTED";
+    /// 调用窗口无权执行该命令
+    pub const COMMAND_NOT_ALLOWED: &str = "COMMAND_NOT_ALLOWED";
+    /// 文件读写失败
+    pub const IO_ERROR: &str = "IO_ERROR";
+
+    // ------------------------------------------------------------------------
+    // 插件生命周期
+    // ------------------------------------------------------------------------
+
+    /// 插件不存在
+    pub const PLUGIN_NOT_FOUND: &str = "PLUGIN_NOT_FOUND";
+    /// 插件未启用
+    pub const PLUGIN_DISABLED: &str = "PLUGIN_DISABLED";
+    /// manifest.json 缺失或无法解析
+    pub const MANIFEST_INVALID: &str = "MANIFEST_INVALID";
+    /// manifest apiVersion 超出支持范围（details 含支持范围和处理建议）
+    pub const INCOMPATIBLE_API_VERSION: &str = "INCOMPATIBLE_API_VERSION";
+    /// 插件配置校验失败（details.fieldErrors 为字段错误列表）
+    pub const CONFIG_INVALID: &str = "CONFIG_INVALID";
+    /// 插件密钥读写失败
+    pub const SECRET_STORE_FAILED: &str = "SECRET_STORE_FAILED";
+    /// 沙盒资源声明或文件大小超出上限
+    pub const RESOURCE_LIMIT_EXCEEDED: &str = "RESOURCE_LIMIT_EXCEEDED";
+    /// 启用插件失败
+    pub const PLUGIN_ENABLE_FAILED: &str = "PLUGIN_ENABLE_FAILED";
+    /// 禁用插件失败
+    pub const PLUGIN_DISABLE_FAILED: &str = "PLUGIN_DISABLE_FAILED";
+    /// 卸载插件失败
+    pub const PLUGIN_UNINSTALL_FAILED: &str = "PLUGIN_UNINSTALL_FAILED";
+    /// 重载插件失败
+    pub const PLUGIN_RELOAD_FAILED: &str = "PLUGIN_RELOAD_FAILED";
+    /// 更新插件失败
+    pub const PLUGIN_UPDATE_FAILED: &str = "PLUGIN_UPDATE_FAILED";
+    /// 刷新插件数据失败
+    pub const PLUGIN_REFRESH_FAILED: &str = "PLUGIN_REFRESH_FAILED";
+    /// 回放测试运行失败
+    pub const PLUGIN_TEST_RUN_FAILED: &str = "PLUGIN_TEST_RUN_FAILED";
+    /// 添加或移除插件账户失败
+    pub const PLUGIN_ACCOUNT_FAILED: &str = "PLUGIN_ACCOUNT_FAILED";
+    /// 保存插件配置失败
+    pub const CONFIG_SET_FAILED: &str = "CONFIG_SET_FAILED";
+    /// 测试输入不存在
+    pub const INPUT_NOT_FOUND: &str = "INPUT_NOT_FOUND";
+    /// 生成诊断文件失败
+    pub const DIAGNOSTICS_FAILED: &str = "DIAGNOSTICS_FAILED";
+    /// 完整性自检失败
+    pub const INTEGRITY_CHECK_FAILED: &str = "INTEGRITY_CHECK_FAILED";
+    /// 扫描并加载插件失败
+    pub const PLUGIN_DISCOVER_FAILED: &str = "PLUGIN_DISCOVER_FAILED";
+    /// 插件文件被篡改，已隔离（plugin:error 事件）
+    pub const PLUGIN_TAMPERED: &str = "PLUGIN_TAMPERED";
+    /// 插件连续失控，已自动禁用（plugin:error 事件）
+    pub const PLUGIN_AUTO_DISABLED: &str = "PLUGIN_AUTO_DISABLED";
+    /// 插件更新后健康检查失败，已回滚
+    pub const PLUGIN_UPDATE_ROLLED_BACK: &str = "PLUGIN_UPDATE_ROLLED_BACK";
+    /// 旧版命令已停用
+    pub const LEGACY_COMMAND_DISABLED: &str = "LEGACY_COMMAND_DISABLED";
+
+    // ------------------------------------------------------------------------
+    // 安全校验（SecurityError）
+    // ------------------------------------------------------------------------
+
+    /// 签名无效、格式错误或公钥未找到
+    pub const SIGNATURE_INVALID: &str = "SIGNATURE_INVALID";
+    /// 文件哈希不匹配或文件缺失
+    pub const INTEGRITY_FAILED: &str = "INTEGRITY_FAILED";
+    /// 插件包包含路径穿越、符号链接或不允许的文件类型
+    pub const UNSAFE_ARCHIVE: &str = "UNSAFE_ARCHIVE";
+    /// 插件包解压失败
+    pub const EXTRACT_FAILED: &str = "EXTRACT_FAILED";
+    /// 插件包解压被用户取消
+    pub const INSTALL_CANCELLED: &str = "INSTALL_CANCELLED";
+    /// 回滚所需的备份不存在
+    pub const BACKUP_NOT_FOUND: &str = "BACKUP_NOT_FOUND";
+    /// 被管理员策略拒绝
+    pub const POLICY_DENIED: &str = "POLICY_DENIED";
+    /// 需要先开启开发者模式
+    pub const DEVELOPER_MODE_REQUIRED: &str = "DEVELOPER_MODE_REQUIRED";
+
+    // ------------------------------------------------------------------------
+    // 插件安装与 Registry
+    // ------------------------------------------------------------------------
+
+    /// 安装来源不合法或未提供下载地址
+    pub const INVALID_SOURCE: &str = "INVALID_SOURCE";
+    /// 插件包或资源下载失败
+    pub const DOWNLOAD_FAILED: &str = "DOWNLOAD_FAILED";
+    /// 插件包中的 manifest.json 无法解析
+    pub const MANIFEST_PARSE_FAILED: &str = "MANIFEST_PARSE_FAILED";
+    /// 安装插件失败
+    pub const INSTALL_FAILED: &str = "INSTALL_FAILED";
+    /// 获取 registry 索引失败
+    pub const REGISTRY_FETCH_FAILED: &str = "REGISTRY_FETCH_FAILED";
+    /// registry 索引格式不合法
+    pub const REGISTRY_INVALID: &str = "REGISTRY_INVALID";
+    /// registry 索引签名无效
+    pub const REGISTRY_SIGNATURE_INVALID: &str = "REGISTRY_SIGNATURE_INVALID";
+    /// 获取 registry 中的 manifest 失败
+    pub const MANIFEST_FETCH_FAILED: &str = "MANIFEST_FETCH_FAILED";
+    /// registry 条目未提供该资源
+    pub const ASSET_NOT_FOUND: &str = "ASSET_NOT_FOUND";
+    /// 资源类型或内容不合法
+    pub const ASSET_INVALID: &str = "ASSET_INVALID";
+
+    // ------------------------------------------------------------------------
+    // 沙盒运行时（RuntimeError）
+    // ------------------------------------------------------------------------
+
+    /// 运行时或上下文创建失败
+    pub const RUNTIME_INIT_FAILED: &str = "RUNTIME_INIT_FAILED";
+    /// 插件执行超时
+    pub const EXECUTION_TIMEOUT: &str = "EXECUTION_TIMEOUT";
+    /// 插件内存超限
+    pub const MEMORY_EXCEEDED: &str = "MEMORY_EXCEEDED";
+    /// 插件执行被中断
+    pub const EXECUTION_INTERRUPTED: &str = "EXECUTION_INTERRUPTED";
+    /// 插件脚本执行出错
+    pub const JS_EXECUTION_FAILED: &str = "JS_EXECUTION_FAILED";
+
+    // ------------------------------------------------------------------------
+    // 设置与窗口
+    // ------------------------------------------------------------------------
+
+    /// 保存告警静默状态失败
+    pub const ALERT_SILENCE_SAVE_FAILED: &str = "ALERT_SILENCE_SAVE_FAILED";
+    /// 告警历史中不存在该告警
+    pub const ALERT_NOT_FOUND: &str = "ALERT_NOT_FOUND";
+    /// 预算参数不合法
+    pub const INVALID_BUDGET: &str = "INVALID_BUDGET";
+    /// 保存预算失败
+    pub const BUDGET_SAVE_FAILED: &str = "BUDGET_SAVE_FAILED";
+    /// 网关配置不合法
+    pub const INVALID_GATEWAY_CONFIG: &str = "INVALID_GATEWAY_CONFIG";
+    /// 保存开发者设置失败
+    pub const DEVELOPER_SETTINGS_SAVE_FAILED: &str = "DEVELOPER_SETTINGS_SAVE_FAILED";
+    /// 保存旧版命令开关失败
+    pub const LEGACY_SETTINGS_SAVE_FAILED: &str = "LEGACY_SETTINGS_SAVE_FAILED";
+    /// 保存更新检查设置失败
+    pub const UPDATE_SETTINGS_SAVE_FAILED: &str = "UPDATE_SETTINGS_SAVE_FAILED";
+    /// 保存用量提醒设置失败
+    pub const USAGE_ALERT_SETTINGS_SAVE_FAILED: &str = "USAGE_ALERT_SETTINGS_SAVE_FAILED";
+    /// 保存应用设置失败
+    pub const SETTINGS_SAVE_FAILED: &str = "SETTINGS_SAVE_FAILED";
+    /// 向导已完成
+    pub const WIZARD_FINISHED: &str = "WIZARD_FINISHED";
+    /// 向导步骤顺序不对
+    pub const WIZARD_STEP_OUT_OF_ORDER: &str = "WIZARD_STEP_OUT_OF_ORDER";
+    /// 向导仍有未完成的步骤
+    pub const WIZARD_INCOMPLETE: &str = "WIZARD_INCOMPLETE";
+    /// 保存向导状态失败
+    pub const WIZARD_SAVE_FAILED: &str = "WIZARD_SAVE_FAILED";
+    /// .env 文件无法解析
+    pub const INVALID_ENV_FILE: &str = "INVALID_ENV_FILE";
+    /// 导入 .env 失败
+    pub const ENV_IMPORT_FAILED: &str = "ENV_IMPORT_FAILED";
+    /// 启动推送服务失败
+    pub const PUSH_SERVER_START_FAILED: &str = "PUSH_SERVER_START_FAILED";
+    /// 保存推送设置失败
+    pub const PUSH_SETTINGS_SAVE_FAILED: &str = "PUSH_SETTINGS_SAVE_FAILED";
+    /// 插件不存在或未声明 acceptsPush
+    pub const PLUGIN_PUSH_NOT_SUPPORTED: &str = "PLUGIN_PUSH_NOT_SUPPORTED";
+    /// 打开窗口失败
+    pub const WINDOW_OPEN_FAILED: &str = "WINDOW_OPEN_FAILED";
+
+    // ------------------------------------------------------------------------
+    // 签名公钥列表与价格表
+    // ------------------------------------------------------------------------
+
+    /// 获取签名公钥列表失败
+    pub const KEY_LIST_FETCH_FAILED: &str = "KEY_LIST_FETCH_FAILED";
+    /// 签名公钥列表格式不合法
+    pub const INVALID_KEY_LIST: &str = "INVALID_KEY_LIST";
+    /// 签名公钥列表版本比本地旧
+    pub const KEY_LIST_OUTDATED: &str = "KEY_LIST_OUTDATED";
+    /// 保存签名公钥列表失败
+    pub const KEY_LIST_SAVE_FAILED: &str = "KEY_LIST_SAVE_FAILED";
+    /// 获取价格表失败
+    pub const PRICING_FETCH_FAILED: &str = "PRICING_FETCH_FAILED";
+    /// 价格表格式不合法
+    pub const INVALID_PRICING_TABLE: &str = "INVALID_PRICING_TABLE";
+    /// 保存价格表失败
+    pub const PRICING_SAVE_FAILED: &str = "PRICING_SAVE_FAILED";
+
+    // ------------------------------------------------------------------------
+    // 应用更新
+    // ------------------------------------------------------------------------
+
+    /// 获取或解析发布信息失败
+    pub const APP_UPDATE_CHECK_FAILED: &str = "APP_UPDATE_CHECK_FAILED";
+    /// 没有可用的新版本
+    pub const APP_UPDATE_NOT_AVAILABLE: &str = "APP_UPDATE_NOT_AVAILABLE";
+    /// 正在下载或安装更新
+    pub const APP_UPDATE_BUSY: &str = "APP_UPDATE_BUSY";
+    /// 下载安装包失败
+    pub const APP_UPDATE_DOWNLOAD_FAILED: &str = "APP_UPDATE_DOWNLOAD_FAILED";
+    /// 更新尚未下载完成
+    pub const APP_UPDATE_NOT_READY: &str = "APP_UPDATE_NOT_READY";
+    /// 替换应用失败
+    pub const APP_UPDATE_INSTALL_FAILED: &str = "APP_UPDATE_INSTALL_FAILED";
+}
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+/// IPC 命令错误
+#[derive(Debug)]
+pub enum IpcError {
+    /// 命令自身的错误（参数校验、状态保存等）
+    Command {
+        code: &'static str,
+        message: String,
+        details: Option<serde_json::Value>,
+    },
+    /// 插件生命周期错误（无法归类时使用 fallback 错误码）
+    Lifecycle {
+        fallback: &'static str,
+        error: LifecycleError,
+    },
+    /// 安全校验错误
+    Security(SecurityError),
+    /// 沙盒运行时错误
+    Runtime(RuntimeError),
+}
+
+impl IpcError {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self::Command {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// 附加结构化详情（仅命令自身的错误）
+    pub fn with_details(mut self, value: serde_json::Value) -> Self {
+        if let Self::Command { details, .. } = &mut self {
+            *details = Some(value);
+        }
+        self
+    }
+
+    /// 包装生命周期错误，fallback 为该操作的失败码
+    pub fn lifecycle(fallback: &'static str, error: LifecycleError) -> Self {
+        Self::Lifecycle { fallback, error }
+    }
+
+    /// 稳定错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Command { code, .. } => code,
+            Self::Lifecycle { fallback, error } => match error {
+                LifecycleError::PluginNotFound(_) | LifecycleError::PluginDirNotFound(_) => codes::PLUGIN_NOT_FOUND,
+                LifecycleError::PluginDisabled(_) => codes::PLUGIN_DISABLED,
+                LifecycleError::ManifestNotFound(_) | LifecycleError::ManifestParse(_) => codes::MANIFEST_INVALID,
+                LifecycleError::IncompatibleApiVersion(_) => codes::INCOMPATIBLE_API_VERSION,
+                LifecycleError::ConfigInvalid(_) => codes::CONFIG_INVALID,
+                LifecycleError::SecretStore(_) => codes::SECRET_STORE_FAILED,
+                LifecycleError::ResourceLimitExceeded(_) => codes::RESOURCE_LIMIT_EXCEEDED,
+                LifecycleError::JsExecution(_) => codes::JS_EXECUTION_FAILED,
+                LifecycleError::PluginLoad(_) | LifecycleError::PluginUnload(_) | LifecycleError::Io(_) => fallback,
+            },
+            Self::Security(error) => match error {
+                SecurityError::SignatureInvalid { .. }
+                | SecurityError::SignatureFormatError { .. }
+                | SecurityError::PublicKeyNotFound { .. } => codes::SIGNATURE_INVALID,
+                SecurityError::HashMismatch { .. } | SecurityError::FileMissing { .. } => codes::INTEGRITY_FAILED,
+                SecurityError::PathTraversal { .. }
+                | SecurityError::SymlinkRejected { .. }
+                | SecurityError::FileTypeNotAllowed { .. } => codes::UNSAFE_ARCHIVE,
+                SecurityError::FileTooLarge { .. }
+                | SecurityError::TotalSizeTooLarge { .. }
+                | SecurityError::TooManyEntries { .. } => codes::RESOURCE_LIMIT_EXCEEDED,
+                SecurityError::ZipError(_) => codes::EXTRACT_FAILED,
+                SecurityError::Cancelled => codes::INSTALL_CANCELLED,
+                SecurityError::JsonError(_) | SecurityError::Base64Error(_) => codes::INVALID_JSON,
+                SecurityError::IoError(_) | SecurityError::AtomicReplaceFailed { .. } => codes::IO_ERROR,
+            },
+            Self::Runtime(error) => match error {
+                RuntimeError::RuntimeCreation(_) | RuntimeError::ContextCreation(_) => codes::RUNTIME_INIT_FAILED,
+                RuntimeError::ExecutionTimeout(_) => codes::EXECUTION_TIMEOUT,
+                RuntimeError::MemoryExceeded(_) => codes::MEMORY_EXCEEDED,
+                RuntimeError::Interrupted => codes::EXECUTION_INTERRUPTED,
+                RuntimeError::JsExecution(_) => codes::JS_EXECUTION_FAILED,
+            },
+        }
+    }
+
+    /// 用户可读消息
+    pub fn message(&self) -> String {
+        match self {
+            Self::Command { message, .. } => message.clone(),
+            Self::Lifecycle { error, .. } => error.to_string(),
+            Self::Security(error) => error.to_string(),
+            Self::Runtime(error) => error.to_string(),
+        }
+    }
+
+    /// 结构化详情
+    pub fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            Self::Command { details, .. } => details.clone(),
+            Self::Lifecycle {
+                error: LifecycleError::ConfigInvalid(field_errors),
+                ..
+            } => Some(serde_json::json!({ "fieldErrors": field_errors })),
+            Self::Lifecycle {
+                error: LifecycleError::IncompatibleApiVersion(incompatible),
+                ..
+            } => serde_json::to_value(incompatible).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for IpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for IpcError {}
+
+impl Serialize for IpcError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let details = self.details();
+        let mut state = serializer.serialize_struct("IpcError", if details.is_some() { 3 } else { 2 })?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.message())?;
+        if let Some(details) = details {
+            state.serialize_field("details", &details)?;
+        }
+        state.end()
+    }
+}
+
+impl From<IpcError> for AppError {
+    fn from(e: IpcError) -> Self {
+        let error = AppError::new(e.code(), e.message());
+        match e.details() {
+            Some(details) => error.with_details(details),
+            None => error,
+        }
+    }
+}
+
+impl From<SecurityError> for IpcError {
+    fn from(e: SecurityError) -> Self {
+        Self::Security(e)
+    }
+}
+
+impl From<RuntimeError> for IpcError {
+    fn from(e: RuntimeError) -> Self {
+        Self::Runtime(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::{FieldValidationError, ValidationErrorType};
+
+    #[test]
+    fn test_error_codes_and_envelope() {
+        let not_found = IpcError::lifecycle(codes::PLUGIN_ENABLE_FAILED, LifecycleError::PluginNotFound("demo".into()));
+        assert_eq!(not_found.code(), codes::PLUGIN_NOT_FOUND);
+        let load = IpcError::lifecycle(codes::PLUGIN_ENABLE_FAILED, LifecycleError::PluginLoad("boom".into()));
+        assert_eq!(load.code(), codes::PLUGIN_ENABLE_FAILED);
+        assert_eq!(
+            IpcError::from(SecurityError::PathTraversal { path: "../x".into() }).code(),
+            codes::UNSAFE_ARCHIVE
+        );
+        assert_eq!(IpcError::from(RuntimeError::Interrupted).code(), codes::EXECUTION_INTERRUPTED);
+
+        // 配置校验错误带字段详情，序列化结果与 AppError 一致
+        let invalid = IpcError::lifecycle(
+            codes::CONFIG_SET_FAILED,
+            LifecycleError::ConfigInvalid(vec![FieldValidationError {
+                field: "apiKey".into(),
+                message: "必填".into(),
+                error_type: ValidationErrorType::Required,
+            }]),
+        );
+        let envelope = serde_json::to_value(&invalid).unwrap();
+        assert_eq!(envelope["code"], "CONFIG_INVALID");
+        assert_eq!(envelope["details"]["fieldErrors"][0]["field"], "apiKey");
+        assert_eq!(envelope, serde_json::to_value(AppError::from(invalid)).unwrap());
+
+        let plain = serde_json::to_value(IpcError::new(codes::INVALID_ARGUMENT, "bad")).unwrap();
+        assert_eq!(plain, serde_json::json!({ "code": "INVALID_ARGUMENT", "message": "bad" }));
+    }
+
+    #[test]
+    fn test_codes_match_contract() {
+        // 每个错误码常量的值与名称一致，且都出现在 IpcErrorCode 联合类型中
+        let source = include_str!("error.rs");
+        let contract = include_str!("../../../contracts/types/ipc-commands.d.ts");
+        let start = contract.find("export type IpcErrorCode =").unwrap();
+        let union = &contract[start..start + contract[start..].find(';').unwrap()];
+
+        let mut count = 0;
+        for line in source.lines().map(str::trim) {
+            let Some(rest) = line.strip_prefix("pub const ") else {
+                continue;
+            };
+            let (name, value) = rest.split_once(": &str = ").unwrap();
+            assert_eq!(value, format!("\"{}\";", name));
+            assert!(union.contains(&format!("'{}'", name)), "IpcErrorCode 缺少 {}", name);
+            count += 1;
+        }
+        assert!(count > 80);
+    }
+}
@@ -1,7 +1,16 @@
 // Phase 7.3.5: IPC Events
 // 实现 contracts/types/ipc-events.d.ts 定义的 6 个事件
 
-use crate::plugin::types::{AppError, PluginData, PluginHealth, PluginInfo};
+use crate::commands::installer::{InstallProgress, InstallProgressCallback};
+use crate::plugin::budget::BudgetLine;
+use crate::plugin::bulk::BulkResult;
+use crate::plugin::legacy::DeprecatedCommand;
+use crate::plugin::monitoring::DataChange;
+use crate::plugin::plugin_logs::PluginLogEntry;
+use crate::plugin::types::{AppError, HealthTransition, PluginData, PluginHealth, PluginInfo, UpdateInfo};
+use crate::plugin::wizard::WizardProgress;
+use crate::plugin::InputRequest;
+use crate::reliability::NetworkStatus;
 use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 
@@ -13,10 +22,24 @@ use tauri::{AppHandle, Emitter};
 pub mod event_names {
     pub const PLUGIN_INSTALLED: &str = "ipc:plugin_installed";
     pub const PLUGIN_UNINSTALLED: &str = "ipc:plugin_uninstalled";
+    pub const PLUGIN_BULK_COMPLETED: &str = "ipc:plugin_bulk_completed";
     pub const PLUGIN_UPDATED: &str = "ipc:plugin_updated";
     pub const PLUGIN_DATA_UPDATED: &str = "ipc:plugin_data_updated";
+    pub const PLUGIN_DATA_CHANGED: &str = "ipc:plugin_data_changed";
     pub const PLUGIN_ERROR: &str = "ipc:plugin_error";
     pub const PLUGIN_HEALTH_CHANGED: &str = "ipc:plugin_health_changed";
+    pub const INPUT_REQUESTED: &str = "ipc:input_requested";
+    pub const NETWORK_STATUS_CHANGED: &str = "ipc:network_status_changed";
+    pub const UPDATE_AVAILABLE: &str = "update:available";
+    pub const PLUGIN_RELOADED: &str = "plugin:reloaded";
+    pub const INSTALL_PROGRESS: &str = "install:progress";
+    pub const BUDGET_WARNING: &str = "budget:warning";
+    pub const DEPRECATED_COMMAND: &str = "ipc:deprecated_command";
+    pub const PLUGIN_DATA_BROADCAST: &str = "plugin:data_updated";
+    pub const PLUGIN_LOG: &str = "plugin:log";
+    pub const WIZARD_FINISHED: &str = "wizard:finished";
+    pub const APP_UPDATE_STATUS: &str = "app_update:status";
+    pub const PLUGIN_HEALTH_TRANSITION: &str = "plugin:health_changed";
 }
 
 // ============================================================================
@@ -72,6 +95,11 @@ impl<'a> IpcEventEmitter<'a> {
         )
     }
 
+    /// 发送批量操作完成事件（汇总各插件结果）
+    pub fn emit_plugin_bulk_completed(&self, result: &BulkResult) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::PLUGIN_BULK_COMPLETED, result)
+    }
+
     /// 发送插件更新完成事件
     pub fn emit_plugin_updated(&self, info: &PluginInfo) -> Result<(), tauri::Error> {
         self.app.emit(event_names::PLUGIN_UPDATED, info)
@@ -88,6 +116,27 @@ impl<'a> IpcEventEmitter<'a> {
         )
     }
 
+    /// 广播插件数据快照（任何来源的数据更新，所有窗口据此实时更新）
+    pub fn emit_plugin_data_broadcast(&self, id: &str, data: &PluginData) -> Result<(), tauri::Error> {
+        self.app.emit(
+            event_names::PLUGIN_DATA_BROADCAST,
+            PluginDataUpdatedPayload {
+                id: id.to_string(),
+                data: data.clone(),
+            },
+        )
+    }
+
+    /// 推送插件 console 日志（日志查看器实时显示）
+    pub fn emit_plugin_log(&self, entry: &PluginLogEntry) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::PLUGIN_LOG, entry)
+    }
+
+    /// 发送插件刷新结果差异事件（百分比变化与越过的关键边界）
+    pub fn emit_plugin_data_changed(&self, change: &DataChange) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::PLUGIN_DATA_CHANGED, change)
+    }
+
     /// 发送插件错误事件
     pub fn emit_plugin_error(&self, id: &str, error: &AppError) -> Result<(), tauri::Error> {
         self.app.emit(
@@ -103,6 +152,51 @@ impl<'a> IpcEventEmitter<'a> {
     pub fn emit_plugin_health_changed(&self, health: &PluginHealth) -> Result<(), tauri::Error> {
         self.app.emit(event_names::PLUGIN_HEALTH_CHANGED, health)
     }
+
+    /// 发送插件健康状态转换事件（含变化前后状态和时间，供 UI 时间线使用）
+    pub fn emit_plugin_health_transition(&self, transition: &HealthTransition) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::PLUGIN_HEALTH_TRANSITION, transition)
+    }
+
+    /// 发送插件请求用户输入事件
+    pub fn emit_input_requested(&self, request: &InputRequest) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::INPUT_REQUESTED, request)
+    }
+
+    /// 发送网络状态变化事件（离线/恢复、排队刷新变化）
+    pub fn emit_network_status_changed(&self, status: &NetworkStatus) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::NETWORK_STATUS_CHANGED, status)
+    }
+
+    /// 发送插件更新可用事件（后台检查发现新版本）
+    pub fn emit_update_available(&self, updates: &[UpdateInfo]) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::UPDATE_AVAILABLE, updates)
+    }
+
+    /// 发送插件热重载完成事件（开发者插件文件变更后自动重载）
+    pub fn emit_plugin_reloaded(&self, info: &PluginInfo) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::PLUGIN_RELOADED, info)
+    }
+
+    /// 发送插件安装进度事件
+    pub fn emit_install_progress(&self, progress: &InstallProgress) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::INSTALL_PROGRESS, progress)
+    }
+
+    /// 发送预算提醒事件（预计本月花费超出预算）
+    pub fn emit_budget_warning(&self, warning: &BudgetLine) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::BUDGET_WARNING, warning)
+    }
+
+    /// 发送旧版命令弃用事件
+    pub fn emit_deprecated_command(&self, command: &DeprecatedCommand) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::DEPRECATED_COMMAND, command)
+    }
+
+    /// 发送首次设置完成事件（各窗口据此应用选择的刷新间隔）
+    pub fn emit_wizard_finished(&self, progress: &WizardProgress) -> Result<(), tauri::Error> {
+        self.app.emit(event_names::WIZARD_FINISHED, progress)
+    }
 }
 
 // ============================================================================
@@ -114,6 +208,16 @@ pub fn emitter(app: &AppHandle) -> IpcEventEmitter<'_> {
     IpcEventEmitter::new(app)
 }
 
+/// 将安装进度转发为 install:progress 事件的回调
+pub fn install_progress_reporter(app: &AppHandle) -> InstallProgressCallback {
+    let app = app.clone();
+    std::sync::Arc::new(move |progress: &InstallProgress| {
+        if let Err(e) = emitter(&app).emit_install_progress(progress) {
+            log::warn!("发送安装进度事件失败: plugin={}, emit_error={}", progress.plugin_id, e);
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,7 +228,10 @@ mod tests {
         assert_eq!(event_names::PLUGIN_UNINSTALLED, "ipc:plugin_uninstalled");
         assert_eq!(event_names::PLUGIN_UPDATED, "ipc:plugin_updated");
         assert_eq!(event_names::PLUGIN_DATA_UPDATED, "ipc:plugin_data_updated");
+        assert_eq!(event_names::PLUGIN_DATA_CHANGED, "ipc:plugin_data_changed");
         assert_eq!(event_names::PLUGIN_ERROR, "ipc:plugin_error");
         assert_eq!(event_names::PLUGIN_HEALTH_CHANGED, "ipc:plugin_health_changed");
+        assert_eq!(event_names::INPUT_REQUESTED, "ipc:input_requested");
+        assert_eq!(event_names::NETWORK_STATUS_CHANGED, "ipc:network_status_changed");
     }
 }
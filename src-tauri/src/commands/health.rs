@@ -0,0 +1,51 @@
+// Phase 6.15: 健康状态变化推送
+// 插件健康状态在 Healthy / Degraded / Unhealthy / Stale（网络离线）之间变化时由 PluginManager 发布到通道，
+// 宿主逐个发送 ipc:plugin_health_changed（健康快照）和 plugin:health_changed（含变化时间的状态转换），
+// 并按所有已启用插件中最差的健康状态更新托盘图标，前端无需轮询 get_all_health。
+// 插件恢复 Healthy 时，将其未恢复的健康类告警标记为已恢复（Phase 6.16）。
+
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager};
+
+use crate::commands::alerts::AlertManagerState;
+use crate::commands::events::emitter;
+use crate::plugin::{AlertType, HealthStatus, PluginManager};
+use crate::tray::{tray_status_for_health, update_tray_status};
+
+/// 插件恢复健康时视为已恢复的告警类型
+const HEALTH_ALERT_TYPES: [AlertType; 3] = [
+    AlertType::ConsecutiveFailures,
+    AlertType::HighLatency,
+    AlertType::LowSuccessRate,
+];
+
+/// 启动健康状态变化推送：消费 PluginManager 的健康状态变化通道
+pub fn spawn_health_change_broadcaster(app: AppHandle, manager: Arc<PluginManager>) {
+    let Some(mut transitions) = manager.take_health_change_receiver() else {
+        log::warn!("健康状态变化推送已启动，跳过");
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        while let Some(transition) = transitions.recv().await {
+            let id = &transition.plugin_id;
+            if let Err(e) = emitter(&app).emit_plugin_health_changed(&transition.health) {
+                log::warn!("发送健康状态变化事件失败: plugin={}, emit_error={}", id, e);
+            }
+            if let Err(e) = emitter(&app).emit_plugin_health_transition(&transition) {
+                log::warn!("发送健康状态转换事件失败: plugin={}, emit_error={}", id, e);
+            }
+
+            if transition.current == HealthStatus::Healthy {
+                if let Some(alerts) = app.try_state::<AlertManagerState>() {
+                    alerts.0.resolve(id, &HEALTH_ALERT_TYPES);
+                }
+            }
+
+            let status = tray_status_for_health(manager.worst_health().await);
+            if let Err(e) = update_tray_status(&app, status) {
+                log::warn!("按健康状态更新托盘失败: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,109 @@
+// Phase 5A.8: 开发者插件热重载
+// 监听 dev-plugins 目录，manifest 或入口文件变更后自动重载插件并通知前端
+//
+// 设计要点:
+// 1. 仅开发者插件参与热重载，已安装插件的文件变更由完整性自检处理
+// 2. 按插件防抖，合并编辑器一次保存产生的多个文件事件
+// 3. 重载成功发送 plugin:reloaded，失败发送 plugin_error 并弹出系统通知
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::error::codes;
+use crate::commands::events::emitter;
+use crate::plugin::types::AppError;
+use crate::plugin::{HotReloadEvent, HotReloadManager, PluginManager, ReloadDebouncer};
+
+/// 文件变更防抖间隔
+const RELOAD_DEBOUNCE_MS: u64 = 500;
+
+/// 重载失败时通知前端和用户
+fn notify_reload_failed(app: &AppHandle, plugin_id: &str, message: &str) {
+    let error = AppError::new(codes::PLUGIN_RELOAD_FAILED, format!("热重载失败: {}", message));
+    if let Err(e) = emitter(app).emit_plugin_error(plugin_id, &error) {
+        log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", plugin_id, e);
+    }
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(format!("插件 {} 热重载失败", plugin_id))
+        .body(message)
+        .show()
+    {
+        log::warn!("发送热重载失败通知失败: {}", e);
+    }
+}
+
+/// 重载单个开发者插件
+async fn reload_dev_plugin(app: &AppHandle, manager: &PluginManager, plugin_id: &str) {
+    match manager.reload_plugin(plugin_id).await {
+        Ok(info) => {
+            log::info!("[{}] 开发者插件已热重载", plugin_id);
+            if let Err(e) = emitter(app).emit_plugin_reloaded(&info) {
+                log::warn!("发送插件重载事件失败: plugin={}, emit_error={}", plugin_id, e);
+            }
+        }
+        Err(e) => {
+            log::warn!("[{}] 开发者插件热重载失败: {}", plugin_id, e);
+            notify_reload_failed(app, plugin_id, &e.to_string());
+        }
+    }
+}
+
+/// 启动开发者插件热重载
+///
+/// 开发者模式可在运行期间开启，因此始终监听 dev-plugins 目录；
+/// 未加载的插件（开发者模式关闭）的变更会被忽略。
+pub fn spawn_dev_hot_reload(app: AppHandle, manager: Arc<PluginManager>) {
+    let dev_dir = manager.dev_plugins_dir();
+    if let Err(e) = std::fs::create_dir_all(&dev_dir) {
+        log::warn!("创建开发者插件目录失败，热重载未启动: {:?}, {}", dev_dir, e);
+        return;
+    }
+
+    let hot_reload = HotReloadManager::new(dev_dir).with_debounce(RELOAD_DEBOUNCE_MS);
+    let mut watcher = match hot_reload.start() {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            log::warn!("启动开发者插件监听失败: {}", e);
+            return;
+        }
+    };
+
+    tauri::async_runtime::spawn(async move {
+        let mut debouncer = ReloadDebouncer::new(hot_reload.debounce());
+        loop {
+            // 没有待重载插件时仅等待文件事件
+            let deadline = debouncer
+                .next_deadline()
+                .unwrap_or_else(|| Instant::now() + Duration::from_secs(3600));
+
+            tokio::select! {
+                event = watcher.recv() => match event {
+                    Some(HotReloadEvent::Modified { path, .. }) => {
+                        if let Some(plugin_id) = manager.dev_plugin_for_change(&path).await {
+                            log::debug!("[{}] 检测到文件变更: {:?}", plugin_id, path);
+                            debouncer.touch(&plugin_id, Instant::now());
+                        }
+                    }
+                    Some(HotReloadEvent::Error { message }) => {
+                        log::warn!("开发者插件监听错误: {}", message);
+                    }
+                    Some(_) => {}
+                    None => {
+                        log::warn!("开发者插件监听已停止");
+                        break;
+                    }
+                },
+                _ = tokio::time::sleep_until(deadline.into()) => {
+                    for plugin_id in debouncer.take_due(Instant::now()) {
+                        reload_dev_plugin(&app, &manager, &plugin_id).await;
+                    }
+                }
+            }
+        }
+    });
+}
@@ -1,12 +1,22 @@
 // Phase 5A.2: 插件安装器
 // 实现从 URL 或 registry 安装插件的完整流程
 
+use crate::commands::error::codes;
+use crate::plugin::api_version::{negotiate, IncompatiblePlugin};
 use crate::plugin::types::{AppError, PluginInfo, Result as IpcResult};
 use crate::plugin::PluginManager;
-use crate::security::{verify_manifest_signature, verify_manifest_files, SecureExtractor};
+use crate::security::backup::{BackupStore, PluginBackup};
+use crate::security::policy::PolicyDenied;
+use crate::security::{
+    verify_manifest_files, ExtractProgress, ExtractReport, HashStats, SecureExtractor,
+    SecurityError,
+};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tempfile::TempDir;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
@@ -53,24 +63,193 @@ pub enum InstallError {
 
     #[error("无效的 source: {0}")]
     InvalidSource(String),
+
+    #[error("{0}")]
+    PolicyDenied(#[from] PolicyDenied),
+
+    #[error("安装已取消")]
+    Cancelled,
+
+    #[error("没有可恢复的备份: {0}")]
+    BackupNotFound(String),
+
+    #[error("{0}")]
+    IncompatibleApiVersion(IncompatiblePlugin),
+}
+
+impl From<SecurityError> for InstallError {
+    fn from(e: SecurityError) -> Self {
+        match e {
+            SecurityError::Cancelled => InstallError::Cancelled,
+            other => InstallError::Extract(other.to_string()),
+        }
+    }
 }
 
 impl From<InstallError> for AppError {
     fn from(e: InstallError) -> Self {
         let code = match &e {
-            InstallError::Download(_) => "DOWNLOAD_FAILED",
-            InstallError::Extract(_) => "EXTRACT_FAILED",
-            InstallError::ManifestParse(_) => "MANIFEST_PARSE_FAILED",
-            InstallError::SignatureInvalid(_) => "SIGNATURE_INVALID",
-            InstallError::IntegrityFailed(_) => "INTEGRITY_FAILED",
-            InstallError::Install(_) => "INSTALL_FAILED",
-            InstallError::Io(_) => "IO_ERROR",
-            InstallError::InvalidSource(_) => "INVALID_SOURCE",
+            InstallError::Download(_) => codes::DOWNLOAD_FAILED,
+            InstallError::Extract(_) => codes::EXTRACT_FAILED,
+            InstallError::ManifestParse(_) => codes::MANIFEST_PARSE_FAILED,
+            InstallError::SignatureInvalid(_) => codes::SIGNATURE_INVALID,
+            InstallError::IntegrityFailed(_) => codes::INTEGRITY_FAILED,
+            InstallError::Install(_) => codes::INSTALL_FAILED,
+            InstallError::Io(_) => codes::IO_ERROR,
+            InstallError::InvalidSource(_) => codes::INVALID_SOURCE,
+            InstallError::PolicyDenied(_) => codes::POLICY_DENIED,
+            InstallError::Cancelled => codes::INSTALL_CANCELLED,
+            InstallError::BackupNotFound(_) => codes::BACKUP_NOT_FOUND,
+            InstallError::IncompatibleApiVersion(_) => codes::INCOMPATIBLE_API_VERSION,
         };
-        AppError::new(code, e.to_string())
+        let error = AppError::new(code, e.to_string());
+        match &e {
+            InstallError::IncompatibleApiVersion(incompatible) => match serde_json::to_value(incompatible) {
+                Ok(details) => error.with_details(details),
+                Err(_) => error,
+            },
+            _ => error,
+        }
     }
 }
 
+// ============================================================================
+// 安装进度
+// ============================================================================
+
+/// 安装阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallStage {
+    /// 正在解压插件包
+    Extracting,
+    /// 文件完整性校验完成
+    Verified,
+}
+
+/// 安装进度（install:progress 事件）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallProgress {
+    pub plugin_id: String,
+    pub stage: InstallStage,
+    /// 已计算哈希的文件数（解压阶段为已处理的条目数）
+    pub files: usize,
+    /// 解压阶段的条目总数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_files: Option<usize>,
+    /// 已计算哈希的字节数（解压阶段为已写出的字节数）
+    pub bytes: u64,
+    /// 哈希计算耗时 (ms)（解压阶段为已解压耗时）
+    pub elapsed_ms: u64,
+    /// 校验吞吐量 (MB/s)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub throughput_mb_per_sec: Option<f64>,
+}
+
+impl InstallProgress {
+    /// 解压进度（插件 ID 尚未解析，使用安装标识）
+    pub fn extracting(install_key: &str, progress: &ExtractProgress, started: Instant) -> Self {
+        Self {
+            plugin_id: install_key.to_string(),
+            stage: InstallStage::Extracting,
+            files: progress.entries_processed,
+            total_files: Some(progress.total_entries),
+            bytes: progress.bytes_written,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            throughput_mb_per_sec: None,
+        }
+    }
+
+    /// 完整性校验完成
+    pub fn verified(plugin_id: &str, stats: &HashStats) -> Self {
+        Self {
+            plugin_id: plugin_id.to_string(),
+            stage: InstallStage::Verified,
+            files: stats.files,
+            total_files: None,
+            bytes: stats.bytes,
+            elapsed_ms: stats.elapsed.as_millis() as u64,
+            throughput_mb_per_sec: stats.throughput_mb_per_sec(),
+        }
+    }
+}
+
+/// 安装进度回调
+pub type InstallProgressCallback = Arc<dyn Fn(&InstallProgress) + Send + Sync>;
+
+// ============================================================================
+// 安装取消
+// ============================================================================
+
+/// 单次安装的取消标记
+///
+/// `key` 为前端发起安装时使用的标识（source / 文件路径 / registry ID），
+/// 同时作为解压阶段 install:progress 事件的 pluginId。
+#[derive(Debug, Clone)]
+pub struct InstallCancelToken {
+    pub key: String,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl InstallCancelToken {
+    /// 是否已请求取消
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// 进行中的安装（按安装标识索引），供 plugin_install_cancel 取消
+#[derive(Debug, Default)]
+pub struct InstallCancellations {
+    active: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl InstallCancellations {
+    /// 登记一次安装（同一标识重复安装时以最新一次为准）
+    pub fn begin(&self, key: &str) -> InstallCancelToken {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.active
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string(), cancelled.clone());
+        InstallCancelToken {
+            key: key.to_string(),
+            cancelled,
+        }
+    }
+
+    /// 安装结束后移除登记
+    pub fn finish(&self, token: &InstallCancelToken) {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        if active
+            .get(&token.key)
+            .is_some_and(|flag| Arc::ptr_eq(flag, &token.cancelled))
+        {
+            active.remove(&token.key);
+        }
+    }
+
+    /// 请求取消进行中的安装，返回是否找到该安装
+    pub fn cancel(&self, key: &str) -> bool {
+        match self.active.lock().unwrap_or_else(|e| e.into_inner()).get(key) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 安装取消状态
+pub struct InstallCancelState(pub Arc<InstallCancellations>);
+
+/// 创建安装取消状态
+pub fn create_install_cancellations() -> InstallCancelState {
+    InstallCancelState(Arc::new(InstallCancellations::default()))
+}
+
 // ============================================================================
 // 下载内容类型
 // ============================================================================
@@ -90,6 +269,8 @@ enum DownloadedContent {
 pub struct PluginInstaller {
     plugin_manager: Arc<PluginManager>,
     http_client: reqwest::Client,
+    progress: Option<InstallProgressCallback>,
+    cancel: Option<InstallCancelToken>,
 }
 
 impl PluginInstaller {
@@ -102,9 +283,69 @@ impl PluginInstaller {
         Self {
             plugin_manager,
             http_client,
+            progress: None,
+            cancel: None,
+        }
+    }
+
+    /// 设置安装进度回调
+    pub fn with_progress(mut self, progress: Option<InstallProgressCallback>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// 设置取消标记
+    pub fn with_cancel(mut self, cancel: Option<InstallCancelToken>) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// 报告安装进度
+    fn report_progress(&self, progress: InstallProgress) {
+        if let Some(callback) = &self.progress {
+            callback(&progress);
+        }
+    }
+
+    /// 已请求取消时中止安装
+    fn check_cancelled(&self) -> Result<(), InstallError> {
+        match &self.cancel {
+            Some(token) if token.is_cancelled() => Err(InstallError::Cancelled),
+            _ => Ok(()),
         }
     }
 
+    /// 在阻塞线程中安全解压，解压进度转发为 install:progress（extracting 阶段）
+    ///
+    /// 取消时解压器删除已写出的临时文件，目标目录不会生成。
+    async fn extract_archive(
+        &self,
+        zip_path: PathBuf,
+        target: PathBuf,
+        fallback_key: &str,
+    ) -> Result<ExtractReport, InstallError> {
+        let mut extractor = SecureExtractor::new();
+        if let Some(callback) = self.progress.clone() {
+            let key = self
+                .cancel
+                .as_ref()
+                .map_or_else(|| fallback_key.to_string(), |token| token.key.clone());
+            let started = Instant::now();
+            extractor = extractor.with_progress(Some(Arc::new(move |progress: &ExtractProgress| {
+                callback(&InstallProgress::extracting(&key, progress, started));
+            })));
+        }
+        if let Some(token) = &self.cancel {
+            extractor = extractor.with_cancel(token.cancelled.clone());
+        }
+
+        let report = tokio::task::spawn_blocking(move || extractor.extract(&zip_path, &target))
+            .await
+            .map_err(|e| InstallError::Extract(format!("解压任务失败: {}", e)))??;
+        self.check_cancelled()?;
+        Ok(report)
+    }
+
     /// 安装插件
     ///
     /// # 参数
@@ -122,8 +363,9 @@ impl PluginInstaller {
     ) -> Result<PluginInfo, InstallError> {
         log::info!("开始安装插件: source={}, skip_signature={}", source, skip_signature);
 
-        // 1. 解析 source，获取下载 URL
-        let download_url = self.resolve_source(source, registry_url).await?;
+        // 1. 解析 source，获取下载 URL（管理员策略可固定 registry 地址）
+        let registry_url = self.plugin_manager.admin_policy().registry_url(registry_url)?;
+        let download_url = self.resolve_source(source, registry_url.as_deref()).await?;
         log::debug!("解析后的下载 URL: {}", download_url);
 
         // 2. 创建临时目录
@@ -134,7 +376,7 @@ impl PluginInstaller {
         // 3. 下载并根据内容类型处理
         let download_result = self.download_and_detect(&download_url).await;
 
-        match download_result {
+        let report = match download_result {
             Ok(DownloadedContent::Zip(bytes)) => {
                 // ZIP 模式：解压
                 let zip_path = temp_dir.path().join("plugin.zip");
@@ -143,11 +385,10 @@ impl PluginInstaller {
                 file.flush().await?;
                 log::debug!("ZIP 下载完成: {:?}", zip_path);
 
-                let extractor = SecureExtractor::new();
-                extractor
-                    .extract(&zip_path, &extract_dir)
-                    .map_err(|e| InstallError::Extract(e.to_string()))?;
+                self.check_cancelled()?;
+                let report = self.extract_archive(zip_path, extract_dir.clone(), source).await?;
                 log::debug!("解压完成: {:?}", extract_dir);
+                Some(report)
             }
             Ok(DownloadedContent::SingleFile { name, bytes }) => {
                 // 单文件模式：直接保存
@@ -156,16 +397,91 @@ impl PluginInstaller {
                 file.write_all(&bytes).await?;
                 file.flush().await?;
                 log::debug!("单文件下载完成: {:?}", file_path);
+                None
             }
             Err(_) if self.is_github_raw_url(&download_url) => {
                 // 404 且是 GitHub raw URL，尝试作为目录处理
                 log::info!("下载失败，尝试作为 GitHub 目录处理");
                 self.download_github_directory(&download_url, &extract_dir).await?;
                 log::debug!("GitHub 目录下载完成: {:?}", extract_dir);
+                None
             }
             Err(e) => return Err(e),
+        };
+
+        self.check_cancelled()?;
+        self.install_extracted(&extract_dir, skip_signature, report).await
+    }
+
+    /// 从本地 ZIP 文件安装插件（离线安装、开发分发、拖拽安装）
+    ///
+    /// 解压到与插件目录同一文件系统的暂存目录，校验通过后通过 rename 原子放入插件目录。
+    /// 支持 ZIP 根目录直接包含 manifest.json，或只包含一个插件子目录。
+    pub async fn install_from_file(
+        &self,
+        path: &Path,
+        skip_signature: bool,
+    ) -> Result<PluginInfo, InstallError> {
+        log::info!("开始从本地文件安装插件: path={:?}, skip_signature={}", path, skip_signature);
+
+        let is_zip = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+        if !is_zip {
+            return Err(InstallError::InvalidSource(format!(
+                "仅支持 .zip 插件包: {}",
+                path.display()
+            )));
+        }
+        if !fs::metadata(path).await.map(|m| m.is_file()).unwrap_or(false) {
+            return Err(InstallError::InvalidSource(format!("文件不存在: {}", path.display())));
+        }
+
+        // 暂存目录与插件目录同级，保证最终 rename 不跨文件系统
+        let plugins_dir = self.plugin_manager.plugins_dir();
+        let staging_root = plugins_dir.parent().unwrap_or(plugins_dir);
+        fs::create_dir_all(staging_root).await?;
+        let staging = TempDir::new_in(staging_root)?;
+        let extract_dir = staging.path().join("extracted");
+
+        let report = self
+            .extract_archive(path.to_path_buf(), extract_dir.clone(), &path.to_string_lossy())
+            .await?;
+        log::debug!("解压完成: {:?}", extract_dir);
+
+        let plugin_root = Self::locate_plugin_root(&extract_dir).await?;
+        let report = report.rebase(plugin_root.strip_prefix(&extract_dir).unwrap_or(Path::new("")));
+        self.install_extracted(&plugin_root, skip_signature, Some(report)).await
+    }
+
+    /// 查找解压目录中的插件根目录（包含 manifest.json）
+    async fn locate_plugin_root(extract_dir: &Path) -> Result<PathBuf, InstallError> {
+        if fs::try_exists(extract_dir.join("manifest.json")).await? {
+            return Ok(extract_dir.to_path_buf());
+        }
+
+        let mut dirs = Vec::new();
+        let mut entries = fs::read_dir(extract_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_dir() {
+                dirs.push(entry.path());
+            }
+        }
+        match dirs.as_slice() {
+            [dir] if fs::try_exists(dir.join("manifest.json")).await? => Ok(dir.clone()),
+            _ => Err(InstallError::ManifestParse("压缩包中未找到 manifest.json".to_string())),
         }
+    }
 
+    /// 校验已解压的插件并放入插件目录
+    ///
+    /// `report` 为 ZIP 解压时计算的文件哈希，命中的文件校验时不再读盘。
+    async fn install_extracted(
+        &self,
+        extract_dir: &Path,
+        skip_signature: bool,
+        report: Option<ExtractReport>,
+    ) -> Result<PluginInfo, InstallError> {
         // 5. 解析 manifest.json
         let manifest_path = extract_dir.join("manifest.json");
         let manifest_content = fs::read_to_string(&manifest_path)
@@ -184,19 +500,44 @@ impl PluginInstaller {
 
         log::info!("解析 manifest 成功: id={}", plugin_id);
 
+        // API 版本超出支持范围的插件不安装（旧格式在加载时迁移，这里只做检查）
+        negotiate(&mut manifest.clone()).map_err(InstallError::IncompatibleApiVersion)?;
+
+        // 管理员策略：禁止安装新插件时只允许更新已安装的插件，要求签名时不允许跳过
+        let is_update = self.plugin_manager.plugins_dir().join(&plugin_id).exists();
+        self.plugin_manager
+            .admin_policy()
+            .check_install(&plugin_id, is_update, skip_signature)?;
+
         // 7. 签名验证（如果不跳过）
         if !skip_signature {
-            verify_manifest_signature(&manifest)
+            self.plugin_manager
+                .publisher_keys()
+                .verifier()
+                .verify_manifest(&manifest)
                 .map_err(|e| InstallError::SignatureInvalid(e.to_string()))?;
             log::info!("签名验证通过: {}", plugin_id);
         } else {
             log::warn!("跳过签名验证: {}", plugin_id);
         }
 
-        // 8. 完整性验证
-        verify_manifest_files(&manifest, &extract_dir)
+        // 8. 完整性验证（解压时已计算的哈希直接比较）
+        let ExtractReport {
+            file_hashes,
+            hash_stats: mut stats,
+        } = report.unwrap_or_default();
+        let disk_stats = verify_manifest_files(&manifest, extract_dir, &file_hashes)
             .map_err(|e| InstallError::IntegrityFailed(e.to_string()))?;
-        log::info!("完整性验证通过: {}", plugin_id);
+        stats.merge(disk_stats);
+        let progress = InstallProgress::verified(&plugin_id, &stats);
+        log::info!(
+            "完整性验证通过: {} ({} 个文件, {} 字节, {:.1} MB/s)",
+            plugin_id,
+            progress.files,
+            progress.bytes,
+            progress.throughput_mb_per_sec.unwrap_or_default()
+        );
+        self.report_progress(progress);
 
         // 9. 保存旧插件的配置和启用状态（用于更新时保留）
         let saved_state = self.save_plugin_state(&plugin_id).await;
@@ -207,18 +548,18 @@ impl PluginInstaller {
         // 10. 移动到插件目录
         let target_dir = self.plugin_manager.plugins_dir().join(&plugin_id);
 
-        // 如果目录已存在，先备份
+        // 如果目录已存在，先备份旧版本到备份存储
         if target_dir.exists() {
-            let backup_dir = self.plugin_manager.plugins_dir().join(format!("{}.backup", plugin_id));
-            if backup_dir.exists() {
-                fs::remove_dir_all(&backup_dir).await?;
-            }
-            fs::rename(&target_dir, &backup_dir).await?;
-            log::info!("已备份旧版本: {:?}", backup_dir);
+            let backup = self
+                .backups()
+                .backup(&target_dir)
+                .map_err(|e| InstallError::Install(format!("备份旧版本失败: {}", e)))?;
+            log::info!("已备份旧版本: {} v{}", plugin_id, backup.version);
+            fs::remove_dir_all(&target_dir).await?;
         }
 
         // 移动解压目录到插件目录
-        self.move_dir(&extract_dir, &target_dir).await?;
+        self.move_dir(extract_dir, &target_dir).await?;
         log::info!("插件已安装到: {:?}", target_dir);
 
         // 11. 重新加载插件
@@ -237,18 +578,69 @@ impl PluginInstaller {
         // 13. 查找并返回新安装的插件信息
         let plugin_info = plugins
             .into_iter()
-            .find(|p| p.id == plugin_id)
+            .find(|p| p.instance_id == plugin_id)
             .ok_or_else(|| InstallError::Install(format!("插件加载后未找到: {}", plugin_id)))?;
 
         log::info!("插件安装成功: {} v{}", plugin_info.id, plugin_info.version);
         Ok(plugin_info)
     }
 
+    /// 插件版本备份存储
+    fn backups(&self) -> BackupStore {
+        BackupStore::new(BackupStore::dir_path(self.plugin_manager.plugins_dir()))
+    }
+
+    /// 列出插件的版本备份（`plugin_id` 为空时列出所有插件）
+    pub fn list_backups(&self, plugin_id: Option<&str>) -> Result<Vec<PluginBackup>, InstallError> {
+        let store = self.backups();
+        let backups = match plugin_id {
+            Some(id) => store.list(id),
+            None => store.list_all(),
+        };
+        backups.map_err(|e| InstallError::Install(format!("读取备份失败: {}", e)))
+    }
+
+    /// 回滚到备份的版本（`version` 为空时回滚到最近的备份，保留当前配置和启用状态）
+    pub async fn rollback_to(&self, plugin_id: &str, version: Option<&str>) -> Result<PluginInfo, InstallError> {
+        let store = self.backups();
+        let backup = store
+            .find(plugin_id, version)
+            .map_err(|e| InstallError::Install(format!("读取备份失败: {}", e)))?
+            .ok_or_else(|| {
+                InstallError::BackupNotFound(match version {
+                    Some(version) => format!("{} v{}", plugin_id, version),
+                    None => plugin_id.to_string(),
+                })
+            })?;
+
+        let saved_state = self.save_plugin_state(plugin_id).await;
+        let target_dir = self.plugin_manager.plugins_dir().join(plugin_id);
+        store.restore(&backup, &target_dir).map_err(|e| match e {
+            SecurityError::HashMismatch { .. } => InstallError::IntegrityFailed(e.to_string()),
+            other => InstallError::Install(format!("恢复备份失败: {}", other)),
+        })?;
+        log::info!("已回滚插件 {} 到 v{}", plugin_id, backup.version);
+
+        let plugins = self
+            .plugin_manager
+            .discover_and_load()
+            .await
+            .map_err(|e| InstallError::Install(format!("加载插件失败: {}", e)))?;
+        if let Some(state) = saved_state {
+            self.restore_plugin_state(plugin_id, state).await;
+        }
+
+        plugins
+            .into_iter()
+            .find(|p| p.instance_id == plugin_id)
+            .ok_or_else(|| InstallError::Install(format!("插件恢复后未找到: {}", plugin_id)))
+    }
+
     /// 保存插件的配置和启用状态
     async fn save_plugin_state(&self, plugin_id: &str) -> Option<SavedPluginState> {
         // 获取插件列表，检查插件是否存在
         let plugins = self.plugin_manager.list_plugins().await;
-        let plugin = plugins.iter().find(|p| p.id == plugin_id)?;
+        let plugin = plugins.iter().find(|p| p.instance_id == plugin_id)?;
 
         // 获取配置
         let config = self.plugin_manager.get_plugin_config(plugin_id).await.unwrap_or_default();
@@ -555,11 +947,39 @@ pub async fn install_plugin(
     source: &str,
     skip_signature: bool,
     registry_url: Option<&str>,
+    progress: Option<InstallProgressCallback>,
+    cancel: Option<InstallCancelToken>,
 ) -> Result<PluginInfo, InstallError> {
-    let installer = PluginInstaller::new(plugin_manager);
+    let installer = PluginInstaller::new(plugin_manager)
+        .with_progress(progress)
+        .with_cancel(cancel);
     installer.install(source, skip_signature, registry_url).await
 }
 
+/// 从本地 ZIP 文件安装插件（便捷函数）
+pub async fn install_plugin_from_file(
+    plugin_manager: Arc<PluginManager>,
+    path: &Path,
+    skip_signature: bool,
+    progress: Option<InstallProgressCallback>,
+    cancel: Option<InstallCancelToken>,
+) -> Result<PluginInfo, InstallError> {
+    PluginInstaller::new(plugin_manager)
+        .with_progress(progress)
+        .with_cancel(cancel)
+        .install_from_file(path, skip_signature)
+        .await
+}
+
+/// 回滚插件到备份的版本（便捷函数）
+pub async fn rollback_plugin(
+    plugin_manager: Arc<PluginManager>,
+    plugin_id: &str,
+    version: Option<&str>,
+) -> Result<PluginInfo, InstallError> {
+    PluginInstaller::new(plugin_manager).rollback_to(plugin_id, version).await
+}
+
 // ============================================================================
 // 测试
 // ============================================================================
@@ -581,4 +1001,83 @@ mod tests {
         let app_error: AppError = error.into();
         assert_eq!(app_error.code, "INVALID_SOURCE");
     }
+
+    fn write_zip(path: &Path, files: &[(&str, &str)]) {
+        use std::io::Write;
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        for (name, content) in files {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_install_from_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let manager = Arc::new(PluginManager::new(crate::plugin::PluginDiscovery::new(
+            plugins_dir.clone(),
+        )));
+        let installer = PluginInstaller::new(manager.clone());
+
+        // 插件包含在子目录中
+        let zip_path = temp_dir.path().join("local-plugin.zip");
+        let manifest = r#"{
+            "id": "local-plugin",
+            "name": "Local Plugin",
+            "version": "1.0.0",
+            "apiVersion": "1.0",
+            "pluginType": "data",
+            "entry": "plugin.js"
+        }"#;
+        write_zip(
+            &zip_path,
+            &[("local-plugin/manifest.json", manifest), ("local-plugin/plugin.js", "// local")],
+        );
+
+        // 未签名的插件包默认拒绝，且不留下任何文件
+        let err = installer.install_from_file(&zip_path, false).await.unwrap_err();
+        assert_eq!(AppError::from(err).code, "SIGNATURE_INVALID");
+        assert!(!plugins_dir.join("local-plugin").exists());
+
+        let progress = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = progress.clone();
+        let installer = installer.with_progress(Some(Arc::new(move |p: &InstallProgress| {
+            sink.lock().unwrap().push(p.clone());
+        })));
+        let info = installer.install_from_file(&zip_path, true).await.unwrap();
+        assert_eq!(info.id, "local-plugin");
+
+        // 解压阶段逐条目报告进度，解压时已计算两个文件的哈希，校验阶段报告吞吐量
+        let progress = progress.lock().unwrap();
+        assert_eq!(progress.len(), 3);
+        assert_eq!(progress[1].stage, InstallStage::Extracting);
+        assert_eq!((progress[1].files, progress[1].total_files), (2, Some(2)));
+        assert_eq!(progress[2].stage, InstallStage::Verified);
+        assert_eq!(progress[2].files, 2);
+        assert!(plugins_dir.join("local-plugin/plugin.js").exists());
+        assert!(manager.get_plugin("local-plugin").await.is_some());
+
+        // 已取消的安装在解压阶段中止
+        let cancellations = InstallCancellations::default();
+        let token = cancellations.begin("local-plugin.zip");
+        assert!(cancellations.cancel("local-plugin.zip"));
+        let err = PluginInstaller::new(manager.clone())
+            .with_cancel(Some(token.clone()))
+            .install_from_file(&zip_path, true)
+            .await
+            .unwrap_err();
+        assert_eq!(AppError::from(err).code, "INSTALL_CANCELLED");
+        cancellations.finish(&token);
+        assert!(!cancellations.cancel("local-plugin.zip"));
+
+        let err = installer
+            .install_from_file(&temp_dir.path().join("plugin.tar.gz"), true)
+            .await
+            .unwrap_err();
+        assert_eq!(AppError::from(err).code, "INVALID_SOURCE");
+    }
 }
@@ -0,0 +1,53 @@
+// Phase 5A.5: 插件完整性自检
+// 定期将已安装插件的磁盘文件与 manifest 哈希比对，发现篡改时隔离插件并通知前端
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+use crate::commands::error::codes;
+use crate::commands::events::emitter;
+use crate::plugin::types::{AppError, IntegrityReport};
+use crate::plugin::PluginManager;
+
+/// 首次自检延迟（等待插件系统初始化完成）
+const INITIAL_CHECK_DELAY: Duration = Duration::from_secs(60);
+
+/// 定期自检间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 插件被隔离时通知前端
+pub fn notify_tampered(app: &AppHandle, report: &IntegrityReport) {
+    if report.passed() {
+        return;
+    }
+    let error = AppError::new(
+        codes::PLUGIN_TAMPERED,
+        format!("插件文件完整性校验失败，已隔离: {}", report.issues.join("; ")),
+    );
+    if let Err(e) = emitter(app).emit_plugin_error(&report.plugin_id, &error) {
+        log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", report.plugin_id, e);
+    }
+}
+
+/// 启动后台完整性自检
+pub fn spawn_integrity_watcher(app: AppHandle, manager: Arc<PluginManager>) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(INITIAL_CHECK_DELAY).await;
+        loop {
+            let reports = manager.verify_all_plugins_integrity().await;
+            let tampered: Vec<&IntegrityReport> = reports.iter().filter(|r| !r.passed()).collect();
+            log::info!(
+                "插件完整性自检完成: {} 个插件, {} 个未通过",
+                reports.len(),
+                tampered.len()
+            );
+            for report in tampered {
+                notify_tampered(&app, report);
+            }
+
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
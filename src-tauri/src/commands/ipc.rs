@@ -1,14 +1,111 @@
 // Phase 7.3: IPC Commands
 // 实现 contracts/types/ipc-commands.d.ts 定义的 18 个命令
 
-use crate::commands::events::emitter;
-use crate::commands::PluginManagerState;
+use crate::commands::alerts::AlertManagerState;
+use crate::commands::events::{emitter, install_progress_reporter};
+use crate::commands::installer::{rollback_plugin, InstallError, PluginInstaller};
+use crate::commands::integrity::notify_tampered;
+use crate::commands::missed_alerts::MissedAlertState;
+use crate::commands::registry_cache::{RegistryAsset, RegistryAssetKind};
+use crate::commands::network::{self, NetworkMonitorState};
+use crate::commands::popup::{PopupSummary, PopupSummaryState};
+use crate::commands::push::PushServerState;
+use crate::commands::registry::install_from_registry;
+use crate::commands::settings::{broadcast_settings, SettingsState};
+use crate::commands::updates::UpdateCheckerState;
+use crate::commands::wizard::{self, SetupWizardState};
+use crate::commands::budget::BudgetState;
+use crate::commands::data_changes::{publish_plugin_data, DataDiffState};
+use crate::commands::error::{codes, IpcError};
+use crate::commands::metrics::{IpcMetricsReport, IpcMetricsState};
+use crate::commands::{
+    InstallCancelState, LegacyCommandState, PluginManagerState, RegistryCacheState, RegistryClientState,
+};
+use crate::logging::{self, AppLogEntry};
+use crate::settings::AppSettings;
+use crate::window::ThemeChangedPayload;
+use crate::plugin::api_version::IncompatiblePlugin;
+use crate::plugin::batch::{BatchQuery, BatchResponse, MAX_BATCH_QUERIES};
+use crate::plugin::bulk::{dedup_ids, BulkOp, BulkResult, MAX_BULK_PLUGINS};
+use crate::plugin::developer::DeveloperSettings;
+use crate::plugin::diagnostics::DiagnosticsFile;
+use crate::plugin::event_bus::{DeadLetter, EventBusStats};
+use crate::plugin::env_import::{self, EnvImportReport};
+use crate::plugin::gateway::GatewayConfig;
+use crate::plugin::legacy::LegacyCommandReport;
+use crate::plugin::push::PushSettings;
+use crate::plugin::registry::{RegistryEntry, RegistryManifest};
+use crate::plugin::summary::DataSummary;
+use crate::plugin::updates::UpdateSettings;
+use crate::plugin::wizard::{WizardProgress, WizardState, WizardStepInput};
+use crate::plugin::usage_history::{UsageTrend, DEFAULT_TREND_POINTS};
+use crate::plugin::UsageAlertSettings;
+use crate::plugin::monitoring::{format_rfc3339, AlertHistoryFilter, AlertRecord, MissedAlertSummary, SystemHealth};
+use crate::plugin::budget::{Budget, BudgetStatus};
+use crate::plugin::network_stats::{PrivacyReport, NETWORK_STATS_RETENTION_DAYS};
+use crate::plugin::plugin_logs::{PluginLogEntry, PluginLogLevel};
+use crate::plugin::pricing::{self, CostEstimate, CostRange, PriceTable, DEFAULT_PRICING_URL};
+use crate::plugin::sandbox::secrets::{KeychainStore, SecretStore};
+use crate::plugin::sandbox::storage::PluginStorageStats;
+use crate::plugin::InputRequest;
 use crate::plugin::types::{
-    AppError, PluginData, PluginHealth, PluginInfo, Result as IpcResult, UpdateInfo,
-    ValidationResult,
+    AppError, IntegrityReport, PluginAccountData, PluginData, PluginDescription, PluginHealth, PluginInfo,
+    PluginResourceStats, ReplayParseResult, Result as IpcResult, UpdateInfo, ValidationResult,
 };
+use crate::reliability::{ActivityAssertions, NetworkMonitor, NetworkStatus};
+use crate::security::audit::AuditEvent;
+use crate::security::backup::PluginBackup;
+use crate::security::keys::{fetch_key_list, PublisherKeyStatus, DEFAULT_PUBLISHER_KEYS_URL};
+use crate::tray::TrayClickConfig;
+use crate::updater::{self, AppUpdateStatus, AppUpdaterState};
+use crate::window::{PopupState, WindowType};
 use std::collections::HashMap;
-use tauri::{command, AppHandle, State};
+use std::path::Path;
+use tauri::{command, AppHandle, Manager, State, Window};
+
+// ============================================================================
+// 命令权限（按调用窗口限制敏感命令）
+// ============================================================================
+
+/// 可以调用敏感命令（安装/卸载/更新/回滚插件、读写 Keychain、修改安全相关设置）的窗口
+const PRIVILEGED_WINDOWS: [WindowType; 2] = [WindowType::Settings, WindowType::Dashboard];
+
+/// 可以下载和安装应用更新的窗口（关于窗口提供更新入口）
+const APP_UPDATE_WINDOWS: [WindowType; 3] = [WindowType::Settings, WindowType::Dashboard, WindowType::About];
+
+/// 窗口是否在允许列表中
+fn is_allowed_window(label: &str, allowed: &[WindowType]) -> bool {
+    allowed
+        .iter()
+        .any(|window_type| window_type.config().label == label)
+}
+
+/// 检查调用窗口是否可以执行敏感命令
+///
+/// 托盘弹窗、HUD、输入对话框等窗口调用时返回 COMMAND_NOT_ALLOWED，
+/// details 中附带命令名、调用窗口和允许的窗口
+fn authorize_window(window: &Window, command: &str) -> Result<(), AppError> {
+    authorize_window_in(window, command, &PRIVILEGED_WINDOWS)
+}
+
+/// 检查调用窗口是否在命令的允许列表中（同 `authorize_window`，允许的窗口由调用方指定）
+fn authorize_window_in(window: &Window, command: &str, allowed: &[WindowType]) -> Result<(), AppError> {
+    let label = window.label();
+    if is_allowed_window(label, allowed) {
+        return Ok(());
+    }
+    log::warn!("拒绝窗口 {} 调用敏感命令 {}", label, command);
+    let allowed: Vec<&str> = allowed.iter().map(|t| t.config().label).collect();
+    Err(IpcError::new(
+        codes::COMMAND_NOT_ALLOWED,
+        format!("当前窗口无权执行 {}，请在设置或仪表盘窗口中操作", command),
+    )
+    .with_details(serde_json::json!({
+        "command": command,
+        "window": label,
+        "allowedWindows": allowed,
+    })).into())
+}
 
 // ============================================================================
 // 7.3.1 插件管理 Commands (9个)
@@ -18,9 +115,41 @@ use tauri::{command, AppHandle, State};
 #[command]
 pub async fn plugin_list(
     state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<Vec<PluginInfo>>, String> {
-    let plugins = state.0.list_plugins().await;
-    Ok(IpcResult::ok(plugins))
+    metrics.0.trace("plugin_list", async move {
+        let plugins = state.0.list_plugins().await;
+        Ok(IpcResult::ok(plugins))
+    })
+    .await
+}
+
+/// 获取插件能力描述（manifest 与实际生效的权限、API、订阅和限制）
+#[command]
+pub async fn plugin_describe(
+    plugin_id: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PluginDescription>, String> {
+    metrics.0.trace("plugin_describe", async move {
+        match state.0.describe_plugin(&plugin_id).await {
+            Ok(description) => Ok(IpcResult::ok(description)),
+            Err(e) => Ok(IpcResult::err(IpcError::lifecycle(codes::PLUGIN_NOT_FOUND, e).into())),
+        }
+    })
+    .await
+}
+
+/// 获取 API 版本不兼容、未加载的插件（含支持范围和处理建议）
+#[command]
+pub async fn plugin_list_incompatible(
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<IncompatiblePlugin>>, String> {
+    metrics.0.trace("plugin_list_incompatible", async move {
+        Ok(IpcResult::ok(state.0.incompatible_plugins()))
+    })
+    .await
 }
 
 /// 启用插件
@@ -29,19 +158,26 @@ pub async fn plugin_enable(
     app: AppHandle,
     id: String,
     state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<()>, String> {
-    // PluginManager 内部 RwLock 会处理并发控制
-    match state.0.enable_plugin(&id).await {
-        Ok(()) => Ok(IpcResult::ok(())),
-        Err(e) => {
-            let error = AppError::new("PLUGIN_ENABLE_FAILED", e.to_string());
-            // 发射错误事件并记录日志（P1: 可观测性）
-            if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
-                log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+    metrics.0.trace("plugin_enable", async move {
+        // PluginManager 内部 RwLock 会处理并发控制
+        match state.0.enable_plugin(&id).await {
+            Ok(()) => {
+                sync_tray_menu(&app, &state.0).await;
+                Ok(IpcResult::ok(()))
+            }
+            Err(e) => {
+                let error: AppError = IpcError::lifecycle(codes::PLUGIN_ENABLE_FAILED, e).into();
+                // 发射错误事件并记录日志（P1: 可观测性）
+                if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
+                    log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+                }
+                Ok(IpcResult::err(error))
             }
-            Ok(IpcResult::err(error))
         }
-    }
+    })
+    .await
 }
 
 /// 禁用插件
@@ -50,19 +186,26 @@ pub async fn plugin_disable(
     app: AppHandle,
     id: String,
     state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<()>, String> {
-    // PluginManager 内部 RwLock 会处理并发控制
-    match state.0.disable_plugin(&id).await {
-        Ok(()) => Ok(IpcResult::ok(())),
-        Err(e) => {
-            let error = AppError::new("PLUGIN_DISABLE_FAILED", e.to_string());
-            // 发射错误事件并记录日志（P1: 可观测性）
-            if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
-                log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+    metrics.0.trace("plugin_disable", async move {
+        // PluginManager 内部 RwLock 会处理并发控制
+        match state.0.disable_plugin(&id).await {
+            Ok(()) => {
+                sync_tray_menu(&app, &state.0).await;
+                Ok(IpcResult::ok(()))
+            }
+            Err(e) => {
+                let error: AppError = IpcError::lifecycle(codes::PLUGIN_DISABLE_FAILED, e).into();
+                // 发射错误事件并记录日志（P1: 可观测性）
+                if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
+                    log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+                }
+                Ok(IpcResult::err(error))
             }
-            Ok(IpcResult::err(error))
         }
-    }
+    })
+    .await
 }
 
 /// 安装插件
@@ -75,74 +218,268 @@ pub async fn plugin_disable(
 /// - `registry_url`: 市场 registry.json URL（用于 registry:// 协议）
 #[command]
 pub async fn plugin_install(
+    window: Window,
     app: AppHandle,
     source: String,
     skip_signature: Option<bool>,
     registry_url: Option<String>,
     state: State<'_, PluginManagerState>,
+    cancels: State<'_, InstallCancelState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<PluginInfo>, String> {
-    use crate::commands::installer::install_plugin;
+    metrics.0.trace("plugin_install", async move {
+        if let Err(error) = authorize_window(&window, "plugin_install") {
+            return Ok(IpcResult::err(error));
+        }
+        use crate::commands::installer::install_plugin;
 
-    let skip_sig = skip_signature.unwrap_or(false);
-    let reg_url = registry_url.as_deref();
+        let skip_sig = skip_signature.unwrap_or(false);
+        let reg_url = registry_url.as_deref();
 
-    match install_plugin(state.0.clone(), &source, skip_sig, reg_url).await {
-        Ok(plugin_info) => {
-            // 发射安装成功事件
-            if let Err(emit_err) = emitter(&app).emit_plugin_installed(&plugin_info) {
-                log::warn!(
-                    "发送插件安装事件失败: plugin={}, emit_error={}",
-                    plugin_info.id,
-                    emit_err
-                );
+        let progress = Some(install_progress_reporter(&app));
+        let cancel = cancels.0.begin(&source);
+        let result = install_plugin(state.0.clone(), &source, skip_sig, reg_url, progress, Some(cancel.clone())).await;
+        cancels.0.finish(&cancel);
+        match result {
+            Ok(plugin_info) => {
+                // 发射安装成功事件
+                if let Err(emit_err) = emitter(&app).emit_plugin_installed(&plugin_info) {
+                    log::warn!(
+                        "发送插件安装事件失败: plugin={}, emit_error={}",
+                        plugin_info.id,
+                        emit_err
+                    );
+                }
+                log::info!("插件安装成功: {} v{}", plugin_info.id, plugin_info.version);
+                sync_tray_menu(&app, &state.0).await;
+                Ok(IpcResult::ok(plugin_info))
+            }
+            Err(InstallError::Cancelled) => {
+                log::info!("插件安装已取消: source={}", source);
+                Ok(IpcResult::err(InstallError::Cancelled.into()))
+            }
+            Err(e) => {
+                let error: AppError = e.into();
+                // 从 source 提取 plugin_id（用于错误事件）
+                let plugin_id = if source.starts_with("registry://") {
+                    source.strip_prefix("registry://").unwrap_or(&source)
+                } else {
+                    &source
+                };
+                // 发射错误事件
+                if let Err(emit_err) = emitter(&app).emit_plugin_error(plugin_id, &error) {
+                    log::warn!(
+                        "发送插件错误事件失败: source={}, emit_error={}",
+                        source,
+                        emit_err
+                    );
+                }
+                log::error!("插件安装失败: source={}, error={}", source, error.message);
+                Ok(IpcResult::err(error))
             }
-            log::info!("插件安装成功: {} v{}", plugin_info.id, plugin_info.version);
-            Ok(IpcResult::ok(plugin_info))
         }
-        Err(e) => {
-            let error: AppError = e.into();
-            // 从 source 提取 plugin_id（用于错误事件）
-            let plugin_id = if source.starts_with("registry://") {
-                source.strip_prefix("registry://").unwrap_or(&source)
-            } else {
-                &source
-            };
-            // 发射错误事件
-            if let Err(emit_err) = emitter(&app).emit_plugin_error(plugin_id, &error) {
-                log::warn!(
-                    "发送插件错误事件失败: source={}, emit_error={}",
-                    source,
-                    emit_err
-                );
-            }
-            log::error!("插件安装失败: source={}, error={}", source, error.message);
-            Ok(IpcResult::err(error))
+    })
+    .await
+}
+
+/// 从本地 ZIP 文件安装插件
+#[command]
+pub async fn plugin_install_from_file(
+    window: Window,
+    app: AppHandle,
+    path: String,
+    skip_signature: Option<bool>,
+    state: State<'_, PluginManagerState>,
+    cancels: State<'_, InstallCancelState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PluginInfo>, String> {
+    metrics.0.trace("plugin_install_from_file", async move {
+        if let Err(error) = authorize_window(&window, "plugin_install_from_file") {
+            return Ok(IpcResult::err(error));
         }
-    }
+        use crate::commands::installer::install_plugin_from_file;
+
+        let skip_sig = skip_signature.unwrap_or(false);
+        let progress = Some(install_progress_reporter(&app));
+        let cancel = cancels.0.begin(&path);
+        let result =
+            install_plugin_from_file(state.0.clone(), std::path::Path::new(&path), skip_sig, progress, Some(cancel.clone()))
+                .await;
+        cancels.0.finish(&cancel);
+        match result {
+            Ok(plugin_info) => {
+                if let Err(emit_err) = emitter(&app).emit_plugin_installed(&plugin_info) {
+                    log::warn!(
+                        "发送插件安装事件失败: plugin={}, emit_error={}",
+                        plugin_info.id,
+                        emit_err
+                    );
+                }
+                log::info!("本地插件安装成功: {} v{}", plugin_info.id, plugin_info.version);
+                sync_tray_menu(&app, &state.0).await;
+                Ok(IpcResult::ok(plugin_info))
+            }
+            Err(e) => {
+                let error: AppError = e.into();
+                log::error!("本地插件安装失败: path={}, error={}", path, error.message);
+                Ok(IpcResult::err(error))
+            }
+        }
+    })
+    .await
+}
+
+/// 取消进行中的插件安装
+///
+/// `key` 为发起安装时的 source / 文件路径 / registry ID，
+/// 解压会在下一个条目前中止并删除已写出的临时文件。
+/// 返回是否找到进行中的安装。
+#[command]
+pub async fn plugin_install_cancel(
+    window: Window,
+    key: String,
+    cancels: State<'_, InstallCancelState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<bool>, String> {
+    metrics.0.trace("plugin_install_cancel", async move {
+        if let Err(error) = authorize_window(&window, "plugin_install_cancel") {
+            return Ok(IpcResult::err(error));
+        }
+        let found = cancels.0.cancel(&key);
+        log::info!("请求取消插件安装: key={}, found={}", key, found);
+        Ok(IpcResult::ok(found))
+    })
+    .await
 }
 
 /// 卸载插件
 #[command]
 pub async fn plugin_uninstall(
+    window: Window,
     app: AppHandle,
     id: String,
     state: State<'_, PluginManagerState>,
+    diff: State<'_, DataDiffState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<()>, String> {
-    match state.0.uninstall_plugin(&id).await {
-        Ok(()) => {
-            if let Err(emit_err) = emitter(&app).emit_plugin_uninstalled(&id) {
-                log::warn!("发送插件卸载事件失败: plugin={}, emit_error={}", id, emit_err);
+    metrics.0.trace("plugin_uninstall", async move {
+        if let Err(error) = authorize_window(&window, "plugin_uninstall") {
+            return Ok(IpcResult::err(error));
+        }
+        match state.0.uninstall_plugin(&id).await {
+            Ok(()) => {
+                diff.0.remove_plugin(&id);
+                if let Err(emit_err) = emitter(&app).emit_plugin_uninstalled(&id) {
+                    log::warn!("发送插件卸载事件失败: plugin={}, emit_error={}", id, emit_err);
+                }
+                sync_tray_menu(&app, &state.0).await;
+                Ok(IpcResult::ok(()))
+            }
+            Err(e) => {
+                let error: AppError = IpcError::lifecycle(codes::PLUGIN_UNINSTALL_FAILED, e).into();
+                if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
+                    log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+                }
+                Ok(IpcResult::err(error))
             }
-            Ok(IpcResult::ok(()))
         }
-        Err(e) => {
-            let error = AppError::new("PLUGIN_UNINSTALL_FAILED", e.to_string());
-            if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
-                log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+    })
+    .await
+}
+
+/// 批量操作选中的插件（启用/禁用/刷新/卸载）
+///
+/// 逐个执行并报告每个插件的结果，完成后发送一个汇总事件、同步一次托盘。
+/// 刷新在离线时排队等待恢复连接；卸载只允许设置窗口和仪表盘窗口调用。
+#[command]
+pub async fn plugin_bulk(
+    window: Window,
+    app: AppHandle,
+    op: BulkOp,
+    ids: Vec<String>,
+    state: State<'_, PluginManagerState>,
+    diff: State<'_, DataDiffState>,
+    network: State<'_, NetworkMonitorState>,
+    activity: State<'_, ActivityAssertions>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<BulkResult>, String> {
+    metrics.0.trace("plugin_bulk", async move {
+        if op == BulkOp::Uninstall {
+            if let Err(error) = authorize_window(&window, "plugin_bulk") {
+                return Ok(IpcResult::err(error));
             }
-            Ok(IpcResult::err(error))
         }
-    }
+        let ids = dedup_ids(ids);
+        if ids.len() > MAX_BULK_PLUGINS {
+            return Ok(IpcResult::err(IpcError::new(
+                codes::BATCH_TOO_LARGE,
+                format!("单批最多 {} 个插件，实际 {} 个", MAX_BULK_PLUGINS, ids.len()),
+            ).into()));
+        }
+
+        let _activity = (op == BulkOp::Refresh).then(|| activity.begin("批量刷新插件数据"));
+        let mut result = BulkResult::new(op);
+        for id in &ids {
+            match op {
+                BulkOp::Enable => match state.0.enable_plugin(id).await {
+                    Ok(()) => result.ok(id),
+                    Err(e) => result.fail(id, IpcError::lifecycle(codes::PLUGIN_ENABLE_FAILED, e).into()),
+                },
+                BulkOp::Disable => match state.0.disable_plugin(id).await {
+                    Ok(()) => result.ok(id),
+                    Err(e) => result.fail(id, IpcError::lifecycle(codes::PLUGIN_DISABLE_FAILED, e).into()),
+                },
+                BulkOp::Uninstall => match state.0.uninstall_plugin(id).await {
+                    Ok(()) => {
+                        diff.0.remove_plugin(id);
+                        result.ok(id);
+                    }
+                    Err(e) => result.fail(id, IpcError::lifecycle(codes::PLUGIN_UNINSTALL_FAILED, e).into()),
+                },
+                BulkOp::Refresh => {
+                    // 离线时排队；服务商维护期间不刷新，与 refresh_plugin 一致
+                    if !network.0.is_online() {
+                        network::queue_refreshes(&app, &network.0, std::slice::from_ref(id));
+                        result.queued(id);
+                        continue;
+                    }
+                    if state.0.plugin_maintenance(id).await.is_some() {
+                        result.ok(id);
+                        continue;
+                    }
+                    match state.0.execute_fetch_data(id, false).await {
+                        Ok(data) => {
+                            publish_plugin_data(&app, &state.0, id, &data).await;
+                            result.ok(id);
+                        }
+                        Err(e) => {
+                            let message = e.to_string();
+                            if network::is_network_error(&message) && network::confirm_offline(&app, &network.0).await {
+                                network::queue_refreshes(&app, &network.0, std::slice::from_ref(id));
+                                result.queued(id);
+                            } else {
+                                result.fail(id, IpcError::lifecycle(codes::PLUGIN_REFRESH_FAILED, e).into());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        log::info!(
+            "[plugin_bulk] {:?}: {} 个成功, {} 个失败",
+            op, result.succeeded, result.failed
+        );
+        match op {
+            BulkOp::Refresh => sync_tray(&app, &state.0, result.failed).await,
+            _ => sync_tray_menu(&app, &state.0).await,
+        }
+        if let Err(emit_err) = emitter(&app).emit_plugin_bulk_completed(&result) {
+            log::warn!("发送批量操作事件失败: op={:?}, emit_error={}", op, emit_err);
+        }
+        Ok(IpcResult::ok(result))
+    })
+    .await
 }
 
 /// 重载插件
@@ -150,63 +487,168 @@ pub async fn plugin_uninstall(
 pub async fn plugin_reload(
     id: String,
     state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<()>, String> {
-    match state.0.reload_plugin(&id).await {
-        Ok(_info) => Ok(IpcResult::ok(())),
-        Err(e) => Ok(IpcResult::err(AppError::new("PLUGIN_RELOAD_FAILED", e.to_string()))),
-    }
+    metrics.0.trace("plugin_reload", async move {
+        match state.0.reload_plugin(&id).await {
+            Ok(_info) => Ok(IpcResult::ok(())),
+            Err(e) => Ok(IpcResult::err(IpcError::lifecycle(codes::PLUGIN_RELOAD_FAILED, e).into())),
+        }
+    })
+    .await
 }
 
-/// 检查插件更新
-/// 注：完整的更新检查需要远程仓库支持，当前返回空列表
+/// 检查插件更新（与 registry 索引比对版本）
 #[command]
 pub async fn plugin_check_updates(
-    _state: State<'_, PluginManagerState>,
+    updates: State<'_, UpdateCheckerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<Vec<UpdateInfo>>, String> {
-    // 更新检查需要远程仓库支持，当前无远程仓库配置
-    Ok(IpcResult::ok(vec![]))
+    metrics.0.trace("plugin_check_updates", async move {
+        match updates.0.check().await {
+            Ok(list) => Ok(IpcResult::ok(list)),
+            Err(error) => Ok(IpcResult::err(error)),
+        }
+    })
+    .await
 }
 
 /// 更新插件
-/// 注：完整的更新功能需要远程仓库支持，当前仅执行重载
+///
+/// registry 中有新版本时安装（更新后健康检查失败则回滚），否则重载插件
 #[command]
 pub async fn plugin_update(
+    window: Window,
     app: AppHandle,
     id: String,
     state: State<'_, PluginManagerState>,
+    updates: State<'_, UpdateCheckerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<PluginInfo>, String> {
-    // 简化实现：重载插件作为更新操作
-    match state.0.reload_plugin(&id).await {
-        Ok(info) => {
-            if let Err(emit_err) = emitter(&app).emit_plugin_updated(&info) {
-                log::warn!("发送插件更新事件失败: plugin={}, emit_error={}", id, emit_err);
-            }
-            Ok(IpcResult::ok(info))
+    metrics.0.trace("plugin_update", async move {
+        if let Err(error) = authorize_window(&window, "plugin_update") {
+            return Ok(IpcResult::err(error));
         }
-        Err(e) => {
-            let error = AppError::new("PLUGIN_UPDATE_FAILED", e.to_string());
-            if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
-                log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+        let available = match updates.0.check().await {
+            Ok(list) => list.into_iter().find(|u| u.id == id),
+            Err(e) => {
+                log::warn!("检查插件 {} 更新失败，仅重载: {}", id, e.message);
+                None
+            }
+        };
+
+        let result = match available {
+            Some(update) => updates.0.apply(&update).await,
+            None => state
+                .0
+                .reload_plugin(&id)
+                .await
+                .map_err(|e| IpcError::lifecycle(codes::PLUGIN_UPDATE_FAILED, e).into()),
+        };
+
+        match result {
+            Ok(info) => {
+                if let Err(emit_err) = emitter(&app).emit_plugin_updated(&info) {
+                    log::warn!("发送插件更新事件失败: plugin={}, emit_error={}", id, emit_err);
+                }
+                Ok(IpcResult::ok(info))
+            }
+            Err(error) => {
+                if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
+                    log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+                }
+                Ok(IpcResult::err(error))
             }
-            Ok(IpcResult::err(error))
         }
-    }
+    })
+    .await
 }
 
-/// 回滚插件
-/// 注：完整的版本回滚需要版本历史支持，当前不支持
+/// 回滚插件（同 rollback_to，不返回插件信息）
 #[command]
 pub async fn plugin_rollback(
+    window: Window,
+    app: AppHandle,
     id: String,
     version: String,
-    _state: State<'_, PluginManagerState>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<()>, String> {
-    // 版本回滚需要版本历史记录支持，当前未实现版本管理
-    log::warn!("插件回滚请求: id={}, version={}, 功能暂不支持", id, version);
-    Ok(IpcResult::err(AppError::new(
-        "NOT_SUPPORTED",
-        "版本回滚需要版本历史记录支持，当前未实现",
-    )))
+    metrics.0.trace("plugin_rollback", async move {
+        if let Err(error) = authorize_window(&window, "plugin_rollback") {
+            return Ok(IpcResult::err(error));
+        }
+        Ok(match rollback_to_backup(&app, &state.0, &id, &version).await {
+            Ok(_) => IpcResult::ok(()),
+            Err(error) => IpcResult::err(error),
+        })
+    })
+    .await
+}
+
+/// 列出插件的版本备份（`plugin_id` 为空时列出所有插件，同一插件最新在前）
+#[command]
+pub async fn list_plugin_backups(
+    plugin_id: Option<String>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<PluginBackup>>, String> {
+    metrics.0.trace("list_plugin_backups", async move {
+        let installer = PluginInstaller::new(state.0.clone());
+        Ok(match installer.list_backups(plugin_id.as_deref()) {
+            Ok(backups) => IpcResult::ok(backups),
+            Err(e) => IpcResult::err(e.into()),
+        })
+    })
+    .await
+}
+
+/// 回滚插件到备份的指定版本（保留当前配置和启用状态）
+#[command]
+pub async fn rollback_to(
+    window: Window,
+    app: AppHandle,
+    plugin_id: String,
+    version: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PluginInfo>, String> {
+    metrics.0.trace("rollback_to", async move {
+        if let Err(error) = authorize_window(&window, "rollback_to") {
+            return Ok(IpcResult::err(error));
+        }
+        Ok(match rollback_to_backup(&app, &state.0, &plugin_id, &version).await {
+            Ok(info) => IpcResult::ok(info),
+            Err(error) => IpcResult::err(error),
+        })
+    })
+    .await
+}
+
+/// 从备份恢复插件并发送更新/错误事件
+async fn rollback_to_backup(
+    app: &AppHandle,
+    manager: &std::sync::Arc<crate::plugin::PluginManager>,
+    plugin_id: &str,
+    version: &str,
+) -> Result<PluginInfo, AppError> {
+    match rollback_plugin(manager.clone(), plugin_id, Some(version)).await {
+        Ok(info) => {
+            if let Err(emit_err) = emitter(app).emit_plugin_updated(&info) {
+                log::warn!("发送插件更新事件失败: plugin={}, emit_error={}", plugin_id, emit_err);
+            }
+            sync_tray_menu(app, manager).await;
+            Ok(info)
+        }
+        Err(e) => {
+            let error: AppError = e.into();
+            if let Err(emit_err) = emitter(app).emit_plugin_error(plugin_id, &error) {
+                log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", plugin_id, emit_err);
+            }
+            log::error!("回滚插件失败: id={}, version={}, error={}", plugin_id, version, error.message);
+            Err(error)
+        }
+    }
 }
 
 // ============================================================================
@@ -217,9 +659,13 @@ pub async fn plugin_rollback(
 #[command]
 pub async fn get_all_data(
     state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<Vec<PluginData>>, String> {
-    let data = state.0.get_all_data().await;
-    Ok(IpcResult::ok(data))
+    metrics.0.trace("get_all_data", async move {
+        let data = state.0.get_all_data().await;
+        Ok(IpcResult::ok(data))
+    })
+    .await
 }
 
 /// 获取单个插件数据
@@ -227,79 +673,265 @@ pub async fn get_all_data(
 pub async fn get_plugin_data(
     id: String,
     state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<Option<PluginData>>, String> {
-    let data = state.0.get_plugin_data(&id).await;
-    Ok(IpcResult::ok(data))
+    metrics.0.trace("get_plugin_data", async move {
+        let data = state.0.get_plugin_data(&id).await;
+        Ok(IpcResult::ok(data))
+    })
+    .await
+}
+
+/// 获取所有已启用插件数据的聚合摘要
+///
+/// 最高使用率、最低余额、最差状态和最近重置时间在 Rust 端计算，各窗口展示一致。
+#[command]
+pub async fn get_summary(
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<DataSummary>, String> {
+    metrics.0.trace("get_summary", async move {
+        Ok(IpcResult::ok(state.0.get_summary().await))
+    })
+    .await
+}
+
+/// 批量执行只读查询（同一快照）
+///
+/// 支持 plugin_list / get_all_data / get_all_health / get_summary / get_plugin_data /
+/// get_plugin_health，结果与请求顺序一致且来自同一个插件表快照。
+#[command]
+pub async fn batch_query(
+    requests: Vec<BatchQuery>,
+    state: State<'_, PluginManagerState>,
+    alerts: State<'_, AlertManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<BatchResponse>, String> {
+    metrics.0.trace("batch_query", async move {
+        if requests.len() > MAX_BATCH_QUERIES {
+            return Ok(IpcResult::err(IpcError::new(
+                codes::BATCH_TOO_LARGE,
+                format!("单批最多 {} 条查询，实际 {} 条", MAX_BATCH_QUERIES, requests.len()),
+            ).into()));
+        }
+
+        let mut response = state.0.batch_query(&requests).await;
+        for item in response.results.iter_mut().flat_map(|r| r.health_mut()) {
+            alerts.0.annotate_health(item);
+        }
+        Ok(IpcResult::ok(response))
+    })
+    .await
 }
 
 /// 刷新单个插件
 ///
-/// 执行插件的 fetchData 函数获取最新数据
+/// 执行插件的 fetchData 函数获取最新数据；缓存有效期内返回缓存结果，force 为 true 时绕过缓存
 #[command]
 pub async fn refresh_plugin(
     app: AppHandle,
     id: String,
-    _force: Option<bool>,
+    force: Option<bool>,
     state: State<'_, PluginManagerState>,
+    network: State<'_, NetworkMonitorState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<Option<PluginData>>, String> {
-    // 执行插件的 fetchData 函数
-    match state.0.execute_fetch_data(&id).await {
-        Ok(data) => {
-            // 发送数据更新事件
-            if let Err(emit_err) = emitter(&app).emit_plugin_data_updated(&id, &data) {
-                log::warn!("发送插件数据更新事件失败: plugin={}, emit_error={}", id, emit_err);
-            }
-            Ok(IpcResult::ok(Some(data)))
+    metrics.0.trace("refresh_plugin", async move {
+        // 离线时不执行插件，排队等待恢复连接后刷新
+        if !network.0.is_online() {
+            network::queue_refreshes(&app, &network.0, std::slice::from_ref(&id));
+            return Ok(IpcResult::ok(state.0.get_plugin_data(&id).await));
         }
-        Err(e) => {
-            // 执行失败，返回缓存数据（如果有）
-            log::warn!("插件 {} 执行 fetchData 失败: {}", id, e);
-            let error = AppError::new("PLUGIN_REFRESH_FAILED", e.to_string());
-            if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
-                log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+
+        // 服务商维护期间不自动刷新，返回维护前的缓存数据（强制刷新仍执行）
+        let force = force.unwrap_or(false);
+        let maintenance = state.0.plugin_maintenance(&id).await;
+        if maintenance.is_some() && !force {
+            return Ok(IpcResult::ok(state.0.get_plugin_data(&id).await));
+        }
+
+        // 执行插件的 fetchData 函数
+        match state.0.execute_fetch_data(&id, force).await {
+            Ok(data) => {
+                // 发送数据更新/变化事件
+                publish_plugin_data(&app, &state.0, &id, &data).await;
+                sync_tray(&app, &state.0, 0).await;
+                Ok(IpcResult::ok(Some(data)))
+            }
+            Err(e) => {
+                // 执行失败，返回缓存数据（如果有）
+                log::warn!("插件 {} 执行 fetchData 失败: {}", id, e);
+                let message = e.to_string();
+                // 维护期间的失败是计划内停机，不发送错误事件
+                // 网络错误且确认离线时不发送错误事件，恢复连接后自动重试
+                if maintenance.is_some() {
+                    log::info!("插件 {} 服务商维护中，不发送错误事件", id);
+                } else if network::is_network_error(&message) && network::confirm_offline(&app, &network.0).await {
+                    network::queue_refreshes(&app, &network.0, std::slice::from_ref(&id));
+                } else {
+                    let error = IpcError::new(codes::PLUGIN_REFRESH_FAILED, message).into();
+                    if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
+                        log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+                    }
+                    sync_tray(&app, &state.0, 1).await;
+                }
+                // 尝试返回缓存数据
+                let cached = state.0.get_plugin_data(&id).await;
+                Ok(IpcResult::ok(cached))
             }
-            // 尝试返回缓存数据
-            let cached = state.0.get_plugin_data(&id).await;
-            Ok(IpcResult::ok(cached))
         }
-    }
+    })
+    .await
 }
 
 /// 刷新所有插件
 ///
-/// 执行所有启用插件的 fetchData 函数获取最新数据
+/// 执行所有启用插件的 fetchData 函数获取最新数据；force 为 true 时绕过缓存
 #[command]
 pub async fn refresh_all(
     app: AppHandle,
-    _force: Option<bool>,
+    force: Option<bool>,
     state: State<'_, PluginManagerState>,
+    network: State<'_, NetworkMonitorState>,
+    activity: State<'_, ActivityAssertions>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<Vec<PluginData>>, String> {
+    metrics.0.trace("refresh_all", async move {
+        let data = refresh_all_plugins(&app, &state.0, &network.0, &activity, force.unwrap_or(false)).await;
+        log::info!("[refresh_all] 返回 {} 条数据", data.len());
+        Ok(IpcResult::ok(data))
+    })
+    .await
+}
+
+/// 刷新所有插件并发布结果（refresh_all 命令与托盘"刷新全部"动作共用）
+pub(crate) async fn refresh_all_plugins(
+    app: &AppHandle,
+    manager: &crate::plugin::PluginManager,
+    network: &NetworkMonitor,
+    activity: &ActivityAssertions,
+    force: bool,
+) -> Vec<PluginData> {
+    // 离线时不执行插件，返回缓存数据并排队等待恢复连接后刷新
+    if !network.is_online() {
+        let ids = manager.refreshable_plugin_ids().await;
+        network::queue_refreshes(app, network, &ids);
+        return manager.get_all_data().await;
+    }
+
+    // 批量刷新期间阻止 App Nap 节流，刷新结束立即释放
+    let _activity = activity.begin("刷新插件数据");
+
     // 执行所有插件的 fetchData 函数
-    let results = state.0.refresh_all_plugins().await;
+    let results = manager.refresh_all_plugins(force).await;
 
     let mut data = Vec::new();
+    let mut failed = 0;
+    let mut network_failed = 0;
     for result in results {
         match result {
             Ok(plugin_data) => {
-                let plugin_id = match &plugin_data {
-                    PluginData::Usage(u) => &u.base.plugin_id,
-                    PluginData::Balance(b) => &b.base.plugin_id,
-                    PluginData::Status(s) => &s.base.plugin_id,
-                    PluginData::Custom(c) => &c.base.plugin_id,
-                };
-                if let Err(emit_err) = emitter(&app).emit_plugin_data_updated(plugin_id, &plugin_data) {
-                    log::warn!("发送插件数据更新事件失败: plugin={}, emit_error={}", plugin_id, emit_err);
-                }
+                let plugin_id = plugin_data.plugin_id();
+                publish_plugin_data(app, manager, plugin_id, &plugin_data).await;
                 data.push(plugin_data);
             }
             Err(e) => {
                 log::warn!("插件执行 fetchData 失败: {}", e);
+                if network::is_network_error(&e.to_string()) {
+                    network_failed += 1;
+                } else {
+                    failed += 1;
+                }
             }
         }
     }
 
-    log::info!("[refresh_all] 返回 {} 条数据", data.len());
-    Ok(IpcResult::ok(data))
+    // 网络错误且确认离线时，未成功的插件排队等待恢复连接后刷新
+    if network_failed > 0 {
+        if network::confirm_offline(app, network).await {
+            let succeeded: std::collections::HashSet<&str> =
+                data.iter().map(|d| d.plugin_id()).collect();
+            let pending: Vec<String> = manager
+                .refreshable_plugin_ids()
+                .await
+                .into_iter()
+                .filter(|id| !succeeded.contains(id.as_str()))
+                .collect();
+            network::queue_refreshes(app, network, &pending);
+        } else {
+            failed += network_failed;
+        }
+    }
+
+    sync_tray(app, manager, failed).await;
+    data
+}
+
+/// 按所有插件的缓存数据同步托盘图标（使用量进度环 + 状态圆点）
+async fn sync_tray(app: &AppHandle, manager: &crate::plugin::PluginManager, failed: usize) {
+    let all_data = manager.get_all_data().await;
+    let worst_health = manager.worst_health().await;
+    if let Err(e) = crate::tray::sync_tray_with_data(app, &all_data, failed, worst_health) {
+        log::warn!("更新托盘图标失败: {}", e);
+    }
+}
+
+/// 插件列表变化后重建托盘菜单的插件子菜单
+async fn sync_tray_menu(app: &AppHandle, manager: &crate::plugin::PluginManager) {
+    if let Err(e) = crate::tray::rebuild_menu(app, &manager.list_plugins().await) {
+        log::warn!("重建托盘菜单失败: {}", e);
+    }
+}
+
+/// 回放解析：将存储或粘贴的 fetchData 原始结果重新解析并校验
+///
+/// 不执行插件代码，用于在没有用户凭证的情况下调试解析问题。
+///
+/// # 参数
+/// - `plugin_id`: 插件 ID（用于读取 manifest 声明的 dataType）
+/// - `raw_json`: fetchData 原始返回值的 JSON 文本
+#[command]
+pub async fn replay_parse(
+    plugin_id: String,
+    raw_json: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<ReplayParseResult>, String> {
+    metrics.0.trace("replay_parse", async move {
+        let raw: serde_json::Value = match serde_json::from_str(&raw_json) {
+            Ok(v) => v,
+            Err(e) => {
+                return Ok(IpcResult::err(IpcError::new(
+                    codes::INVALID_JSON,
+                    format!("原始数据不是有效的 JSON: {}", e),
+                ).into()))
+            }
+        };
+
+        match state.0.replay_parse(&plugin_id, raw).await {
+            Ok(result) => Ok(IpcResult::ok(result)),
+            Err(e) => Ok(IpcResult::err(IpcError::new(codes::PLUGIN_NOT_FOUND, e.to_string()).into())),
+        }
+    })
+    .await
+}
+
+/// 试运行插件：执行 fetchData 并按 dataType 和 manifest payloadSchema 校验结果
+///
+/// 不更新缓存和健康统计，未启用的插件也可试运行
+#[command]
+pub async fn plugin_test_run(
+    id: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<ReplayParseResult>, String> {
+    metrics.0.trace("plugin_test_run", async move {
+        match state.0.test_run(&id).await {
+            Ok(result) => Ok(IpcResult::ok(result)),
+            Err(e) => Ok(IpcResult::err(IpcError::lifecycle(codes::PLUGIN_TEST_RUN_FAILED, e).into())),
+        }
+    })
+    .await
 }
 
 // ============================================================================
@@ -311,11 +943,15 @@ pub async fn refresh_all(
 pub async fn get_plugin_config(
     id: String,
     state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<HashMap<String, serde_json::Value>>, String> {
-    match state.0.get_plugin_config(&id).await {
-        Some(config) => Ok(IpcResult::ok(config)),
-        None => Ok(IpcResult::err(AppError::new("PLUGIN_NOT_FOUND", format!("插件不存在: {}", id)))),
-    }
+    metrics.0.trace("get_plugin_config", async move {
+        match state.0.get_plugin_config(&id).await {
+            Some(config) => Ok(IpcResult::ok(config)),
+            None => Ok(IpcResult::err(IpcError::new(codes::PLUGIN_NOT_FOUND, format!("插件不存在: {}", id)).into())),
+        }
+    })
+    .await
 }
 
 /// 设置插件配置
@@ -324,11 +960,15 @@ pub async fn set_plugin_config(
     id: String,
     config: HashMap<String, serde_json::Value>,
     state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<()>, String> {
-    match state.0.set_plugin_config(&id, config).await {
-        Ok(()) => Ok(IpcResult::ok(())),
-        Err(e) => Ok(IpcResult::err(AppError::new("CONFIG_SET_FAILED", e.to_string()))),
-    }
+    metrics.0.trace("set_plugin_config", async move {
+        match state.0.set_plugin_config(&id, config).await {
+            Ok(()) => Ok(IpcResult::ok(())),
+            Err(e) => Ok(IpcResult::err(IpcError::lifecycle(codes::CONFIG_SET_FAILED, e).into())),
+        }
+    })
+    .await
 }
 
 /// 验证插件配置
@@ -337,22 +977,34 @@ pub async fn validate_plugin_config(
     id: String,
     config: HashMap<String, serde_json::Value>,
     state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<ValidationResult>, String> {
-    let result = state.0.validate_plugin_config(&id, &config).await;
-    Ok(IpcResult::ok(result))
+    metrics.0.trace("validate_plugin_config", async move {
+        let result = state.0.validate_plugin_config(&id, &config).await;
+        Ok(IpcResult::ok(result))
+    })
+    .await
 }
 
 // ============================================================================
-// 7.3.4 监控 Commands (2个)
+// 7.3.4 监控 Commands (3个)
 // ============================================================================
 
 /// 获取所有插件健康状态
 #[command]
 pub async fn get_all_health(
     state: State<'_, PluginManagerState>,
+    alerts: State<'_, AlertManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<Vec<PluginHealth>>, String> {
-    let health = state.0.get_all_health().await;
-    Ok(IpcResult::ok(health))
+    metrics.0.trace("get_all_health", async move {
+        let mut health = state.0.get_all_health().await;
+        for item in &mut health {
+            alerts.0.annotate_health(item);
+        }
+        Ok(IpcResult::ok(health))
+    })
+    .await
 }
 
 /// 获取单个插件健康状态
@@ -360,29 +1012,1673 @@ pub async fn get_all_health(
 pub async fn get_plugin_health(
     id: String,
     state: State<'_, PluginManagerState>,
+    alerts: State<'_, AlertManagerState>,
+    metrics: State<'_, IpcMetricsState>,
 ) -> Result<IpcResult<Option<PluginHealth>>, String> {
-    let health = state.0.get_plugin_health(&id).await;
-    Ok(IpcResult::ok(health))
+    metrics.0.trace("get_plugin_health", async move {
+        let mut health = state.0.get_plugin_health(&id).await;
+        if let Some(item) = health.as_mut() {
+            alerts.0.annotate_health(item);
+        }
+        Ok(IpcResult::ok(health))
+    })
+    .await
+}
+
+/// 获取插件沙盒资源占用（CPU 时间、内存，基于最近 N 次执行）
+#[command]
+pub async fn get_plugin_resource_stats(
+    id: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PluginResourceStats>, String> {
+    metrics.0.trace("get_plugin_resource_stats", async move {
+        match state.0.get_plugin_resource_stats(&id).await {
+            Some(stats) => Ok(IpcResult::ok(stats)),
+            None => Ok(IpcResult::err(IpcError::new(codes::PLUGIN_NOT_FOUND, format!("插件不存在: {}", id)).into())),
+        }
+    })
+    .await
+}
+
+/// 获取系统健康状态（插件数量、网络状态、生效的管理员策略）
+#[command]
+pub async fn get_system_health(
+    state: State<'_, PluginManagerState>,
+    network: State<'_, NetworkMonitorState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<SystemHealth>, String> {
+    metrics.0.trace("get_system_health", async move {
+        let plugins = state.0.list_plugins().await;
+        Ok(IpcResult::ok(SystemHealth::new(
+            &plugins,
+            network.0.status(),
+            state.0.admin_policy().status(),
+        )))
+    })
+    .await
+}
+
+/// 死信默认返回数量
+const DEFAULT_DEAD_LETTER_LIMIT: usize = 50;
+
+/// 获取事件总线统计（队列积压、死信数量、各订阅者投递延迟）
+#[command]
+pub async fn get_event_bus_stats(
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<EventBusStats>, String> {
+    metrics.0.trace("get_event_bus_stats", async move {
+        Ok(IpcResult::ok(state.0.event_bus().get_stats().await))
+    })
+    .await
+}
+
+/// 获取投递失败的事件（最新的在前）
+///
+/// # 参数
+/// - `limit`: 返回数量，默认 50
+#[command]
+pub async fn get_dead_letters(
+    limit: Option<usize>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<DeadLetter>>, String> {
+    metrics.0.trace("get_dead_letters", async move {
+        let limit = limit.unwrap_or(DEFAULT_DEAD_LETTER_LIMIT);
+        Ok(IpcResult::ok(state.0.event_bus().get_dead_letters(Some(limit)).await))
+    })
+    .await
 }
 
 // ============================================================================
-// 7.3.5 窗口 Commands
+// 5A.3 Registry 资源 Commands
 // ============================================================================
 
-/// 打开仪表盘窗口并导航到指定路由
+/// 获取插件市场资源（图标/截图）的本地缓存路径
+///
+/// 资源地址取自已验证签名的 registry 索引（管理员策略指定的 registry 或默认地址）。
+///
+/// # 参数
+/// - `id`: registry 中的插件 ID
+/// - `kind`: 资源类型，`icon` 或 `screenshots`
 #[command]
-pub async fn open_dashboard(
+pub async fn get_registry_asset(
+    id: String,
+    kind: String,
+    cache: State<'_, RegistryCacheState>,
+    registry: State<'_, RegistryClientState>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<RegistryAsset>, String> {
+    metrics.0.trace("get_registry_asset", async move {
+        let registry_url = match state.0.admin_policy().registry_url(None) {
+            Ok(url) => url,
+            Err(e) => return Ok(IpcResult::err(e.into())),
+        };
+        let Some(asset_kind) = RegistryAssetKind::parse(&kind) else {
+            return Ok(IpcResult::err(IpcError::new(
+                codes::INVALID_ARGUMENT,
+                format!("不支持的资源类型: {}", kind),
+            ).into()));
+        };
+
+        match cache.0.get_asset(&registry.0, registry_url.as_deref(), &id, asset_kind).await {
+            Ok(asset) => Ok(IpcResult::ok(asset)),
+            Err(e) => {
+                log::warn!("获取 registry 资源失败: plugin={}, kind={}, error={}", id, kind, e);
+                Ok(IpcResult::err(e.into()))
+            }
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 5A.6 插件市场 Commands
+// ============================================================================
+
+/// 搜索 registry 中的插件
+///
+/// # 参数
+/// - `query`: 关键字（匹配 id/名称/描述/作者/标签，为空时返回全部）
+/// - `registry_url`: registry.json URL（为空时使用默认地址）
+#[command]
+pub async fn registry_search(
+    query: Option<String>,
+    registry_url: Option<String>,
+    registry: State<'_, RegistryClientState>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<RegistryEntry>>, String> {
+    metrics.0.trace("registry_search", async move {
+        let registry_url = match state.0.admin_policy().registry_url(registry_url.as_deref()) {
+            Ok(url) => url,
+            Err(e) => return Ok(IpcResult::err(e.into())),
+        };
+        match registry
+            .0
+            .search(registry_url.as_deref(), query.as_deref().unwrap_or(""))
+            .await
+        {
+            Ok(entries) => Ok(IpcResult::ok(entries)),
+            Err(e) => {
+                log::warn!("搜索 registry 失败: {}", e);
+                Ok(IpcResult::err(e.into()))
+            }
+        }
+    })
+    .await
+}
+
+/// 获取 registry 中插件的 manifest（附签名验证结果）
+#[command]
+pub async fn registry_get_manifest(
+    id: String,
+    registry_url: Option<String>,
+    registry: State<'_, RegistryClientState>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<RegistryManifest>, String> {
+    metrics.0.trace("registry_get_manifest", async move {
+        let registry_url = match state.0.admin_policy().registry_url(registry_url.as_deref()) {
+            Ok(url) => url,
+            Err(e) => return Ok(IpcResult::err(e.into())),
+        };
+        match registry.0.manifest(registry_url.as_deref(), &id).await {
+            Ok(manifest) => Ok(IpcResult::ok(manifest)),
+            Err(e) => {
+                log::warn!("获取 registry manifest 失败: plugin={}, error={}", id, e);
+                Ok(IpcResult::err(e.into()))
+            }
+        }
+    })
+    .await
+}
+
+/// 从 registry 安装插件（强制验证签名）
+#[command]
+pub async fn registry_install(
+    window: Window,
     app: AppHandle,
-    route: Option<String>,
-) -> Result<IpcResult<()>, String> {
-    use crate::window::WindowManager;
-
-    let route_ref = route.as_deref();
-    match WindowManager::open_dashboard_with_route(&app, route_ref) {
-        Some(_) => Ok(IpcResult::ok(())),
-        None => Ok(IpcResult::err(AppError::new(
-            "WINDOW_OPEN_FAILED",
-            "打开仪表盘窗口失败",
-        ))),
+    id: String,
+    registry_url: Option<String>,
+    state: State<'_, PluginManagerState>,
+    registry: State<'_, RegistryClientState>,
+    cancels: State<'_, InstallCancelState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PluginInfo>, String> {
+    metrics.0.trace("registry_install", async move {
+        if let Err(error) = authorize_window(&window, "registry_install") {
+            return Ok(IpcResult::err(error));
+        }
+        let progress = Some(install_progress_reporter(&app));
+        let cancel = cancels.0.begin(&id);
+        let result =
+            install_from_registry(state.0.clone(), &registry.0, registry_url.as_deref(), &id, progress, Some(cancel.clone()))
+                .await;
+        cancels.0.finish(&cancel);
+        match result {
+            Ok(plugin_info) => {
+                if let Err(emit_err) = emitter(&app).emit_plugin_installed(&plugin_info) {
+                    log::warn!(
+                        "发送插件安装事件失败: plugin={}, emit_error={}",
+                        plugin_info.id,
+                        emit_err
+                    );
+                }
+                log::info!("插件安装成功: {} v{}", plugin_info.id, plugin_info.version);
+                sync_tray_menu(&app, &state.0).await;
+                Ok(IpcResult::ok(plugin_info))
+            }
+            Err(error) if error.code == codes::INSTALL_CANCELLED => {
+                log::info!("从 registry 安装插件已取消: id={}", id);
+                Ok(IpcResult::err(error))
+            }
+            Err(error) => {
+                if let Err(emit_err) = emitter(&app).emit_plugin_error(&id, &error) {
+                    log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+                }
+                log::error!("从 registry 安装插件失败: id={}, error={}", id, error.message);
+                Ok(IpcResult::err(error))
+            }
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 5A.7 插件更新 Commands
+// ============================================================================
+
+/// 获取插件自动更新设置
+#[command]
+pub async fn get_update_settings(
+    updates: State<'_, UpdateCheckerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<UpdateSettings>, String> {
+    metrics.0.trace("get_update_settings", async move {
+        Ok(IpcResult::ok(updates.0.settings().await))
+    })
+    .await
+}
+
+/// 保存插件自动更新设置（下一轮检查生效）
+#[command]
+pub async fn set_update_settings(
+    window: Window,
+    settings: UpdateSettings,
+    updates: State<'_, UpdateCheckerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("set_update_settings", async move {
+        if let Err(error) = authorize_window(&window, "set_update_settings") {
+            return Ok(IpcResult::err(error));
+        }
+        match updates.0.set_settings(settings).await {
+            Ok(()) => Ok(IpcResult::ok(())),
+            Err(e) => Ok(IpcResult::err(IpcError::new(
+                codes::UPDATE_SETTINGS_SAVE_FAILED,
+                e.to_string(),
+            ).into())),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 6.5 用量提醒 Commands
+// ============================================================================
+
+/// 获取用量提醒设置
+#[command]
+pub async fn get_usage_alert_settings(
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<UsageAlertSettings>, String> {
+    metrics.0.trace("get_usage_alert_settings", async move {
+        Ok(IpcResult::ok(state.0.config_manager().usage_alert_settings().await))
+    })
+    .await
+}
+
+/// 保存用量提醒设置（下次刷新时生效）
+#[command]
+pub async fn set_usage_alert_settings(
+    settings: UsageAlertSettings,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("set_usage_alert_settings", async move {
+        match state.0.config_manager().set_usage_alert_settings(settings).await {
+            Ok(()) => Ok(IpcResult::ok(())),
+            Err(e) => Ok(IpcResult::err(IpcError::new(
+                codes::USAGE_ALERT_SETTINGS_SAVE_FAILED,
+                e.to_string(),
+            ).into())),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 6.6 告警静默 Commands
+// ============================================================================
+
+/// 暂停插件提醒，返回截止时间 (ISO 8601)
+///
+/// # 参数
+/// - `plugin_id`: 插件 ID（`*` 表示全部插件）
+/// - `duration`: 暂停时长（秒）
+#[command]
+pub async fn alert_snooze(
+    plugin_id: String,
+    duration: u64,
+    alerts: State<'_, AlertManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<String>, String> {
+    metrics.0.trace("alert_snooze", async move {
+        if duration == 0 {
+            return Ok(IpcResult::err(IpcError::new(codes::INVALID_ARGUMENT, "暂停时长必须大于 0").into()));
+        }
+        match alerts.0.snooze(&plugin_id, std::time::Duration::from_secs(duration)) {
+            Ok(until) => Ok(IpcResult::ok(format_rfc3339(until))),
+            Err(e) => Ok(IpcResult::err(IpcError::new(codes::ALERT_SILENCE_SAVE_FAILED, e.to_string()).into())),
+        }
+    })
+    .await
+}
+
+/// 静音插件提醒（直到取消静音）
+#[command]
+pub async fn alert_mute(
+    plugin_id: String,
+    alerts: State<'_, AlertManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("alert_mute", async move {
+        match alerts.0.mute(&plugin_id) {
+            Ok(()) => Ok(IpcResult::ok(())),
+            Err(e) => Ok(IpcResult::err(IpcError::new(codes::ALERT_SILENCE_SAVE_FAILED, e.to_string()).into())),
+        }
+    })
+    .await
+}
+
+/// 取消插件提醒的静音和暂停
+#[command]
+pub async fn alert_unmute(
+    plugin_id: String,
+    alerts: State<'_, AlertManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("alert_unmute", async move {
+        match alerts.0.unmute(&plugin_id) {
+            Ok(()) => Ok(IpcResult::ok(())),
+            Err(e) => Ok(IpcResult::err(IpcError::new(codes::ALERT_SILENCE_SAVE_FAILED, e.to_string()).into())),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 6.16 告警历史 Commands
+// ============================================================================
+
+/// 查询告警历史（按触发时间倒序，默认最多 100 条）
+#[command]
+pub async fn get_alert_history(
+    filter: Option<AlertHistoryFilter>,
+    alerts: State<'_, AlertManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<AlertRecord>>, String> {
+    metrics.0.trace("get_alert_history", async move {
+        Ok(IpcResult::ok(alerts.0.query_history(&filter.unwrap_or_default())))
+    })
+    .await
+}
+
+/// 确认告警，返回更新后的记录
+#[command]
+pub async fn acknowledge_alert(
+    id: u64,
+    alerts: State<'_, AlertManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<AlertRecord>, String> {
+    metrics.0.trace("acknowledge_alert", async move {
+        match alerts.0.acknowledge(id) {
+            Some(record) => Ok(IpcResult::ok(record)),
+            None => Ok(IpcResult::err(
+                IpcError::new(codes::ALERT_NOT_FOUND, format!("告警不存在: {}", id)).into(),
+            )),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 6.10 未送达告警 Commands
+// ============================================================================
+
+/// 获取错过的告警（通知发送失败、等待重投的告警）
+#[command]
+pub async fn get_missed_alerts(
+    missed: State<'_, MissedAlertState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<MissedAlertSummary>, String> {
+    metrics.0.trace("get_missed_alerts", async move {
+        Ok(IpcResult::ok(missed.0.summary(chrono::Utc::now())))
+    })
+    .await
+}
+
+/// 清空错过的告警（用户已在应用内查看）
+#[command]
+pub async fn clear_missed_alerts(
+    missed: State<'_, MissedAlertState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("clear_missed_alerts", async move {
+        missed.0.clear();
+        Ok(IpcResult::ok(()))
+    })
+    .await
+}
+
+/// 设置未送达告警的过期时间（小时），返回实际生效值（最长 7 天）
+#[command]
+pub async fn set_missed_alert_expiry(
+    hours: u32,
+    missed: State<'_, MissedAlertState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<u32>, String> {
+    metrics.0.trace("set_missed_alert_expiry", async move {
+        if hours == 0 {
+            return Ok(IpcResult::err(IpcError::new(codes::INVALID_ARGUMENT, "过期时间必须大于 0").into()));
+        }
+        Ok(IpcResult::ok(missed.0.set_expiry_hours(hours)))
+    })
+    .await
+}
+
+// ============================================================================
+// 6.7 隐私报告与存储占用 Commands
+// ============================================================================
+
+/// 获取各插件的存储占用（storage + cache）
+#[command]
+pub async fn get_storage_stats(
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<PluginStorageStats>>, String> {
+    metrics.0.trace("get_storage_stats", async move {
+        Ok(IpcResult::ok(state.0.get_storage_stats().await))
+    })
+    .await
+}
+
+/// 获取插件隐私报告（最近 days 天，默认并最多为统计保留天数）
+#[command]
+pub async fn get_privacy_report(
+    plugin_id: String,
+    days: Option<u32>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PrivacyReport>, String> {
+    metrics.0.trace("get_privacy_report", async move {
+        let days = days
+            .unwrap_or(NETWORK_STATS_RETENTION_DAYS)
+            .clamp(1, NETWORK_STATS_RETENTION_DAYS);
+        match state.0.get_privacy_report(&plugin_id, days).await {
+            Ok(report) => Ok(IpcResult::ok(report)),
+            Err(e) => Ok(IpcResult::err(IpcError::new(codes::PLUGIN_NOT_FOUND, e.to_string()).into())),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 6.12 插件日志与诊断 Commands
+// ============================================================================
+
+/// 获取插件 console 日志（按时间顺序）
+///
+/// # 参数
+/// - `plugin_id`: 插件 ID
+/// - `since`: 只返回该时间 (ISO 8601) 之后的日志
+/// - `level`: 最低日志级别
+#[command]
+pub async fn get_plugin_logs(
+    plugin_id: String,
+    since: Option<String>,
+    level: Option<PluginLogLevel>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<PluginLogEntry>>, String> {
+    metrics.0.trace("get_plugin_logs", async move {
+        let since = match since.as_deref().map(chrono::DateTime::parse_from_rfc3339) {
+            None => None,
+            Some(Ok(since)) => Some(since.with_timezone(&chrono::Utc)),
+            Some(Err(e)) => {
+                return Ok(IpcResult::err(IpcError::new(codes::INVALID_ARGUMENT, format!("无效的时间: {}", e)).into()));
+            }
+        };
+        Ok(IpcResult::ok(state.0.plugin_logs().query(&plugin_id, since, level)))
+    })
+    .await
+}
+
+/// 清除插件 console 日志，返回清除的条数
+#[command]
+pub async fn clear_plugin_logs(
+    plugin_id: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<usize>, String> {
+    metrics.0.trace("clear_plugin_logs", async move {
+        Ok(IpcResult::ok(state.0.plugin_logs().clear(&plugin_id)))
+    })
+    .await
+}
+
+/// 生成插件诊断包（zip），用于附在问题报告中
+#[command]
+pub async fn generate_diagnostics(
+    plugin_id: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<DiagnosticsFile>, String> {
+    metrics.0.trace("generate_diagnostics", async move {
+        if state.0.get_plugin(&plugin_id).await.is_none() {
+            return Ok(IpcResult::err(IpcError::new(
+                codes::PLUGIN_NOT_FOUND,
+                format!("插件不存在: {}", plugin_id),
+            ).into()));
+        }
+        match state.0.generate_diagnostics(&plugin_id).await {
+            Ok(file) => Ok(IpcResult::ok(file)),
+            Err(e) => Ok(IpcResult::err(IpcError::lifecycle(codes::DIAGNOSTICS_FAILED, e).into())),
+        }
+    })
+    .await
+}
+
+/// get_app_logs 默认返回条数
+const DEFAULT_APP_LOG_LINES: usize = 200;
+
+/// get_app_logs 最多返回条数
+const MAX_APP_LOG_LINES: usize = 2000;
+
+/// 获取最近的应用日志（按时间顺序）
+///
+/// `level` 为最低级别 (error/warn/info/debug/trace)，默认返回全部级别
+#[command]
+pub async fn get_app_logs(
+    lines: Option<usize>,
+    level: Option<String>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<AppLogEntry>>, String> {
+    metrics.0.trace("get_app_logs", async move {
+        let min_level = match level.as_deref().map(str::parse::<log::Level>) {
+            None => log::Level::Trace,
+            Some(Ok(level)) => level,
+            Some(Err(_)) => {
+                return Ok(IpcResult::err(IpcError::new(
+                    codes::INVALID_ARGUMENT,
+                    format!("无效的日志级别: {}", level.unwrap_or_default()),
+                ).into()));
+            }
+        };
+        let lines = lines.unwrap_or(DEFAULT_APP_LOG_LINES).min(MAX_APP_LOG_LINES);
+        Ok(IpcResult::ok(logging::read_recent(&logging::log_dir(), lines, min_level)))
+    })
+    .await
+}
+
+// ============================================================================
+// 6.8 预算 Commands
+// ============================================================================
+
+/// 获取本月预算状态（已花费、预计月底花费、是否超出预算）
+#[command]
+pub async fn get_budget_status(
+    budgets: State<'_, BudgetState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<BudgetStatus>, String> {
+    metrics.0.trace("get_budget_status", async move {
+        Ok(IpcResult::ok(budgets.0.status(chrono::Utc::now())))
+    })
+    .await
+}
+
+/// 设置或清除月度预算（pluginId 缺省为全局预算，budget 缺省为清除）
+#[command]
+pub async fn set_budget(
+    plugin_id: Option<String>,
+    budget: Option<Budget>,
+    budgets: State<'_, BudgetState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("set_budget", async move {
+        let budget = match budget {
+            Some(Budget { limit, currency }) => {
+                let currency = currency.trim().to_ascii_uppercase();
+                if !limit.is_finite() || limit <= 0.0 || currency.is_empty() {
+                    return Ok(IpcResult::err(IpcError::new(
+                        codes::INVALID_BUDGET,
+                        "预算上限须为正数，且须指定货币",
+                    ).into()));
+                }
+                Some(Budget { limit, currency })
+            }
+            None => None,
+        };
+
+        match budgets.0.set_budget(plugin_id.as_deref(), budget) {
+            Ok(()) => Ok(IpcResult::ok(())),
+            Err(e) => Ok(IpcResult::err(IpcError::new(codes::BUDGET_SAVE_FAILED, e.to_string()).into())),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 5A.8 开发者模式 Commands
+// ============================================================================
+
+/// 获取开发者模式设置
+#[command]
+pub async fn get_developer_settings(
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<DeveloperSettings>, String> {
+    metrics.0.trace("get_developer_settings", async move {
+        Ok(IpcResult::ok(state.0.developer_settings().await))
+    })
+    .await
+}
+
+/// 保存开发者模式设置，返回当前加载的开发者插件
+///
+/// 前端在信任开发者插件前应向用户展示其 restrictedPermissions 并取得明确确认。
+#[command]
+pub async fn set_developer_settings(
+    window: Window,
+    settings: DeveloperSettings,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<PluginInfo>>, String> {
+    metrics.0.trace("set_developer_settings", async move {
+        if let Err(error) = authorize_window(&window, "set_developer_settings") {
+            return Ok(IpcResult::err(error));
+        }
+        match state.0.set_developer_settings(settings).await {
+            Ok(plugins) => Ok(IpcResult::ok(plugins)),
+            Err(e) => Ok(IpcResult::err(IpcError::new(
+                codes::DEVELOPER_SETTINGS_SAVE_FAILED,
+                e.to_string(),
+            ).into())),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 5A.9 .env 密钥导入 Commands
+// ============================================================================
+
+/// 从用户选择的 .env 文件导入已知 API Key（仅开发者模式）
+///
+/// 值写入 Keychain，报告中只包含变量名与插件 ID。
+#[command]
+pub async fn import_keys_from_env(
+    window: Window,
+    path: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<EnvImportReport>, String> {
+    metrics.0.trace("import_keys_from_env", async move {
+        if let Err(error) = authorize_window(&window, "import_keys_from_env") {
+            return Ok(IpcResult::err(error));
+        }
+        match env_import::import_keys_from_env(&state.0, Path::new(&path), &KeychainStore).await {
+            Ok(report) => Ok(IpcResult::ok(report)),
+            Err(e) => Ok(IpcResult::err(AppError::new(e.code(), e.to_string()))),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// Keychain Commands
+// ============================================================================
+
+/// 写入 Keychain 项（仅设置/仪表盘窗口）
+#[command]
+pub async fn keychain_set(
+    window: Window,
+    service: String,
+    key: String,
+    value: String,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("keychain_set", async move {
+        if let Err(error) = authorize_window(&window, "keychain_set") {
+            return Ok(IpcResult::err(error));
+        }
+        match KeychainStore.set(&service, &key, &value) {
+            Ok(()) => Ok(IpcResult::ok(())),
+            Err(e) => Ok(IpcResult::err(AppError::new(codes::SECRET_STORE_FAILED, e))),
+        }
+    })
+    .await
+}
+
+/// 读取 Keychain 项，不存在时返回 null（仅设置/仪表盘窗口）
+#[command]
+pub async fn keychain_get(
+    window: Window,
+    service: String,
+    key: String,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Option<String>>, String> {
+    metrics.0.trace("keychain_get", async move {
+        if let Err(error) = authorize_window(&window, "keychain_get") {
+            return Ok(IpcResult::err(error));
+        }
+        match KeychainStore.get(&service, &key) {
+            Ok(value) => Ok(IpcResult::ok(value)),
+            Err(e) => Ok(IpcResult::err(AppError::new(codes::SECRET_STORE_FAILED, e))),
+        }
+    })
+    .await
+}
+
+/// 删除 Keychain 项，不存在的项不报错（仅设置/仪表盘窗口）
+#[command]
+pub async fn keychain_delete(
+    window: Window,
+    service: String,
+    key: String,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("keychain_delete", async move {
+        if let Err(error) = authorize_window(&window, "keychain_delete") {
+            return Ok(IpcResult::err(error));
+        }
+        match KeychainStore.delete(&service, &key) {
+            Ok(()) => Ok(IpcResult::ok(())),
+            Err(e) => Ok(IpcResult::err(AppError::new(codes::SECRET_STORE_FAILED, e))),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 4.6 数据推送 Commands
+// ============================================================================
+
+/// 获取本地推送端点设置（tokens 仅含哈希）
+#[command]
+pub async fn get_push_settings(
+    push: State<'_, PushServerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PushSettings>, String> {
+    metrics.0.trace("get_push_settings", async move {
+        Ok(IpcResult::ok(push.0.settings().await))
+    })
+    .await
+}
+
+/// 开启/关闭本地推送端点（可同时修改端口）
+#[command]
+pub async fn set_push_settings(
+    app: AppHandle,
+    window: Window,
+    enabled: bool,
+    port: Option<u16>,
+    push: State<'_, PushServerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PushSettings>, String> {
+    metrics.0.trace("set_push_settings", async move {
+        if let Err(error) = authorize_window(&window, "set_push_settings") {
+            return Ok(IpcResult::err(error));
+        }
+        match push.0.configure(app, enabled, port).await {
+            Ok(settings) => Ok(IpcResult::ok(settings)),
+            Err(e) => Ok(IpcResult::err(e)),
+        }
+    })
+    .await
+}
+
+/// 为插件签发推送 token（明文仅返回这一次）
+#[command]
+pub async fn issue_push_token(
+    window: Window,
+    id: String,
+    push: State<'_, PushServerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<String>, String> {
+    metrics.0.trace("issue_push_token", async move {
+        if let Err(error) = authorize_window(&window, "issue_push_token") {
+            return Ok(IpcResult::err(error));
+        }
+        match push.0.issue_token(&id).await {
+            Ok(token) => Ok(IpcResult::ok(token)),
+            Err(e) => Ok(IpcResult::err(e)),
+        }
+    })
+    .await
+}
+
+/// 吊销插件的推送 token
+#[command]
+pub async fn revoke_push_token(
+    id: String,
+    push: State<'_, PushServerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<bool>, String> {
+    metrics.0.trace("revoke_push_token", async move {
+        match push.0.revoke_token(&id).await {
+            Ok(revoked) => Ok(IpcResult::ok(revoked)),
+            Err(e) => Ok(IpcResult::err(e)),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 7.3.5 窗口 Commands
+// ============================================================================
+
+/// 打开仪表盘窗口并导航到指定路由
+#[command]
+pub async fn open_dashboard(
+    app: AppHandle,
+    route: Option<String>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("open_dashboard", async move {
+        use crate::window::WindowManager;
+
+        let route_ref = route.as_deref();
+        match WindowManager::open_dashboard_with_route(&app, route_ref) {
+            Some(_) => Ok(IpcResult::ok(())),
+            None => Ok(IpcResult::err(IpcError::new(
+                codes::WINDOW_OPEN_FAILED,
+                "打开仪表盘窗口失败",
+            ).into())),
+        }
+    })
+    .await
+}
+
+/// 获取托盘弹窗是否固定（固定时失焦不自动隐藏）
+#[command]
+pub async fn get_popup_pinned(
+    state: State<'_, PopupState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<bool>, String> {
+    metrics.0.trace("get_popup_pinned", async move {
+        Ok(IpcResult::ok(state.is_pinned()))
+    })
+    .await
+}
+
+/// 设置托盘弹窗是否固定
+#[command]
+pub async fn set_popup_pinned(
+    pinned: bool,
+    state: State<'_, PopupState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("set_popup_pinned", async move {
+        state.set_pinned(pinned);
+        log::debug!("托盘弹窗固定状态: {}", pinned);
+        Ok(IpcResult::ok(()))
+    })
+    .await
+}
+
+/// 获取弹窗摘要（插件列表、数据、健康状态、网络状态）
+///
+/// 延迟预算 150ms，超预算后返回快照（snapshotOnly=true）直到竞争消退
+#[command]
+pub async fn get_popup_summary(
+    state: State<'_, PluginManagerState>,
+    network: State<'_, NetworkMonitorState>,
+    summary: State<'_, PopupSummaryState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PopupSummary>, String> {
+    metrics.0.trace("get_popup_summary", async move {
+        let summary = summary.0.get(&state.0, &network.0).await;
+        Ok(IpcResult::ok(summary))
+    })
+    .await
+}
+
+/// 获取插件最近 24 小时的使用率趋势（弹窗迷你折线图）
+///
+/// points 缺省为 24，最多 96；每个点为该时间段内的峰值，无数据时为 null
+#[command]
+pub async fn get_usage_trend(
+    plugin_id: String,
+    points: Option<u32>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<UsageTrend>, String> {
+    metrics.0.trace("get_usage_trend", async move {
+        let points = points.unwrap_or(DEFAULT_TREND_POINTS);
+        match state.0.get_usage_trend(&plugin_id, points).await {
+            Ok(trend) => Ok(IpcResult::ok(trend)),
+            Err(e) => Ok(IpcResult::err(IpcError::new(codes::PLUGIN_NOT_FOUND, e.to_string()).into())),
+        }
+    })
+    .await
+}
+
+/// 按模型价格估算插件的花费（Phase 6.14）
+///
+/// range 为 day / week / month（今天 / 最近 7 天 / 最近 30 天，按 UTC 日期），缺省为 month；
+/// 只有上报了 token 明细的使用量维度参与估算
+#[command]
+pub async fn estimate_cost(
+    plugin_id: String,
+    range: Option<CostRange>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<CostEstimate>, String> {
+    metrics.0.trace("estimate_cost", async move {
+        match state.0.estimate_cost(&plugin_id, range.unwrap_or_default()).await {
+            Ok(estimate) => Ok(IpcResult::ok(estimate)),
+            Err(e) => Ok(IpcResult::err(IpcError::new(codes::PLUGIN_NOT_FOUND, e.to_string()).into())),
+        }
+    })
+    .await
+}
+
+/// 获取当前生效的模型价格表
+#[command]
+pub async fn get_price_table(
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PriceTable>, String> {
+    metrics.0.trace("get_price_table", async move {
+        Ok(IpcResult::ok(state.0.cost_estimator().price_table()))
+    })
+    .await
+}
+
+/// 从远程 JSON 更新模型价格表（url 缺省为随发布附带的 pricing.json）
+///
+/// 远程价格按模型覆盖内置价格，返回合并后的价格表
+#[command]
+pub async fn update_price_table(
+    url: Option<String>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PriceTable>, String> {
+    metrics.0.trace("update_price_table", async move {
+        let url = url.unwrap_or_else(|| DEFAULT_PRICING_URL.to_string());
+        let result = match pricing::fetch_price_table(&url).await {
+            Ok(remote) => state.0.cost_estimator().update_price_table(remote),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(table) => {
+                log::info!("价格表已更新: version={}, models={}", table.version, table.models.len());
+                Ok(IpcResult::ok(table))
+            }
+            Err(e) => Ok(IpcResult::err(AppError::new(e.code(), e.to_string()))),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 7.3.6 托盘 Commands
+// ============================================================================
+
+/// 获取托盘点击配置
+#[command]
+pub async fn get_tray_click_config(
+    settings: State<'_, SettingsState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<TrayClickConfig>, String> {
+    metrics.0.trace("get_tray_click_config", async move {
+        Ok(IpcResult::ok(settings.0.get().tray_click))
+    })
+    .await
+}
+
+/// 设置托盘点击配置（保存到应用设置的 trayClick，同步到所有窗口并更新工具提示）
+#[command]
+pub async fn set_tray_click_config(
+    app: AppHandle,
+    config: TrayClickConfig,
+    settings: State<'_, SettingsState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("set_tray_click_config", async move {
+        let previous = settings.0.get();
+        match settings.0.update(&serde_json::json!({ "trayClick": config })) {
+            Ok(updated) => {
+                broadcast_settings(&app, &previous, &updated, None);
+                Ok(IpcResult::ok(()))
+            }
+            Err(e) => Ok(IpcResult::err(AppError::new(e.code(), e.to_string()))),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 7.3.7 插件输入请求 Commands
+// ============================================================================
+
+/// 获取待处理的插件输入请求（输入对话框打开时拉取）
+#[command]
+pub async fn get_pending_inputs(
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<InputRequest>>, String> {
+    metrics.0.trace("get_pending_inputs", async move {
+        Ok(IpcResult::ok(state.0.input_broker().pending_requests()))
+    })
+    .await
+}
+
+/// 提交插件输入请求的响应（`values` 为空表示用户取消）
+///
+/// 没有剩余待处理请求时自动关闭输入对话框
+#[command]
+pub async fn submit_input(
+    app: AppHandle,
+    request_id: String,
+    values: Option<HashMap<String, String>>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("submit_input", async move {
+        use crate::plugin::sandbox::input::InputError;
+        use crate::window::{WindowManager, WindowType};
+
+        let broker = state.0.input_broker();
+        let result = match broker.respond(&request_id, values) {
+            Ok(()) => IpcResult::ok(()),
+            Err(e @ InputError::NotFound(_)) => {
+                IpcResult::err(IpcError::new(codes::INPUT_NOT_FOUND, e.to_string()).into())
+            }
+            Err(e) => IpcResult::err(IpcError::new(codes::INVALID_INPUT, e.to_string()).into()),
+        };
+
+        if broker.pending_requests().is_empty() {
+            WindowManager::close(&app, WindowType::Input.config().label);
+        }
+        Ok(result)
+    })
+    .await
+}
+
+// ============================================================================
+// 7.3.8 网络状态 Commands
+// ============================================================================
+
+/// 获取网络状态（在线/离线、离线起始时间、排队刷新的插件）
+#[command]
+pub async fn get_network_status(
+    network: State<'_, NetworkMonitorState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<NetworkStatus>, String> {
+    metrics.0.trace("get_network_status", async move {
+        Ok(IpcResult::ok(network.0.status()))
+    })
+    .await
+}
+
+// ============================================================================
+// 7.3.9 完整性校验 Commands
+// ============================================================================
+
+/// 审计事件默认返回条数
+const DEFAULT_AUDIT_LIMIT: usize = 100;
+
+/// 校验插件文件完整性（篡改时隔离插件）
+#[command]
+pub async fn verify_plugin_integrity(
+    app: AppHandle,
+    id: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<IntegrityReport>, String> {
+    metrics.0.trace("verify_plugin_integrity", async move {
+        if state.0.get_plugin(&id).await.is_none() {
+            return Ok(IpcResult::err(IpcError::new(
+                codes::PLUGIN_NOT_FOUND,
+                format!("插件不存在: {}", id),
+            ).into()));
+        }
+
+        match state.0.verify_plugin_integrity(&id).await {
+            Ok(report) => {
+                notify_tampered(&app, &report);
+                Ok(IpcResult::ok(report))
+            }
+            Err(e) => Ok(IpcResult::err(IpcError::lifecycle(codes::INTEGRITY_CHECK_FAILED, e).into())),
+        }
+    })
+    .await
+}
+
+/// 获取最近的安全审计事件
+#[command]
+pub async fn get_audit_events(
+    limit: Option<usize>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<AuditEvent>>, String> {
+    metrics.0.trace("get_audit_events", async move {
+        let events = state.0.recent_audit_events(limit.unwrap_or(DEFAULT_AUDIT_LIMIT));
+        Ok(IpcResult::ok(events))
+    })
+    .await
+}
+
+/// 获取签名信任库（嵌入的根密钥和发布者密钥）
+#[command]
+pub async fn get_publisher_keys(
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PublisherKeyStatus>, String> {
+    metrics.0.trace("get_publisher_keys", async move {
+        Ok(IpcResult::ok(state.0.publisher_keys().status()))
+    })
+    .await
+}
+
+/// 下载并应用根密钥签名的发布者密钥列表（密钥轮换与吊销）
+///
+/// 应用后重新发现插件，按新的信任库重新评估各插件的信任级别。
+#[command]
+pub async fn update_publisher_keys(
+    window: Window,
+    url: Option<String>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PublisherKeyStatus>, String> {
+    metrics.0.trace("update_publisher_keys", async move {
+        if let Err(error) = authorize_window(&window, "update_publisher_keys") {
+            return Ok(IpcResult::err(error));
+        }
+        let url = url.unwrap_or_else(|| DEFAULT_PUBLISHER_KEYS_URL.to_string());
+        let result = match fetch_key_list(&url).await {
+            Ok(raw) => state.0.publisher_keys().apply(&raw),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(status) => {
+                if let Err(e) = state.0.discover_and_load().await {
+                    log::warn!("密钥列表更新后重新加载插件失败: {}", e);
+                }
+                Ok(IpcResult::ok(status))
+            }
+            Err(e) => {
+                log::warn!("更新发布者密钥列表失败: url={}, error={}", url, e);
+                Ok(IpcResult::err(AppError::new(e.code(), e.to_string())))
+            }
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 7.3.10 后台活动 Commands
+// ============================================================================
+
+/// 获取是否在批量刷新期间阻止 App Nap
+#[command]
+pub async fn get_app_nap_prevention(
+    activity: State<'_, ActivityAssertions>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<bool>, String> {
+    metrics.0.trace("get_app_nap_prevention", async move {
+        Ok(IpcResult::ok(activity.is_enabled()))
+    })
+    .await
+}
+
+/// 设置是否在批量刷新期间阻止 App Nap（电池敏感用户可关闭）
+#[command]
+pub async fn set_app_nap_prevention(
+    enabled: bool,
+    activity: State<'_, ActivityAssertions>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("set_app_nap_prevention", async move {
+        activity.set_enabled(enabled);
+        log::info!("App Nap 规避: {}", if enabled { "开启" } else { "关闭" });
+        Ok(IpcResult::ok(()))
+    })
+    .await
+}
+
+// ============================================================================
+// 7.3.11 网关 Commands
+// ============================================================================
+
+/// 获取宿主网关配置
+#[command]
+pub async fn get_gateway_config(
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<GatewayConfig>, String> {
+    metrics.0.trace("get_gateway_config", async move {
+        Ok(IpcResult::ok(state.0.gateway_config().await))
+    })
+    .await
+}
+
+/// 更新宿主网关配置（仅设置/仪表盘窗口）
+#[command]
+pub async fn set_gateway_config(
+    window: Window,
+    config: GatewayConfig,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("set_gateway_config", async move {
+        if let Err(error) = authorize_window(&window, "set_gateway_config") {
+            return Ok(IpcResult::err(error));
+        }
+        match state.0.set_gateway_config(config).await {
+            Ok(()) => Ok(IpcResult::ok(())),
+            Err(e) => Ok(IpcResult::err(IpcError::new(
+                codes::INVALID_GATEWAY_CONFIG,
+                e.to_string(),
+            ).into())),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 7.3.13 旧版命令 Commands
+// ============================================================================
+
+/// 获取 Phase 2 旧版命令的调用统计（用于规划移除）
+#[command]
+pub async fn get_legacy_command_report(
+    legacy: State<'_, LegacyCommandState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<LegacyCommandReport>, String> {
+    metrics.0.trace("get_legacy_command_report", async move {
+        Ok(IpcResult::ok(legacy.0.report()))
+    })
+    .await
+}
+
+/// 允许/停用 Phase 2 旧版命令
+#[command]
+pub async fn set_legacy_commands_enabled(
+    enabled: bool,
+    legacy: State<'_, LegacyCommandState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("set_legacy_commands_enabled", async move {
+        match legacy.0.set_enabled(enabled) {
+            Ok(()) => {
+                log::info!("Phase 2 旧版命令: {}", if enabled { "允许" } else { "停用" });
+                Ok(IpcResult::ok(()))
+            }
+            Err(e) => Ok(IpcResult::err(IpcError::new(
+                codes::LEGACY_SETTINGS_SAVE_FAILED,
+                e.to_string(),
+            ).into())),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 7.2 首次设置向导 Commands
+// ============================================================================
+
+/// 获取首次设置向导状态
+#[command]
+pub async fn wizard_get_state(
+    wizard: State<'_, SetupWizardState>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<WizardState>, String> {
+    metrics.0.trace("wizard_get_state", async move {
+        Ok(IpcResult::ok(wizard::wizard_state(&wizard.0, &state.0).await))
+    })
+    .await
+}
+
+/// 完成向导的一个步骤（只能重做已完成的步骤或完成当前步骤）
+#[command]
+pub async fn wizard_complete_step(
+    app: AppHandle,
+    input: WizardStepInput,
+    wizard: State<'_, SetupWizardState>,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<WizardState>, String> {
+    metrics.0.trace("wizard_complete_step", async move {
+        let changes_plugins = matches!(input, WizardStepInput::Plugins { .. });
+        if let Err(e) = wizard::complete_wizard_step(&wizard.0, &state.0, input).await {
+            return Ok(IpcResult::err(AppError::new(e.code(), e.to_string())));
+        }
+        if changes_plugins {
+            sync_tray_menu(&app, &state.0).await;
+        }
+        Ok(IpcResult::ok(wizard::wizard_state(&wizard.0, &state.0).await))
+    })
+    .await
+}
+
+/// 结束首次设置向导：写入完成标记、通知各窗口并关闭向导窗口
+#[command]
+pub async fn wizard_finish(
+    app: AppHandle,
+    wizard: State<'_, SetupWizardState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<WizardProgress>, String> {
+    metrics.0.trace("wizard_finish", async move {
+        use crate::window::{WindowManager, WindowType};
+
+        let progress = match wizard.0.finish() {
+            Ok(progress) => progress,
+            Err(e) => return Ok(IpcResult::err(AppError::new(e.code(), e.to_string()))),
+        };
+        // 向导中选择的刷新间隔写入应用设置
+        if let Some(settings) = app.try_state::<SettingsState>() {
+            let previous = settings.0.get();
+            let patch = serde_json::json!({ "refresh": { "intervalMs": progress.refresh_interval_ms } });
+            match settings.0.update(&patch) {
+                Ok(updated) => broadcast_settings(&app, &previous, &updated, None),
+                Err(e) => log::warn!("保存向导选择的刷新间隔失败: {}", e),
+            }
+        }
+        if let Err(e) = emitter(&app).emit_wizard_finished(&progress) {
+            log::warn!("发送首次设置完成事件失败: {}", e);
+        }
+        WindowManager::close(&app, WindowType::Wizard.config().label);
+        Ok(IpcResult::ok(progress))
+    })
+    .await
+}
+
+// ============================================================================
+// 7.6 应用设置 Commands
+// ============================================================================
+
+/// 获取应用设置
+#[command]
+pub async fn get_app_settings(
+    settings: State<'_, SettingsState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<AppSettings>, String> {
+    metrics.0.trace("get_app_settings", async move {
+        Ok(IpcResult::ok(settings.0.get()))
+    })
+    .await
+}
+
+/// 修改应用设置（JSON merge patch：只提交要修改的字段，null 恢复默认值）
+#[command]
+pub async fn set_app_settings(
+    patch: serde_json::Value,
+    app: AppHandle,
+    window: Window,
+    settings: State<'_, SettingsState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<AppSettings>, String> {
+    metrics.0.trace("set_app_settings", async move {
+        if let Err(error) = authorize_window(&window, "set_app_settings") {
+            return Ok(IpcResult::err(error));
+        }
+        let previous = settings.0.get();
+        match settings.0.update(&patch) {
+            Ok(updated) => {
+                broadcast_settings(&app, &previous, &updated, Some(window.label()));
+                Ok(IpcResult::ok(updated))
+            }
+            Err(e) => Ok(IpcResult::err(AppError::new(e.code(), e.to_string()))),
+        }
+    })
+    .await
+}
+
+/// 获取当前主题（模式 + 解析后的实际外观）
+#[command]
+pub async fn get_theme(
+    app: AppHandle,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<ThemeChangedPayload>, String> {
+    metrics.0.trace("get_theme", async move {
+        Ok(IpcResult::ok(crate::window::theme::current_theme(&app)))
+    })
+    .await
+}
+
+/// 恢复默认应用设置
+#[command]
+pub async fn reset_app_settings(
+    app: AppHandle,
+    window: Window,
+    settings: State<'_, SettingsState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<AppSettings>, String> {
+    metrics.0.trace("reset_app_settings", async move {
+        if let Err(error) = authorize_window(&window, "reset_app_settings") {
+            return Ok(IpcResult::err(error));
+        }
+        let previous = settings.0.get();
+        match settings.0.reset() {
+            Ok(defaults) => {
+                broadcast_settings(&app, &previous, &defaults, Some(window.label()));
+                Ok(IpcResult::ok(defaults))
+            }
+            Err(e) => Ok(IpcResult::err(AppError::new(e.code(), e.to_string()))),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 7.3.14 IPC 追踪 Commands
+// ============================================================================
+
+/// 获取 IPC 命令调用统计（调试面板，自身不计入统计）
+#[command]
+pub async fn get_ipc_metrics(
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<IpcMetricsReport>, String> {
+    Ok(IpcResult::ok(metrics.0.report()))
+}
+
+// ============================================================================
+// 7.12 应用更新 Commands
+// ============================================================================
+
+/// 获取应用更新状态（不发起网络请求）
+#[command]
+pub async fn get_app_update_status(
+    updater: State<'_, AppUpdaterState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<AppUpdateStatus>, String> {
+    metrics.0.trace("get_app_update_status", async move {
+        Ok(IpcResult::ok(updater.0.status()))
+    })
+    .await
+}
+
+/// 立即检查应用新版本
+#[command]
+pub async fn check_for_app_updates(
+    app: AppHandle,
+    updater: State<'_, AppUpdaterState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<AppUpdateStatus>, String> {
+    metrics.0.trace("check_for_app_updates", async move {
+        match updater.0.check().await {
+            Ok(status) => {
+                updater::notify_status(&app, &status);
+                Ok(IpcResult::ok(status))
+            }
+            Err(e) => Ok(IpcResult::err(AppError::new(e.code(), e.to_string()))),
+        }
+    })
+    .await
+}
+
+/// 下载新版本安装包（进度通过 app_update:status 事件通知，仅设置/仪表盘/关于窗口）
+#[command]
+pub async fn download_app_update(
+    window: Window,
+    app: AppHandle,
+    updater: State<'_, AppUpdaterState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<AppUpdateStatus>, String> {
+    metrics.0.trace("download_app_update", async move {
+        if let Err(error) = authorize_window_in(&window, "download_app_update", &APP_UPDATE_WINDOWS) {
+            return Ok(IpcResult::err(error));
+        }
+        let result = updater
+            .0
+            .download(|progress| updater::notify_status(&app, progress))
+            .await;
+        // 失败时也通知，让其他窗口回到 available 状态
+        updater::notify_status(&app, &updater.0.status());
+        match result {
+            Ok(status) => Ok(IpcResult::ok(status)),
+            Err(e) => Ok(IpcResult::err(AppError::new(e.code(), e.to_string()))),
+        }
+    })
+    .await
+}
+
+/// 安装已下载的更新并重启应用（前端在用户确认后调用，仅设置/仪表盘/关于窗口）
+#[command]
+pub async fn install_app_update(
+    window: Window,
+    app: AppHandle,
+    updater: State<'_, AppUpdaterState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("install_app_update", async move {
+        if let Err(error) = authorize_window_in(&window, "install_app_update", &APP_UPDATE_WINDOWS) {
+            return Ok(IpcResult::err(error));
+        }
+        match updater.0.install() {
+            Ok(version) => {
+                log::info!("更新 {} 已安装，重启应用", version);
+                updater::notify_status(&app, &updater.0.status());
+                app.request_restart();
+                Ok(IpcResult::ok(()))
+            }
+            Err(e) => Ok(IpcResult::err(AppError::new(e.code(), e.to_string()))),
+        }
+    })
+    .await
+}
+
+// ============================================================================
+// 7.14 多账户 Commands
+// ============================================================================
+
+/// 为数据插件添加账户（新账户默认禁用，配置凭证后启用；仅设置/仪表盘窗口）
+#[command]
+pub async fn add_plugin_account(
+    window: Window,
+    app: AppHandle,
+    plugin_id: String,
+    label: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<PluginInfo>, String> {
+    metrics.0.trace("add_plugin_account", async move {
+        if let Err(error) = authorize_window(&window, "add_plugin_account") {
+            return Ok(IpcResult::err(error));
+        }
+        match state.0.add_plugin_account(&plugin_id, &label).await {
+            Ok(info) => {
+                sync_tray_menu(&app, &state.0).await;
+                Ok(IpcResult::ok(info))
+            }
+            Err(e) => Ok(IpcResult::err(IpcError::lifecycle(codes::PLUGIN_ACCOUNT_FAILED, e).into())),
+        }
+    })
+    .await
+}
+
+/// 移除插件账户（配置、数据和存储一并删除，插件本身保留）
+#[command]
+pub async fn remove_plugin_account(
+    window: Window,
+    app: AppHandle,
+    instance_id: String,
+    state: State<'_, PluginManagerState>,
+    diff: State<'_, DataDiffState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    metrics.0.trace("remove_plugin_account", async move {
+        if let Err(error) = authorize_window(&window, "remove_plugin_account") {
+            return Ok(IpcResult::err(error));
+        }
+        match state.0.remove_plugin_account(&instance_id).await {
+            Ok(()) => {
+                diff.0.remove_plugin(&instance_id);
+                sync_tray_menu(&app, &state.0).await;
+                Ok(IpcResult::ok(()))
+            }
+            Err(e) => Ok(IpcResult::err(IpcError::lifecycle(codes::PLUGIN_ACCOUNT_FAILED, e).into())),
+        }
+    })
+    .await
+}
+
+/// 获取插件所有账户的数据（按账户聚合）
+#[command]
+pub async fn get_plugin_accounts(
+    plugin_id: String,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<PluginAccountData>>, String> {
+    metrics.0.trace("get_plugin_accounts", async move {
+        match state.0.get_plugin_accounts(&plugin_id).await {
+            Ok(accounts) => Ok(IpcResult::ok(accounts)),
+            Err(e) => Ok(IpcResult::err(IpcError::lifecycle(codes::PLUGIN_NOT_FOUND, e).into())),
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 需要限制调用窗口的敏感命令
+    const GATED_COMMANDS: &[&str] = &[
+        "plugin_install",
+        "plugin_install_from_file",
+        "plugin_install_cancel",
+        "plugin_uninstall",
+        "plugin_update",
+        "plugin_rollback",
+        "rollback_to",
+        "registry_install",
+        "set_update_settings",
+        "set_developer_settings",
+        "import_keys_from_env",
+        "keychain_set",
+        "keychain_get",
+        "keychain_delete",
+        "set_push_settings",
+        "issue_push_token",
+        "update_publisher_keys",
+        "set_gateway_config",
+        "set_app_settings",
+        "reset_app_settings",
+        "download_app_update",
+        "install_app_update",
+        "add_plugin_account",
+        "remove_plugin_account",
+    ];
+
+    #[test]
+    fn test_privileged_windows() {
+        assert!(is_allowed_window(WindowType::Settings.config().label, &PRIVILEGED_WINDOWS));
+        assert!(is_allowed_window(WindowType::Dashboard.config().label, &PRIVILEGED_WINDOWS));
+        for window_type in [WindowType::Popup, WindowType::Wizard, WindowType::About, WindowType::Input, WindowType::Hud] {
+            assert!(!is_allowed_window(window_type.config().label, &PRIVILEGED_WINDOWS));
+        }
+
+        // 应用更新额外允许关于窗口，托盘弹窗和 HUD 仍被拒绝
+        assert!(is_allowed_window(WindowType::About.config().label, &APP_UPDATE_WINDOWS));
+        assert!(!is_allowed_window(WindowType::Popup.config().label, &APP_UPDATE_WINDOWS));
+        assert!(!is_allowed_window(WindowType::Hud.config().label, &APP_UPDATE_WINDOWS));
+    }
+
+    #[test]
+    fn test_sensitive_commands_are_gated() {
+        let source = include_str!("ipc.rs");
+        for command in GATED_COMMANDS {
+            let start = source
+                .find(&format!("pub async fn {}(", command))
+                .unwrap_or_else(|| panic!("命令 {} 不存在", command));
+            let body = &source[start..];
+            let end = body.find("#[command]").unwrap_or(body.len());
+            let body = &body[..end];
+            assert!(
+                body.contains(&format!("authorize_window(&window, \"{}\")", command))
+                    || body.contains(&format!("authorize_window_in(&window, \"{}\",", command)),
+                "命令 {} 未检查调用窗口",
+                command
+            );
+        }
     }
 }
@@ -0,0 +1,218 @@
+// Phase 7.3.14: IPC 命令追踪
+// commands::ipc 中的命令统一经 IpcMetrics::trace 执行:
+// - 每次调用分配请求 ID（ipc-1, ipc-2 …），进入/退出时以 debug 级别记录命令名、耗时和结果
+// - 超过 SLOW_COMMAND_THRESHOLD 的调用以 warn 级别记录，便于排查界面卡顿
+// - 按命令累计调用次数、失败次数（IpcResult 失败或命令返回 Err）、耗时，
+//   通过 get_ipc_metrics 提供给调试面板；统计只在内存中保存，重启后清零
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::plugin::types::Result as IpcResult;
+
+/// 慢调用阈值
+const SLOW_COMMAND_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// 单个命令的累计统计
+#[derive(Debug, Clone, Default)]
+struct CommandStats {
+    invocations: u64,
+    errors: u64,
+    in_flight: u64,
+    total: Duration,
+    max: Duration,
+    last_error_code: Option<String>,
+}
+
+/// 单个命令的统计结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpcCommandMetrics {
+    /// 命令名
+    pub command: String,
+    /// 调用次数（已完成）
+    pub invocations: u64,
+    /// 失败次数
+    pub errors: u64,
+    /// 失败率 (0-1)
+    pub error_rate: f64,
+    /// 正在执行的调用数
+    pub in_flight: u64,
+    /// 平均耗时 (毫秒)
+    pub avg_duration_ms: f64,
+    /// 最大耗时 (毫秒)
+    pub max_duration_ms: f64,
+    /// 最近一次失败的错误码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error_code: Option<String>,
+}
+
+/// IPC 调用统计（get_ipc_metrics 返回值）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpcMetricsReport {
+    /// 统计开始时间 (ISO 8601)
+    pub since: String,
+    /// 总调用次数
+    pub total_invocations: u64,
+    /// 总失败次数
+    pub total_errors: u64,
+    /// 各命令统计（按命令名排序）
+    pub commands: Vec<IpcCommandMetrics>,
+}
+
+/// IPC 命令追踪状态
+pub struct IpcMetricsState(pub Arc<IpcMetrics>);
+
+/// 创建 IPC 命令追踪状态
+pub fn create_ipc_metrics() -> IpcMetricsState {
+    IpcMetricsState(Arc::new(IpcMetrics::new()))
+}
+
+/// IPC 命令追踪器
+pub struct IpcMetrics {
+    next_id: AtomicU64,
+    since: String,
+    commands: Mutex<BTreeMap<&'static str, CommandStats>>,
+}
+
+impl Default for IpcMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IpcMetrics {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            since: Utc::now().to_rfc3339(),
+            commands: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<&'static str, CommandStats>> {
+        self.commands.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 开始一次调用，返回请求 ID
+    fn begin(&self, command: &'static str) -> String {
+        self.lock().entry(command).or_default().in_flight += 1;
+        format!("ipc-{}", self.next_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// 结束一次调用，error_code 为 None 表示成功
+    fn finish(&self, command: &'static str, elapsed: Duration, error_code: Option<&str>) {
+        let mut commands = self.lock();
+        let stats = commands.entry(command).or_default();
+        stats.in_flight = stats.in_flight.saturating_sub(1);
+        stats.invocations += 1;
+        stats.total += elapsed;
+        stats.max = stats.max.max(elapsed);
+        if let Some(code) = error_code {
+            stats.errors += 1;
+            stats.last_error_code = Some(code.to_string());
+        }
+    }
+
+    /// 执行命令并记录请求 ID、耗时和结果
+    pub async fn trace<T, F>(&self, command: &'static str, future: F) -> Result<IpcResult<T>, String>
+    where
+        F: Future<Output = Result<IpcResult<T>, String>>,
+    {
+        let request_id = self.begin(command);
+        log::debug!("[{}] {} 开始", request_id, command);
+        let started = Instant::now();
+
+        let result = future.await;
+
+        let elapsed = started.elapsed();
+        let error_code = match &result {
+            Ok(response) if response.success => None,
+            Ok(response) => Some(response.error.as_ref().map_or("UNKNOWN", |e| e.code.as_str())),
+            Err(_) => Some("COMMAND_FAILED"),
+        };
+        self.finish(command, elapsed, error_code);
+
+        let outcome = error_code.unwrap_or("ok");
+        if elapsed >= SLOW_COMMAND_THRESHOLD {
+            log::warn!("[{}] {} 耗时 {}ms ({})", request_id, command, elapsed.as_millis(), outcome);
+        } else {
+            log::debug!("[{}] {} 完成 {}ms ({})", request_id, command, elapsed.as_millis(), outcome);
+        }
+        result
+    }
+
+    /// 当前统计
+    pub fn report(&self) -> IpcMetricsReport {
+        let commands: Vec<IpcCommandMetrics> = self
+            .lock()
+            .iter()
+            .map(|(command, stats)| IpcCommandMetrics {
+                command: command.to_string(),
+                invocations: stats.invocations,
+                errors: stats.errors,
+                error_rate: if stats.invocations == 0 {
+                    0.0
+                } else {
+                    stats.errors as f64 / stats.invocations as f64
+                },
+                in_flight: stats.in_flight,
+                avg_duration_ms: if stats.invocations == 0 {
+                    0.0
+                } else {
+                    stats.total.as_secs_f64() * 1000.0 / stats.invocations as f64
+                },
+                max_duration_ms: stats.max.as_secs_f64() * 1000.0,
+                last_error_code: stats.last_error_code.clone(),
+            })
+            .collect();
+
+        IpcMetricsReport {
+            since: self.since.clone(),
+            total_invocations: commands.iter().map(|c| c.invocations).sum(),
+            total_errors: commands.iter().map(|c| c.errors).sum(),
+            commands,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::types::AppError;
+
+    #[tokio::test]
+    async fn test_trace_counts_invocations_and_errors() {
+        let metrics = IpcMetrics::new();
+        metrics.trace("plugin_list", async { Ok(IpcResult::ok(())) }).await.unwrap();
+        metrics
+            .trace("plugin_list", async {
+                Ok(IpcResult::<()>::err(AppError::new("PLUGIN_NOT_FOUND", "missing")))
+            })
+            .await
+            .unwrap();
+        let failed = metrics
+            .trace("get_version", async { Err::<IpcResult<()>, _>("boom".to_string()) })
+            .await;
+        assert!(failed.is_err());
+
+        let report = metrics.report();
+        assert_eq!(report.total_invocations, 3);
+        assert_eq!(report.total_errors, 2);
+        let list = report.commands.iter().find(|c| c.command == "plugin_list").unwrap();
+        assert_eq!((list.invocations, list.errors, list.in_flight), (2, 1, 0));
+        assert_eq!(list.error_rate, 0.5);
+        assert_eq!(list.last_error_code.as_deref(), Some("PLUGIN_NOT_FOUND"));
+
+        assert_ne!(metrics.begin("x"), metrics.begin("x"));
+        let pending = metrics.report().commands.into_iter().find(|c| c.command == "x").unwrap();
+        assert_eq!((pending.invocations, pending.in_flight), (0, 2));
+    }
+}
@@ -0,0 +1,82 @@
+// Phase 6.10: 未送达告警重投
+// 所有告警通知经 deliver_notification 发送：通知权限未授予或发送失败时写入磁盘队列，
+// 后台定期重投，前端通过 get_missed_alerts 显示"错过的告警"数量。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::{NotificationExt, PermissionState};
+
+use crate::plugin::monitoring::AlertQueue;
+use crate::plugin::PluginManager;
+
+/// 重投检查间隔
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 未送达告警队列状态
+pub struct MissedAlertState(pub Arc<AlertQueue>);
+
+/// 创建未送达告警队列（加载 missed_alerts.json）
+pub fn create_missed_alert_queue(manager: &PluginManager) -> MissedAlertState {
+    let path = AlertQueue::file_path(manager.plugins_dir());
+    MissedAlertState(Arc::new(AlertQueue::load(path)))
+}
+
+/// 显示系统通知（权限未授予时视为失败）
+fn show_notification(app: &AppHandle, title: &str, body: &str) -> Result<(), String> {
+    let notification = app.notification();
+    match notification.permission_state() {
+        Ok(PermissionState::Granted) => {}
+        Ok(state) => return Err(format!("通知权限未授予: {:?}", state)),
+        Err(e) => return Err(e.to_string()),
+    }
+    notification
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+        .map_err(|e| e.to_string())
+}
+
+/// 发送告警通知，失败时加入未送达队列等待重投
+///
+/// 返回是否已立即送达。
+pub fn deliver_notification(app: &AppHandle, title: &str, body: &str, critical: bool) -> bool {
+    match show_notification(app, title, body) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("发送通知失败，加入重投队列: title={}, error={}", title, e);
+            if let Some(state) = app.try_state::<MissedAlertState>() {
+                state.0.enqueue(title, body, critical, Utc::now());
+            }
+            false
+        }
+    }
+}
+
+/// 启动后台重投任务
+pub fn spawn_missed_alert_retrier(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(RETRY_INTERVAL).await;
+            let Some(state) = app.try_state::<MissedAlertState>() else {
+                continue;
+            };
+            let now = Utc::now();
+            for alert in state.0.take_due(now) {
+                match show_notification(&app, &alert.title, &alert.body) {
+                    Ok(()) => {
+                        log::info!("已重投告警通知: {} (第 {} 次重投)", alert.title, alert.attempts + 1);
+                        state.0.mark_delivered(alert.id);
+                    }
+                    Err(e) => {
+                        log::debug!("重投告警通知失败: {}, error={}", alert.title, e);
+                        state.0.mark_failed(alert.id, now);
+                    }
+                }
+            }
+        }
+    });
+}
@@ -1,17 +1,100 @@
 // IPC Commands 模块
 // 定义前端可调用的命令
 
+pub mod alerts;
+pub mod budget;
+pub mod data_changes;
+pub mod error;
 pub mod events;
+pub mod health;
+pub mod hot_reload;
 pub mod installer;
+pub mod integrity;
 pub mod ipc;
+pub mod metrics;
+pub mod missed_alerts;
+pub mod network;
 pub mod plugin;
+pub mod popup;
+pub mod push;
+pub mod reconcile;
+pub mod registry;
+pub mod registry_cache;
+pub mod settings;
+pub mod updates;
+pub mod usage_alerts;
+pub mod watchdog;
+pub mod wizard;
 
 use tauri::command;
 
 // 导出插件管理器状态
 pub use plugin::{create_plugin_manager, PluginManagerState};
 
-// 导出 Phase 2 旧版命令 (向后兼容)
+// 导出旧版命令兼容层状态
+pub use plugin::{create_legacy_commands, LegacyCommandState};
+
+// 导出安装取消状态
+pub use installer::{create_install_cancellations, InstallCancelState};
+
+// 导出 Registry 资源缓存状态
+pub use registry_cache::{create_registry_cache, RegistryCacheState};
+
+// 导出 Registry 客户端状态
+pub use registry::{create_registry_client, RegistryClientState};
+
+// 导出插件更新检查
+pub use updates::{create_update_checker, spawn_update_checker};
+
+// 导出网络监视器状态
+pub use network::{create_network_monitor, spawn_network_watcher};
+
+// 导出弹窗摘要状态
+pub use popup::create_popup_summary_state;
+
+// 导出 IPC 命令追踪状态
+pub use metrics::create_ipc_metrics;
+
+// 导出插件完整性自检
+pub use integrity::spawn_integrity_watcher;
+
+// 导出本地推送端点
+pub use push::{create_push_server, spawn_push_server};
+
+// 导出孤立注册清理
+pub use reconcile::spawn_registration_reconciler;
+
+// 导出开发者插件热重载
+pub use hot_reload::spawn_dev_hot_reload;
+
+// 导出用量提醒跟踪器状态
+pub use usage_alerts::create_usage_alert_tracker;
+
+// 导出告警管理器状态
+pub use alerts::{create_alert_manager, AlertManagerState};
+
+// 导出预算跟踪器状态
+pub use budget::create_budget_tracker;
+
+// 导出刷新结果差异跟踪器状态
+pub use data_changes::{create_data_diff_tracker, spawn_data_update_broadcaster};
+
+// 导出健康状态变化推送
+pub use health::spawn_health_change_broadcaster;
+
+// 导出看门狗自动禁用通知
+pub use watchdog::spawn_watchdog_broadcaster;
+
+// 导出应用设置状态
+pub use settings::create_settings_store;
+
+// 导出首次设置向导状态
+pub use wizard::{create_setup_wizard, SetupWizardState};
+
+// 导出未送达告警队列
+pub use missed_alerts::{create_missed_alert_queue, spawn_missed_alert_retrier};
+
+// 导出 Phase 2 旧版命令 (已弃用，映射到 7.3 实现)
 pub use plugin::{
     discover_plugins, disable_plugin as old_disable_plugin,
     enable_plugin as old_enable_plugin, get_plugin, get_plugins_dir,
@@ -21,14 +104,68 @@ pub use plugin::{
 // 导出 Phase 7.3 IPC Commands (符合 contracts 定义)
 pub use ipc::{
     // 7.3.1 插件管理 Commands (9个)
-    plugin_list, plugin_enable, plugin_disable, plugin_install,
+    plugin_list, plugin_enable, plugin_disable, plugin_install, plugin_install_from_file,
     plugin_uninstall, plugin_reload, plugin_check_updates, plugin_update, plugin_rollback,
     // 7.3.2 数据 Commands (4个)
-    get_all_data, get_plugin_data, refresh_plugin, refresh_all,
+    get_all_data, get_plugin_data, get_summary, batch_query, refresh_plugin, refresh_all, replay_parse, plugin_test_run,
     // 7.3.3 配置 Commands (3个)
     get_plugin_config, set_plugin_config, validate_plugin_config,
-    // 7.3.4 监控 Commands (2个)
-    get_all_health, get_plugin_health,
+    // 7.3.4 监控 Commands (6个)
+    get_all_health, get_plugin_health, get_plugin_resource_stats, get_system_health, get_event_bus_stats,
+    get_dead_letters,
+    // 5A.3 Registry 资源 Commands
+    get_registry_asset,
+    // 5A.6 插件市场 Commands
+    registry_search, registry_get_manifest, registry_install,
+    // 5A.7 插件更新 Commands
+    get_update_settings, set_update_settings,
+    // 6.5 用量提醒 Commands
+    get_usage_alert_settings, set_usage_alert_settings,
+    // 6.6 告警静默 Commands
+    alert_snooze, alert_mute, alert_unmute,
+    // 6.16 告警历史 Commands
+    get_alert_history, acknowledge_alert,
+    // 6.10 未送达告警 Commands
+    get_missed_alerts, clear_missed_alerts, set_missed_alert_expiry,
+    // 6.7 隐私报告与存储占用 Commands
+    get_privacy_report, get_storage_stats,
+    // 6.12 插件日志与诊断 Commands
+    get_plugin_logs, clear_plugin_logs, generate_diagnostics, get_app_logs,
+    // 6.8 预算 Commands
+    get_budget_status, set_budget,
+    // 5A.8 开发者模式 Commands
+    get_developer_settings, set_developer_settings,
+    // 5A.9 .env 密钥导入 Commands
+    import_keys_from_env,
+    // 4.6 数据推送 Commands
+    get_push_settings, set_push_settings, issue_push_token, revoke_push_token,
+    // 7.3.5 窗口 Commands
+    get_popup_pinned, set_popup_pinned, get_popup_summary, get_usage_trend,
+    estimate_cost, get_price_table, update_price_table,
+    // 7.3.6 托盘 Commands
+    get_tray_click_config, set_tray_click_config,
+    // 7.3.7 插件输入请求 Commands
+    get_pending_inputs, submit_input,
+    // 7.3.8 网络状态 Commands
+    get_network_status,
+    // 7.3.9 完整性校验 Commands
+    verify_plugin_integrity, get_audit_events,
+    // 7.3.10 后台活动 Commands
+    get_app_nap_prevention, set_app_nap_prevention,
+    // 7.3.11 网关 Commands
+    get_gateway_config, set_gateway_config,
+    // 7.3.13 旧版命令 Commands
+    get_legacy_command_report, set_legacy_commands_enabled,
+    // 7.2 首次设置向导 Commands
+    wizard_get_state, wizard_complete_step, wizard_finish,
+    // 7.6 应用设置 Commands
+    get_app_settings, set_app_settings, reset_app_settings, get_theme,
+    // 7.3.14 IPC 追踪 Commands
+    get_ipc_metrics,
+    // 7.12 应用更新 Commands
+    get_app_update_status, check_for_app_updates, download_app_update, install_app_update,
+    // 7.14 多账户 Commands
+    add_plugin_account, remove_plugin_account, get_plugin_accounts,
 };
 
 // 导出 IPC Events
@@ -45,68 +182,3 @@ pub fn get_version() -> String {
 pub fn health_check() -> bool {
     true
 }
-
-// ============================================================================
-// Keychain Commands (macOS)
-// ============================================================================
-
-#[cfg(target_os = "macos")]
-mod keychain {
-    use security_framework::passwords::{delete_generic_password, get_generic_password, set_generic_password};
-    use tauri::command;
-
-    #[command]
-    pub fn keychain_set(service: String, key: String, value: String) -> Result<(), String> {
-        set_generic_password(&service, &key, value.as_bytes())
-            .map_err(|e| format!("Failed to set keychain item: {}", e))
-    }
-
-    #[command]
-    pub fn keychain_get(service: String, key: String) -> Result<Option<String>, String> {
-        match get_generic_password(&service, &key) {
-            Ok(password) => {
-                String::from_utf8(password.to_vec())
-                    .map(Some)
-                    .map_err(|e| format!("Failed to decode password: {}", e))
-            }
-            Err(e) if e.code() == -25300 => Ok(None), // errSecItemNotFound
-            Err(e) => Err(format!("Failed to get keychain item: {}", e)),
-        }
-    }
-
-    #[command]
-    pub fn keychain_delete(service: String, key: String) -> Result<(), String> {
-        match delete_generic_password(&service, &key) {
-            Ok(_) => Ok(()),
-            Err(e) if e.code() == -25300 => Ok(()), // errSecItemNotFound
-            Err(e) => Err(format!("Failed to delete keychain item: {}", e)),
-        }
-    }
-}
-
-#[cfg(target_os = "macos")]
-pub use keychain::*;
-
-// 非 macOS 平台的占位实现
-#[cfg(not(target_os = "macos"))]
-mod keychain_stub {
-    use tauri::command;
-
-    #[command]
-    pub fn keychain_set(_service: String, _key: String, _value: String) -> Result<(), String> {
-        Err("Keychain is only available on macOS".to_string())
-    }
-
-    #[command]
-    pub fn keychain_get(_service: String, _key: String) -> Result<Option<String>, String> {
-        Err("Keychain is only available on macOS".to_string())
-    }
-
-    #[command]
-    pub fn keychain_delete(_service: String, _key: String) -> Result<(), String> {
-        Err("Keychain is only available on macOS".to_string())
-    }
-}
-
-#[cfg(not(target_os = "macos"))]
-pub use keychain_stub::*;
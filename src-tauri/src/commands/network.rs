@@ -0,0 +1,153 @@
+// Phase 3.5: 离线模式集成
+// 将网络监视器接入刷新流程：离线时暂停刷新、抑制网络错误事件，恢复连接后补刷
+// 离线状态同步到 PluginManager：健康状态显示为 Stale，重复定时器暂停触发
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::error::codes;
+use crate::commands::events::emitter;
+use crate::commands::data_changes::publish_plugin_data;
+use crate::commands::PluginManagerState;
+use crate::plugin::types::{AppError, PluginErrorType};
+use crate::plugin::PluginManager;
+use crate::reliability::network::{
+    OFFLINE_PROBE_INTERVAL, ONLINE_PROBE_INTERVAL, REACHABILITY_POLL_INTERVAL,
+};
+use crate::reliability::reachability::system_reachable;
+use crate::reliability::{ActivityAssertions, NetworkMonitor};
+
+/// 网络监视器状态
+pub struct NetworkMonitorState(pub Arc<NetworkMonitor>);
+
+/// 创建默认的网络监视器
+pub fn create_network_monitor() -> NetworkMonitorState {
+    NetworkMonitorState(Arc::new(NetworkMonitor::new()))
+}
+
+/// 错误消息是否属于网络错误
+pub fn is_network_error(message: &str) -> bool {
+    PluginErrorType::classify(message) == PluginErrorType::NetworkError
+}
+
+/// 刷新出现网络错误时确认是否离线
+///
+/// 立即探测一次可达性，不可达则进入离线模式并通知前端。
+pub async fn confirm_offline(app: &AppHandle, monitor: &NetworkMonitor) -> bool {
+    if monitor.probe().await {
+        return false;
+    }
+    if monitor.mark_offline(Utc::now()) {
+        emit_status(app, monitor);
+        if let Some(manager) = app.try_state::<PluginManagerState>() {
+            manager.0.set_offline(true).await;
+        }
+    }
+    true
+}
+
+/// 离线期间排队刷新（每个插件只排队一次）
+pub fn queue_refreshes(app: &AppHandle, monitor: &NetworkMonitor, plugin_ids: &[String]) {
+    let mut queued_any = false;
+    for id in plugin_ids {
+        if monitor.queue_refresh(id) {
+            log::debug!("[{}] 离线中，已排队等待恢复连接后刷新", id);
+            queued_any = true;
+        }
+    }
+    if queued_any {
+        emit_status(app, monitor);
+    }
+}
+
+/// 发送网络状态变化事件
+fn emit_status(app: &AppHandle, monitor: &NetworkMonitor) {
+    if let Err(e) = emitter(app).emit_network_status_changed(&monitor.status()) {
+        log::warn!("发送网络状态事件失败: {}", e);
+    }
+}
+
+/// 启动后台网络探测
+///
+/// 在线时低频探测以便提前发现断网，离线时高频探测以便尽快恢复；
+/// macOS 上同时轮询系统可达性，网络变化（断开 Wi-Fi、切换网络）时立即探测。
+/// 恢复连接后立即刷新所有可刷新的插件。
+pub fn spawn_network_watcher(
+    app: AppHandle,
+    monitor: Arc<NetworkMonitor>,
+    manager: Arc<PluginManager>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut next_probe = tokio::time::Instant::now() + ONLINE_PROBE_INTERVAL;
+        loop {
+            tokio::time::sleep(REACHABILITY_POLL_INTERVAL).await;
+
+            let changed = system_reachable().is_some_and(|reachable| monitor.observe_reachability(reachable));
+            if !changed && tokio::time::Instant::now() < next_probe {
+                continue;
+            }
+
+            // 系统报告没有网络路由时无需探测
+            let online = !monitor.system_unreachable() && monitor.probe().await;
+            if online {
+                if let Some(queued) = monitor.mark_online() {
+                    emit_status(&app, &monitor);
+                    manager.set_offline(false).await;
+                    run_reconnect_refresh(&app, &manager, queued).await;
+                }
+            } else if monitor.mark_offline(Utc::now()) {
+                emit_status(&app, &monitor);
+                manager.set_offline(true).await;
+            }
+
+            let interval = if online {
+                ONLINE_PROBE_INTERVAL
+            } else {
+                OFFLINE_PROBE_INTERVAL
+            };
+            next_probe = tokio::time::Instant::now() + interval;
+        }
+    });
+}
+
+/// 恢复连接后刷新离线期间排队的插件和其他可刷新的插件
+async fn run_reconnect_refresh(app: &AppHandle, manager: &PluginManager, queued: Vec<String>) {
+    let _activity = app
+        .try_state::<ActivityAssertions>()
+        .and_then(|activity| activity.begin("恢复连接后补刷"));
+
+    let mut plugin_ids = manager.refreshable_plugin_ids().await;
+    for id in queued {
+        if !plugin_ids.contains(&id) {
+            plugin_ids.push(id);
+        }
+    }
+
+    let mut failed = 0;
+    for id in &plugin_ids {
+        // 离线期间的缓存视为过期，强制重新获取
+        match manager.execute_fetch_data(id, true).await {
+            Ok(data) => {
+                publish_plugin_data(app, manager, id, &data).await;
+            }
+            Err(e) => {
+                failed += 1;
+                let error = AppError::new(codes::PLUGIN_REFRESH_FAILED, e.to_string());
+                if let Err(emit_err) = emitter(app).emit_plugin_error(id, &error) {
+                    log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, emit_err);
+                }
+            }
+        }
+    }
+
+    if !plugin_ids.is_empty() {
+        log::info!("恢复连接后补刷完成: {} 个插件, {} 个失败", plugin_ids.len(), failed);
+        let all_data = manager.get_all_data().await;
+        let worst_health = manager.worst_health().await;
+        if let Err(e) = crate::tray::sync_tray_with_data(app, &all_data, failed, worst_health) {
+            log::warn!("更新托盘图标失败: {}", e);
+        }
+    }
+}
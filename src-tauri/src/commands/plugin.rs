@@ -1,10 +1,17 @@
 // 插件 IPC Commands
 // Phase 2: 插件运行时核心
+// Phase 7.3.13: 旧版命令弃用，映射到 7.3 实现（见 plugin/legacy.rs）
 
+use crate::commands::error::{codes, IpcError};
+use crate::commands::events::emitter;
+use crate::commands::ipc;
+use crate::commands::metrics::IpcMetricsState;
+use crate::plugin::legacy::LegacyCommands;
 use crate::plugin::{PluginDiscovery, PluginManager};
-use crate::plugin::types::PluginInfo;
+use crate::plugin::types::{AppError, PluginInfo, Result as IpcResult};
+use chrono::Utc;
 use std::sync::Arc;
-use tauri::{command, State};
+use tauri::{command, AppHandle, Manager, State};
 
 /// 插件管理器状态包装
 ///
@@ -12,65 +19,139 @@ use tauri::{command, State};
 /// 外层锁是多余的，会降低并发性能并增加死锁风险。
 pub struct PluginManagerState(pub Arc<PluginManager>);
 
+/// 旧版命令兼容层状态
+pub struct LegacyCommandState(pub Arc<LegacyCommands>);
+
+/// 创建旧版命令兼容层（加载设置与调用统计）
+pub fn create_legacy_commands(manager: &PluginManager) -> LegacyCommandState {
+    LegacyCommandState(Arc::new(LegacyCommands::load(LegacyCommands::file_path(
+        manager.plugins_dir(),
+    ))))
+}
+
+/// 记录旧命令调用并发送弃用事件，旧命令已停用时返回 LEGACY_COMMAND_DISABLED
+fn deprecated(app: &AppHandle, command: &str) -> Result<(), AppError> {
+    let Some(legacy) = app.try_state::<LegacyCommandState>() else {
+        return Ok(());
+    };
+    let event = legacy
+        .0
+        .record(command, Utc::now())
+        .map_err(|e| AppError::from(IpcError::new(codes::LEGACY_COMMAND_DISABLED, e.to_string())))?;
+    log::warn!(
+        "调用了已弃用的旧版命令 {}（第 {} 次），替代命令: {}",
+        command,
+        event.count,
+        event.replacement.as_deref().unwrap_or("无")
+    );
+    if let Err(e) = emitter(app).emit_deprecated_command(&event) {
+        log::warn!("发送弃用命令事件失败: command={}, emit_error={}", command, e);
+    }
+    Ok(())
+}
+
 /// 列出所有插件
+///
+/// 已弃用：请使用 plugin_list
 #[command]
-pub async fn list_plugins(state: State<'_, PluginManagerState>) -> Result<Vec<PluginInfo>, String> {
-    Ok(state.0.list_plugins().await)
+pub async fn list_plugins(
+    app: AppHandle,
+    state: State<'_, PluginManagerState>,
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<PluginInfo>>, String> {
+    if let Err(error) = deprecated(&app, "list_plugins") {
+        return Ok(IpcResult::err(error));
+    }
+    ipc::plugin_list(state, metrics).await
 }
 
 /// 获取单个插件信息
+///
+/// 已弃用：请使用 plugin_list
 #[command]
 pub async fn get_plugin(
+    app: AppHandle,
     id: String,
     state: State<'_, PluginManagerState>,
-) -> Result<Option<PluginInfo>, String> {
-    Ok(state.0.get_plugin(&id).await)
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Option<PluginInfo>>, String> {
+    if let Err(error) = deprecated(&app, "get_plugin") {
+        return Ok(IpcResult::err(error));
+    }
+    let result = ipc::plugin_list(state, metrics).await?;
+    Ok(match (result.data, result.error) {
+        (Some(plugins), _) => IpcResult::ok(plugins.into_iter().find(|p| p.instance_id == id)),
+        (None, Some(error)) => IpcResult::err(error),
+        (None, None) => IpcResult::ok(None),
+    })
 }
 
 /// 启用插件
+///
+/// 已弃用：请使用 plugin_enable
 #[command]
 pub async fn enable_plugin(
+    app: AppHandle,
     id: String,
     state: State<'_, PluginManagerState>,
-) -> Result<(), String> {
-    // PluginManager 内部 RwLock 会处理并发控制
-    state.0
-        .enable_plugin(&id)
-        .await
-        .map_err(|e| format!("Failed to enable plugin: {}", e))
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    if let Err(error) = deprecated(&app, "enable_plugin") {
+        return Ok(IpcResult::err(error));
+    }
+    ipc::plugin_enable(app, id, state, metrics).await
 }
 
 /// 禁用插件
+///
+/// 已弃用：请使用 plugin_disable
 #[command]
 pub async fn disable_plugin(
+    app: AppHandle,
     id: String,
     state: State<'_, PluginManagerState>,
-) -> Result<(), String> {
-    // PluginManager 内部 RwLock 会处理并发控制
-    state.0
-        .disable_plugin(&id)
-        .await
-        .map_err(|e| format!("Failed to disable plugin: {}", e))
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<()>, String> {
+    if let Err(error) = deprecated(&app, "disable_plugin") {
+        return Ok(IpcResult::err(error));
+    }
+    ipc::plugin_disable(app, id, state, metrics).await
 }
 
 /// 发现并加载插件
+///
+/// 已弃用：请使用 plugin_list（插件在启动时已加载）
 #[command]
 pub async fn discover_plugins(
+    app: AppHandle,
     state: State<'_, PluginManagerState>,
-) -> Result<Vec<PluginInfo>, String> {
-    // PluginManager 内部 RwLock 会处理并发控制
-    state.0
-        .discover_and_load()
-        .await
-        .map_err(|e| format!("Failed to discover plugins: {}", e))
+    metrics: State<'_, IpcMetricsState>,
+) -> Result<IpcResult<Vec<PluginInfo>>, String> {
+    if let Err(error) = deprecated(&app, "discover_plugins") {
+        return Ok(IpcResult::err(error));
+    }
+    metrics.0.trace("discover_plugins", async move {
+        // PluginManager 内部 RwLock 会处理并发控制
+        match state.0.discover_and_load().await {
+            Ok(plugins) => Ok(IpcResult::ok(plugins)),
+            Err(e) => Ok(IpcResult::err(IpcError::lifecycle(codes::PLUGIN_DISCOVER_FAILED, e).into())),
+        }
+    })
+    .await
 }
 
 /// 获取插件目录路径
+///
+/// 已弃用：插件目录由宿主管理，前端不应直接访问
 #[command]
 pub async fn get_plugins_dir(
+    app: AppHandle,
     state: State<'_, PluginManagerState>,
-) -> Result<String, String> {
-    Ok(state.0.plugins_dir().to_string_lossy().to_string())
+) -> Result<IpcResult<String>, String> {
+    if let Err(error) = deprecated(&app, "get_plugins_dir") {
+        return Ok(IpcResult::err(error));
+    }
+    Ok(IpcResult::ok(state.0.plugins_dir().to_string_lossy().to_string()))
 }
 
 /// 创建默认的 PluginManager
@@ -0,0 +1,114 @@
+// Phase 7.4: 弹窗摘要
+// 弹窗打开时一次性获取所需数据，并对该路径执行延迟预算
+//
+// 超出预算（通常是刷新持有插件表写锁导致的竞争）时进入快照模式:
+// 直接返回上次生成的摘要，同时在后台探测实时路径，竞争消退后恢复。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::plugin::types::{PluginData, PluginHealth, PluginInfo};
+use crate::plugin::PluginManager;
+use crate::reliability::latency::{LatencyBudget, StageTimer};
+use crate::reliability::{NetworkMonitor, NetworkStatus};
+
+/// 弹窗摘要
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PopupSummary {
+    /// 插件列表
+    pub plugins: Vec<PluginInfo>,
+    /// 插件缓存数据
+    pub data: Vec<PluginData>,
+    /// 插件健康状态
+    pub health: Vec<PluginHealth>,
+    /// 网络状态
+    pub network: NetworkStatus,
+    /// 是否为快照（延迟超预算时返回上次结果）
+    pub snapshot_only: bool,
+    /// 生成时间 (ISO 8601)
+    pub generated_at: String,
+}
+
+/// 弹窗摘要状态
+pub struct PopupSummaryState(pub Arc<PopupSummaryTracker>);
+
+/// 创建默认的弹窗摘要状态（预算 150ms）
+pub fn create_popup_summary_state() -> PopupSummaryState {
+    PopupSummaryState(Arc::new(PopupSummaryTracker {
+        budget: LatencyBudget::default(),
+        snapshot: RwLock::new(None),
+        probing: AtomicBool::new(false),
+    }))
+}
+
+/// 弹窗摘要跟踪器（延迟预算 + 上次结果）
+pub struct PopupSummaryTracker {
+    budget: LatencyBudget,
+    snapshot: RwLock<Option<PopupSummary>>,
+    /// 是否有后台探测在执行
+    probing: AtomicBool,
+}
+
+impl PopupSummaryTracker {
+    /// 获取弹窗摘要
+    ///
+    /// 快照模式下返回上次结果并在后台探测实时路径；否则实时生成。
+    pub async fn get(
+        self: &Arc<Self>,
+        manager: &Arc<PluginManager>,
+        network: &Arc<NetworkMonitor>,
+    ) -> PopupSummary {
+        if self.budget.is_snapshot_only() {
+            let snapshot = self.snapshot.read().unwrap_or_else(|e| e.into_inner()).clone();
+            if let Some(mut summary) = snapshot {
+                self.spawn_probe(manager.clone(), network.clone());
+                summary.snapshot_only = true;
+                return summary;
+            }
+        }
+        self.load_live(manager, network).await
+    }
+
+    /// 实时生成摘要，记录阶段耗时并更新快照
+    async fn load_live(&self, manager: &PluginManager, network: &NetworkMonitor) -> PopupSummary {
+        let mut timer = StageTimer::start("get_popup_summary");
+        let plugins = manager.list_plugins().await;
+        timer.stage("plugins");
+        let health = manager.get_all_health().await;
+        timer.stage("health");
+        let data = manager.get_all_data().await;
+        timer.stage("data");
+        let network = network.status();
+        timer.stage("network");
+
+        let summary = PopupSummary {
+            plugins,
+            data,
+            health,
+            network,
+            snapshot_only: false,
+            generated_at: Utc::now().to_rfc3339(),
+        };
+        *self.snapshot.write().unwrap_or_else(|e| e.into_inner()) = Some(summary.clone());
+
+        let report = timer.finish(self.budget.budget());
+        self.budget.record(&report);
+        summary
+    }
+
+    /// 后台探测实时路径（同一时间只执行一个）
+    fn spawn_probe(self: &Arc<Self>, manager: Arc<PluginManager>, network: Arc<NetworkMonitor>) {
+        if self.probing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let tracker = self.clone();
+        tauri::async_runtime::spawn(async move {
+            tracker.load_live(&manager, &network).await;
+            tracker.probing.store(false, Ordering::Release);
+        });
+    }
+}
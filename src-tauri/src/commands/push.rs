@@ -0,0 +1,362 @@
+// Phase 4.6: 本地推送端点
+// 监听 127.0.0.1，接收外部系统（如 CI）推送的插件数据: `POST /v1/push/{plugin_id}`
+//
+// 请求示例:
+//   curl -X POST http://127.0.0.1:47821/v1/push/ci-usage \
+//        -H "Authorization: Bearer <token>" -d '{"dataType":"usage", ...}'
+//
+// 响应体与 IPC 一致: `{"success":true,"data":...}` / `{"success":false,"error":{...}}`
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::AppHandle;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::RwLock;
+
+use crate::commands::data_changes::publish_plugin_data;
+use crate::commands::error::codes;
+use crate::plugin::push::{parse_push_request, PushRejection, PushSettings, MAX_HEADER_SIZE};
+use crate::plugin::types::{AppError, PluginData, Result as IpcResult};
+use crate::plugin::PluginManager;
+
+/// 单个请求的读取超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 推送端点状态
+pub struct PushServerState(pub Arc<PushServer>);
+
+/// 创建推送端点（设置从 push.json 加载，需调用 spawn_push_server 启动）
+pub fn create_push_server(manager: Arc<PluginManager>) -> PushServerState {
+    let path = PushSettings::file_path(manager.plugins_dir());
+    let settings = PushSettings::load(&path);
+    PushServerState(Arc::new(PushServer {
+        manager,
+        settings: RwLock::new(settings),
+        path,
+        task: Mutex::new(None),
+    }))
+}
+
+/// 本地推送端点
+pub struct PushServer {
+    manager: Arc<PluginManager>,
+    settings: RwLock<PushSettings>,
+    path: PathBuf,
+    /// 监听任务（重新配置时中止）
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl PushServer {
+    /// 当前推送设置
+    pub async fn settings(&self) -> PushSettings {
+        self.settings.read().await.clone()
+    }
+
+    /// 更新端点开关和端口，按新设置重启监听
+    pub async fn configure(
+        self: &Arc<Self>,
+        app: AppHandle,
+        enabled: bool,
+        port: Option<u16>,
+    ) -> Result<PushSettings, AppError> {
+        let mut settings = self.settings().await;
+        settings.enabled = enabled;
+        if let Some(port) = port {
+            settings.port = port;
+        }
+
+        self.stop();
+        if settings.enabled {
+            self.start(app, settings.port)
+                .await
+                .map_err(|e| AppError::new(codes::PUSH_SERVER_START_FAILED, e.to_string()))?;
+        }
+        self.save(settings.clone()).await?;
+        Ok(settings)
+    }
+
+    /// 为插件签发 token（仅声明 acceptsPush 的插件），返回明文
+    pub async fn issue_token(&self, plugin_id: &str) -> Result<String, AppError> {
+        let accepts_push = self
+            .manager
+            .get_plugin(plugin_id)
+            .await
+            .is_some_and(|p| p.accepts_push);
+        if !accepts_push {
+            return Err(AppError::new(
+                codes::PLUGIN_PUSH_NOT_SUPPORTED,
+                format!("插件不存在或未声明 acceptsPush: {}", plugin_id),
+            ));
+        }
+
+        let mut settings = self.settings().await;
+        let token = settings.issue_token(plugin_id);
+        self.save(settings).await?;
+        log::info!("[{}] 已签发推送 token", plugin_id);
+        Ok(token)
+    }
+
+    /// 吊销插件的 token
+    pub async fn revoke_token(&self, plugin_id: &str) -> Result<bool, AppError> {
+        let mut settings = self.settings().await;
+        let revoked = settings.revoke_token(plugin_id);
+        if revoked {
+            self.save(settings).await?;
+            log::info!("[{}] 已吊销推送 token", plugin_id);
+        }
+        Ok(revoked)
+    }
+
+    async fn save(&self, settings: PushSettings) -> Result<(), AppError> {
+        settings
+            .save(&self.path)
+            .map_err(|e| AppError::new(codes::PUSH_SETTINGS_SAVE_FAILED, e.to_string()))?;
+        *self.settings.write().await = settings;
+        Ok(())
+    }
+
+    /// 开始监听 127.0.0.1:port
+    async fn start(self: &Arc<Self>, app: AppHandle, port: u16) -> std::io::Result<()> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+        log::info!("本地推送端点已启动: http://127.0.0.1:{}", port);
+
+        let server = self.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        log::warn!("推送端点接受连接失败: {}", e);
+                        continue;
+                    }
+                };
+                let server = server.clone();
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Some((plugin_id, data)) = server.handle_connection(stream).await {
+                        publish_plugin_data(&app, &server.manager, &plugin_id, &data).await;
+                    }
+                });
+            }
+        });
+        *self.task.lock().unwrap_or_else(|e| e.into_inner()) = Some(task);
+        Ok(())
+    }
+
+    /// 停止监听
+    fn stop(&self) {
+        if let Some(task) = self.task.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            task.abort();
+            log::info!("本地推送端点已停止");
+        }
+    }
+
+    /// 处理单个连接，成功写入时返回插件 ID 和数据
+    async fn handle_connection(&self, mut stream: TcpStream) -> Option<(String, PluginData)> {
+        let outcome = tokio::time::timeout(REQUEST_TIMEOUT, self.process(&mut stream))
+            .await
+            .unwrap_or_else(|_| Err(PushRejection::new(408, "请求超时")));
+
+        let (status, body) = match &outcome {
+            Ok((_, data)) => (200, serde_json::to_vec(&IpcResult::ok(data))),
+            Err(rejection) => {
+                log::warn!("拒绝推送请求: {} {}", rejection.status, rejection.message);
+                let error = AppError::new(status_code(rejection.status), rejection.message.clone());
+                (rejection.status, serde_json::to_vec(&IpcResult::<()>::err(error)))
+            }
+        };
+        let body = body.unwrap_or_default();
+        let head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            status,
+            reason_phrase(status),
+            body.len()
+        );
+        if let Err(e) = async {
+            stream.write_all(head.as_bytes()).await?;
+            stream.write_all(&body).await?;
+            stream.shutdown().await
+        }
+        .await
+        {
+            log::debug!("写入推送响应失败: {}", e);
+        }
+
+        outcome.ok()
+    }
+
+    /// 读取请求、校验 token 并写入数据
+    async fn process(&self, stream: &mut TcpStream) -> Result<(String, PluginData), PushRejection> {
+        let io_error = |e: std::io::Error| PushRejection::new(400, format!("读取请求失败: {}", e));
+
+        // 1. 读取请求头
+        let mut buf = Vec::with_capacity(1024);
+        let head_end = loop {
+            if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos;
+            }
+            if buf.len() > MAX_HEADER_SIZE {
+                return Err(PushRejection::new(431, "请求头过大"));
+            }
+            let mut chunk = [0u8; 1024];
+            let n = stream.read(&mut chunk).await.map_err(io_error)?;
+            if n == 0 {
+                return Err(PushRejection::new(400, "请求不完整"));
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        };
+        let head = std::str::from_utf8(&buf[..head_end])
+            .map_err(|_| PushRejection::new(400, "请求头不是有效的 UTF-8"))?;
+        let request = parse_push_request(head)?;
+
+        // 2. 校验 token（读取请求体前，避免为未授权请求分配内存）
+        let authorized = match request.token.as_deref() {
+            Some(token) => self.settings.read().await.verify_token(&request.plugin_id, token),
+            None => false,
+        };
+        if !authorized {
+            return Err(PushRejection::new(401, "token 无效或缺失"));
+        }
+
+        // 3. 读取请求体
+        let mut body = buf.split_off(head_end + 4);
+        if body.len() > request.content_length {
+            return Err(PushRejection::new(400, "请求体长度与 Content-Length 不符"));
+        }
+        let already_read = body.len();
+        body.resize(request.content_length, 0);
+        stream
+            .read_exact(&mut body[already_read..])
+            .await
+            .map_err(io_error)?;
+        let raw: serde_json::Value = serde_json::from_slice(&body)
+            .map_err(|e| PushRejection::new(400, format!("JSON 解析失败: {}", e)))?;
+
+        // 4. 按 fetchData 结果解析并写入缓存
+        let data = self
+            .manager
+            .ingest_pushed_data(&request.plugin_id, raw)
+            .await
+            .map_err(|e| PushRejection::new(422, e.to_string()))?;
+        Ok((request.plugin_id, data))
+    }
+}
+
+/// 启动推送端点（设置中启用时）
+pub fn spawn_push_server(app: AppHandle, server: Arc<PushServer>) {
+    tauri::async_runtime::spawn(async move {
+        let settings = server.settings().await;
+        if !settings.enabled {
+            return;
+        }
+        if let Err(e) = server.start(app, settings.port).await {
+            log::error!("启动本地推送端点失败 (port={}): {}", settings.port, e);
+        }
+    });
+}
+
+/// HTTP 状态码对应的错误码
+fn status_code(status: u16) -> &'static str {
+    match status {
+        401 => "PUSH_UNAUTHORIZED",
+        404 => "NOT_FOUND",
+        405 => "METHOD_NOT_ALLOWED",
+        408 => "REQUEST_TIMEOUT",
+        411 | 413 | 431 => "INVALID_REQUEST",
+        422 => "PUSH_REJECTED",
+        _ => "INVALID_REQUEST",
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        _ => "Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::PluginDiscovery;
+
+    async fn push(port: u16, request: String) -> String {
+        let mut client = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        client.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_push_endpoint_ingests_data() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("ci-usage");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        let manifest = r#"{
+            "id": "ci-usage",
+            "name": "CI Usage",
+            "version": "1.0.0",
+            "apiVersion": "1.0",
+            "pluginType": "data",
+            "dataType": "custom",
+            "entry": "plugin.js",
+            "acceptsPush": true,
+            "payloadSchema": { "type": "object", "required": ["runs"] }
+        }"#;
+        std::fs::write(plugin_dir.join("manifest.json"), manifest).unwrap();
+        std::fs::write(plugin_dir.join("plugin.js"), "// push only").unwrap();
+
+        let manager = Arc::new(PluginManager::new(PluginDiscovery::new(plugins_dir)));
+        manager.discover_and_load().await.unwrap();
+        manager.enable_plugin("ci-usage").await.unwrap();
+        let server = create_push_server(manager.clone()).0;
+        let token = server.issue_token("ci-usage").await.unwrap();
+
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let accept = {
+            let server = server.clone();
+            tokio::spawn(async move {
+                let mut results = Vec::new();
+                for _ in 0..3 {
+                    let (stream, _) = listener.accept().await.unwrap();
+                    results.push(server.handle_connection(stream).await);
+                }
+                results
+            })
+        };
+
+        let request = |token: &str, body: &str| {
+            format!(
+                "POST /v1/push/ci-usage HTTP/1.1\r\nAuthorization: Bearer {}\r\nContent-Length: {}\r\n\r\n{}",
+                token,
+                body.len(),
+                body
+            )
+        };
+        let ok_body = r#"{"dataType":"custom","runs":3}"#;
+        let bad_body = r#"{"dataType":"custom"}"#;
+
+        assert!(push(port, request(&token, ok_body)).await.starts_with("HTTP/1.1 200"));
+        assert!(push(port, request("wrong", ok_body)).await.starts_with("HTTP/1.1 401"));
+        assert!(push(port, request(&token, bad_body)).await.starts_with("HTTP/1.1 422"));
+
+        let results = accept.await.unwrap();
+        assert_eq!(results.iter().filter(|r| r.is_some()).count(), 1);
+        assert!(manager.get_plugin_data("ci-usage").await.is_some());
+    }
+}
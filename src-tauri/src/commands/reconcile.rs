@@ -0,0 +1,44 @@
+// Phase 4.6: 孤立注册清理
+// 定期比对各注册表与磁盘上的插件，回收外部删除插件残留的订阅、权限、方法和配置 Schema
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+use crate::commands::events::emitter;
+use crate::plugin::PluginManager;
+
+/// 首次清理延迟（等待插件系统初始化完成）
+const INITIAL_DELAY: Duration = Duration::from_secs(90);
+
+/// 清理间隔
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// 启动后台孤立注册清理
+pub fn spawn_registration_reconciler(app: AppHandle, manager: Arc<PluginManager>) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(INITIAL_DELAY).await;
+        loop {
+            match manager.prune_orphaned_registrations().await {
+                Ok(cleanups) if !cleanups.is_empty() => {
+                    log::info!("孤立注册清理完成: 回收 {} 个插件", cleanups.len());
+                    // 内存中的插件实例被移除时通知前端
+                    for cleanup in cleanups.iter().filter(|c| c.reclaimed.contains(&"instance")) {
+                        if let Err(e) = emitter(&app).emit_plugin_uninstalled(&cleanup.plugin_id) {
+                            log::warn!(
+                                "发送插件卸载事件失败: plugin={}, emit_error={}",
+                                cleanup.plugin_id,
+                                e
+                            );
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("孤立注册清理失败: {}", e),
+            }
+
+            tokio::time::sleep(RECONCILE_INTERVAL).await;
+        }
+    });
+}
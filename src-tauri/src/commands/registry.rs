@@ -0,0 +1,43 @@
+// Phase 5A.6: 插件市场
+// 通过签名的 registry 索引发现并安装插件
+
+use std::sync::Arc;
+
+use crate::commands::error::codes;
+use crate::commands::installer::{install_plugin, InstallCancelToken, InstallProgressCallback};
+use crate::plugin::registry::RegistryClient;
+use crate::plugin::types::{AppError, PluginInfo};
+use crate::plugin::PluginManager;
+
+/// Registry 客户端状态
+pub struct RegistryClientState(pub Arc<RegistryClient>);
+
+/// 创建默认的 Registry 客户端
+pub fn create_registry_client() -> RegistryClientState {
+    RegistryClientState(Arc::new(RegistryClient::new()))
+}
+
+/// 从 registry 安装插件
+///
+/// 下载地址取自已验证签名的索引，安装时强制验证插件 manifest 签名。
+pub async fn install_from_registry(
+    manager: Arc<PluginManager>,
+    client: &RegistryClient,
+    registry_url: Option<&str>,
+    id: &str,
+    progress: Option<InstallProgressCallback>,
+    cancel: Option<InstallCancelToken>,
+) -> Result<PluginInfo, AppError> {
+    let registry_url = manager.admin_policy().registry_url(registry_url)?;
+    let entry = client.entry(registry_url.as_deref(), id).await?;
+    let download_url = entry.download_url.ok_or_else(|| {
+        AppError::new(codes::INVALID_SOURCE, format!("插件 {} 未提供下载地址", id))
+    })?;
+
+    log::info!("从 registry 安装插件: {} v{} ({})", id, entry.version, download_url);
+    let info = install_plugin(manager, &download_url, false, None, progress, cancel).await?;
+    if info.id != id {
+        log::warn!("registry 条目 {} 安装后的插件 ID 为 {}", id, info.id);
+    }
+    Ok(info)
+}
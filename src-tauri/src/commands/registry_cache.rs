@@ -0,0 +1,568 @@
+// Phase 5A.3: Registry 资源缓存
+// 为插件市场视图下载并缓存插件图标/截图，缓存随 registry 索引版本失效
+//
+// 资源地址取自已验证签名的 registry 索引（plugin::registry），下载同样只允许 HTTPS 并拒绝内网地址。
+// 索引版本变化时清空缓存目录；清空与下载互斥，进行中的下载完成后才会清空。
+
+use crate::commands::error::codes;
+use crate::plugin::registry::{secure_client, RegistryClient, RegistryEntry, RegistryError, RegistryIndex};
+use crate::plugin::types::AppError;
+use crate::security::integrity::calculate_sha256_bytes;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{RwLock, RwLockReadGuard};
+
+// ============================================================================
+// 常量
+// ============================================================================
+
+/// 单个资源最大大小 (2MB)
+pub const MAX_ASSET_SIZE: usize = 2 * 1024 * 1024;
+
+/// 每个插件最多缓存的截图数量
+pub const MAX_SCREENSHOTS: usize = 8;
+
+/// 缓存目录中记录索引版本的文件名
+const VERSION_FILE: &str = ".index-version";
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+#[derive(Debug, thiserror::Error)]
+pub enum RegistryCacheError {
+    #[error("获取 registry 失败: {0}")]
+    Registry(String),
+
+    #[error("在 registry 中未找到插件: {0}")]
+    PluginNotFound(String),
+
+    #[error("插件未提供该资源: {0}")]
+    AssetNotFound(String),
+
+    #[error("资源下载失败: {0}")]
+    Download(String),
+
+    #[error("资源校验失败: {0}")]
+    InvalidAsset(String),
+
+    #[error("无效的参数: {0}")]
+    InvalidArgument(String),
+
+    #[error("IO 错误: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<RegistryError> for RegistryCacheError {
+    fn from(e: RegistryError) -> Self {
+        match e {
+            RegistryError::PluginNotFound(id) => RegistryCacheError::PluginNotFound(id),
+            e => RegistryCacheError::Registry(e.to_string()),
+        }
+    }
+}
+
+impl From<RegistryCacheError> for AppError {
+    fn from(e: RegistryCacheError) -> Self {
+        let code = match &e {
+            RegistryCacheError::Registry(_) => codes::REGISTRY_FETCH_FAILED,
+            RegistryCacheError::PluginNotFound(_) => codes::PLUGIN_NOT_FOUND,
+            RegistryCacheError::AssetNotFound(_) => codes::ASSET_NOT_FOUND,
+            RegistryCacheError::Download(_) => codes::DOWNLOAD_FAILED,
+            RegistryCacheError::InvalidAsset(_) => codes::ASSET_INVALID,
+            RegistryCacheError::InvalidArgument(_) => codes::INVALID_ARGUMENT,
+            RegistryCacheError::Io(_) => codes::IO_ERROR,
+        };
+        AppError::new(code, e.to_string())
+    }
+}
+
+// ============================================================================
+// 资源类型
+// ============================================================================
+
+/// registry 资源类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RegistryAssetKind {
+    /// 插件图标 (registry 条目的 `icon` 字段)
+    Icon,
+    /// 插件截图 (registry 条目的 `screenshots` 数组)
+    Screenshots,
+}
+
+impl RegistryAssetKind {
+    /// 从字符串解析
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "icon" => Some(Self::Icon),
+            "screenshot" | "screenshots" => Some(Self::Screenshots),
+            _ => None,
+        }
+    }
+
+    /// 缓存文件名前缀
+    fn file_prefix(&self) -> &'static str {
+        match self {
+            Self::Icon => "icon",
+            Self::Screenshots => "screenshot",
+        }
+    }
+}
+
+/// 缓存的 registry 资源（返回给前端）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryAsset {
+    pub plugin_id: String,
+    pub kind: RegistryAssetKind,
+    /// 本地文件路径（按 registry 中的顺序）
+    pub paths: Vec<String>,
+    /// 资源所属的 registry 索引版本
+    pub index_version: String,
+}
+
+/// 已校验的图片格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Svg,
+}
+
+impl ImageFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::Gif => "gif",
+            Self::Webp => "webp",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+/// 通过魔数检测图片格式，不信任 Content-Type 和 URL 扩展名
+fn detect_image_format(bytes: &[u8]) -> Option<ImageFormat> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some(ImageFormat::Png);
+    }
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        return Some(ImageFormat::Jpeg);
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return Some(ImageFormat::Gif);
+    }
+    if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return Some(ImageFormat::Webp);
+    }
+
+    // SVG: 文本格式，必须是 UTF-8 且不含脚本
+    let text = std::str::from_utf8(bytes).ok()?;
+    let head = text.trim_start_matches('\u{feff}').trim_start();
+    if head.starts_with("<svg") || (head.starts_with("<?xml") && head.contains("<svg")) {
+        let lower = text.to_ascii_lowercase();
+        if lower.contains("<script") || lower.contains("javascript:") || has_event_handler(&lower) {
+            return None;
+        }
+        return Some(ImageFormat::Svg);
+    }
+
+    None
+}
+
+/// 检查 SVG 标签中是否存在 on* 事件属性
+///
+/// 属性名可以紧跟 `/`、引号或换行（如 `<svg/onload=...>`），不能按空白切分判断；
+/// 标签内任何 `on\w+=` 形式（不属于更长的属性名）都视为事件属性。
+fn has_event_handler(lower: &str) -> bool {
+    lower.split('<').skip(1).any(|rest| {
+        let tag = rest.split('>').next().unwrap_or(rest);
+        let bytes = tag.as_bytes();
+        tag.match_indices("on").any(|(start, _)| {
+            let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+            if start > 0 && is_word(bytes[start - 1]) {
+                return false;
+            }
+            let name_end = start + 2 + bytes[start + 2..].iter().take_while(|b| is_word(**b)).count();
+            if name_end == start + 2 {
+                return false;
+            }
+            bytes[name_end..]
+                .iter()
+                .find(|b| !b.is_ascii_whitespace())
+                .is_some_and(|b| *b == b'=')
+        })
+    })
+}
+
+/// 校验插件 ID 可安全用作目录名
+fn is_safe_plugin_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 128
+        && !id.starts_with('.')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+// ============================================================================
+// Registry 索引
+// ============================================================================
+
+/// 计算 registry 索引版本
+///
+/// 优先使用 registry.json 的 `version` 字段，缺失时使用内容哈希
+fn index_version(index: &RegistryIndex) -> String {
+    if !index.version.is_empty() {
+        return index.version.clone();
+    }
+    let bytes = serde_json::to_vec(index).unwrap_or_default();
+    calculate_sha256_bytes(&bytes)[..16].to_string()
+}
+
+/// 从 registry 条目中取出资源 URL 列表
+fn asset_urls(entry: &RegistryEntry, kind: RegistryAssetKind) -> Vec<String> {
+    match kind {
+        RegistryAssetKind::Icon => entry.icon.iter().cloned().collect(),
+        RegistryAssetKind::Screenshots => entry.screenshots.iter().take(MAX_SCREENSHOTS).cloned().collect(),
+    }
+}
+
+// ============================================================================
+// 资源缓存
+// ============================================================================
+
+/// Registry 资源缓存
+pub struct RegistryAssetCache {
+    /// 缓存根目录
+    cache_dir: PathBuf,
+    /// 下载持有读锁，清空缓存目录持有写锁
+    dir_lock: RwLock<()>,
+}
+
+impl RegistryAssetCache {
+    pub fn new(cache_dir: PathBuf) -> Self {
+        Self {
+            cache_dir,
+            dir_lock: RwLock::new(()),
+        }
+    }
+
+    /// 使用默认目录创建 (系统缓存目录/cuk/registry-cache)
+    pub fn with_default_dir() -> Self {
+        let dir = dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("cuk")
+            .join("registry-cache");
+        Self::new(dir)
+    }
+
+    /// 获取插件资源的本地路径，未缓存时下载
+    ///
+    /// 资源地址取自 `registry` 客户端验证过签名的索引（`registry_url` 为空时使用默认地址）。
+    pub async fn get_asset(
+        &self,
+        registry: &RegistryClient,
+        registry_url: Option<&str>,
+        plugin_id: &str,
+        kind: RegistryAssetKind,
+    ) -> Result<RegistryAsset, RegistryCacheError> {
+        if !is_safe_plugin_id(plugin_id) {
+            return Err(RegistryCacheError::InvalidArgument(format!(
+                "无效的插件 ID: {}",
+                plugin_id
+            )));
+        }
+
+        let index = registry.index(registry_url).await?;
+        let entry = index
+            .get(plugin_id)
+            .ok_or_else(|| RegistryCacheError::PluginNotFound(plugin_id.to_string()))?;
+        let version = index_version(&index);
+        // 持有读锁直到下载完成，索引版本变化时的清空操作等待进行中的下载
+        let _dir_guard = self.sync_index_version(&version).await?;
+
+        let urls = asset_urls(entry, kind);
+        if urls.is_empty() {
+            return Err(RegistryCacheError::AssetNotFound(format!(
+                "{} ({:?})",
+                plugin_id, kind
+            )));
+        }
+
+        let plugin_dir = self.cache_dir.join(plugin_id);
+        fs::create_dir_all(&plugin_dir).await?;
+
+        let mut paths = Vec::with_capacity(urls.len());
+        for (index, url) in urls.iter().enumerate() {
+            let stem = format!("{}-{}", kind.file_prefix(), index);
+            let path = match find_cached(&plugin_dir, &stem).await {
+                Some(path) => path,
+                None => self.download_asset(url, &plugin_dir, &stem).await?,
+            };
+            paths.push(path.to_string_lossy().to_string());
+        }
+
+        Ok(RegistryAsset {
+            plugin_id: plugin_id.to_string(),
+            kind,
+            paths,
+            index_version: version,
+        })
+    }
+
+    /// 比较磁盘缓存记录的索引版本，不一致时清空缓存
+    ///
+    /// 返回缓存目录读锁：调用方持有期间其他请求不会清空缓存目录。
+    async fn sync_index_version(&self, version: &str) -> Result<RwLockReadGuard<'_, ()>, RegistryCacheError> {
+        let version_path = self.cache_dir.join(VERSION_FILE);
+        {
+            let guard = self.dir_lock.read().await;
+            let stored = fs::read_to_string(&version_path).await.ok();
+            if stored.as_deref().map(str::trim) == Some(version) {
+                return Ok(guard);
+            }
+        }
+
+        // 等待进行中的下载完成后再清空
+        let guard = self.dir_lock.write().await;
+        let stored = fs::read_to_string(&version_path).await.ok();
+        if stored.as_deref().map(str::trim) == Some(version) {
+            return Ok(guard.downgrade());
+        }
+
+        if fs::metadata(&self.cache_dir).await.is_ok() {
+            log::info!(
+                "registry 索引版本变化 ({:?} -> {})，清空资源缓存",
+                stored.as_deref().map(str::trim),
+                version
+            );
+            fs::remove_dir_all(&self.cache_dir).await?;
+        }
+
+        fs::create_dir_all(&self.cache_dir).await?;
+        fs::write(&version_path, version).await?;
+        Ok(guard.downgrade())
+    }
+
+    /// 下载单个资源，校验大小和内容后写入缓存
+    async fn download_asset(
+        &self,
+        url: &str,
+        dir: &Path,
+        stem: &str,
+    ) -> Result<PathBuf, RegistryCacheError> {
+        let (client, parsed) = secure_client(url)
+            .await
+            .map_err(|e| RegistryCacheError::InvalidAsset(format!("资源地址不可用: {}", e)))?;
+
+        let mut response = client
+            .get(parsed)
+            .send()
+            .await
+            .map_err(|e| RegistryCacheError::Download(format!("HTTP 请求失败: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(RegistryCacheError::Download(format!(
+                "HTTP {}: {}",
+                response.status(),
+                url
+            )));
+        }
+
+        if let Some(len) = response.content_length() {
+            if len as usize > MAX_ASSET_SIZE {
+                return Err(RegistryCacheError::InvalidAsset(format!(
+                    "资源过大: {} bytes (上限 {} bytes)",
+                    len, MAX_ASSET_SIZE
+                )));
+            }
+        }
+
+        // 流式读取，防止服务器绕过 Content-Length 检查
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| RegistryCacheError::Download(format!("读取响应失败: {}", e)))?
+        {
+            if bytes.len() + chunk.len() > MAX_ASSET_SIZE {
+                return Err(RegistryCacheError::InvalidAsset(format!(
+                    "资源超过大小上限: {} bytes",
+                    MAX_ASSET_SIZE
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let format = detect_image_format(&bytes).ok_or_else(|| {
+            RegistryCacheError::InvalidAsset(format!("不支持的图片格式: {}", url))
+        })?;
+
+        // 先写临时文件再重命名，避免留下半截文件
+        let path = dir.join(format!("{}.{}", stem, format.extension()));
+        let tmp_path = dir.join(format!("{}.tmp", stem));
+        let mut file = fs::File::create(&tmp_path).await?;
+        file.write_all(&bytes).await?;
+        file.flush().await?;
+        drop(file);
+        fs::rename(&tmp_path, &path).await?;
+
+        log::debug!("registry 资源已缓存: {} -> {:?}", url, path);
+        Ok(path)
+    }
+}
+
+/// 查找已缓存的资源文件（任意已知扩展名）
+async fn find_cached(dir: &Path, stem: &str) -> Option<PathBuf> {
+    for ext in ["png", "jpg", "gif", "webp", "svg"] {
+        let path = dir.join(format!("{}.{}", stem, ext));
+        if fs::metadata(&path).await.map(|m| m.is_file()).unwrap_or(false) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Registry 资源缓存状态
+pub struct RegistryCacheState(pub Arc<RegistryAssetCache>);
+
+/// 创建默认的 Registry 资源缓存
+pub fn create_registry_cache() -> RegistryCacheState {
+    RegistryCacheState(Arc::new(RegistryAssetCache::with_default_dir()))
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_image_format() {
+        assert_eq!(
+            detect_image_format(b"\x89PNG\r\n\x1a\n0000"),
+            Some(ImageFormat::Png)
+        );
+        assert_eq!(detect_image_format(&[0xFF, 0xD8, 0xFF, 0xE0]), Some(ImageFormat::Jpeg));
+        assert_eq!(detect_image_format(b"RIFF0000WEBPVP8 "), Some(ImageFormat::Webp));
+        assert_eq!(
+            detect_image_format(b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>"),
+            Some(ImageFormat::Svg)
+        );
+        assert_eq!(detect_image_format(b"<html><body></body></html>"), None);
+        assert_eq!(detect_image_format(b"PK\x03\x04"), None);
+    }
+
+    #[test]
+    fn test_reject_svg_with_script() {
+        assert_eq!(detect_image_format(b"<svg><script>alert(1)</script></svg>"), None);
+        assert_eq!(detect_image_format(b"<svg onload=\"alert(1)\"></svg>"), None);
+        assert_eq!(detect_image_format(b"<svg/onload=alert(1)>"), None);
+        assert_eq!(detect_image_format(b"<svg><rect\nONCLICK =\"x()\"/></svg>"), None);
+        assert_eq!(detect_image_format(b"<svg><g x=\"1\"/onmouseover=x()></g></svg>"), None);
+        // 普通属性和文本中的 on 不误判
+        assert_eq!(
+            detect_image_format(b"<svg version=\"1.1\"><text>turn on = off</text></svg>"),
+            Some(ImageFormat::Svg)
+        );
+    }
+
+    #[test]
+    fn test_safe_plugin_id() {
+        assert!(is_safe_plugin_id("claude-usage"));
+        assert!(is_safe_plugin_id("openai_balance.v2"));
+        assert!(!is_safe_plugin_id(""));
+        assert!(!is_safe_plugin_id("../etc"));
+        assert!(!is_safe_plugin_id("a/b"));
+        assert!(!is_safe_plugin_id(".hidden"));
+    }
+
+    fn index(value: serde_json::Value) -> RegistryIndex {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_index_version() {
+        assert_eq!(index_version(&index(serde_json::json!({"version": "2024.1", "plugins": []}))), "2024.1");
+
+        let a = index_version(&index(serde_json::json!({"plugins": [{"id": "a", "name": "A", "version": "1"}]})));
+        let b = index_version(&index(serde_json::json!({"plugins": [{"id": "b", "name": "B", "version": "1"}]})));
+        assert_eq!(a.len(), 16);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_asset_urls() {
+        let registry = index(serde_json::json!({"plugins": [
+            {
+                "id": "demo",
+                "name": "Demo",
+                "version": "1.0.0",
+                "icon": "https://example.com/icon.png",
+                "screenshots": ["https://example.com/1.png", "https://example.com/2.png"]
+            },
+            {"id": "x", "name": "X", "version": "1.0.0"}
+        ]}));
+        let entry = registry.get("demo").unwrap();
+        assert_eq!(asset_urls(entry, RegistryAssetKind::Icon).len(), 1);
+        assert_eq!(asset_urls(entry, RegistryAssetKind::Screenshots).len(), 2);
+        assert!(asset_urls(registry.get("x").unwrap(), RegistryAssetKind::Icon).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_private_asset_url_rejected() {
+        let temp = TempDir::new().unwrap();
+        let cache = RegistryAssetCache::new(temp.path().join("cache"));
+        for url in ["http://example.com/icon.png", "https://127.0.0.1/icon.png", "https://10.0.0.8/icon.png"] {
+            assert!(matches!(
+                cache.download_asset(url, temp.path(), "icon-0").await,
+                Err(RegistryCacheError::InvalidAsset(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_version_change_invalidates_cache() {
+        let temp = TempDir::new().unwrap();
+        let cache = RegistryAssetCache::new(temp.path().join("cache"));
+
+        drop(cache.sync_index_version("1").await.unwrap());
+        let asset_dir = cache.cache_dir.join("demo");
+        std::fs::create_dir_all(&asset_dir).unwrap();
+        std::fs::write(asset_dir.join("icon-0.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+
+        // 相同版本：保留缓存
+        drop(cache.sync_index_version("1").await.unwrap());
+        assert!(find_cached(&asset_dir, "icon-0").await.is_some());
+
+        // 版本变化：等待进行中的下载（持有读锁）结束后才清空缓存
+        let cache = Arc::new(cache);
+        let download_guard = cache.sync_index_version("1").await.unwrap();
+        let c = cache.clone();
+        let invalidate = tokio::spawn(async move { c.sync_index_version("2").await.map(|_| ()) });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(find_cached(&asset_dir, "icon-0").await.is_some());
+        drop(download_guard);
+        invalidate.await.unwrap().unwrap();
+        assert!(find_cached(&asset_dir, "icon-0").await.is_none());
+        let stored = std::fs::read_to_string(cache.cache_dir.join(VERSION_FILE)).unwrap();
+        assert_eq!(stored, "2");
+    }
+
+    #[test]
+    fn test_registry_cache_error_to_app_error() {
+        let error = RegistryCacheError::InvalidAsset("too big".to_string());
+        let app_error: AppError = error.into();
+        assert_eq!(app_error.code, "ASSET_INVALID");
+    }
+}
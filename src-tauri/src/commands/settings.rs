@@ -0,0 +1,79 @@
+// Phase 7.6: 应用设置
+// 设置模型与持久化见 settings.rs；这里负责托管状态和变更广播
+// Phase 6.17: 看门狗策略变更时同步到 PluginManager
+// Phase 4.5: 代理设置变更时同步到 PluginManager（密码从 Keychain 读取）
+// Phase 4.6: 自定义根证书与证书固定变更时同步到 PluginManager
+// Phase 7.1: 托盘点击配置变更时刷新托盘工具提示
+
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager};
+
+use crate::commands::plugin::PluginManagerState;
+use crate::plugin::sandbox::secrets::KeychainStore;
+use crate::plugin::PluginManager;
+use crate::settings::{AppSettings, SettingsStore, TrayDisplayMode};
+use crate::tray;
+use crate::window;
+
+/// 广播设置变更时使用的状态类型
+pub const SETTINGS_STATE_TYPE: &str = "settings";
+
+/// 应用设置状态
+pub struct SettingsState(pub Arc<SettingsStore>);
+
+/// 创建应用设置存储（加载并迁移 app_settings.json）
+pub fn create_settings_store(manager: &PluginManager) -> SettingsState {
+    SettingsState(Arc::new(SettingsStore::load(SettingsStore::file_path(
+        manager.plugins_dir(),
+    ))))
+}
+
+/// 将设置变更同步到所有窗口
+///
+/// 主题模式变化时同时应用到原生窗口，并发送 window:theme_changed；切回弹窗模式时隐藏 HUD；
+/// 看门狗策略、代理和 TLS 设置变化时同步到 PluginManager；托盘点击配置变化时刷新工具提示
+pub fn broadcast_settings(
+    app: &AppHandle,
+    previous: &AppSettings,
+    settings: &AppSettings,
+    source_window: Option<&str>,
+) {
+    let data = match serde_json::to_value(settings) {
+        Ok(data) => data,
+        Err(e) => {
+            log::warn!("序列化应用设置失败: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = window::broadcast_state(app, SETTINGS_STATE_TYPE, data, source_window) {
+        log::warn!("广播应用设置失败: {}", e);
+    }
+    if previous.watchdog != settings.watchdog {
+        if let Some(manager) = app.try_state::<PluginManagerState>() {
+            manager.0.set_watchdog_policy(settings.watchdog);
+        }
+    }
+    if previous.proxy != settings.proxy {
+        if let Some(manager) = app.try_state::<PluginManagerState>() {
+            manager.0.set_proxy(settings.proxy.resolve(&KeychainStore));
+        }
+    }
+    if previous.tls != settings.tls {
+        if let Some(manager) = app.try_state::<PluginManagerState>() {
+            manager.0.set_tls(settings.tls.resolve());
+        }
+    }
+    if previous.tray_click != settings.tray_click {
+        if let Err(e) = tray::apply_click_config(app, settings.tray_click) {
+            log::warn!("更新托盘工具提示失败: {}", e);
+        }
+    }
+    if previous.theme != settings.theme {
+        window::theme::apply_theme(app, settings.theme);
+    }
+    // 切回弹窗模式时收起 HUD
+    if settings.tray_display == TrayDisplayMode::Popup && window::hud::hide(app) {
+        log::info!("已切换为托盘弹窗模式，隐藏 HUD");
+    }
+}
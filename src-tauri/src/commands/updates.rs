@@ -0,0 +1,154 @@
+// Phase 5A.7: 插件更新检查
+// 后台定期比对 registry 版本，通知前端并为信任的插件自动安装更新
+//
+// 自动更新流程:
+// 1. 经 registry 安装新版本（安装器会备份旧版本）
+// 2. 对已启用的数据插件执行一次 fetchData 作为更新后健康检查
+// 3. 健康检查失败时恢复备份版本
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::AppHandle;
+use tokio::sync::RwLock;
+
+use crate::commands::error::codes;
+use crate::commands::events::emitter;
+use crate::commands::installer::{install_plugin, rollback_plugin};
+use crate::plugin::registry::RegistryClient;
+use crate::plugin::types::{AppError, PluginInfo, UpdateInfo};
+use crate::plugin::updates::{find_updates, UpdateSettings};
+use crate::plugin::PluginManager;
+
+/// 首次检查延迟（等待插件系统初始化完成）
+const INITIAL_CHECK_DELAY: Duration = Duration::from_secs(120);
+
+/// 更新检查器状态
+pub struct UpdateCheckerState(pub Arc<UpdateChecker>);
+
+/// 创建更新检查器（设置从 updates.json 加载）
+pub fn create_update_checker(
+    manager: Arc<PluginManager>,
+    registry: Arc<RegistryClient>,
+) -> UpdateCheckerState {
+    let path = UpdateSettings::file_path(manager.plugins_dir());
+    let settings = UpdateSettings::load(&path);
+    UpdateCheckerState(Arc::new(UpdateChecker {
+        manager,
+        registry,
+        settings: RwLock::new(settings),
+        path,
+    }))
+}
+
+/// 插件更新检查器
+pub struct UpdateChecker {
+    manager: Arc<PluginManager>,
+    registry: Arc<RegistryClient>,
+    settings: RwLock<UpdateSettings>,
+    path: PathBuf,
+}
+
+impl UpdateChecker {
+    /// 当前更新设置
+    pub async fn settings(&self) -> UpdateSettings {
+        self.settings.read().await.clone()
+    }
+
+    /// 保存更新设置
+    pub async fn set_settings(&self, settings: UpdateSettings) -> std::io::Result<()> {
+        settings.save(&self.path)?;
+        *self.settings.write().await = settings;
+        Ok(())
+    }
+
+    /// 检查可用更新
+    pub async fn check(&self) -> Result<Vec<UpdateInfo>, AppError> {
+        // 管理员策略固定了 registry 地址时优先使用
+        let registry_url = match &self.manager.admin_policy().policy().registry_url {
+            Some(pinned) => Some(pinned.clone()),
+            None => self.settings.read().await.registry_url.clone(),
+        };
+        let index = self.registry.index(registry_url.as_deref()).await?;
+        let installed = self.manager.list_plugins().await;
+        Ok(find_updates(&installed, &index))
+    }
+
+    /// 安装更新，更新后健康检查失败时回滚
+    pub async fn apply(&self, update: &UpdateInfo) -> Result<PluginInfo, AppError> {
+        log::info!(
+            "安装插件更新: {} {} -> {}",
+            update.id,
+            update.current_version,
+            update.latest_version
+        );
+        let info = install_plugin(self.manager.clone(), &update.download_url, false, None, None, None).await?;
+
+        // 仅已启用的数据插件可执行健康检查
+        if !info.enabled || info.data_type.is_none() {
+            return Ok(info);
+        }
+        let Err(e) = self.manager.execute_fetch_data(&info.id, true).await else {
+            return Ok(info);
+        };
+
+        log::warn!("插件 {} 更新后健康检查失败，回滚: {}", info.id, e);
+        rollback_plugin(self.manager.clone(), &info.id, Some(&update.current_version)).await?;
+        Err(AppError::new(
+            codes::PLUGIN_UPDATE_ROLLED_BACK,
+            format!(
+                "插件 {} 更新到 {} 后健康检查失败，已回滚到 {}: {}",
+                update.id, update.latest_version, update.current_version, e
+            ),
+        ))
+    }
+}
+
+/// 启动后台更新检查
+pub fn spawn_update_checker(app: AppHandle, checker: Arc<UpdateChecker>) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(INITIAL_CHECK_DELAY).await;
+        loop {
+            let settings = checker.settings().await;
+            if settings.enabled {
+                run_check(&app, &checker, &settings).await;
+            }
+            tokio::time::sleep(settings.interval()).await;
+        }
+    });
+}
+
+/// 执行一次检查：通知可用更新，并为信任的插件自动安装
+async fn run_check(app: &AppHandle, checker: &UpdateChecker, settings: &UpdateSettings) {
+    let updates = match checker.check().await {
+        Ok(updates) => updates,
+        Err(e) => {
+            log::warn!("插件更新检查失败: {}", e.message);
+            return;
+        }
+    };
+    log::info!("插件更新检查完成: {} 个可用更新", updates.len());
+    if updates.is_empty() {
+        return;
+    }
+
+    if let Err(e) = emitter(app).emit_update_available(&updates) {
+        log::warn!("发送插件更新事件失败: {}", e);
+    }
+
+    for update in updates.iter().filter(|u| settings.is_trusted(&u.id)) {
+        match checker.apply(update).await {
+            Ok(info) => {
+                if let Err(e) = emitter(app).emit_plugin_updated(&info) {
+                    log::warn!("发送插件更新事件失败: plugin={}, emit_error={}", info.id, e);
+                }
+            }
+            Err(error) => {
+                if let Err(e) = emitter(app).emit_plugin_error(&update.id, &error) {
+                    log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", update.id, e);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+// Phase 6.5: 用量阈值提醒
+// 每次刷新得到新数据后按插件阈值检查使用量，通过系统通知中心提醒用户
+//
+// 阈值保存在 ConfigManager（usage_alerts.json），去重状态仅保存在内存中
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Local};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::alerts::AlertManagerState;
+use crate::commands::missed_alerts::deliver_notification;
+use crate::plugin::types::PluginData;
+use crate::plugin::{PluginManager, UsageAlert, UsageAlertTracker};
+
+/// 用量提醒跟踪器状态
+pub struct UsageAlertState(pub Arc<UsageAlertTracker>);
+
+/// 创建用量提醒跟踪器
+pub fn create_usage_alert_tracker() -> UsageAlertState {
+    UsageAlertState(Arc::new(UsageAlertTracker::new()))
+}
+
+/// 格式化重置时间（本地时间）
+fn format_reset_time(reset_time: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(reset_time.trim())
+        .ok()
+        .map(|t| t.with_timezone(&Local).format("%m-%d %H:%M").to_string())
+}
+
+/// 发送用量提醒通知
+fn notify_usage_alert(app: &AppHandle, plugin_name: &str, alert: &UsageAlert) {
+    let title = match &alert.dimension {
+        Some(dimension) => format!("{} {} 用量已达 {:.0}%", plugin_name, dimension, alert.threshold),
+        None => format!("{} 用量已达 {:.0}%", plugin_name, alert.threshold),
+    };
+    let mut body = format!("当前使用 {:.1}%", alert.percentage);
+    if let Some(reset) = alert.reset_time.as_deref().and_then(format_reset_time) {
+        body.push_str(&format!("，将于 {} 重置", reset));
+    }
+
+    log::info!(
+        "[{}] 用量提醒: dimension={:?}, threshold={}, percentage={:.1}",
+        alert.plugin_id,
+        alert.dimension,
+        alert.threshold,
+        alert.percentage
+    );
+    deliver_notification(app, &title, &body, false);
+}
+
+/// 检查刷新后的插件数据，越过阈值时发送提醒
+pub async fn check_usage_alerts(app: &AppHandle, manager: &PluginManager, data: &PluginData) {
+    let PluginData::Usage(usage) = data else {
+        return;
+    };
+    let Some(state) = app.try_state::<UsageAlertState>() else {
+        return;
+    };
+    let plugin_id = &usage.base.plugin_id;
+
+    let thresholds = manager.config_manager().usage_thresholds(plugin_id).await;
+    let alerts = state.0.evaluate(data, &thresholds);
+    if alerts.is_empty() {
+        return;
+    }
+    // 暂停/静音期间越过的阈值同样记为已触发，恢复后不补发
+    if app
+        .try_state::<AlertManagerState>()
+        .is_some_and(|alert_manager| alert_manager.0.is_silenced(plugin_id))
+    {
+        log::debug!("[{}] 提醒已静默，跳过 {} 条用量提醒", plugin_id, alerts.len());
+        return;
+    }
+
+    let plugin_name = manager
+        .get_plugin(plugin_id)
+        .await
+        .map(|p| p.name)
+        .unwrap_or_else(|| plugin_id.clone());
+    for alert in &alerts {
+        notify_usage_alert(app, &plugin_name, alert);
+    }
+}
@@ -0,0 +1,41 @@
+// Phase 6.17: 失控插件看门狗
+// PluginManager 在插件连续执行超时/内存超限达到阈值时自动禁用插件并发布到通道，
+// 宿主逐个触发 Critical 告警并发送 PLUGIN_AUTO_DISABLED 错误事件，用户可在插件列表中重新启用。
+// 看门狗策略保存在应用设置中，加载和修改时同步到 PluginManager。
+
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager};
+
+use crate::commands::alerts::AlertManagerState;
+use crate::commands::error::codes;
+use crate::commands::events::emitter;
+use crate::plugin::types::AppError;
+use crate::plugin::PluginManager;
+
+/// 启动看门狗通知：消费 PluginManager 的自动禁用通道
+pub fn spawn_watchdog_broadcaster(app: AppHandle, manager: Arc<PluginManager>) {
+    let Some(mut trips) = manager.take_watchdog_receiver() else {
+        log::warn!("看门狗通知已启动，跳过");
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        while let Some(trip) = trips.recv().await {
+            let id = &trip.plugin_id;
+            if let Some(alerts) = app.try_state::<AlertManagerState>() {
+                alerts.0.check_auto_disabled(&trip).await;
+            }
+
+            let error = AppError::new(
+                codes::PLUGIN_AUTO_DISABLED,
+                format!(
+                    "插件连续 {} 次执行超时或内存超限，已自动禁用: {}",
+                    trip.consecutive_runaways, trip.last_error
+                ),
+            );
+            if let Err(e) = emitter(&app).emit_plugin_error(id, &error) {
+                log::warn!("发送插件错误事件失败: plugin={}, emit_error={}", id, e);
+            }
+        }
+    });
+}
@@ -0,0 +1,105 @@
+// Phase 7.2: 首次设置向导
+// 向导状态机见 plugin/wizard.rs；这里负责应用各步骤的副作用:
+// - plugins: 启用选中的插件、停用未选中的插件
+// - apiKeys: 写入 Keychain 并在插件配置中保存 secretRef
+// - refreshInterval: 记录选择，完成时通过 wizard:finished 事件交给前端应用
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::plugin::wizard::{
+    validate_refresh_interval, ConfiguredSecret, SetupWizard, WizardError, WizardPluginOption,
+    WizardProgress, WizardState, WizardStepInput,
+};
+use crate::plugin::PluginManager;
+
+/// 首次设置向导状态
+pub struct SetupWizardState(pub Arc<SetupWizard>);
+
+/// 创建首次设置向导（加载进度与完成标记）
+pub fn create_setup_wizard(manager: &PluginManager) -> SetupWizardState {
+    SetupWizardState(Arc::new(SetupWizard::load(manager.plugins_dir())))
+}
+
+/// 向导状态（含可选择的插件）
+pub async fn wizard_state(wizard: &SetupWizard, manager: &PluginManager) -> WizardState {
+    let mut plugins = Vec::new();
+    // 向导只针对默认账户，其他账户在插件设置中添加
+    for info in manager.list_plugins().await.into_iter().filter(|p| p.account_label.is_none()) {
+        plugins.push(WizardPluginOption {
+            secret_names: manager.secret_names(&info.id).await,
+            id: info.id,
+            name: info.name,
+            enabled: info.enabled,
+        });
+    }
+    plugins.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let progress = wizard.progress();
+    WizardState {
+        completed: wizard.is_completed(),
+        current_step: progress.current_step(),
+        progress,
+        plugins,
+    }
+}
+
+/// 应用步骤的副作用并记录完成
+pub async fn complete_wizard_step(
+    wizard: &SetupWizard,
+    manager: &PluginManager,
+    input: WizardStepInput,
+) -> Result<WizardProgress, WizardError> {
+    let step = input.step();
+    wizard.check_step(step)?;
+
+    match input {
+        WizardStepInput::Welcome => wizard.complete_step(step, |_| {}),
+        WizardStepInput::Plugins { plugin_ids } => {
+            let plugins = manager.list_plugins().await;
+            let selected: HashSet<&str> = plugin_ids.iter().map(String::as_str).collect();
+            if let Some(unknown) = selected.iter().find(|id| !plugins.iter().any(|p| p.id == **id)) {
+                return Err(WizardError::InvalidInput(format!("插件不存在: {}", unknown)));
+            }
+            for plugin in &plugins {
+                let wanted = selected.contains(plugin.id.as_str());
+                let result = match (wanted, plugin.enabled) {
+                    (true, false) => manager.enable_plugin(&plugin.id).await,
+                    (false, true) => manager.disable_plugin(&plugin.id).await,
+                    _ => Ok(()),
+                };
+                if let Err(e) = result {
+                    log::warn!("[{}] 首次设置向导切换插件状态失败: {}", plugin.id, e);
+                }
+            }
+            let mut selected: Vec<String> = selected.into_iter().map(String::from).collect();
+            selected.sort();
+            wizard.complete_step(step, |progress| progress.selected_plugins = selected)
+        }
+        WizardStepInput::ApiKeys { keys } => {
+            let mut configured = Vec::new();
+            for key in keys {
+                if key.value.trim().is_empty() {
+                    return Err(WizardError::InvalidInput(format!("{} 的 {} 不能为空", key.plugin_id, key.name)));
+                }
+                manager
+                    .store_plugin_secret(&key.plugin_id, &key.name, key.value.trim())
+                    .await
+                    .map_err(|e| WizardError::InvalidInput(e.to_string()))?;
+                configured.push(ConfiguredSecret {
+                    plugin_id: key.plugin_id,
+                    name: key.name,
+                });
+            }
+            wizard.complete_step(step, |progress| {
+                progress.configured_secrets.extend(configured);
+                progress.configured_secrets.sort();
+                progress.configured_secrets.dedup();
+            })
+        }
+        WizardStepInput::RefreshInterval { interval_ms } => {
+            validate_refresh_interval(interval_ms)?;
+            wizard.complete_step(step, |progress| progress.refresh_interval_ms = interval_ms)
+        }
+    }
+}
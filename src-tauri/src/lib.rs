@@ -4,28 +4,50 @@
 use tauri::{Emitter, Manager};
 
 mod commands;
+mod logging;
 mod plugin;
 mod reliability;
 mod security;
+mod settings;
+mod shutdown;
+mod single_instance;
 mod state;
 mod tray;
+mod updater;
 mod window;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // 单实例检查 (Phase 7.11)：已有实例运行时唤起其托盘弹窗后退出
+    let primary_instance = match single_instance::acquire(&single_instance::lock_path()) {
+        Ok(single_instance::Instance::Primary(primary)) => Some(primary),
+        Ok(single_instance::Instance::Secondary) => {
+            eprintln!("CUK 已在运行，已唤起托盘弹窗");
+            return;
+        }
+        Err(e) => {
+            eprintln!("单实例检查失败，继续启动: {}", e);
+            None
+        }
+    };
+
     tauri::Builder::default()
-        .plugin(tauri_plugin_log::Builder::default().build())
+        .plugin(logging::plugin())
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_positioner::init())
+        // IPC 命令调用统计 (Phase 7.3.14)：所有 7.3 命令都依赖该状态，需在处理任何调用前注册
+        .manage(commands::create_ipc_metrics())
+        // 退出流程状态 (Phase 7.10)
+        .manage(shutdown::ShutdownState::new())
         .invoke_handler(tauri::generate_handler![
             // 基础命令
             commands::get_version,
             commands::health_check,
-            commands::keychain_set,
-            commands::keychain_get,
-            commands::keychain_delete,
-            // Phase 2 插件命令 (向后兼容)
+            crate::commands::ipc::keychain_set,
+            crate::commands::ipc::keychain_get,
+            crate::commands::ipc::keychain_delete,
+            // Phase 2 插件命令 (已弃用，映射到 7.3 实现)
             crate::commands::plugin::list_plugins,
             crate::commands::plugin::get_plugin,
             crate::commands::plugin::enable_plugin,
@@ -34,29 +56,137 @@ pub fn run() {
             crate::commands::plugin::get_plugins_dir,
             // Phase 7.3.1 插件管理 Commands (9个)
             crate::commands::ipc::plugin_list,
+            crate::commands::ipc::plugin_list_incompatible,
+            crate::commands::ipc::plugin_describe,
             crate::commands::ipc::plugin_enable,
             crate::commands::ipc::plugin_disable,
             crate::commands::ipc::plugin_install,
+            crate::commands::ipc::plugin_install_from_file,
+            crate::commands::ipc::plugin_install_cancel,
             crate::commands::ipc::plugin_uninstall,
+            crate::commands::ipc::plugin_bulk,
             crate::commands::ipc::plugin_reload,
             crate::commands::ipc::plugin_check_updates,
             crate::commands::ipc::plugin_update,
             crate::commands::ipc::plugin_rollback,
+            crate::commands::ipc::list_plugin_backups,
+            crate::commands::ipc::rollback_to,
             // Phase 7.3.2 数据 Commands (4个)
             crate::commands::ipc::get_all_data,
             crate::commands::ipc::get_plugin_data,
+            crate::commands::ipc::get_summary,
+            crate::commands::ipc::batch_query,
             crate::commands::ipc::refresh_plugin,
             crate::commands::ipc::refresh_all,
+            crate::commands::ipc::replay_parse,
+            crate::commands::ipc::plugin_test_run,
             // Phase 7.3.3 配置 Commands (3个)
             crate::commands::ipc::get_plugin_config,
             crate::commands::ipc::set_plugin_config,
             crate::commands::ipc::validate_plugin_config,
-            // Phase 7.3.4 监控 Commands (2个)
+            // Phase 7.3.4 监控 Commands (6个)
             crate::commands::ipc::get_all_health,
             crate::commands::ipc::get_plugin_health,
+            crate::commands::ipc::get_plugin_resource_stats,
+            crate::commands::ipc::get_system_health,
+            crate::commands::ipc::get_event_bus_stats,
+            crate::commands::ipc::get_dead_letters,
+            // Phase 5A.3 Registry 资源 Commands
+            crate::commands::ipc::get_registry_asset,
+            // Phase 5A.6 插件市场 Commands
+            crate::commands::ipc::registry_search,
+            crate::commands::ipc::registry_get_manifest,
+            crate::commands::ipc::registry_install,
+            // Phase 5A.7 插件更新 Commands
+            crate::commands::ipc::get_update_settings,
+            crate::commands::ipc::set_update_settings,
+            // Phase 6.5 用量提醒 Commands
+            crate::commands::ipc::get_usage_alert_settings,
+            crate::commands::ipc::set_usage_alert_settings,
+            // Phase 6.6 告警静默 Commands
+            crate::commands::ipc::alert_snooze,
+            crate::commands::ipc::alert_mute,
+            crate::commands::ipc::alert_unmute,
+            crate::commands::ipc::get_alert_history,
+            crate::commands::ipc::acknowledge_alert,
+            // Phase 6.10 未送达告警 Commands
+            crate::commands::ipc::get_missed_alerts,
+            crate::commands::ipc::clear_missed_alerts,
+            crate::commands::ipc::set_missed_alert_expiry,
+            // Phase 6.7 隐私报告与存储占用 Commands
+            crate::commands::ipc::get_privacy_report,
+            crate::commands::ipc::get_storage_stats,
+            // Phase 6.12 插件日志与诊断 Commands
+            crate::commands::ipc::get_plugin_logs,
+            crate::commands::ipc::clear_plugin_logs,
+            crate::commands::ipc::generate_diagnostics,
+            crate::commands::ipc::get_app_logs,
+            // Phase 6.8 预算 Commands
+            crate::commands::ipc::get_budget_status,
+            crate::commands::ipc::set_budget,
+            // Phase 5A.8 开发者模式 Commands
+            crate::commands::ipc::get_developer_settings,
+            crate::commands::ipc::set_developer_settings,
+            // Phase 5A.9 .env 密钥导入 Commands
+            crate::commands::ipc::import_keys_from_env,
+            // Phase 4.6 数据推送 Commands
+            crate::commands::ipc::get_push_settings,
+            crate::commands::ipc::set_push_settings,
+            crate::commands::ipc::issue_push_token,
+            crate::commands::ipc::revoke_push_token,
             // Phase 7.3.5 窗口 Commands
             crate::commands::ipc::open_dashboard,
+            crate::commands::ipc::get_popup_pinned,
+            crate::commands::ipc::set_popup_pinned,
+            crate::commands::ipc::get_popup_summary,
+            crate::commands::ipc::get_usage_trend,
+            crate::commands::ipc::estimate_cost,
+            crate::commands::ipc::get_price_table,
+            crate::commands::ipc::update_price_table,
+            // Phase 7.3.6 托盘 Commands
+            crate::commands::ipc::get_tray_click_config,
+            crate::commands::ipc::set_tray_click_config,
+            // Phase 7.3.7 插件输入请求 Commands
+            crate::commands::ipc::get_pending_inputs,
+            crate::commands::ipc::submit_input,
+            // Phase 7.3.8 网络状态 Commands
+            crate::commands::ipc::get_network_status,
+            // Phase 7.3.9 完整性校验 Commands
+            crate::commands::ipc::verify_plugin_integrity,
+            crate::commands::ipc::get_audit_events,
+            crate::commands::ipc::get_publisher_keys,
+            crate::commands::ipc::update_publisher_keys,
+            // Phase 7.3.10 后台活动 Commands
+            crate::commands::ipc::get_app_nap_prevention,
+            crate::commands::ipc::set_app_nap_prevention,
+            // Phase 7.3.11 网关 Commands
+            crate::commands::ipc::get_gateway_config,
+            crate::commands::ipc::set_gateway_config,
+            // Phase 7.3.13 旧版命令 Commands
+            crate::commands::ipc::get_legacy_command_report,
+            crate::commands::ipc::set_legacy_commands_enabled,
+            // Phase 7.2 首次设置向导 Commands
+            crate::commands::ipc::wizard_get_state,
+            crate::commands::ipc::wizard_complete_step,
+            crate::commands::ipc::wizard_finish,
+            // Phase 7.6 应用设置 Commands
+            crate::commands::ipc::get_app_settings,
+            crate::commands::ipc::set_app_settings,
+            crate::commands::ipc::reset_app_settings,
+            crate::commands::ipc::get_theme,
+            // Phase 7.3.14 IPC 追踪 Commands
+            crate::commands::ipc::get_ipc_metrics,
+            // Phase 7.12 应用更新 Commands
+            crate::commands::ipc::get_app_update_status,
+            crate::commands::ipc::check_for_app_updates,
+            crate::commands::ipc::download_app_update,
+            crate::commands::ipc::install_app_update,
+            crate::commands::ipc::add_plugin_account,
+            crate::commands::ipc::remove_plugin_account,
+            crate::commands::ipc::get_plugin_accounts,
         ])
+        // 托盘弹窗失焦自动隐藏
+        .on_window_event(window::handle_window_event)
         .setup(|app| {
             // 初始化日志
             log::info!("CUK 应用启动中...");
@@ -67,6 +197,9 @@ pub fn run() {
                 app.set_activation_policy(tauri::ActivationPolicy::Accessory);
             }
 
+            // 托盘菜单中的插件列表由插件初始化任务写入，需先于该任务注册
+            app.manage(tray::TrayManagerState::new());
+
             // 初始化插件管理器 (Phase 2)
             let plugin_manager = commands::create_plugin_manager();
 
@@ -84,6 +217,10 @@ pub fn run() {
                         for plugin in &plugins {
                             log::debug!("  - {} v{} ({})", plugin.name, plugin.version, plugin.id);
                         }
+                        // 按发现的插件生成托盘菜单的插件子菜单 (Phase 7.1)
+                        if let Err(e) = tray::rebuild_menu(&app_handle, &plugins) {
+                            log::warn!("重建托盘菜单失败: {}", e);
+                        }
                         // 通知前端插件系统已就绪
                         if let Err(e) = app_handle.emit("ipc:plugins_ready", plugins.len()) {
                             log::warn!("发送插件就绪事件失败: {}", e);
@@ -97,17 +234,178 @@ pub fn run() {
                 }
             });
 
+            // 消费插件输入请求：通知前端并打开输入对话框
+            if let Some(mut input_rx) = plugin_manager.0.input_broker().take_receiver() {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    while let Some(request) = input_rx.recv().await {
+                        if let Err(e) = commands::emitter(&app_handle).emit_input_requested(&request) {
+                            log::warn!("发送输入请求事件失败: {}", e);
+                        }
+                        window::WindowManager::open(&app_handle, window::WindowType::Input);
+                    }
+                });
+            }
+
+            // 持久定时器触发：在新的沙盒中调用插件的 onTimer (Phase 2.2.9)
+            if let Some(mut timer_rx) = plugin_manager.0.host_timers().take_receiver() {
+                let manager = plugin_manager.0.clone();
+                tauri::async_runtime::spawn(async move {
+                    while let Some(fire) = timer_rx.recv().await {
+                        let manager = manager.clone();
+                        tauri::async_runtime::spawn(async move { manager.run_timer(fire).await });
+                    }
+                });
+            }
+
+            // EventBus 事件投递：在订阅插件的沙盒中执行 onEvent (Phase 4.1)
+            if let Some(mut event_rx) = plugin_manager.0.take_event_receiver() {
+                let manager = plugin_manager.0.clone();
+                tauri::async_runtime::spawn(async move {
+                    while let Some(delivery) = event_rx.recv().await {
+                        let manager = manager.clone();
+                        tauri::async_runtime::spawn(async move { manager.deliver_event(delivery).await });
+                    }
+                });
+            }
+
+            // 插件 console 日志实时推送到日志查看器 (plugin:log)
+            if let Some(mut log_rx) = plugin_manager.0.plugin_logs().take_receiver() {
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    while let Some(entry) = log_rx.recv().await {
+                        if let Err(e) = commands::emitter(&handle).emit_plugin_log(&entry) {
+                            log::debug!("推送插件日志失败: plugin={}, emit_error={}", entry.plugin_id, e);
+                        }
+                    }
+                });
+            }
+
+            // 插件数据更新推送到所有窗口 (plugin:data_updated)
+            commands::spawn_data_update_broadcaster(app.handle().clone(), plugin_manager.0.clone());
+
+            // 插件健康状态变化推送并驱动托盘状态 (plugin:health_changed, Phase 6.15)
+            commands::spawn_health_change_broadcaster(app.handle().clone(), plugin_manager.0.clone());
+
+            // 失控插件自动禁用告警 (Phase 6.17)
+            commands::spawn_watchdog_broadcaster(app.handle().clone(), plugin_manager.0.clone());
+
+            // 初始化网络监视器并启动后台探测 (离线模式)
+            let network_monitor = commands::create_network_monitor();
+            commands::spawn_network_watcher(
+                app.handle().clone(),
+                network_monitor.0.clone(),
+                plugin_manager.0.clone(),
+            );
+            app.manage(network_monitor);
+
+            // 批量刷新期间的 App Nap 规避 (默认开启，可在设置中关闭)
+            app.manage(reliability::ActivityAssertions::new());
+
+            // 定期校验已安装插件的文件完整性
+            commands::spawn_integrity_watcher(app.handle().clone(), plugin_manager.0.clone());
+
+            // 本地推送端点 (Phase 4.6，默认关闭)
+            let push_server = commands::create_push_server(plugin_manager.0.clone());
+            commands::spawn_push_server(app.handle().clone(), push_server.0.clone());
+            app.manage(push_server);
+
+            // 定期清理外部删除插件残留的 Phase 4 注册
+            commands::spawn_registration_reconciler(app.handle().clone(), plugin_manager.0.clone());
+
+            // 开发者插件热重载 (Phase 5A.8)
+            commands::spawn_dev_hot_reload(app.handle().clone(), plugin_manager.0.clone());
+
+            // 插件市场 registry 客户端与后台更新检查 (Phase 5A.6/5A.7)
+            let registry_client = commands::create_registry_client();
+            let update_checker =
+                commands::create_update_checker(plugin_manager.0.clone(), registry_client.0.clone());
+            commands::spawn_update_checker(app.handle().clone(), update_checker.0.clone());
+            app.manage(registry_client);
+            app.manage(update_checker);
+
+            // 应用自动更新：后台每天检查一次，下载与安装由用户确认 (Phase 7.12)
+            let app_updater = updater::create_app_updater(app.handle());
+            updater::spawn_app_update_checker(app.handle().clone(), app_updater.0.clone());
+            app.manage(app_updater);
+
+            // 告警管理器与暂停/静音设置 (Phase 6.6)
+            app.manage(commands::create_alert_manager(app.handle().clone(), &plugin_manager.0));
+
+            // 未送达告警队列与后台重投 (Phase 6.10)
+            app.manage(commands::create_missed_alert_queue(&plugin_manager.0));
+            commands::spawn_missed_alert_retrier(app.handle().clone());
+
+            // 月度预算设置与花费账本 (Phase 6.8)
+            app.manage(commands::create_budget_tracker(&plugin_manager.0));
+
+            // 应用设置 (Phase 7.6)，看门狗策略 (Phase 6.17)、代理 (Phase 4.5) 和 TLS (Phase 4.6) 同步到 PluginManager
+            let settings_store = commands::create_settings_store(&plugin_manager.0);
+            let settings = settings_store.0.get();
+            plugin_manager.0.set_watchdog_policy(settings.watchdog);
+            if settings.proxy.enabled {
+                plugin_manager.0.set_proxy(settings.proxy.resolve(&plugin::sandbox::secrets::KeychainStore));
+            }
+            if let Some(tls) = settings.tls.resolve() {
+                plugin_manager.0.set_tls(Some(tls));
+            }
+            app.manage(settings_store);
+
+            // 仪表盘/设置窗口位置与尺寸 (Phase 7.8)
+            app.manage(window::geometry::WindowGeometryStore::load(
+                window::geometry::WindowGeometryStore::file_path(plugin_manager.0.plugins_dir()),
+            ));
+
+            // 首次设置向导进度与完成标记 (Phase 7.2)
+            app.manage(commands::create_setup_wizard(&plugin_manager.0));
+
+            // Phase 2 旧版命令弃用设置与调用统计 (Phase 7.3.13)
+            app.manage(commands::create_legacy_commands(&plugin_manager.0));
+
             app.manage(plugin_manager);
             log::info!("插件管理器已创建");
 
+            // 进行中的插件安装，供取消解压使用 (Phase 5A.3)
+            app.manage(commands::create_install_cancellations());
+
+            // 初始化 Registry 资源缓存 (Phase 5A.3)
+            app.manage(commands::create_registry_cache());
+
+            // 用量阈值提醒去重状态 (Phase 6.5)
+            app.manage(commands::create_usage_alert_tracker());
+
+            // 刷新结果差异跟踪 (Phase 6.11)
+            app.manage(commands::create_data_diff_tracker());
+
+            // 托盘状态需在托盘创建前注册（托盘点击配置来自应用设置，用于生成工具提示）
+            app.manage(window::PopupState::new());
+            app.manage(commands::create_popup_summary_state());
+
+            // 主题管理：应用保存的主题模式，跟随系统时监听外观变化 (Phase 7.7)
+            app.manage(window::theme::create_theme_manager(app.handle()));
+            if let Some(settings) = app.try_state::<commands::settings::SettingsState>() {
+                window::theme::apply_native_theme(app.handle(), settings.0.get().theme);
+            }
+
             // 初始化系统托盘 (Phase 7.1)
             match tray::setup_tray(app.handle()) {
                 Ok(_) => log::info!("系统托盘已初始化"),
                 Err(e) => log::error!("系统托盘初始化失败: {}", e),
             }
 
-            // 初始化托盘管理器
-            app.manage(tray::TrayManager::new());
+            // 重复启动时唤起托盘弹窗 (Phase 7.11)
+            if let Some(primary) = primary_instance {
+                let handle = app.handle().clone();
+                single_instance::serve(primary, move || {
+                    let app = handle.clone();
+                    if let Err(e) = handle.run_on_main_thread(move || tray::show_popup(&app)) {
+                        log::warn!("唤起托盘弹窗失败: {}", e);
+                    }
+                });
+            }
+
+            // 首次启动时显示设置向导
+            window::show_wizard(app.handle());
 
             // 获取主窗口引用
             let main_window = app.get_webview_window("main");
@@ -122,6 +420,8 @@ pub fn run() {
             log::info!("CUK 应用启动完成");
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        // 退出前关闭插件系统 (Phase 7.10)
+        .run(shutdown::handle_run_event);
 }
@@ -0,0 +1,254 @@
+// Phase 7.5: 应用日志
+// 配置 tauri_plugin_log，除 stdout 外将应用日志写入日志目录，便于用户自行排查启动问题:
+// - macOS: ~/Library/Logs/CUK；其他平台: {本地数据目录}/cuk/logs
+// - 按大小轮转: cuk.log 超过 MAX_LOG_FILE_BYTES 后依次重命名为 cuk.1.log … cuk.N.log，
+//   最多保留 MAX_ROTATED_FILES 个旧文件。运行期间也会轮转（插件自带的轮转只在启动时检查）
+// - 每行格式: `{RFC3339 时间} [{级别}] [{target}] {消息}`，多行消息的后续行归入上一条
+// - get_app_logs 从最新文件倒序读取最近 N 条
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::Runtime;
+use tauri_plugin_log::{fern, Target, TargetKind};
+
+/// 日志文件名（不含扩展名）
+pub const LOG_FILE_NAME: &str = "cuk";
+
+/// 单个日志文件的最大大小 (5MB)
+pub const MAX_LOG_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// 保留的旧日志文件数量
+pub const MAX_ROTATED_FILES: usize = 5;
+
+/// 应用日志目录
+pub fn log_dir() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    if let Some(home) = dirs::home_dir() {
+        return home.join("Library").join("Logs").join("CUK");
+    }
+    dirs::data_local_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("cuk")
+        .join("logs")
+}
+
+/// 创建日志插件
+pub fn plugin<R: Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    let mut builder = tauri_plugin_log::Builder::new()
+        .clear_targets()
+        .target(Target::new(TargetKind::Stdout))
+        .level(log::LevelFilter::Info)
+        .format(|out, message, record| {
+            out.finish(format_args!(
+                "{} [{}] [{}] {}",
+                chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+                record.level(),
+                record.target(),
+                message
+            ))
+        });
+
+    match RotatingFile::open(log_dir(), MAX_LOG_FILE_BYTES, MAX_ROTATED_FILES) {
+        Ok(file) => {
+            let writer: Box<dyn Write + Send> = Box::new(file);
+            builder = builder.target(Target::new(TargetKind::Dispatch(
+                fern::Dispatch::new().chain(writer),
+            )));
+        }
+        // 日志尚未初始化，只能输出到 stderr
+        Err(e) => eprintln!("打开日志文件失败，仅输出到 stdout: {}", e),
+    }
+
+    builder.build()
+}
+
+// ============================================================================
+// 轮转写入
+// ============================================================================
+
+/// 按大小轮转的日志文件
+pub struct RotatingFile {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    size: u64,
+    /// 上一次写入是否以换行结束（只在行边界轮转，避免一条日志被拆到两个文件）
+    at_line_start: bool,
+}
+
+impl RotatingFile {
+    /// 打开（或创建）`dir/cuk.log`
+    pub fn open(dir: PathBuf, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let file = OpenOptions::new().create(true).append(true).open(log_file_path(&dir, 0))?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            dir,
+            max_bytes,
+            max_files,
+            file,
+            size,
+            at_line_start: true,
+        })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let _ = fs::remove_file(log_file_path(&self.dir, self.max_files));
+        for index in (0..self.max_files).rev() {
+            let from = log_file_path(&self.dir, index);
+            if from.exists() {
+                fs::rename(&from, log_file_path(&self.dir, index + 1))?;
+            }
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_file_path(&self.dir, 0))?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.at_line_start && self.size >= self.max_bytes {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        if written > 0 {
+            self.at_line_start = buf[written - 1] == b'\n';
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// 第 index 个日志文件（0 为当前文件）
+fn log_file_path(dir: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        dir.join(format!("{}.log", LOG_FILE_NAME))
+    } else {
+        dir.join(format!("{}.{}.log", LOG_FILE_NAME, index))
+    }
+}
+
+// ============================================================================
+// 读取
+// ============================================================================
+
+/// 单条应用日志
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLogEntry {
+    /// 时间 (RFC3339)
+    pub timestamp: String,
+    /// 级别 (ERROR/WARN/INFO/DEBUG/TRACE)
+    pub level: String,
+    /// 日志来源模块
+    pub target: String,
+    /// 日志内容（可能含多行）
+    pub message: String,
+}
+
+/// 解析一行日志的头部，非日志头（多行消息的后续行）返回 None
+fn parse_line(line: &str) -> Option<AppLogEntry> {
+    let (timestamp, rest) = line.split_once(" [")?;
+    chrono::DateTime::parse_from_rfc3339(timestamp).ok()?;
+    let (level, rest) = rest.split_once("] [")?;
+    level.parse::<log::Level>().ok()?;
+    let (target, message) = rest.split_once("] ")?;
+    Some(AppLogEntry {
+        timestamp: timestamp.to_string(),
+        level: level.to_string(),
+        target: target.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// 解析单个日志文件
+fn read_entries(path: &Path) -> Vec<AppLogEntry> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<AppLogEntry> = Vec::new();
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        match parse_line(&line) {
+            Some(entry) => entries.push(entry),
+            None => {
+                if let Some(last) = entries.last_mut() {
+                    last.message.push('\n');
+                    last.message.push_str(&line);
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// 读取最近 `lines` 条不低于 `min_level` 的日志（按时间顺序）
+pub fn read_recent(dir: &Path, lines: usize, min_level: log::Level) -> Vec<AppLogEntry> {
+    let mut recent: Vec<AppLogEntry> = Vec::new();
+    for index in 0..=MAX_ROTATED_FILES {
+        if recent.len() >= lines {
+            break;
+        }
+        let path = log_file_path(dir, index);
+        if !path.exists() {
+            continue;
+        }
+        let wanted = lines - recent.len();
+        // 文件内按时间顺序，取末尾 wanted 条后拼接在更新的日志之前
+        let mut older: Vec<AppLogEntry> = read_entries(&path)
+            .into_iter()
+            .filter(|e| e.level.parse::<log::Level>().is_ok_and(|level| level <= min_level))
+            .collect();
+        let skip = older.len().saturating_sub(wanted);
+        older.drain(..skip);
+        older.append(&mut recent);
+        recent = older;
+    }
+    recent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(level: &str, message: &str) -> String {
+        format!("2026-01-01T00:00:00.000+00:00 [{}] [cuk_lib] {}\n", level, message)
+    }
+
+    #[test]
+    fn test_rotation_and_read_recent() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = RotatingFile::open(dir.path().to_path_buf(), 100, 2).unwrap();
+        for i in 0..11 {
+            let level = if i % 3 == 0 { "ERROR" } else { "INFO" };
+            file.write_all(line(level, &format!("message {}", i)).as_bytes()).unwrap();
+        }
+        file.write_all(line("INFO", "message 11\n  second line").as_bytes()).unwrap();
+
+        // 只保留当前文件 + 2 个旧文件
+        assert!(log_file_path(dir.path(), 2).exists());
+        assert!(!log_file_path(dir.path(), 3).exists());
+
+        let recent = read_recent(dir.path(), 3, log::Level::Trace);
+        let messages: Vec<&str> = recent.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["message 9", "message 10", "message 11\n  second line"]);
+        assert_eq!(recent[2].target, "cuk_lib");
+
+        let errors = read_recent(dir.path(), 2, log::Level::Error);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.level == "ERROR"));
+        assert_eq!(errors[1].message, "message 9");
+    }
+}
@@ -0,0 +1,205 @@
+// Phase 2.3.10: manifest API 版本协商与迁移
+// manifest 的 apiVersion 为 `主版本.次版本`，运行时支持 [MIN_API_VERSION, CURRENT_API_VERSION]:
+// - 低于当前版本的 manifest 在加载时按 MIGRATIONS 逐级升级到当前格式，插件无需修改
+// - 高于当前版本（需要更新应用）或低于最低版本（需要更新插件）的插件不加载，
+//   以 LifecycleError::IncompatibleApiVersion 报告给界面，附带处理建议
+//
+// 迁移只作用于解析后的 JSON，不修改磁盘上的 manifest.json，签名仍按原始内容校验。
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// manifest API 版本
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl ApiVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// 解析 `1` / `1.0` 形式的版本号
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().splitn(2, '.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = match parts.next() {
+            Some(minor) => minor.parse().ok()?,
+            None => 0,
+        };
+        Some(Self { major, minor })
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// 当前运行时的 API 版本
+pub const CURRENT_API_VERSION: ApiVersion = ApiVersion::new(1, 0);
+
+/// 可迁移的最低 API 版本
+pub const MIN_API_VERSION: ApiVersion = ApiVersion::new(0, 9);
+
+/// manifest 迁移步骤: (源版本, 目标版本, 迁移函数)，按版本升序排列
+const MIGRATIONS: &[(ApiVersion, ApiVersion, fn(&mut Map<String, Value>))] =
+    &[(ApiVersion::new(0, 9), ApiVersion::new(1, 0), migrate_0_9_to_1_0)];
+
+/// 0.9 → 1.0: 字段改名，刷新间隔从秒改为毫秒
+fn migrate_0_9_to_1_0(manifest: &mut Map<String, Value>) {
+    if let Some(plugin_type) = manifest.remove("type") {
+        manifest.entry("pluginType").or_insert(plugin_type);
+    }
+    if let Some(hosts) = manifest.remove("hosts") {
+        manifest.entry("allowedHosts").or_insert(hosts);
+    }
+    if let Some(seconds) = manifest.remove("refreshInterval").and_then(|v| v.as_u64()) {
+        manifest
+            .entry("refreshIntervalMs")
+            .or_insert_with(|| Value::from(seconds.saturating_mul(1000)));
+    }
+}
+
+// ============================================================================
+// 不兼容报告
+// ============================================================================
+
+/// 不兼容原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Incompatibility {
+    /// 缺少 apiVersion 或格式无效
+    Invalid,
+    /// 低于可迁移的最低版本
+    TooOld,
+    /// 高于当前运行时版本
+    TooNew,
+}
+
+impl Incompatibility {
+    /// 处理建议
+    pub fn guidance(&self) -> &'static str {
+        match self {
+            Incompatibility::Invalid => "manifest 的 apiVersion 缺失或格式无效（应为 主版本.次版本，如 1.0），请联系插件作者",
+            Incompatibility::TooOld => "插件使用的 API 版本已不再支持，请更新插件或联系插件作者",
+            Incompatibility::TooNew => "插件需要更新版本的应用，请更新应用后重试",
+        }
+    }
+}
+
+/// API 版本不兼容、未加载的插件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncompatiblePlugin {
+    /// 插件 ID（manifest 中缺失时为空）
+    pub plugin_id: String,
+    /// 显示名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// manifest 声明的 apiVersion（缺失时为空）
+    pub api_version: String,
+    /// 运行时支持的版本范围
+    pub supported: String,
+    pub reason: Incompatibility,
+    /// 处理建议
+    pub guidance: String,
+}
+
+impl fmt::Display for IncompatiblePlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "插件 {} 的 API 版本 \"{}\" 不兼容（支持 {}）: {}",
+            self.plugin_id, self.api_version, self.supported, self.guidance
+        )
+    }
+}
+
+// ============================================================================
+// 版本协商
+// ============================================================================
+
+/// 检查 manifest 的 apiVersion 并迁移到当前格式
+///
+/// 返回 manifest 原本声明的版本；迁移后 apiVersion 改写为当前版本。
+pub fn negotiate(manifest: &mut Value) -> Result<ApiVersion, IncompatiblePlugin> {
+    let field = |key: &str| manifest.get(key).and_then(|v| v.as_str()).map(String::from);
+    let (plugin_id, name, declared) = (field("id").unwrap_or_default(), field("name"), field("apiVersion"));
+    let incompatible = |reason: Incompatibility| IncompatiblePlugin {
+        plugin_id: plugin_id.clone(),
+        name: name.clone(),
+        api_version: declared.clone().unwrap_or_default(),
+        supported: format!("{} - {}", MIN_API_VERSION, CURRENT_API_VERSION),
+        reason,
+        guidance: reason.guidance().to_string(),
+    };
+
+    let version = match declared.as_deref().and_then(ApiVersion::parse) {
+        Some(version) => version,
+        None => return Err(incompatible(Incompatibility::Invalid)),
+    };
+    if version > CURRENT_API_VERSION {
+        return Err(incompatible(Incompatibility::TooNew));
+    }
+    if version < MIN_API_VERSION {
+        return Err(incompatible(Incompatibility::TooOld));
+    }
+    let Some(fields) = manifest.as_object_mut() else {
+        return Err(incompatible(Incompatibility::Invalid));
+    };
+
+    let mut current = version;
+    for (from, to, migrate) in MIGRATIONS {
+        if current <= *from {
+            migrate(fields);
+            current = *to;
+        }
+    }
+    if current != version {
+        log::info!("[{}] manifest 已从 API {} 迁移到 {}", plugin_id, version, current);
+        fields.insert("apiVersion".to_string(), Value::from(current.to_string()));
+    }
+    Ok(version)
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_negotiate_migrates_and_rejects() {
+        let mut legacy = json!({
+            "id": "legacy", "name": "Legacy", "version": "0.1.0", "apiVersion": "0.9",
+            "type": "data", "refreshInterval": 300, "hosts": ["api.example.com"]
+        });
+        assert_eq!(negotiate(&mut legacy).unwrap(), ApiVersion::new(0, 9));
+        assert_eq!(legacy["apiVersion"], "1.0");
+        assert_eq!(legacy["pluginType"], "data");
+        assert_eq!(legacy["refreshIntervalMs"], 300_000);
+        assert_eq!(legacy["allowedHosts"], json!(["api.example.com"]));
+        assert!(legacy.get("type").is_none());
+
+        let mut current = json!({"id": "x", "apiVersion": "1", "pluginType": "data"});
+        assert_eq!(negotiate(&mut current).unwrap(), CURRENT_API_VERSION);
+        assert_eq!(current["apiVersion"], "1");
+
+        let too_new = negotiate(&mut json!({"id": "x", "name": "X", "apiVersion": "2.0"})).unwrap_err();
+        assert_eq!((too_new.reason, too_new.name.as_deref()), (Incompatibility::TooNew, Some("X")));
+        assert_eq!(too_new.supported, "0.9 - 1.0");
+        let too_old = negotiate(&mut json!({"id": "x", "apiVersion": "0.5"})).unwrap_err();
+        assert_eq!(too_old.reason, Incompatibility::TooOld);
+        let missing = negotiate(&mut json!({"id": "x"})).unwrap_err();
+        assert_eq!(missing.reason, Incompatibility::Invalid);
+    }
+}
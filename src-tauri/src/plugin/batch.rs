@@ -0,0 +1,87 @@
+// Phase 7.3.12: 快照一致的批量查询
+// 仪表盘依次调用 plugin_list / get_all_data / get_all_health 时，两次调用之间可能发生刷新，
+// 看到插件列表与数据/健康状态不一致的中间状态。batch_query 在同一个插件表读锁下执行所有
+// 只读查询，保证结果来自同一个快照。
+//
+// 只支持只读命令；每批最多 MAX_BATCH_QUERIES 条。
+
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::summary::DataSummary;
+use crate::plugin::types::{PluginData, PluginHealth, PluginInfo};
+
+/// 单批最大查询数
+pub const MAX_BATCH_QUERIES: usize = 32;
+
+/// 批量查询中的单条请求（command 与对应的独立 IPC 命令同名）
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum BatchQuery {
+    PluginList,
+    GetAllData,
+    GetAllHealth,
+    GetSummary,
+    #[serde(rename_all = "camelCase")]
+    GetPluginData { id: String },
+    #[serde(rename_all = "camelCase")]
+    GetPluginHealth { id: String },
+}
+
+/// 单条请求的结果（与请求按顺序一一对应）
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "command", content = "result", rename_all = "snake_case")]
+pub enum BatchResult {
+    PluginList(Vec<PluginInfo>),
+    GetAllData(Vec<PluginData>),
+    GetAllHealth(Vec<PluginHealth>),
+    GetSummary(DataSummary),
+    GetPluginData(Option<PluginData>),
+    GetPluginHealth(Option<PluginHealth>),
+}
+
+impl BatchResult {
+    /// 结果中包含的健康状态（供 IPC 层补充告警静默信息）
+    pub fn health_mut(&mut self) -> impl Iterator<Item = &mut PluginHealth> {
+        let items: &mut [PluginHealth] = match self {
+            BatchResult::GetAllHealth(health) => health,
+            BatchResult::GetPluginHealth(Some(health)) => std::slice::from_mut(health),
+            _ => &mut [],
+        };
+        items.iter_mut()
+    }
+}
+
+/// 批量查询响应
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResponse {
+    /// 各请求的结果（与请求顺序一致）
+    pub results: Vec<BatchResult>,
+    /// 快照时间 (ISO 8601)
+    pub snapshot_at: String,
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_query_serde() {
+        let queries: Vec<BatchQuery> = serde_json::from_str(
+            r#"[{"command": "plugin_list"}, {"command": "get_plugin_health", "id": "demo"}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            queries,
+            vec![BatchQuery::PluginList, BatchQuery::GetPluginHealth { id: "demo".to_string() }]
+        );
+        assert!(serde_json::from_str::<BatchQuery>(r#"{"command": "plugin_uninstall"}"#).is_err());
+
+        let result = serde_json::to_value(BatchResult::GetPluginData(None)).unwrap();
+        assert_eq!(result, serde_json::json!({"command": "get_plugin_data", "result": null}));
+    }
+}
@@ -0,0 +1,430 @@
+// Phase 6.8: 月度预算
+// 用户为单个插件或全局设置月度花费上限，根据余额/用量变化推算本月花费，
+// 预计超出预算时提醒
+//
+// 设计要点:
+// 1. 花费来自 BalanceData 余额下降和货币单位 UsageData 的 used 增长（充值/重置不计入）
+// 2. 按自然月（UTC）统计，进入新月份时清零；账本持久化，重启不丢失本月花费
+// 3. 预测按本月首次观测以来的平均速率线性外推到月底，观测不足 1 小时不预测
+// 4. 预算按货币记账，不同货币的花费不计入该预算；每个预算每月只提醒一次
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, RwLock};
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::types::PluginData;
+
+/// 开始预测所需的最短观测时长（秒）
+const MIN_PROJECTION_SECS: i64 = 3600;
+
+/// 全局预算在账本中的提醒键
+const GLOBAL_KEY: &str = "*";
+
+// ============================================================================
+// 预算设置
+// ============================================================================
+
+/// 单个预算
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Budget {
+    /// 月度上限
+    pub limit: f64,
+    /// 货币（ISO 4217，如 USD）
+    pub currency: String,
+}
+
+/// 预算设置（budgets.json）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BudgetSettings {
+    /// 全局预算（所有插件同币种花费之和）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global: Option<Budget>,
+    /// 按插件设置的预算
+    pub plugins: BTreeMap<String, Budget>,
+}
+
+// ============================================================================
+// 花费账本
+// ============================================================================
+
+/// 单个插件本月的花费记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PluginSpend {
+    currency: String,
+    /// 本月已花费
+    spent: f64,
+    /// 本月首次观测时间
+    first_observed: DateTime<Utc>,
+    /// 上次观测到的余额或已用量
+    last_value: f64,
+}
+
+/// 花费账本（budget_ledger.json）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct SpendLedger {
+    /// 账本月份 (YYYY-MM)
+    month: String,
+    plugins: BTreeMap<String, PluginSpend>,
+    /// 本月已提醒的预算（插件 ID 或 "*"）
+    warned: BTreeSet<String>,
+}
+
+/// 花费观测值
+enum SpendSample {
+    /// 余额（下降计为花费）
+    Balance(f64),
+    /// 已用金额（增长计为花费）
+    Used(f64),
+}
+
+/// 规范化货币代码，非货币单位返回 None
+fn normalize_currency(unit: &str) -> Option<String> {
+    let unit = unit.trim();
+    let code = match unit {
+        "$" | "US$" => "USD",
+        "¥" | "￥" | "元" => "CNY",
+        "€" => "EUR",
+        "£" => "GBP",
+        _ => {
+            let upper = unit.to_ascii_uppercase();
+            return match upper.as_str() {
+                "RMB" => Some("CNY".to_string()),
+                "USD" | "CNY" | "EUR" | "GBP" | "JPY" | "HKD" => Some(upper),
+                _ => None,
+            };
+        }
+    };
+    Some(code.to_string())
+}
+
+/// 提取插件数据中的花费观测值，返回 (插件 ID, 货币, 观测值)
+fn spend_sample(data: &PluginData) -> Option<(&str, String, SpendSample)> {
+    match data {
+        PluginData::Balance(balance) if balance.balance.is_finite() => {
+            let currency = normalize_currency(&balance.currency)
+                .unwrap_or_else(|| balance.currency.trim().to_ascii_uppercase());
+            Some((&balance.base.plugin_id, currency, SpendSample::Balance(balance.balance)))
+        }
+        PluginData::Usage(usage) if usage.used.is_finite() => {
+            let currency = normalize_currency(&usage.unit)?;
+            Some((&usage.base.plugin_id, currency, SpendSample::Used(usage.used)))
+        }
+        _ => None,
+    }
+}
+
+/// 月份标识 (YYYY-MM)
+fn month_key(now: DateTime<Utc>) -> String {
+    now.format("%Y-%m").to_string()
+}
+
+/// 下个月的开始时间
+fn next_month_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    let (year, month) = if now.month() == 12 {
+        (now.year() + 1, 1)
+    } else {
+        (now.year(), now.month() + 1)
+    };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+impl PluginSpend {
+    /// 按本月平均速率推算月底花费
+    fn projected(&self, now: DateTime<Utc>) -> Option<f64> {
+        let elapsed = (now - self.first_observed).num_seconds();
+        if elapsed < MIN_PROJECTION_SECS {
+            return None;
+        }
+        let remaining = (next_month_start(now) - now).num_seconds().max(0);
+        Some(self.spent + self.spent / elapsed as f64 * remaining as f64)
+    }
+}
+
+// ============================================================================
+// 预算状态
+// ============================================================================
+
+/// 单条预算状态（插件或全局）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetLine {
+    /// 插件 ID（全局预算为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_id: Option<String>,
+    pub currency: String,
+    /// 本月已花费
+    pub spent: f64,
+    /// 预计月底花费（观测不足时为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected: Option<f64>,
+    /// 月度上限（未设置预算时为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<f64>,
+    /// 预计超出预算
+    pub over_budget: bool,
+}
+
+impl BudgetLine {
+    fn new(plugin_id: Option<String>, currency: String, spent: f64, projected: Option<f64>, limit: Option<f64>) -> Self {
+        let over_budget = match limit {
+            Some(limit) => projected.unwrap_or(spent) > limit,
+            None => false,
+        };
+        Self { plugin_id, currency, spent, projected, limit, over_budget }
+    }
+}
+
+/// 预算状态
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    /// 统计月份 (YYYY-MM, UTC)
+    pub month: String,
+    /// 全局预算
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global: Option<BudgetLine>,
+    /// 有花费或设置了预算的插件（按插件 ID 排序）
+    pub plugins: Vec<BudgetLine>,
+}
+
+// ============================================================================
+// 预算跟踪器
+// ============================================================================
+
+/// 预算跟踪器
+pub struct BudgetTracker {
+    settings: RwLock<BudgetSettings>,
+    settings_path: PathBuf,
+    ledger: Mutex<SpendLedger>,
+    ledger_path: PathBuf,
+}
+
+impl BudgetTracker {
+    /// 加载预算设置和账本（文件与插件目录同级）
+    pub fn load(plugins_dir: &Path) -> Self {
+        let base = plugins_dir.parent().unwrap_or(plugins_dir);
+        let settings_path = base.join("budgets.json");
+        let ledger_path = base.join("budget_ledger.json");
+        Self {
+            settings: RwLock::new(read_json(&settings_path)),
+            settings_path,
+            ledger: Mutex::new(read_json(&ledger_path)),
+            ledger_path,
+        }
+    }
+
+    fn lock_ledger(&self) -> MutexGuard<'_, SpendLedger> {
+        self.ledger.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 当前预算设置
+    pub fn settings(&self) -> BudgetSettings {
+        self.settings.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 设置或清除预算（`plugin_id` 为 None 时为全局预算）
+    pub fn set_budget(&self, plugin_id: Option<&str>, budget: Option<Budget>) -> std::io::Result<()> {
+        let mut settings = self.settings.write().unwrap_or_else(|e| e.into_inner());
+        let mut next = settings.clone();
+        match (plugin_id, budget) {
+            (None, budget) => next.global = budget,
+            (Some(id), Some(budget)) => {
+                next.plugins.insert(id.to_string(), budget);
+            }
+            (Some(id), None) => {
+                next.plugins.remove(id);
+            }
+        }
+        write_json(&self.settings_path, &next)?;
+        *settings = next;
+        drop(settings);
+
+        // 预算变化后允许重新提醒
+        let mut ledger = self.lock_ledger();
+        ledger.warned.remove(plugin_id.unwrap_or(GLOBAL_KEY));
+        if let Err(e) = write_json(&self.ledger_path, &*ledger) {
+            log::warn!("保存预算账本失败: {}", e);
+        }
+        Ok(())
+    }
+
+    /// 记录插件数据中的花费，返回本次新触发的预算提醒
+    pub fn record(&self, data: &PluginData, now: DateTime<Utc>) -> Vec<BudgetLine> {
+        let Some((plugin_id, currency, sample)) = spend_sample(data) else {
+            return Vec::new();
+        };
+
+        let mut ledger = self.lock_ledger();
+        let month = month_key(now);
+        if ledger.month != month {
+            *ledger = SpendLedger { month, ..Default::default() };
+        }
+
+        let value = match sample {
+            SpendSample::Balance(v) | SpendSample::Used(v) => v,
+        };
+        match ledger.plugins.get_mut(plugin_id) {
+            Some(spend) if spend.currency == currency => {
+                let delta = match sample {
+                    // 余额上升视为充值
+                    SpendSample::Balance(v) => (spend.last_value - v).max(0.0),
+                    // 已用量回落视为重置，回落后的用量全部计入
+                    SpendSample::Used(v) if v >= spend.last_value => v - spend.last_value,
+                    SpendSample::Used(v) => v,
+                };
+                spend.spent += delta;
+                spend.last_value = value;
+            }
+            _ => {
+                ledger.plugins.insert(
+                    plugin_id.to_string(),
+                    PluginSpend { currency, spent: 0.0, first_observed: now, last_value: value },
+                );
+            }
+        }
+
+        let status = Self::compute_status(&self.settings(), &ledger, now);
+        let warnings: Vec<BudgetLine> = status
+            .global
+            .into_iter()
+            .chain(status.plugins)
+            .filter(|line| line.over_budget)
+            .filter(|line| ledger.warned.insert(line.plugin_id.clone().unwrap_or_else(|| GLOBAL_KEY.to_string())))
+            .collect();
+
+        if let Err(e) = write_json(&self.ledger_path, &*ledger) {
+            log::warn!("保存预算账本失败: {}", e);
+        }
+        warnings
+    }
+
+    /// 当前预算状态
+    pub fn status(&self, now: DateTime<Utc>) -> BudgetStatus {
+        let ledger = self.lock_ledger();
+        Self::compute_status(&self.settings(), &ledger, now)
+    }
+
+    fn compute_status(settings: &BudgetSettings, ledger: &SpendLedger, now: DateTime<Utc>) -> BudgetStatus {
+        let month = month_key(now);
+        // 账本属于之前的月份时，本月尚无花费
+        let empty = BTreeMap::new();
+        let spends = if ledger.month == month { &ledger.plugins } else { &empty };
+
+        let ids: BTreeSet<&String> = spends.keys().chain(settings.plugins.keys()).collect();
+        let plugins = ids
+            .into_iter()
+            .filter_map(|id| {
+                let budget = settings.plugins.get(id);
+                match (spends.get(id), budget) {
+                    (Some(spend), budget) => Some(BudgetLine::new(
+                        Some(id.clone()),
+                        spend.currency.clone(),
+                        spend.spent,
+                        spend.projected(now),
+                        budget.filter(|b| b.currency == spend.currency).map(|b| b.limit),
+                    )),
+                    (None, Some(budget)) => Some(BudgetLine::new(
+                        Some(id.clone()),
+                        budget.currency.clone(),
+                        0.0,
+                        None,
+                        Some(budget.limit),
+                    )),
+                    (None, None) => None,
+                }
+            })
+            .collect();
+
+        let global = settings.global.as_ref().map(|budget| {
+            let matching: Vec<&PluginSpend> =
+                spends.values().filter(|s| s.currency == budget.currency).collect();
+            let spent = matching.iter().map(|s| s.spent).sum();
+            let projected = matching
+                .iter()
+                .any(|s| s.projected(now).is_some())
+                .then(|| matching.iter().map(|s| s.projected(now).unwrap_or(s.spent)).sum());
+            BudgetLine::new(None, budget.currency.clone(), spent, projected, Some(budget.limit))
+        });
+
+        BudgetStatus { month, global, plugins }
+    }
+}
+
+/// 读取 JSON 文件（不存在或无法解析时使用默认值）
+fn read_json<T: Default + for<'de> Deserialize<'de>>(path: &Path) -> T {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("解析 {:?} 失败，使用默认值: {}", path, e);
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+/// 写入 JSON 文件（临时文件 + rename）
+fn write_json<T: Serialize>(path: &Path, value: &T) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    std::fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn balance(balance: f64) -> PluginData {
+        serde_json::from_value(json!({
+            "dataType": "balance", "pluginId": "deepseek", "lastUpdated": "2025-01-01T00:00:00Z",
+            "balance": balance, "currency": "¥"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_budget_projection_and_warning() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let tracker = BudgetTracker::load(&plugins_dir);
+        let budget = |limit| Some(Budget { limit, currency: "CNY".to_string() });
+        tracker.set_budget(Some("deepseek"), budget(100.0)).unwrap();
+        tracker.set_budget(None, budget(1000.0)).unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        assert!(tracker.record(&balance(500.0), start).is_empty());
+        // 充值不计入花费
+        assert!(tracker.record(&balance(600.0), start + chrono::Duration::hours(1)).is_empty());
+
+        // 两天花费 10 元，按此速率月底约 155 元，超出插件预算但未超出全局预算
+        let warnings = tracker.record(&balance(590.0), start + chrono::Duration::days(2));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].plugin_id.as_deref(), Some("deepseek"));
+        assert!((warnings[0].projected.unwrap() - 155.0).abs() < 0.01);
+        // 同月只提醒一次
+        assert!(tracker.record(&balance(580.0), start + chrono::Duration::days(3)).is_empty());
+
+        // 重启后本月花费仍在
+        let reloaded = BudgetTracker::load(&plugins_dir);
+        let status = reloaded.status(start + chrono::Duration::days(3));
+        assert_eq!(status.month, "2025-01");
+        assert_eq!(status.plugins[0].spent, 20.0);
+        assert!(status.plugins[0].over_budget);
+        assert!(!status.global.unwrap().over_budget);
+
+        // 新月份清零
+        let status = reloaded.status(Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!((status.plugins[0].spent, status.plugins[0].limit), (0.0, Some(100.0)));
+    }
+}
@@ -0,0 +1,361 @@
+// Phase 7.13: 内置 Claude 使用量数据源
+// 两种鉴权方式（configSchema authMethod）:
+// - session: claude.ai 的 sessionKey Cookie，读取订阅的 5 小时 / 7 天用量窗口（百分比）
+// - apiKey: Anthropic Admin API Key（sk-ant-admin…），汇总本月 Messages API token 用量，
+//   配置 monthlyTokenLimit 时按限额计算百分比
+// 凭证为 secret 字段，保存在 Keychain
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde_json::{json, Value};
+
+use super::config_str;
+
+/// 插件 ID
+pub const PLUGIN_ID: &str = "builtin-claude";
+
+/// claude.ai API 地址
+const CLAUDE_AI_BASE: &str = "https://claude.ai/api";
+
+/// Admin API 地址
+const ADMIN_API_BASE: &str = "https://api.anthropic.com/v1/organizations";
+
+/// Admin API 版本头
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// 用量报告最多翻页数（按天分桶，一个月最多 31 个桶）
+const MAX_REPORT_PAGES: usize = 5;
+
+/// 订阅用量窗口（claude.ai usage 响应字段、维度 ID、显示标签）
+const SESSION_WINDOWS: [(&str, &str, &str); 3] = [
+    ("five_hour", "fiveHour", "5 小时"),
+    ("seven_day", "sevenDay", "7 天"),
+    ("seven_day_opus", "sevenDayOpus", "7 天 (Opus)"),
+];
+
+/// 内部插件 manifest
+pub(super) fn manifest() -> Value {
+    json!({
+        "id": PLUGIN_ID,
+        "name": "Claude 使用量",
+        "version": "0.0.0",
+        "apiVersion": "1.0",
+        "pluginType": "data",
+        "dataType": "usage",
+        "entry": "builtin",
+        "author": "AiBal",
+        "description": "内置数据源：通过 claude.ai 会话或 Admin API Key 获取 Claude 使用量，无需安装插件",
+        "refreshIntervalMs": 300000,
+        "configSchema": {
+            "authMethod": {
+                "type": "select",
+                "required": true,
+                "label": "鉴权方式",
+                "default": "session",
+                "options": [
+                    { "value": "session", "label": "claude.ai 会话 (订阅用量)" },
+                    { "value": "apiKey", "label": "Admin API Key (API 用量)" }
+                ]
+            },
+            "sessionKey": {
+                "type": "string",
+                "secret": true,
+                "label": "sessionKey",
+                "description": "claude.ai 登录后 Cookie 中的 sessionKey"
+            },
+            "organizationId": {
+                "type": "string",
+                "label": "组织 ID",
+                "description": "可选，未填写时使用会话的第一个组织"
+            },
+            "apiKey": {
+                "type": "string",
+                "secret": true,
+                "label": "Admin API Key",
+                "description": "在 Anthropic Console 创建的 Admin API Key (sk-ant-admin…)"
+            },
+            "monthlyTokenLimit": {
+                "type": "number",
+                "label": "每月 token 限额",
+                "description": "可选，用于计算 API 用量百分比",
+                "min": 0
+            }
+        }
+    })
+}
+
+/// 获取使用量（返回 fetchData 格式的 usage 数据）
+pub(super) async fn fetch(
+    client: &reqwest::Client,
+    config: &HashMap<String, Value>,
+) -> Result<Value, String> {
+    match config_str(config, "authMethod").unwrap_or("session") {
+        "session" => {
+            let session_key = config_str(config, "sessionKey")
+                .ok_or_else(|| "未配置 sessionKey 凭证".to_string())?;
+            fetch_session_usage(client, session_key, config_str(config, "organizationId")).await
+        }
+        "apiKey" => {
+            let api_key = config_str(config, "apiKey")
+                .ok_or_else(|| "未配置 Admin API Key 凭证".to_string())?;
+            let limit = config.get("monthlyTokenLimit").and_then(Value::as_f64);
+            fetch_api_usage(client, api_key, limit).await
+        }
+        other => Err(format!("不支持的鉴权方式: {}", other)),
+    }
+}
+
+// ============================================================================
+// claude.ai 会话
+// ============================================================================
+
+async fn fetch_session_usage(
+    client: &reqwest::Client,
+    session_key: &str,
+    organization_id: Option<&str>,
+) -> Result<Value, String> {
+    let cookie = format!("sessionKey={}", session_key);
+    let get = |url: String| {
+        let request = client
+            .get(url)
+            .header(reqwest::header::COOKIE, cookie.clone())
+            .header(reqwest::header::ACCEPT, "application/json");
+        async move { send_json(request).await }
+    };
+
+    let organization = match organization_id {
+        Some(id) => id.to_string(),
+        None => {
+            let organizations = get(format!("{}/organizations", CLAUDE_AI_BASE)).await?;
+            pick_organization(&organizations).ok_or_else(|| "会话没有可用的组织".to_string())?
+        }
+    };
+    let usage = get(format!("{}/organizations/{}/usage", CLAUDE_AI_BASE, organization)).await?;
+    session_usage(&usage)
+}
+
+/// 选择组织（优先支持聊天的组织）
+fn pick_organization(organizations: &Value) -> Option<String> {
+    let list = organizations.as_array()?;
+    let has_chat = |org: &&Value| {
+        org.get("capabilities")
+            .and_then(Value::as_array)
+            .is_some_and(|caps| caps.iter().any(|c| c.as_str() == Some("chat")))
+    };
+    list.iter()
+        .find(has_chat)
+        .or_else(|| list.first())
+        .and_then(|org| org.get("uuid"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// 将 claude.ai usage 响应转换为 usage 数据（主百分比取各窗口最高值）
+fn session_usage(usage: &Value) -> Result<Value, String> {
+    let dimensions: Vec<Value> = SESSION_WINDOWS
+        .iter()
+        .filter_map(|(field, id, label)| {
+            let window = usage.get(*field)?;
+            let utilization = window.get("utilization")?.as_f64()?;
+            Some(json!({
+                "id": id,
+                "label": label,
+                "percentage": utilization,
+                "used": utilization,
+                "limit": 100,
+                "resetTime": window.get("resets_at").and_then(Value::as_str),
+            }))
+        })
+        .collect();
+
+    let top = dimensions
+        .iter()
+        .max_by(|a, b| a["percentage"].as_f64().partial_cmp(&b["percentage"].as_f64()).unwrap_or(std::cmp::Ordering::Equal))
+        .ok_or_else(|| "usage 响应中没有用量窗口".to_string())?;
+
+    Ok(json!({
+        "dataType": "usage",
+        "percentage": top["percentage"],
+        "used": top["used"],
+        "limit": 100,
+        "unit": "%",
+        "resetTime": top["resetTime"],
+        "resetLabel": top["label"],
+        "dimensions": dimensions,
+    }))
+}
+
+// ============================================================================
+// Admin API
+// ============================================================================
+
+async fn fetch_api_usage(
+    client: &reqwest::Client,
+    api_key: &str,
+    monthly_limit: Option<f64>,
+) -> Result<Value, String> {
+    let now = Utc::now();
+    let month_start = month_start(now);
+    let mut pages = Vec::new();
+    let mut next_page: Option<String> = None;
+
+    for _ in 0..MAX_REPORT_PAGES {
+        let mut query = vec![
+            ("starting_at", month_start.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+            ("bucket_width", "1d".to_string()),
+            ("limit", "31".to_string()),
+        ];
+        if let Some(page) = next_page.take() {
+            query.push(("page", page));
+        }
+        let request = client
+            .get(format!("{}/usage_report/messages", ADMIN_API_BASE))
+            .query(&query)
+            .header("x-api-key", api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION);
+        let page = send_json(request).await?;
+
+        next_page = page
+            .get("has_more")
+            .and_then(Value::as_bool)
+            .filter(|more| *more)
+            .and_then(|_| page.get("next_page"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        pages.push(page);
+        if next_page.is_none() {
+            break;
+        }
+    }
+
+    Ok(api_usage(&pages, monthly_limit, next_month(month_start)))
+}
+
+/// 汇总用量报告各分桶的 token 数
+fn api_usage(pages: &[Value], monthly_limit: Option<f64>, reset: DateTime<Utc>) -> Value {
+    let (mut input, mut output, mut cache_read) = (0.0, 0.0, 0.0);
+    let results = pages
+        .iter()
+        .filter_map(|page| page.get("data").and_then(Value::as_array))
+        .flatten()
+        .filter_map(|bucket| bucket.get("results").and_then(Value::as_array))
+        .flatten();
+    for result in results {
+        let tokens = |pointer: &str| result.pointer(pointer).and_then(Value::as_f64).unwrap_or(0.0);
+        input += tokens("/uncached_input_tokens")
+            + tokens("/cache_creation/ephemeral_1h_input_tokens")
+            + tokens("/cache_creation/ephemeral_5m_input_tokens");
+        output += tokens("/output_tokens");
+        cache_read += tokens("/cache_read_input_tokens");
+    }
+
+    let limit = monthly_limit.filter(|limit| *limit > 0.0).unwrap_or(0.0);
+    let percentage = |used: f64| if limit > 0.0 { used / limit * 100.0 } else { 0.0 };
+    let total = input + output + cache_read;
+    let reset_time = reset.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let dimension = |id: &str, label: &str, used: f64| {
+        json!({
+            "id": id,
+            "label": label,
+            "percentage": percentage(used),
+            "used": used,
+            "limit": limit,
+            "resetTime": reset_time,
+        })
+    };
+
+    json!({
+        "dataType": "usage",
+        "percentage": percentage(total),
+        "used": total,
+        "limit": limit,
+        "unit": "tokens",
+        "resetTime": reset_time,
+        "resetLabel": "本月",
+        "dimensions": [
+            dimension("input", "输入", input),
+            dimension("output", "输出", output),
+            dimension("cacheRead", "缓存读取", cache_read),
+        ],
+    })
+}
+
+/// 当月第一天 00:00 (UTC)
+fn month_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// 下月第一天 00:00 (UTC)
+fn next_month(start: DateTime<Utc>) -> DateTime<Utc> {
+    let (year, month) = if start.month() == 12 {
+        (start.year() + 1, 1)
+    } else {
+        (start.year(), start.month() + 1)
+    };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single().unwrap_or(start)
+}
+
+/// 发送请求并解析 JSON（非 2xx 返回 HTTP 状态，供错误分类）
+async fn send_json(request: reqwest::RequestBuilder) -> Result<Value, String> {
+    let response = request.send().await.map_err(|e| format!("network error: {}", e))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status.as_u16()));
+    }
+    response.json().await.map_err(|e| format!("响应解析失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_usage_picks_highest_window() {
+        let usage = json!({
+            "five_hour": { "utilization": 42.0, "resets_at": "2026-10-18T05:00:00Z" },
+            "seven_day": { "utilization": 71.0, "resets_at": "2026-10-22T00:00:00Z" },
+            "seven_day_opus": null
+        });
+        let data = session_usage(&usage).unwrap();
+        assert_eq!(data["percentage"], 71.0);
+        assert_eq!(data["resetLabel"], "7 天");
+        assert_eq!(data["dimensions"].as_array().unwrap().len(), 2);
+        assert!(session_usage(&json!({})).is_err());
+
+        let orgs = json!([
+            { "uuid": "api-org", "capabilities": ["api"] },
+            { "uuid": "chat-org", "capabilities": ["chat", "claude_pro"] }
+        ]);
+        assert_eq!(pick_organization(&orgs).as_deref(), Some("chat-org"));
+    }
+
+    #[test]
+    fn test_api_usage_sums_buckets() {
+        let page = json!({
+            "data": [
+                { "results": [{
+                    "uncached_input_tokens": 1000,
+                    "cache_creation": { "ephemeral_5m_input_tokens": 500 },
+                    "cache_read_input_tokens": 2000,
+                    "output_tokens": 1500
+                }] },
+                { "results": [{ "uncached_input_tokens": 1000, "output_tokens": 4000 }] }
+            ],
+            "has_more": false
+        });
+        let start = month_start(Utc.with_ymd_and_hms(2026, 12, 15, 8, 0, 0).unwrap());
+        let reset = next_month(start);
+        assert_eq!(reset, Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap());
+
+        let data = api_usage(&[page.clone()], Some(20000.0), reset);
+        assert_eq!(data["used"], 10000.0);
+        assert_eq!(data["percentage"], 50.0);
+        assert_eq!(data["dimensions"][0]["used"], 2500.0);
+
+        // 未配置限额时不计算百分比
+        assert_eq!(api_usage(&[page], None, reset)["percentage"], 0.0);
+    }
+}
@@ -0,0 +1,367 @@
+// Phase 7.15: Claude Code 本地用量
+// 读取 Claude Code 写在本机的会话日志（与 ccusage 相同的数据来源），完全离线统计 token 用量:
+// - 日志目录: 配置的 claudeDir > 环境变量 CLAUDE_CONFIG_DIR（逗号分隔）> ~/.config/claude、~/.claude，
+//   其下 projects/<项目>/<会话>.jsonl，每行一条消息记录
+// - 只统计带 message.usage 的 assistant 记录，按 message.id + requestId 去重（会话恢复时会重复写入）
+// - 统计周期为本地时间的今天或本月，按模型拆分为维度
+// - 配置 tokenLimit 时按限额计算百分比，否则主百分比为 0，维度百分比为该模型占总用量的比例
+// - 维度附带 token 明细（输入/输出/缓存），供费用估算按模型价格计算（Phase 6.14）
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use serde_json::{json, Value};
+
+use super::config_str;
+use crate::plugin::types::TokenBreakdown;
+
+/// 插件 ID
+pub const PLUGIN_ID: &str = "builtin-local-usage";
+
+/// 日志子目录
+const PROJECTS_DIR: &str = "projects";
+
+/// 内部插件 manifest
+pub(super) fn manifest() -> Value {
+    json!({
+        "id": PLUGIN_ID,
+        "name": "Claude Code 本地用量",
+        "version": "0.0.0",
+        "apiVersion": "1.0",
+        "pluginType": "data",
+        "dataType": "usage",
+        "entry": "builtin",
+        "author": "AiBal",
+        "description": "内置数据源：读取本机 Claude Code 会话日志统计 token 用量，无需网络和凭证",
+        "refreshIntervalMs": 60000,
+        "configSchema": {
+            "period": {
+                "type": "select",
+                "label": "统计周期",
+                "default": "month",
+                "options": [
+                    { "value": "day", "label": "今天" },
+                    { "value": "month", "label": "本月" }
+                ]
+            },
+            "tokenLimit": {
+                "type": "number",
+                "label": "token 限额",
+                "description": "可选，用于计算统计周期内的用量百分比",
+                "min": 0
+            },
+            "claudeDir": {
+                "type": "string",
+                "label": "Claude 配置目录",
+                "description": "可选，默认依次查找 CLAUDE_CONFIG_DIR、~/.config/claude、~/.claude"
+            }
+        }
+    })
+}
+
+/// 统计周期
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Period {
+    Day,
+    Month,
+}
+
+impl Period {
+    fn from_config(config: &HashMap<String, Value>) -> Result<Self, String> {
+        match config_str(config, "period").unwrap_or("month") {
+            "day" => Ok(Period::Day),
+            "month" => Ok(Period::Month),
+            other => Err(format!("不支持的统计周期: {}", other)),
+        }
+    }
+
+    /// 周期起止（本地时间 00:00）
+    fn bounds(self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            Period::Day => (today, today + Duration::days(1)),
+            Period::Month => {
+                let start = today.with_day(1).unwrap_or(today);
+                let next = if start.month() == 12 {
+                    NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+                } else {
+                    NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+                };
+                (start, next.unwrap_or(start))
+            }
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Period::Day => "今天",
+            Period::Month => "本月",
+        }
+    }
+}
+
+/// 周期内的用量汇总
+#[derive(Debug, Default)]
+struct LocalUsage {
+    /// 模型 -> 用量
+    models: BTreeMap<String, TokenBreakdown>,
+    /// 已统计的消息（去重键）
+    seen: HashSet<String>,
+    /// 涉及的会话数
+    sessions: HashSet<String>,
+}
+
+impl LocalUsage {
+    /// 统计一行日志（不在周期内、没有用量或重复的记录忽略）
+    fn record(&mut self, line: &str, since: DateTime<Utc>) {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+        let Some(usage) = entry.pointer("/message/usage") else {
+            return;
+        };
+        let in_period = entry
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .is_some_and(|t| t >= since);
+        if !in_period {
+            return;
+        }
+
+        let tokens = |key: &str| usage.get(key).and_then(Value::as_u64).unwrap_or(0);
+        let counts = TokenBreakdown {
+            input: tokens("input_tokens"),
+            output: tokens("output_tokens"),
+            cache_creation: tokens("cache_creation_input_tokens"),
+            cache_read: tokens("cache_read_input_tokens"),
+        };
+        if counts.total() == 0 {
+            return;
+        }
+
+        let message_id = entry.pointer("/message/id").and_then(Value::as_str);
+        let request_id = entry.get("requestId").and_then(Value::as_str);
+        if let (Some(message_id), Some(request_id)) = (message_id, request_id) {
+            if !self.seen.insert(format!("{}:{}", message_id, request_id)) {
+                return;
+            }
+        }
+
+        if let Some(session) = entry.get("sessionId").and_then(Value::as_str) {
+            self.sessions.insert(session.to_string());
+        }
+        let model = entry
+            .pointer("/message/model")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        self.models.entry(model.to_string()).or_default().add(&counts);
+    }
+
+    fn total(&self) -> TokenBreakdown {
+        let mut total = TokenBreakdown::default();
+        for counts in self.models.values() {
+            total.add(counts);
+        }
+        total
+    }
+}
+
+/// 获取本地用量（返回 fetchData 格式的 usage 数据）
+pub(super) async fn fetch(config: &HashMap<String, Value>) -> Result<Value, String> {
+    let period = Period::from_config(config)?;
+    let limit = config.get("tokenLimit").and_then(Value::as_f64);
+    let roots = log_roots(config_str(config, "claudeDir"));
+    if roots.is_empty() {
+        return Err("未找到 Claude Code 日志目录（projects）".to_string());
+    }
+
+    let (start, end) = period.bounds(Local::now().date_naive());
+    let since = local_midnight(start);
+    let reset = local_midnight(end);
+    let usage = tokio::task::spawn_blocking(move || scan(&roots, since))
+        .await
+        .map_err(|e| format!("读取本地日志失败: {}", e))?;
+    Ok(usage_value(&usage, limit, period, reset))
+}
+
+/// 本地日期 00:00 对应的 UTC 时间
+fn local_midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|t| t.and_local_timezone(Local).earliest())
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now)
+}
+
+/// 存在的日志根目录（…/projects）
+fn log_roots(configured: Option<&str>) -> Vec<PathBuf> {
+    let candidates: Vec<PathBuf> = match configured {
+        Some(dir) => vec![PathBuf::from(dir)],
+        None => match std::env::var("CLAUDE_CONFIG_DIR") {
+            Ok(dirs) if !dirs.trim().is_empty() => dirs
+                .split(',')
+                .map(str::trim)
+                .filter(|d| !d.is_empty())
+                .map(PathBuf::from)
+                .collect(),
+            _ => dirs::home_dir()
+                .map(|home| vec![home.join(".config").join("claude"), home.join(".claude")])
+                .unwrap_or_default(),
+        },
+    };
+    candidates
+        .into_iter()
+        .map(|dir| dir.join(PROJECTS_DIR))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+/// 扫描日志目录下的所有 jsonl 文件（修改时间早于周期开始的文件跳过）
+fn scan(roots: &[PathBuf], since: DateTime<Utc>) -> LocalUsage {
+    let since_time = SystemTime::from(since);
+    let mut usage = LocalUsage::default();
+    for root in roots {
+        for file in jsonl_files(root) {
+            let modified = std::fs::metadata(&file).and_then(|m| m.modified());
+            if modified.is_ok_and(|t| t < since_time) {
+                continue;
+            }
+            let Ok(handle) = std::fs::File::open(&file) else {
+                continue;
+            };
+            for line in BufReader::new(handle).lines().map_while(Result::ok) {
+                usage.record(&line, since);
+            }
+        }
+    }
+    usage
+}
+
+/// 项目目录（一层）下的 jsonl 文件
+fn jsonl_files(root: &Path) -> Vec<PathBuf> {
+    let Ok(projects) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    projects
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect()
+}
+
+/// 模型显示名（去掉 claude- 前缀和日期后缀）
+fn model_label(model: &str) -> String {
+    let name = model.strip_prefix("claude-").unwrap_or(model);
+    match name.rsplit_once('-') {
+        Some((base, date)) if date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()) => base.to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// 转换为 usage 数据
+fn usage_value(usage: &LocalUsage, limit: Option<f64>, period: Period, reset: DateTime<Utc>) -> Value {
+    let limit = limit.filter(|limit| *limit > 0.0).unwrap_or(0.0);
+    let total = usage.total().total() as f64;
+    let reset_time = reset.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let share = |used: f64| {
+        if limit > 0.0 {
+            used / limit * 100.0
+        } else if total > 0.0 {
+            used / total * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    let dimensions: Vec<Value> = usage
+        .models
+        .iter()
+        .map(|(model, counts)| {
+            let used = counts.total() as f64;
+            json!({
+                "id": model,
+                "label": model_label(model),
+                "percentage": share(used),
+                "used": used,
+                "limit": if limit > 0.0 { limit } else { total },
+                "resetTime": reset_time,
+                "tokens": counts,
+            })
+        })
+        .collect();
+
+    json!({
+        "dataType": "usage",
+        "percentage": if limit > 0.0 { total / limit * 100.0 } else { 0.0 },
+        "used": total,
+        "limit": limit,
+        "unit": "tokens",
+        "resetTime": reset_time,
+        "resetLabel": format!("{} · {} 个会话", period.label(), usage.sessions.len()),
+        "dimensions": dimensions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_usage_dedup_and_per_model_dimensions() {
+        let since = DateTime::parse_from_rfc3339("2025-06-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let line = |time: &str, id: &str, model: &str, input: u64, output: u64| {
+            json!({
+                "type": "assistant",
+                "timestamp": time,
+                "sessionId": "s1",
+                "requestId": format!("req-{}", id),
+                "message": {
+                    "id": id,
+                    "model": model,
+                    "usage": { "input_tokens": input, "output_tokens": output, "cache_read_input_tokens": 10 }
+                }
+            })
+            .to_string()
+        };
+
+        let mut usage = LocalUsage::default();
+        usage.record(&line("2025-06-02T10:00:00Z", "a", "claude-sonnet-4-20250514", 100, 50), since);
+        // 会话恢复重复写入的消息不重复计数
+        usage.record(&line("2025-06-02T10:00:00Z", "a", "claude-sonnet-4-20250514", 100, 50), since);
+        usage.record(&line("2025-06-03T10:00:00Z", "b", "claude-opus-4-20250514", 200, 30), since);
+        // 周期外、无用量和无法解析的行忽略
+        usage.record(&line("2025-05-31T23:59:59Z", "c", "claude-opus-4-20250514", 999, 1), since);
+        usage.record(r#"{"type": "user", "timestamp": "2025-06-02T10:00:00Z"}"#, since);
+        usage.record("not json", since);
+
+        let reset = DateTime::parse_from_rfc3339("2025-07-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let value = usage_value(&usage, Some(1000.0), Period::Month, reset);
+        assert_eq!(value["used"], 400.0);
+        assert_eq!(value["percentage"], 40.0);
+        assert_eq!(value["resetLabel"], "本月 · 1 个会话");
+        let dimensions = value["dimensions"].as_array().unwrap();
+        let labels: Vec<_> = dimensions.iter().map(|d| (d["label"].as_str().unwrap(), d["used"].as_f64().unwrap())).collect();
+        assert_eq!(labels, [("opus-4", 240.0), ("sonnet-4", 160.0)]);
+        assert_eq!(dimensions[1]["tokens"]["cacheRead"], 10);
+
+        // 未配置限额时维度百分比为模型占比
+        let value = usage_value(&usage, None, Period::Month, reset);
+        assert_eq!(value["percentage"], 0.0);
+        assert_eq!(value["dimensions"][0]["percentage"], 60.0);
+    }
+
+    #[test]
+    fn test_period_bounds() {
+        let today = NaiveDate::from_ymd_opt(2025, 12, 15).unwrap();
+        assert_eq!(
+            Period::Month.bounds(today),
+            (NaiveDate::from_ymd_opt(2025, 12, 1).unwrap(), NaiveDate::from_ymd_opt(2026, 1, 1).unwrap())
+        );
+        assert_eq!(Period::Day.bounds(today), (today, NaiveDate::from_ymd_opt(2025, 12, 16).unwrap()));
+    }
+}
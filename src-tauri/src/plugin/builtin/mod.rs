@@ -0,0 +1,86 @@
+// Phase 7.13: 内置数据源
+// 不需要编写插件即可使用的第一方数据源，以内部插件的形式注册到 PluginManager:
+// - 每个数据源提供 manifest（id、名称、configSchema 等）和原生 fetch 实现，
+//   fetch 返回与插件 fetchData 相同格式的 JSON，经相同的解析、缓存、重试和健康统计流程
+// - 启动时随插件发现一并注册，trustLevel 为 builtin，默认禁用，用户填写凭证（如需要）后启用
+// - secret 配置字段与普通插件一样存放在 Keychain
+// - 没有 JS 入口：生命周期钩子、validateConfig 和测试运行不适用，配置只按 configSchema 校验
+// - 不能卸载或重载
+
+pub mod claude;
+pub mod local_usage;
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::plugin::lifecycle::{LifecycleError, PluginManifest};
+
+/// 内置数据源请求超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 内置数据源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinProvider {
+    /// Claude 使用量（claude.ai 会话或 Admin API Key）
+    Claude,
+    /// Claude Code 本地会话日志用量（离线）
+    LocalUsage,
+}
+
+impl BuiltinProvider {
+    /// 所有内置数据源
+    pub const ALL: [BuiltinProvider; 2] = [BuiltinProvider::Claude, BuiltinProvider::LocalUsage];
+
+    /// 插件 ID
+    pub fn id(self) -> &'static str {
+        match self {
+            BuiltinProvider::Claude => claude::PLUGIN_ID,
+            BuiltinProvider::LocalUsage => local_usage::PLUGIN_ID,
+        }
+    }
+
+    /// 内部插件 manifest（版本跟随应用）
+    pub fn manifest(self) -> PluginManifest {
+        let raw = match self {
+            BuiltinProvider::Claude => claude::manifest(),
+            BuiltinProvider::LocalUsage => local_usage::manifest(),
+        };
+        let mut manifest: PluginManifest =
+            serde_json::from_value(raw).expect("内置数据源 manifest 格式错误");
+        manifest.version = env!("CARGO_PKG_VERSION").to_string();
+        manifest
+    }
+
+    /// 获取数据（返回值格式与 fetchData 相同）
+    pub async fn fetch(self, config: &HashMap<String, Value>) -> Result<Value, LifecycleError> {
+        let result = match self {
+            BuiltinProvider::Claude => claude::fetch(http_client(), config).await,
+            BuiltinProvider::LocalUsage => local_usage::fetch(config).await,
+        };
+        result.map_err(LifecycleError::PluginLoad)
+    }
+}
+
+/// 内置数据源共用的 HTTP 客户端
+fn http_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("AiBal/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// 读取字符串配置（空白视为未配置）
+fn config_str<'a>(config: &'a HashMap<String, Value>, key: &str) -> Option<&'a str> {
+    config
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
@@ -0,0 +1,128 @@
+// Phase 7.3.15: 批量插件操作
+// 仪表盘多选插件后启用/禁用/刷新/卸载，一次 IPC 调用完成，避免 N 次往返:
+// - 按 ID 顺序逐个执行，单个插件失败不影响其余插件，结果按 ID 逐条报告
+// - 完成后发送一个汇总事件（ipc:plugin_bulk_completed），不再逐个发送卸载/错误事件
+// - 重复的 ID 只执行一次；每批最多 MAX_BULK_PLUGINS 个
+
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::types::AppError;
+
+/// 单批最多操作的插件数
+pub const MAX_BULK_PLUGINS: usize = 64;
+
+/// 批量操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkOp {
+    Enable,
+    Disable,
+    Refresh,
+    Uninstall,
+}
+
+/// 单个插件的执行结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemResult {
+    /// 插件实例 ID
+    pub id: String,
+    pub success: bool,
+    /// 离线时刷新已排队，恢复连接后执行（仅 refresh）
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub queued: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<AppError>,
+}
+
+/// 批量操作结果（同时作为汇总事件的 payload）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkResult {
+    pub op: BulkOp,
+    /// 各插件的结果（与去重后的 ID 顺序一致）
+    pub results: Vec<BulkItemResult>,
+    /// 成功数（含已排队）
+    pub succeeded: usize,
+    /// 失败数
+    pub failed: usize,
+}
+
+impl BulkResult {
+    pub fn new(op: BulkOp) -> Self {
+        Self {
+            op,
+            results: Vec::new(),
+            succeeded: 0,
+            failed: 0,
+        }
+    }
+
+    /// 记录成功
+    pub fn ok(&mut self, id: &str) {
+        self.push(id, false, None);
+    }
+
+    /// 记录已排队（离线刷新）
+    pub fn queued(&mut self, id: &str) {
+        self.push(id, true, None);
+    }
+
+    /// 记录失败
+    pub fn fail(&mut self, id: &str, error: AppError) {
+        self.push(id, false, Some(error));
+    }
+
+    fn push(&mut self, id: &str, queued: bool, error: Option<AppError>) {
+        if error.is_some() {
+            self.failed += 1;
+        } else {
+            self.succeeded += 1;
+        }
+        self.results.push(BulkItemResult {
+            id: id.to_string(),
+            success: error.is_none(),
+            queued,
+            error,
+        });
+    }
+}
+
+/// 去除重复和空白 ID，保留首次出现的顺序
+pub fn dedup_ids(ids: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    ids.into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty() && seen.insert(id.clone()))
+        .collect()
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_result_counts_and_serde() {
+        assert_eq!(serde_json::from_str::<BulkOp>(r#""uninstall""#).unwrap(), BulkOp::Uninstall);
+        assert_eq!(
+            dedup_ids(vec!["a".into(), "b".into(), " a ".into(), "".into()]),
+            ["a", "b"]
+        );
+
+        let mut result = BulkResult::new(BulkOp::Refresh);
+        result.ok("a");
+        result.queued("b");
+        result.fail("c", AppError::new("PLUGIN_NOT_FOUND", "插件不存在: c"));
+        assert_eq!((result.succeeded, result.failed), (2, 1));
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["op"], "refresh");
+        assert_eq!(json["results"][0], serde_json::json!({"id": "a", "success": true}));
+        assert_eq!(json["results"][1]["queued"], true);
+        assert_eq!(json["results"][2]["error"]["code"], "PLUGIN_NOT_FOUND");
+    }
+}
@@ -6,14 +6,18 @@
 // - 4.2.2 实现配置验证 (validateConfig) - 验证失败有错误信息
 // - 4.2.3 实现配置 UI 自动生成 - 根据 schema 渲染表单 (前端)
 // - 4.2.4 实现配置变更通知 - 配置更新后通知插件
+// - 6.5 用量提醒阈值 - 按插件配置，持久化到 usage_alerts.json
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
+use super::env_import::SECRET_REF_FIELD;
 use super::event_bus::EventBus;
+use super::monitoring::UsageAlertSettings;
 
 // ============================================================================
 // 配置 Schema 类型定义
@@ -88,6 +92,7 @@ pub type ConfigSchema = HashMap<String, ConfigField>;
 
 /// 字段验证错误
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FieldValidationError {
     /// 字段名
     pub field: String,
@@ -173,6 +178,10 @@ pub struct ConfigManager {
     schemas: RwLock<HashMap<String, ConfigSchema>>,
     /// 事件总线引用 (用于发送变更通知)
     event_bus: Option<Arc<EventBus>>,
+    /// 用量提醒设置
+    usage_alerts: RwLock<UsageAlertSettings>,
+    /// 用量提醒设置文件路径（为 None 时仅保存在内存）
+    usage_alerts_path: Option<PathBuf>,
 }
 
 impl ConfigManager {
@@ -181,17 +190,26 @@ impl ConfigManager {
         Self {
             schemas: RwLock::new(HashMap::new()),
             event_bus: None,
+            usage_alerts: RwLock::new(UsageAlertSettings::default()),
+            usage_alerts_path: None,
         }
     }
 
     /// 创建带事件总线的配置管理器
     pub fn with_event_bus(event_bus: Arc<EventBus>) -> Self {
         Self {
-            schemas: RwLock::new(HashMap::new()),
             event_bus: Some(event_bus),
+            ..Self::new()
         }
     }
 
+    /// 从文件加载用量提醒设置，保存时写回该文件
+    pub fn with_usage_alerts_file(mut self, path: PathBuf) -> Self {
+        self.usage_alerts = RwLock::new(UsageAlertSettings::load(&path));
+        self.usage_alerts_path = Some(path);
+        self
+    }
+
     // ========================================================================
     // Schema 管理 (4.2.1)
     // ========================================================================
@@ -229,12 +247,33 @@ impl ConfigManager {
         self.schemas.read().await.get(plugin_id).cloned()
     }
 
+    /// 标记为 secret 的字段名（值存放在 Keychain）
+    pub async fn secret_fields(&self, plugin_id: &str) -> Vec<String> {
+        self.schemas
+            .read()
+            .await
+            .get(plugin_id)
+            .map(|schema| {
+                schema
+                    .iter()
+                    .filter(|(_, field)| field.secret)
+                    .map(|(name, _)| name.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// 移除插件配置 Schema
     pub async fn unregister_schema(&self, plugin_id: &str) {
         self.schemas.write().await.remove(plugin_id);
         log::debug!("已移除插件 {} 的配置 Schema", plugin_id);
     }
 
+    /// 注册了配置 Schema 的插件 ID
+    pub async fn registered_plugin_ids(&self) -> Vec<String> {
+        self.schemas.read().await.keys().cloned().collect()
+    }
+
     // ========================================================================
     // 配置验证 (4.2.2)
     // ========================================================================
@@ -281,6 +320,11 @@ impl ConfigManager {
                 _ => continue,
             };
 
+            // 敏感字段可存放 Keychain 引用（.env 导入），不按字段类型检查
+            if field_def.secret && value.get(SECRET_REF_FIELD).is_some_and(|v| v.is_string()) {
+                continue;
+            }
+
             // 验证类型
             match field_def.field_type {
                 ConfigFieldType::String => {
@@ -414,6 +458,29 @@ impl ConfigManager {
         }
     }
 
+    // ========================================================================
+    // 用量提醒阈值 (6.5)
+    // ========================================================================
+
+    /// 当前用量提醒设置
+    pub async fn usage_alert_settings(&self) -> UsageAlertSettings {
+        self.usage_alerts.read().await.clone()
+    }
+
+    /// 保存用量提醒设置
+    pub async fn set_usage_alert_settings(&self, settings: UsageAlertSettings) -> std::io::Result<()> {
+        if let Some(path) = &self.usage_alerts_path {
+            settings.save(path)?;
+        }
+        *self.usage_alerts.write().await = settings;
+        Ok(())
+    }
+
+    /// 插件生效的用量提醒阈值（升序）
+    pub async fn usage_thresholds(&self, plugin_id: &str) -> Vec<f64> {
+        self.usage_alerts.read().await.thresholds_for(plugin_id)
+    }
+
     // ========================================================================
     // 工具方法
     // ========================================================================
@@ -0,0 +1,212 @@
+// Phase 4.5: 自定义数据 Schema 校验
+// 插件在 manifest 中通过 `payloadSchema` 声明 custom 数据的结构，宿主据此校验 fetchData 结果
+//
+// 支持 JSON Schema 的常用子集:
+// - type（string / number / integer / boolean / object / array / null，或其数组）
+// - enum / const
+// - object: properties / required / additionalProperties（布尔或 schema）
+// - array: items / minItems / maxItems
+// - number: minimum / maximum
+// - string: minLength / maxLength
+// 未知关键字忽略。
+
+use serde_json::Value;
+
+/// 单次校验最多报告的错误数
+const MAX_ERRORS: usize = 20;
+
+/// 按 schema 校验数据，返回错误列表（路径使用 JSON Pointer，根为 `/`）
+pub fn validate_payload(schema: &Value, value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(schema, value, "", &mut errors);
+    errors.truncate(MAX_ERRORS);
+    errors
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    if errors.len() >= MAX_ERRORS {
+        return;
+    }
+    let Some(schema) = schema.as_object() else {
+        // `true` / 空 schema 接受任意值，`false` 拒绝
+        if schema == &Value::Bool(false) {
+            errors.push(format!("{}: 不允许出现", display_path(path)));
+        }
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(types) => types.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| matches_type(t, value)) {
+            errors.push(format!(
+                "{}: 应为 {}，实际为 {}",
+                display_path(path),
+                allowed.join(" | "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            errors.push(format!("{}: 不在允许的取值范围内", display_path(path)));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!("{}: 应为 {}", display_path(path), expected));
+        }
+    }
+
+    match value {
+        Value::Object(obj) => {
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for field in required.iter().filter_map(|f| f.as_str()) {
+                    if !obj.contains_key(field) {
+                        errors.push(format!("{}: 缺少必需字段 {}", display_path(path), field));
+                    }
+                }
+            }
+            for (key, child) in obj {
+                let child_path = format!("{}/{}", path, key);
+                match properties.and_then(|p| p.get(key)) {
+                    Some(child_schema) => validate_at(child_schema, child, &child_path, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{}: 不允许的字段", display_path(&child_path)))
+                        }
+                        Some(extra) => validate_at(extra, child, &child_path, errors),
+                        None => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            check_bound(schema, "minItems", items.len() as f64, path, "元素数", errors);
+            check_bound(schema, "maxItems", items.len() as f64, path, "元素数", errors);
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_at(item_schema, item, &format!("{}/{}", path, i), errors);
+                }
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            check_bound(schema, "minimum", n, path, "值", errors);
+            check_bound(schema, "maximum", n, path, "值", errors);
+        }
+        Value::String(s) => {
+            let len = s.chars().count() as f64;
+            check_bound(schema, "minLength", len, path, "长度", errors);
+            check_bound(schema, "maxLength", len, path, "长度", errors);
+        }
+        _ => {}
+    }
+}
+
+/// 检查 minimum/maximum 类关键字
+fn check_bound(
+    schema: &serde_json::Map<String, Value>,
+    keyword: &str,
+    actual: f64,
+    path: &str,
+    what: &str,
+    errors: &mut Vec<String>,
+) {
+    let Some(limit) = schema.get(keyword).and_then(|v| v.as_f64()) else {
+        return;
+    };
+    let violated = if keyword.starts_with("min") {
+        actual < limit
+    } else {
+        actual > limit
+    };
+    if violated {
+        let relation = if keyword.starts_with("min") { "不能小于" } else { "不能大于" };
+        errors.push(format!("{}: {} {} {}（实际 {}）", display_path(path), what, relation, limit, actual));
+    }
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        other => type_name(value) == other,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "/"
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_payload() {
+        let schema = json!({
+            "type": "object",
+            "required": ["items"],
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "maxItems": 2,
+                    "items": {
+                        "type": "object",
+                        "required": ["label", "value"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "label": { "type": "string", "minLength": 1 },
+                            "value": { "type": "integer", "minimum": 0 },
+                            "level": { "enum": ["ok", "warn"] }
+                        }
+                    }
+                }
+            }
+        });
+
+        let valid = json!({ "items": [{ "label": "a", "value": 3, "level": "ok" }], "title": "x" });
+        assert!(validate_payload(&schema, &valid).is_empty());
+
+        let invalid = json!({
+            "items": [
+                { "label": "", "value": 1.5 },
+                { "value": -1, "level": "bad", "extra": true },
+                { "label": "c", "value": 1 }
+            ]
+        });
+        let errors = validate_payload(&schema, &invalid);
+        assert_eq!(errors.len(), 7, "{:?}", errors);
+        assert!(errors.iter().any(|e| e.starts_with("/items: 元素数")));
+        assert!(errors.iter().any(|e| e.starts_with("/items/0/value: 应为 integer")));
+        assert!(errors.iter().any(|e| e == "/items/1: 缺少必需字段 label"));
+        assert!(errors.iter().any(|e| e == "/items/1/extra: 不允许的字段"));
+
+        assert_eq!(
+            validate_payload(&schema, &json!([])),
+            vec!["/: 应为 object，实际为 array".to_string()]
+        );
+    }
+}
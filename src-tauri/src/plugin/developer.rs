@@ -0,0 +1,101 @@
+// Phase 5A.8: 开发者模式
+// 允许从独立的 dev-plugins 目录加载未签名插件，便于本地迭代
+//
+// 设计要点:
+// 1. 开发者插件不经过安装流程（无签名/完整性校验），仅在开发者模式开启时加载
+// 2. 开发者插件在 PluginInfo 中标记为 trustLevel = "developer"
+// 3. 默认只授予低风险权限（timer/storage/cache），网络、密钥、用户输入和跨插件调用
+//    需用户对该插件显式信任后才授予
+// 4. 设置持久化到 developer.json（与插件目录同级）
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::permission::Permission;
+
+/// 开发者插件目录名（与插件目录同级）
+pub const DEV_PLUGINS_DIR_NAME: &str = "dev-plugins";
+
+/// 开发者模式设置（developer.json）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DeveloperSettings {
+    /// 是否启用开发者模式
+    pub enabled: bool,
+    /// 用户显式信任的开发者插件 ID（授予全部声明权限）
+    pub trusted: BTreeSet<String>,
+}
+
+impl DeveloperSettings {
+    /// 默认配置文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("developer.json")
+    }
+
+    /// 开发者插件目录（~/.config/cuk/dev-plugins）
+    pub fn dev_plugins_dir(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join(DEV_PLUGINS_DIR_NAME)
+    }
+
+    /// 从文件加载（文件不存在或无法解析时使用默认设置）
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析开发者模式设置失败，使用默认设置: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// 开发者插件是否已被用户信任
+    pub fn is_trusted(&self, id: &str) -> bool {
+        self.trusted.contains(id)
+    }
+}
+
+/// 未受信任的开发者插件默认可获得的权限
+pub fn is_dev_default_permission(permission: &str) -> bool {
+    matches!(
+        Permission::parse(permission),
+        Some(Permission::Timer | Permission::Storage | Permission::Cache)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dev_default_permissions() {
+        assert!(is_dev_default_permission("storage"));
+        assert!(is_dev_default_permission("setTimeout"));
+        assert!(!is_dev_default_permission("network"));
+        assert!(!is_dev_default_permission("secrets:api_key"));
+        assert!(!is_dev_default_permission("call:other:getData"));
+
+        let path = Path::new("/tmp/cuk/plugins");
+        assert_eq!(
+            DeveloperSettings::dev_plugins_dir(path),
+            PathBuf::from("/tmp/cuk/dev-plugins")
+        );
+    }
+}
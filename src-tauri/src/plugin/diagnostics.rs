@@ -0,0 +1,229 @@
+// Phase 6.13: 插件诊断包
+// 用户反馈插件故障时导出一个 zip 附在问题报告中，包含:
+// - report.json: 应用/运行时版本、插件信息、健康快照、最后错误、资源占用
+// - manifest.json: 插件清单
+// - config.json: 脱敏后的插件配置
+// - logs.txt: 最近的插件 console 日志
+//
+// 脱敏规则: configSchema 中标记为 secret 的字段、Keychain 引用，以及字段名看起来像凭据
+// （包含 token/key/secret/password/cookie/auth 等）的字段，值一律替换为 [REDACTED]。
+// 诊断包写入与插件目录同级的 diagnostics/ 目录，每个插件只保留最近 MAX_BUNDLES_PER_PLUGIN 个。
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::plugin::plugin_logs::PluginLogEntry;
+use crate::plugin::types::{PluginHealth, PluginInfo, PluginResourceStats};
+
+/// 每个插件保留的诊断包数量
+const MAX_BUNDLES_PER_PLUGIN: usize = 5;
+
+/// 脱敏后的占位值
+pub const REDACTED: &str = "[REDACTED]";
+
+/// 字段名包含这些片段时视为凭据（不区分大小写）
+const SENSITIVE_KEY_PARTS: [&str; 8] = [
+    "token", "key", "secret", "password", "passwd", "cookie", "auth", "credential",
+];
+
+/// 诊断包错误
+#[derive(Debug, thiserror::Error)]
+pub enum DiagnosticsError {
+    #[error("IO 错误: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("写入 zip 失败: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    #[error("序列化失败: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// 应用与运行时版本
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeInfo {
+    /// 应用版本
+    pub app_version: String,
+    /// 插件 JS 引擎
+    pub js_engine: String,
+    /// 操作系统
+    pub os: String,
+    /// CPU 架构
+    pub arch: String,
+}
+
+impl RuntimeInfo {
+    /// 当前进程的版本信息
+    pub fn current() -> Self {
+        Self {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            js_engine: "QuickJS (rquickjs 0.6)".to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+        }
+    }
+}
+
+/// 诊断报告（report.json）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    /// 生成时间
+    pub generated_at: DateTime<Utc>,
+    /// 应用与运行时版本
+    pub runtime: RuntimeInfo,
+    /// 插件信息
+    pub plugin: PluginInfo,
+    /// 健康快照
+    pub health: Option<PluginHealth>,
+    /// 最后错误
+    pub last_error: Option<String>,
+    /// 沙盒资源占用
+    pub resources: Option<PluginResourceStats>,
+}
+
+/// 诊断包内容
+pub struct DiagnosticsBundle {
+    /// 诊断报告
+    pub report: DiagnosticsReport,
+    /// 插件清单
+    pub manifest: serde_json::Value,
+    /// 脱敏后的配置
+    pub config: serde_json::Map<String, serde_json::Value>,
+    /// 最近的插件日志
+    pub logs: Vec<PluginLogEntry>,
+}
+
+/// 已生成的诊断包
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsFile {
+    /// zip 文件路径
+    pub path: String,
+    /// 文件大小 (字节)
+    pub size_bytes: u64,
+    /// 生成时间 (ISO 8601)
+    pub generated_at: String,
+}
+
+/// 脱敏插件配置
+///
+/// `secret_fields` 为 configSchema 中标记为 secret 的字段
+pub fn redact_config(
+    config: &HashMap<String, serde_json::Value>,
+    secret_fields: &[String],
+) -> serde_json::Map<String, serde_json::Value> {
+    config
+        .iter()
+        .map(|(key, value)| {
+            let sensitive = secret_fields.contains(key) || is_sensitive_key(key) || value.get("secretRef").is_some();
+            let value = if sensitive {
+                serde_json::Value::String(REDACTED.to_string())
+            } else {
+                value.clone()
+            };
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+/// 字段名是否像凭据
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SENSITIVE_KEY_PARTS.iter().any(|part| key.contains(part))
+}
+
+/// 诊断包目录（与插件目录同级）
+pub fn diagnostics_dir(plugins_dir: &Path) -> PathBuf {
+    plugins_dir.parent().unwrap_or(plugins_dir).join("diagnostics")
+}
+
+impl DiagnosticsBundle {
+    /// 写入 `dir/{pluginId}-{时间}.zip`，并清理该插件较旧的诊断包
+    pub fn write_to(&self, dir: &Path) -> Result<DiagnosticsFile, DiagnosticsError> {
+        std::fs::create_dir_all(dir)?;
+        let plugin_id = &self.report.plugin.id;
+        let generated_at = self.report.generated_at;
+        let path = dir.join(format!("{}-{}.zip", plugin_id, generated_at.format("%Y%m%d-%H%M%S%.3f")));
+
+        let mut zip = ZipWriter::new(std::fs::File::create(&path)?);
+        let options = SimpleFileOptions::default();
+        zip.start_file("report.json", options)?;
+        zip.write_all(&serde_json::to_vec_pretty(&self.report)?)?;
+        zip.start_file("manifest.json", options)?;
+        zip.write_all(&serde_json::to_vec_pretty(&self.manifest)?)?;
+        zip.start_file("config.json", options)?;
+        zip.write_all(&serde_json::to_vec_pretty(&self.config)?)?;
+        zip.start_file("logs.txt", options)?;
+        for entry in &self.logs {
+            writeln!(
+                zip,
+                "{} [{:?}] {}",
+                entry.timestamp.to_rfc3339(),
+                entry.level,
+                entry.message
+            )?;
+        }
+        zip.finish()?;
+
+        prune_bundles(dir, plugin_id);
+        log::info!("[{}] 已生成诊断包: {:?}", plugin_id, path);
+        Ok(DiagnosticsFile {
+            size_bytes: std::fs::metadata(&path)?.len(),
+            path: path.display().to_string(),
+            generated_at: generated_at.to_rfc3339(),
+        })
+    }
+}
+
+/// 只保留插件最近的诊断包（文件名含时间，按名称排序即按时间排序）
+fn prune_bundles(dir: &Path, plugin_id: &str) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let prefix = format!("{}-", plugin_id);
+    let mut bundles: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix(&prefix))
+                .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()) && rest.ends_with(".zip"))
+        })
+        .collect();
+    bundles.sort();
+    let excess = bundles.len().saturating_sub(MAX_BUNDLES_PER_PLUGIN);
+    for old in bundles.drain(..excess) {
+        if let Err(e) = std::fs::remove_file(&old) {
+            log::warn!("删除旧诊断包失败: {:?}, {}", old, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_config() {
+        let config = HashMap::from([
+            ("apiKey".to_string(), serde_json::json!("sk-live-123")),
+            ("org".to_string(), serde_json::json!({ "secretRef": "org" })),
+            ("region".to_string(), serde_json::json!("us")),
+            ("sessionCookie".to_string(), serde_json::json!("abc")),
+            ("plan".to_string(), serde_json::json!("pro")),
+        ]);
+        let redacted = redact_config(&config, &["plan".to_string()]);
+        assert_eq!(redacted["region"], "us");
+        for field in ["apiKey", "org", "sessionCookie", "plan"] {
+            assert_eq!(redacted[field], REDACTED, "{}", field);
+        }
+    }
+}
@@ -0,0 +1,424 @@
+// Phase 5A.9: 从 .env 批量导入 API Key（开发者模式）
+// 开发者通常把各服务商的 Key 放在 .env 文件中，导入后写入 Keychain 并在插件配置中
+// 以 secretRef 引用，避免手动逐个粘贴
+//
+// 安全设计：
+// 1. 仅在开发者模式开启时可用，且只读取用户显式选择的普通文件（拒绝符号链接、超大文件）
+// 2. 只识别已知变量名，按 `secrets:<name>` 权限映射到声明了该密钥的插件
+// 3. 值只写入 Keychain（服务名 cuk.plugin.{pluginId}），插件配置中仅保存引用
+// 4. 日志与导入报告只包含变量名/插件 ID，永不包含值
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::commands::error::codes;
+use crate::plugin::lifecycle::PluginManager;
+use crate::plugin::sandbox::secrets::{secrets_service, SecretStore};
+
+/// .env 文件大小上限
+const MAX_ENV_FILE_SIZE: u64 = 64 * 1024;
+
+/// 已知变量名 → 插件密钥名称（`secrets:<name>`）
+pub const KNOWN_ENV_KEYS: &[(&str, &str)] = &[
+    ("ANTHROPIC_API_KEY", "anthropic_api_key"),
+    ("OPENAI_API_KEY", "openai_api_key"),
+];
+
+/// 插件配置中引用 Keychain 密钥的字段名
+pub const SECRET_REF_FIELD: &str = "secretRef";
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum EnvImportError {
+    #[error("仅在开发者模式下可导入 .env")]
+    DeveloperModeRequired,
+
+    #[error("无效的 .env 文件: {0}")]
+    InvalidFile(String),
+
+    #[error("IO 错误: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl EnvImportError {
+    /// IPC 错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            EnvImportError::DeveloperModeRequired => codes::DEVELOPER_MODE_REQUIRED,
+            EnvImportError::InvalidFile(_) => codes::INVALID_ENV_FILE,
+            EnvImportError::Io(_) => codes::ENV_IMPORT_FAILED,
+        }
+    }
+}
+
+// ============================================================================
+// 导入报告
+// ============================================================================
+
+/// 已导入的密钥（不含值）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedKey {
+    /// 环境变量名
+    pub variable: String,
+    /// 目标插件 ID
+    pub plugin_id: String,
+    /// 插件密钥名称
+    pub secret_name: String,
+}
+
+/// 未导入的已知变量
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedKey {
+    /// 环境变量名
+    pub variable: String,
+    /// 目标插件 ID（写入失败时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_id: Option<String>,
+    /// 原因
+    pub reason: String,
+}
+
+/// .env 导入报告
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvImportReport {
+    /// 已导入
+    pub imported: Vec<ImportedKey>,
+    /// 已识别但未导入
+    pub skipped: Vec<SkippedKey>,
+    /// 未识别的变量数量
+    pub ignored: usize,
+}
+
+// ============================================================================
+// .env 解析
+// ============================================================================
+
+/// 解析 dotenv 内容（后出现的同名变量覆盖先出现的）
+///
+/// 支持 `KEY=VALUE`、`export KEY=VALUE`、单/双引号值与行尾注释，不支持多行值。
+pub fn parse_dotenv(content: &str) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").map(str::trim_start).unwrap_or(line);
+        let Some((key, raw)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        if !is_valid_env_name(key) {
+            continue;
+        }
+        if let Some(value) = parse_value(raw.trim()) {
+            vars.insert(key.to_string(), value);
+        }
+    }
+    vars
+}
+
+/// 变量名校验：`[A-Za-z_][A-Za-z0-9_]*`
+fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// 解析变量值（引号未闭合时返回 None）
+fn parse_value(raw: &str) -> Option<String> {
+    if let Some(rest) = raw.strip_prefix('\'') {
+        return rest.find('\'').map(|end| rest[..end].to_string());
+    }
+    if let Some(rest) = raw.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Some(value),
+                '\\' => match chars.next()? {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    other => value.push(other),
+                },
+                _ => value.push(c),
+            }
+        }
+        return None;
+    }
+    // 无引号：` #` 之后为注释
+    let value = match raw.find(" #") {
+        Some(pos) => &raw[..pos],
+        None => raw,
+    };
+    Some(value.trim_end().to_string())
+}
+
+// ============================================================================
+// 导入
+// ============================================================================
+
+/// 规划导入目标：已知变量 × 授予了对应密钥的插件
+///
+/// 返回 (待写入目标, 跳过的变量, 未识别变量数)
+pub fn plan_import(
+    vars: &BTreeMap<String, String>,
+    targets: &[(String, HashSet<String>)],
+) -> (Vec<ImportedKey>, Vec<SkippedKey>, usize) {
+    let mut planned = Vec::new();
+    let mut skipped = Vec::new();
+    let mut ignored = 0;
+
+    for (variable, value) in vars {
+        let Some((_, secret_name)) = KNOWN_ENV_KEYS.iter().find(|(name, _)| name == variable) else {
+            ignored += 1;
+            continue;
+        };
+        if value.is_empty() {
+            skipped.push(SkippedKey {
+                variable: variable.clone(),
+                plugin_id: None,
+                reason: "值为空".to_string(),
+            });
+            continue;
+        }
+
+        let mut plugin_ids: Vec<&String> = targets
+            .iter()
+            .filter(|(_, names)| names.contains(*secret_name))
+            .map(|(id, _)| id)
+            .collect();
+        if plugin_ids.is_empty() {
+            skipped.push(SkippedKey {
+                variable: variable.clone(),
+                plugin_id: None,
+                reason: format!("没有插件声明 secrets:{} 权限", secret_name),
+            });
+            continue;
+        }
+        plugin_ids.sort();
+        planned.extend(plugin_ids.into_iter().map(|id| ImportedKey {
+            variable: variable.clone(),
+            plugin_id: id.clone(),
+            secret_name: secret_name.to_string(),
+        }));
+    }
+
+    (planned, skipped, ignored)
+}
+
+/// 读取用户选择的 .env 文件
+fn read_env_file(path: &Path) -> Result<String, EnvImportError> {
+    if !path.is_absolute() {
+        return Err(EnvImportError::InvalidFile("路径必须为绝对路径".to_string()));
+    }
+    let metadata = std::fs::symlink_metadata(path)?;
+    if !metadata.file_type().is_file() {
+        return Err(EnvImportError::InvalidFile("不是普通文件".to_string()));
+    }
+    if metadata.len() > MAX_ENV_FILE_SIZE {
+        return Err(EnvImportError::InvalidFile(format!(
+            "文件过大（上限 {} KB）",
+            MAX_ENV_FILE_SIZE / 1024
+        )));
+    }
+    let bytes = std::fs::read(path)?;
+    String::from_utf8(bytes).map_err(|_| EnvImportError::InvalidFile("文件不是 UTF-8 编码".to_string()))
+}
+
+/// 从 .env 文件导入已知 API Key 到 Keychain，并在插件配置中写入 secretRef
+///
+/// 写入 secretRef 失败时恢复 Keychain 中原有的值（原来没有时删除），该变量记为跳过
+pub async fn import_keys_from_env(
+    manager: &PluginManager,
+    path: &Path,
+    store: &dyn SecretStore,
+) -> Result<EnvImportReport, EnvImportError> {
+    if !manager.developer_settings().await.enabled {
+        return Err(EnvImportError::DeveloperModeRequired);
+    }
+
+    let vars = parse_dotenv(&read_env_file(path)?);
+    let targets = manager.granted_secret_names().await;
+    let (planned, skipped, ignored) = plan_import(&vars, &targets);
+    let mut report = EnvImportReport {
+        imported: Vec::new(),
+        skipped,
+        ignored,
+    };
+
+    for key in planned {
+        let value = &vars[&key.variable];
+        let service = secrets_service(&key.plugin_id);
+        let previous = store.get(&service, &key.secret_name).ok().flatten();
+        if let Err(e) = store.set(&service, &key.secret_name, value) {
+            log::warn!("[{}] 写入密钥 {} 失败: {}", key.plugin_id, key.secret_name, e);
+            report.skipped.push(SkippedKey {
+                variable: key.variable,
+                plugin_id: Some(key.plugin_id),
+                reason: format!("写入 Keychain 失败: {}", e),
+            });
+            continue;
+        }
+
+        let mut config: HashMap<String, serde_json::Value> =
+            manager.get_plugin_config(&key.plugin_id).await.unwrap_or_default();
+        config.insert(
+            key.secret_name.clone(),
+            serde_json::json!({ SECRET_REF_FIELD: key.secret_name }),
+        );
+        if let Err(e) = manager.set_plugin_config(&key.plugin_id, config).await {
+            log::warn!("[{}] 更新密钥引用失败: {}", key.plugin_id, e);
+            let restored = match &previous {
+                Some(previous) => store.set(&service, &key.secret_name, previous),
+                None => store.delete(&service, &key.secret_name),
+            };
+            if let Err(restore_error) = restored {
+                log::warn!("[{}] 恢复密钥 {} 失败: {}", key.plugin_id, key.secret_name, restore_error);
+            }
+            report.skipped.push(SkippedKey {
+                variable: key.variable,
+                plugin_id: Some(key.plugin_id),
+                reason: format!("更新插件配置失败: {}", e),
+            });
+            continue;
+        }
+
+        log::info!("[{}] 已从 .env 导入 {} → secrets:{}", key.plugin_id, key.variable, key.secret_name);
+        report.imported.push(key);
+    }
+
+    Ok(report)
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::developer::DeveloperSettings;
+    use crate::plugin::lifecycle::PluginDiscovery;
+    use std::sync::Mutex;
+
+    /// 内存密钥存储
+    #[derive(Default)]
+    struct MemoryStore(Mutex<HashMap<(String, String), String>>);
+
+    impl SecretStore for MemoryStore {
+        fn get(&self, service: &str, account: &str) -> Result<Option<String>, String> {
+            Ok(self.0.lock().unwrap().get(&(service.to_string(), account.to_string())).cloned())
+        }
+
+        fn set(&self, service: &str, account: &str, value: &str) -> Result<(), String> {
+            self.0.lock().unwrap().insert((service.to_string(), account.to_string()), value.to_string());
+            Ok(())
+        }
+
+        fn delete(&self, service: &str, account: &str) -> Result<(), String> {
+            self.0.lock().unwrap().remove(&(service.to_string(), account.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let vars = parse_dotenv(
+            "# provider keys\n\
+             export ANTHROPIC_API_KEY=\"sk-ant-1\\\"x\"\n\
+             OPENAI_API_KEY='sk-#raw' \n\
+             PLAIN = value # comment\n\
+             BROKEN=\"unterminated\n\
+             1BAD=x\n\
+             no_equals\n\
+             PLAIN=override\n",
+        );
+        assert_eq!(vars.get("ANTHROPIC_API_KEY").unwrap(), "sk-ant-1\"x");
+        assert_eq!(vars.get("OPENAI_API_KEY").unwrap(), "sk-#raw");
+        assert_eq!(vars.get("PLAIN").unwrap(), "override");
+        assert!(!vars.contains_key("BROKEN"));
+        assert_eq!(vars.len(), 3);
+    }
+
+    #[test]
+    fn test_plan_import() {
+        let vars = parse_dotenv("ANTHROPIC_API_KEY=sk-ant\nOPENAI_API_KEY=\nDATABASE_URL=x\n");
+        let targets = vec![
+            ("claude-usage".to_string(), HashSet::from(["anthropic_api_key".to_string()])),
+            ("other".to_string(), HashSet::from(["token".to_string()])),
+        ];
+        let (planned, skipped, ignored) = plan_import(&vars, &targets);
+        assert_eq!(
+            planned,
+            vec![ImportedKey {
+                variable: "ANTHROPIC_API_KEY".to_string(),
+                plugin_id: "claude-usage".to_string(),
+                secret_name: "anthropic_api_key".to_string(),
+            }]
+        );
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].variable, "OPENAI_API_KEY");
+        assert_eq!(ignored, 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_skips_key_when_config_update_fails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        for (id, schema) in [
+            ("env-ok", ""),
+            // 缺少必填字段，写入 secretRef 时配置校验失败
+            ("env-invalid", r#", "configSchema": {"region": {"type": "string", "required": true}}"#),
+        ] {
+            let plugin_dir = plugins_dir.join(id);
+            std::fs::create_dir_all(&plugin_dir).unwrap();
+            std::fs::write(
+                plugin_dir.join("manifest.json"),
+                format!(
+                    r#"{{"id": "{}", "name": "Env", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data",
+                        "permissions": ["secrets:anthropic_api_key"]{}}}"#,
+                    id, schema
+                ),
+            )
+            .unwrap();
+            std::fs::write(plugin_dir.join("plugin.js"), "export async function fetchData() { return {}; }").unwrap();
+        }
+        let env_path = temp_dir.path().join(".env");
+        std::fs::write(&env_path, "ANTHROPIC_API_KEY=sk-new
+").unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir));
+        manager.discover_and_load().await.unwrap();
+        let developer = DeveloperSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        manager.set_developer_settings(developer).await.unwrap();
+
+        let store = MemoryStore::default();
+        store.set("cuk.plugin.env-invalid", "anthropic_api_key", "sk-old").unwrap();
+        let report = import_keys_from_env(&manager, &env_path, &store).await.unwrap();
+
+        assert_eq!(report.imported.len(), 1);
+        assert_eq!(report.imported[0].plugin_id, "env-ok");
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].plugin_id.as_deref(), Some("env-invalid"));
+        assert!(report.skipped[0].reason.starts_with("更新插件配置失败"));
+
+        // 失败插件的 Keychain 恢复原值，成功插件写入新值和引用
+        assert_eq!(store.get("cuk.plugin.env-invalid", "anthropic_api_key").unwrap().as_deref(), Some("sk-old"));
+        assert_eq!(store.get("cuk.plugin.env-ok", "anthropic_api_key").unwrap().as_deref(), Some("sk-new"));
+        let config = manager.get_plugin_config("env-ok").await.unwrap();
+        assert_eq!(config["anthropic_api_key"], serde_json::json!({ SECRET_REF_FIELD: "anthropic_api_key" }));
+    }
+}
@@ -6,8 +6,16 @@
 // - 4.1.2 实现 subscribedEvents 解析 - 声明式订阅生效
 // - 4.1.3 实现 onEvent 回调分发 - 事件路由正确
 // - 4.1.4 实现事件队列 - 异步处理不阻塞
+// - 4.1.5 实现优先级通道 - 系统事件优先于插件事件分发，插件通道有防饥饿保护
+// - 4.1.6 通配符订阅与负载过滤 - 如 plugin:*:data_updated，按事件数据字段过滤
+// - 4.1.7 死信队列与投递指标 - 投递失败的事件保留在有界死信缓冲区，按订阅者统计投递延迟
+//
+// 通配符: 段内 `*` 匹配任意字符（不跨越 `:`），如 plugin:*:data_updated、plugin:claude-*:*、system:*。
+// 过滤器: 订阅可附带 { "字段路径": 期望值 }，字段路径用 `.` 访问嵌套字段，
+// 全部字段相等时才投递；期望值为数组时匹配其中任一值。
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
@@ -89,6 +97,95 @@ impl QueuedEvent {
     }
 }
 
+/// 订阅的负载过滤器: 字段路径 (如 dataType、base.pluginId) -> 期望值
+pub type EventFilter = serde_json::Map<String, serde_json::Value>;
+
+/// 事件订阅（manifest `subscribedEvents` 的元素）
+///
+/// 可以是事件名称（支持通配符）或带负载过滤器的对象:
+/// `{ "event": "plugin:*:data_updated", "filter": { "dataType": "usage" } }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EventSubscription {
+    /// 事件名称或通配符模式
+    Name(String),
+    /// 带负载过滤器的订阅
+    Filtered {
+        /// 事件名称或通配符模式
+        event: String,
+        /// 负载过滤器
+        #[serde(default)]
+        filter: EventFilter,
+    },
+}
+
+impl EventSubscription {
+    /// 事件名称或通配符模式
+    pub fn event(&self) -> &str {
+        match self {
+            Self::Name(event) | Self::Filtered { event, .. } => event,
+        }
+    }
+
+    /// 负载过滤器（空过滤器视为无过滤）
+    pub fn filter(&self) -> Option<&EventFilter> {
+        match self {
+            Self::Filtered { filter, .. } if !filter.is_empty() => Some(filter),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for EventSubscription {
+    fn from(event: &str) -> Self {
+        Self::Name(event.to_string())
+    }
+}
+
+impl std::fmt::Display for EventSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.filter() {
+            Some(filter) => write!(f, "{} {}", self.event(), serde_json::Value::Object(filter.clone())),
+            None => f.write_str(self.event()),
+        }
+    }
+}
+
+/// 事件名称是否匹配订阅模式（段内 `*` 匹配任意字符）
+pub fn event_matches(pattern: &str, event_name: &str) -> bool {
+    if !pattern.contains('*') {
+        return pattern == event_name;
+    }
+    let pattern_parts: Vec<&str> = pattern.split(':').collect();
+    let name_parts: Vec<&str> = event_name.split(':').collect();
+    pattern_parts.len() == name_parts.len()
+        && pattern_parts
+            .iter()
+            .zip(&name_parts)
+            .all(|(p, n)| glob_segment(p.as_bytes(), n.as_bytes()))
+}
+
+/// 单段通配符匹配
+fn glob_segment(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|i| glob_segment(rest, &text[i..])),
+        Some((c, rest)) => text.first() == Some(c) && glob_segment(rest, &text[1..]),
+    }
+}
+
+/// 事件数据是否满足过滤器
+pub fn filter_matches(filter: &EventFilter, data: &serde_json::Value) -> bool {
+    filter.iter().all(|(path, expected)| {
+        let actual = path.split('.').try_fold(data, |value, key| value.get(key));
+        match (actual, expected) {
+            (None, _) => false,
+            (Some(actual), serde_json::Value::Array(options)) if !actual.is_array() => options.contains(actual),
+            (Some(actual), expected) => actual == expected,
+        }
+    })
+}
+
 /// 事件分发结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventDispatchResult {
@@ -107,6 +204,62 @@ pub struct EventDispatchFailure {
     pub error: String,
 }
 
+/// 死信：投递失败（处理器报错或超时）的事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetter {
+    /// 完整事件名称
+    pub event_name: String,
+    /// 事件数据
+    pub data: serde_json::Value,
+    /// 事件来源插件 ID (系统事件为 None)
+    pub source_plugin: Option<String>,
+    /// 投递失败的订阅者插件 ID
+    pub subscriber: String,
+    /// 失败原因
+    pub error: String,
+    /// 是否因超时失败
+    pub timed_out: bool,
+    /// 事件发布时间
+    pub published_at: chrono::DateTime<chrono::Utc>,
+    /// 投递失败时间
+    pub failed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 单个订阅者的投递指标
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriberMetrics {
+    /// 投递次数（含失败）
+    pub deliveries: u64,
+    /// 失败次数（含超时）
+    pub failures: u64,
+    /// 超时次数
+    pub timeouts: u64,
+    /// 最近一次投递延迟 (ms)
+    pub last_latency_ms: f64,
+    /// 平均投递延迟 (ms)
+    pub avg_latency_ms: f64,
+    /// 最大投递延迟 (ms)
+    pub max_latency_ms: f64,
+}
+
+impl SubscriberMetrics {
+    /// 记录一次投递
+    fn record(&mut self, latency_ms: f64, failed: bool, timed_out: bool) {
+        self.deliveries += 1;
+        if failed {
+            self.failures += 1;
+        }
+        if timed_out {
+            self.timeouts += 1;
+        }
+        self.last_latency_ms = latency_ms;
+        self.avg_latency_ms += (latency_ms - self.avg_latency_ms) / self.deliveries as f64;
+        self.max_latency_ms = self.max_latency_ms.max(latency_ms);
+    }
+}
+
 // ============================================================================
 // 事件总线错误
 // ============================================================================
@@ -156,6 +309,10 @@ pub struct EventBusConfig {
     pub handler_timeout_ms: u64,
     /// 最大并发处理数
     pub max_concurrent_handlers: usize,
+    /// 插件通道有积压时，连续分发系统事件的上限（防饥饿）
+    pub system_burst_limit: usize,
+    /// 死信缓冲区容量（满时丢弃最旧的死信）
+    pub dead_letter_capacity: usize,
 }
 
 impl Default for EventBusConfig {
@@ -164,30 +321,45 @@ impl Default for EventBusConfig {
             queue_size: 1000,
             handler_timeout_ms: 5000,
             max_concurrent_handlers: 10,
+            system_burst_limit: 16,
+            dead_letter_capacity: 100,
         }
     }
 }
 
+/// 优先级通道接收端（分发器持有）
+struct LaneReceivers {
+    system: mpsc::Receiver<QueuedEvent>,
+    plugin: mpsc::Receiver<QueuedEvent>,
+}
+
 /// 事件总线
 ///
 /// 实现插件间的发布/订阅通信机制
 pub struct EventBus {
-    /// 订阅映射: event_name -> Set<plugin_id>
-    subscriptions: RwLock<HashMap<String, HashSet<String>>>,
+    /// 订阅映射: 事件名称或通配符模式 -> (plugin_id -> 负载过滤器)
+    subscriptions: RwLock<HashMap<String, HashMap<String, Option<EventFilter>>>>,
     /// 事件处理器映射: plugin_id -> handler
     handlers: RwLock<HashMap<String, EventHandler>>,
-    /// 事件发送通道
-    event_tx: mpsc::Sender<QueuedEvent>,
+    /// 系统事件发送通道（高优先级）
+    system_tx: mpsc::Sender<QueuedEvent>,
+    /// 插件事件发送通道（低优先级）
+    plugin_tx: mpsc::Sender<QueuedEvent>,
     /// 事件接收通道 (后台任务持有)
-    event_rx: Arc<tokio::sync::Mutex<mpsc::Receiver<QueuedEvent>>>,
+    lanes_rx: Arc<tokio::sync::Mutex<LaneReceivers>>,
     /// 配置
     config: EventBusConfig,
     /// 统计信息
     stats: RwLock<EventBusStats>,
+    /// 死信缓冲区（最旧的在前）
+    dead_letters: RwLock<VecDeque<DeadLetter>>,
+    /// 分发器是否正在分发事件（退出前等待队列清空时使用）
+    dispatching: AtomicBool,
 }
 
 /// 事件总线统计
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EventBusStats {
     /// 已发布事件总数
     pub events_published: u64,
@@ -197,20 +369,39 @@ pub struct EventBusStats {
     pub dispatch_failures: u64,
     /// 当前订阅者总数
     pub total_subscriptions: usize,
+    /// 系统通道积压事件数
+    pub system_lane_depth: usize,
+    /// 插件通道积压事件数
+    pub plugin_lane_depth: usize,
+    /// 因防饥饿保护提前分发的插件事件数
+    pub starvation_yields: u64,
+    /// 进入死信缓冲区的事件总数（含已被丢弃的）
+    pub dead_letters: u64,
+    /// 死信缓冲区当前大小
+    pub dead_letter_depth: usize,
+    /// 各订阅者的投递指标: plugin_id -> 指标
+    pub subscribers: HashMap<String, SubscriberMetrics>,
 }
 
 impl EventBus {
     /// 创建新的事件总线
     pub fn new(config: EventBusConfig) -> Self {
-        let (tx, rx) = mpsc::channel(config.queue_size);
+        let (system_tx, system_rx) = mpsc::channel(config.queue_size);
+        let (plugin_tx, plugin_rx) = mpsc::channel(config.queue_size);
 
         Self {
             subscriptions: RwLock::new(HashMap::new()),
             handlers: RwLock::new(HashMap::new()),
-            event_tx: tx,
-            event_rx: Arc::new(tokio::sync::Mutex::new(rx)),
+            system_tx,
+            plugin_tx,
+            lanes_rx: Arc::new(tokio::sync::Mutex::new(LaneReceivers {
+                system: system_rx,
+                plugin: plugin_rx,
+            })),
             config,
             stats: RwLock::new(EventBusStats::default()),
+            dead_letters: RwLock::new(VecDeque::new()),
+            dispatching: AtomicBool::new(false),
         }
     }
 
@@ -229,22 +420,31 @@ impl EventBus {
     ///
     /// # 参数
     /// - `plugin_id`: 订阅者插件 ID
-    /// - `events`: 订阅的事件列表 (完整事件名)
+    /// - `events`: 订阅的事件列表 (完整事件名或通配符模式)
     pub async fn subscribe(&self, plugin_id: &str, events: &[String]) {
+        let subscriptions: Vec<EventSubscription> = events.iter().map(|e| e.as_str().into()).collect();
+        self.subscribe_with_filters(plugin_id, &subscriptions).await;
+    }
+
+    /// 注册带负载过滤器的事件订阅 (4.1.6)
+    ///
+    /// 同一插件重复订阅同一模式时以最后一次的过滤器为准
+    pub async fn subscribe_with_filters(&self, plugin_id: &str, events: &[EventSubscription]) {
         let mut subs = self.subscriptions.write().await;
 
-        for event_name in events {
+        for subscription in events {
+            let event_name = subscription.event();
             // 验证事件名称格式
             if !Self::is_valid_event_name(event_name) {
                 log::warn!("插件 {} 订阅了无效的事件名称: {}", plugin_id, event_name);
                 continue;
             }
 
-            subs.entry(event_name.clone())
-                .or_insert_with(HashSet::new)
-                .insert(plugin_id.to_string());
+            subs.entry(event_name.to_string())
+                .or_default()
+                .insert(plugin_id.to_string(), subscription.filter().cloned());
 
-            log::debug!("插件 {} 订阅事件: {}", plugin_id, event_name);
+            log::debug!("插件 {} 订阅事件: {}", plugin_id, subscription);
         }
 
         // 更新统计
@@ -275,6 +475,20 @@ impl EventBus {
         log::debug!("已取消插件 {} 的所有事件订阅和处理器", plugin_id);
     }
 
+    /// 有订阅或事件处理器的插件 ID
+    pub async fn registered_plugin_ids(&self) -> HashSet<String> {
+        let mut ids: HashSet<String> = self
+            .subscriptions
+            .read()
+            .await
+            .values()
+            .flat_map(|subscribers| subscribers.keys())
+            .cloned()
+            .collect();
+        ids.extend(self.handlers.read().await.keys().cloned());
+        ids
+    }
+
     /// 只取消插件的订阅（保留事件处理器）
     ///
     /// 在插件重载时调用，只清理订阅关系，保留已注册的事件处理器
@@ -306,14 +520,37 @@ impl EventBus {
         log::debug!("已注册插件 {} 的事件处理器", plugin_id);
     }
 
-    /// 获取事件的所有订阅者
+    /// 获取事件的所有订阅者（含通配符订阅，不考虑负载过滤器）
     pub async fn get_subscribers(&self, event_name: &str) -> Vec<String> {
-        self.subscriptions
-            .read()
-            .await
-            .get(event_name)
-            .map(|s| s.iter().cloned().collect())
-            .unwrap_or_default()
+        self.collect_subscribers(event_name, None).await
+    }
+
+    /// 获取应接收事件的订阅者（通配符与负载过滤器均匹配）
+    pub async fn matching_subscribers(&self, event_name: &str, data: &serde_json::Value) -> Vec<String> {
+        self.collect_subscribers(event_name, Some(data)).await
+    }
+
+    /// 收集匹配的订阅者（`data` 为 None 时跳过过滤器），任一订阅匹配即计入
+    async fn collect_subscribers(&self, event_name: &str, data: Option<&serde_json::Value>) -> Vec<String> {
+        let subs = self.subscriptions.read().await;
+        let mut matched = HashSet::new();
+        for (pattern, subscribers) in subs.iter() {
+            if !event_matches(pattern, event_name) {
+                continue;
+            }
+            for (plugin_id, filter) in subscribers {
+                let passes = match (filter, data) {
+                    (Some(filter), Some(data)) => filter_matches(filter, data),
+                    _ => true,
+                };
+                if passes {
+                    matched.insert(plugin_id.clone());
+                }
+            }
+        }
+        let mut matched: Vec<String> = matched.into_iter().collect();
+        matched.sort();
+        matched
     }
 
     // ========================================================================
@@ -373,7 +610,7 @@ impl EventBus {
         let event = QueuedEvent::plugin_event(plugin_id, action, data);
 
         // 使用 try_send 同步发送
-        self.event_tx
+        self.plugin_tx
             .try_send(event)
             .map_err(|e| match e {
                 mpsc::error::TrySendError::Full(_) => EventBusError::QueueFull,
@@ -402,13 +639,23 @@ impl EventBus {
         self.queue_event(event).await
     }
 
-    /// 将事件放入队列 (4.1.4)
+    /// 按事件名称选择通道的发送端
+    ///
+    /// system:/ipc: 事件（退出、权限撤销等）走高优先级通道，plugin: 事件走低优先级通道
+    fn lane_sender(&self, event_name: &str) -> &mpsc::Sender<QueuedEvent> {
+        match EventPrefix::from_event_name(event_name) {
+            Some(EventPrefix::System) | Some(EventPrefix::Ipc) => &self.system_tx,
+            Some(EventPrefix::Plugin) | None => &self.plugin_tx,
+        }
+    }
+
+    /// 将事件放入对应优先级的队列 (4.1.4)
     async fn queue_event(&self, event: QueuedEvent) -> Result<(), EventBusError> {
         // 更新统计
         self.stats.write().await.events_published += 1;
 
         // 发送到队列
-        self.event_tx
+        self.lane_sender(&event.event_name)
             .send(event)
             .await
             .map_err(|_| EventBusError::QueueClosed)?;
@@ -425,7 +672,7 @@ impl EventBus {
     /// 遍历订阅者，调用其 onEvent 回调
     /// 注意：先复制 handler 列表，释放锁后再执行 await，避免持锁 await 导致死锁
     async fn dispatch_event(&self, event: &QueuedEvent) -> EventDispatchResult {
-        let subscribers = self.get_subscribers(&event.event_name).await;
+        let subscribers = self.matching_subscribers(&event.event_name, &event.data).await;
 
         if subscribers.is_empty() {
             log::trace!("事件 {} 无订阅者", event.event_name);
@@ -457,36 +704,51 @@ impl EventBus {
         let timeout = std::time::Duration::from_millis(self.config.handler_timeout_ms);
         let mut success_count = 0;
         let mut failures = Vec::new();
+        let mut latencies = Vec::new();
+        let mut dead_letters = Vec::new();
 
         for (subscriber, handler) in handlers_to_call {
             let event_name = event.event_name.clone();
             let data = event.data.clone();
 
             // 带超时的事件处理（无持锁）
+            let started = std::time::Instant::now();
             let result = tokio::time::timeout(timeout, async move {
                 handler(event_name, data).await
             })
             .await;
+            let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
 
-            match result {
+            let (error, timed_out) = match result {
                 Ok(Ok(())) => {
                     success_count += 1;
+                    latencies.push((subscriber, latency_ms, false, false));
+                    continue;
                 }
                 Ok(Err(e)) => {
                     log::warn!("插件 {} 处理事件失败: {}", subscriber, e);
-                    failures.push(EventDispatchFailure {
-                        plugin_id: subscriber,
-                        error: e,
-                    });
+                    (e, false)
                 }
                 Err(_) => {
                     log::warn!("插件 {} 处理事件超时", subscriber);
-                    failures.push(EventDispatchFailure {
-                        plugin_id: subscriber,
-                        error: "处理超时".to_string(),
-                    });
+                    ("处理超时".to_string(), true)
                 }
-            }
+            };
+            latencies.push((subscriber.clone(), latency_ms, true, timed_out));
+            dead_letters.push(DeadLetter {
+                event_name: event.event_name.clone(),
+                data: event.data.clone(),
+                source_plugin: event.source_plugin.clone(),
+                subscriber: subscriber.clone(),
+                error: error.clone(),
+                timed_out,
+                published_at: event.timestamp,
+                failed_at: chrono::Utc::now(),
+            });
+            failures.push(EventDispatchFailure {
+                plugin_id: subscriber,
+                error,
+            });
         }
 
         // 更新统计
@@ -494,6 +756,17 @@ impl EventBus {
             let mut stats = self.stats.write().await;
             stats.events_dispatched += 1;
             stats.dispatch_failures += failures.len() as u64;
+            stats.dead_letters += dead_letters.len() as u64;
+            for (subscriber, latency_ms, failed, timed_out) in latencies {
+                stats
+                    .subscribers
+                    .entry(subscriber)
+                    .or_default()
+                    .record(latency_ms, failed, timed_out);
+            }
+        }
+        if !dead_letters.is_empty() {
+            self.push_dead_letters(dead_letters).await;
         }
 
         EventDispatchResult {
@@ -502,17 +775,76 @@ impl EventBus {
         }
     }
 
+    /// 放入死信缓冲区（超出容量时丢弃最旧的死信）
+    async fn push_dead_letters(&self, letters: Vec<DeadLetter>) {
+        let capacity = self.config.dead_letter_capacity;
+        let mut buffer = self.dead_letters.write().await;
+        buffer.extend(letters);
+        let overflow = buffer.len().saturating_sub(capacity);
+        buffer.drain(..overflow);
+    }
+
+    /// 获取死信（最新的在前），`limit` 为 None 时返回全部
+    pub async fn get_dead_letters(&self, limit: Option<usize>) -> Vec<DeadLetter> {
+        let buffer = self.dead_letters.read().await;
+        buffer
+            .iter()
+            .rev()
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+
+    /// 清空死信缓冲区，返回清除的数量
+    pub async fn clear_dead_letters(&self) -> usize {
+        let mut buffer = self.dead_letters.write().await;
+        let cleared = buffer.len();
+        buffer.clear();
+        cleared
+    }
+
+    /// 从优先级通道取出下一个事件
+    ///
+    /// 系统通道优先；连续分发 `system_burst_limit` 个系统事件后，
+    /// 若插件通道有积压则先分发一个插件事件，避免插件事件被无限推迟。
+    /// 两个通道都关闭时返回 None。
+    async fn next_event(&self, lanes: &mut LaneReceivers, system_burst: &mut usize) -> Option<QueuedEvent> {
+        if *system_burst >= self.config.system_burst_limit {
+            *system_burst = 0;
+            if let Ok(event) = lanes.plugin.try_recv() {
+                self.stats.write().await.starvation_yields += 1;
+                return Some(event);
+            }
+        }
+
+        tokio::select! {
+            biased;
+            Some(event) = lanes.system.recv() => {
+                *system_burst += 1;
+                Some(event)
+            }
+            Some(event) = lanes.plugin.recv() => {
+                *system_burst = 0;
+                Some(event)
+            }
+            else => None,
+        }
+    }
+
     /// 启动事件处理循环
     ///
-    /// 在后台运行，从队列中取出事件并分发
+    /// 在后台运行，按优先级从队列中取出事件并分发
     pub async fn start_dispatcher(self: Arc<Self>) {
         log::info!("事件总线分发器已启动");
 
-        let mut rx = self.event_rx.lock().await;
+        let mut lanes = self.lanes_rx.lock().await;
+        let mut system_burst = 0;
 
-        while let Some(event) = rx.recv().await {
+        while let Some(event) = self.next_event(&mut lanes, &mut system_burst).await {
             let event_name = event.event_name.clone();
+            self.dispatching.store(true, Ordering::SeqCst);
             let result = self.dispatch_event(&event).await;
+            self.dispatching.store(false, Ordering::SeqCst);
 
             if !result.failures.is_empty() {
                 log::debug!(
@@ -527,6 +859,15 @@ impl EventBus {
         log::info!("事件总线分发器已停止");
     }
 
+    /// 尚未分发完成的事件数（队列中的事件 + 正在分发的事件）
+    pub fn pending_events(&self) -> usize {
+        let queued = [&self.system_tx, &self.plugin_tx]
+            .iter()
+            .map(|tx| tx.max_capacity() - tx.capacity())
+            .sum::<usize>();
+        queued + usize::from(self.dispatching.load(Ordering::SeqCst))
+    }
+
     /// 在 Tokio 运行时中启动分发器
     pub fn spawn_dispatcher(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
         tokio::spawn(async move {
@@ -567,9 +908,13 @@ impl EventBus {
         action.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
     }
 
-    /// 获取统计信息
+    /// 获取统计信息（含各通道当前积压）
     pub async fn get_stats(&self) -> EventBusStats {
-        self.stats.read().await.clone()
+        let mut stats = self.stats.read().await.clone();
+        stats.system_lane_depth = self.system_tx.max_capacity() - self.system_tx.capacity();
+        stats.plugin_lane_depth = self.plugin_tx.max_capacity() - self.plugin_tx.capacity();
+        stats.dead_letter_depth = self.dead_letters.read().await.len();
+        stats
     }
 
     /// 获取配置
@@ -702,6 +1047,43 @@ mod tests {
         assert_eq!(subscribers, vec!["plugin-b"]);
     }
 
+    #[tokio::test]
+    async fn test_wildcard_subscriptions_with_filters() {
+        assert!(event_matches("plugin:*:data_updated", "plugin:claude-usage:data_updated"));
+        assert!(event_matches("plugin:claude-*:*", "plugin:claude-status:status_changed"));
+        assert!(!event_matches("plugin:*:data_updated", "plugin:claude-usage:status_changed"));
+        assert!(!event_matches("system:*", "plugin:claude-usage:data_updated"));
+
+        let bus = EventBus::new_default();
+        let subscriptions: Vec<EventSubscription> = serde_json::from_value(serde_json::json!([
+            "plugin:*:data_updated",
+            "plugin:claude-usage:data_updated",
+        ]))
+        .unwrap();
+        bus.subscribe_with_filters("dashboard", &subscriptions).await;
+        let subscriptions: Vec<EventSubscription> = serde_json::from_value(serde_json::json!([
+            { "event": "plugin:*:data_updated", "filter": { "dataType": ["usage", "balance"], "base.stale": false } },
+        ]))
+        .unwrap();
+        bus.subscribe_with_filters("usage-alerts", &subscriptions).await;
+
+        assert_eq!(
+            bus.get_subscribers("plugin:claude-usage:data_updated").await,
+            vec!["dashboard", "usage-alerts"]
+        );
+        let usage = serde_json::json!({ "dataType": "usage", "base": { "stale": false } });
+        let status = serde_json::json!({ "dataType": "status", "base": { "stale": false } });
+        assert_eq!(
+            bus.matching_subscribers("plugin:other:data_updated", &usage).await,
+            vec!["dashboard", "usage-alerts"]
+        );
+        assert_eq!(bus.matching_subscribers("plugin:other:data_updated", &status).await, vec!["dashboard"]);
+        assert!(bus.matching_subscribers("plugin:other:status_changed", &usage).await.is_empty());
+
+        bus.unsubscribe_all("dashboard").await;
+        assert_eq!(bus.registered_plugin_ids().await, HashSet::from(["usage-alerts".to_string()]));
+    }
+
     #[tokio::test]
     async fn test_emit_event() {
         let bus = EventBus::new_default();
@@ -727,4 +1109,120 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_priority_lanes_with_starvation_protection() {
+        let bus = Arc::new(EventBus::new(EventBusConfig {
+            system_burst_limit: 2,
+            ..EventBusConfig::default()
+        }));
+
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let handler: EventHandler = Arc::new(move |name, _| {
+            sink.lock().unwrap().push(name);
+            Box::pin(async { Ok(()) })
+        });
+        bus.register_handler("rec", handler).await;
+
+        let mut names = Vec::new();
+        for action in ["p1", "p2"] {
+            bus.emit("src", action, serde_json::json!({})).await.unwrap();
+            names.push(format!("plugin:src:{}", action));
+        }
+        for action in ["s1", "s2", "s3", "s4"] {
+            bus.emit_system(action, serde_json::json!({})).await.unwrap();
+            names.push(format!("system:{}", action));
+        }
+        bus.subscribe("rec", &names).await;
+
+        let stats = bus.get_stats().await;
+        assert_eq!((stats.system_lane_depth, stats.plugin_lane_depth), (4, 2));
+
+        let dispatcher = bus.clone().spawn_dispatcher();
+        for _ in 0..50 {
+            if received.lock().unwrap().len() == 6 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        dispatcher.abort();
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![
+                "system:s1",
+                "system:s2",
+                "plugin:src:p1",
+                "system:s3",
+                "system:s4",
+                "plugin:src:p2"
+            ]
+        );
+        assert_eq!(bus.get_stats().await.starvation_yields, 2);
+    }
+
+    #[tokio::test]
+    async fn test_dead_letters_and_subscriber_metrics() {
+        let bus = EventBus::new(EventBusConfig {
+            handler_timeout_ms: 50,
+            dead_letter_capacity: 2,
+            ..EventBusConfig::default()
+        });
+        let ok: EventHandler = Arc::new(|_, _| Box::pin(async { Ok(()) }));
+        let failing: EventHandler = Arc::new(|name, _| Box::pin(async move { Err(format!("rejected {}", name)) }));
+        let slow: EventHandler = Arc::new(|_, _| {
+            Box::pin(async {
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                Ok(())
+            })
+        });
+        for (id, handler) in [("ok", ok), ("failing", failing), ("slow", slow)] {
+            bus.register_handler(id, handler).await;
+            bus.subscribe(id, &["plugin:src:*".to_string()]).await;
+        }
+
+        for action in ["a", "b"] {
+            let result = bus
+                .dispatch_event(&QueuedEvent::plugin_event("src", action, serde_json::json!({"n": 1})))
+                .await;
+            assert_eq!((result.success_count, result.failures.len()), (1, 2));
+        }
+
+        // 容量 2：只保留最近的两条死信，最新的在前
+        let letters = bus.get_dead_letters(None).await;
+        assert_eq!(letters.len(), 2);
+        assert_eq!(letters[0].event_name, "plugin:src:b");
+        assert!(letters.iter().any(|l| l.subscriber == "slow" && l.timed_out));
+        assert!(letters.iter().any(|l| l.error == "rejected plugin:src:b"));
+        assert_eq!(bus.get_dead_letters(Some(1)).await.len(), 1);
+
+        let stats = bus.get_stats().await;
+        assert_eq!((stats.dead_letters, stats.dead_letter_depth), (4, 2));
+        let slow = &stats.subscribers["slow"];
+        assert_eq!((slow.deliveries, slow.failures, slow.timeouts), (2, 2, 2));
+        assert!(slow.avg_latency_ms >= 50.0 && slow.max_latency_ms >= slow.last_latency_ms);
+        assert_eq!(stats.subscribers["ok"].failures, 0);
+
+        assert_eq!(bus.clear_dead_letters().await, 2);
+        assert_eq!(bus.get_stats().await.dead_letter_depth, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pending_events_drain() {
+        let bus = Arc::new(EventBus::new_default());
+        bus.emit_system("refresh_all", serde_json::json!({})).await.unwrap();
+        bus.emit("src", "data_updated", serde_json::json!({})).await.unwrap();
+        assert_eq!(bus.pending_events(), 2);
+
+        let handle = bus.clone().spawn_dispatcher();
+        tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            while bus.pending_events() > 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+        handle.abort();
+    }
 }
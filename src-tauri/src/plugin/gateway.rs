@@ -0,0 +1,402 @@
+// Phase 4.4: 企业网关适配
+// 在宿主层配置 API 网关（Bedrock / Vertex / LiteLLM 等代理），插件通过符号引用
+//
+// 插件侧用法:
+// - URL 中使用 `{{gateway.base}}` 引用网关基础地址
+//   fetch("{{gateway.base}}/v1/messages", ...)
+// - 请求头名使用 `{{gateway.auth}}` 传入凭证，由宿主按网关的鉴权方案改写
+//   headers: { "{{gateway.auth}}": apiKey }
+//
+// 同一插件无需修改代码即可在直连与各类网关部署间切换。
+// 网关地址仍需通过 SSRF 检查（不允许内网地址）。
+// 网关附加请求头只发往网关主机；网关主机仅对 `{{gateway.base}}` 展开的请求
+// 或在 plugins 中显式映射的插件放行，默认网关不会扩大插件的 allowedHosts。
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::plugin::sandbox::fetch::FetchOptions;
+
+/// 网关基础地址占位符
+pub const GATEWAY_BASE_PLACEHOLDER: &str = "{{gateway.base}}";
+
+/// 网关鉴权请求头占位符
+pub const GATEWAY_AUTH_PLACEHOLDER: &str = "{{gateway.auth}}";
+
+/// 内置直连网关名称
+pub const BUILTIN_GATEWAY: &str = "anthropic";
+
+/// 内置直连网关地址
+const BUILTIN_GATEWAY_BASE: &str = "https://api.anthropic.com";
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum GatewayError {
+    #[error("网关配置无效: {0}")]
+    Invalid(String),
+
+    #[error("无法解析网关引用: {0}")]
+    Unresolved(String),
+
+    #[error("IO 错误: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON 错误: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+// ============================================================================
+// 网关配置
+// ============================================================================
+
+/// 网关鉴权方案
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "scheme", rename_all = "camelCase")]
+pub enum GatewayAuth {
+    /// `x-api-key: <key>`（Anthropic 直连）
+    #[default]
+    ApiKey,
+    /// `Authorization: Bearer <key>`（LiteLLM 等 OpenAI 兼容网关）
+    Bearer,
+    /// 自定义请求头，值为 `<prefix><key>`
+    Header {
+        name: String,
+        #[serde(default)]
+        prefix: String,
+    },
+    /// 网关自行处理鉴权，丢弃插件提供的凭证
+    None,
+}
+
+impl GatewayAuth {
+    /// 生成实际请求头（None 方案返回 None）
+    fn header(&self, credential: &str) -> Option<(String, String)> {
+        match self {
+            Self::ApiKey => Some(("x-api-key".to_string(), credential.to_string())),
+            Self::Bearer => Some(("Authorization".to_string(), format!("Bearer {}", credential))),
+            Self::Header { name, prefix } => Some((name.clone(), format!("{}{}", prefix, credential))),
+            Self::None => None,
+        }
+    }
+}
+
+/// 单个网关配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayProfile {
+    /// 基础地址（替换 `{{gateway.base}}`）
+    pub base_url: String,
+    /// 鉴权方案
+    #[serde(default)]
+    pub auth: GatewayAuth,
+    /// 附加请求头（仅发往网关主机，插件未设置同名请求头时添加）
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl GatewayProfile {
+    /// 网关主机名
+    pub fn host(&self) -> Option<String> {
+        url::Url::parse(&self.base_url)
+            .ok()
+            .and_then(|u| u.host_str().map(String::from))
+    }
+
+    /// 展开 URL 中的网关引用
+    pub fn expand_url(&self, url: &str) -> Result<String, GatewayError> {
+        let expanded = url.replace(GATEWAY_BASE_PLACEHOLDER, self.base_url.trim_end_matches('/'));
+        reject_unknown_placeholder(&expanded)?;
+        Ok(expanded)
+    }
+
+    /// 改写请求头：`{{gateway.auth}}` 按鉴权方案替换；`to_gateway` 为 true 时补充附加请求头
+    pub fn apply_headers(&self, headers: &mut HashMap<String, String>, to_gateway: bool) -> Result<(), GatewayError> {
+        if let Some(credential) = headers.remove(GATEWAY_AUTH_PLACEHOLDER) {
+            if let Some((name, value)) = self.auth.header(&credential) {
+                headers.insert(name, value);
+            }
+        }
+        if let Some(key) = headers.keys().find(|k| k.contains("{{gateway.")) {
+            return Err(GatewayError::Unresolved(key.clone()));
+        }
+
+        if !to_gateway {
+            return Ok(());
+        }
+        for (name, value) in &self.headers {
+            if !headers.keys().any(|k| k.eq_ignore_ascii_case(name)) {
+                headers.insert(name.clone(), value.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// 对 fetch 请求应用网关（返回展开后的 URL）
+    ///
+    /// 附加请求头可能包含网关凭证，只在展开后的主机与网关主机一致时添加；
+    /// 以 `{{gateway.base}}` 开头的请求标记为网关请求（`FetchOptions::via_gateway`）
+    pub fn apply(&self, url: &str, options: &mut FetchOptions) -> Result<String, GatewayError> {
+        let expanded = self.expand_url(url)?;
+        let host = url::Url::parse(&expanded)
+            .ok()
+            .and_then(|u| u.host_str().map(String::from));
+        let to_gateway = host.is_some() && host == self.host();
+        self.apply_headers(&mut options.headers, to_gateway)?;
+        options.via_gateway = to_gateway && url.starts_with(GATEWAY_BASE_PLACEHOLDER);
+        Ok(expanded)
+    }
+
+    fn validate(&self, name: &str) -> Result<(), GatewayError> {
+        let parsed = url::Url::parse(&self.base_url)
+            .map_err(|e| GatewayError::Invalid(format!("{}: baseUrl 无效 ({})", name, e)))?;
+        if parsed.scheme() != "https" {
+            return Err(GatewayError::Invalid(format!("{}: baseUrl 必须使用 https", name)));
+        }
+        if parsed.host_str().is_none() {
+            return Err(GatewayError::Invalid(format!("{}: baseUrl 缺少主机名", name)));
+        }
+        if let GatewayAuth::Header { name: header, .. } = &self.auth {
+            if reqwest::header::HeaderName::from_bytes(header.as_bytes()).is_err() {
+                return Err(GatewayError::Invalid(format!("{}: 鉴权请求头名无效: {}", name, header)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 宿主网关配置（gateways.json）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayConfig {
+    /// 默认网关名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// 已配置的网关
+    #[serde(default)]
+    pub gateways: BTreeMap<String, GatewayProfile>,
+    /// 插件 ID -> 网关名称（覆盖默认网关）
+    #[serde(default)]
+    pub plugins: BTreeMap<String, String>,
+}
+
+impl Default for GatewayConfig {
+    /// 仅包含内置直连网关
+    fn default() -> Self {
+        let mut gateways = BTreeMap::new();
+        gateways.insert(
+            BUILTIN_GATEWAY.to_string(),
+            GatewayProfile {
+                base_url: BUILTIN_GATEWAY_BASE.to_string(),
+                auth: GatewayAuth::ApiKey,
+                headers: BTreeMap::new(),
+            },
+        );
+        Self {
+            default: Some(BUILTIN_GATEWAY.to_string()),
+            gateways,
+            plugins: BTreeMap::new(),
+        }
+    }
+}
+
+impl GatewayConfig {
+    /// 默认配置文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("gateways.json")
+    }
+
+    /// 从文件加载（文件不存在时使用默认配置）
+    pub fn load(path: &Path) -> Result<Self, GatewayError> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        let config: Self = serde_json::from_str(&content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    pub fn save(&self, path: &Path) -> Result<(), GatewayError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// 校验配置
+    pub fn validate(&self) -> Result<(), GatewayError> {
+        for (name, profile) in &self.gateways {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(GatewayError::Invalid(format!("网关名称无效: {:?}", name)));
+            }
+            profile.validate(name)?;
+        }
+
+        let references = self.default.iter().chain(self.plugins.values());
+        for name in references {
+            if !self.gateways.contains_key(name) {
+                return Err(GatewayError::Invalid(format!("引用了不存在的网关: {}", name)));
+            }
+        }
+        Ok(())
+    }
+
+    /// 解析插件使用的网关（插件映射优先，其次默认网关）
+    pub fn resolve(&self, plugin_id: &str) -> Option<&GatewayProfile> {
+        self.plugins
+            .get(plugin_id)
+            .or(self.default.as_ref())
+            .and_then(|name| self.gateways.get(name))
+    }
+
+    /// 插件是否在 plugins 中显式映射到网关（映射的插件始终允许访问网关主机）
+    pub fn is_mapped(&self, plugin_id: &str) -> bool {
+        self.plugins.contains_key(plugin_id)
+    }
+}
+
+/// 检查是否残留未识别的网关占位符
+fn reject_unknown_placeholder(value: &str) -> Result<(), GatewayError> {
+    match value.find("{{gateway.") {
+        Some(start) => {
+            let rest = &value[start..];
+            let end = rest.find("}}").map(|i| i + 2).unwrap_or(rest.len());
+            Err(GatewayError::Unresolved(rest[..end].to_string()))
+        }
+        None => Ok(()),
+    }
+}
+
+/// 未配置网关时拒绝网关引用
+pub fn reject_gateway_reference(url: &str, options: &FetchOptions) -> Result<(), GatewayError> {
+    reject_unknown_placeholder(url)?;
+    if let Some(key) = options.headers.keys().find(|k| k.contains("{{gateway.")) {
+        return Err(GatewayError::Unresolved(format!("{} (未配置网关)", key)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn litellm() -> GatewayProfile {
+        let mut headers = BTreeMap::new();
+        headers.insert("anthropic-version".to_string(), "2023-06-01".to_string());
+        GatewayProfile {
+            base_url: "https://llm.example.com/anthropic/".to_string(),
+            auth: GatewayAuth::Bearer,
+            headers,
+        }
+    }
+
+    #[test]
+    fn test_apply_gateway() {
+        let gateway = litellm();
+        let mut options = FetchOptions::default();
+        options
+            .headers
+            .insert(GATEWAY_AUTH_PLACEHOLDER.to_string(), "sk-test".to_string());
+        options
+            .headers
+            .insert("Anthropic-Version".to_string(), "2024-01-01".to_string());
+
+        let url = gateway.apply("{{gateway.base}}/v1/messages", &mut options).unwrap();
+        assert_eq!(url, "https://llm.example.com/anthropic/v1/messages");
+        assert_eq!(options.headers.get("Authorization").unwrap(), "Bearer sk-test");
+        // 插件已设置的同名请求头优先
+        assert_eq!(options.headers.get("Anthropic-Version").unwrap(), "2024-01-01");
+        assert!(!options.headers.contains_key("anthropic-version"));
+
+        assert!(options.via_gateway);
+
+        // 非网关主机不附加网关请求头，也不视为网关请求
+        let mut options = FetchOptions::default();
+        let url = gateway.apply("https://other.example.com/v1", &mut options).unwrap();
+        assert_eq!(url, "https://other.example.com/v1");
+        assert!(options.headers.is_empty());
+        assert!(!options.via_gateway);
+
+        // 直接写网关地址时附加请求头，但不是网关引用
+        let mut options = FetchOptions::default();
+        gateway.apply("https://llm.example.com/v1", &mut options).unwrap();
+        assert_eq!(options.headers.get("anthropic-version").unwrap(), "2023-06-01");
+        assert!(!options.via_gateway);
+
+        // 借助 userinfo 改变主机的引用不附加请求头
+        let direct = GatewayConfig::default().gateways[BUILTIN_GATEWAY].clone();
+        let direct = GatewayProfile {
+            headers: litellm().headers,
+            ..direct
+        };
+        let mut options = FetchOptions::default();
+        let url = direct.apply("{{gateway.base}}@evil.example.com/v1", &mut options).unwrap();
+        assert!(url.contains("evil.example.com"));
+        assert!(options.headers.is_empty());
+        assert!(!options.via_gateway);
+
+        // 未识别的占位符
+        assert!(gateway.expand_url("{{gateway.region}}/v1").is_err());
+        assert!(reject_gateway_reference("{{gateway.base}}/v1", &FetchOptions::default()).is_err());
+        assert!(reject_gateway_reference("https://example.com", &FetchOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn test_resolve_and_validate() {
+        let mut config = GatewayConfig::default();
+        assert_eq!(
+            config.resolve("any").unwrap().base_url,
+            BUILTIN_GATEWAY_BASE
+        );
+
+        config.gateways.insert("litellm".to_string(), litellm());
+        config.plugins.insert("claude-usage".to_string(), "litellm".to_string());
+        config.validate().unwrap();
+        assert_eq!(config.resolve("claude-usage").unwrap().auth, GatewayAuth::Bearer);
+        assert_eq!(config.resolve("other").unwrap().auth, GatewayAuth::ApiKey);
+        assert!(config.is_mapped("claude-usage"));
+        assert!(!config.is_mapped("other"));
+
+        config.plugins.insert("x".to_string(), "missing".to_string());
+        assert!(config.validate().is_err());
+        config.plugins.remove("x");
+
+        config.gateways.get_mut("litellm").unwrap().base_url = "http://llm.example.com".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_load_and_save() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = GatewayConfig::file_path(&temp_dir.path().join("plugins"));
+        assert_eq!(GatewayConfig::load(&path).unwrap(), GatewayConfig::default());
+
+        let mut config = GatewayConfig::default();
+        config.gateways.insert("litellm".to_string(), litellm());
+        config.default = Some("litellm".to_string());
+        config.save(&path).unwrap();
+        assert_eq!(GatewayConfig::load(&path).unwrap(), config);
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert!(json.contains(r#""scheme": "bearer""#));
+        assert!(json.contains(r#""baseUrl""#));
+    }
+}
@@ -0,0 +1,297 @@
+// Phase 2.2.9: 宿主调度的持久定时器
+// 沙盒在每次执行结束后即被回收，执行期的 setTimeout/setInterval 随之取消。
+// 需要周期性工作的插件（如事件型插件）通过 context.timers.schedule() 登记持久定时器，
+// 由宿主计时，到期时在新的沙盒中调用插件导出的 onTimer(name, data, context)。
+//
+// 语义:
+// 1. 只在插件启用期间有效；每个定时器在插件的 ResourceRegistry 中占一个 Timer 资源
+// 2. 同名定时器重新登记时替换旧定时器；一次性定时器触发后自动注销
+// 3. 禁用/卸载插件时按 ResourceRegistry 中的 Timer 资源全部取消
+// 4. 不持久化：应用重启后由插件在 onLoad 中重新登记
+// 5. 触发通过通道交给宿主分发；同一定时器上次触发仍在执行时跳过本次触发
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// 每个插件最多同时存在的持久定时器数量
+pub const MAX_HOST_TIMERS_PER_PLUGIN: usize = 10;
+
+/// 定时器触发（交给宿主调用插件的 onTimer）
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimerFire {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 定时器名称
+    pub name: String,
+    /// 登记时附带的数据
+    pub data: serde_json::Value,
+    /// 对应的 ResourceRegistry 资源 ID
+    pub resource_id: u64,
+    /// 是否为重复定时器
+    pub repeating: bool,
+}
+
+/// 已登记的持久定时器
+struct HostTimer {
+    /// ResourceRegistry 资源 ID
+    resource_id: u64,
+    /// 取消令牌
+    cancel: CancellationToken,
+}
+
+/// 持久定时器调度器
+pub struct HostTimerScheduler {
+    /// 插件 ID -> 定时器名称 -> 定时器
+    timers: Mutex<HashMap<String, HashMap<String, HostTimer>>>,
+    /// 正在执行的触发（插件 ID, 定时器名称）
+    running: Mutex<HashSet<(String, String)>>,
+    /// 触发通知发送端
+    fire_tx: mpsc::UnboundedSender<TimerFire>,
+    /// 触发通知接收端（由宿主取出消费）
+    fire_rx: Mutex<Option<mpsc::UnboundedReceiver<TimerFire>>>,
+}
+
+impl HostTimerScheduler {
+    /// 创建调度器
+    pub fn new() -> Self {
+        let (fire_tx, fire_rx) = mpsc::unbounded_channel();
+        Self {
+            timers: Mutex::new(HashMap::new()),
+            running: Mutex::new(HashSet::new()),
+            fire_tx,
+            fire_rx: Mutex::new(Some(fire_rx)),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, HashMap<String, HostTimer>>> {
+        self.timers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 取出触发通知接收端（只能取出一次，供宿主消费）
+    pub fn take_receiver(&self) -> Option<mpsc::UnboundedReceiver<TimerFire>> {
+        self.fire_rx.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// 插件是否已登记同名定时器
+    pub fn contains(&self, plugin_id: &str, name: &str) -> bool {
+        self.lock()
+            .get(plugin_id)
+            .is_some_and(|timers| timers.contains_key(name))
+    }
+
+    /// 插件当前的定时器数量
+    pub fn count(&self, plugin_id: &str) -> usize {
+        self.lock().get(plugin_id).map_or(0, HashMap::len)
+    }
+
+    /// 插件当前的定时器名称（排序）
+    pub fn names(&self, plugin_id: &str) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .lock()
+            .get(plugin_id)
+            .map(|timers| timers.keys().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    /// 登记定时器并开始计时
+    ///
+    /// 返回被替换的同名定时器的资源 ID（调用方负责从 ResourceRegistry 注销）
+    pub fn schedule(
+        &self,
+        plugin_id: &str,
+        name: &str,
+        resource_id: u64,
+        delay: Duration,
+        interval: Option<Duration>,
+        data: serde_json::Value,
+    ) -> Option<u64> {
+        let cancel = CancellationToken::new();
+        let replaced = self
+            .lock()
+            .entry(plugin_id.to_string())
+            .or_default()
+            .insert(
+                name.to_string(),
+                HostTimer {
+                    resource_id,
+                    cancel: cancel.clone(),
+                },
+            )
+            .map(|old| {
+                old.cancel.cancel();
+                old.resource_id
+            });
+
+        let fire = TimerFire {
+            plugin_id: plugin_id.to_string(),
+            name: name.to_string(),
+            data,
+            resource_id,
+            repeating: interval.is_some(),
+        };
+        let fire_tx = self.fire_tx.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                biased;
+                _ = cancel.cancelled() => return,
+                _ = tokio::time::sleep(delay) => {}
+            }
+            if fire_tx.send(fire.clone()).is_err() {
+                return;
+            }
+            let Some(period) = interval else { return };
+            let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            // 宿主繁忙时不补发错过的触发
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = cancel.cancelled() => break,
+                    _ = ticker.tick() => {
+                        if fire_tx.send(fire.clone()).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        log::debug!(
+            "[{}] 已登记持久定时器 {}: 延时 {:?}, 间隔 {:?}",
+            plugin_id,
+            name,
+            delay,
+            interval
+        );
+        replaced
+    }
+
+    /// 取消定时器，返回其资源 ID
+    pub fn cancel(&self, plugin_id: &str, name: &str) -> Option<u64> {
+        let mut timers = self.lock();
+        let plugin_timers = timers.get_mut(plugin_id)?;
+        let timer = plugin_timers.remove(name)?;
+        if plugin_timers.is_empty() {
+            timers.remove(plugin_id);
+        }
+        timer.cancel.cancel();
+        Some(timer.resource_id)
+    }
+
+    /// 一次性定时器触发后注销（同名定时器已被替换时不处理）
+    ///
+    /// 返回是否注销
+    pub fn finish(&self, fire: &TimerFire) -> bool {
+        if fire.repeating {
+            return false;
+        }
+        let mut timers = self.lock();
+        let Some(plugin_timers) = timers.get_mut(&fire.plugin_id) else {
+            return false;
+        };
+        if plugin_timers.get(&fire.name).map(|t| t.resource_id) != Some(fire.resource_id) {
+            return false;
+        }
+        plugin_timers.remove(&fire.name);
+        if plugin_timers.is_empty() {
+            timers.remove(&fire.plugin_id);
+        }
+        true
+    }
+
+    /// 触发是否仍然有效（定时器未被取消或替换）
+    pub fn is_active(&self, fire: &TimerFire) -> bool {
+        self.lock()
+            .get(&fire.plugin_id)
+            .and_then(|timers| timers.get(&fire.name))
+            .is_some_and(|t| t.resource_id == fire.resource_id)
+    }
+
+    /// 标记触发开始执行，同一定时器上次触发仍在执行时返回 false
+    pub fn begin(&self, fire: &TimerFire) -> bool {
+        self.running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert((fire.plugin_id.clone(), fire.name.clone()))
+    }
+
+    /// 标记触发执行结束
+    pub fn end(&self, fire: &TimerFire) {
+        self.running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&(fire.plugin_id.clone(), fire.name.clone()));
+    }
+
+    /// 取消 ResourceRegistry 中已释放的 Timer 资源对应的定时器
+    ///
+    /// 返回取消的数量
+    pub fn cancel_resources(&self, plugin_id: &str, resource_ids: &[u64]) -> usize {
+        let mut timers = self.lock();
+        let Some(plugin_timers) = timers.get_mut(plugin_id) else {
+            return 0;
+        };
+        let before = plugin_timers.len();
+        plugin_timers.retain(|_, timer| {
+            let released = resource_ids.contains(&timer.resource_id);
+            if released {
+                timer.cancel.cancel();
+            }
+            !released
+        });
+        let cancelled = before - plugin_timers.len();
+        if plugin_timers.is_empty() {
+            timers.remove(plugin_id);
+        }
+        cancelled
+    }
+}
+
+impl Default for HostTimerScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_host_timer_fire_replace_and_cancel() {
+        let scheduler = HostTimerScheduler::new();
+        let mut rx = scheduler.take_receiver().unwrap();
+        assert!(scheduler.take_receiver().is_none());
+
+        // 一次性定时器：触发后注销
+        scheduler.schedule("demo", "once", 1, Duration::from_millis(5), None, serde_json::json!({"n": 1}));
+        let fire = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await.unwrap().unwrap();
+        assert_eq!((fire.name.as_str(), fire.resource_id, fire.repeating), ("once", 1, false));
+        assert_eq!(fire.data, serde_json::json!({"n": 1}));
+        assert!(scheduler.finish(&fire));
+        assert_eq!(scheduler.count("demo"), 0);
+
+        // 重复定时器：同名登记替换，资源释放时取消
+        let interval = Some(Duration::from_millis(5));
+        scheduler.schedule("demo", "tick", 2, Duration::from_secs(60), interval, serde_json::Value::Null);
+        assert_eq!(scheduler.schedule("demo", "tick", 3, Duration::ZERO, interval, serde_json::Value::Null), Some(2));
+        for _ in 0..2 {
+            let fire = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await.unwrap().unwrap();
+            assert_eq!(fire.resource_id, 3);
+            assert!(scheduler.is_active(&fire) && !scheduler.finish(&fire));
+        }
+        let fire = rx.recv().await.unwrap();
+        assert!(scheduler.begin(&fire) && !scheduler.begin(&fire));
+        scheduler.end(&fire);
+        assert_eq!(scheduler.names("demo"), vec!["tick"]);
+        assert_eq!(scheduler.cancel_resources("demo", &[3]), 1);
+        assert_eq!(scheduler.count("demo"), 0);
+        assert!(scheduler.cancel("demo", "tick").is_none());
+    }
+}
@@ -0,0 +1,233 @@
+// Phase 7.3.13: Phase 2 旧版命令弃用
+// Phase 2 的 list_plugins/enable_plugin 等命令改为映射到 7.3 实现的兼容层:
+// - 每次调用发送 ipc:deprecated_command 事件并记录警告日志
+// - 本地统计各旧命令的调用次数和最后调用时间，用于规划移除时间（不上报）
+// - 返回值与 7.3 命令一致为 IpcResult 信封，错误带稳定错误码
+// - 设置项可停用旧命令，停用后调用返回 LEGACY_COMMAND_DISABLED
+//
+// 设置与统计持久化到 legacy_commands.json（与插件目录同级）
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::monitoring::format_rfc3339;
+
+/// 旧版命令及其替代命令
+pub const LEGACY_COMMANDS: &[(&str, Option<&str>)] = &[
+    ("list_plugins", Some("plugin_list")),
+    ("get_plugin", Some("plugin_list")),
+    ("enable_plugin", Some("plugin_enable")),
+    ("disable_plugin", Some("plugin_disable")),
+    ("discover_plugins", Some("plugin_list")),
+    ("get_plugins_dir", None),
+];
+
+/// 旧版命令的替代命令
+pub fn replacement_for(command: &str) -> Option<&'static str> {
+    LEGACY_COMMANDS
+        .iter()
+        .find(|(name, _)| *name == command)
+        .and_then(|(_, replacement)| *replacement)
+}
+
+/// 单个旧命令的调用统计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyCommandUsage {
+    /// 调用次数
+    pub count: u64,
+    /// 最后调用时间 (ISO 8601)
+    pub last_used: String,
+}
+
+/// 旧版命令设置与调用统计（legacy_commands.json）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LegacyCommandSettings {
+    /// 是否允许调用旧版命令
+    pub enabled: bool,
+    /// 命令名 -> 调用统计
+    pub usage: BTreeMap<String, LegacyCommandUsage>,
+}
+
+impl Default for LegacyCommandSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            usage: BTreeMap::new(),
+        }
+    }
+}
+
+/// 弃用命令事件 / 统计报告中的单条记录
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeprecatedCommand {
+    /// 旧命令名
+    pub command: String,
+    /// 替代命令（无替代时为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+    /// 累计调用次数
+    pub count: u64,
+    /// 最后调用时间 (ISO 8601)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<String>,
+}
+
+/// 旧版命令统计报告
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyCommandReport {
+    /// 是否允许调用旧版命令
+    pub enabled: bool,
+    /// 所有旧命令（含未调用过的）
+    pub commands: Vec<DeprecatedCommand>,
+}
+
+/// 旧版命令被停用
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("旧版命令 {command} 已停用{}", .replacement.map(|r| format!("，请改用 {}", r)).unwrap_or_default())]
+pub struct LegacyCommandDisabled {
+    pub command: String,
+    pub replacement: Option<&'static str>,
+}
+
+/// 旧版命令兼容层
+pub struct LegacyCommands {
+    settings: Mutex<LegacyCommandSettings>,
+    /// 持久化路径
+    path: PathBuf,
+}
+
+impl LegacyCommands {
+    /// 默认文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("legacy_commands.json")
+    }
+
+    /// 从文件加载（文件不存在或无法解析时允许旧命令、统计从零开始）
+    pub fn load(path: PathBuf) -> Self {
+        let settings = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析旧版命令设置失败，使用默认设置: {}", e);
+                LegacyCommandSettings::default()
+            }),
+            Err(_) => LegacyCommandSettings::default(),
+        };
+        Self {
+            settings: Mutex::new(settings),
+            path,
+        }
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    fn save(&self, settings: &LegacyCommandSettings) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(settings)?)?;
+        std::fs::rename(&tmp, &self.path)
+    }
+
+    /// 记录一次旧命令调用
+    ///
+    /// 旧命令已停用时返回错误（不计入统计）
+    pub fn record(&self, command: &str, now: DateTime<Utc>) -> Result<DeprecatedCommand, LegacyCommandDisabled> {
+        let replacement = replacement_for(command);
+        let mut settings = self.settings.lock().unwrap_or_else(|e| e.into_inner());
+        if !settings.enabled {
+            return Err(LegacyCommandDisabled {
+                command: command.to_string(),
+                replacement,
+            });
+        }
+
+        let last_used = format_rfc3339(now);
+        let usage = settings
+            .usage
+            .entry(command.to_string())
+            .or_insert_with(|| LegacyCommandUsage {
+                count: 0,
+                last_used: last_used.clone(),
+            });
+        usage.count += 1;
+        usage.last_used = last_used.clone();
+        let event = DeprecatedCommand {
+            command: command.to_string(),
+            replacement: replacement.map(str::to_string),
+            count: usage.count,
+            last_used: Some(last_used),
+        };
+        if let Err(e) = self.save(&settings) {
+            log::warn!("保存旧版命令统计失败: {}", e);
+        }
+        Ok(event)
+    }
+
+    /// 允许/停用旧版命令
+    pub fn set_enabled(&self, enabled: bool) -> std::io::Result<()> {
+        let mut settings = self.settings.lock().unwrap_or_else(|e| e.into_inner());
+        settings.enabled = enabled;
+        self.save(&settings)
+    }
+
+    /// 调用统计报告
+    pub fn report(&self) -> LegacyCommandReport {
+        let settings = self.settings.lock().unwrap_or_else(|e| e.into_inner());
+        LegacyCommandReport {
+            enabled: settings.enabled,
+            commands: LEGACY_COMMANDS
+                .iter()
+                .map(|(command, replacement)| {
+                    let usage = settings.usage.get(*command);
+                    DeprecatedCommand {
+                        command: command.to_string(),
+                        replacement: replacement.map(str::to_string),
+                        count: usage.map_or(0, |u| u.count),
+                        last_used: usage.map(|u| u.last_used.clone()),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_command_usage_and_disable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("legacy_commands.json");
+        let legacy = LegacyCommands::load(path.clone());
+        let now = Utc::now();
+
+        legacy.record("list_plugins", now).unwrap();
+        let event = legacy.record("list_plugins", now).unwrap();
+        assert_eq!(event.count, 2);
+        assert_eq!(event.replacement.as_deref(), Some("plugin_list"));
+
+        // 统计持久化，重启后保留
+        let report = LegacyCommands::load(path.clone()).report();
+        assert!(report.enabled);
+        assert_eq!(report.commands.len(), LEGACY_COMMANDS.len());
+        assert_eq!(report.commands[0].count, 2);
+        assert_eq!(report.commands[2].count, 0);
+
+        // 停用后调用返回错误并提示替代命令
+        legacy.set_enabled(false).unwrap();
+        let err = legacy.record("enable_plugin", now).unwrap_err();
+        assert!(err.to_string().contains("plugin_enable"));
+        assert!(!LegacyCommands::load(path).report().enabled);
+    }
+}
@@ -10,24 +10,65 @@
 // - 2.3.5 onUnload 生命周期
 // - 2.3.6 资源注册表
 // - 2.3.7 资源强制回收
+// - 2.3.8 启用状态与配置持久化 (state.json)
 // - 4.x Phase 4 集成 (EventBus/ConfigManager/PermissionChecker)
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-
-use tokio::sync::{mpsc, RwLock};
-
-use crate::plugin::config::{ConfigManager, ConfigSchema};
-use crate::plugin::event_bus::EventBus;
-use crate::plugin::monitoring::SlidingWindow;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, oneshot, RwLock};
+
+use crate::plugin::api_version::{negotiate, IncompatiblePlugin};
+use crate::plugin::batch::{BatchQuery, BatchResponse, BatchResult};
+use crate::plugin::builtin::BuiltinProvider;
+use crate::plugin::config::{ConfigManager, ConfigSchema, FieldValidationError};
+use crate::plugin::data_schema::validate_payload;
+use crate::plugin::env_import::SECRET_REF_FIELD;
+use crate::plugin::developer::{is_dev_default_permission, DeveloperSettings};
+use crate::plugin::event_bus::{EventBus, EventHandler, EventSubscription};
+use crate::plugin::gateway::{GatewayConfig, GatewayError};
+use crate::plugin::proxy::{ProxyConfig, DNS_WARNING};
+use crate::plugin::tls::TlsConfig;
+use crate::plugin::maintenance::{active_maintenance, ActiveMaintenance, MaintenanceWindow};
+use crate::plugin::monitoring::{
+    format_rfc3339, is_runaway_error, normalize_timestamp, summarize_error, RecordedAt,
+    SlidingWindow, UsageAlertSettings, WatchdogPolicy, WatchdogTrip,
+};
+use crate::plugin::network_stats::{NetworkRecorder, NetworkStats, PrivacyReport};
+use crate::plugin::plugin_logs::{PluginLogRecorder, PluginLogStore};
+use crate::plugin::diagnostics::{
+    diagnostics_dir, redact_config, DiagnosticsBundle, DiagnosticsFile, DiagnosticsReport, RuntimeInfo,
+};
 use crate::plugin::permission::{MethodRegistry, PermissionChecker};
-use crate::plugin::sandbox::PluginCallRequest;
+use crate::plugin::runtime::{
+    SandboxApis, SandboxConfig, SandboxPool, DEFAULT_EXECUTION_TIMEOUT, DEFAULT_MEMORY_LIMIT,
+};
+use crate::plugin::sandbox::storage::PluginStorageStats;
+use crate::plugin::sandbox::secrets::{declared_secrets, secrets_service, KeychainStore, SecretStore};
+use crate::plugin::host_timer::{HostTimerScheduler, TimerFire, MAX_HOST_TIMERS_PER_PLUGIN};
+use crate::plugin::sandbox::{HostTimerRequest, InputBroker, PluginCallRequest, PluginModule};
+use crate::plugin::pricing::{CostEstimate, CostEstimator, CostRange};
+use crate::plugin::summary::{summarize, DataSummary};
+use crate::plugin::state::{PersistedAccount, PluginStateStore};
+use crate::plugin::usage_history::{UsageAnomaly, UsageHistory, UsageTrend};
+use crate::reliability::retry::RetryableError;
+use crate::reliability::{
+    CacheConfig, CacheKey, CacheLayer, RateLimitConfig, RateLimiter, RetryConfig, RetryExecutor,
+};
 use crate::plugin::types::{
-    DataType, HealthStatus, PluginData, PluginHealth, PluginInfo, PluginType, ValidationResult,
+    is_provider_gone, DataType, HealthStatus, HealthTransition, PluginAccountData, PluginData, PluginErrorType,
+    PluginHealth, PluginResourceStats,
+    IntegrityReport, PluginDescription, PluginInfo, PluginSandboxLimits, PluginType, ReplayParseResult,
+    TrustLevel, ValidationResult,
 };
-use chrono::Utc;
-use std::time::Instant;
+use crate::security::audit::{AuditEvent, AuditEventKind, AuditLog};
+use crate::security::integrity::check_files;
+use crate::security::policy::{PolicyManager, SandboxCaps};
+use crate::security::keys::PublisherKeyStore;
+use crate::security::signature::SignatureVerifier;
+use chrono::{DateTime, Utc};
 
 // ============================================================================
 // TOCTOU 防护：openat 链式验证（Unix）
@@ -46,7 +87,7 @@ use std::time::Instant;
 /// - 攻击者无法在打开后替换（fd 已指向 inode）
 /// - **关键修复**：返回打开的 File 而非路径，彻底消除验证-使用窗口
 #[cfg(unix)]
-mod openat_verifier {
+pub(crate) mod openat_verifier {
     use std::ffi::CString;
     use std::fs::File;
     use std::io::Read;
@@ -187,7 +228,7 @@ mod openat_verifier {
 
 /// Windows: 使用 symlink_metadata 检查（无原生 openat 支持）
 #[cfg(not(unix))]
-mod openat_verifier {
+pub(crate) mod openat_verifier {
     use std::fs::File;
     use std::io::Read;
     use std::path::Path;
@@ -248,6 +289,12 @@ pub enum LifecycleError {
     #[error("插件目录不存在: {0}")]
     PluginDirNotFound(PathBuf),
 
+    #[error("插件不存在: {0}")]
+    PluginNotFound(String),
+
+    #[error("插件未启用: {0}")]
+    PluginDisabled(String),
+
     #[error("manifest.json 不存在: {0}")]
     ManifestNotFound(PathBuf),
 
@@ -265,6 +312,18 @@ pub enum LifecycleError {
 
     #[error("JS 执行错误: {0}")]
     JsExecution(String),
+
+    #[error("配置校验失败: {}", .0.iter().map(|e| e.message.as_str()).collect::<Vec<_>>().join("; "))]
+    ConfigInvalid(Vec<FieldValidationError>),
+
+    #[error("密钥存储失败: {0}")]
+    SecretStore(String),
+
+    #[error("沙盒资源声明超出上限: {0}")]
+    ResourceLimitExceeded(String),
+
+    #[error("{0}")]
+    IncompatibleApiVersion(IncompatiblePlugin),
 }
 
 // ============================================================================
@@ -306,18 +365,40 @@ pub struct PluginManifest {
     /// 权限声明 (格式: call:{pluginId}:{method})
     #[serde(default)]
     pub permissions: Vec<String>,
+    /// 允许访问的主机白名单（支持 `*.example.com` 通配子域名）
+    /// 未声明时 fetch 可访问任意公网主机
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_hosts: Option<Vec<String>>,
     /// 刷新间隔 (毫秒)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_interval_ms: Option<u64>,
-    /// 订阅事件 (完整事件名，如 plugin:claude-usage:data_updated)
+    /// 每分钟最多发起的 fetch 请求数（未声明时使用默认预算）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_requests_per_minute: Option<u32>,
+    /// fetchData 瞬时失败的重试策略（未声明时使用默认策略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<RetryPolicy>,
+    /// 服务商计划维护窗口（维护期间不调度刷新、不计失败）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    /// 沙盒资源限制（未声明时使用默认限制）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourceLimits>,
+    /// 订阅事件 (完整事件名或通配符模式，如 plugin:*:data_updated，可附带负载过滤器)
     #[serde(default)]
-    pub subscribed_events: Vec<String>,
+    pub subscribed_events: Vec<EventSubscription>,
     /// 配置 Schema (Phase 4.2)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config_schema: Option<serde_json::Value>,
     /// 暴露的方法列表 (Phase 4.3)
     #[serde(default)]
     pub exposed_methods: Vec<String>,
+    /// custom 数据的 JSON Schema (Phase 4.5)，校验 fetchData 返回的完整对象
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_schema: Option<serde_json::Value>,
+    /// 是否接受外部推送数据 (Phase 4.6)，经本地推送端点写入
+    #[serde(default)]
+    pub accepts_push: bool,
     /// 文件哈希 (签名验证用)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub files: Option<HashMap<String, String>>,
@@ -330,12 +411,196 @@ fn default_entry() -> String {
     "plugin.js".to_string()
 }
 
+/// fetchData 重试策略（manifest `retryPolicy`）
+///
+/// 仅网络错误和超时会重试，其余错误立即计入失败。`maxRetries: 0` 关闭重试。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryPolicy {
+    /// 最大重试次数（不含首次执行）
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// 首次重试前的退避时间 (ms)，之后按 2 倍递增
+    #[serde(default = "default_initial_delay_ms")]
+    pub initial_delay_ms: u64,
+    /// 退避时间上限 (ms)
+    #[serde(default = "default_max_delay_ms")]
+    pub max_delay_ms: u64,
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_initial_delay_ms() -> u64 {
+    500
+}
+
+fn default_max_delay_ms() -> u64 {
+    5_000
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: default_max_retries(),
+            initial_delay_ms: default_initial_delay_ms(),
+            max_delay_ms: default_max_delay_ms(),
+        }
+    }
+}
+
+/// 沙盒资源限制（manifest `resources`），不得超过管理员策略的上限
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLimits {
+    /// 内存上限 (MB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_limit_mb: Option<u32>,
+    /// 单次执行超时 (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+/// 单次执行超时下限 (ms)
+const MIN_SANDBOX_TIMEOUT_MS: u64 = 100;
+
+/// 已安装插件的信任级别：manifest 签名（根密钥或发布者密钥）校验通过为 signed，否则为 installed（Phase 5A.10）
+fn installed_trust_level(manifest_content: &str, verifier: &SignatureVerifier) -> TrustLevel {
+    let signed = serde_json::from_str::<serde_json::Value>(manifest_content)
+        .is_ok_and(|raw| verifier.verify_manifest(&raw).is_ok());
+    if signed {
+        TrustLevel::Signed
+    } else {
+        TrustLevel::Installed
+    }
+}
+
+/// 插件可声明的最大重试次数
+const MAX_FETCH_RETRIES: u32 = 5;
+
+/// 退避时间范围 (ms)
+const MIN_FETCH_RETRY_DELAY_MS: u64 = 100;
+const MAX_FETCH_RETRY_DELAY_MS: u64 = 60_000;
+
+impl RetryPolicy {
+    /// 转换为宿主重试执行器配置（超出范围的值被截断）
+    pub fn to_retry_config(self) -> RetryConfig {
+        let initial_delay_ms = self
+            .initial_delay_ms
+            .clamp(MIN_FETCH_RETRY_DELAY_MS, MAX_FETCH_RETRY_DELAY_MS);
+        let max_delay_ms = self.max_delay_ms.clamp(initial_delay_ms, MAX_FETCH_RETRY_DELAY_MS);
+        RetryConfig {
+            max_retries: self.max_retries.min(MAX_FETCH_RETRIES),
+            initial_delay: Duration::from_millis(initial_delay_ms),
+            max_delay: Duration::from_millis(max_delay_ms),
+            multiplier: 2.0,
+            ..Default::default()
+        }
+    }
+}
+
+/// fetchData 的执行来源
+enum FetchSource {
+    /// 插件入口模块（沙盒执行）
+    Sandbox(PluginModule),
+    /// 内置数据源（原生实现）
+    Builtin(BuiltinProvider),
+}
+
+impl FetchSource {
+    fn of(plugin: &PluginInstance) -> Result<Self, LifecycleError> {
+        match plugin.builtin {
+            Some(provider) => Ok(FetchSource::Builtin(provider)),
+            None => plugin.entry_module().map(FetchSource::Sandbox),
+        }
+    }
+}
+
+/// 单次 fetchData 执行失败（供 RetryExecutor 判断是否重试）
+struct FetchAttemptError {
+    message: String,
+}
+
+impl RetryableError for FetchAttemptError {
+    /// 只重试瞬时失败：网络错误和超时
+    fn is_retryable(&self) -> bool {
+        matches!(
+            PluginErrorType::classify(&self.message),
+            PluginErrorType::NetworkError | PluginErrorType::Timeout
+        )
+    }
+
+    fn error_message(&self) -> String {
+        self.message.clone()
+    }
+}
+
 impl PluginManifest {
     /// 从文件加载
     pub fn load_from_file(path: &Path) -> Result<Self, LifecycleError> {
         let content = std::fs::read_to_string(path)?;
-        serde_json::from_str(&content)
-            .map_err(|e| LifecycleError::ManifestParse(e.to_string()))
+        Self::parse(&content)
+    }
+
+    /// 解析 manifest.json 内容并校验维护窗口
+    ///
+    /// 先协商 apiVersion，旧版本格式迁移到当前格式后再反序列化
+    pub fn parse(content: &str) -> Result<Self, LifecycleError> {
+        let mut value: serde_json::Value = serde_json::from_str(content)
+            .map_err(|e| LifecycleError::ManifestParse(e.to_string()))?;
+        negotiate(&mut value).map_err(LifecycleError::IncompatibleApiVersion)?;
+        let manifest: Self = serde_json::from_value(value)
+            .map_err(|e| LifecycleError::ManifestParse(e.to_string()))?;
+        for window in &manifest.maintenance_windows {
+            window.validate().map_err(LifecycleError::ManifestParse)?;
+        }
+        if let Some(resources) = manifest.resources {
+            if resources.memory_limit_mb == Some(0) {
+                return Err(LifecycleError::ManifestParse("resources.memoryLimitMb 必须大于 0".to_string()));
+            }
+            if resources.timeout_ms.is_some_and(|t| t < MIN_SANDBOX_TIMEOUT_MS) {
+                return Err(LifecycleError::ManifestParse(format!(
+                    "resources.timeoutMs 不能小于 {}",
+                    MIN_SANDBOX_TIMEOUT_MS
+                )));
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// 按 `resources` 声明生成沙盒配置
+    ///
+    /// 声明值超出上限时返回错误；未声明的项使用默认值（默认值高于上限时取上限）
+    pub fn sandbox_config(&self, caps: &SandboxCaps) -> Result<SandboxConfig, LifecycleError> {
+        let resources = self.resources.unwrap_or_default();
+        let max_memory = caps.memory_limit_mb as usize * 1024 * 1024;
+        let memory_limit = match resources.memory_limit_mb {
+            Some(mb) if mb > caps.memory_limit_mb => {
+                return Err(LifecycleError::ResourceLimitExceeded(format!(
+                    "memoryLimitMb {} 超过上限 {}",
+                    mb, caps.memory_limit_mb
+                )));
+            }
+            Some(mb) => mb as usize * 1024 * 1024,
+            None => DEFAULT_MEMORY_LIMIT.min(max_memory),
+        };
+        let max_timeout = Duration::from_millis(caps.timeout_ms);
+        let execution_timeout = match resources.timeout_ms {
+            Some(ms) if ms > caps.timeout_ms => {
+                return Err(LifecycleError::ResourceLimitExceeded(format!(
+                    "timeoutMs {} 超过上限 {}",
+                    ms, caps.timeout_ms
+                )));
+            }
+            Some(ms) => Duration::from_millis(ms),
+            None => DEFAULT_EXECUTION_TIMEOUT.min(max_timeout),
+        };
+        Ok(SandboxConfig {
+            memory_limit,
+            execution_timeout,
+            ..SandboxConfig::default()
+        })
     }
 
     /// 获取插件类型枚举
@@ -359,10 +624,19 @@ impl PluginManifest {
         })
     }
 
+    /// 每分钟请求预算（限制在 1..=MAX_REQUESTS_PER_MINUTE）
+    pub fn request_budget_per_minute(&self) -> u32 {
+        self.max_requests_per_minute
+            .unwrap_or(DEFAULT_MAX_REQUESTS_PER_MINUTE)
+            .clamp(1, MAX_REQUESTS_PER_MINUTE)
+    }
+
     /// 转换为 PluginInfo
     pub fn to_plugin_info(&self, enabled: bool, healthy: bool) -> PluginInfo {
         PluginInfo {
             id: self.id.clone(),
+            instance_id: self.id.clone(),
+            account_label: None,
             name: self.name.clone(),
             version: self.version.clone(),
             plugin_type: self.get_plugin_type(),
@@ -373,6 +647,86 @@ impl PluginManifest {
             description: self.description.clone(),
             icon: self.icon.clone(),
             config_schema: self.config_schema.clone(),
+            possibly_defunct: false,
+            defunct_since: None,
+            quarantined: false,
+            trust_level: TrustLevel::Installed,
+            restricted_permissions: Vec::new(),
+            accepts_push: self.accepts_push,
+            maintenance_until: None,
+            auto_disabled_at: None,
+        }
+    }
+}
+
+// ============================================================================
+// 事件投递 (Phase 4.1)
+// ============================================================================
+
+/// 事件投递通道容量
+const EVENT_DELIVERY_CAPACITY: usize = 100;
+
+/// 事件投递：EventBus 分发给订阅插件的事件，由宿主在插件沙盒中执行 onEvent
+///
+/// EventBus 中注册的处理器只负责转发并等待 `reply`，
+/// 执行由 [`PluginManager::deliver_event`] 完成（超时与 EventBus 处理超时一致）
+pub struct EventDelivery {
+    /// 订阅者插件 ID
+    pub plugin_id: String,
+    /// 完整事件名称（如 plugin:claude-usage:data_updated）
+    pub event: String,
+    /// 事件数据
+    pub data: serde_json::Value,
+    /// 执行结果回传
+    pub reply: oneshot::Sender<Result<(), String>>,
+}
+
+/// 数据更新广播通道容量（宿主消费慢于刷新时丢弃最新的更新，窗口下次刷新时补齐）
+const DATA_UPDATE_CAPACITY: usize = 100;
+
+/// 健康状态变化通道容量（队列满时丢弃，托盘在下一次变化时同步）
+const HEALTH_CHANGE_CAPACITY: usize = 100;
+
+/// 看门狗自动禁用通道容量
+const WATCHDOG_TRIP_CAPACITY: usize = 32;
+
+/// 数据更新广播：插件缓存数据更新后交给宿主推送到所有窗口
+#[derive(Debug, Clone)]
+pub struct DataUpdate {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 更新后的数据快照
+    pub data: PluginData,
+}
+
+// ============================================================================
+// 生命周期钩子
+// ============================================================================
+
+/// onLoad/onUnload 钩子的执行超时
+const LIFECYCLE_HOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 关闭时等待事件队列清空的超时
+const EVENT_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 等待事件队列清空时的检查间隔
+const EVENT_FLUSH_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// 插件生命周期钩子
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleHook {
+    /// 启用插件后调用
+    OnLoad,
+    /// 禁用、卸载插件或应用退出前调用
+    OnUnload,
+}
+
+impl LifecycleHook {
+    /// 插件导出的函数名
+    pub fn export_name(self) -> &'static str {
+        match self {
+            LifecycleHook::OnLoad => "onLoad",
+            LifecycleHook::OnUnload => "onUnload",
         }
     }
 }
@@ -486,6 +840,108 @@ pub enum PluginState {
     Error,
 }
 
+/// 持续失效判定阈值：连续 N 天 404/410 或 NXDOMAIN
+pub const DEFUNCT_THRESHOLD_DAYS: i64 = 3;
+
+/// 数据源失效时的恢复建议
+const DEFUNCT_SUGGESTED_ACTION: &str = "数据源可能已下线，请检查插件更新或移除该插件";
+
+/// 网络离线时的提示（Phase 3.5）
+const OFFLINE_SUGGESTED_ACTION: &str = "网络离线，数据暂停刷新，恢复连接后自动刷新";
+
+/// 单个插件最多添加的账户数（不含默认账户）
+const MAX_PLUGIN_ACCOUNTS: usize = 10;
+
+/// 账户名称最大长度（字符）
+const MAX_ACCOUNT_LABEL_LEN: usize = 64;
+
+/// 隐私报告读取的审计事件上限（审计日志本身有 1MB 上限）
+const PRIVACY_REPORT_AUDIT_LIMIT: usize = 5000;
+
+/// fetchData 结果缓存条目上限
+const FETCH_CACHE_CAPACITY: u64 = 256;
+
+/// 未声明刷新间隔时的 fetchData 缓存有效期（合并短时间内的重复刷新）
+const DEFAULT_FETCH_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// fetchData 缓存有效期下限/上限
+const MIN_FETCH_CACHE_TTL: Duration = Duration::from_secs(5);
+const MAX_FETCH_CACHE_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// fetchData 缓存有效期：刷新间隔的一半，保证定时刷新总能拿到新数据
+fn fetch_cache_ttl(refresh_interval_ms: Option<u64>) -> Duration {
+    refresh_interval_ms
+        .map(|ms| (Duration::from_millis(ms) / 2).clamp(MIN_FETCH_CACHE_TTL, MAX_FETCH_CACHE_TTL))
+        .unwrap_or(DEFAULT_FETCH_CACHE_TTL)
+}
+
+/// 将 validateConfig 的返回值转换为 ValidationResult
+///
+/// 支持 `boolean` 或 `{ valid, message?, fieldErrors? }`，
+/// fieldErrors 可以是 `{ 字段: 消息 }` 或 `[{ field, message }]`；存在字段错误时视为无效
+fn validation_result_from_hook(value: &serde_json::Value) -> ValidationResult {
+    if let Some(valid) = value.as_bool() {
+        return ValidationResult {
+            valid,
+            message: None,
+            field_errors: None,
+        };
+    }
+
+    let mut field_errors = HashMap::new();
+    match value.get("fieldErrors") {
+        Some(serde_json::Value::Object(errors)) => {
+            for (field, message) in errors {
+                let message = message.as_str().map(str::to_string).unwrap_or_else(|| message.to_string());
+                field_errors.insert(field.clone(), message);
+            }
+        }
+        Some(serde_json::Value::Array(errors)) => {
+            for error in errors {
+                if let (Some(field), Some(message)) = (
+                    error.get("field").and_then(|v| v.as_str()),
+                    error.get("message").and_then(|v| v.as_str()),
+                ) {
+                    field_errors.insert(field.to_string(), message.to_string());
+                }
+            }
+        }
+        _ => {}
+    }
+
+    let message = value.get("message").and_then(|v| v.as_str()).map(str::to_string);
+    let (valid, message) = match value.get("valid").and_then(|v| v.as_bool()) {
+        Some(valid) => (valid && field_errors.is_empty(), message),
+        None => (
+            false,
+            message.or_else(|| Some("validateConfig 返回值缺少 valid 字段".to_string())),
+        ),
+    };
+    ValidationResult {
+        valid,
+        message,
+        field_errors: (!field_errors.is_empty()).then_some(field_errors),
+    }
+}
+
+/// 未声明 maxRequestsPerMinute 时的每分钟请求预算
+const DEFAULT_MAX_REQUESTS_PER_MINUTE: u32 = 60;
+
+/// 插件可声明的每分钟请求预算上限
+const MAX_REQUESTS_PER_MINUTE: u32 = 600;
+
+/// 按信任状态过滤权限（受限时只保留低风险权限）
+fn grant_permissions(permissions: &[String], restricted: bool) -> Vec<String> {
+    if !restricted {
+        return permissions.to_vec();
+    }
+    permissions
+        .iter()
+        .filter(|p| is_dev_default_permission(p))
+        .cloned()
+        .collect()
+}
+
 /// 插件实例
 pub struct PluginInstance {
     /// 插件 ID
@@ -502,12 +958,14 @@ pub struct PluginInstance {
     pub resources: ResourceRegistry,
     /// 最后错误
     pub last_error: Option<String>,
+    /// 最后错误类型（用于生成恢复建议）
+    pub last_error_type: Option<PluginErrorType>,
     /// 插件配置
     pub config: HashMap<String, serde_json::Value>,
     /// 缓存的插件数据
     pub cached_data: Option<PluginData>,
-    /// 最后成功时间
-    pub last_success: Option<Instant>,
+    /// 最后成功时间（墙钟 + 单调时钟锚点）
+    pub last_success: Option<RecordedAt>,
     /// 错误计数（累计）
     pub error_count: u32,
     /// 成功请求数（累计）
@@ -520,6 +978,34 @@ pub struct PluginInstance {
     pub consecutive_failures: u32,
     /// 总调用次数（Phase 6）
     pub total_calls: u64,
+    /// fetchData 结果缓存命中次数
+    pub cache_hits: u64,
+    /// fetchData 结果缓存未命中次数（含强制刷新）
+    pub cache_misses: u64,
+    /// 首次出现"服务已不存在"错误的时间（期间无成功调用）
+    pub gone_since: Option<DateTime<Utc>>,
+    /// 数据源可能已失效（停止调度）
+    pub possibly_defunct: bool,
+    /// 完整性校验失败被隔离（禁止启用，直到重新校验通过）
+    pub quarantined: bool,
+    /// 信任级别
+    pub trust_level: TrustLevel,
+    /// 开发者插件已被用户显式信任
+    pub dev_trusted: bool,
+    /// 沙盒配置（按 manifest `resources` 和管理员上限生成）
+    pub sandbox_config: SandboxConfig,
+    /// 内置数据源（Phase 7.13，没有 JS 入口，fetchData 由宿主原生实现）
+    pub builtin: Option<BuiltinProvider>,
+    /// 账户名称（Phase 7.14，同一插件的其他账户实例；默认账户为 None）
+    pub account_label: Option<String>,
+    /// 最近一次发布的健康状态（Phase 6.15，变化时发布 health_changed）
+    pub health_status: HealthStatus,
+    /// 健康状态最近一次变化的时间
+    pub health_changed_at: Option<DateTime<Utc>>,
+    /// 连续执行超时/内存超限次数（Phase 6.17，看门狗）
+    pub consecutive_runaways: u32,
+    /// 被看门狗自动禁用的时间（重新启用时清除）
+    pub auto_disabled_at: Option<DateTime<Utc>>,
 }
 
 impl PluginInstance {
@@ -533,6 +1019,7 @@ impl PluginInstance {
             enabled: false,
             resources: ResourceRegistry::new(),
             last_error: None,
+            last_error_type: None,
             config: HashMap::new(),
             cached_data: None,
             last_success: None,
@@ -542,9 +1029,76 @@ impl PluginInstance {
             sliding_window: SlidingWindow::with_default_size(),
             consecutive_failures: 0,
             total_calls: 0,
+            cache_hits: 0,
+            cache_misses: 0,
+            gone_since: None,
+            possibly_defunct: false,
+            quarantined: false,
+            trust_level: TrustLevel::Installed,
+            dev_trusted: false,
+            sandbox_config: SandboxConfig::default(),
+            builtin: None,
+            account_label: None,
+            health_status: HealthStatus::Healthy,
+            health_changed_at: None,
+            consecutive_runaways: 0,
+            auto_disabled_at: None,
         }
     }
 
+    /// 创建内置数据源实例（没有插件目录，默认禁用）
+    pub fn builtin(provider: BuiltinProvider) -> Self {
+        let mut instance = Self::new(PathBuf::new(), provider.manifest());
+        instance.trust_level = TrustLevel::Builtin;
+        instance.builtin = Some(provider);
+        instance
+    }
+
+    /// 创建同一插件的另一个账户实例（共享插件目录和 manifest，配置、密钥和数据独立）
+    pub fn account(base: &PluginInstance, instance_id: String, label: String) -> Self {
+        let mut instance = Self::new(base.path.clone(), base.manifest.clone());
+        instance.id = instance_id;
+        instance.account_label = Some(label);
+        instance.follow(base);
+        instance
+    }
+
+    /// 账户实例同步主插件的 manifest 和信任状态
+    /// 设置信任级别，并将沙盒配置收紧到该级别可使用的上限之内
+    pub fn apply_trust(&mut self, trust_level: TrustLevel, caps: &SandboxCaps) {
+        self.trust_level = trust_level;
+        self.sandbox_config = trust_level.limits().clamp_sandbox(self.sandbox_config.clone(), caps);
+    }
+
+    fn follow(&mut self, base: &PluginInstance) {
+        self.path = base.path.clone();
+        self.manifest = base.manifest.clone();
+        self.sandbox_config = base.sandbox_config.clone();
+        self.trust_level = base.trust_level;
+        self.dev_trusted = base.dev_trusted;
+        self.builtin = base.builtin;
+    }
+
+    /// 是否为主插件 `plugin_id` 添加的账户实例
+    fn is_account_of(&self, plugin_id: &str) -> bool {
+        self.account_label.is_some() && self.manifest.id == plugin_id
+    }
+
+    /// 正在进行的服务商维护
+    pub fn maintenance(&self, now: DateTime<Utc>) -> Option<ActiveMaintenance> {
+        active_maintenance(&self.manifest.maintenance_windows, now)
+    }
+
+    /// 权限是否受限（未受信任的开发者插件）
+    pub fn permissions_restricted(&self) -> bool {
+        self.trust_level == TrustLevel::Developer && !self.dev_trusted
+    }
+
+    /// 实际授予的权限
+    pub fn granted_permissions(&self) -> Vec<String> {
+        grant_permissions(&self.manifest.permissions, self.permissions_restricted())
+    }
+
     /// 读取入口文件内容（安全版本，无 TOCTOU 窗口）
     ///
     /// 使用 openat 链式打开并直接读取文件内容，完全消除 TOCTOU 窗口。
@@ -559,6 +1113,9 @@ impl PluginInstance {
     /// - `Ok(String)`: 入口文件内容
     /// - `Err(LifecycleError)`: 路径校验或读取失败
     pub fn read_entry_content(&self) -> Result<String, LifecycleError> {
+        if self.builtin.is_some() {
+            return Err(LifecycleError::PluginLoad(format!("内置插件没有入口文件: {}", self.id)));
+        }
         let entry = &self.manifest.entry;
 
         // 1. 检查是否为绝对路径
@@ -584,6 +1141,12 @@ impl PluginInstance {
         Ok(content)
     }
 
+    /// 读取入口文件并构建 ES Module（相对导入从插件目录加载）
+    pub fn entry_module(&self) -> Result<PluginModule, LifecycleError> {
+        let source = self.read_entry_content()?;
+        Ok(PluginModule::new(self.path.clone(), &self.manifest.entry, source))
+    }
+
     /// 获取入口文件路径（存在 TOCTOU 窗口，仅用于兼容）
     ///
     /// **警告**：返回路径后再打开文件存在 TOCTOU 窗口。
@@ -668,10 +1231,30 @@ impl PluginInstance {
 
     /// 转换为 PluginInfo
     pub fn to_info(&self) -> PluginInfo {
-        self.manifest.to_plugin_info(
+        let mut info = self.manifest.to_plugin_info(
             self.enabled,
             self.state == PluginState::Running,
-        )
+        );
+        if self.possibly_defunct {
+            info.possibly_defunct = true;
+            info.defunct_since = self.gone_since.map(|t| t.to_rfc3339());
+        }
+        info.instance_id = self.id.clone();
+        info.account_label = self.account_label.clone();
+        info.quarantined = self.quarantined;
+        info.trust_level = self.trust_level;
+        info.maintenance_until = self.maintenance(Utc::now()).map(|m| format_rfc3339(m.until));
+        info.auto_disabled_at = self.auto_disabled_at.map(format_rfc3339);
+        if self.permissions_restricted() {
+            info.restricted_permissions = self
+                .manifest
+                .permissions
+                .iter()
+                .filter(|p| !is_dev_default_permission(p))
+                .cloned()
+                .collect();
+        }
+        info
     }
 
     /// 转换为 PluginHealth（基于滑动窗口统计）
@@ -706,19 +1289,15 @@ impl PluginInstance {
             HealthStatus::Healthy
         };
 
-        // 格式化最后成功时间
-        let last_success = self.last_success.map(|t| {
-            let elapsed = t.elapsed();
-            chrono::Utc::now()
-                .checked_sub_signed(chrono::Duration::from_std(elapsed).unwrap_or_default())
-                .map(|dt| dt.to_rfc3339())
-                .unwrap_or_default()
-        });
+        // 最后成功时间：序列化记录时的墙钟时间，经过时长按单调时钟计算（睡眠后不漂移）
+        let last_success = self.last_success.map(|t| t.to_rfc3339());
+        let last_success_age_ms = self.last_success.map(|t| t.age().as_millis() as u64);
 
         PluginHealth {
             plugin_id: self.id.clone(),
             status,
             last_success,
+            last_success_age_ms,
             last_error: self.last_error.clone(),
             error_count: self.error_count,
             avg_latency_ms,
@@ -726,7 +1305,62 @@ impl PluginInstance {
             success_rate,
             total_calls: self.total_calls,
             consecutive_failures: self.consecutive_failures,
+            cache_hits: self.cache_hits,
+            cache_misses: self.cache_misses,
+            throttled_requests: 0,
+            blocked_hosts: Vec::new(),
+            avg_cpu_time_ms: self.sliding_window.avg_cpu_time_ms(),
+            peak_memory_bytes: self.sliding_window.peak_memory_bytes(),
+            suggested_action: if self.possibly_defunct {
+                Some(DEFUNCT_SUGGESTED_ACTION.to_string())
+            } else if let Some(maintenance) = self.maintenance(Utc::now()) {
+                Some(format!(
+                    "服务商计划维护中，{} 后恢复刷新{}",
+                    format_rfc3339(maintenance.until),
+                    maintenance.reason.map(|r| format!("（{}）", r)).unwrap_or_default()
+                ))
+            } else {
+                self.last_error_type
+                    .and_then(|t| t.suggested_action())
+                    .map(String::from)
+            },
+            // 静默状态由 AlertManager 标注
+            alerts_muted: false,
+            alerts_snoozed_until: None,
+        }
+    }
+
+    /// 按网络状态生成健康信息（Phase 3.5）
+    ///
+    /// 离线期间已启用插件的数据无法刷新，状态标记为 Stale 而非 Unhealthy。
+    pub fn to_network_health(&self, offline: bool) -> PluginHealth {
+        let mut health = self.to_health();
+        if offline && self.enabled {
+            health.status = HealthStatus::Stale;
+            health.suggested_action = Some(OFFLINE_SUGGESTED_ACTION.to_string());
+        }
+        health
+    }
+
+    /// 检测健康状态变化（Phase 6.15）
+    ///
+    /// 与最近一次发布的状态比较，变化时记录新状态和变化时间并返回状态转换。
+    pub fn observe_health(&mut self, now: DateTime<Utc>, offline: bool) -> Option<HealthTransition> {
+        let health = self.to_network_health(offline);
+        if health.status == self.health_status {
+            return None;
         }
+        let transition = HealthTransition {
+            plugin_id: self.id.clone(),
+            previous: self.health_status,
+            current: health.status,
+            changed_at: format_rfc3339(now),
+            previous_since: self.health_changed_at.map(format_rfc3339),
+            health,
+        };
+        self.health_status = transition.current;
+        self.health_changed_at = Some(now);
+        Some(transition)
     }
 
     /// 记录成功请求
@@ -736,8 +1370,9 @@ impl PluginInstance {
         // 累计统计
         self.success_count += 1;
         self.total_latency_ms += latency_ms;
-        self.last_success = Some(Instant::now());
+        self.last_success = Some(RecordedAt::now());
         self.last_error = None;
+        self.last_error_type = None;
         self.total_calls += 1;
 
         // 滑动窗口统计（Phase 6）
@@ -745,15 +1380,24 @@ impl PluginInstance {
 
         // 重置连续失败计数（Phase 6）
         self.consecutive_failures = 0;
+        self.consecutive_runaways = 0;
+
+        // 服务可用，清除失效标记
+        self.gone_since = None;
+        self.possibly_defunct = false;
     }
 
     /// 记录失败请求
     ///
-    /// 更新累计统计、滑动窗口统计和连续失败计数
+    /// 更新累计统计、滑动窗口统计和连续失败计数。
+    /// 错误类型按原始错误分类，last_error 只保存提取后的错误摘要。
     pub fn record_failure(&mut self, error: String) {
+        self.track_defunct(&error, Utc::now());
+
         // 累计统计
         self.error_count += 1;
-        self.last_error = Some(error);
+        self.last_error_type = Some(PluginErrorType::classify(&error));
+        self.last_error = Some(summarize_error(&error));
         self.total_calls += 1;
 
         // 滑动窗口统计（Phase 6）
@@ -762,17 +1406,30 @@ impl PluginInstance {
 
         // 增加连续失败计数（Phase 6）
         self.consecutive_failures += 1;
+        self.track_runaway(&error);
+    }
+
+    /// 更新连续失控次数（只有连续的执行超时/内存超限才累计，Phase 6.17）
+    fn track_runaway(&mut self, error: &str) {
+        if is_runaway_error(error) {
+            self.consecutive_runaways += 1;
+        } else {
+            self.consecutive_runaways = 0;
+        }
     }
 
     /// 记录失败请求（带延迟）
     ///
     /// Phase 6 新增：允许记录失败请求的延迟
     pub fn record_failure_with_latency(&mut self, error: String, latency_ms: f64) {
+        self.track_defunct(&error, Utc::now());
         self.error_count += 1;
-        self.last_error = Some(error);
+        self.last_error_type = Some(PluginErrorType::classify(&error));
+        self.last_error = Some(summarize_error(&error));
         self.total_calls += 1;
         self.sliding_window.record_failure(latency_ms);
         self.consecutive_failures += 1;
+        self.track_runaway(&error);
     }
 
     /// 重置健康统计
@@ -781,9 +1438,34 @@ impl PluginInstance {
         self.success_count = 0;
         self.total_latency_ms = 0.0;
         self.last_error = None;
+        self.last_error_type = None;
         self.sliding_window.clear();
         self.consecutive_failures = 0;
         self.total_calls = 0;
+        self.cache_hits = 0;
+        self.cache_misses = 0;
+        self.gone_since = None;
+        self.possibly_defunct = false;
+    }
+
+    /// 跟踪数据源失效状态
+    ///
+    /// 404/410 或 NXDOMAIN 持续 `DEFUNCT_THRESHOLD_DAYS` 天（期间无成功调用）
+    /// 即标记为可能失效。其他类型的失败不影响计时。
+    fn track_defunct(&mut self, error: &str, now: DateTime<Utc>) {
+        if !is_provider_gone(error) {
+            return;
+        }
+
+        let since = *self.gone_since.get_or_insert(now);
+        if !self.possibly_defunct && now - since >= chrono::Duration::days(DEFUNCT_THRESHOLD_DAYS) {
+            self.possibly_defunct = true;
+            log::warn!(
+                "[{}] 数据源自 {} 起持续不可用，标记为可能失效并停止调度",
+                self.id,
+                since.to_rfc3339()
+            );
+        }
     }
 }
 
@@ -791,6 +1473,15 @@ impl PluginInstance {
 // 插件发现器
 // ============================================================================
 
+/// 插件发现结果
+#[derive(Debug, Default)]
+pub struct Discovered {
+    /// 可加载的插件
+    pub plugins: Vec<(PathBuf, PluginManifest)>,
+    /// API 版本不兼容、未加载的插件（插件目录, 不兼容报告）
+    pub incompatible: Vec<(PathBuf, IncompatiblePlugin)>,
+}
+
 /// 插件发现器
 pub struct PluginDiscovery {
     /// 插件目录
@@ -878,10 +1569,10 @@ impl PluginDiscovery {
     /// 发现所有插件（异步版本）
     ///
     /// 使用 tokio::fs 进行异步文件操作，不阻塞 Tokio worker
-    pub async fn discover_async(&self) -> Result<Vec<(PathBuf, PluginManifest)>, LifecycleError> {
+    pub async fn discover_async(&self) -> Result<Discovered, LifecycleError> {
         self.ensure_dir_async().await?;
 
-        let mut plugins = Vec::new();
+        let mut discovered = Discovered::default();
 
         // 使用 tokio::fs::read_dir 进行异步目录遍历
         let mut entries = tokio::fs::read_dir(&self.plugins_dir)
@@ -910,10 +1601,14 @@ impl PluginDiscovery {
 
             // 异步加载清单
             match tokio::fs::read_to_string(&manifest_path).await {
-                Ok(content) => match serde_json::from_str::<PluginManifest>(&content) {
+                Ok(content) => match PluginManifest::parse(&content) {
                     Ok(manifest) => {
                         log::info!("发现插件: {} v{}", manifest.name, manifest.version);
-                        plugins.push((path, manifest));
+                        discovered.plugins.push((path, manifest));
+                    }
+                    Err(LifecycleError::IncompatibleApiVersion(incompatible)) => {
+                        log::warn!("跳过 {:?}: {}", path, incompatible);
+                        discovered.incompatible.push((path, incompatible));
                     }
                     Err(e) => {
                         log::warn!("解析 {:?} 失败: {}", manifest_path, e);
@@ -925,7 +1620,7 @@ impl PluginDiscovery {
             }
         }
 
-        Ok(plugins)
+        Ok(discovered)
     }
 }
 
@@ -933,6 +1628,15 @@ impl PluginDiscovery {
 // 插件管理器
 // ============================================================================
 
+/// 孤立注册清理结果
+#[derive(Debug, Clone)]
+pub struct OrphanCleanup {
+    /// 已不存在的插件 ID
+    pub plugin_id: String,
+    /// 被回收的注册类型
+    pub reclaimed: Vec<&'static str>,
+}
+
 /// 插件管理器
 pub struct PluginManager {
     /// 发现器
@@ -960,6 +1664,68 @@ pub struct PluginManager {
     dispatcher_handle: RwLock<Option<tokio::task::JoinHandle<()>>>,
     /// 跨插件调用分发器 handle（用于 shutdown）
     call_dispatcher_handle: RwLock<Option<tokio::task::JoinHandle<()>>>,
+    /// 用户输入请求中介（context.requestInput）
+    input_broker: Arc<InputBroker>,
+    /// 持久定时器调度器（context.timers，Phase 2.2.9）
+    host_timers: Arc<HostTimerScheduler>,
+    /// 事件投递发送端（EventBus 处理器 -> onEvent 执行）
+    event_tx: mpsc::Sender<EventDelivery>,
+    /// 事件投递接收端（由宿主取出消费）
+    event_rx: std::sync::Mutex<Option<mpsc::Receiver<EventDelivery>>>,
+    /// 数据更新广播发送端（set_plugin_data -> 窗口推送）
+    data_update_tx: mpsc::Sender<DataUpdate>,
+    /// 数据更新广播接收端（由宿主取出消费）
+    data_update_rx: std::sync::Mutex<Option<mpsc::Receiver<DataUpdate>>>,
+    /// 健康状态变化发送端（Phase 6.15，状态变化 -> 事件与托盘状态）
+    health_change_tx: mpsc::Sender<HealthTransition>,
+    /// 健康状态变化接收端（由宿主取出消费）
+    health_change_rx: std::sync::Mutex<Option<mpsc::Receiver<HealthTransition>>>,
+    /// 看门狗策略（Phase 6.17，由应用设置同步）
+    watchdog: std::sync::Mutex<WatchdogPolicy>,
+    /// 插件请求使用的代理（Phase 4.5，由应用设置同步，None 表示直连）
+    proxy: std::sync::Mutex<Option<ProxyConfig>>,
+    /// 自定义根证书与证书固定（Phase 4.6，由应用设置同步）
+    tls: std::sync::Mutex<Option<Arc<TlsConfig>>>,
+    /// 网络是否离线（Phase 3.5，由网络监视器同步）
+    offline: std::sync::atomic::AtomicBool,
+    /// 看门狗自动禁用发送端（自动禁用 -> 告警与前端通知）
+    watchdog_tx: mpsc::Sender<WatchdogTrip>,
+    /// 看门狗自动禁用接收端（由宿主取出消费）
+    watchdog_rx: std::sync::Mutex<Option<mpsc::Receiver<WatchdogTrip>>>,
+    /// 安全审计日志（与插件目录同级的 audit.log）
+    audit_log: AuditLog,
+    /// 宿主网关配置（Phase 4.4）
+    gateways: RwLock<GatewayConfig>,
+    /// 网关配置文件路径
+    gateway_path: PathBuf,
+    /// 开发者模式设置（Phase 5A.8）
+    developer: RwLock<DeveloperSettings>,
+    /// 开发者模式设置文件路径
+    developer_path: PathBuf,
+    /// 插件网络访问统计（Phase 6.7，隐私报告）
+    network_stats: Arc<NetworkStats>,
+    /// 插件 console 日志（Phase 6.12，日志查看器）
+    plugin_logs: Arc<PluginLogStore>,
+    /// 使用率历史（Phase 6.9，弹窗趋势图）
+    usage_history: UsageHistory,
+    /// 价格表与 token 账本（Phase 6.14，费用估算）
+    cost_estimator: CostEstimator,
+    /// 插件启用状态与配置的持久化（Phase 2.3.8）
+    plugin_state: PluginStateStore,
+    /// secret 配置字段的存储后端（macOS Keychain）
+    secret_store: Arc<dyn SecretStore>,
+    /// fetchData 结果缓存（Phase 3.3，TTL 由刷新间隔推导）
+    fetch_cache: CacheLayer,
+    /// 插件 fetch 请求预算限流器
+    rate_limiter: Arc<RateLimiter>,
+    /// 沙盒运行时池（复用预热的 QuickJS 运行时）
+    sandbox_pool: SandboxPool,
+    /// 管理员策略（机器级 policy.json，只读）
+    admin_policy: Arc<PolicyManager>,
+    /// 发布者密钥信任库（Phase 5A.12）
+    publisher_keys: PublisherKeyStore,
+    /// 最近一次发现时 API 版本不兼容、未加载的插件（Phase 2.3.10）
+    incompatible: std::sync::Mutex<BTreeMap<PathBuf, IncompatiblePlugin>>,
 }
 
 impl PluginManager {
@@ -967,30 +1733,135 @@ impl PluginManager {
     pub fn new(discovery: PluginDiscovery) -> Self {
         // 创建跨插件调用通道
         let (call_tx, call_rx) = mpsc::channel(100);
+        let (event_tx, event_rx) = mpsc::channel(EVENT_DELIVERY_CAPACITY);
+        let (data_update_tx, data_update_rx) = mpsc::channel(DATA_UPDATE_CAPACITY);
+        let (health_change_tx, health_change_rx) = mpsc::channel(HEALTH_CHANGE_CAPACITY);
+        let (watchdog_tx, watchdog_rx) = mpsc::channel(WATCHDOG_TRIP_CAPACITY);
 
         // Phase 4 组件
         let method_registry = Arc::new(MethodRegistry::new());
 
+        let plugins_dir = discovery.plugins_dir();
+        let audit_log = AuditLog::new(plugins_dir.parent().unwrap_or(plugins_dir).join("audit.log"));
+
+        let gateway_path = GatewayConfig::file_path(plugins_dir);
+        let gateways = GatewayConfig::load(&gateway_path).unwrap_or_else(|e| {
+            log::warn!("加载网关配置失败，使用默认配置: {}", e);
+            GatewayConfig::default()
+        });
+
+        let developer_path = DeveloperSettings::file_path(plugins_dir);
+        let developer = DeveloperSettings::load(&developer_path);
+
+        let event_bus = Arc::new(EventBus::new_default());
+        let config_manager = ConfigManager::with_event_bus(event_bus.clone())
+            .with_usage_alerts_file(UsageAlertSettings::file_path(plugins_dir));
+
+        let network_stats = NetworkStats::load(NetworkStats::file_path(plugins_dir));
+        let usage_history = UsageHistory::load(UsageHistory::file_path(plugins_dir));
+        let cost_estimator = CostEstimator::load(plugins_dir);
+        let plugin_state = PluginStateStore::load(PluginStateStore::file_path(plugins_dir));
+        let publisher_keys = PublisherKeyStore::load(PublisherKeyStore::file_path(plugins_dir));
+
         Self {
             discovery,
             plugins: RwLock::new(HashMap::new()),
             // Phase 4 组件
-            event_bus: Arc::new(EventBus::new_default()),
-            config_manager: Arc::new(ConfigManager::new()),
+            event_bus,
+            config_manager: Arc::new(config_manager),
             permission_checker: Arc::new(PermissionChecker::new(method_registry.clone())),
             method_registry,
             call_tx,
             call_rx: Arc::new(RwLock::new(Some(call_rx))),
             dispatcher_handle: RwLock::new(None),
             call_dispatcher_handle: RwLock::new(None),
+            input_broker: Arc::new(InputBroker::new()),
+            host_timers: Arc::new(HostTimerScheduler::new()),
+            event_tx,
+            event_rx: std::sync::Mutex::new(Some(event_rx)),
+            data_update_tx,
+            data_update_rx: std::sync::Mutex::new(Some(data_update_rx)),
+            health_change_tx,
+            health_change_rx: std::sync::Mutex::new(Some(health_change_rx)),
+            watchdog: std::sync::Mutex::new(WatchdogPolicy::default()),
+            proxy: std::sync::Mutex::new(None),
+            tls: std::sync::Mutex::new(None),
+            offline: std::sync::atomic::AtomicBool::new(false),
+            watchdog_tx,
+            watchdog_rx: std::sync::Mutex::new(Some(watchdog_rx)),
+            audit_log,
+            gateways: RwLock::new(gateways),
+            gateway_path,
+            developer: RwLock::new(developer),
+            developer_path,
+            network_stats: Arc::new(network_stats),
+            plugin_logs: Arc::new(PluginLogStore::new()),
+            usage_history,
+            cost_estimator,
+            plugin_state,
+            secret_store: Arc::new(KeychainStore),
+            fetch_cache: CacheLayer::new(CacheConfig {
+                max_capacity: FETCH_CACHE_CAPACITY,
+                time_to_live: MAX_FETCH_CACHE_TTL,
+                time_to_idle: MAX_FETCH_CACHE_TTL,
+                enable_stats: true,
+            }),
+            rate_limiter: Arc::new(RateLimiter::new(RateLimitConfig::default())),
+            sandbox_pool: SandboxPool::default(),
+            admin_policy: Arc::new(PolicyManager::default()),
+            publisher_keys,
+            incompatible: std::sync::Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// 管理员策略
+    pub fn admin_policy(&self) -> &PolicyManager {
+        &self.admin_policy
+    }
+
+    /// 使用指定的管理员策略（替换机器级策略文件）
+    pub fn with_admin_policy(mut self, policy: PolicyManager) -> Self {
+        self.admin_policy = Arc::new(policy);
+        self
+    }
+
+    /// 发布者密钥信任库
+    pub fn publisher_keys(&self) -> &PublisherKeyStore {
+        &self.publisher_keys
+    }
+
+    /// 最近一次发现时 API 版本不兼容、未加载的插件（含开发者插件）
+    pub fn incompatible_plugins(&self) -> Vec<IncompatiblePlugin> {
+        self.incompatible
+            .lock()
+            .map(|found| found.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// 替换某个目录下的不兼容插件记录
+    fn record_incompatible(&self, dir: &Path, found: Vec<(PathBuf, IncompatiblePlugin)>) {
+        if let Ok(mut incompatible) = self.incompatible.lock() {
+            incompatible.retain(|path, _| !path.starts_with(dir));
+            incompatible.extend(found);
         }
     }
 
+    /// 插件可声明的沙盒资源上限
+    fn sandbox_caps(&self) -> SandboxCaps {
+        self.admin_policy.policy().sandbox_caps()
+    }
+
     /// 使用默认配置创建
     pub fn with_defaults() -> Self {
         Self::new(PluginDiscovery::with_default_dir())
     }
 
+    /// 替换 secret 配置字段的存储后端
+    pub fn with_secret_store(mut self, store: Arc<dyn SecretStore>) -> Self {
+        self.secret_store = store;
+        self
+    }
+
     // ========================================================================
     // Phase 4: 组件访问器
     // ========================================================================
@@ -1025,49 +1896,226 @@ impl PluginManager {
         self.call_rx.write().await.take()
     }
 
-    /// 启动 EventBus 分发器
-    ///
-    /// 在 discover_and_load 之后调用，开始事件分发循环
-    /// 分发器会从队列中取出事件并分发给订阅者
-    pub async fn start_dispatcher(&self) {
-        let mut handle = self.dispatcher_handle.write().await;
-        if handle.is_some() {
-            log::warn!("EventBus 分发器已在运行");
-            return;
-        }
+    /// 获取用户输入请求中介
+    pub fn input_broker(&self) -> Arc<InputBroker> {
+        self.input_broker.clone()
+    }
 
-        let dispatcher_handle = self.event_bus.clone().spawn_dispatcher();
-        *handle = Some(dispatcher_handle);
-        log::info!("EventBus 分发器已启动");
+    /// 获取插件日志存储
+    pub fn plugin_logs(&self) -> Arc<PluginLogStore> {
+        self.plugin_logs.clone()
     }
 
-    /// 停止 EventBus 分发器
-    ///
-    /// 在应用关闭时调用，终止事件分发循环
-    pub async fn stop_dispatcher(&self) {
-        let mut handle = self.dispatcher_handle.write().await;
-        if let Some(h) = handle.take() {
-            h.abort();
-            log::info!("EventBus 分发器已停止");
-        }
+    /// 获取持久定时器调度器
+    pub fn host_timers(&self) -> Arc<HostTimerScheduler> {
+        self.host_timers.clone()
     }
 
-    /// 检查分发器是否正在运行
-    pub async fn is_dispatcher_running(&self) -> bool {
-        let handle = self.dispatcher_handle.read().await;
-        if let Some(h) = handle.as_ref() {
-            !h.is_finished()
-        } else {
-            false
-        }
+    /// 取出事件投递接收端（只能取出一次，由宿主逐个调用 deliver_event）
+    pub fn take_event_receiver(&self) -> Option<mpsc::Receiver<EventDelivery>> {
+        self.event_rx.lock().unwrap_or_else(|e| e.into_inner()).take()
     }
 
-    /// 启动跨插件调用分发器
-    ///
-    /// 消费 call_rx 中的调用请求，执行目标方法并回传结果
-    ///
-    /// 修复说明：
-    /// - 使用动态插件列表查询，避免启动时快照漂移问题
+    /// 取出数据更新广播接收端（只能取出一次，由宿主推送到所有窗口）
+    pub fn take_data_update_receiver(&self) -> Option<mpsc::Receiver<DataUpdate>> {
+        self.data_update_rx.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// 取出健康状态变化接收端（只能取出一次，由宿主发送事件并同步托盘状态）
+    pub fn take_health_change_receiver(&self) -> Option<mpsc::Receiver<HealthTransition>> {
+        self.health_change_rx.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// 取出看门狗自动禁用接收端（只能取出一次，由宿主触发告警并通知前端）
+    pub fn take_watchdog_receiver(&self) -> Option<mpsc::Receiver<WatchdogTrip>> {
+        self.watchdog_rx.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// 当前看门狗策略
+    pub fn watchdog_policy(&self) -> WatchdogPolicy {
+        *self.watchdog.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 更新看门狗策略（应用设置加载或修改时同步）
+    pub fn set_watchdog_policy(&self, policy: WatchdogPolicy) {
+        *self.watchdog.lock().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    /// 更新插件请求代理（应用设置加载或修改时同步）
+    pub fn set_proxy(&self, proxy: Option<ProxyConfig>) {
+        match &proxy {
+            Some(proxy) => log::warn!("插件请求代理: {}。{}", proxy.url(), DNS_WARNING),
+            None => log::info!("插件请求不使用代理"),
+        }
+        *self.proxy.lock().unwrap_or_else(|e| e.into_inner()) = proxy;
+    }
+
+    /// 更新自定义根证书与证书固定（应用设置加载或修改时同步）
+    pub fn set_tls(&self, tls: Option<TlsConfig>) {
+        match &tls {
+            Some(tls) if !tls.names().is_empty() => {
+                log::warn!("插件请求额外信任 {} 个自定义根证书: {:?}", tls.names().len(), tls.names())
+            }
+            _ => log::info!("插件请求只使用系统根证书"),
+        }
+        if let Some(tls) = tls.as_ref().filter(|tls| !tls.pinned_hosts().is_empty()) {
+            log::info!("已固定证书的主机: {:?}", tls.pinned_hosts());
+        }
+        *self.tls.lock().unwrap_or_else(|e| e.into_inner()) = tls.map(Arc::new);
+    }
+
+    /// 连续失控次数达到阈值时自动禁用插件（不调用 onUnload，避免再占用一次沙盒）
+    async fn enforce_watchdog(&self, plugin: &mut PluginInstance) {
+        if !plugin.enabled || !self.watchdog_policy().should_disable(plugin.consecutive_runaways) {
+            return;
+        }
+        let trip = WatchdogTrip {
+            plugin_id: plugin.id.clone(),
+            consecutive_runaways: plugin.consecutive_runaways,
+            last_error: plugin.last_error.clone().unwrap_or_default(),
+            disabled_at: Utc::now(),
+        };
+        plugin.auto_disabled_at = Some(trip.disabled_at);
+        let released = self.deactivate_plugin(plugin).await;
+        log::error!(
+            "[{}] 连续 {} 次执行超时或内存超限，看门狗已自动禁用（释放 {} 个资源）",
+            plugin.id,
+            trip.consecutive_runaways,
+            released
+        );
+        if let Err(e) = self.watchdog_tx.try_send(trip) {
+            log::warn!("[{}] 看门狗通知队列不可用: {}", plugin.id, e);
+        }
+    }
+
+    /// 网络是否离线
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// 同步网络离线状态（网络监视器状态切换时调用）
+    ///
+    /// 离线期间已启用插件的健康状态为 Stale，重复定时器暂停触发，网络错误不计入健康统计。
+    pub async fn set_offline(&self, offline: bool) {
+        if self.offline.swap(offline, std::sync::atomic::Ordering::Relaxed) == offline {
+            return;
+        }
+        let mut plugins = self.plugins.write().await;
+        for plugin in plugins.values_mut() {
+            self.publish_health_change(plugin);
+        }
+    }
+
+    /// 持久化数据源失效跟踪状态（重启后继续计时，不会因重启清零）
+    fn persist_defunct(&self, plugin: &PluginInstance) {
+        self.plugin_state
+            .set_defunct(&plugin.id, plugin.gone_since, plugin.possibly_defunct);
+    }
+
+    /// 检测插件健康状态变化，变化时发布到健康状态变化通道（队列满时丢弃）
+    fn publish_health_change(&self, plugin: &mut PluginInstance) {
+        if let Some(transition) = plugin.observe_health(Utc::now(), self.is_offline()) {
+            log::info!(
+                "[{}] 健康状态变化: {:?} -> {:?}",
+                transition.plugin_id,
+                transition.previous,
+                transition.current
+            );
+            if let Err(e) = self.health_change_tx.try_send(transition) {
+                log::debug!("[{}] 健康状态变化队列不可用: {}", plugin.id, e);
+            }
+        }
+    }
+
+    /// 所有已启用插件中最差的健康状态（没有已启用插件时为 None）
+    pub async fn worst_health(&self) -> Option<HealthStatus> {
+        let severity = |status: HealthStatus| match status {
+            HealthStatus::Healthy => 0,
+            HealthStatus::Stale => 1,
+            HealthStatus::Degraded => 2,
+            HealthStatus::Unhealthy => 3,
+        };
+        self.plugins
+            .read()
+            .await
+            .values()
+            .filter(|p| p.enabled)
+            .map(|p| p.health_status)
+            .max_by_key(|status| severity(*status))
+    }
+
+    /// 获取宿主网关配置
+    pub async fn gateway_config(&self) -> GatewayConfig {
+        self.gateways.read().await.clone()
+    }
+
+    /// 更新宿主网关配置（校验后持久化，下次执行插件时生效）
+    pub async fn set_gateway_config(&self, config: GatewayConfig) -> Result<(), GatewayError> {
+        config.validate()?;
+        config.save(&self.gateway_path)?;
+        *self.gateways.write().await = config;
+        log::info!("网关配置已更新");
+        Ok(())
+    }
+
+    /// 启动 EventBus 分发器
+    ///
+    /// 在 discover_and_load 之后调用，开始事件分发循环
+    /// 分发器会从队列中取出事件并分发给订阅者
+    pub async fn start_dispatcher(&self) {
+        let mut handle = self.dispatcher_handle.write().await;
+        if handle.is_some() {
+            log::warn!("EventBus 分发器已在运行");
+            return;
+        }
+
+        let dispatcher_handle = self.event_bus.clone().spawn_dispatcher();
+        *handle = Some(dispatcher_handle);
+        log::info!("EventBus 分发器已启动");
+    }
+
+    /// 停止 EventBus 分发器
+    ///
+    /// 在应用关闭时调用，终止事件分发循环
+    pub async fn stop_dispatcher(&self) {
+        let mut handle = self.dispatcher_handle.write().await;
+        if let Some(h) = handle.take() {
+            h.abort();
+            log::info!("EventBus 分发器已停止");
+        }
+    }
+
+    /// 等待 EventBus 队列清空，返回是否在超时前完成
+    ///
+    /// 分发器未运行时队列不会被消费，直接返回
+    pub async fn flush_events(&self, timeout: Duration) -> bool {
+        if !self.is_dispatcher_running().await {
+            return self.event_bus.pending_events() == 0;
+        }
+        let drained = async {
+            while self.event_bus.pending_events() > 0 {
+                tokio::time::sleep(EVENT_FLUSH_POLL_INTERVAL).await;
+            }
+        };
+        tokio::time::timeout(timeout, drained).await.is_ok()
+    }
+
+    /// 检查分发器是否正在运行
+    pub async fn is_dispatcher_running(&self) -> bool {
+        let handle = self.dispatcher_handle.read().await;
+        if let Some(h) = handle.as_ref() {
+            !h.is_finished()
+        } else {
+            false
+        }
+    }
+
+    /// 启动跨插件调用分发器
+    ///
+    /// 消费 call_rx 中的调用请求，执行目标方法并回传结果
+    ///
+    /// 修复说明：
+    /// - 使用动态插件列表查询，避免启动时快照漂移问题
     /// - 支持 stop 后重新 start（在 stop 时重建 channel）
     pub async fn start_call_dispatcher(&self) {
         let mut call_handle = self.call_dispatcher_handle.write().await;
@@ -1173,50 +2221,373 @@ impl PluginManager {
     pub async fn discover_and_load(&self) -> Result<Vec<PluginInfo>, LifecycleError> {
         // 使用异步版本的 discover
         let discovered = self.discovery.discover_async().await?;
+        self.record_incompatible(self.discovery.plugins_dir(), discovered.incompatible);
 
         let mut plugins = self.plugins.write().await;
         let mut infos = Vec::new();
 
-        for (path, manifest) in discovered {
+        let verifier = self.publisher_keys.verifier();
+        for (path, manifest) in discovered.plugins {
             let id = manifest.id.clone();
+            let sandbox_config = match manifest.sandbox_config(&self.sandbox_caps()) {
+                Ok(config) => config,
+                Err(e) => {
+                    log::warn!("[{}] 插件已跳过: {}", id, e);
+                    continue;
+                }
+            };
+            let manifest_content = tokio::fs::read_to_string(path.join("manifest.json"))
+                .await
+                .unwrap_or_default();
+            let mut instance = PluginInstance::new(path, manifest);
+            instance.sandbox_config = sandbox_config;
+            instance.apply_trust(installed_trust_level(&manifest_content, &verifier), &self.sandbox_caps());
+            self.register_components(&instance).await;
+
+            // 重新发现不解除隔离
+            instance.quarantined = plugins.get(&id).is_some_and(|p| p.quarantined);
+
+            // 已添加的账户跟随主插件的新 manifest（Phase 7.14）
+            let mut account_ids = Vec::new();
+            for account in plugins.values_mut().filter(|p| p.is_account_of(&id)) {
+                account.follow(&instance);
+                account_ids.push(account.id.clone());
+            }
+            for account_id in &account_ids {
+                self.unregister_components(account_id).await;
+                if let Some(account) = plugins.get(account_id) {
+                    self.register_components(account).await;
+                }
+            }
+
+            let info = instance.to_info();
+            if let Some(mut previous) = plugins.insert(id, instance) {
+                self.release_resources(&mut previous);
+            }
+            infos.push(info);
+        }
+        drop(plugins);
+
+        log::info!("已发现 {} 个插件，Phase 4 组件已初始化", infos.len());
+        infos.extend(self.load_dev_plugins().await?);
+        self.restore_persisted_state(&mut infos).await;
+        Ok(infos)
+    }
+
+    /// 注册内置数据源（Phase 7.13，与已安装插件 ID 冲突时以已安装插件为准）
+    async fn load_builtin_plugins(&self) -> Vec<PluginInfo> {
+        let mut plugins = self.plugins.write().await;
+        let mut infos = Vec::new();
+        for provider in BuiltinProvider::ALL {
+            let id = provider.id();
+            if plugins.get(id).is_some_and(|p| p.builtin.is_none()) {
+                log::warn!("[{}] 内置数据源与已安装插件 ID 冲突，已跳过", id);
+                continue;
+            }
+            let instance = PluginInstance::builtin(provider);
+            self.register_components(&instance).await;
+            let info = instance.to_info();
+            if let Some(mut previous) = plugins.insert(id.to_string(), instance) {
+                self.release_resources(&mut previous);
+            }
+            infos.push(info);
+        }
+        infos
+    }
 
-            // Phase 4.1: 注册事件订阅
-            if !manifest.subscribed_events.is_empty() {
-                self.event_bus.subscribe(&id, &manifest.subscribed_events).await;
-                log::debug!("[{}] 注册事件订阅: {:?}", id, manifest.subscribed_events);
+    /// 按 state.json 恢复插件的其他账户实例（Phase 7.14）
+    ///
+    /// 所属插件已不存在的账户跳过（保留状态，插件重新安装后下次启动恢复）
+    async fn load_plugin_accounts(&self) -> Vec<PluginInfo> {
+        let mut plugins = self.plugins.write().await;
+        let mut infos = Vec::new();
+        for (instance_id, account) in self.plugin_state.accounts() {
+            if plugins.contains_key(&instance_id) {
+                continue;
             }
+            let Some(base) = plugins.get(&account.plugin_id).filter(|p| p.account_label.is_none()) else {
+                log::warn!("[{}] 账户所属插件 {} 不存在，已跳过", instance_id, account.plugin_id);
+                continue;
+            };
+            let instance = PluginInstance::account(base, instance_id.clone(), account.label);
+            self.register_components(&instance).await;
+            infos.push(instance.to_info());
+            plugins.insert(instance_id, instance);
+        }
+        infos
+    }
 
-            // Phase 4.2: 注册配置 Schema
-            if let Some(ref schema) = manifest.config_schema {
-                if let Err(e) = self.config_manager.register_schema_from_json(&id, schema).await {
-                    log::warn!("[{}] 配置 Schema 注册失败: {}", id, e);
-                } else {
-                    log::debug!("[{}] 已注册配置 Schema", id);
+    /// 恢复持久化的配置、启用状态和数据源失效跟踪（Phase 2.3.8）
+    ///
+    /// 启用经由 enable_plugin，隔离检查和 onLoad 钩子与手动启用一致
+    async fn restore_persisted_state(&self, infos: &mut [PluginInfo]) {
+        for info in infos.iter_mut() {
+            let id = info.instance_id.clone();
+            let Some(persisted) = self.plugin_state.get(&id) else {
+                continue;
+            };
+            // 旧版本保存的 secret 字段明文迁移到 Keychain
+            let mut config = persisted.config;
+            match self.store_secret_fields(&id, &mut config).await {
+                Ok(true) => self.plugin_state.set_config(&id, &config),
+                Ok(false) => {}
+                Err(e) => log::warn!("[{}] 迁移 secret 配置失败: {}", id, e),
+            }
+            if let Some(plugin) = self.plugins.write().await.get_mut(&id) {
+                plugin.config = config;
+                plugin.gone_since = persisted.gone_since;
+                plugin.possibly_defunct = persisted.possibly_defunct;
+            }
+            if persisted.enabled {
+                if let Err(e) = self.enable_plugin(&id).await {
+                    log::warn!("[{}] 恢复启用状态失败: {}", id, e);
                 }
             }
+            if let Some(plugin) = self.plugins.read().await.get(&id) {
+                *info = plugin.to_info();
+            }
+        }
+    }
+
+    /// 注册插件的 Phase 4 组件（事件订阅、配置 Schema、权限、暴露方法）
+    async fn register_components(&self, instance: &PluginInstance) {
+        let id = instance.id.as_str();
+        let manifest = &instance.manifest;
+
+        // Phase 4.1: 注册事件订阅和 onEvent 处理器
+        self.register_event_subscriptions(id, manifest).await;
 
-            // Phase 4.3: 注册权限声明
-            if !manifest.permissions.is_empty() {
-                self.permission_checker.register_permissions(&id, &manifest.permissions).await;
-                log::debug!("[{}] 注册权限声明: {:?}", id, manifest.permissions);
+        // Phase 4.2: 注册配置 Schema
+        if let Some(ref schema) = manifest.config_schema {
+            if let Err(e) = self.config_manager.register_schema_from_json(id, schema).await {
+                log::warn!("[{}] 配置 Schema 注册失败: {}", id, e);
+            } else {
+                log::debug!("[{}] 已注册配置 Schema", id);
             }
+        }
+
+        // Phase 4.3: 注册权限声明（开发者插件按信任状态过滤）
+        let permissions = instance.granted_permissions();
+        if !permissions.is_empty() {
+            self.permission_checker.register_permissions(id, &permissions).await;
+            log::debug!("[{}] 注册权限声明: {:?}", id, permissions);
+        }
+
+        // Phase 4.3: 注册暴露方法
+        for method in &manifest.exposed_methods {
+            self.method_registry.register(id, method, None).await;
+            log::debug!("[{}] 注册暴露方法: {}", id, method);
+        }
+    }
+
+    /// 注册插件的事件订阅，并注册在沙盒中执行其 onEvent 的处理器
+    async fn register_event_subscriptions(&self, id: &str, manifest: &PluginManifest) {
+        if manifest.subscribed_events.is_empty() {
+            return;
+        }
+        self.event_bus.subscribe_with_filters(id, &manifest.subscribed_events).await;
+        self.event_bus.register_handler(id, self.event_handler(id)).await;
+        log::debug!("[{}] 注册事件订阅: {:?}", id, manifest.subscribed_events);
+    }
+
+    /// 创建插件的事件处理器：转发到事件投递通道并等待 onEvent 执行结果
+    fn event_handler(&self, id: &str) -> EventHandler {
+        let event_tx = self.event_tx.clone();
+        let plugin_id = id.to_string();
+        Arc::new(move |event, data| {
+            let event_tx = event_tx.clone();
+            let plugin_id = plugin_id.clone();
+            Box::pin(async move {
+                let (reply, reply_rx) = oneshot::channel();
+                event_tx
+                    .send(EventDelivery {
+                        plugin_id,
+                        event,
+                        data,
+                        reply,
+                    })
+                    .await
+                    .map_err(|_| "事件投递通道已关闭".to_string())?;
+                reply_rx.await.map_err(|_| "事件处理已取消".to_string())?
+            })
+        })
+    }
+
+    /// 清理插件的 Phase 4 组件
+    async fn unregister_components(&self, id: &str) {
+        self.event_bus.unsubscribe_all(id).await;
+        self.permission_checker.unregister_permissions(id).await;
+        self.method_registry.unregister_all(id).await;
+        self.config_manager.unregister_schema(id).await;
+    }
+
+    // ========================================================================
+    // 开发者模式 (Phase 5A.8)
+    // ========================================================================
+
+    /// 开发者插件目录
+    pub fn dev_plugins_dir(&self) -> PathBuf {
+        DeveloperSettings::dev_plugins_dir(self.plugins_dir())
+    }
+
+    /// 查找文件变更所属的开发者插件
+    ///
+    /// 仅开发者插件的 manifest.json 和入口文件触发热重载，其余文件（如插件自身写入的数据）忽略。
+    pub async fn dev_plugin_for_change(&self, path: &Path) -> Option<String> {
+        self.plugins
+            .read()
+            .await
+            .values()
+            .find(|p| {
+                p.trust_level == TrustLevel::Developer
+                    && (path == p.path.join("manifest.json") || path == p.path.join(&p.manifest.entry))
+            })
+            .map(|p| p.id.clone())
+    }
+
+    /// 当前开发者模式设置
+    pub async fn developer_settings(&self) -> DeveloperSettings {
+        self.developer.read().await.clone()
+    }
+
+    /// 保存开发者模式设置
+    ///
+    /// 开启时加载 dev-plugins 目录中的插件，关闭时卸载（不删除文件）；
+    /// 信任列表变化时重新注册开发者插件的权限。
+    pub async fn set_developer_settings(
+        &self,
+        settings: DeveloperSettings,
+    ) -> Result<Vec<PluginInfo>, LifecycleError> {
+        settings.save(&self.developer_path)?;
+        let was_enabled = std::mem::replace(&mut *self.developer.write().await, settings.clone()).enabled;
+
+        if settings.enabled && !was_enabled {
+            log::warn!("开发者模式已开启，将加载未签名插件: {:?}", self.dev_plugins_dir());
+            self.load_dev_plugins().await?;
+        } else if !settings.enabled && was_enabled {
+            log::info!("开发者模式已关闭，卸载开发者插件");
+            self.unload_dev_plugins().await;
+        } else {
+            self.apply_dev_trust(&settings).await;
+        }
+
+        Ok(self
+            .list_plugins()
+            .await
+            .into_iter()
+            .filter(|p| p.trust_level == TrustLevel::Developer)
+            .collect())
+    }
+
+    /// 加载 dev-plugins 目录中的插件（开发者模式关闭时不加载）
+    ///
+    /// 与已安装插件 ID 冲突时跳过，已安装版本优先。
+    async fn load_dev_plugins(&self) -> Result<Vec<PluginInfo>, LifecycleError> {
+        let settings = self.developer.read().await.clone();
+        if !settings.enabled {
+            return Ok(Vec::new());
+        }
+        // 开发者插件未签名，管理员策略要求签名时不加载
+        if self.admin_policy.policy().require_signatures {
+            log::warn!("管理员策略要求插件签名，不加载开发者插件");
+            return Ok(Vec::new());
+        }
+
+        let dev_dir = self.dev_plugins_dir();
+        let discovered = PluginDiscovery::new(dev_dir.clone()).discover_async().await?;
+        self.record_incompatible(&dev_dir, discovered.incompatible);
+        let mut plugins = self.plugins.write().await;
+        let mut infos = Vec::new();
 
-            // Phase 4.3: 注册暴露方法
-            for method in &manifest.exposed_methods {
-                self.method_registry.register(&id, method, None).await;
-                log::debug!("[{}] 注册暴露方法: {}", id, method);
+        for (path, manifest) in discovered.plugins {
+            let id = manifest.id.clone();
+            if plugins
+                .get(&id)
+                .is_some_and(|p| p.trust_level != TrustLevel::Developer)
+            {
+                log::warn!("[{}] 开发者插件与已安装插件 ID 冲突，已跳过: {:?}", id, path);
+                continue;
             }
+            let sandbox_config = match manifest.sandbox_config(&self.sandbox_caps()) {
+                Ok(config) => config,
+                Err(e) => {
+                    log::warn!("[{}] 开发者插件已跳过: {}", id, e);
+                    continue;
+                }
+            };
 
-            let instance = PluginInstance::new(path, manifest);
+            let mut instance = PluginInstance::new(path, manifest);
+            instance.sandbox_config = sandbox_config;
+            instance.apply_trust(TrustLevel::Developer, &self.sandbox_caps());
+            instance.dev_trusted = settings.is_trusted(&id);
+            // 重新加载时清理旧注册，避免权限残留
+            self.unregister_components(&id).await;
+            self.register_components(&instance).await;
+
+            if instance.permissions_restricted() {
+                log::warn!(
+                    "[{}] 开发者插件未受信任，已限制权限: {:?}",
+                    id,
+                    instance.to_info().restricted_permissions
+                );
+            }
             let info = instance.to_info();
-            plugins.insert(id, instance);
+            if let Some(mut previous) = plugins.insert(id, instance) {
+                self.release_resources(&mut previous);
+            }
             infos.push(info);
         }
 
-        log::info!("已发现 {} 个插件，Phase 4 组件已初始化", infos.len());
+        if !infos.is_empty() {
+            log::info!("已加载 {} 个开发者插件（未签名）", infos.len());
+        }
         Ok(infos)
     }
 
+    /// 卸载所有开发者插件（不删除文件）
+    async fn unload_dev_plugins(&self) {
+        let removed: Vec<PluginInstance> = {
+            let mut plugins = self.plugins.write().await;
+            let ids: Vec<String> = plugins
+                .values()
+                .filter(|p| p.trust_level == TrustLevel::Developer)
+                .map(|p| p.id.clone())
+                .collect();
+            ids.iter().filter_map(|id| plugins.remove(id)).collect()
+        };
+
+        for mut plugin in removed {
+            self.unregister_components(&plugin.id).await;
+            let released = self.release_resources(&mut plugin);
+            log::info!("已卸载开发者插件: {}, 释放 {} 个资源", plugin.id, released);
+        }
+        self.record_incompatible(&self.dev_plugins_dir(), Vec::new());
+    }
+
+    /// 按信任列表更新开发者插件的权限
+    async fn apply_dev_trust(&self, settings: &DeveloperSettings) {
+        let mut plugins = self.plugins.write().await;
+        for plugin in plugins
+            .values_mut()
+            .filter(|p| p.trust_level == TrustLevel::Developer)
+        {
+            let trusted = settings.is_trusted(&plugin.manifest.id);
+            if plugin.dev_trusted == trusted {
+                continue;
+            }
+            plugin.dev_trusted = trusted;
+            self.permission_checker.unregister_permissions(&plugin.id).await;
+            self.permission_checker
+                .register_permissions(&plugin.id, &plugin.granted_permissions())
+                .await;
+            log::info!(
+                "[{}] 开发者插件{}",
+                plugin.id,
+                if trusted { "已被信任，授予全部声明权限" } else { "已取消信任，权限已限制" }
+            );
+        }
+    }
+
     /// 初始化插件系统（完整流程）
     ///
     /// 执行完整的插件系统初始化：
@@ -1227,7 +2598,17 @@ impl PluginManager {
     /// 这是推荐的初始化入口，替代手动调用 discover_and_load + start_dispatcher
     pub async fn init(&self) -> Result<Vec<PluginInfo>, LifecycleError> {
         // 1. 发现并加载插件
-        let infos = self.discover_and_load().await?;
+        let mut infos = self.discover_and_load().await?;
+
+        // 1.1 注册内置数据源并恢复其启用状态和配置 (Phase 7.13)
+        let mut builtins = self.load_builtin_plugins().await;
+        self.restore_persisted_state(&mut builtins).await;
+        infos.extend(builtins);
+
+        // 1.2 恢复插件的其他账户 (Phase 7.14)
+        let mut accounts = self.load_plugin_accounts().await;
+        self.restore_persisted_state(&mut accounts).await;
+        infos.extend(accounts);
 
         // 2. 启动事件分发器
         self.start_dispatcher().await;
@@ -1242,17 +2623,41 @@ impl PluginManager {
     /// 关闭插件系统
     ///
     /// 清理资源：
-    /// 1. 停止 EventBus 分发器
-    /// 2. 停止跨插件调用分发器
-    /// 3. 卸载所有插件
+    /// 1. 等待 EventBus 队列中已发布的事件分发完成（最多 EVENT_FLUSH_TIMEOUT）
+    /// 2. 停止 EventBus 分发器
+    /// 3. 停止跨插件调用分发器
+    /// 4. 卸载所有插件（onUnload 中插件可自行保存状态）
     pub async fn shutdown(&self) {
-        // 1. 停止事件分发器
+        // 1. 等待已发布的事件分发完成，避免退出时中断正在分发的事件
+        if !self.flush_events(EVENT_FLUSH_TIMEOUT).await {
+            log::warn!(
+                "等待事件分发超时，剩余 {} 个事件未分发",
+                self.event_bus.pending_events()
+            );
+        }
+
+        // 2. 停止事件分发器
         self.stop_dispatcher().await;
 
-        // 2. 停止调用分发器
+        // 3. 停止调用分发器
         self.stop_call_dispatcher().await;
 
-        // 3. 清理插件（可选，视需求实现完整卸载）
+        // 4. 对启用的插件调用 onUnload（并发执行，每个钩子受超时保护）
+        let enabled: Vec<String> = self
+            .plugins
+            .read()
+            .await
+            .values()
+            .filter(|p| p.enabled)
+            .map(|p| p.id.clone())
+            .collect();
+        futures::future::join_all(
+            enabled
+                .iter()
+                .map(|id| self.run_lifecycle_hook(id, LifecycleHook::OnUnload)),
+        )
+        .await;
+
         log::info!("插件系统已关闭");
     }
 
@@ -1276,44 +2681,80 @@ impl PluginManager {
     }
 
     /// 启用插件
+    ///
+    /// 已隔离的插件需重新通过完整性校验才能启用
     pub async fn enable_plugin(&self, id: &str) -> Result<(), LifecycleError> {
-        let mut plugins = self.plugins.write().await;
+        let quarantined = self.plugins.read().await.get(id).is_some_and(|p| p.quarantined);
+        if quarantined && self.verify_plugin_integrity(id).await?.quarantined {
+            return Err(LifecycleError::PluginLoad(format!(
+                "插件已被隔离（文件完整性校验失败）: {}",
+                id
+            )));
+        }
 
-        if let Some(plugin) = plugins.get_mut(id) {
-            plugin.enabled = true;
-            log::info!("已启用插件: {}", id);
-            Ok(())
-        } else {
-            Err(LifecycleError::PluginLoad(format!("插件不存在: {}", id)))
+        let was_enabled = {
+            let mut plugins = self.plugins.write().await;
+            let plugin = plugins
+                .get_mut(id)
+                .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?;
+            // 用户重新启用被看门狗禁用的插件，重新计数
+            plugin.auto_disabled_at = None;
+            plugin.consecutive_runaways = 0;
+            std::mem::replace(&mut plugin.enabled, true)
+        };
+        self.plugin_state.set_enabled(id, true);
+        log::info!("已启用插件: {}", id);
+
+        if !was_enabled {
+            // 禁用时取消了事件订阅，重新启用时恢复
+            let manifest = self.plugins.read().await.get(id).map(|p| p.manifest.clone());
+            if let Some(manifest) = manifest {
+                self.register_event_subscriptions(id, &manifest).await;
+            }
+            self.run_lifecycle_hook(id, LifecycleHook::OnLoad).await;
         }
+        Ok(())
     }
 
-    /// 禁用插件
-    ///
-    /// Phase 4: 同时清理事件订阅和暴露方法
-    pub async fn disable_plugin(&self, id: &str) -> Result<(), LifecycleError> {
-        let mut plugins = self.plugins.write().await;
+    /// 停用插件：持久化禁用状态、清空缓存、释放资源并取消 Phase 4 注册，返回释放的资源数
+    async fn deactivate_plugin(&self, plugin: &mut PluginInstance) -> usize {
+        let id = plugin.id.clone();
+        plugin.enabled = false;
+        self.plugin_state.set_enabled(&id, false);
+        self.fetch_cache.invalidate_plugin(&id).await;
 
-        if let Some(plugin) = plugins.get_mut(id) {
-            plugin.enabled = false;
+        // 清理资源（含持久定时器）
+        let released = self.release_resources(plugin);
 
-            // 清理资源
-            let resources = plugin.resources.clear();
+        // Phase 4.1: 取消事件订阅
+        self.event_bus.unsubscribe_all(&id).await;
 
-            // Phase 4.1: 取消事件订阅
-            self.event_bus.unsubscribe_all(id).await;
+        // Phase 4.3: 取消方法注册
+        self.method_registry.unregister_all(&id).await;
+        released
+    }
 
-            // Phase 4.3: 取消方法注册
-            self.method_registry.unregister_all(id).await;
+    /// 禁用插件
+    ///
+    /// Phase 4: 同时清理事件订阅和暴露方法
+    pub async fn disable_plugin(&self, id: &str) -> Result<(), LifecycleError> {
+        // 资源回收前调用 onUnload
+        if self.plugins.read().await.get(id).is_some_and(|p| p.enabled) {
+            self.run_lifecycle_hook(id, LifecycleHook::OnUnload).await;
+        }
 
+        let mut plugins = self.plugins.write().await;
+
+        if let Some(plugin) = plugins.get_mut(id) {
+            let released = self.deactivate_plugin(plugin).await;
             log::info!(
                 "已禁用插件: {}, 释放 {} 个资源, 已清理 Phase 4 组件",
                 id,
-                resources.len()
+                released
             );
             Ok(())
         } else {
-            Err(LifecycleError::PluginUnload(format!("插件不存在: {}", id)))
+            Err(LifecycleError::PluginNotFound(id.to_string()))
         }
     }
 
@@ -1322,6 +2763,277 @@ impl PluginManager {
         self.discovery.plugins_dir()
     }
 
+    // ========================================================================
+    // 完整性自检
+    // ========================================================================
+
+    /// 校验已安装插件的文件完整性
+    ///
+    /// 将磁盘上的文件与 manifest.files 中的哈希比对：
+    /// - 发现篡改或缺失时禁用并隔离插件，写入审计日志
+    /// - 已隔离的插件重新校验通过后解除隔离（保持禁用，由用户手动启用）
+    ///
+    /// manifest 未声明文件哈希时无法校验，视为通过。
+    pub async fn verify_plugin_integrity(&self, id: &str) -> Result<IntegrityReport, LifecycleError> {
+        let (path, files, was_quarantined) = {
+            let plugins = self.plugins.read().await;
+            let plugin = plugins
+                .get(id)
+                .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?;
+            (
+                plugin.path.clone(),
+                plugin.manifest.files.clone().unwrap_or_default(),
+                plugin.quarantined,
+            )
+        };
+
+        let files_checked = files.len();
+        // 哈希计算涉及文件读取，放到 blocking 线程执行
+        let issues: Vec<String> = tokio::task::spawn_blocking(move || {
+            check_files(&files, &path)
+                .iter()
+                .map(|e| e.to_string())
+                .collect()
+        })
+        .await
+        .map_err(|e| LifecycleError::PluginLoad(format!("完整性校验任务失败: {}", e)))?;
+
+        let quarantined = if !issues.is_empty() {
+            if !was_quarantined {
+                self.quarantine_plugin(id, &issues).await?;
+            }
+            true
+        } else {
+            if was_quarantined {
+                self.release_quarantine(id).await;
+            }
+            false
+        };
+
+        Ok(IntegrityReport {
+            plugin_id: id.to_string(),
+            checked_at: Utc::now().to_rfc3339(),
+            has_hashes: files_checked > 0,
+            files_checked,
+            issues,
+            quarantined,
+        })
+    }
+
+    /// 校验所有已安装插件的文件完整性
+    pub async fn verify_all_plugins_integrity(&self) -> Vec<IntegrityReport> {
+        let ids: Vec<String> = self.plugins.read().await.keys().cloned().collect();
+        let mut reports = Vec::with_capacity(ids.len());
+        for id in ids {
+            match self.verify_plugin_integrity(&id).await {
+                Ok(report) => reports.push(report),
+                // 校验期间插件被卸载
+                Err(e) => log::debug!("[{}] 跳过完整性校验: {}", id, e),
+            }
+        }
+        reports
+    }
+
+    /// 隔离被篡改的插件（禁用并记录审计事件）
+    async fn quarantine_plugin(&self, id: &str, issues: &[String]) -> Result<(), LifecycleError> {
+        if let Some(plugin) = self.plugins.write().await.get_mut(id) {
+            plugin.quarantined = true;
+        }
+        self.disable_plugin(id).await?;
+
+        log::error!("[{}] 文件完整性校验失败，已隔离: {}", id, issues.join("; "));
+        self.record_audit(
+            AuditEvent::plugin(AuditEventKind::IntegrityFailed, id, "插件文件与 manifest 哈希不一致")
+                .with_details(issues.to_vec()),
+        );
+        self.record_audit(AuditEvent::plugin(
+            AuditEventKind::PluginQuarantined,
+            id,
+            "插件已被禁用并隔离",
+        ));
+        Ok(())
+    }
+
+    /// 解除隔离（插件保持禁用）
+    async fn release_quarantine(&self, id: &str) {
+        if let Some(plugin) = self.plugins.write().await.get_mut(id) {
+            plugin.quarantined = false;
+        }
+        log::info!("[{}] 完整性校验通过，已解除隔离", id);
+        self.record_audit(AuditEvent::plugin(
+            AuditEventKind::QuarantineReleased,
+            id,
+            "重新校验通过，已解除隔离",
+        ));
+    }
+
+    /// 读取最近的审计事件
+    pub fn recent_audit_events(&self, limit: usize) -> Vec<AuditEvent> {
+        self.audit_log.recent(limit)
+    }
+
+    /// 生成插件隐私报告：最近 `days` 天访问过的主机、流量及作为请求头发送的配置字段
+    pub async fn get_privacy_report(
+        &self,
+        id: &str,
+        days: u32,
+    ) -> Result<PrivacyReport, LifecycleError> {
+        let declared_hosts = self
+            .plugins
+            .read()
+            .await
+            .get(id)
+            .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?
+            .manifest
+            .allowed_hosts
+            .clone();
+
+        let now = Utc::now();
+        Ok(PrivacyReport::build(
+            id,
+            days,
+            now,
+            declared_hosts,
+            self.network_stats.host_reports(id, days, now),
+            self.audit_log.recent(PRIVACY_REPORT_AUDIT_LIMIT),
+        ))
+    }
+
+    /// 插件最近 24 小时的使用率趋势（降采样为 `points` 个点）
+    pub async fn get_usage_trend(&self, id: &str, points: u32) -> Result<UsageTrend, LifecycleError> {
+        if !self.plugins.read().await.contains_key(id) {
+            return Err(LifecycleError::PluginNotFound(id.to_string()));
+        }
+        Ok(self.usage_history.trend(id, points, Utc::now()))
+    }
+
+    /// 检测插件最近一小时的用量异常
+    pub fn detect_usage_anomaly(&self, id: &str) -> Option<UsageAnomaly> {
+        self.usage_history.detect_anomaly(id, Utc::now())
+    }
+
+    /// 各插件的存储占用（storage 文件 + cache 目录），按插件 ID 排序
+    pub async fn get_storage_stats(&self) -> Vec<PluginStorageStats> {
+        use crate::plugin::sandbox::cache::DEFAULT_CACHE_QUOTA;
+        use crate::plugin::sandbox::storage::DEFAULT_STORAGE_QUOTA;
+        use crate::plugin::{PluginCache, PluginStorage};
+
+        let mut ids: Vec<String> = self.plugins.read().await.keys().cloned().collect();
+        ids.sort();
+
+        let storage_dir = PluginStorage::default_dir();
+        let cache_dir = PluginCache::default_dir();
+        ids.into_iter()
+            .map(|id| {
+                let storage_bytes = PluginStorage::disk_usage(&storage_dir, &id).unwrap_or(0);
+                let cache = PluginCache::open(&cache_dir, &id, DEFAULT_CACHE_QUOTA)
+                    .map(|c| c.usage())
+                    .unwrap_or_default();
+                PluginStorageStats {
+                    plugin_id: id,
+                    storage_bytes,
+                    storage_quota: DEFAULT_STORAGE_QUOTA as u64,
+                    cache_entries: cache.entries,
+                    cache_bytes: cache.bytes,
+                    cache_quota: DEFAULT_CACHE_QUOTA,
+                }
+            })
+            .collect()
+    }
+
+    /// 写入审计日志（失败仅记录日志）
+    fn record_audit(&self, event: AuditEvent) {
+        if let Err(e) = self.audit_log.record(&event) {
+            log::warn!("写入审计日志失败: {}", e);
+        }
+    }
+
+    // ========================================================================
+    // 孤立注册清理
+    // ========================================================================
+
+    /// 清理孤立的 Phase 4 注册
+    ///
+    /// 插件目录在应用运行期间被外部删除时，其事件订阅、权限、暴露方法和配置 Schema
+    /// 会残留。此方法将各注册表中的插件 ID 与磁盘上发现的插件比对，清理不再存在的插件。
+    pub async fn prune_orphaned_registrations(&self) -> Result<Vec<OrphanCleanup>, LifecycleError> {
+        let discovered: HashSet<String> = self
+            .discovery
+            .discover_async()
+            .await?
+            .plugins
+            .into_iter()
+            .map(|(_, manifest)| manifest.id)
+            .collect();
+
+        let subscriptions = self.event_bus.registered_plugin_ids().await;
+        let permissions = self.permission_checker.registered_plugin_ids().await;
+        let methods = self.method_registry.registered_plugin_ids().await;
+        let schemas: HashSet<String> =
+            self.config_manager.registered_plugin_ids().await.into_iter().collect();
+        let instances: Vec<(String, PathBuf, bool)> = self
+            .plugins
+            .read()
+            .await
+            .iter()
+            .map(|(id, p)| (id.clone(), p.path.clone(), p.builtin.is_some()))
+            .collect();
+
+        // 目录仍存在的实例视为存活（discover 可能因 manifest 暂时无效而跳过它）
+        // 内置数据源（及其账户）没有插件目录，始终存活
+        let mut live = discovered;
+        live.extend(BuiltinProvider::ALL.iter().map(|p| p.id().to_string()));
+        for (id, path, builtin) in &instances {
+            if *builtin || tokio::fs::try_exists(path).await.unwrap_or(false) {
+                live.insert(id.clone());
+            }
+        }
+
+        let mut candidates: Vec<String> = subscriptions
+            .iter()
+            .chain(&permissions)
+            .chain(&methods)
+            .chain(&schemas)
+            .cloned()
+            .chain(instances.into_iter().map(|(id, _, _)| id))
+            .filter(|id| !live.contains(id))
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+
+        let mut cleanups = Vec::with_capacity(candidates.len());
+        for id in candidates {
+            let mut reclaimed = Vec::new();
+            if subscriptions.contains(&id) {
+                self.event_bus.unsubscribe_all(&id).await;
+                reclaimed.push("subscriptions");
+            }
+            if permissions.contains(&id) {
+                self.permission_checker.unregister_permissions(&id).await;
+                reclaimed.push("permissions");
+            }
+            if methods.contains(&id) {
+                self.method_registry.unregister_all(&id).await;
+                reclaimed.push("methods");
+            }
+            if schemas.contains(&id) {
+                self.config_manager.unregister_schema(&id).await;
+                reclaimed.push("configSchema");
+            }
+            if let Some(mut plugin) = self.plugins.write().await.remove(&id) {
+                self.release_resources(&mut plugin);
+                reclaimed.push("instance");
+            }
+
+            log::info!("[{}] 插件目录已不存在，已清理孤立注册: {}", id, reclaimed.join(", "));
+            cleanups.push(OrphanCleanup {
+                plugin_id: id,
+                reclaimed,
+            });
+        }
+        Ok(cleanups)
+    }
+
     // ========================================================================
     // 卸载和重载
     // ========================================================================
@@ -1331,21 +3043,63 @@ impl PluginManager {
     /// 使用 tokio::fs 执行异步文件操作，避免阻塞 Tokio worker
     /// Phase 4: 同时清理事件订阅、权限和暴露方法
     pub async fn uninstall_plugin(&self, id: &str) -> Result<(), LifecycleError> {
+        // 账户实例与主插件共享目录，只移除账户
+        if self.plugins.read().await.get(id).is_some_and(|p| p.account_label.is_some()) {
+            return self.remove_plugin_account(id).await;
+        }
+
+        // 开发者插件目录属于用户源码，不由宿主删除
+        let is_dev = self
+            .plugins
+            .read()
+            .await
+            .get(id)
+            .is_some_and(|p| p.trust_level == TrustLevel::Developer);
+        if is_dev {
+            return Err(LifecycleError::PluginUnload(format!(
+                "开发者插件不能卸载，请直接从 {:?} 移除: {}",
+                self.dev_plugins_dir(),
+                id
+            )));
+        }
+        if self.plugins.read().await.get(id).is_some_and(|p| p.builtin.is_some()) {
+            return Err(LifecycleError::PluginUnload(format!("内置插件不能卸载，可以禁用: {}", id)));
+        }
+
+        // 先移除该插件的其他账户（onUnload 仍需读取插件目录）
+        for account_id in self.account_ids(id).await {
+            if let Err(e) = self.remove_plugin_account(&account_id).await {
+                log::warn!("[{}] 移除账户失败: {}", account_id, e);
+            }
+        }
+
+        if self.plugins.read().await.get(id).is_some_and(|p| p.enabled) {
+            self.run_lifecycle_hook(id, LifecycleHook::OnUnload).await;
+        }
+
         // Phase 4: 清理组件（在获取写锁前）
         self.event_bus.unsubscribe_all(id).await;
         self.permission_checker.unregister_permissions(id).await;
         self.method_registry.unregister_all(id).await;
         self.config_manager.unregister_schema(id).await;
+        self.network_stats.remove(id);
+        self.plugin_logs.remove(id);
+        self.usage_history.remove(id);
+        self.cost_estimator.remove(id);
+        self.plugin_state.remove(id);
+        self.fetch_cache.invalidate_plugin(id).await;
+        self.rate_limiter.remove_plugin(id).await;
+        self.sandbox_pool.remove_plugin(id);
 
         // 1. 先从内存移除，释放写锁
         let plugin_path = {
             let mut plugins = self.plugins.write().await;
             if let Some(mut plugin) = plugins.remove(id) {
-                let resources = plugin.resources.clear();
+                let released = self.release_resources(&mut plugin);
                 log::info!(
                     "已卸载插件: {}, 释放 {} 个资源, 已清理 Phase 4 组件",
                     id,
-                    resources.len()
+                    released
                 );
                 Some(plugin.path.clone())
             } else {
@@ -1362,9 +3116,25 @@ impl PluginManager {
                     .map_err(|e| LifecycleError::Io(e))?;
                 log::info!("已删除插件目录: {:?}", path);
             }
+
+            // 清理插件存储
+            if let Err(e) = crate::plugin::PluginStorage::remove(
+                &crate::plugin::PluginStorage::default_dir(),
+                id,
+            ) {
+                log::warn!("[{}] 删除插件存储失败: {}", id, e);
+            }
+            // 清理插件缓存目录
+            if let Err(e) = crate::plugin::PluginCache::remove(
+                &crate::plugin::PluginCache::default_dir(),
+                id,
+            ) {
+                log::warn!("[{}] 删除插件缓存失败: {}", id, e);
+            }
+            crate::plugin::HttpCache::shared().clear_plugin(id);
             Ok(())
         } else {
-            Err(LifecycleError::PluginUnload(format!("插件不存在: {}", id)))
+            Err(LifecycleError::PluginNotFound(id.to_string()))
         }
     }
 
@@ -1378,26 +3148,42 @@ impl PluginManager {
     /// - Phase 2: 切换阶段 - 验证全部成功后，清理旧注册并一次性切换
     /// 如果验证失败，旧注册完全保持不变
     pub async fn reload_plugin(&self, id: &str) -> Result<PluginInfo, LifecycleError> {
+        let info = self.reload_instance(id).await?;
+        // 账户实例共享插件目录，随主插件一起重载
+        for account_id in self.account_ids(id).await {
+            if let Err(e) = self.reload_instance(&account_id).await {
+                log::warn!("[{}] 重载账户失败: {}", account_id, e);
+            }
+        }
+        Ok(info)
+    }
+
+    /// 重载单个插件实例
+    async fn reload_instance(&self, id: &str) -> Result<PluginInfo, LifecycleError> {
         // ========================================================================
         // Phase 1: 验证阶段（不触碰现有注册）
         // ========================================================================
 
-        // 1.1 获取 manifest 路径，释放读锁
-        let manifest_path = {
+        // 1.1 获取 manifest 路径和权限限制状态，释放读锁（内置插件没有 manifest 文件）
+        let (manifest_path, permissions_restricted) = {
             let plugins = self.plugins.read().await;
-            plugins.get(id).map(|p| p.path.join("manifest.json"))
+            let plugin = plugins
+                .get(id)
+                .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?;
+            if plugin.builtin.is_some() {
+                return Err(LifecycleError::PluginLoad(format!("内置插件不能重载: {}", id)));
+            }
+            (plugin.path.join("manifest.json"), plugin.permissions_restricted())
         };
 
-        let manifest_path = manifest_path
-            .ok_or_else(|| LifecycleError::PluginLoad(format!("插件不存在: {}", id)))?;
-
         // 1.2 读取和解析新 manifest
         let content = tokio::fs::read_to_string(&manifest_path)
             .await
             .map_err(|e| LifecycleError::Io(e))?;
 
-        let new_manifest: PluginManifest = serde_json::from_str(&content)
-            .map_err(|e| LifecycleError::ManifestParse(e.to_string()))?;
+        let new_manifest = PluginManifest::parse(&content)?;
+        let sandbox_config = new_manifest.sandbox_config(&self.sandbox_caps())?;
+        let new_trust = installed_trust_level(&content, &self.publisher_keys.verifier());
 
         // 1.3 预验证 config_schema（关键：只验证不注册，失败时旧状态完全保留）
         let validated_schema: Option<ConfigSchema> = if let Some(ref schema_json) = new_manifest.config_schema {
@@ -1426,14 +3212,12 @@ impl PluginManager {
         self.permission_checker.unregister_permissions(id).await;
         self.method_registry.unregister_all(id).await;
         self.config_manager.unregister_schema(id).await;
+        self.fetch_cache.invalidate_plugin(id).await;
         log::debug!("[{}] 已清理旧的 Phase 4 注册（保留事件处理器）", id);
 
         // 2.2 注册新的 Phase 4 组件
-        // Phase 4.1: 注册事件订阅
-        if !new_manifest.subscribed_events.is_empty() {
-            self.event_bus.subscribe(id, &new_manifest.subscribed_events).await;
-            log::debug!("[{}] 重新注册事件订阅: {:?}", id, new_manifest.subscribed_events);
-        }
+        // Phase 4.1: 注册事件订阅（新增订阅的插件同时注册 onEvent 处理器）
+        self.register_event_subscriptions(id, &new_manifest).await;
 
         // Phase 4.2: 注册已验证的配置 Schema（直接使用预验证结果，不会失败）
         if let Some(schema) = validated_schema {
@@ -1441,10 +3225,11 @@ impl PluginManager {
             log::debug!("[{}] 已重新注册配置 Schema", id);
         }
 
-        // Phase 4.3: 注册权限声明
-        if !new_manifest.permissions.is_empty() {
-            self.permission_checker.register_permissions(id, &new_manifest.permissions).await;
-            log::debug!("[{}] 重新注册权限声明: {:?}", id, new_manifest.permissions);
+        // Phase 4.3: 注册权限声明（开发者插件按信任状态过滤）
+        let permissions = grant_permissions(&new_manifest.permissions, permissions_restricted);
+        if !permissions.is_empty() {
+            self.permission_checker.register_permissions(id, &permissions).await;
+            log::debug!("[{}] 重新注册权限声明: {:?}", id, permissions);
         }
 
         // Phase 4.4: 注册暴露方法
@@ -1458,9 +3243,18 @@ impl PluginManager {
         if let Some(plugin) = plugins.get_mut(id) {
             let was_enabled = plugin.enabled;
             plugin.manifest = new_manifest;
+            plugin.sandbox_config = sandbox_config;
+            // 开发者插件保持 developer，已安装插件按新 manifest 的签名重新确定
+            let trust_level = match plugin.trust_level {
+                TrustLevel::Developer => TrustLevel::Developer,
+                _ => new_trust,
+            };
+            plugin.apply_trust(trust_level, &self.sandbox_caps());
             plugin.state = PluginState::Unloaded;
             plugin.enabled = was_enabled;
             plugin.reset_health_stats();
+            self.persist_defunct(plugin);
+            self.publish_health_change(plugin);
 
             log::info!("[{}] 已重载插件 v{}，Phase 4 组件已同步", id, plugin.manifest.version);
             Ok(plugin.to_info())
@@ -1471,15 +3265,159 @@ impl PluginManager {
             self.permission_checker.unregister_permissions(id).await;
             self.method_registry.unregister_all(id).await;
             self.config_manager.unregister_schema(id).await;
-            Err(LifecycleError::PluginLoad(format!("插件不存在: {}", id)))
+            Err(LifecycleError::PluginNotFound(id.to_string()))
         }
     }
 
+    // ========================================================================
+    // 多账户 (Phase 7.14)
+    // ========================================================================
+
+    /// 为数据插件添加一个账户
+    ///
+    /// 新账户是同一插件的独立实例（实例 ID 为 `插件ID@序号`），拥有独立的配置、
+    /// Keychain 密钥、缓存数据和健康统计；创建后默认禁用，填写凭证后启用。
+    pub async fn add_plugin_account(&self, plugin_id: &str, label: &str) -> Result<PluginInfo, LifecycleError> {
+        let label = label.trim();
+        if label.is_empty() || label.chars().count() > MAX_ACCOUNT_LABEL_LEN {
+            return Err(LifecycleError::PluginLoad(format!(
+                "账户名称不能为空且不能超过 {} 个字符",
+                MAX_ACCOUNT_LABEL_LEN
+            )));
+        }
+
+        let mut plugins = self.plugins.write().await;
+        let base = plugins
+            .get(plugin_id)
+            .filter(|p| p.account_label.is_none())
+            .ok_or_else(|| LifecycleError::PluginNotFound(plugin_id.to_string()))?;
+        if base.manifest.get_data_type().is_none() {
+            return Err(LifecycleError::PluginLoad(format!("只有数据插件支持多账户: {}", plugin_id)));
+        }
+
+        let accounts: Vec<&PluginInstance> = plugins.values().filter(|p| p.is_account_of(plugin_id)).collect();
+        if accounts.len() >= MAX_PLUGIN_ACCOUNTS {
+            return Err(LifecycleError::PluginLoad(format!(
+                "每个插件最多添加 {} 个账户: {}",
+                MAX_PLUGIN_ACCOUNTS, plugin_id
+            )));
+        }
+        if base.account_label.as_deref() == Some(label)
+            || accounts.iter().any(|p| p.account_label.as_deref() == Some(label))
+        {
+            return Err(LifecycleError::PluginLoad(format!("账户名称已存在: {}", label)));
+        }
+
+        let mut seq = 2;
+        while plugins.contains_key(&format!("{}@{}", plugin_id, seq)) {
+            seq += 1;
+        }
+        let instance_id = format!("{}@{}", plugin_id, seq);
+        let instance = PluginInstance::account(base, instance_id.clone(), label.to_string());
+        self.register_components(&instance).await;
+        let info = instance.to_info();
+        plugins.insert(instance_id.clone(), instance);
+        drop(plugins);
+
+        self.plugin_state.set_account(
+            &instance_id,
+            PersistedAccount {
+                plugin_id: plugin_id.to_string(),
+                label: label.to_string(),
+            },
+        );
+        log::info!("[{}] 已添加账户: {} ({})", plugin_id, label, instance_id);
+        Ok(info)
+    }
+
+    /// 移除插件账户（不删除插件目录，默认账户需通过卸载插件移除）
+    pub async fn remove_plugin_account(&self, instance_id: &str) -> Result<(), LifecycleError> {
+        let (is_account, enabled) = self
+            .plugins
+            .read()
+            .await
+            .get(instance_id)
+            .map(|p| (p.account_label.is_some(), p.enabled))
+            .ok_or_else(|| LifecycleError::PluginNotFound(instance_id.to_string()))?;
+        if !is_account {
+            return Err(LifecycleError::PluginUnload(format!("不是插件账户: {}", instance_id)));
+        }
+        // 注销组件前取密钥名称（configSchema 中的 secret 字段随 Schema 一起注销）
+        let secret_names = self.secret_names(instance_id).await;
+
+        if enabled {
+            self.run_lifecycle_hook(instance_id, LifecycleHook::OnUnload).await;
+        }
+        self.unregister_components(instance_id).await;
+        self.network_stats.remove(instance_id);
+        self.plugin_logs.remove(instance_id);
+        self.usage_history.remove(instance_id);
+        self.cost_estimator.remove(instance_id);
+        self.plugin_state.remove(instance_id);
+        self.fetch_cache.invalidate_plugin(instance_id).await;
+        self.rate_limiter.remove_plugin(instance_id).await;
+        self.sandbox_pool.remove_plugin(instance_id);
+
+        if let Some(mut plugin) = self.plugins.write().await.remove(instance_id) {
+            self.release_resources(&mut plugin);
+        }
+        let service = secrets_service(instance_id);
+        for name in &secret_names {
+            if let Err(e) = self.secret_store.delete(&service, name) {
+                log::warn!("[{}] 删除账户密钥 {} 失败: {}", instance_id, name, e);
+            }
+        }
+        if let Err(e) = crate::plugin::PluginStorage::remove(&crate::plugin::PluginStorage::default_dir(), instance_id) {
+            log::warn!("[{}] 删除账户存储失败: {}", instance_id, e);
+        }
+        if let Err(e) = crate::plugin::PluginCache::remove(&crate::plugin::PluginCache::default_dir(), instance_id) {
+            log::warn!("[{}] 删除账户缓存失败: {}", instance_id, e);
+        }
+        crate::plugin::HttpCache::shared().clear_plugin(instance_id);
+        log::info!("已移除插件账户: {}", instance_id);
+        Ok(())
+    }
+
+    /// 插件所有账户的数据（默认账户在前，其余按实例 ID 排序）
+    pub async fn get_plugin_accounts(&self, plugin_id: &str) -> Result<Vec<PluginAccountData>, LifecycleError> {
+        let plugins = self.plugins.read().await;
+        if !plugins.get(plugin_id).is_some_and(|p| p.account_label.is_none()) {
+            return Err(LifecycleError::PluginNotFound(plugin_id.to_string()));
+        }
+        let mut accounts: Vec<&PluginInstance> = plugins
+            .values()
+            .filter(|p| p.id == plugin_id || p.is_account_of(plugin_id))
+            .collect();
+        accounts.sort_by(|a, b| {
+            (a.account_label.is_some(), &a.id).cmp(&(b.account_label.is_some(), &b.id))
+        });
+        Ok(accounts
+            .into_iter()
+            .map(|p| PluginAccountData {
+                instance_id: p.id.clone(),
+                account_label: p.account_label.clone(),
+                enabled: p.enabled,
+                data: p.cached_data.clone(),
+            })
+            .collect())
+    }
+
+    /// 插件的其他账户实例 ID
+    async fn account_ids(&self, plugin_id: &str) -> Vec<String> {
+        self.plugins
+            .read()
+            .await
+            .values()
+            .filter(|p| p.is_account_of(plugin_id))
+            .map(|p| p.id.clone())
+            .collect()
+    }
+
     // ========================================================================
     // 配置管理
     // ========================================================================
 
-    /// 获取插件配置
+    /// 获取插件配置（secret 字段只含 Keychain 引用）
     pub async fn get_plugin_config(&self, id: &str) -> Option<HashMap<String, serde_json::Value>> {
         self.plugins
             .read()
@@ -1489,52 +3427,206 @@ impl PluginManager {
     }
 
     /// 设置插件配置
+    ///
+    /// 先按注册的 configSchema 校验，不通过时返回字段错误且不修改配置；
+    /// secret 字段写入 Keychain，内存和 state.json 中只保留引用；
+    /// 保存成功后才发送配置变更通知
     pub async fn set_plugin_config(
         &self,
         id: &str,
-        config: HashMap<String, serde_json::Value>,
+        mut config: HashMap<String, serde_json::Value>,
     ) -> Result<(), LifecycleError> {
-        let mut plugins = self.plugins.write().await;
+        if !self.plugins.read().await.contains_key(id) {
+            return Err(LifecycleError::PluginNotFound(id.to_string()));
+        }
 
-        if let Some(plugin) = plugins.get_mut(id) {
-            plugin.config = config;
-            log::info!("已更新插件配置: {}", id);
-            Ok(())
-        } else {
-            Err(LifecycleError::PluginLoad(format!("插件不存在: {}", id)))
+        let validation = self.config_manager.validate(id, &config).await;
+        if !validation.valid {
+            return Err(LifecycleError::ConfigInvalid(validation.field_errors));
+        }
+        self.store_secret_fields(id, &mut config).await?;
+
+        {
+            let mut plugins = self.plugins.write().await;
+            let Some(plugin) = plugins.get_mut(id) else {
+                return Err(LifecycleError::PluginNotFound(id.to_string()));
+            };
+            plugin.config = config.clone();
         }
+        self.plugin_state.set_config(id, &config);
+        self.fetch_cache.invalidate_plugin(id).await;
+        log::info!("已更新插件配置: {}", id);
+
+        self.config_manager.notify_config_changed(id, &config).await;
+        Ok(())
     }
 
-    /// 验证插件配置 (基础验证，可扩展)
-    pub async fn validate_plugin_config(
+    /// 将 secret 字段的明文写入 Keychain 并替换为 `{ secretRef: 字段名 }`
+    ///
+    /// 返回是否有字段被替换
+    async fn store_secret_fields(
+        &self,
+        id: &str,
+        config: &mut HashMap<String, serde_json::Value>,
+    ) -> Result<bool, LifecycleError> {
+        let service = secrets_service(id);
+        let mut stored = false;
+        for field in self.config_manager.secret_fields(id).await {
+            let Some(serde_json::Value::String(value)) = config.get(&field) else {
+                continue;
+            };
+            self.secret_store
+                .set(&service, &field, value)
+                .map_err(|e| LifecycleError::SecretStore(format!("{}: {}", field, e)))?;
+            config.insert(field.clone(), serde_json::json!({ SECRET_REF_FIELD: field }));
+            stored = true;
+        }
+        Ok(stored)
+    }
+
+    /// 执行插件前将 Keychain 引用替换为明文（仅存在于本次调用）
+    ///
+    /// 引用的密钥不存在或读取失败时移除该字段，由插件按缺少配置处理
+    fn resolve_secret_config(
         &self,
         id: &str,
         config: &HashMap<String, serde_json::Value>,
-    ) -> ValidationResult {
-        let plugins = self.plugins.read().await;
+    ) -> HashMap<String, serde_json::Value> {
+        let service = secrets_service(id);
+        config
+            .iter()
+            .filter_map(|(key, value)| {
+                let Some(name) = value.get(SECRET_REF_FIELD).and_then(|v| v.as_str()) else {
+                    return Some((key.clone(), value.clone()));
+                };
+                match self.secret_store.get(&service, name) {
+                    Ok(Some(secret)) => Some((key.clone(), serde_json::Value::String(secret))),
+                    Ok(None) => {
+                        log::warn!("[{}] 配置 {} 引用的密钥不存在: {}", id, key, name);
+                        None
+                    }
+                    Err(e) => {
+                        log::warn!("[{}] 读取配置 {} 的密钥失败: {}", id, key, e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
 
-        if plugins.get(id).is_none() {
-            return ValidationResult {
-                valid: false,
-                message: Some(format!("插件不存在: {}", id)),
-                field_errors: None,
-            };
+    /// 各插件已获授予的密钥名称（`secrets:<name>` 权限，受限开发者插件不含）
+    pub async fn granted_secret_names(&self) -> Vec<(String, HashSet<String>)> {
+        self.plugins
+            .read()
+            .await
+            .values()
+            .map(|p| (p.id.clone(), declared_secrets(&p.granted_permissions())))
+            .filter(|(_, names)| !names.is_empty())
+            .collect()
+    }
+
+    /// 插件可由用户填写的密钥名称（已授予的 `secrets:<name>` 与 configSchema 中的 secret 字段，已排序）
+    pub async fn secret_names(&self, id: &str) -> Vec<String> {
+        let mut names: BTreeSet<String> = match self.plugins.read().await.get(id) {
+            Some(plugin) => declared_secrets(&plugin.granted_permissions()).into_iter().collect(),
+            None => return Vec::new(),
+        };
+        names.extend(self.config_manager.secret_fields(id).await);
+        names.into_iter().collect()
+    }
+
+    /// 将用户填写的密钥写入 Keychain，并在插件配置中写入 `{ secretRef: 名称 }`
+    pub async fn store_plugin_secret(&self, id: &str, name: &str, value: &str) -> Result<(), LifecycleError> {
+        if !self.secret_names(id).await.iter().any(|n| n == name) {
+            return Err(LifecycleError::SecretStore(format!("插件 {} 未声明密钥 {}", id, name)));
         }
+        self.secret_store
+            .set(&secrets_service(id), name, value)
+            .map_err(|e| LifecycleError::SecretStore(format!("{}: {}", name, e)))?;
+
+        let mut config = self.get_plugin_config(id).await.unwrap_or_default();
+        config.insert(name.to_string(), serde_json::json!({ SECRET_REF_FIELD: name }));
+        self.set_plugin_config(id, config).await?;
+        log::info!("[{}] 已写入密钥 {}", id, name);
+        Ok(())
+    }
 
-        // 基础验证：检查配置项是否为空
-        if config.is_empty() {
-            return ValidationResult {
-                valid: true,
-                message: Some("配置为空".to_string()),
-                field_errors: None,
+    /// 验证插件配置
+    ///
+    /// 插件导出 validateConfig 时在沙盒中以待保存的配置调用（受超时保护），
+    /// 返回的 fieldErrors 合并到结果中；未导出时按 manifest 的 configSchema 校验。
+    pub async fn validate_plugin_config(
+        &self,
+        id: &str,
+        config: &HashMap<String, serde_json::Value>,
+    ) -> ValidationResult {
+        let prepared = {
+            let plugins = self.plugins.read().await;
+            let Some(plugin) = plugins.get(id) else {
+                return ValidationResult {
+                    valid: false,
+                    message: Some(format!("插件不存在: {}", id)),
+                    field_errors: None,
+                };
             };
+            // 内置插件没有 validateConfig，只按 configSchema 校验
+            plugin.builtin.is_none().then(|| {
+                plugin.entry_module().map(|module| {
+                    (
+                        module,
+                        plugin.granted_permissions(),
+                        plugin.manifest.allowed_hosts.clone(),
+                        plugin.manifest.request_budget_per_minute(),
+                    )
+                })
+            })
+        };
+
+        match prepared {
+            None => {}
+            Some(Ok((module, permissions, allowed_hosts, budget))) => {
+                let resolved = self.resolve_secret_config(id, config);
+                let run = async {
+                    let invocation = Self::build_validate_invocation(id, &resolved)?;
+                    self.execute_in_sandbox(id, &module, &invocation, &permissions, allowed_hosts, &resolved, budget)
+                        .await
+                };
+                match tokio::time::timeout(LIFECYCLE_HOOK_TIMEOUT, run).await {
+                    // 未导出 validateConfig
+                    Ok(Ok(serde_json::Value::Null)) => {}
+                    Ok(Ok(value)) => return validation_result_from_hook(&value),
+                    Ok(Err(e)) => {
+                        return ValidationResult {
+                            valid: false,
+                            message: Some(format!("validateConfig 执行失败: {}", e)),
+                            field_errors: None,
+                        }
+                    }
+                    Err(_) => {
+                        return ValidationResult {
+                            valid: false,
+                            message: Some(format!(
+                                "validateConfig 执行超时 ({}s)",
+                                LIFECYCLE_HOOK_TIMEOUT.as_secs()
+                            )),
+                            field_errors: None,
+                        }
+                    }
+                }
+            }
+            Some(Err(e)) => log::warn!("[{}] 读取入口模块失败，仅按 configSchema 校验: {}", id, e),
         }
 
-        // TODO: 可以根据 manifest 中的 configSchema 进行更详细的验证
+        let result = self.config_manager.validate(id, config).await;
+        let field_errors: HashMap<String, String> = result
+            .field_errors
+            .into_iter()
+            .map(|error| (error.field, error.message))
+            .collect();
         ValidationResult {
-            valid: true,
-            message: None,
-            field_errors: None,
+            valid: result.valid,
+            message: result.message,
+            field_errors: (!field_errors.is_empty()).then_some(field_errors),
         }
     }
 
@@ -1546,23 +3638,143 @@ impl PluginManager {
     ///
     /// P1 修复：使用读锁，支持并发健康查询。
     pub async fn get_plugin_health(&self, id: &str) -> Option<PluginHealth> {
-        self.plugins
+        let health = self
+            .plugins
             .read()
             .await
             .get(id)
-            .map(|p| p.to_health())
+            .map(|p| p.to_network_health(self.is_offline()))?;
+        Some(self.with_throttle_stats(health).await)
     }
 
     /// 获取所有插件健康状态
     ///
     /// P1 修复：使用读锁，支持并发健康查询。
     pub async fn get_all_health(&self) -> Vec<PluginHealth> {
-        self.plugins
+        let health: Vec<PluginHealth> = self
+            .plugins
             .read()
             .await
             .values()
-            .map(|p| p.to_health())
-            .collect()
+            .map(|p| p.to_network_health(self.is_offline()))
+            .collect();
+        let mut result = Vec::with_capacity(health.len());
+        for h in health {
+            result.push(self.with_throttle_stats(h).await);
+        }
+        result
+    }
+
+    /// 获取插件沙盒资源占用统计（插件不存在时返回 None）
+    pub async fn get_plugin_resource_stats(&self, id: &str) -> Option<PluginResourceStats> {
+        let plugins = self.plugins.read().await;
+        let plugin = plugins.get(id)?;
+        let window = &plugin.sliding_window;
+        let memory_limit_bytes = plugin.sandbox_config.memory_limit as u64;
+        let last = window.last_resources();
+        Some(PluginResourceStats {
+            plugin_id: id.to_string(),
+            executions: window.resource_sample_count(),
+            last_cpu_time_ms: last.map(|r| r.cpu_time_ms).unwrap_or(0.0),
+            avg_cpu_time_ms: window.avg_cpu_time_ms(),
+            max_cpu_time_ms: window.max_cpu_time_ms(),
+            last_memory_bytes: last.map(|r| r.memory_bytes).unwrap_or(0),
+            peak_memory_bytes: window.peak_memory_bytes(),
+            memory_limit_bytes,
+        })
+    }
+
+    /// 插件能力描述：解析后的 manifest 及运行时实际生效的权限、API、订阅和限制
+    pub async fn describe_plugin(&self, id: &str) -> Result<PluginDescription, LifecycleError> {
+        let plugins = self.plugins.read().await;
+        let plugin = plugins
+            .get(id)
+            .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?;
+        let manifest = &plugin.manifest;
+
+        // 与沙盒执行时一致：先按开发者信任状态授予，再按信任级别过滤
+        let granted = plugin.trust_level.filter_permissions(&plugin.granted_permissions());
+        let restricted = manifest
+            .permissions
+            .iter()
+            .filter(|p| !granted.contains(p))
+            .cloned()
+            .collect();
+        let injected_apis = match plugin.builtin {
+            Some(_) => Vec::new(),
+            None => SandboxApis::from_permissions(&granted)
+                .names()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        };
+
+        Ok(PluginDescription {
+            plugin_id: manifest.id.clone(),
+            instance_id: plugin.id.clone(),
+            manifest: serde_json::to_value(manifest).unwrap_or_default(),
+            trust_level: plugin.trust_level,
+            granted_permissions: granted,
+            restricted_permissions: restricted,
+            injected_apis,
+            exposed_methods: manifest.exposed_methods.clone(),
+            subscribed_events: manifest.subscribed_events.clone(),
+            allowed_hosts: manifest.allowed_hosts.clone(),
+            limits: PluginSandboxLimits {
+                memory_limit_bytes: plugin.sandbox_config.memory_limit as u64,
+                timeout_ms: plugin.sandbox_config.execution_timeout.as_millis() as u64,
+                max_concurrent_fetches: plugin.trust_level.limits().max_concurrent_fetches,
+                max_requests_per_minute: manifest.request_budget_per_minute(),
+            },
+        })
+    }
+
+    /// 生成插件诊断包（日志、最后错误、清单、健康快照、脱敏配置、版本信息），返回 zip 路径
+    pub async fn generate_diagnostics(&self, id: &str) -> Result<DiagnosticsFile, LifecycleError> {
+        let not_found = || LifecycleError::PluginNotFound(id.to_string());
+        let plugin = self.get_plugin(id).await.ok_or_else(not_found)?;
+        let (manifest, config, last_error) = {
+            let plugins = self.plugins.read().await;
+            let instance = plugins.get(id).ok_or_else(not_found)?;
+            (
+                serde_json::to_value(&instance.manifest).unwrap_or_default(),
+                instance.config.clone(),
+                instance.last_error.clone(),
+            )
+        };
+        let secret_fields = self.config_manager.secret_fields(id).await;
+
+        let bundle = DiagnosticsBundle {
+            report: DiagnosticsReport {
+                generated_at: Utc::now(),
+                runtime: RuntimeInfo::current(),
+                plugin,
+                health: self.get_plugin_health(id).await,
+                last_error,
+                resources: self.get_plugin_resource_stats(id).await,
+            },
+            manifest,
+            config: redact_config(&config, &secret_fields),
+            logs: self.plugin_logs.query(id, None, None),
+        };
+        bundle
+            .write_to(&diagnostics_dir(self.plugins_dir()))
+            .map_err(|e| LifecycleError::PluginLoad(format!("生成诊断包失败: {}", e)))
+    }
+
+    /// 填充请求预算限流次数和越权主机（限流器、权限检查器独立于插件表，释放读锁后查询）
+    async fn with_throttle_stats(&self, mut health: PluginHealth) -> PluginHealth {
+        if let Some((_, throttled)) = self.rate_limiter.plugin_stats(&health.plugin_id).await {
+            health.throttled_requests = throttled;
+        }
+        health.blocked_hosts = self.permission_checker.blocked_hosts(&health.plugin_id);
+        if !health.blocked_hosts.is_empty() && health.suggested_action.is_none() {
+            health.suggested_action = Some(format!(
+                "插件尝试访问未声明的主机（{}），请确认插件来源或联系作者补充 fetch 权限",
+                health.blocked_hosts.join(", ")
+            ));
+        }
+        health
     }
 
     // ========================================================================
@@ -1588,6 +3800,91 @@ impl PluginManager {
             .collect()
     }
 
+    /// 获取已启用插件缓存数据的聚合摘要
+    pub async fn get_summary(&self) -> DataSummary {
+        let data: Vec<PluginData> = self
+            .plugins
+            .read()
+            .await
+            .values()
+            .filter(|p| p.enabled)
+            .filter_map(|p| p.cached_data.clone())
+            .collect();
+        self.summarize_with_spend(&data, Utc::now())
+    }
+
+    /// 聚合数据摘要并填入最近 30 天的预估花费
+    fn summarize_with_spend(&self, data: &[PluginData], now: DateTime<Utc>) -> DataSummary {
+        let mut summary = summarize(data, now);
+        summary.estimated_spend = self.cost_estimator.total_spend(
+            data.iter().map(|d| d.plugin_id()),
+            CostRange::Month,
+            now,
+        );
+        summary
+    }
+
+    /// 估算插件在区间内按模型价格计算的花费
+    pub async fn estimate_cost(&self, id: &str, range: CostRange) -> Result<CostEstimate, LifecycleError> {
+        if !self.plugins.read().await.contains_key(id) {
+            return Err(LifecycleError::PluginNotFound(id.to_string()));
+        }
+        Ok(self.cost_estimator.estimate(id, range, Utc::now()))
+    }
+
+    /// 价格表与 token 账本
+    pub fn cost_estimator(&self) -> &CostEstimator {
+        &self.cost_estimator
+    }
+
+    /// 在同一个插件表快照上执行多个只读查询
+    ///
+    /// 整个批次只获取一次读锁，期间刷新/启停等写操作被阻塞，各结果彼此一致。
+    pub async fn batch_query(&self, queries: &[BatchQuery]) -> BatchResponse {
+        let plugins = self.plugins.read().await;
+        let now = Utc::now();
+
+        let mut results = Vec::with_capacity(queries.len());
+        for query in queries {
+            let result = match query {
+                BatchQuery::PluginList => {
+                    BatchResult::PluginList(plugins.values().map(|p| p.to_info()).collect())
+                }
+                BatchQuery::GetAllData => BatchResult::GetAllData(
+                    plugins.values().filter_map(|p| p.cached_data.clone()).collect(),
+                ),
+                BatchQuery::GetAllHealth => {
+                    let mut health = Vec::with_capacity(plugins.len());
+                    for p in plugins.values() {
+                        health.push(self.with_throttle_stats(p.to_network_health(self.is_offline())).await);
+                    }
+                    BatchResult::GetAllHealth(health)
+                }
+                BatchQuery::GetSummary => {
+                    let data: Vec<PluginData> = plugins
+                        .values()
+                        .filter(|p| p.enabled)
+                        .filter_map(|p| p.cached_data.clone())
+                        .collect();
+                    BatchResult::GetSummary(self.summarize_with_spend(&data, now))
+                }
+                BatchQuery::GetPluginData { id } => {
+                    BatchResult::GetPluginData(plugins.get(id).and_then(|p| p.cached_data.clone()))
+                }
+                BatchQuery::GetPluginHealth { id } => BatchResult::GetPluginHealth(match plugins.get(id) {
+                    Some(p) => Some(self.with_throttle_stats(p.to_network_health(self.is_offline())).await),
+                    None => None,
+                }),
+            };
+            results.push(result);
+        }
+
+        BatchResponse {
+            results,
+            snapshot_at: format_rfc3339(now),
+        }
+    }
+
     /// 设置插件缓存数据 (供运行时调用)
     pub async fn set_plugin_data(
         &self,
@@ -1598,23 +3895,302 @@ impl PluginManager {
         let mut plugins = self.plugins.write().await;
 
         if let Some(plugin) = plugins.get_mut(id) {
+            let now = Utc::now();
+            self.usage_history.record(&data, now);
+            self.cost_estimator.record(&data, now);
+            // 发布 plugin:{id}:data_updated，订阅的事件插件在 onEvent 中响应（队列满时丢弃）
+            if let Ok(payload) = serde_json::to_value(&data) {
+                if let Err(e) = self.event_bus.emit_sync(id, "data_updated", payload) {
+                    log::debug!("[{}] 发布 data_updated 事件失败: {}", id, e);
+                }
+            }
+            // 推送到所有窗口（队列满时丢弃）
+            let update = DataUpdate {
+                plugin_id: id.to_string(),
+                data: data.clone(),
+            };
+            if let Err(e) = self.data_update_tx.try_send(update) {
+                log::debug!("[{}] 数据更新广播队列不可用: {}", id, e);
+            }
             plugin.cached_data = Some(data);
             plugin.record_success(latency_ms);
+            self.persist_defunct(plugin);
+            self.publish_health_change(plugin);
             Ok(())
         } else {
-            Err(LifecycleError::PluginLoad(format!("插件不存在: {}", id)))
+            Err(LifecycleError::PluginNotFound(id.to_string()))
+        }
+    }
+
+    /// 记录 fetchData 缓存命中/未命中
+    async fn record_fetch_cache(&self, id: &str, hit: bool) {
+        if let Some(plugin) = self.plugins.write().await.get_mut(id) {
+            if hit {
+                plugin.cache_hits += 1;
+            } else {
+                plugin.cache_misses += 1;
+            }
+        }
+    }
+
+    /// 调用插件的生命周期钩子（onLoad/onUnload）
+    ///
+    /// 钩子在沙盒中执行并受超时保护；执行失败或超时计入插件健康统计，不影响启用/禁用本身。
+    /// 入口模块无法读取时只记录日志，由后续 fetchData 报告错误。
+    pub async fn run_lifecycle_hook(&self, id: &str, hook: LifecycleHook) {
+        let Some((module, permissions, allowed_hosts, config, budget)) =
+            self.prepare_export_call(id, hook.export_name(), false).await
+        else {
+            return;
+        };
+
+        let run = async {
+            let invocation = Self::build_hook_invocation(id, &config, hook)?;
+            self.execute_in_sandbox(id, &module, &invocation, &permissions, allowed_hosts, &config, budget)
+                .await
+        };
+        let error = match tokio::time::timeout(LIFECYCLE_HOOK_TIMEOUT, run).await {
+            Ok(Ok(_)) => return,
+            Ok(Err(e)) => e.to_string(),
+            Err(_) => format!("执行超时 ({}s)", LIFECYCLE_HOOK_TIMEOUT.as_secs()),
+        };
+        log::warn!("[{}] {} 执行失败: {}", id, hook.export_name(), error);
+        if let Err(e) = self
+            .record_plugin_failure(id, format!("{} 执行失败: {}", hook.export_name(), error))
+            .await
+        {
+            log::warn!("[{}] 记录执行失败统计失败: {}", id, e);
+        }
+    }
+
+    /// 准备调用插件导出函数所需的入口模块、权限、网络白名单、配置（已解析 secret）和请求预算
+    ///
+    /// 插件不存在（或 `require_enabled` 时未启用）、内置插件、入口模块无法读取时返回 None
+    async fn prepare_export_call(
+        &self,
+        id: &str,
+        export: &str,
+        require_enabled: bool,
+    ) -> Option<(PluginModule, Vec<String>, Option<Vec<String>>, HashMap<String, serde_json::Value>, u32)> {
+        let prepared = {
+            let plugins = self.plugins.read().await;
+            let plugin = plugins
+                .get(id)
+                .filter(|p| p.enabled || !require_enabled)
+                .filter(|p| p.builtin.is_none())?;
+            plugin.entry_module().map(|module| {
+                (
+                    module,
+                    plugin.granted_permissions(),
+                    plugin.manifest.allowed_hosts.clone(),
+                    plugin.config.clone(),
+                    plugin.manifest.request_budget_per_minute(),
+                )
+            })
+        };
+        match prepared {
+            Ok((module, permissions, allowed_hosts, config, budget)) => {
+                let config = self.resolve_secret_config(id, &config);
+                Some((module, permissions, allowed_hosts, config, budget))
+            }
+            Err(e) => {
+                log::warn!("[{}] 读取入口模块失败，跳过 {}: {}", id, export, e);
+                None
+            }
+        }
+    }
+
+    // ========================================================================
+    // 持久定时器 (Phase 2.2.9)
+    // ========================================================================
+
+    /// 释放插件登记的资源并取消其持久定时器，返回释放的资源数量
+    fn release_resources(&self, plugin: &mut PluginInstance) -> usize {
+        let released = plugin.resources.clear();
+        let timer_ids: Vec<u64> = released
+            .iter()
+            .filter(|r| r.resource_type == ResourceType::Timer)
+            .map(|r| r.id)
+            .collect();
+        let cancelled = self.host_timers.cancel_resources(&plugin.id, &timer_ids);
+        if cancelled > 0 {
+            log::debug!("[{}] 已取消 {} 个持久定时器", plugin.id, cancelled);
         }
+        released.len()
+    }
+
+    /// 处理一次执行中登记的持久定时器请求
+    ///
+    /// 只有启用的插件可以登记定时器（test_run 等未启用时的执行忽略登记），
+    /// 每个定时器在插件的 ResourceRegistry 中占一个 Timer 资源
+    fn apply_timer_requests(&self, plugin: &mut PluginInstance, requests: Vec<HostTimerRequest>) {
+        for request in requests {
+            match request {
+                HostTimerRequest::Schedule {
+                    name,
+                    delay_ms,
+                    interval_ms,
+                    data,
+                } => {
+                    if !plugin.enabled {
+                        log::debug!("[{}] 插件未启用，忽略持久定时器 {}", plugin.id, name);
+                        continue;
+                    }
+                    if !self.host_timers.contains(&plugin.id, &name)
+                        && self.host_timers.count(&plugin.id) >= MAX_HOST_TIMERS_PER_PLUGIN
+                    {
+                        log::warn!(
+                            "[{}] 持久定时器数量已达上限 ({})，忽略 {}",
+                            plugin.id,
+                            MAX_HOST_TIMERS_PER_PLUGIN,
+                            name
+                        );
+                        continue;
+                    }
+                    let resource_id = plugin.resources.register(ResourceType::Timer);
+                    if let Some(replaced) = self.host_timers.schedule(
+                        &plugin.id,
+                        &name,
+                        resource_id,
+                        Duration::from_millis(delay_ms),
+                        interval_ms.map(Duration::from_millis),
+                        data,
+                    ) {
+                        plugin.resources.unregister(replaced);
+                    }
+                }
+                HostTimerRequest::Cancel { name } => {
+                    if let Some(resource_id) = self.host_timers.cancel(&plugin.id, &name) {
+                        plugin.resources.unregister(resource_id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// 执行持久定时器触发：在新的沙盒中调用插件导出的 onTimer(name, data, context)
+    ///
+    /// 定时器已取消/替换、插件未启用或上次触发仍在执行时跳过；执行失败计入插件健康统计
+    pub async fn run_timer(&self, fire: TimerFire) {
+        if !self.host_timers.is_active(&fire) {
+            return;
+        }
+        // 离线期间暂停重复定时器（恢复连接后统一刷新）
+        if fire.repeating && self.is_offline() {
+            log::debug!("[{}] 离线中，跳过定时器 {} 的本次触发", fire.plugin_id, fire.name);
+            return;
+        }
+        // 一次性定时器触发后注销资源
+        if self.host_timers.finish(&fire) {
+            if let Some(plugin) = self.plugins.write().await.get_mut(&fire.plugin_id) {
+                plugin.resources.unregister(fire.resource_id);
+            }
+        }
+        if !self.host_timers.begin(&fire) {
+            log::debug!("[{}] 定时器 {} 上次触发仍在执行，跳过", fire.plugin_id, fire.name);
+            return;
+        }
+
+        let id = fire.plugin_id.as_str();
+        if let Some((module, permissions, allowed_hosts, config, budget)) =
+            self.prepare_export_call(id, "onTimer", true).await
+        {
+            let result = match Self::build_timer_invocation(id, &config, &fire.name, &fire.data) {
+                Ok(invocation) => {
+                    self.execute_in_sandbox(id, &module, &invocation, &permissions, allowed_hosts, &config, budget)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                log::warn!("[{}] 定时器 {} 的 onTimer 执行失败: {}", id, fire.name, e);
+                if let Err(e) = self
+                    .record_plugin_failure(id, format!("onTimer({}) 执行失败: {}", fire.name, e))
+                    .await
+                {
+                    log::warn!("[{}] 记录执行失败统计失败: {}", id, e);
+                }
+            }
+        }
+        self.host_timers.end(&fire);
+    }
+
+    /// 执行事件投递：在订阅插件的沙盒中调用 onEvent(event, data, context)
+    ///
+    /// 超时与 EventBus 处理超时一致；失败和超时计入插件健康统计，
+    /// 事件插件 (pluginType = event) 的成功执行同样计入。插件未启用时忽略事件。
+    pub async fn deliver_event(&self, delivery: EventDelivery) {
+        let EventDelivery {
+            plugin_id,
+            event,
+            data,
+            reply,
+        } = delivery;
+        let id = plugin_id.as_str();
+        let Some((module, permissions, allowed_hosts, config, budget)) =
+            self.prepare_export_call(id, "onEvent", true).await
+        else {
+            let _ = reply.send(Ok(()));
+            return;
+        };
+
+        let start = std::time::Instant::now();
+        let timeout = Duration::from_millis(self.event_bus.config().handler_timeout_ms);
+        let run = async {
+            let invocation = Self::build_event_invocation(id, &config, &event, &data)?;
+            self.execute_in_sandbox(id, &module, &invocation, &permissions, allowed_hosts, &config, budget)
+                .await
+        };
+        let result = match tokio::time::timeout(timeout, run).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err(format!("执行超时 ({}ms)", timeout.as_millis())),
+        };
+
+        {
+            let mut plugins = self.plugins.write().await;
+            if let Some(plugin) = plugins.get_mut(id) {
+                match &result {
+                    Ok(()) if plugin.manifest.get_plugin_type() == PluginType::Event => {
+                        plugin.record_success(start.elapsed().as_secs_f64() * 1000.0);
+                    }
+                    Ok(()) => {}
+                    Err(e) => {
+                        log::warn!("[{}] onEvent({}) 执行失败: {}", id, event, e);
+                        plugin.record_failure(format!("onEvent({}) 执行失败: {}", event, e));
+                    }
+                }
+                self.persist_defunct(plugin);
+                self.publish_health_change(plugin);
+                self.enforce_watchdog(plugin).await;
+            }
+        }
+        let _ = reply.send(result);
+    }
+
+    /// 插件正在进行的服务商维护
+    pub async fn plugin_maintenance(&self, id: &str) -> Option<ActiveMaintenance> {
+        self.plugins.read().await.get(id)?.maintenance(Utc::now())
     }
 
     /// 记录插件执行失败
+    ///
+    /// 离线期间的网络错误不计入健康统计（恢复连接后会立即重新刷新）。
     pub async fn record_plugin_failure(&self, id: &str, error: String) -> Result<(), LifecycleError> {
+        if self.is_offline() && PluginErrorType::classify(&error) == PluginErrorType::NetworkError {
+            log::debug!("[{}] 离线期间的网络错误不计入健康统计: {}", id, error);
+            return Ok(());
+        }
         let mut plugins = self.plugins.write().await;
 
         if let Some(plugin) = plugins.get_mut(id) {
             plugin.record_failure(error);
+            self.persist_defunct(plugin);
+            self.publish_health_change(plugin);
+            self.enforce_watchdog(plugin).await;
             Ok(())
         } else {
-            Err(LifecycleError::PluginLoad(format!("插件不存在: {}", id)))
+            Err(LifecycleError::PluginNotFound(id.to_string()))
         }
     }
 
@@ -1643,18 +4219,18 @@ impl PluginManager {
 
         let plugin = plugins
             .get(id)
-            .ok_or_else(|| LifecycleError::PluginLoad(format!("插件不存在: {}", id)))?;
+            .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?;
 
         // 检查插件是否启用
         if !plugin.enabled {
-            return Err(LifecycleError::PluginLoad(format!("插件未启用: {}", id)));
+            return Err(LifecycleError::PluginDisabled(id.to_string()));
         }
 
         // 使用安全 API 直接读取内容（无 TOCTOU 窗口）
         let content = plugin.read_entry_content()?;
 
         // 获取权限列表
-        let permissions = plugin.manifest.permissions.clone();
+        let permissions = plugin.granted_permissions();
 
         log::debug!("安全获取插件执行内容: {} ({} bytes)", id, content.len());
         Ok((content, permissions))
@@ -1673,11 +4249,11 @@ impl PluginManager {
 
         let plugin = plugins
             .get(id)
-            .ok_or_else(|| LifecycleError::PluginLoad(format!("插件不存在: {}", id)))?;
+            .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?;
 
         // 检查插件是否启用
         if !plugin.enabled {
-            return Err(LifecycleError::PluginLoad(format!("插件未启用: {}", id)));
+            return Err(LifecycleError::PluginDisabled(id.to_string()));
         }
 
         // 获取安全校验后的入口路径
@@ -1686,7 +4262,7 @@ impl PluginManager {
         let entry_path = plugin.entry_path()?;
 
         // 获取权限列表
-        let permissions = plugin.manifest.permissions.clone();
+        let permissions = plugin.granted_permissions();
 
         Ok((entry_path, permissions))
     }
@@ -1700,7 +4276,7 @@ impl PluginManager {
             log::info!("插件状态更新为运行中: {}", id);
             Ok(())
         } else {
-            Err(LifecycleError::PluginLoad(format!("插件不存在: {}", id)))
+            Err(LifecycleError::PluginNotFound(id.to_string()))
         }
     }
 
@@ -1713,7 +4289,7 @@ impl PluginManager {
             log::info!("插件状态更新为已卸载: {}", id);
             Ok(())
         } else {
-            Err(LifecycleError::PluginLoad(format!("插件不存在: {}", id)))
+            Err(LifecycleError::PluginNotFound(id.to_string()))
         }
     }
 
@@ -1724,172 +4300,785 @@ impl PluginManager {
     /// 执行插件的 fetchData 函数获取数据
     ///
     /// 1. 获取插件代码和配置
-    /// 2. 转换 ES Module 为可执行代码
-    /// 3. 在沙盒中执行并调用 fetchData
+    /// 2. 生成调用 fetchData 的脚本
+    /// 3. 在沙盒中按 ES Module 求值插件并调用 fetchData
     /// 4. 返回 PluginData
-    pub async fn execute_fetch_data(&self, id: &str) -> Result<PluginData, LifecycleError> {
+    ///
+    /// 缓存有效期内直接返回上次结果（不访问网络），`force` 为 true 时绕过缓存。
+    pub async fn execute_fetch_data(&self, id: &str, force: bool) -> Result<PluginData, LifecycleError> {
         let start = std::time::Instant::now();
 
         // 1. 获取插件信息
-        let (code, permissions, config, data_type) = {
+        let (source, permissions, allowed_hosts, config, data_type, payload_schema, refresh_interval_ms, budget, retry_policy) = {
             let plugins = self.plugins.read().await;
             let plugin = plugins
                 .get(id)
-                .ok_or_else(|| LifecycleError::PluginLoad(format!("插件不存在: {}", id)))?;
+                .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?;
 
             if !plugin.enabled {
-                return Err(LifecycleError::PluginLoad(format!("插件未启用: {}", id)));
+                return Err(LifecycleError::PluginDisabled(id.to_string()));
             }
 
-            let code = plugin.read_entry_content()?;
-            let permissions = plugin.manifest.permissions.clone();
+            let source = FetchSource::of(plugin)?;
+            let permissions = plugin.granted_permissions();
+            let allowed_hosts = plugin.manifest.allowed_hosts.clone();
             let config = plugin.config.clone();
             let data_type = plugin.manifest.data_type.clone();
+            let payload_schema = plugin.manifest.payload_schema.clone();
+            let refresh_interval_ms = plugin.manifest.refresh_interval_ms;
+            let budget = plugin.manifest.request_budget_per_minute();
+            let retry_policy = plugin.manifest.retry_policy.unwrap_or_default();
 
-            (code, permissions, config, data_type)
+            (source, permissions, allowed_hosts, config, data_type, payload_schema, refresh_interval_ms, budget, retry_policy)
         };
 
-        // 2. 转换 ES Module 为可执行代码
-        let executable_code = Self::transform_esm_to_executable(&code, id, &config)?;
+        // 缓存命中时不执行插件
+        let cache_key = CacheKey::new(id, "fetchData", &serde_json::Value::Null);
+        let cached = self
+            .fetch_cache
+            .lookup(&cache_key, force)
+            .await
+            .and_then(|value| serde_json::from_value::<PluginData>(value).ok());
+        self.record_fetch_cache(id, cached.is_some()).await;
+        if let Some(data) = cached {
+            log::debug!("[{}] fetchData 命中缓存", id);
+            return Ok(data);
+        }
 
-        // 3. 创建沙盒运行时并执行
-        let result = self.execute_in_sandbox(&executable_code, &permissions).await?;
+        // 2-4. 转换代码、沙盒执行、解析结果（失败计入健康统计）
+        // 网络错误/超时按插件重试策略指数退避重试，重试耗尽后才记为一次失败
+        let config = self.resolve_secret_config(id, &config);
+        let outcome = async {
+            let executor = RetryExecutor::new(retry_policy.to_retry_config())
+                .map_err(|e| LifecycleError::PluginLoad(format!("无效的重试策略: {}", e)))?;
+            let last_error: std::sync::Mutex<Option<LifecycleError>> = std::sync::Mutex::new(None);
+            let last_error_ref = &last_error;
+            let (source, permissions, allowed_hosts, config) =
+                (&source, &permissions, &allowed_hosts, &config);
+            let data_type = data_type.as_deref();
+            let payload_schema = payload_schema.as_ref();
+
+            let attempt = || async move {
+                let result = async {
+                    let result = self
+                        .run_fetch_source(id, source, permissions, allowed_hosts.clone(), config, budget)
+                        .await?;
+                    let data = Self::parse_fetch_result(id, result, data_type)?;
+
+                    // 不符合 payloadSchema 的 custom 数据不进入缓存，避免渲染器出错
+                    let schema_errors = Self::payload_schema_errors(&data, payload_schema);
+                    if !schema_errors.is_empty() {
+                        return Err(LifecycleError::PluginLoad(format!(
+                            "custom 数据不符合 payloadSchema: {}",
+                            schema_errors.join("; ")
+                        )));
+                    }
+                    Ok(data)
+                }
+                .await;
+                result.map_err(|e| {
+                    let message = e.to_string();
+                    *last_error_ref.lock().unwrap_or_else(|e| e.into_inner()) = Some(e);
+                    FetchAttemptError { message }
+                })
+            };
 
-        // 4. 解析结果为 PluginData
-        let plugin_data = Self::parse_fetch_result(id, result, data_type.as_deref())?;
+            let outcome = executor
+                .execute_with_context(attempt, |retry, delay| {
+                    log::info!("[{}] fetchData 瞬时失败，{:?} 后第 {} 次重试", id, delay, retry);
+                })
+                .await;
+            outcome.map_err(|e| {
+                last_error
+                    .into_inner()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .unwrap_or_else(|| LifecycleError::PluginLoad(e.to_string()))
+            })
+        }
+        .await;
+
+        let plugin_data = match outcome {
+            Ok(data) => data,
+            Err(e) => {
+                // 维护期间的失败是计划内停机，不计入健康统计
+                if let Some(maintenance) = self.plugin_maintenance(id).await {
+                    log::info!("[{}] 服务商维护中（至 {}），忽略失败: {}", id, format_rfc3339(maintenance.until), e);
+                    return Err(e);
+                }
+                if let Err(record_err) = self.record_plugin_failure(id, e.to_string()).await {
+                    log::warn!("[{}] 记录执行失败统计失败: {}", id, record_err);
+                }
+                return Err(e);
+            }
+        };
 
         // 5. 更新缓存和统计
         let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
         self.set_plugin_data(id, plugin_data.clone(), latency_ms).await?;
+        if let Ok(value) = serde_json::to_value(&plugin_data) {
+            self.fetch_cache
+                .set_with_ttl(&cache_key, value, fetch_cache_ttl(refresh_interval_ms))
+                .await;
+        }
 
         log::info!("[{}] fetchData 执行成功, 耗时 {:.2}ms", id, latency_ms);
         Ok(plugin_data)
     }
 
-    /// 刷新所有启用的插件数据
-    pub async fn refresh_all_plugins(&self) -> Vec<Result<PluginData, LifecycleError>> {
-        // 获取所有启用的插件 ID
-        let enabled_ids: Vec<String> = {
+    /// 写入外部推送的数据 (Phase 4.6)
+    ///
+    /// 推送内容与 fetchData 返回值格式相同，经相同的解析和 payloadSchema 校验后写入缓存。
+    /// 仅声明 `acceptsPush` 且已启用的插件接受推送。
+    pub async fn ingest_pushed_data(
+        &self,
+        id: &str,
+        raw: serde_json::Value,
+    ) -> Result<PluginData, LifecycleError> {
+        let (data_type, payload_schema) = {
             let plugins = self.plugins.read().await;
-            plugins
-                .values()
-                .filter(|p| p.enabled)
-                .map(|p| p.id.clone())
-                .collect()
+            let plugin = plugins
+                .get(id)
+                .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?;
+            if !plugin.manifest.accepts_push {
+                return Err(LifecycleError::PluginLoad(format!("插件未声明 acceptsPush: {}", id)));
+            }
+            if !plugin.enabled {
+                return Err(LifecycleError::PluginDisabled(id.to_string()));
+            }
+            (plugin.manifest.data_type.clone(), plugin.manifest.payload_schema.clone())
         };
 
+        let data = Self::parse_fetch_result(id, raw, data_type.as_deref())?;
+        let schema_errors = Self::payload_schema_errors(&data, payload_schema.as_ref());
+        if !schema_errors.is_empty() {
+            return Err(LifecycleError::PluginLoad(format!(
+                "custom 数据不符合 payloadSchema: {}",
+                schema_errors.join("; ")
+            )));
+        }
+
+        self.set_plugin_data(id, data.clone(), 0.0).await?;
+        log::info!("[{}] 已写入推送数据", id);
+        Ok(data)
+    }
+
+    /// 获取参与批量刷新的插件 ID（启用、未失效且不在维护窗口内）
+    pub async fn refreshable_plugin_ids(&self) -> Vec<String> {
+        let now = Utc::now();
+        let plugins = self.plugins.read().await;
+        plugins
+            .values()
+            .filter(|p| p.enabled)
+            .filter(|p| {
+                if p.possibly_defunct {
+                    log::debug!("[{}] 数据源可能已失效，跳过刷新", p.id);
+                }
+                !p.possibly_defunct
+            })
+            .filter(|p| match p.maintenance(now) {
+                Some(maintenance) => {
+                    log::debug!("[{}] 服务商维护中（至 {}），跳过刷新", p.id, format_rfc3339(maintenance.until));
+                    false
+                }
+                None => true,
+            })
+            .map(|p| p.id.clone())
+            .collect()
+    }
+
+    /// 刷新所有启用的插件数据
+    ///
+    /// 可能已失效的插件不参与批量刷新，仍可通过单个插件刷新手动重试。
+    /// `force` 为 true 时绕过 fetchData 结果缓存。
+    pub async fn refresh_all_plugins(&self, force: bool) -> Vec<Result<PluginData, LifecycleError>> {
+        let enabled_ids = self.refreshable_plugin_ids().await;
+
         // 并发执行所有插件
         let mut results = Vec::new();
         for id in enabled_ids {
-            let result = self.execute_fetch_data(&id).await;
+            let result = self.execute_fetch_data(&id, force).await;
             results.push(result);
         }
 
         results
     }
 
-    /// 转换 ES Module 代码为可执行的 IIFE
+    /// 回放解析：将存储或粘贴的 fetchData 原始结果重新解析并校验
+    ///
+    /// 不执行插件代码、不访问网络，也不更新缓存和健康统计，
+    /// 用于在没有用户凭证的情况下复现解析问题。
+    pub async fn replay_parse(
+        &self,
+        id: &str,
+        raw: serde_json::Value,
+    ) -> Result<ReplayParseResult, LifecycleError> {
+        let (data_type, payload_schema) = {
+            let plugins = self.plugins.read().await;
+            let plugin = plugins
+                .get(id)
+                .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?;
+            (plugin.manifest.data_type.clone(), plugin.manifest.payload_schema.clone())
+        };
+
+        let mut result = Self::replay_fetch_result(id, raw, data_type.as_deref());
+        Self::apply_payload_schema(&mut result, payload_schema.as_ref());
+        Ok(result)
+    }
+
+    /// 试运行：执行 fetchData 并校验结果，不更新缓存和健康统计
+    ///
+    /// 供插件作者调试使用，未启用的插件也可试运行；
+    /// 执行失败时返回 valid=false 的结果而非错误。
+    pub async fn test_run(&self, id: &str) -> Result<ReplayParseResult, LifecycleError> {
+        let (source, permissions, allowed_hosts, config, data_type, payload_schema, budget) = {
+            let plugins = self.plugins.read().await;
+            let plugin = plugins
+                .get(id)
+                .ok_or_else(|| LifecycleError::PluginNotFound(id.to_string()))?;
+            (
+                FetchSource::of(plugin)?,
+                plugin.granted_permissions(),
+                plugin.manifest.allowed_hosts.clone(),
+                plugin.config.clone(),
+                plugin.manifest.data_type.clone(),
+                plugin.manifest.payload_schema.clone(),
+                plugin.manifest.request_budget_per_minute(),
+            )
+        };
+
+        let config = self.resolve_secret_config(id, &config);
+        let raw = self
+            .run_fetch_source(id, &source, &permissions, allowed_hosts, &config, budget)
+            .await;
+
+        let mut result = match raw {
+            Ok(raw) => Self::replay_fetch_result(id, raw, data_type.as_deref()),
+            Err(e) => ReplayParseResult {
+                valid: false,
+                data: None,
+                errors: vec![e.to_string()],
+                warnings: Vec::new(),
+            },
+        };
+        Self::apply_payload_schema(&mut result, payload_schema.as_ref());
+        Ok(result)
+    }
+
+    /// 执行一次 fetchData：插件在沙盒中调用导出函数，内置数据源直接调用原生实现
+    async fn run_fetch_source(
+        &self,
+        id: &str,
+        source: &FetchSource,
+        permissions: &[String],
+        allowed_hosts: Option<Vec<String>>,
+        config: &HashMap<String, serde_json::Value>,
+        budget: u32,
+    ) -> Result<serde_json::Value, LifecycleError> {
+        match source {
+            FetchSource::Sandbox(module) => {
+                let invocation = Self::build_fetch_invocation(id, config)?;
+                self.execute_in_sandbox(id, module, &invocation, permissions, allowed_hosts, config, budget)
+                    .await
+            }
+            FetchSource::Builtin(provider) => provider.fetch(config).await,
+        }
+    }
+
+    /// custom 数据与 manifest payloadSchema 的不符项（其他 dataType 或未声明 schema 时为空）
+    fn payload_schema_errors(
+        data: &PluginData,
+        schema: Option<&serde_json::Value>,
+    ) -> Vec<String> {
+        match (data, schema) {
+            (PluginData::Custom(custom), Some(schema)) => {
+                validate_payload(schema, &custom.payload)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// 将 payloadSchema 校验结果合并到解析结果
+    fn apply_payload_schema(result: &mut ReplayParseResult, schema: Option<&serde_json::Value>) {
+        let Some(data) = result.data.as_ref() else {
+            return;
+        };
+        let schema_errors = Self::payload_schema_errors(data, schema);
+        if !schema_errors.is_empty() {
+            result
+                .errors
+                .extend(schema_errors.into_iter().map(|e| format!("payloadSchema {}", e)));
+            result.valid = false;
+        }
+    }
+
+    /// 回放解析（无状态部分）
+    fn replay_fetch_result(
+        plugin_id: &str,
+        raw: serde_json::Value,
+        expected_data_type: Option<&str>,
+    ) -> ReplayParseResult {
+        let (mut errors, warnings) = Self::validate_fetch_result(&raw, expected_data_type);
+
+        let data = if raw.is_object() {
+            match Self::parse_fetch_result(plugin_id, raw, expected_data_type) {
+                Ok(data) => Some(data),
+                Err(e) => {
+                    errors.push(e.to_string());
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        ReplayParseResult {
+            valid: errors.is_empty(),
+            data,
+            errors,
+            warnings,
+        }
+    }
+
+    /// 校验 fetchData 原始结果，返回 (错误, 警告)
+    ///
+    /// `parse_fetch_result` 对缺失或类型不符的字段静默使用默认值，
+    /// 这里把这些情况逐一列出，便于定位解析问题。
+    fn validate_fetch_result(
+        raw: &serde_json::Value,
+        expected_data_type: Option<&str>,
+    ) -> (Vec<String>, Vec<String>) {
+        use serde_json::Value;
+
+        /// 各 dataType 的字段规则: (字段名, 期望类型, 是否必需)
+        const FIELD_RULES: &[(&str, &[(&str, &str, bool)])] = &[
+            ("usage", &[
+                ("percentage", "number", true),
+                ("used", "number", true),
+                ("limit", "number", true),
+                ("unit", "string", false),
+                ("resetTime", "string", false),
+                ("dimensions", "array", false),
+            ]),
+            ("balance", &[
+                ("balance", "number", true),
+                ("currency", "string", false),
+                ("quota", "number", false),
+                ("usedQuota", "number", false),
+                ("items", "array", false),
+            ]),
+            ("status", &[
+                ("description", "string", false),
+            ]),
+            ("custom", &[
+                ("title", "string", false),
+                ("renderHtml", "string", false),
+            ]),
+        ];
+
+        fn type_name(v: &Value) -> &'static str {
+            match v {
+                Value::Null => "null",
+                Value::Bool(_) => "boolean",
+                Value::Number(_) => "number",
+                Value::String(_) => "string",
+                Value::Array(_) => "array",
+                Value::Object(_) => "object",
+            }
+        }
+
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        let Some(obj) = raw.as_object() else {
+            errors.push(format!("fetchData 返回结果必须是对象，实际为 {}", type_name(raw)));
+            return (errors, warnings);
+        };
+
+        // dataType 一致性
+        let declared = obj.get("dataType").and_then(|v| v.as_str());
+        match (declared, expected_data_type) {
+            (Some(actual), Some(expected)) if actual != expected => warnings.push(format!(
+                "dataType \"{}\" 与 manifest 声明的 \"{}\" 不一致",
+                actual, expected
+            )),
+            (None, Some(expected)) => warnings.push(format!(
+                "缺少 dataType，按 manifest 声明的 \"{}\" 解析",
+                expected
+            )),
+            _ => {}
+        }
+
+        let Some(data_type) = declared.or(expected_data_type) else {
+            // parse_fetch_result 会给出缺少 dataType 的错误
+            return (errors, warnings);
+        };
+
+        let Some((_, rules)) = FIELD_RULES.iter().find(|(t, _)| *t == data_type) else {
+            warnings.push(format!("未知 dataType \"{}\"，按 custom 解析", data_type));
+            return (errors, warnings);
+        };
+
+        for (field, expected, required) in rules.iter() {
+            match obj.get(*field) {
+                None | Some(Value::Null) => {
+                    if *required {
+                        warnings.push(format!("缺少字段 {}，将使用默认值", field));
+                    }
+                }
+                Some(v) if type_name(v) != *expected => errors.push(format!(
+                    "字段 {} 应为 {}，实际为 {}",
+                    field,
+                    expected,
+                    type_name(v)
+                )),
+                Some(_) => {}
+            }
+        }
+
+        // 数据类型特有的校验
+        match data_type {
+            "balance" => {
+                let items = obj.get("items").or_else(|| obj.get("limits"));
+                if let Some(items) = items.and_then(|v| v.as_array()) {
+                    for (i, item) in items.iter().enumerate() {
+                        let has_name = ["name", "label", "displayName", "limitType"]
+                            .iter()
+                            .any(|k| item.get(*k).and_then(|v| v.as_str()).is_some());
+                        if !has_name {
+                            warnings.push(format!("items[{}] 缺少 name，将被忽略", i));
+                        }
+                    }
+                }
+            }
+            "status" => {
+                let indicator = obj
+                    .get("indicator")
+                    .or_else(|| obj.get("status"))
+                    .and_then(|v| v.as_str());
+                match indicator.map(|s| s.to_lowercase()) {
+                    None => warnings.push("缺少字段 indicator，将显示为 unknown".to_string()),
+                    Some(s)
+                        if !matches!(
+                            s.as_str(),
+                            "none" | "ok" | "healthy" | "minor" | "warning" | "major" | "error"
+                                | "critical" | "unknown"
+                        ) =>
+                    {
+                        warnings.push(format!("未知 indicator \"{}\"，将显示为 unknown", s))
+                    }
+                    Some(_) => {}
+                }
+            }
+            "usage" => {
+                if let Some(p) = obj.get("percentage").and_then(|v| v.as_f64()) {
+                    if !(0.0..=100.0).contains(&p) {
+                        warnings.push(format!("percentage {} 超出 0-100 范围", p));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        (errors, warnings)
+    }
+
+    /// 生成调用 fetchData 的脚本
     ///
-    /// 将 `export const X = ...` 和 `export async function X` 转换为
-    /// 可以直接 eval 的代码格式
-    fn transform_esm_to_executable(
-        code: &str,
+    /// 插件入口由沙盒按 ES Module 求值，导出对象位于全局 `__pluginExports`；
+    /// 脚本注入 context 并调用导出的 fetchData（也支持 `export default { fetchData }`）
+    fn build_fetch_invocation(
+        plugin_id: &str,
+        config: &HashMap<String, serde_json::Value>,
+    ) -> Result<String, LifecycleError> {
+        Self::build_invocation(
+            plugin_id,
+            config,
+            "fetchData",
+            r#"  // 调用 fetchData
+  if (typeof __exports.fetchData !== 'function') {
+    throw new Error('插件未导出 fetchData 函数');
+  }
+
+  // 直接返回 fetchData 的结果（可能是 Promise）
+  // execute_plugin 会正确处理异步 Promise
+  return __exports.fetchData(config, context);"#,
+        )
+    }
+
+    /// 生成调用生命周期钩子的脚本（插件未导出该钩子时返回 null）
+    fn build_hook_invocation(
+        plugin_id: &str,
+        config: &HashMap<String, serde_json::Value>,
+        hook: LifecycleHook,
+    ) -> Result<String, LifecycleError> {
+        let name = hook.export_name();
+        let call = format!(
+            r#"  if (typeof __exports.{name} !== 'function') {{
+    return null;
+  }}
+  return Promise.resolve(__exports.{name}(context)).then(function() {{ return null; }});"#
+        );
+        Self::build_invocation(plugin_id, config, name, &call)
+    }
+
+    /// 生成调用 onEvent 的脚本（EventBus 事件投递）
+    fn build_event_invocation(
         plugin_id: &str,
         config: &HashMap<String, serde_json::Value>,
+        event: &str,
+        data: &serde_json::Value,
     ) -> Result<String, LifecycleError> {
-        // 转换 export 语句
-        let mut transformed = code.to_string();
+        let event_json = serde_json::to_string(event)
+            .map_err(|e| LifecycleError::PluginLoad(format!("事件名称序列化失败: {}", e)))?;
+        let data_json = serde_json::to_string(data)
+            .map_err(|e| LifecycleError::PluginLoad(format!("事件数据序列化失败: {}", e)))?;
+        let call = format!(
+            r#"  if (typeof __exports.onEvent !== 'function') {{
+    throw new Error('插件未导出 onEvent 函数');
+  }}
+  return Promise.resolve(__exports.onEvent({event_json}, {data_json}, context)).then(function() {{ return null; }});"#
+        );
+        Self::build_invocation(plugin_id, config, "onEvent", &call)
+    }
 
-        // 1. 转换 `export const X = ...` 为 `const X = ...; __exports.X = X;`
-        // 使用简单的字符串替换（更复杂的情况需要 AST 解析）
-        transformed = transformed.replace("export const ", "const ");
-        transformed = transformed.replace("export let ", "let ");
-        transformed = transformed.replace("export var ", "var ");
+    /// 生成调用 onTimer 的脚本（持久定时器触发）
+    fn build_timer_invocation(
+        plugin_id: &str,
+        config: &HashMap<String, serde_json::Value>,
+        name: &str,
+        data: &serde_json::Value,
+    ) -> Result<String, LifecycleError> {
+        let name_json = serde_json::to_string(name)
+            .map_err(|e| LifecycleError::PluginLoad(format!("定时器名称序列化失败: {}", e)))?;
+        let data_json = serde_json::to_string(data)
+            .map_err(|e| LifecycleError::PluginLoad(format!("定时器数据序列化失败: {}", e)))?;
+        let call = format!(
+            r#"  if (typeof __exports.onTimer !== 'function') {{
+    throw new Error('插件未导出 onTimer 函数');
+  }}
+  return Promise.resolve(__exports.onTimer({name_json}, {data_json}, context)).then(function() {{ return null; }});"#
+        );
+        Self::build_invocation(plugin_id, config, "onTimer", &call)
+    }
 
-        // 2. 转换 `export async function X` 为 `async function X`
-        transformed = transformed.replace("export async function ", "async function ");
-        transformed = transformed.replace("export function ", "function ");
+    /// 生成调用 validateConfig 的脚本（插件未导出时返回 null）
+    fn build_validate_invocation(
+        plugin_id: &str,
+        config: &HashMap<String, serde_json::Value>,
+    ) -> Result<String, LifecycleError> {
+        Self::build_invocation(
+            plugin_id,
+            config,
+            "validateConfig",
+            r#"  if (typeof __exports.validateConfig !== 'function') {
+    return null;
+  }
+  // 返回 undefined 视为通过，避免与“未导出”混淆
+  return Promise.resolve(__exports.validateConfig(config, context)).then(function(result) {
+    return result === undefined || result === null ? { valid: true } : result;
+  });"#,
+        )
+    }
 
-        // 3. 序列化配置
+    /// 生成调用插件导出函数的脚本
+    ///
+    /// `export` 用于判断是否回退到 default 导出，`call` 为注入 context 后执行的函数体
+    fn build_invocation(
+        plugin_id: &str,
+        config: &HashMap<String, serde_json::Value>,
+        export: &str,
+        call: &str,
+    ) -> Result<String, LifecycleError> {
+        // 1. 序列化配置
         let config_json = serde_json::to_string(config)
             .map_err(|e| LifecycleError::PluginLoad(format!("配置序列化失败: {}", e)))?;
 
-        // 4. 构建可执行代码
-        // 使用 IIFE 包装，注入 context，调用 fetchData
-        let executable = format!(
+        // 2. 构建调用脚本
+        // 使用 IIFE 包装，注入 context，执行调用
+        let invocation = format!(
             r#"(function() {{
-  var __exports = {{}};
+  var __exports = __pluginExports;
+  if (typeof __exports.{export} !== 'function' && __exports.default && typeof __exports.default === 'object') {{
+    __exports = __exports.default;
+  }}
 
   // 注入 context 对象
   var context = {{
-    pluginId: "{}",
-    config: {},
+    pluginId: "{plugin_id}",
+    config: {config_json},
     log: function(level, msg) {{
-      console.log("[" + level + "][{}] " + msg);
+      // 按级别写入插件日志（console 已带插件 ID）
+      var write = typeof console[level] === 'function' ? console[level] : console.log;
+      write(msg);
     }},
     emit: function(event, data) {{
-      console.log("[emit][{}] " + event);
-    }}
+      console.log("[emit][{plugin_id}] " + event);
+    }},
+    storage: typeof __cukStorage !== 'undefined' ? __cukStorage : (function() {{
+      var denied = function() {{
+        return Promise.reject(new PluginError('PERMISSION_DENIED', '插件未声明 storage 权限', undefined));
+      }};
+      return {{ get: denied, set: denied, delete: denied, keys: denied, clear: denied }};
+    }})(),
+    cache: typeof __cukCache !== 'undefined' ? __cukCache : (function() {{
+      var denied = function() {{
+        return Promise.reject(new PluginError('PERMISSION_DENIED', '插件未声明 cache 权限', undefined));
+      }};
+      return {{ put: denied, set: denied, get: denied, has: denied, delete: denied, keys: denied }};
+    }})(),
+    secrets: typeof __cukSecrets !== 'undefined' ? __cukSecrets : {{
+      get: function(name) {{
+        return Promise.reject(new PluginError('PERMISSION_DENIED', '插件未声明 secrets:' + name + ' 权限', undefined));
+      }}
+    }},
+    getData: typeof __cukPluginData !== 'undefined' ? __cukPluginData.getData : function(pluginId) {{
+      return Promise.reject(new PluginError('PERMISSION_DENIED', '插件未声明 read:' + pluginId + ' 权限', undefined));
+    }},
+    requestInput: typeof __cukRequestInput !== 'undefined' ? __cukRequestInput : function() {{
+      return Promise.reject(new PluginError('PERMISSION_DENIED', '插件未声明 input 权限', undefined));
+    }},
+    timers: typeof __cukTimers !== 'undefined' ? __cukTimers : (function() {{
+      var denied = function() {{
+        throw new PluginError('PERMISSION_DENIED', '插件未声明 timer 权限', undefined);
+      }};
+      return {{ schedule: denied, cancel: denied }};
+    }})()
   }};
 
-  // 插件代码开始
-  {}
-  // 插件代码结束
-
-  // 收集导出（简化版：假设标准命名）
-  if (typeof metadata !== 'undefined') __exports.metadata = metadata;
-  if (typeof fetchData !== 'undefined') __exports.fetchData = fetchData;
-  if (typeof onLoad !== 'undefined') __exports.onLoad = onLoad;
-  if (typeof onUnload !== 'undefined') __exports.onUnload = onUnload;
-  if (typeof validateConfig !== 'undefined') __exports.validateConfig = validateConfig;
-
-  // 调用 fetchData
-  if (typeof __exports.fetchData !== 'function') {{
-    throw new Error('插件未导出 fetchData 函数');
-  }}
-
-  var config = {5};
-  // 直接返回 fetchData 的结果（可能是 Promise）
-  // execute_plugin 会正确处理异步 Promise
-  return __exports.fetchData(config, context);
-}})()"#,
-            plugin_id,
-            config_json,
-            plugin_id,
-            plugin_id,
-            transformed,
-            config_json
+  var config = {config_json};
+{call}
+}})()"#
         );
 
-        Ok(executable)
+        Ok(invocation)
     }
 
-    /// 在沙盒中执行代码
+    /// 在沙盒中执行插件模块
     async fn execute_in_sandbox(
         &self,
-        code: &str,
+        plugin_id: &str,
+        module: &PluginModule,
+        invocation: &str,
         permissions: &[String],
+        allowed_hosts: Option<Vec<String>>,
+        plugin_config: &HashMap<String, serde_json::Value>,
+        max_requests_per_minute: u32,
     ) -> Result<serde_json::Value, LifecycleError> {
-        use crate::plugin::{SandboxConfig, SandboxRuntime, PluginExecutor, RequestManager};
+        use crate::plugin::{
+            HttpCache, PluginCache, PluginDataReader, PluginExecutor, PluginInput, PluginSecrets, PluginStorage,
+            RequestManager, TimerRegistry,
+        };
+        use crate::plugin::sandbox::cache::DEFAULT_CACHE_QUOTA;
+        use crate::plugin::sandbox::storage::DEFAULT_STORAGE_QUOTA;
         use std::sync::Arc;
 
-        // 创建沙盒运行时
-        let config = SandboxConfig::default();
-        let runtime = SandboxRuntime::new(config)
+        // 从运行时池取出沙盒运行时（没有空闲运行时时按插件的资源限制新建）
+        let (sandbox_config, trust_level) = self
+            .plugins
+            .read()
+            .await
+            .get(plugin_id)
+            .map(|p| (p.sandbox_config.clone(), p.trust_level))
+            .unwrap_or_default();
+        let runtime = self
+            .sandbox_pool
+            .acquire(plugin_id, &sandbox_config)
             .await
             .map_err(|e| LifecycleError::PluginLoad(format!("创建沙盒失败: {}", e)))?;
 
-        // 创建执行器
-        let request_manager = RequestManager::new()
-            .map_err(|e| LifecycleError::PluginLoad(format!("创建 RequestManager 失败: {}", e)))?;
-        let executor = PluginExecutor::new(Arc::new(runtime))
-            .with_request_manager(Arc::new(request_manager));
+        // 默认网关只服务于 {{gateway.base}} 引用，显式映射的插件才把网关主机加入白名单
+        let (gateway, gateway_mapped) = {
+            let gateways = self.gateways.read().await;
+            (gateways.resolve(plugin_id).cloned(), gateways.is_mapped(plugin_id))
+        };
 
-        // 执行代码
-        executor
-            .execute_plugin(code, permissions)
-            .await
-            .map_err(|e| LifecycleError::PluginLoad(format!("执行插件失败: {}", e)))
+        // 创建执行器（共享 HTTP 客户端）
+        let request_manager = RequestManager::shared()
+            .map_err(|e| LifecycleError::PluginLoad(format!("创建 RequestManager 失败: {}", e)))?
+            .with_allowed_hosts(allowed_hosts)
+            .with_gateway(gateway, gateway_mapped)
+            .with_policy_hosts(self.admin_policy.policy().network_allowlist.clone())
+            .with_recorder(Some(NetworkRecorder::new(
+                plugin_id,
+                self.network_stats.clone(),
+                plugin_config,
+            )))
+            .with_rate_limiter(self.rate_limiter.clone(), plugin_id, max_requests_per_minute)
+            .with_permission_checker(self.permission_checker.clone(), plugin_id)
+            .with_http_cache(HttpCache::shared(), plugin_id)
+            .with_proxy(self.proxy.lock().unwrap_or_else(|e| e.into_inner()).clone())
+            .with_tls(self.tls.lock().unwrap_or_else(|e| e.into_inner()).clone())
+            .with_max_concurrent(trust_level.limits().max_concurrent_fetches);
+        let mut executor = PluginExecutor::new(runtime.clone())
+            .with_trust_level(trust_level)
+            .with_request_manager(Arc::new(request_manager))
+            .with_log_recorder(PluginLogRecorder::new(plugin_id, self.plugin_logs.clone()));
+
+        // 声明 storage 权限时打开插件存储
+        if permissions.iter().any(|p| p == "storage") {
+            let storage =
+                PluginStorage::open(&PluginStorage::default_dir(), plugin_id, DEFAULT_STORAGE_QUOTA)
+                    .map_err(|e| LifecycleError::PluginLoad(format!("打开插件存储失败: {}", e)))?;
+            executor = executor.with_storage(Arc::new(storage));
+        }
+
+        // 声明 cache 权限时打开插件缓存目录
+        if permissions.iter().any(|p| p == "cache") {
+            let cache = PluginCache::open(&PluginCache::default_dir(), plugin_id, DEFAULT_CACHE_QUOTA)
+                .map_err(|e| LifecycleError::PluginLoad(format!("打开插件缓存失败: {}", e)))?;
+            executor = executor.with_cache(Arc::new(cache));
+        }
+
+        // 声明 secrets:<name> 权限时提供 Keychain 只读访问
+        if permissions.iter().any(|p| p.starts_with("secrets:")) {
+            executor = executor
+                .with_secrets(Arc::new(PluginSecrets::from_permissions(plugin_id, permissions)));
+        }
+
+        // 声明 read:<pluginId> 权限时提供其他插件缓存数据的快照（context.getData）
+        let read_targets: Vec<&str> = permissions.iter().filter_map(|p| p.strip_prefix("read:")).collect();
+        if !read_targets.is_empty() {
+            let mut data = HashMap::new();
+            for target in read_targets {
+                if self.permission_checker.check_read_permission(plugin_id, target).await.is_ok() {
+                    let cached = self.get_plugin_data(target).await;
+                    data.insert(target.to_string(), cached.and_then(|d| serde_json::to_value(d).ok()));
+                }
+            }
+            executor = executor.with_plugin_data(Arc::new(PluginDataReader::new(plugin_id, data)));
+        }
+
+        // 声明 input 权限时允许运行时请求用户输入
+        if permissions.iter().any(|p| p == "input") {
+            executor = executor
+                .with_input(Arc::new(PluginInput::new(plugin_id, self.input_broker.clone())));
+        }
+
+        // 声明 timer 权限时提供执行期定时器和持久定时器（context.timers）
+        let timer_registry = permissions
+            .iter()
+            .any(|p| p == "timer" || p == "setTimeout")
+            .then(|| Arc::new(TimerRegistry::new()));
+        if let Some(registry) = &timer_registry {
+            executor = executor.with_timer_registry(registry.clone());
+        }
+
+        // 执行代码，完成后释放执行器并将运行时归还运行时池
+        let result = executor.execute_module(module, invocation, permissions).await;
+        let stats = executor.last_stats();
+        drop(executor);
+        // 每次沙盒执行（含重试和生命周期钩子）都记录资源占用
+        if let Some(plugin) = self.plugins.write().await.get_mut(plugin_id) {
+            plugin
+                .sliding_window
+                .record_resources(stats.cpu_time.as_secs_f64() * 1000.0, stats.memory_bytes);
+            // 执行成功时才登记本次请求的持久定时器
+            if let (Some(registry), true) = (&timer_registry, result.is_ok()) {
+                self.apply_timer_requests(plugin, registry.take_host_requests());
+            }
+        }
+        self.sandbox_pool.release(plugin_id, runtime, result.is_ok()).await;
+        result.map_err(|e| LifecycleError::PluginLoad(format!("执行插件失败: {}", e)))
     }
 
     /// 解析 fetchData 返回的结果为 PluginData
@@ -1912,11 +5101,11 @@ impl PluginManager {
             })?;
 
         // 构建基础数据
+        // 规范化为 RFC 3339（插件可能返回 Unix 时间戳或带时区偏移的字符串）
         let last_updated = result
             .get("lastUpdated")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| Utc::now().to_rfc3339());
+            .and_then(normalize_timestamp)
+            .unwrap_or_else(|| format_rfc3339(Utc::now()));
 
         let base = PluginDataBase {
             plugin_id: plugin_id.to_string(),
@@ -1951,6 +5140,7 @@ impl PluginManager {
                                     used: d.get("used")?.as_f64()?,
                                     limit: d.get("limit")?.as_f64()?,
                                     reset_time: d.get("resetTime").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                                    tokens: d.get("tokens").and_then(|v| serde_json::from_value(v.clone()).ok()),
                                 })
                             })
                             .collect()
@@ -2125,20 +5315,149 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_manifest_parse() {
+    fn test_manifest_parse() {
+        let json = r#"{
+            "id": "test-plugin",
+            "name": "Test Plugin",
+            "version": "1.0.0",
+            "apiVersion": "1.0",
+            "pluginType": "data",
+            "dataType": "usage"
+        }"#;
+
+        let manifest: PluginManifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.id, "test-plugin");
+        assert_eq!(manifest.get_plugin_type(), PluginType::Data);
+        assert_eq!(manifest.get_data_type(), Some(DataType::Usage));
+        assert!(manifest.allowed_hosts.is_none());
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        // 未声明字段使用默认值，超出范围的值被截断
+        let policy: RetryPolicy = serde_json::from_str(r#"{"maxRetries": 10, "initialDelayMs": 1}"#).unwrap();
+        let config = policy.to_retry_config();
+        assert_eq!(config.max_retries, MAX_FETCH_RETRIES);
+        assert_eq!(config.initial_delay, Duration::from_millis(MIN_FETCH_RETRY_DELAY_MS));
+        assert_eq!(config.max_delay, Duration::from_millis(default_max_delay_ms()));
+        assert!(config.validate().is_ok());
+
+        // 只重试网络错误和超时
+        let retryable = |message: &str| FetchAttemptError { message: message.to_string() }.is_retryable();
+        assert!(retryable("[NETWORK_ERROR] connection reset"));
+        assert!(retryable("请求超时"));
+        assert!(!retryable("HTTP 401: invalid x-api-key"));
+        assert!(!retryable("[RATE_LIMIT] HTTP 429"));
+        assert!(!retryable("custom 数据不符合 payloadSchema: missing field"));
+    }
+
+    #[test]
+    fn test_fetch_cache_ttl() {
+        assert_eq!(fetch_cache_ttl(None), DEFAULT_FETCH_CACHE_TTL);
+        assert_eq!(fetch_cache_ttl(Some(300_000)), Duration::from_secs(150));
+        assert_eq!(fetch_cache_ttl(Some(1_000)), MIN_FETCH_CACHE_TTL);
+        assert_eq!(fetch_cache_ttl(Some(24 * 3600 * 1000)), MAX_FETCH_CACHE_TTL);
+    }
+
+    #[test]
+    fn test_replay_fetch_result() {
+        use serde_json::json;
+
+        // 正常 usage 数据
+        let result = PluginManager::replay_fetch_result(
+            "demo",
+            json!({"dataType": "usage", "percentage": 42.0, "used": 42, "limit": 100, "unit": "req"}),
+            Some("usage"),
+        );
+        assert!(result.valid);
+        assert!(result.warnings.is_empty());
+        assert!(matches!(result.data, Some(PluginData::Usage(_))));
+
+        // 字段类型错误 + dataType 不一致
+        let result = PluginManager::replay_fetch_result(
+            "demo",
+            json!({"dataType": "usage", "percentage": "42%", "used": 1, "limit": 2}),
+            Some("balance"),
+        );
+        assert!(!result.valid);
+        assert_eq!(result.errors, vec!["字段 percentage 应为 number，实际为 string"]);
+        assert!(result.warnings[0].contains("不一致"));
+        assert!(result.data.is_some());
+
+        // 非对象 / 缺少 dataType
+        let result = PluginManager::replay_fetch_result("demo", json!([1, 2]), None);
+        assert!(!result.valid && result.data.is_none());
+        let result = PluginManager::replay_fetch_result("demo", json!({"used": 1}), None);
+        assert!(!result.valid);
+        assert!(result.errors[0].contains("dataType"));
+
+        // balance 子项缺少 name
+        let result = PluginManager::replay_fetch_result(
+            "demo",
+            json!({"dataType": "balance", "balance": 5, "items": [{"used": 1}]}),
+            None,
+        );
+        assert!(result.valid);
+        assert_eq!(result.warnings, vec!["items[0] 缺少 name，将被忽略"]);
+    }
+
+    #[test]
+    fn test_payload_schema_flags_nonconforming_custom_data() {
+        use serde_json::json;
+
+        let schema = json!({
+            "type": "object",
+            "required": ["rows"],
+            "properties": { "rows": { "type": "array", "items": { "type": "number" } } }
+        });
+
+        let mut result = PluginManager::replay_fetch_result(
+            "demo",
+            json!({"dataType": "custom", "rows": [1, 2]}),
+            Some("custom"),
+        );
+        PluginManager::apply_payload_schema(&mut result, Some(&schema));
+        assert!(result.valid, "{:?}", result.errors);
+
+        let mut result = PluginManager::replay_fetch_result(
+            "demo",
+            json!({"dataType": "custom", "rows": [1, "two"]}),
+            Some("custom"),
+        );
+        PluginManager::apply_payload_schema(&mut result, Some(&schema));
+        assert!(!result.valid);
+        assert_eq!(
+            result.errors,
+            vec!["payloadSchema /rows/1: 应为 number，实际为 string"]
+        );
+
+        // 非 custom 数据不受 payloadSchema 约束
+        let mut result = PluginManager::replay_fetch_result(
+            "demo",
+            json!({"dataType": "status", "description": "ok"}),
+            None,
+        );
+        PluginManager::apply_payload_schema(&mut result, Some(&schema));
+        assert!(result.valid);
+    }
+
+    #[test]
+    fn test_manifest_parse_allowed_hosts() {
         let json = r#"{
-            "id": "test-plugin",
-            "name": "Test Plugin",
+            "id": "claude-usage",
+            "name": "Claude Usage",
             "version": "1.0.0",
             "apiVersion": "1.0",
             "pluginType": "data",
-            "dataType": "usage"
+            "permissions": ["network"],
+            "allowedHosts": ["api.anthropic.com", "*.anthropic.com"]
         }"#;
 
         let manifest: PluginManifest = serde_json::from_str(json).unwrap();
-        assert_eq!(manifest.id, "test-plugin");
-        assert_eq!(manifest.get_plugin_type(), PluginType::Data);
-        assert_eq!(manifest.get_data_type(), Some(DataType::Usage));
+        assert_eq!(
+            manifest.allowed_hosts,
+            Some(vec!["api.anthropic.com".to_string(), "*.anthropic.com".to_string()])
+        );
     }
 
     #[test]
@@ -2388,6 +5707,71 @@ mod tests {
         assert_eq!(instance.consecutive_failures, 0);
     }
 
+    #[test]
+    fn test_to_health_suggested_action() {
+        let mut instance = create_test_instance();
+        assert!(instance.to_health().suggested_action.is_none());
+
+        instance.record_failure("执行插件失败: [AUTH_ERROR] invalid key".into());
+        assert_eq!(instance.last_error_type, Some(PluginErrorType::AuthError));
+        assert_eq!(
+            instance.to_health().suggested_action.as_deref(),
+            PluginErrorType::AuthError.suggested_action()
+        );
+
+        instance.record_failure("Provider returned HTTP 429".into());
+        assert_eq!(instance.last_error_type, Some(PluginErrorType::RateLimit));
+
+        // 成功后清除建议
+        instance.record_success(50.0);
+        assert!(instance.to_health().suggested_action.is_none());
+    }
+
+    #[test]
+    fn test_error_type_classify() {
+        assert_eq!(PluginErrorType::classify("[TIMEOUT] slow"), PluginErrorType::Timeout);
+        assert_eq!(PluginErrorType::classify("HTTP 401 Unauthorized"), PluginErrorType::AuthError);
+        assert_eq!(PluginErrorType::classify("status: 503"), PluginErrorType::ProviderError);
+        assert_eq!(PluginErrorType::classify("执行超时: 超过 30s"), PluginErrorType::Timeout);
+        assert_eq!(PluginErrorType::classify("DNS error: no host"), PluginErrorType::NetworkError);
+        assert_eq!(PluginErrorType::classify("something odd"), PluginErrorType::Unknown);
+        assert!(PluginErrorType::Unknown.suggested_action().is_none());
+    }
+
+    #[test]
+    fn test_defunct_detection() {
+        assert!(is_provider_gone("HTTP 404 Not Found"));
+        assert!(is_provider_gone("status: 410"));
+        assert!(is_provider_gone("DNS error: DNS resolution failed: failed to lookup address information: Name or service not known"));
+        assert!(!is_provider_gone("HTTP 500"));
+        assert!(!is_provider_gone("DNS error: DNS resolution timeout"));
+
+        let mut instance = create_test_instance();
+        let start = Utc::now();
+        instance.track_defunct("HTTP 404", start);
+        assert!(!instance.possibly_defunct);
+
+        // 其他错误不影响计时
+        instance.track_defunct("timeout", start + chrono::Duration::days(1));
+        assert_eq!(instance.gone_since, Some(start));
+
+        instance.track_defunct("HTTP 410", start + chrono::Duration::days(DEFUNCT_THRESHOLD_DAYS));
+        assert!(instance.possibly_defunct);
+        let info = instance.to_info();
+        assert!(info.possibly_defunct);
+        assert_eq!(info.defunct_since, Some(start.to_rfc3339()));
+        assert_eq!(
+            instance.to_health().suggested_action.as_deref(),
+            Some(DEFUNCT_SUGGESTED_ACTION)
+        );
+
+        // 成功后恢复
+        instance.record_success(10.0);
+        assert!(!instance.possibly_defunct);
+        assert!(instance.gone_since.is_none());
+        assert!(!instance.to_info().possibly_defunct);
+    }
+
     #[test]
     fn test_to_health_total_calls_accuracy() {
         let mut instance = create_test_instance();
@@ -2400,6 +5784,96 @@ mod tests {
         assert_eq!(health.total_calls, 3);
     }
 
+    #[test]
+    fn test_observe_health_reports_transitions_once() {
+        let mut instance = create_test_instance();
+        let t1 = DateTime::parse_from_rfc3339("2025-06-01T10:00:00Z").unwrap().with_timezone(&Utc);
+        let t2 = DateTime::parse_from_rfc3339("2025-06-01T10:05:00Z").unwrap().with_timezone(&Utc);
+
+        instance.record_success(50.0);
+        assert!(instance.observe_health(t1, false).is_none());
+
+        for _ in 0..3 {
+            instance.record_failure("error".into());
+        }
+        let transition = instance.observe_health(t1, false).unwrap();
+        assert_eq!((transition.previous, transition.current), (HealthStatus::Healthy, HealthStatus::Unhealthy));
+        assert_eq!(transition.changed_at, "2025-06-01T10:00:00.000Z");
+        assert!(transition.previous_since.is_none());
+        // 状态未变化时不重复发布
+        instance.record_failure("error".into());
+        assert!(instance.observe_health(t2, false).is_none());
+
+        instance.reset_health_stats();
+        let transition = instance.observe_health(t2, false).unwrap();
+        assert_eq!(transition.current, HealthStatus::Healthy);
+        assert_eq!(transition.previous_since.as_deref(), Some("2025-06-01T10:00:00.000Z"));
+    }
+
+    #[test]
+    fn test_offline_health_is_stale() {
+        let mut instance = create_test_instance();
+        instance.enabled = true;
+        let now = Utc::now();
+        for _ in 0..3 {
+            instance.record_failure("error sending request: dns error".into());
+        }
+        // 离线期间不发布 Unhealthy，而是 Stale
+        let transition = instance.observe_health(now, true).unwrap();
+        assert_eq!(transition.current, HealthStatus::Stale);
+        assert_eq!(transition.health.suggested_action.as_deref(), Some(OFFLINE_SUGGESTED_ACTION));
+        assert!(instance.observe_health(now, true).is_none());
+        assert_eq!(instance.observe_health(now, false).unwrap().current, HealthStatus::Unhealthy);
+
+        // 未启用的插件不受网络状态影响
+        instance.enabled = false;
+        assert_eq!(instance.to_network_health(true).status, HealthStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_auto_disables_runaway_plugin() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("runaway");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"id": "runaway", "name": "Runaway", "version": "1.0.0", "apiVersion": "1.0",
+                "pluginType": "data", "entry": "plugin.js"}"#,
+        )
+        .unwrap();
+        std::fs::write(plugin_dir.join("plugin.js"), "// test plugin").unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir));
+        manager.discover_and_load().await.unwrap();
+        manager.plugins.write().await.get_mut("runaway").unwrap().enabled = true;
+        let mut trips = manager.take_watchdog_receiver().unwrap();
+
+        let timeout = || "执行插件失败: 执行超时: 超过 30s".to_string();
+        manager.record_plugin_failure("runaway", timeout()).await.unwrap();
+        manager.record_plugin_failure("runaway", timeout()).await.unwrap();
+        // 其他类型的失败重新计数
+        manager.record_plugin_failure("runaway", "HTTP 500".to_string()).await.unwrap();
+        manager.record_plugin_failure("runaway", timeout()).await.unwrap();
+        manager.record_plugin_failure("runaway", "执行插件失败: 内存超限: 超过 1024 字节".to_string()).await.unwrap();
+        assert!(manager.get_plugin("runaway").await.unwrap().enabled);
+        assert!(trips.try_recv().is_err());
+
+        manager.record_plugin_failure("runaway", timeout()).await.unwrap();
+        let info = manager.get_plugin("runaway").await.unwrap();
+        assert!(!info.enabled);
+        assert!(info.auto_disabled_at.is_some());
+        let trip = trips.try_recv().unwrap();
+        assert_eq!((trip.plugin_id.as_str(), trip.consecutive_runaways), ("runaway", 3));
+
+        // 重新启用后清除标记并重新计数
+        manager.enable_plugin("runaway").await.unwrap();
+        let info = manager.get_plugin("runaway").await.unwrap();
+        assert!(info.enabled && info.auto_disabled_at.is_none());
+        manager.record_plugin_failure("runaway", timeout()).await.unwrap();
+        assert!(manager.get_plugin("runaway").await.unwrap().enabled);
+    }
+
     // ========================================================================
     // 回归测试：reload_plugin 两阶段切换
     // ========================================================================
@@ -2603,4 +6077,901 @@ mod tests {
         assert!(permissions.contains(&"storage".to_string()),
             "新权限应已注册");
     }
+
+    // ========================================================================
+    // 完整性自检
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_verify_plugin_integrity_quarantines_tampered_plugin() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("test-integrity");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+
+        let original = b"// original plugin";
+        let manifest = format!(
+            r#"{{
+                "id": "test-integrity",
+                "name": "Test Integrity Plugin",
+                "version": "1.0.0",
+                "apiVersion": "1.0",
+                "pluginType": "data",
+                "entry": "plugin.js",
+                "files": {{ "plugin.js": "sha256:{}" }}
+            }}"#,
+            crate::security::integrity::calculate_sha256_bytes(original)
+        );
+        std::fs::write(plugin_dir.join("manifest.json"), manifest).unwrap();
+        std::fs::write(plugin_dir.join("plugin.js"), original).unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir));
+        manager.discover_and_load().await.unwrap();
+        manager.enable_plugin("test-integrity").await.unwrap();
+
+        // 未篡改：通过
+        let report = manager.verify_plugin_integrity("test-integrity").await.unwrap();
+        assert!(report.passed());
+        assert!(report.has_hashes);
+        assert_eq!(report.files_checked, 1);
+
+        // 篡改后：禁用并隔离，写入审计日志
+        std::fs::write(plugin_dir.join("plugin.js"), "// tampered").unwrap();
+        let report = manager.verify_plugin_integrity("test-integrity").await.unwrap();
+        assert!(!report.passed());
+        assert!(report.quarantined);
+        let info = manager.get_plugin("test-integrity").await.unwrap();
+        assert!(info.quarantined);
+        assert!(!info.enabled);
+        assert!(manager.enable_plugin("test-integrity").await.is_err());
+
+        let events = manager.recent_audit_events(10);
+        assert!(events.iter().any(|e| e.kind == AuditEventKind::IntegrityFailed));
+        assert!(events.iter().any(|e| e.kind == AuditEventKind::PluginQuarantined));
+
+        // 恢复文件后可重新启用
+        std::fs::write(plugin_dir.join("plugin.js"), original).unwrap();
+        manager.enable_plugin("test-integrity").await.unwrap();
+        let info = manager.get_plugin("test-integrity").await.unwrap();
+        assert!(!info.quarantined);
+        assert!(info.enabled);
+        assert_eq!(
+            manager.recent_audit_events(1)[0].kind,
+            AuditEventKind::QuarantineReleased
+        );
+    }
+
+    // ========================================================================
+    // 孤立注册清理
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_prune_orphaned_registrations() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        for id in ["test-orphan", "test-kept"] {
+            let plugin_dir = plugins_dir.join(id);
+            std::fs::create_dir_all(&plugin_dir).unwrap();
+            let manifest = format!(
+                r#"{{
+                    "id": "{}",
+                    "name": "Test Plugin",
+                    "version": "1.0.0",
+                    "apiVersion": "1.0",
+                    "pluginType": "data",
+                    "entry": "plugin.js",
+                    "subscribedEvents": ["plugin:other:event_a"],
+                    "permissions": ["network"],
+                    "exposedMethods": ["getData"],
+                    "configSchema": {{ "apiKey": {{ "type": "string" }} }}
+                }}"#,
+                id
+            );
+            std::fs::write(plugin_dir.join("manifest.json"), manifest).unwrap();
+            std::fs::write(plugin_dir.join("plugin.js"), "// test plugin").unwrap();
+        }
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir.clone()));
+        manager.discover_and_load().await.unwrap();
+        assert!(manager.prune_orphaned_registrations().await.unwrap().is_empty());
+
+        // 外部删除插件目录
+        std::fs::remove_dir_all(plugins_dir.join("test-orphan")).unwrap();
+        let cleanups = manager.prune_orphaned_registrations().await.unwrap();
+        assert_eq!(cleanups.len(), 1);
+        assert_eq!(cleanups[0].plugin_id, "test-orphan");
+        assert_eq!(
+            cleanups[0].reclaimed,
+            vec!["subscriptions", "permissions", "methods", "configSchema", "instance"]
+        );
+
+        assert_eq!(
+            manager.event_bus().get_subscribers("plugin:other:event_a").await,
+            vec!["test-kept".to_string()]
+        );
+        assert!(manager.permission_checker().get_plugin_permissions("test-orphan").await.is_empty());
+        assert!(manager.method_registry().get_plugin_methods("test-orphan").await.is_empty());
+        assert!(manager.config_manager().get_schema("test-orphan").await.is_none());
+        assert!(manager.get_plugin("test-orphan").await.is_none());
+        assert!(manager.get_plugin("test-kept").await.is_some());
+    }
+
+    // ========================================================================
+    // 开发者模式
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_developer_mode_loads_restricted_dev_plugins() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = temp_dir.path().join("dev-plugins").join("test-dev");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        let manifest = r#"{
+            "id": "test-dev",
+            "name": "Test Dev Plugin",
+            "version": "0.1.0",
+            "apiVersion": "1.0",
+            "pluginType": "data",
+            "entry": "plugin.js",
+            "permissions": ["storage", "network"]
+        }"#;
+        std::fs::write(plugin_dir.join("manifest.json"), manifest).unwrap();
+        std::fs::write(plugin_dir.join("plugin.js"), "// dev plugin").unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir));
+        assert_eq!(manager.dev_plugins_dir(), temp_dir.path().join("dev-plugins"));
+
+        // 未开启开发者模式时不加载
+        assert!(manager.discover_and_load().await.unwrap().is_empty());
+
+        // 开启后加载，未受信任时只授予低风险权限
+        let mut settings = DeveloperSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        let dev_plugins = manager.set_developer_settings(settings.clone()).await.unwrap();
+        assert_eq!(dev_plugins.len(), 1);
+        assert_eq!(dev_plugins[0].trust_level, TrustLevel::Developer);
+        assert_eq!(dev_plugins[0].restricted_permissions, vec!["network".to_string()]);
+        assert_eq!(
+            manager.permission_checker().get_plugin_permissions("test-dev").await,
+            vec!["storage".to_string()]
+        );
+
+        // 热重载只关注 manifest 和入口文件
+        assert_eq!(
+            manager.dev_plugin_for_change(&plugin_dir.join("plugin.js")).await.as_deref(),
+            Some("test-dev")
+        );
+        assert!(manager.dev_plugin_for_change(&plugin_dir.join("cache.json")).await.is_none());
+
+        // 开发者插件不能通过宿主卸载
+        assert!(manager.uninstall_plugin("test-dev").await.is_err());
+        assert!(plugin_dir.exists());
+
+        // 显式信任后授予全部声明权限
+        settings.trusted.insert("test-dev".to_string());
+        let dev_plugins = manager.set_developer_settings(settings.clone()).await.unwrap();
+        assert!(dev_plugins[0].restricted_permissions.is_empty());
+        assert!(manager
+            .permission_checker()
+            .get_plugin_permissions("test-dev")
+            .await
+            .contains(&"network".to_string()));
+
+        // 设置已持久化，重新发现时保持信任
+        let info = manager.discover_and_load().await.unwrap();
+        assert_eq!(info.len(), 1);
+        assert!(info[0].restricted_permissions.is_empty());
+        assert!(DeveloperSettings::load(&manager.developer_path).is_trusted("test-dev"));
+
+        // 关闭后卸载，保留文件
+        settings.enabled = false;
+        assert!(manager.set_developer_settings(settings).await.unwrap().is_empty());
+        assert!(manager.get_plugin("test-dev").await.is_none());
+        assert!(manager.permission_checker().get_plugin_permissions("test-dev").await.is_empty());
+        assert!(plugin_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_hooks_report_failures() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("test-hooks");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"id": "test-hooks", "name": "Hooks", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            plugin_dir.join("plugin.js"),
+            r#"
+                export async function fetchData() { return {}; }
+                export async function onLoad(context) { throw new Error('load failed: ' + context.pluginId); }
+                export default { onUnload: async () => { throw new Error('unload failed'); } };
+            "#,
+        )
+        .unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir));
+        manager.discover_and_load().await.unwrap();
+
+        // 启用时调用 onLoad，失败计入健康统计但插件保持启用
+        manager.enable_plugin("test-hooks").await.unwrap();
+        let health = manager.get_plugin_health("test-hooks").await.unwrap();
+        assert_eq!(health.consecutive_failures, 1);
+        assert!(manager.get_plugin("test-hooks").await.unwrap().enabled);
+
+        // 已启用时不重复调用 onLoad
+        manager.enable_plugin("test-hooks").await.unwrap();
+        assert_eq!(manager.get_plugin_health("test-hooks").await.unwrap().consecutive_failures, 1);
+
+        // 禁用时调用 default 导出中的 onUnload
+        manager.disable_plugin("test-hooks").await.unwrap();
+        let health = manager.get_plugin_health("test-hooks").await.unwrap();
+        assert_eq!(health.consecutive_failures, 2);
+        assert!(health.last_error.unwrap().contains("onUnload"));
+    }
+
+    #[tokio::test]
+    async fn test_sandbox_resource_stats_recorded_per_execution() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("test-resources");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"id": "test-resources", "name": "Resources", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data", "dataType": "status"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            plugin_dir.join("plugin.js"),
+            r#"
+                export async function fetchData() {
+                    const items = [];
+                    for (let i = 0; i < 20000; i++) { items.push({ index: i, label: 'item-' + i }); }
+                    return { dataType: 'status', indicator: 'none', description: String(items.length) };
+                }
+            "#,
+        )
+        .unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir));
+        manager.discover_and_load().await.unwrap();
+        assert!(manager.get_plugin_resource_stats("missing").await.is_none());
+        assert_eq!(manager.get_plugin_resource_stats("test-resources").await.unwrap().executions, 0);
+
+        manager.test_run("test-resources").await.unwrap();
+        let stats = manager.get_plugin_resource_stats("test-resources").await.unwrap();
+        assert_eq!(stats.executions, 1);
+        assert!(stats.last_cpu_time_ms > 0.0);
+        // 执行结束时的运行时内存包含未回收的数组
+        assert!(stats.peak_memory_bytes > 1024 * 1024);
+        assert!(stats.peak_memory_bytes < stats.memory_limit_bytes);
+
+        let health = manager.get_plugin_health("test-resources").await.unwrap();
+        assert_eq!(health.peak_memory_bytes, stats.peak_memory_bytes);
+    }
+
+    #[tokio::test]
+    async fn test_timers_fire_and_release_with_plugin() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("test-timers");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"id": "test-timers", "name": "Timers", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data", "dataType": "status", "permissions": ["timer"]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            plugin_dir.join("plugin.js"),
+            r#"
+                export async function fetchData() {
+                    let ticks = 0;
+                    const id = setInterval(() => { ticks++; }, 10);
+                    await new Promise((resolve) => setTimeout(resolve, 55));
+                    clearInterval(id);
+                    return { dataType: 'status', indicator: 'none', description: String(ticks) };
+                }
+                export async function onLoad(context) {
+                    context.timers.schedule('tick', { intervalMs: 60000 });
+                    context.timers.schedule('once', { delayMs: 10, data: { n: 1 } });
+                }
+                export async function onTimer(name, data, context) {
+                    throw new Error('fired ' + name + ' ' + JSON.stringify(data));
+                }
+            "#,
+        )
+        .unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir));
+        let mut fires = manager.host_timers().take_receiver().unwrap();
+        manager.discover_and_load().await.unwrap();
+        let timer_resources = |manager: &PluginManager| {
+            let plugins = manager.plugins.try_read().unwrap();
+            plugins["test-timers"].resources.ids_by_type(ResourceType::Timer).len()
+        };
+
+        // 执行期定时器在等待结果期间触发
+        let result = manager.test_run("test-timers").await.unwrap();
+        let data = serde_json::to_value(result.data.unwrap()).unwrap();
+        let ticks: u32 = data["description"].as_str().unwrap().parse().unwrap();
+        assert!(ticks >= 3, "interval fired {} times", ticks);
+
+        // onLoad 登记的持久定时器占用 Timer 资源，一次性定时器触发后注销
+        manager.enable_plugin("test-timers").await.unwrap();
+        assert_eq!(manager.host_timers().names("test-timers"), vec!["once", "tick"]);
+        assert_eq!(timer_resources(&manager), 2);
+        let fire = tokio::time::timeout(Duration::from_secs(2), fires.recv()).await.unwrap().unwrap();
+        manager.run_timer(fire).await;
+        let health = manager.get_plugin_health("test-timers").await.unwrap();
+        assert!(health.last_error.unwrap().contains(r#"fired once {"n":1}"#));
+        assert_eq!(manager.host_timers().names("test-timers"), vec!["tick"]);
+        assert_eq!(timer_resources(&manager), 1);
+
+        // 禁用插件时取消剩余定时器
+        manager.disable_plugin("test-timers").await.unwrap();
+        assert_eq!(manager.host_timers().count("test-timers"), 0);
+        assert_eq!(timer_resources(&manager), 0);
+    }
+
+    #[tokio::test]
+    async fn test_event_plugin_receives_data_updates() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let write_plugin = |id: &str, manifest_extra: &str, script: &str| {
+            let dir = plugins_dir.join(id);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("manifest.json"),
+                format!(r#"{{"id": "{id}", "name": "Test", "version": "1.0.0", "apiVersion": "1.0"{manifest_extra}}}"#),
+            )
+            .unwrap();
+            std::fs::write(dir.join("plugin.js"), script).unwrap();
+        };
+        write_plugin(
+            "test-source",
+            r#", "pluginType": "data", "dataType": "status""#,
+            "export async function fetchData(config) { return { dataType: 'status', indicator: 'none', description: config.label || 'ok' }; }",
+        );
+        write_plugin(
+            "test-listener",
+            r#", "pluginType": "event", "subscribedEvents": ["plugin:test-source:data_updated"]"#,
+            r#"
+                export async function onEvent(event, data, context) {
+                    if (data.description !== 'ok') throw new Error(event + ' rejected ' + data.description);
+                }
+            "#,
+        );
+
+        let manager = Arc::new(PluginManager::new(PluginDiscovery::new(plugins_dir)));
+        let mut updates = manager.take_data_update_receiver().unwrap();
+        assert!(manager.take_data_update_receiver().is_none());
+        let mut deliveries = manager.take_event_receiver().unwrap();
+        let consumer = manager.clone();
+        tokio::spawn(async move {
+            while let Some(delivery) = deliveries.recv().await {
+                consumer.deliver_event(delivery).await;
+            }
+        });
+        manager.init().await.unwrap();
+        manager.enable_plugin("test-source").await.unwrap();
+        // 禁用再启用后订阅恢复
+        manager.enable_plugin("test-listener").await.unwrap();
+        manager.disable_plugin("test-listener").await.unwrap();
+        manager.enable_plugin("test-listener").await.unwrap();
+
+        let listener_health = || async { manager.get_plugin_health("test-listener").await.unwrap() };
+        let wait_for = |calls: u64| async move {
+            for _ in 0..100 {
+                if listener_health().await.total_calls >= calls {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            panic!("onEvent not executed");
+        };
+
+        // fetchData 成功后发布 data_updated，事件插件的成功执行计入健康统计
+        manager.execute_fetch_data("test-source", true).await.unwrap();
+        wait_for(1).await;
+        let health = listener_health().await;
+        assert_eq!((health.total_calls, health.error_count), (1, 0));
+
+        // 数据快照同时进入窗口广播通道
+        let update = updates.try_recv().unwrap();
+        assert_eq!(update.plugin_id, "test-source");
+        assert_eq!(serde_json::to_value(&update.data).unwrap()["description"], "ok");
+
+        // onEvent 抛出的错误计入失败统计
+        manager.set_plugin_config("test-source", HashMap::from([("label".to_string(), serde_json::json!("bad"))])).await.unwrap();
+        manager.execute_fetch_data("test-source", true).await.unwrap();
+        wait_for(2).await;
+        let health = listener_health().await;
+        assert_eq!(health.consecutive_failures, 1);
+        assert!(health.last_error.unwrap().contains("plugin:test-source:data_updated rejected bad"));
+    }
+
+    #[tokio::test]
+    async fn test_builtin_provider_registered_as_internal_plugin() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = PluginManager::new(PluginDiscovery::new(temp_dir.path().join("plugins")));
+        manager.init().await.unwrap();
+
+        let info = manager.get_plugin(BuiltinProvider::Claude.id()).await.unwrap();
+        assert_eq!(info.trust_level, TrustLevel::Builtin);
+        assert!(!info.enabled);
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+
+        // 不能卸载或重载
+        assert!(manager.uninstall_plugin(&info.id).await.is_err());
+        assert!(manager.reload_plugin(&info.id).await.is_err());
+        assert!(manager.get_plugin(&info.id).await.is_some());
+
+        // 走原生实现：未配置凭证时直接失败，不经过沙盒
+        manager.enable_plugin(&info.id).await.unwrap();
+        let err = manager.execute_fetch_data(&info.id, true).await.unwrap_err();
+        assert!(err.to_string().contains("未配置 sessionKey"));
+    }
+
+    #[tokio::test]
+    async fn test_console_output_captured_per_plugin() {
+        use crate::plugin::plugin_logs::PluginLogLevel;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugin_dir = temp_dir.path().join("plugins").join("test-logs");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"id": "test-logs", "name": "Logs", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data", "dataType": "status"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            plugin_dir.join("plugin.js"),
+            r#"
+                export async function fetchData(config, context) {
+                    console.log('fetching', 1, [2, 3]);
+                    context.log('error', 'upstream slow');
+                    return { dataType: 'status', indicator: 'none', description: 'ok' };
+                }
+            "#,
+        )
+        .unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(temp_dir.path().join("plugins")));
+        let mut live = manager.plugin_logs().take_receiver().unwrap();
+        manager.init().await.unwrap();
+        manager.enable_plugin("test-logs").await.unwrap();
+        manager.execute_fetch_data("test-logs", true).await.unwrap();
+
+        let logs = manager.plugin_logs().query("test-logs", None, None);
+        let lines: Vec<_> = logs.iter().map(|e| (e.level, e.message.as_str())).collect();
+        assert_eq!(
+            lines,
+            vec![(PluginLogLevel::Info, "fetching 1 [2, 3]"), (PluginLogLevel::Error, "upstream slow")]
+        );
+        assert_eq!(manager.plugin_logs().query("test-logs", None, Some(PluginLogLevel::Warn)).len(), 1);
+        assert_eq!(live.try_recv().unwrap().message, "fetching 1 [2, 3]");
+
+        // 诊断包：日志、脱敏配置、清单与报告
+        let config = HashMap::from([
+            ("apiToken".to_string(), serde_json::json!("tok-123")),
+            ("region".to_string(), serde_json::json!("eu")),
+        ]);
+        manager.set_plugin_config("test-logs", config).await.unwrap();
+        let file = manager.generate_diagnostics("test-logs").await.unwrap();
+        assert!(file.path.starts_with(temp_dir.path().join("diagnostics").to_str().unwrap()));
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&file.path).unwrap()).unwrap();
+        let mut read = |name: &str| {
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut archive.by_name(name).unwrap(), &mut content).unwrap();
+            content
+        };
+        assert!(read("logs.txt").contains("[Error] upstream slow"));
+        let config: serde_json::Value = serde_json::from_str(&read("config.json")).unwrap();
+        assert_eq!(config, serde_json::json!({ "apiToken": "[REDACTED]", "region": "eu" }));
+        let report: serde_json::Value = serde_json::from_str(&read("report.json")).unwrap();
+        assert_eq!(report["plugin"]["id"], "test-logs");
+        assert_eq!(report["health"]["totalCalls"], 1);
+        assert!(read("manifest.json").contains("\"dataType\": \"status\""));
+        assert!(manager.generate_diagnostics("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_manifest_resource_limits_within_policy_caps() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let write_plugin = |id: &str, resources: &str| {
+            let plugin_dir = plugins_dir.join(id);
+            std::fs::create_dir_all(&plugin_dir).unwrap();
+            std::fs::write(
+                plugin_dir.join("manifest.json"),
+                format!(
+                    r#"{{"id": "{}", "name": "Limits", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data", "resources": {}}}"#,
+                    id, resources
+                ),
+            )
+            .unwrap();
+            std::fs::write(plugin_dir.join("plugin.js"), "export async function fetchData() { while (true) {} }").unwrap();
+        };
+        write_plugin("test-limits", r#"{"memoryLimitMb": 8, "timeoutMs": 200}"#);
+        write_plugin("test-greedy", r#"{"memoryLimitMb": 64}"#);
+        let policy_path = temp_dir.path().join("policy.json");
+        std::fs::write(&policy_path, r#"{"maxPluginMemoryMb": 32, "maxPluginTimeoutMs": 1000}"#).unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir.clone()))
+            .with_admin_policy(PolicyManager::load(&policy_path));
+        let infos = manager.discover_and_load().await.unwrap();
+
+        // 超出管理员上限的插件不加载
+        assert_eq!(infos.len(), 1);
+        assert!(manager.get_plugin("test-greedy").await.is_none());
+        let stats = manager.get_plugin_resource_stats("test-limits").await.unwrap();
+        assert_eq!(stats.memory_limit_bytes, 8 * 1024 * 1024);
+
+        // 声明的超时生效
+        let started = std::time::Instant::now();
+        let result = manager.test_run("test-limits").await.unwrap();
+        assert!(!result.valid);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // 重载为超限的声明时拒绝，保留原限制
+        write_plugin("test-limits", r#"{"timeoutMs": 5000}"#);
+        assert!(matches!(
+            manager.reload_plugin("test-limits").await,
+            Err(LifecycleError::ResourceLimitExceeded(_))
+        ));
+        let stats = manager.get_plugin_resource_stats("test-limits").await.unwrap();
+        assert_eq!(stats.memory_limit_bytes, 8 * 1024 * 1024);
+
+        assert!(PluginManifest::parse(
+            r#"{"id": "x", "name": "x", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data", "resources": {"memoryLimitMb": 0}}"#
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_discovery_migrates_legacy_manifest_and_reports_incompatible() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let write_plugin = |id: &str, manifest: &str| {
+            let plugin_dir = plugins_dir.join(id);
+            std::fs::create_dir_all(&plugin_dir).unwrap();
+            std::fs::write(plugin_dir.join("manifest.json"), manifest).unwrap();
+            std::fs::write(plugin_dir.join("plugin.js"), "export async function fetchData() {}").unwrap();
+        };
+        write_plugin(
+            "legacy",
+            r#"{"id": "legacy", "name": "Legacy", "version": "0.1.0", "apiVersion": "0.9", "type": "data", "refreshInterval": 120}"#,
+        );
+        write_plugin(
+            "future",
+            r#"{"id": "future", "name": "Future", "version": "3.0.0", "apiVersion": "2.0", "pluginType": "data"}"#,
+        );
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir.clone()));
+        let infos = manager.discover_and_load().await.unwrap();
+        assert_eq!(infos.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(), ["legacy"]);
+        let plugins = manager.plugins.read().await;
+        let legacy = &plugins["legacy"].manifest;
+        assert_eq!((legacy.api_version.as_str(), legacy.refresh_interval_ms), ("1.0", Some(120_000)));
+        drop(plugins);
+
+        let incompatible = manager.incompatible_plugins();
+        assert_eq!(incompatible.len(), 1);
+        assert_eq!(incompatible[0].plugin_id, "future");
+        assert_eq!(incompatible[0].reason, crate::plugin::api_version::Incompatibility::TooNew);
+
+        // 插件更新到兼容版本后重新发现即可加载，不兼容记录清除
+        write_plugin(
+            "future",
+            r#"{"id": "future", "name": "Future", "version": "3.0.1", "apiVersion": "1.0", "pluginType": "data"}"#,
+        );
+        manager.discover_and_load().await.unwrap();
+        assert!(manager.get_plugin("future").await.is_some());
+        assert!(manager.incompatible_plugins().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_describe_plugin_reports_effective_capabilities() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("describe");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"id": "describe", "name": "Describe", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "hybrid",
+                "permissions": ["network", "storage", "secrets:api_key"], "allowedHosts": ["api.example.com"],
+                "exposedMethods": ["getQuota"], "subscribedEvents": ["plugin:*:data_updated"],
+                "resources": {"memoryLimitMb": 16}, "maxRequestsPerMinute": 12}"#,
+        )
+        .unwrap();
+        std::fs::write(plugin_dir.join("plugin.js"), "export async function fetchData() {}").unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir));
+        manager.discover_and_load().await.unwrap();
+        let description = manager.describe_plugin("describe").await.unwrap();
+
+        // 未签名插件不授予 secrets，对应 API 不注入
+        assert_eq!(description.trust_level, TrustLevel::Installed);
+        assert_eq!(description.granted_permissions, ["network", "storage"]);
+        assert_eq!(description.restricted_permissions, ["secrets:api_key"]);
+        assert_eq!(description.injected_apis, ["console", "encoding", "errors", "fetch", "storage"]);
+        assert_eq!(description.exposed_methods, ["getQuota"]);
+        assert_eq!(description.subscribed_events[0].event(), "plugin:*:data_updated");
+        assert_eq!(description.manifest["allowedHosts"], serde_json::json!(["api.example.com"]));
+        assert_eq!(description.limits.memory_limit_bytes, 16 * 1024 * 1024);
+        assert_eq!(description.limits.max_requests_per_minute, 12);
+        assert_eq!(description.limits.max_concurrent_fetches, TrustLevel::Installed.limits().max_concurrent_fetches);
+        assert!(matches!(
+            manager.describe_plugin("missing").await,
+            Err(LifecycleError::PluginNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validate_plugin_config_hook_and_schema_fallback() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let write_plugin = |id: &str, manifest_extra: &str, script: &str| {
+            let dir = plugins_dir.join(id);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("manifest.json"),
+                format!(
+                    r#"{{"id": "{id}", "name": "Test", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data"{manifest_extra}}}"#
+                ),
+            )
+            .unwrap();
+            std::fs::write(dir.join("plugin.js"), script).unwrap();
+        };
+        write_plugin(
+            "test-validate",
+            "",
+            r#"
+                export async function fetchData() { return {}; }
+                export async function validateConfig(config) {
+                    return config.apiKey
+                        ? { valid: true }
+                        : { valid: true, message: '缺少凭据', fieldErrors: { apiKey: 'API Key 不能为空' } };
+                }
+            "#,
+        );
+        write_plugin(
+            "test-schema",
+            r#", "configSchema": {"apiKey": {"type": "string", "required": true}}"#,
+            "export async function fetchData() { return {}; }",
+        );
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir.clone()));
+        manager.discover_and_load().await.unwrap();
+
+        // 插件导出 validateConfig：按待保存的配置执行，字段错误使结果无效
+        let result = manager.validate_plugin_config("test-validate", &HashMap::new()).await;
+        assert!(!result.valid);
+        assert_eq!(result.message.as_deref(), Some("缺少凭据"));
+        assert_eq!(result.field_errors.unwrap()["apiKey"], "API Key 不能为空");
+        let config = HashMap::from([("apiKey".to_string(), serde_json::json!("sk-test"))]);
+        assert!(manager.validate_plugin_config("test-validate", &config).await.valid);
+
+        // 未导出 validateConfig：回退到 configSchema 校验
+        let result = manager.validate_plugin_config("test-schema", &HashMap::new()).await;
+        assert!(!result.valid);
+        assert!(result.field_errors.unwrap().contains_key("apiKey"));
+        assert!(manager.validate_plugin_config("test-schema", &config).await.valid);
+
+        assert!(!manager.validate_plugin_config("missing", &config).await.valid);
+    }
+
+    #[tokio::test]
+    async fn test_set_plugin_config_validates_schema() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("test-config");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"id": "test-config", "name": "Config", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data",
+                "configSchema": {
+                    "apiKey": {"type": "string", "required": true, "secret": true},
+                    "limit": {"type": "number", "min": 1}
+                }}"#,
+        )
+        .unwrap();
+        std::fs::write(plugin_dir.join("plugin.js"), "export async function fetchData() { return {}; }").unwrap();
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir));
+        manager.discover_and_load().await.unwrap();
+        let published = || async { manager.event_bus().get_stats().await.events_published };
+        let before = published().await;
+
+        // 校验失败：返回字段错误，不保存、不通知
+        let invalid = HashMap::from([("limit".to_string(), serde_json::json!(0))]);
+        let Err(LifecycleError::ConfigInvalid(errors)) = manager.set_plugin_config("test-config", invalid).await else {
+            panic!("应返回 ConfigInvalid");
+        };
+        let mut fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        fields.sort();
+        assert_eq!(fields, ["apiKey", "limit"]);
+        assert!(manager.get_plugin_config("test-config").await.unwrap().is_empty());
+        assert_eq!(published().await, before);
+
+        // 敏感字段接受 Keychain 引用；保存成功后发送变更通知
+        let valid = HashMap::from([("apiKey".to_string(), serde_json::json!({ "secretRef": "apiKey" }))]);
+        manager.set_plugin_config("test-config", valid.clone()).await.unwrap();
+        assert_eq!(manager.get_plugin_config("test-config").await.unwrap(), valid);
+        assert_eq!(published().await, before + 1);
+    }
+
+    #[tokio::test]
+    async fn test_enabled_state_and_config_survive_restart() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("test-state");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"id": "test-state", "name": "State", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data"}"#,
+        )
+        .unwrap();
+        std::fs::write(plugin_dir.join("plugin.js"), "export async function fetchData() { return {}; }").unwrap();
+        let config = HashMap::from([("region".to_string(), serde_json::json!("eu"))]);
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir.clone()));
+        manager.discover_and_load().await.unwrap();
+        manager.enable_plugin("test-state").await.unwrap();
+        manager.set_plugin_config("test-state", config.clone()).await.unwrap();
+        manager.record_plugin_failure("test-state", "HTTP 404".to_string()).await.unwrap();
+        let gone_since = manager.plugins.read().await["test-state"].gone_since;
+        assert!(gone_since.is_some());
+
+        // 模拟重启（失效跟踪继续计时）
+        let restarted = PluginManager::new(PluginDiscovery::new(plugins_dir.clone()));
+        let infos = restarted.discover_and_load().await.unwrap();
+        assert!(infos.iter().any(|p| p.id == "test-state" && p.enabled));
+        assert_eq!(restarted.get_plugin_config("test-state").await.unwrap(), config);
+        assert_eq!(restarted.plugins.read().await["test-state"].gone_since, gone_since);
+
+        restarted.disable_plugin("test-state").await.unwrap();
+        let restarted = PluginManager::new(PluginDiscovery::new(plugins_dir));
+        restarted.discover_and_load().await.unwrap();
+        assert!(!restarted.get_plugin("test-state").await.unwrap().enabled);
+    }
+
+    #[tokio::test]
+    async fn test_plugin_accounts_have_separate_config_and_data() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("test-accounts");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"id": "test-accounts", "name": "Accounts", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data", "dataType": "status",
+                "permissions": ["secrets:api_key"]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            plugin_dir.join("plugin.js"),
+            "export async function fetchData(config) { return { dataType: 'status', indicator: 'none', description: config.label }; }",
+        )
+        .unwrap();
+        let label = |value: &str| HashMap::from([("label".to_string(), serde_json::json!(value))]);
+
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir.clone()));
+        manager.init().await.unwrap();
+        let work = manager.add_plugin_account("test-accounts", " Work ").await.unwrap();
+        assert_eq!((work.id.as_str(), work.instance_id.as_str()), ("test-accounts", "test-accounts@2"));
+        assert_eq!(work.account_label.as_deref(), Some("Work"));
+        assert!(manager.add_plugin_account("test-accounts", "Work").await.is_err());
+        assert!(manager.add_plugin_account("test-accounts@2", "Nested").await.is_err());
+
+        for (id, value) in [("test-accounts", "personal"), ("test-accounts@2", "work")] {
+            manager.set_plugin_config(id, label(value)).await.unwrap();
+            manager.enable_plugin(id).await.unwrap();
+            manager.execute_fetch_data(id, true).await.unwrap();
+        }
+        let accounts = manager.get_plugin_accounts("test-accounts").await.unwrap();
+        let described: Vec<_> = accounts
+            .iter()
+            .map(|a| {
+                let data = serde_json::to_value(a.data.as_ref().unwrap()).unwrap();
+                (a.instance_id.clone(), data["description"].as_str().unwrap().to_string())
+            })
+            .collect();
+        assert_eq!(
+            described,
+            [("test-accounts".to_string(), "personal".to_string()), ("test-accounts@2".to_string(), "work".to_string())]
+        );
+
+        // 重启后恢复账户及其配置
+        let store = Arc::new(MemorySecretStore::default());
+        let restarted = PluginManager::new(PluginDiscovery::new(plugins_dir.clone())).with_secret_store(store.clone());
+        restarted.init().await.unwrap();
+        let info = restarted.get_plugin("test-accounts@2").await.unwrap();
+        assert!(info.enabled);
+        assert_eq!(restarted.get_plugin_config("test-accounts@2").await.unwrap(), label("work"));
+
+        // 移除账户时删除该账户的 Keychain 密钥，其他账户不受影响
+        for (id, value) in [("test-accounts", "sk-personal"), ("test-accounts@2", "sk-work")] {
+            restarted.store_plugin_secret(id, "api_key", value).await.unwrap();
+        }
+        restarted.remove_plugin_account("test-accounts@2").await.unwrap();
+        assert_eq!(store.get("cuk.plugin.test-accounts@2", "api_key").unwrap(), None);
+        assert_eq!(store.get("cuk.plugin.test-accounts", "api_key").unwrap().as_deref(), Some("sk-personal"));
+
+        // 移除账户不影响插件目录，卸载插件时账户一并移除
+        assert!(restarted.remove_plugin_account("test-accounts").await.is_err());
+        assert!(plugin_dir.exists());
+        restarted.add_plugin_account("test-accounts", "Team").await.unwrap();
+        restarted.uninstall_plugin("test-accounts").await.unwrap();
+        assert!(restarted.list_plugins().await.iter().all(|p| p.id != "test-accounts"));
+    }
+
+    /// 内存密钥存储
+    #[derive(Default)]
+    struct MemorySecretStore(std::sync::Mutex<HashMap<(String, String), String>>);
+
+    impl SecretStore for MemorySecretStore {
+        fn get(&self, service: &str, account: &str) -> Result<Option<String>, String> {
+            Ok(self.0.lock().unwrap().get(&(service.to_string(), account.to_string())).cloned())
+        }
+
+        fn set(&self, service: &str, account: &str, value: &str) -> Result<(), String> {
+            self.0.lock().unwrap().insert((service.to_string(), account.to_string()), value.to_string());
+            Ok(())
+        }
+
+        fn delete(&self, service: &str, account: &str) -> Result<(), String> {
+            self.0.lock().unwrap().remove(&(service.to_string(), account.to_string()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_secret_config_fields_stay_in_keychain() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        let plugin_dir = plugins_dir.join("test-secret");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"id": "test-secret", "name": "Secret", "version": "1.0.0", "apiVersion": "1.0", "pluginType": "data",
+                "configSchema": {"apiKey": {"type": "string", "required": true, "secret": true}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            plugin_dir.join("plugin.js"),
+            r#"
+                export async function fetchData() { return {}; }
+                export async function validateConfig(config) {
+                    return { valid: config.apiKey === 'sk-live', message: typeof config.apiKey };
+                }
+            "#,
+        )
+        .unwrap();
+
+        let store = Arc::new(MemorySecretStore::default());
+        let manager = PluginManager::new(PluginDiscovery::new(plugins_dir)).with_secret_store(store.clone());
+        manager.discover_and_load().await.unwrap();
+        let config = HashMap::from([("apiKey".to_string(), serde_json::json!("sk-live"))]);
+        manager.set_plugin_config("test-secret", config).await.unwrap();
+
+        // 明文只在 Keychain 中，内存配置和 state.json 只有引用
+        let stored = manager.get_plugin_config("test-secret").await.unwrap();
+        assert_eq!(stored["apiKey"], serde_json::json!({ "secretRef": "apiKey" }));
+        assert_eq!(store.get("cuk.plugin.test-secret", "apiKey").unwrap().as_deref(), Some("sk-live"));
+        let state = std::fs::read_to_string(temp_dir.path().join("state.json")).unwrap();
+        assert!(!state.contains("sk-live"));
+
+        // 执行插件时替换为明文
+        assert!(manager.validate_plugin_config("test-secret", &stored).await.valid);
+
+        // 首次设置向导写入密钥：只接受插件声明的名称
+        assert_eq!(manager.secret_names("test-secret").await, ["apiKey"]);
+        assert!(manager.store_plugin_secret("test-secret", "other", "x").await.is_err());
+        manager.store_plugin_secret("test-secret", "apiKey", "sk-new").await.unwrap();
+        assert_eq!(store.get("cuk.plugin.test-secret", "apiKey").unwrap().as_deref(), Some("sk-new"));
+    }
 }
@@ -0,0 +1,166 @@
+// Phase 3.8: 服务商维护窗口
+// 插件在 manifest `maintenanceWindows` 中声明服务商公布的计划维护时间，维护期间:
+// - 批量刷新和自动刷新跳过该插件（不访问服务商 API），返回缓存数据
+// - 手动强制刷新仍会执行，但失败不计入健康统计、不发送插件错误事件
+// - PluginInfo.maintenanceUntil 标注维护截止时间，前端据此说明数据为何未更新
+//
+// 支持两种窗口:
+// - 一次性窗口: { "start": "2025-01-01T02:00:00Z", "end": "2025-01-01T04:00:00Z" }
+// - 每周重复窗口（UTC）: { "weekdays": ["sun"], "startTime": "02:00", "durationMinutes": 120 }
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// 每周重复窗口的最大时长（分钟）
+const MAX_WEEKLY_DURATION_MINUTES: u32 = 7 * 24 * 60;
+
+/// 维护窗口（manifest `maintenanceWindows` 的元素）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MaintenanceWindow {
+    /// 一次性窗口
+    #[serde(rename_all = "camelCase")]
+    Scheduled {
+        /// 开始时间 (RFC 3339)
+        start: DateTime<Utc>,
+        /// 结束时间 (RFC 3339)
+        end: DateTime<Utc>,
+        /// 维护说明
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// 每周重复窗口
+    #[serde(rename_all = "camelCase")]
+    Weekly {
+        /// 星期（如 "mon"、"sunday"）
+        weekdays: Vec<Weekday>,
+        /// 开始时间 (UTC, HH:MM)
+        start_time: String,
+        /// 持续时长（分钟）
+        duration_minutes: u32,
+        /// 维护说明
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+}
+
+/// 正在进行的维护
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveMaintenance {
+    /// 维护结束时间
+    pub until: DateTime<Utc>,
+    /// 维护说明
+    pub reason: Option<String>,
+}
+
+impl MaintenanceWindow {
+    /// 校验窗口声明
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Scheduled { start, end, .. } if end <= start => {
+                Err(format!("维护窗口结束时间早于开始时间: {} - {}", start, end))
+            }
+            Self::Scheduled { .. } => Ok(()),
+            Self::Weekly {
+                weekdays,
+                start_time,
+                duration_minutes,
+                ..
+            } => {
+                if weekdays.is_empty() {
+                    return Err("每周维护窗口未声明 weekdays".to_string());
+                }
+                parse_start_time(start_time)?;
+                if *duration_minutes == 0 || *duration_minutes > MAX_WEEKLY_DURATION_MINUTES {
+                    return Err(format!(
+                        "每周维护窗口时长应为 1-{} 分钟: {}",
+                        MAX_WEEKLY_DURATION_MINUTES, duration_minutes
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// `now` 位于窗口内时返回维护结束时间
+    pub fn active_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Scheduled { start, end, .. } => (*start <= now && now < *end).then_some(*end),
+            Self::Weekly {
+                weekdays,
+                start_time,
+                duration_minutes,
+                ..
+            } => {
+                let time = parse_start_time(start_time).ok()?;
+                let duration = Duration::minutes(i64::from(*duration_minutes));
+                // 跨午夜（或跨多天）的窗口可能从前几天开始
+                (0..=7)
+                    .filter_map(|days_ago| {
+                        let date = (now - Duration::days(days_ago)).date_naive();
+                        weekdays.contains(&date.weekday()).then(|| date.and_time(time).and_utc())
+                    })
+                    .map(|start| start + duration)
+                    .filter(|end| now < *end && now >= *end - duration)
+                    .max()
+            }
+        }
+    }
+
+    fn reason(&self) -> Option<&String> {
+        match self {
+            Self::Scheduled { reason, .. } | Self::Weekly { reason, .. } => reason.as_ref(),
+        }
+    }
+}
+
+/// 解析 HH:MM 格式的开始时间
+fn parse_start_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| format!("无效的维护开始时间（应为 HH:MM）: {}", value))
+}
+
+/// 当前正在进行的维护（多个窗口重叠时取最晚结束的一个）
+pub fn active_maintenance(windows: &[MaintenanceWindow], now: DateTime<Utc>) -> Option<ActiveMaintenance> {
+    windows
+        .iter()
+        .filter_map(|window| window.active_until(now).map(|until| (until, window)))
+        .max_by_key(|(until, _)| *until)
+        .map(|(until, window)| ActiveMaintenance {
+            until,
+            reason: window.reason().cloned(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_maintenance_windows() {
+        let windows: Vec<MaintenanceWindow> = serde_json::from_str(
+            r#"[
+                {"start": "2025-03-01T02:00:00Z", "end": "2025-03-01T04:00:00Z", "reason": "数据库升级"},
+                {"weekdays": ["sun"], "startTime": "23:00", "durationMinutes": 120}
+            ]"#,
+        )
+        .unwrap();
+        assert!(windows.iter().all(|w| w.validate().is_ok()));
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+
+        // 2025-03-01 为周六
+        let active = active_maintenance(&windows, at("2025-03-01T03:00:00Z")).unwrap();
+        assert_eq!(active.until, at("2025-03-01T04:00:00Z"));
+        assert_eq!(active.reason.as_deref(), Some("数据库升级"));
+        assert_eq!(active_maintenance(&windows, at("2025-03-01T04:00:00Z")), None);
+
+        // 周日 23:00 开始的窗口跨越午夜
+        let active = active_maintenance(&windows, at("2025-03-03T00:30:00Z")).unwrap();
+        assert_eq!(active.until, at("2025-03-03T01:00:00Z"));
+        assert_eq!(active_maintenance(&windows, at("2025-03-03T01:00:00Z")), None);
+
+        let invalid: MaintenanceWindow =
+            serde_json::from_str(r#"{"weekdays": ["mon"], "startTime": "25:00", "durationMinutes": 30}"#).unwrap();
+        assert!(invalid.validate().is_err());
+    }
+}
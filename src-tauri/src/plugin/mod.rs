@@ -2,16 +2,43 @@
 // Phase 2: 插件运行时核心
 // Phase 4: 通信与配置
 // Phase 6: 监控层
+// Phase 7.13: 内置数据源
 
+pub mod api_version;
+pub mod batch;
+pub mod budget;
+pub mod bulk;
+pub mod builtin;
 pub mod config;
+pub mod data_schema;
+pub mod developer;
+pub mod diagnostics;
+pub mod env_import;
 pub mod event_bus;
+pub mod gateway;
+pub mod host_timer;
+pub mod legacy;
 pub mod lifecycle;
+pub mod maintenance;
 pub mod monitoring;
+pub mod network_stats;
 pub mod permission;
+pub mod plugin_logs;
+pub mod pricing;
+pub mod proxy;
+pub mod push;
+pub mod registry;
 pub mod runtime;
 pub mod sandbox;
+pub mod state;
+pub mod summary;
+pub mod tls;
+pub mod trust;
 pub mod types;
+pub mod updates;
+pub mod usage_history;
 pub mod watcher;
+pub mod wizard;
 
 #[cfg(test)]
 mod tests;
@@ -24,8 +51,9 @@ pub use runtime::{
 
 // 导出沙盒 API
 pub use sandbox::{
-    ConsoleApi, EncodingApi, FetchApi, PluginErrorApi, RequestManager, SandboxApiInitializer,
-    TimerApi, TimerRegistry, UrlSecurityChecker,
+    ConsoleApi, EncodingApi, FetchApi, HttpCache, InputApi, InputBroker, InputRequest, PluginCache,
+    PluginDataReader, PluginErrorApi, PluginInput, PluginSecrets, PluginStorage, RequestManager,
+    SandboxApiInitializer, SecretsApi, StorageApi, TimerApi, TimerRegistry, UrlSecurityChecker,
 };
 
 // 导出生命周期管理
@@ -35,13 +63,14 @@ pub use lifecycle::{
 };
 
 // 导出热重载
-pub use watcher::{HotReloadEvent, HotReloadManager, PluginWatcher};
+pub use watcher::{HotReloadEvent, HotReloadManager, PluginWatcher, ReloadDebouncer};
 
 // 导出监控层 (Phase 6)
 pub use monitoring::{
-    Alert, AlertData, AlertManager, AlertSeverity, AlertStats, AlertThresholds, AlertType,
-    NotificationHandler, SlidingWindow, TauriNotificationHandler, WindowStats,
-    create_alert_manager_with_notifications, DEFAULT_WINDOW_SIZE,
+    Alert, AlertData, AlertManager, AlertSeverity, AlertSilences, AlertStats, AlertThresholds,
+    AlertType, NotificationHandler, SlidingWindow, TauriNotificationHandler, UsageAlert,
+    UsageAlertSettings, UsageAlertTracker, WindowStats, create_alert_manager_with_notifications,
+    ALL_PLUGINS, DEFAULT_WINDOW_SIZE,
 };
 
 // 导出类型定义
@@ -49,8 +78,8 @@ pub use types::*;
 
 // 导出事件总线 (Phase 4.1)
 pub use event_bus::{
-    EventBus, EventBusConfig, EventBusError, EventBusStats, EventDispatchResult, EventPrefix,
-    QueuedEvent, system_events,
+    DeadLetter, EventBus, EventBusConfig, EventBusError, EventBusStats, EventDispatchResult,
+    EventFilter, EventPrefix, EventSubscription, QueuedEvent, SubscriberMetrics, system_events,
 };
 
 // 导出配置管理 (Phase 4.2)
@@ -1,14 +1,28 @@
 // Phase 6.3: 告警机制
 // 实现连续失败、高延迟、低成功率告警
+// Phase 6.6: 告警暂停/静音（持久化到 alert_silences.json）
+// Phase 6.13: 用量异常告警（最近一小时消耗明显高于基线）
+// Phase 6.16: 告警历史持久化（alert_history.json），条件恢复时标记 resolvedAt
+// Phase 6.17: 看门狗自动禁用失控插件时触发 Critical 告警
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
+use super::alert_history::{AlertHistory, AlertHistoryFilter, AlertRecord};
+use super::watchdog::WatchdogTrip;
+use crate::plugin::monitoring::format_rfc3339;
+use crate::plugin::types::PluginHealth;
+use crate::plugin::usage_history::{UsageAnomaly, ANOMALY_Z_SCORE_THRESHOLD};
+
 /// 告警类型
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AlertType {
     /// 连续失败告警 (3 次失败触发)
     ConsecutiveFailures,
@@ -16,6 +30,10 @@ pub enum AlertType {
     HighLatency,
     /// 低成功率告警 (<80%)
     LowSuccessRate,
+    /// 用量异常告警（每小时消耗 z-score ≥ 3）
+    AnomalyDetected,
+    /// 失控插件被看门狗自动禁用
+    AutoDisabled,
 }
 
 impl AlertType {
@@ -25,12 +43,15 @@ impl AlertType {
             AlertType::ConsecutiveFailures => "连续失败",
             AlertType::HighLatency => "高延迟",
             AlertType::LowSuccessRate => "低成功率",
+            AlertType::AnomalyDetected => "用量异常",
+            AlertType::AutoDisabled => "自动禁用",
         }
     }
 }
 
 /// 告警级别
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AlertSeverity {
     /// 警告
     Warning,
@@ -73,6 +94,8 @@ pub enum AlertData {
     HighLatency(f64),
     /// 低成功率值 (0-1)
     LowSuccessRate(f64),
+    /// 用量异常（含对比基线）
+    AnomalyDetected(UsageAnomaly),
 }
 
 /// 告警阈值配置
@@ -86,6 +109,8 @@ pub struct AlertThresholds {
     pub low_success_rate: f64,
     /// 告警冷却时间 (秒)
     pub cooldown_seconds: u64,
+    /// 用量异常告警冷却时间 (秒)，异常按小时统计，同一小时内只提醒一次
+    pub anomaly_cooldown_seconds: u64,
 }
 
 impl Default for AlertThresholds {
@@ -95,8 +120,79 @@ impl Default for AlertThresholds {
             high_latency_ms: 5000.0,
             low_success_rate: 0.8,
             cooldown_seconds: 300, // 5 分钟冷却
+            anomaly_cooldown_seconds: 3600,
+        }
+    }
+}
+
+// ============================================================================
+// 告警静默
+// ============================================================================
+
+/// 暂停全部插件提醒时使用的键
+pub const ALL_PLUGINS: &str = "*";
+
+/// 告警静默设置（alert_silences.json）
+///
+/// 静音的插件不再发送通知，直到取消静音；暂停在截止时间后自动恢复。
+/// 使用墙钟时间记录截止时间，重启后仍然有效。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AlertSilences {
+    /// 已静音的插件 ID
+    pub muted: BTreeSet<String>,
+    /// 暂停提醒的插件 ID -> 截止时间（"*" 表示全部插件）
+    pub snoozed: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl AlertSilences {
+    /// 默认配置文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("alert_silences.json")
+    }
+
+    /// 从文件加载（文件不存在或无法解析时不静默任何插件）
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析告警静默设置失败，忽略: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
         }
     }
+
+    /// 保存到文件（临时文件 + rename）
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// 插件提醒暂停的截止时间（取插件和全局暂停中较晚的一个）
+    pub fn snoozed_until(&self, plugin_id: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        [plugin_id, ALL_PLUGINS]
+            .iter()
+            .filter_map(|key| self.snoozed.get(*key).copied())
+            .filter(|until| *until > now)
+            .max()
+    }
+
+    /// 插件提醒是否被静默
+    pub fn is_silenced(&self, plugin_id: &str, now: DateTime<Utc>) -> bool {
+        self.muted.contains(plugin_id) || self.snoozed_until(plugin_id, now).is_some()
+    }
+
+    /// 移除已过期的暂停
+    fn prune(&mut self, now: DateTime<Utc>) {
+        self.snoozed.retain(|_, until| *until > now);
+    }
 }
 
 /// 告警冷却键
@@ -120,10 +216,16 @@ pub struct AlertManager {
     history: RwLock<VecDeque<Alert>>,
     /// 历史记录最大数量
     max_history: usize,
+    /// 持久化的告警历史（Phase 6.16）
+    records: AlertHistory,
     /// 冷却映射 (plugin_id + alert_type -> 上次告警时间)
     cooldown_map: RwLock<HashMap<CooldownKey, Instant>>,
     /// 通知回调
     notification_handler: Option<Arc<dyn NotificationHandler + Send + Sync>>,
+    /// 告警静默设置
+    silences: std::sync::RwLock<AlertSilences>,
+    /// 告警静默设置文件路径（为 None 时仅保存在内存）
+    silences_path: Option<PathBuf>,
 }
 
 /// 通知处理器 trait
@@ -141,11 +243,27 @@ impl AlertManager {
             thresholds,
             history: RwLock::new(VecDeque::with_capacity(100)),
             max_history: 100,
+            records: AlertHistory::in_memory(),
             cooldown_map: RwLock::new(HashMap::new()),
             notification_handler: None,
+            silences: std::sync::RwLock::new(AlertSilences::default()),
+            silences_path: None,
         }
     }
 
+    /// 从文件加载告警静默设置，变更时写回该文件
+    pub fn with_silences_file(mut self, path: PathBuf) -> Self {
+        self.silences = std::sync::RwLock::new(AlertSilences::load(&path));
+        self.silences_path = Some(path);
+        self
+    }
+
+    /// 从文件加载告警历史，新告警写回该文件
+    pub fn with_history_file(mut self, path: PathBuf) -> Self {
+        self.records = AlertHistory::load(path);
+        self
+    }
+
     /// 使用默认配置创建
     pub fn with_defaults() -> Self {
         Self::new(AlertThresholds::default())
@@ -233,6 +351,49 @@ impl AlertManager {
         }
     }
 
+    /// 检查用量异常告警
+    ///
+    /// z-score 达到阈值两倍时为严重告警
+    pub async fn check_usage_anomaly(&self, anomaly: &UsageAnomaly) {
+        let severity = if anomaly.z_score >= ANOMALY_Z_SCORE_THRESHOLD * 2.0 {
+            AlertSeverity::Critical
+        } else {
+            AlertSeverity::Warning
+        };
+
+        self.trigger_alert(
+            AlertType::AnomalyDetected,
+            severity,
+            &anomaly.plugin_id,
+            format!(
+                "插件 {} 最近一小时用量增长 {:.1}%，远高于过去 {} 小时的基线 {:.1}% ± {:.1}% (z={:.1})，请检查是否存在密钥泄露或失控的自动化任务",
+                anomaly.plugin_id,
+                anomaly.observed,
+                anomaly.baseline_hours,
+                anomaly.baseline_mean,
+                anomaly.baseline_std_dev,
+                anomaly.z_score
+            ),
+            Some(AlertData::AnomalyDetected(anomaly.clone())),
+        )
+        .await;
+    }
+
+    /// 插件被看门狗自动禁用（Critical，提示用户排查后重新启用）
+    pub async fn check_auto_disabled(&self, trip: &WatchdogTrip) {
+        self.trigger_alert(
+            AlertType::AutoDisabled,
+            AlertSeverity::Critical,
+            &trip.plugin_id,
+            format!(
+                "插件 {} 连续 {} 次执行超时或内存超限，已自动禁用。最后错误: {}。排查后可在插件列表中重新启用",
+                trip.plugin_id, trip.consecutive_runaways, trip.last_error
+            ),
+            None,
+        )
+        .await;
+    }
+
     /// 触发告警
     ///
     /// P1 修复：原子化冷却检查与更新，避免并发竞态。
@@ -252,7 +413,12 @@ impl AlertManager {
         // P1 修复：在同一个写锁临界区完成冷却检查与更新
         {
             let mut cooldown_map = self.cooldown_map.write().await;
-            let cooldown = Duration::from_secs(self.thresholds.cooldown_seconds);
+            let cooldown = Duration::from_secs(match alert_type {
+                AlertType::AnomalyDetected => self.thresholds.anomaly_cooldown_seconds,
+                // 每次自动禁用都需要通知（重新启用后再次失控不应被冷却吞掉）
+                AlertType::AutoDisabled => 0,
+                _ => self.thresholds.cooldown_seconds,
+            });
 
             if let Some(&last_time) = cooldown_map.get(&key) {
                 // P2 修复：缓存 elapsed 避免多次调用导致下溢
@@ -292,6 +458,7 @@ impl AlertManager {
             }
             history.push_back(alert.clone());
         }
+        self.records.record(alert_type, severity, plugin_id, &message, Utc::now());
 
         // 发送日志
         match severity {
@@ -303,6 +470,12 @@ impl AlertManager {
             }
         }
 
+        // 静默的插件只记录不通知
+        if self.is_silenced(plugin_id) {
+            log::debug!("插件 {} 告警已静默，跳过通知", plugin_id);
+            return;
+        }
+
         // 发送系统通知
         if let Some(ref handler) = self.notification_handler {
             let title = format!("[{}] 插件告警", severity.as_str());
@@ -331,11 +504,94 @@ impl AlertManager {
         self.history.write().await.clear();
     }
 
+    /// 查询持久化的告警历史（按触发时间倒序）
+    pub fn query_history(&self, filter: &AlertHistoryFilter) -> Vec<AlertRecord> {
+        self.records.query(filter)
+    }
+
+    /// 确认告警，告警不存在时返回 None
+    pub fn acknowledge(&self, id: u64) -> Option<AlertRecord> {
+        self.records.acknowledge(id, Utc::now())
+    }
+
+    /// 告警条件恢复：标记插件指定类型的未恢复告警为已恢复
+    pub fn resolve(&self, plugin_id: &str, types: &[AlertType]) {
+        let resolved = self.records.resolve(plugin_id, types, Utc::now());
+        if resolved > 0 {
+            log::info!("插件 {} 的 {} 条告警已恢复", plugin_id, resolved);
+        }
+    }
+
     /// 清除冷却（用于测试）
     pub async fn clear_cooldown(&self) {
         self.cooldown_map.write().await.clear();
     }
 
+    // ========================================================================
+    // 告警静默 (6.6)
+    // ========================================================================
+
+    /// 修改静默设置并持久化
+    fn update_silences(&self, f: impl FnOnce(&mut AlertSilences)) -> std::io::Result<()> {
+        let mut silences = self.silences.write().unwrap_or_else(|e| e.into_inner());
+        silences.prune(Utc::now());
+        f(&mut silences);
+        match &self.silences_path {
+            Some(path) => silences.save(path),
+            None => Ok(()),
+        }
+    }
+
+    /// 暂停插件提醒，返回截止时间（`ALL_PLUGINS` 暂停全部插件）
+    pub fn snooze(&self, plugin_id: &str, duration: Duration) -> std::io::Result<DateTime<Utc>> {
+        let until = Utc::now()
+            + chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::days(365));
+        self.update_silences(|s| {
+            s.snoozed.insert(plugin_id.to_string(), until);
+        })?;
+        log::info!("已暂停插件 {} 的提醒至 {}", plugin_id, format_rfc3339(until));
+        Ok(until)
+    }
+
+    /// 静音插件提醒（直到取消静音）
+    pub fn mute(&self, plugin_id: &str) -> std::io::Result<()> {
+        self.update_silences(|s| {
+            s.muted.insert(plugin_id.to_string());
+        })?;
+        log::info!("已静音插件 {} 的提醒", plugin_id);
+        Ok(())
+    }
+
+    /// 取消插件的静音和暂停
+    pub fn unmute(&self, plugin_id: &str) -> std::io::Result<()> {
+        self.update_silences(|s| {
+            s.muted.remove(plugin_id);
+            s.snoozed.remove(plugin_id);
+        })
+    }
+
+    /// 取消所有暂停（静音的插件保持静音）
+    pub fn resume_all(&self) -> std::io::Result<()> {
+        self.update_silences(|s| s.snoozed.clear())
+    }
+
+    /// 插件提醒是否被静默
+    pub fn is_silenced(&self, plugin_id: &str) -> bool {
+        self.silences
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_silenced(plugin_id, Utc::now())
+    }
+
+    /// 在健康状态中标注静默状态
+    pub fn annotate_health(&self, health: &mut PluginHealth) {
+        let silences = self.silences.read().unwrap_or_else(|e| e.into_inner());
+        health.alerts_muted = silences.muted.contains(&health.plugin_id);
+        health.alerts_snoozed_until = silences
+            .snoozed_until(&health.plugin_id, Utc::now())
+            .map(format_rfc3339);
+    }
+
     /// 获取告警统计
     pub async fn get_stats(&self) -> AlertStats {
         let history = self.history.read().await;
@@ -353,6 +609,7 @@ impl AlertManager {
             consecutive_failures: by_type.get(&AlertType::ConsecutiveFailures).copied().unwrap_or(0),
             high_latency: by_type.get(&AlertType::HighLatency).copied().unwrap_or(0),
             low_success_rate: by_type.get(&AlertType::LowSuccessRate).copied().unwrap_or(0),
+            anomalies: by_type.get(&AlertType::AnomalyDetected).copied().unwrap_or(0),
             warnings: by_severity.get(&AlertSeverity::Warning).copied().unwrap_or(0),
             criticals: by_severity.get(&AlertSeverity::Critical).copied().unwrap_or(0),
         }
@@ -370,6 +627,8 @@ pub struct AlertStats {
     pub high_latency: usize,
     /// 低成功率告警数
     pub low_success_rate: usize,
+    /// 用量异常告警数
+    pub anomalies: usize,
     /// 警告级别数
     pub warnings: usize,
     /// 严重级别数
@@ -425,6 +684,29 @@ mod tests {
         assert_eq!(history[0].alert_type, AlertType::LowSuccessRate);
     }
 
+    #[tokio::test]
+    async fn test_usage_anomaly_alert() {
+        let manager = AlertManager::with_defaults();
+        let anomaly = UsageAnomaly {
+            plugin_id: "test-plugin".to_string(),
+            observed: 30.0,
+            baseline_mean: 2.0,
+            baseline_std_dev: 1.0,
+            baseline_hours: 12,
+            z_score: 28.0,
+        };
+
+        manager.check_usage_anomaly(&anomaly).await;
+        // 同一小时内不重复告警
+        manager.check_usage_anomaly(&anomaly).await;
+        let history = manager.get_history().await;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].alert_type, AlertType::AnomalyDetected);
+        assert_eq!(history[0].severity, AlertSeverity::Critical);
+        assert!(matches!(&history[0].data, Some(AlertData::AnomalyDetected(a)) if a.baseline_mean == 2.0));
+        assert_eq!(manager.get_stats().await.anomalies, 1);
+    }
+
     #[tokio::test]
     async fn test_cooldown() {
         let thresholds = AlertThresholds {
@@ -510,4 +792,39 @@ mod tests {
         let history = manager.get_history().await;
         assert_eq!(history.len(), 3, "不同告警类型应该分别触发");
     }
+
+    #[tokio::test]
+    async fn test_snooze_and_mute_persist() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("alert_silences.json");
+        let manager = AlertManager::with_defaults().with_silences_file(path.clone());
+
+        manager.snooze("a", Duration::from_secs(3600)).unwrap();
+        manager.mute("b").unwrap();
+        assert!(manager.is_silenced("a") && manager.is_silenced("b"));
+        assert!(!manager.is_silenced("c"));
+
+        // 静默的插件仍记录告警历史
+        manager.check_consecutive_failures("a", 3).await;
+        assert_eq!(manager.get_history().await.len(), 1);
+
+        // 重启后恢复静默状态
+        let restarted = AlertManager::with_defaults().with_silences_file(path);
+        let mut health: PluginHealth = serde_json::from_value(serde_json::json!({
+            "pluginId": "a", "status": "healthy", "errorCount": 0, "avgLatencyMs": 0.0,
+            "p99LatencyMs": 0.0, "successRate": 1.0, "totalCalls": 0, "consecutiveFailures": 0
+        }))
+        .unwrap();
+        restarted.annotate_health(&mut health);
+        assert!(!health.alerts_muted && health.alerts_snoozed_until.is_some());
+
+        // 全局暂停覆盖所有插件，恢复后仅保留静音
+        restarted.snooze(ALL_PLUGINS, Duration::from_secs(60)).unwrap();
+        assert!(restarted.is_silenced("c"));
+        restarted.resume_all().unwrap();
+        assert!(!restarted.is_silenced("a") && !restarted.is_silenced("c"));
+        assert!(restarted.is_silenced("b"));
+        restarted.unmute("b").unwrap();
+        assert!(!restarted.is_silenced("b"));
+    }
 }
@@ -0,0 +1,300 @@
+// Phase 6.16: 告警历史
+// AlertManager 触发的告警持久化到磁盘，供告警历史页面查询、确认
+//
+// 设计要点:
+// 1. 持久化到 alert_history.json（与插件目录同级），重启后历史不丢失
+// 2. 告警条件恢复时（插件恢复健康、用量不再异常）记录 resolvedAt；用户确认后记录 acknowledgedAt
+// 3. 最多保留 1000 条、30 天，每次写入和加载时自动清理
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::alert::{AlertSeverity, AlertType};
+
+/// 最多保留的告警条数
+const MAX_ALERT_HISTORY: usize = 1000;
+
+/// 告警保留天数
+const ALERT_HISTORY_RETENTION_DAYS: i64 = 30;
+
+/// 默认查询条数
+const DEFAULT_QUERY_LIMIT: usize = 100;
+
+/// 告警历史记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertRecord {
+    /// 历史内唯一 ID
+    pub id: u64,
+    /// 告警类型
+    pub alert_type: AlertType,
+    /// 告警级别
+    pub severity: AlertSeverity,
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 告警消息
+    pub message: String,
+    /// 触发时间
+    pub timestamp: DateTime<Utc>,
+    /// 告警条件恢复的时间
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// 用户确认的时间
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+/// 告警历史查询条件（均为可选，按触发时间倒序返回）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AlertHistoryFilter {
+    /// 插件 ID
+    pub plugin_id: Option<String>,
+    /// 告警类型
+    pub alert_type: Option<AlertType>,
+    /// 告警级别
+    pub severity: Option<AlertSeverity>,
+    /// 只返回该时间之后触发的告警
+    pub since: Option<DateTime<Utc>>,
+    /// 只返回未恢复的告警
+    pub unresolved_only: bool,
+    /// 只返回未确认的告警
+    pub unacknowledged_only: bool,
+    /// 最多返回条数（默认 100）
+    pub limit: Option<usize>,
+}
+
+impl AlertHistoryFilter {
+    fn matches(&self, record: &AlertRecord) -> bool {
+        self.plugin_id.as_ref().map_or(true, |id| &record.plugin_id == id)
+            && self.alert_type.map_or(true, |t| record.alert_type == t)
+            && self.severity.map_or(true, |s| record.severity == s)
+            && self.since.map_or(true, |since| record.timestamp >= since)
+            && !(self.unresolved_only && record.resolved_at.is_some())
+            && !(self.unacknowledged_only && record.acknowledged_at.is_some())
+    }
+}
+
+/// 历史文件内容
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct HistoryData {
+    next_id: u64,
+    alerts: VecDeque<AlertRecord>,
+}
+
+impl Default for HistoryData {
+    fn default() -> Self {
+        Self {
+            next_id: 1,
+            alerts: VecDeque::new(),
+        }
+    }
+}
+
+impl HistoryData {
+    /// 丢弃超出条数或保留天数的告警
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::days(ALERT_HISTORY_RETENTION_DAYS);
+        self.alerts.retain(|a| a.timestamp > cutoff);
+        while self.alerts.len() > MAX_ALERT_HISTORY {
+            self.alerts.pop_front();
+        }
+    }
+}
+
+/// 告警历史（alert_history.json）
+pub struct AlertHistory {
+    data: Mutex<HistoryData>,
+    /// 持久化路径（为 None 时仅保存在内存）
+    path: Option<PathBuf>,
+}
+
+impl AlertHistory {
+    /// 默认文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("alert_history.json")
+    }
+
+    /// 仅保存在内存的历史
+    pub fn in_memory() -> Self {
+        Self {
+            data: Mutex::new(HistoryData::default()),
+            path: None,
+        }
+    }
+
+    /// 从文件加载（文件不存在或无法解析时从空历史开始）
+    pub fn load(path: PathBuf) -> Self {
+        let mut data = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析告警历史失败，重新开始记录: {}", e);
+                HistoryData::default()
+            }),
+            Err(_) => HistoryData::default(),
+        };
+        data.prune(Utc::now());
+        Self {
+            data: Mutex::new(data),
+            path: Some(path),
+        }
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    fn save(&self, data: &HistoryData) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_vec(data)?)?;
+            std::fs::rename(&tmp, path)
+        })();
+        if let Err(e) = result {
+            log::warn!("保存告警历史失败: {}", e);
+        }
+    }
+
+    /// 记录一条告警
+    pub fn record(
+        &self,
+        alert_type: AlertType,
+        severity: AlertSeverity,
+        plugin_id: &str,
+        message: &str,
+        now: DateTime<Utc>,
+    ) -> AlertRecord {
+        let mut data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        let record = AlertRecord {
+            id: data.next_id,
+            alert_type,
+            severity,
+            plugin_id: plugin_id.to_string(),
+            message: message.to_string(),
+            timestamp: now,
+            resolved_at: None,
+            acknowledged_at: None,
+        };
+        data.next_id += 1;
+        data.alerts.push_back(record.clone());
+        data.prune(now);
+        self.save(&data);
+        record
+    }
+
+    /// 查询告警历史（按触发时间倒序）
+    pub fn query(&self, filter: &AlertHistoryFilter) -> Vec<AlertRecord> {
+        let data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        data.alerts
+            .iter()
+            .rev()
+            .filter(|record| filter.matches(record))
+            .take(filter.limit.unwrap_or(DEFAULT_QUERY_LIMIT))
+            .cloned()
+            .collect()
+    }
+
+    /// 确认告警，返回更新后的记录（已确认的告警保持原确认时间）
+    pub fn acknowledge(&self, id: u64, now: DateTime<Utc>) -> Option<AlertRecord> {
+        let mut data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        let record = data.alerts.iter_mut().find(|a| a.id == id)?;
+        if record.acknowledged_at.is_none() {
+            record.acknowledged_at = Some(now);
+        }
+        let record = record.clone();
+        self.save(&data);
+        Some(record)
+    }
+
+    /// 标记插件指定类型的未恢复告警为已恢复，返回标记的条数
+    pub fn resolve(&self, plugin_id: &str, types: &[AlertType], now: DateTime<Utc>) -> usize {
+        let mut data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        let mut resolved = 0;
+        for record in data.alerts.iter_mut().filter(|a| {
+            a.plugin_id == plugin_id && a.resolved_at.is_none() && types.contains(&a.alert_type)
+        }) {
+            record.resolved_at = Some(now);
+            resolved += 1;
+        }
+        if resolved > 0 {
+            self.save(&data);
+        }
+        resolved
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_alert_history_persist_resolve_acknowledge() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("alert_history.json");
+        let history = AlertHistory::load(path.clone());
+        let now = Utc::now();
+
+        let first = history.record(AlertType::ConsecutiveFailures, AlertSeverity::Warning, "a", "连续失败", now);
+        history.record(AlertType::AnomalyDetected, AlertSeverity::Critical, "a", "用量异常", now);
+        history.record(AlertType::HighLatency, AlertSeverity::Warning, "b", "高延迟", now);
+
+        assert_eq!(history.resolve("a", &[AlertType::ConsecutiveFailures, AlertType::HighLatency], now), 1);
+        assert!(history.acknowledge(first.id, now).is_some());
+        assert!(history.acknowledge(999, now).is_none());
+
+        // 重新加载后状态保留，ID 继续递增
+        let history = AlertHistory::load(path);
+        let all = history.query(&AlertHistoryFilter::default());
+        assert_eq!(all.iter().map(|a| a.plugin_id.as_str()).collect::<Vec<_>>(), ["b", "a", "a"]);
+        assert!(all[2].resolved_at.is_some() && all[2].acknowledged_at.is_some());
+
+        let filter = AlertHistoryFilter {
+            plugin_id: Some("a".to_string()),
+            unresolved_only: true,
+            ..Default::default()
+        };
+        let unresolved = history.query(&filter);
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].alert_type, AlertType::AnomalyDetected);
+
+        let next = history.record(AlertType::LowSuccessRate, AlertSeverity::Critical, "b", "低成功率", now);
+        assert_eq!(next.id, 4);
+        let critical = AlertHistoryFilter { severity: Some(AlertSeverity::Critical), limit: Some(1), ..Default::default() };
+        assert_eq!(history.query(&critical)[0].id, 4);
+    }
+
+    #[test]
+    fn test_alert_history_retention() {
+        let history = AlertHistory::in_memory();
+        history.record(AlertType::HighLatency, AlertSeverity::Warning, "a", "旧告警", at("2025-02-20T00:00:00Z"));
+        for _ in 0..MAX_ALERT_HISTORY {
+            history.record(AlertType::HighLatency, AlertSeverity::Warning, "a", "新告警", at("2025-03-01T00:00:00Z"));
+        }
+        let all = history.query(&AlertHistoryFilter { limit: Some(usize::MAX), ..Default::default() });
+        assert_eq!(all.len(), MAX_ALERT_HISTORY);
+        // 超出条数上限时丢弃最旧的告警
+        assert!(all.iter().all(|a| a.message == "新告警"));
+
+        // 超过保留天数的告警在下一次写入时清理
+        history.record(AlertType::HighLatency, AlertSeverity::Warning, "a", "最新", at("2025-04-15T00:00:00Z"));
+        assert_eq!(history.query(&AlertHistoryFilter::default()).len(), 1);
+    }
+}
@@ -0,0 +1,268 @@
+// Phase 6.10: 未送达告警队列
+// 通知权限被拒绝或系统繁忙时，告警不能直接丢弃：写入磁盘队列，后台按指数退避重投，
+// 前端可读取"错过的告警"数量；超过过期时间仍未送达的告警才被丢弃。
+//
+// 设计要点:
+// 1. 持久化到 missed_alerts.json（与插件目录同级），重启后继续重投
+// 2. 退避间隔 30s 起按 2 倍递增，上限 1 小时
+// 3. 过期时间可配置（默认 24 小时，最长 7 天），队列长度有上限，超出时丢弃最旧的告警
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 默认过期时间（小时）
+pub const DEFAULT_MISSED_ALERT_EXPIRY_HOURS: u32 = 24;
+
+/// 最长过期时间（小时）
+pub const MAX_MISSED_ALERT_EXPIRY_HOURS: u32 = 7 * 24;
+
+/// 队列长度上限
+const MAX_QUEUED_ALERTS: usize = 200;
+
+/// 首次重投延迟（秒）
+const INITIAL_RETRY_DELAY_SECS: i64 = 30;
+
+/// 重投延迟上限（秒）
+const MAX_RETRY_DELAY_SECS: i64 = 60 * 60;
+
+/// 未送达的告警
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissedAlert {
+    /// 队列内唯一 ID
+    pub id: u64,
+    /// 通知标题
+    pub title: String,
+    /// 通知内容
+    pub body: String,
+    /// 是否为严重告警
+    pub critical: bool,
+    /// 首次发送失败的时间
+    pub created_at: DateTime<Utc>,
+    /// 已重投次数
+    pub attempts: u32,
+    /// 下次重投时间
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+/// 错过的告警摘要（IPC 返回）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissedAlertSummary {
+    /// 待重投的告警数
+    pub count: usize,
+    /// 过期时间（小时）
+    pub expiry_hours: u32,
+    /// 待重投的告警（按时间升序）
+    pub alerts: Vec<MissedAlert>,
+}
+
+/// 队列文件内容
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct QueueData {
+    expiry_hours: u32,
+    next_id: u64,
+    alerts: Vec<MissedAlert>,
+}
+
+impl Default for QueueData {
+    fn default() -> Self {
+        Self {
+            expiry_hours: DEFAULT_MISSED_ALERT_EXPIRY_HOURS,
+            next_id: 1,
+            alerts: Vec::new(),
+        }
+    }
+}
+
+impl QueueData {
+    /// 丢弃已过期的告警，返回是否有变化
+    fn prune(&mut self, now: DateTime<Utc>) -> bool {
+        let cutoff = now - Duration::hours(self.expiry_hours as i64);
+        let before = self.alerts.len();
+        self.alerts.retain(|a| a.created_at > cutoff);
+        let dropped = before - self.alerts.len();
+        if dropped > 0 {
+            log::warn!("丢弃 {} 条过期未送达的告警", dropped);
+        }
+        dropped > 0
+    }
+}
+
+/// 未送达告警队列（missed_alerts.json）
+pub struct AlertQueue {
+    data: Mutex<QueueData>,
+    /// 持久化路径
+    path: PathBuf,
+}
+
+impl AlertQueue {
+    /// 默认文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("missed_alerts.json")
+    }
+
+    /// 从文件加载（文件不存在或无法解析时从空队列开始）
+    pub fn load(path: PathBuf) -> Self {
+        let data = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析未送达告警队列失败，重新开始: {}", e);
+                QueueData::default()
+            }),
+            Err(_) => QueueData::default(),
+        };
+        Self {
+            data: Mutex::new(data),
+            path,
+        }
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    fn save(&self, data: &QueueData) {
+        let path = &self.path;
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_vec(data)?)?;
+            std::fs::rename(&tmp, path)
+        })();
+        if let Err(e) = result {
+            log::warn!("保存未送达告警队列失败: {}", e);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueData> {
+        self.data.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 加入发送失败的告警
+    pub fn enqueue(&self, title: &str, body: &str, critical: bool, now: DateTime<Utc>) {
+        let mut data = self.lock();
+        data.prune(now);
+        if data.alerts.len() >= MAX_QUEUED_ALERTS {
+            data.alerts.remove(0);
+        }
+        let id = data.next_id;
+        data.next_id += 1;
+        data.alerts.push(MissedAlert {
+            id,
+            title: title.to_string(),
+            body: body.to_string(),
+            critical,
+            created_at: now,
+            attempts: 0,
+            next_attempt_at: now + Duration::seconds(INITIAL_RETRY_DELAY_SECS),
+        });
+        self.save(&data);
+    }
+
+    /// 取出到期需要重投的告警（同时丢弃已过期的告警）
+    pub fn take_due(&self, now: DateTime<Utc>) -> Vec<MissedAlert> {
+        let mut data = self.lock();
+        if data.prune(now) {
+            self.save(&data);
+        }
+        data.alerts
+            .iter()
+            .filter(|a| a.next_attempt_at <= now)
+            .cloned()
+            .collect()
+    }
+
+    /// 重投成功，移出队列
+    pub fn mark_delivered(&self, id: u64) {
+        let mut data = self.lock();
+        let before = data.alerts.len();
+        data.alerts.retain(|a| a.id != id);
+        if data.alerts.len() != before {
+            self.save(&data);
+        }
+    }
+
+    /// 重投失败，按指数退避安排下次重投
+    pub fn mark_failed(&self, id: u64, now: DateTime<Utc>) {
+        let mut data = self.lock();
+        let Some(alert) = data.alerts.iter_mut().find(|a| a.id == id) else {
+            return;
+        };
+        alert.attempts += 1;
+        let delay = INITIAL_RETRY_DELAY_SECS
+            .saturating_mul(1_i64 << alert.attempts.min(16))
+            .min(MAX_RETRY_DELAY_SECS);
+        alert.next_attempt_at = now + Duration::seconds(delay);
+        self.save(&data);
+    }
+
+    /// 错过的告警摘要
+    pub fn summary(&self, now: DateTime<Utc>) -> MissedAlertSummary {
+        let mut data = self.lock();
+        if data.prune(now) {
+            self.save(&data);
+        }
+        MissedAlertSummary {
+            count: data.alerts.len(),
+            expiry_hours: data.expiry_hours,
+            alerts: data.alerts.clone(),
+        }
+    }
+
+    /// 清空队列（用户已查看）
+    pub fn clear(&self) {
+        let mut data = self.lock();
+        if !data.alerts.is_empty() {
+            data.alerts.clear();
+            self.save(&data);
+        }
+    }
+
+    /// 设置过期时间（限制在 1..=MAX_MISSED_ALERT_EXPIRY_HOURS），返回实际生效值
+    pub fn set_expiry_hours(&self, hours: u32) -> u32 {
+        let mut data = self.lock();
+        data.expiry_hours = hours.clamp(1, MAX_MISSED_ALERT_EXPIRY_HOURS);
+        self.save(&data);
+        data.expiry_hours
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_queue_backoff_and_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missed_alerts.json");
+        let queue = AlertQueue::load(path.clone());
+        let now = Utc::now();
+
+        queue.enqueue("[WARNING] 插件告警", "连续失败 3 次", false, now);
+        assert!(queue.take_due(now).is_empty());
+
+        // 到期后重投失败，退避翻倍
+        let due_at = now + Duration::seconds(INITIAL_RETRY_DELAY_SECS);
+        let due = queue.take_due(due_at);
+        assert_eq!(due.len(), 1);
+        queue.mark_failed(due[0].id, due_at);
+        let reloaded = AlertQueue::load(path);
+        let alert = &reloaded.summary(due_at).alerts[0];
+        assert_eq!(alert.attempts, 1);
+        assert_eq!(alert.next_attempt_at, due_at + Duration::seconds(2 * INITIAL_RETRY_DELAY_SECS));
+
+        // 超过过期时间后丢弃
+        assert_eq!(reloaded.set_expiry_hours(0), 1);
+        assert_eq!(reloaded.summary(now + Duration::hours(2)).count, 0);
+    }
+}
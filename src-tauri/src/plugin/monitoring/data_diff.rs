@@ -0,0 +1,232 @@
+// Phase 6.11: 刷新结果差异
+// 每次刷新后与上一次结果比较，得到使用百分比的变化量和越过的关键边界（50/80/90%），
+// 前端据此播放变化动画，告警只需在数据实际变化时评估，而不是每次刷新都评估。
+//
+// 设计要点:
+// 1. 差异在 Rust 侧统一计算，前端与告警共享同一份结果
+// 2. 主用量和各维度分别比较；首次刷新没有上一次结果，不产生越界
+// 3. 只记录内存中的上一次百分比，重启后从头开始
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+use crate::plugin::types::PluginData;
+
+/// 关键边界（百分比）
+pub const NOTABLE_BOUNDARIES: [f64; 3] = [50.0, 80.0, 90.0];
+
+/// 越界方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CrossingDirection {
+    /// 从边界以下升到边界及以上
+    Up,
+    /// 从边界及以上回落到边界以下
+    Down,
+}
+
+/// 越过的边界
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoundaryCrossing {
+    /// 边界百分比
+    pub boundary: f64,
+    /// 越界方向
+    pub direction: CrossingDirection,
+}
+
+/// 单个用量计量（主用量或维度）的变化
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PercentageChange {
+    /// 维度 ID（主用量为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimension_id: Option<String>,
+    /// 维度标签（主用量为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// 上一次的使用百分比（首次刷新为 None）
+    pub previous_percentage: Option<f64>,
+    /// 当前使用百分比
+    pub percentage: f64,
+    /// 变化量（首次刷新为 0）
+    pub delta: f64,
+    /// 越过的关键边界（按越过顺序）
+    pub crossings: Vec<BoundaryCrossing>,
+}
+
+impl PercentageChange {
+    fn new(dimension_id: Option<&str>, label: Option<&str>, previous: Option<f64>, percentage: f64) -> Self {
+        let delta = previous.map_or(0.0, |p| percentage - p);
+        let crossings = match previous {
+            Some(previous) => boundary_crossings(previous, percentage),
+            None => Vec::new(),
+        };
+        Self {
+            dimension_id: dimension_id.map(str::to_string),
+            label: label.map(str::to_string),
+            previous_percentage: previous,
+            percentage,
+            delta,
+            crossings,
+        }
+    }
+
+    /// 与上一次结果相比是否有变化（首次刷新视为有变化）
+    pub fn is_changed(&self) -> bool {
+        self.previous_percentage.is_none() || self.delta != 0.0
+    }
+}
+
+/// 刷新结果差异（ipc:plugin_data_changed 事件 Payload）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataChange {
+    /// 插件 ID
+    pub id: String,
+    /// 主用量变化
+    #[serde(flatten)]
+    pub primary: PercentageChange,
+    /// 各维度变化
+    pub dimensions: Vec<PercentageChange>,
+}
+
+impl DataChange {
+    /// 是否有任何计量发生变化
+    pub fn is_changed(&self) -> bool {
+        self.primary.is_changed() || self.dimensions.iter().any(PercentageChange::is_changed)
+    }
+
+    /// 是否越过了任何关键边界
+    pub fn has_crossings(&self) -> bool {
+        !self.primary.crossings.is_empty() || self.dimensions.iter().any(|d| !d.crossings.is_empty())
+    }
+}
+
+/// 从 previous 变为 current 时越过的关键边界
+fn boundary_crossings(previous: f64, current: f64) -> Vec<BoundaryCrossing> {
+    let crossings = NOTABLE_BOUNDARIES.iter().filter_map(|&boundary| {
+        let direction = if previous < boundary && current >= boundary {
+            CrossingDirection::Up
+        } else if previous >= boundary && current < boundary {
+            CrossingDirection::Down
+        } else {
+            return None;
+        };
+        Some(BoundaryCrossing { boundary, direction })
+    });
+    // 回落时按从高到低的顺序越过
+    if current < previous {
+        crossings.rev().collect()
+    } else {
+        crossings.collect()
+    }
+}
+
+/// 刷新结果差异跟踪器
+///
+/// 记录每个插件（及维度）上一次的使用百分比
+#[derive(Default)]
+pub struct DataDiffTracker {
+    /// (plugin_id, dimension_id) -> 上一次的使用百分比
+    previous: Mutex<HashMap<(String, Option<String>), f64>>,
+}
+
+impl DataDiffTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录新的刷新结果，返回与上一次结果的差异
+    ///
+    /// 只有使用量数据有百分比，其他数据类型返回 None
+    pub fn record(&self, data: &PluginData) -> Option<DataChange> {
+        let PluginData::Usage(usage) = data else {
+            return None;
+        };
+        let plugin_id = &usage.base.plugin_id;
+        let mut previous = self.previous.lock().unwrap_or_else(|e| e.into_inner());
+        let mut track = |dimension_id: Option<&str>, label: Option<&str>, percentage: f64| {
+            let key = (plugin_id.clone(), dimension_id.map(str::to_string));
+            let last = previous.get(&key).copied();
+            if percentage.is_finite() {
+                previous.insert(key, percentage);
+            }
+            PercentageChange::new(dimension_id, label, last, percentage)
+        };
+
+        let primary = track(None, None, usage.percentage);
+        let dimensions = usage
+            .dimensions
+            .iter()
+            .flatten()
+            .map(|d| track(Some(&d.id), Some(&d.label), d.percentage))
+            .collect();
+        Some(DataChange {
+            id: plugin_id.clone(),
+            primary,
+            dimensions,
+        })
+    }
+
+    /// 清除插件的记录（卸载后重新安装时从头开始）
+    pub fn remove_plugin(&self, plugin_id: &str) {
+        let mut previous = self.previous.lock().unwrap_or_else(|e| e.into_inner());
+        previous.retain(|(id, _), _| id != plugin_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn usage(percentage: f64, weekly: f64) -> PluginData {
+        serde_json::from_value(json!({
+            "dataType": "usage", "pluginId": "claude", "lastUpdated": "2025-01-01T00:00:00Z",
+            "percentage": percentage, "used": percentage, "limit": 100.0, "unit": "%",
+            "dimensions": [{
+                "id": "weekly", "label": "Weekly", "percentage": weekly, "used": weekly, "limit": 100.0
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_record_diff_and_crossings() {
+        let tracker = DataDiffTracker::new();
+
+        // 首次刷新：没有上一次结果
+        let first = tracker.record(&usage(45.0, 10.0)).unwrap();
+        assert_eq!(first.primary.previous_percentage, None);
+        assert!(first.is_changed() && !first.has_crossings());
+
+        // 一次越过多个边界
+        let change = tracker.record(&usage(85.0, 10.0)).unwrap();
+        assert_eq!(change.primary.delta, 40.0);
+        let boundaries: Vec<f64> = change.primary.crossings.iter().map(|c| c.boundary).collect();
+        assert_eq!(boundaries, vec![50.0, 80.0]);
+        assert!(change.dimensions[0].crossings.is_empty());
+
+        // 数据未变化
+        assert!(!tracker.record(&usage(85.0, 10.0)).unwrap().is_changed());
+
+        // 回落按从高到低的顺序
+        let change = tracker.record(&usage(20.0, 90.0)).unwrap();
+        assert_eq!(
+            change.primary.crossings,
+            vec![
+                BoundaryCrossing { boundary: 80.0, direction: CrossingDirection::Down },
+                BoundaryCrossing { boundary: 50.0, direction: CrossingDirection::Down },
+            ]
+        );
+        assert_eq!(change.dimensions[0].crossings.len(), 3);
+
+        let value = serde_json::to_value(&change).unwrap();
+        assert_eq!(value["id"], "claude");
+        assert_eq!(value["previousPercentage"], 85.0);
+        assert_eq!(value["dimensions"][0]["dimensionId"], "weekly");
+    }
+}
@@ -0,0 +1,152 @@
+// Phase 6: 插件错误摘要
+// 服务商返回的错误体格式各异（JSON / HTML / 纯文本，语言不一），直接存入 last_error
+// 既冗长又可能包含无关内容。这里提取对用户有意义的错误信息并安全截断。
+//
+// 支持的常见 JSON 结构:
+// - {"error": {"message": "..."}}          (Anthropic / OpenAI)
+// - {"error": "...", "error_description"}  (OAuth)
+// - {"message": "..."} / {"msg": "..."}
+// - {"detail": "..."} / {"detail": [{"msg": "..."}]}  (FastAPI)
+// - {"errors": [{"message": "..."}]}        (GraphQL)
+// - {"title": "..."}                        (RFC 7807)
+
+use serde_json::Value;
+
+/// 错误摘要最大字符数
+pub const MAX_ERROR_SUMMARY_CHARS: usize = 300;
+
+/// JSON 嵌套查找深度上限
+const MAX_LOOKUP_DEPTH: usize = 3;
+
+/// 尝试解析 JSON 的起始位置数上限（跳过 `[RATE_LIMIT]` 之类的前缀标记）
+const MAX_JSON_CANDIDATES: usize = 4;
+
+/// 提取错误摘要
+///
+/// 保留 JSON 之前的前缀（如 `HTTP 401: `），JSON 体替换为其中的错误信息；
+/// HTML 响应取 `<title>`；折叠空白并按字符截断。
+pub fn summarize_error(raw: &str) -> String {
+    let summary = raw
+        .match_indices(['{', '['])
+        .take(MAX_JSON_CANDIDATES)
+        .find_map(|(start, _)| {
+            let message = parse_json_suffix(&raw[start..]).and_then(|v| extract_message(&v, 0))?;
+            Some(format!("{}{}", &raw[..start], message))
+        })
+        .unwrap_or_else(|| raw.to_string());
+    let summary = summarize_html(&summary).unwrap_or(summary);
+    truncate_chars(&collapse_whitespace(&summary), MAX_ERROR_SUMMARY_CHARS)
+}
+
+/// 解析从 JSON 开始到最后一个闭合括号的内容
+fn parse_json_suffix(text: &str) -> Option<Value> {
+    let end = text.rfind(['}', ']'])?;
+    serde_json::from_str(&text[..=end]).ok()
+}
+
+/// 从错误 JSON 中提取信息
+fn extract_message(value: &Value, depth: usize) -> Option<String> {
+    if depth > MAX_LOOKUP_DEPTH {
+        return None;
+    }
+    match value {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Array(items) => items.iter().find_map(|item| extract_message(item, depth + 1)),
+        Value::Object(map) => {
+            const MESSAGE_KEYS: &[&str] = &[
+                "message",
+                "error_description",
+                "error",
+                "detail",
+                "errors",
+                "msg",
+                "title",
+                "description",
+            ];
+            let message = MESSAGE_KEYS
+                .iter()
+                .filter_map(|key| map.get(*key))
+                .find_map(|v| extract_message(v, depth + 1))?;
+            // 带上机器可读的错误类型/代码，便于区分同名信息
+            let code = ["type", "code"]
+                .iter()
+                .filter_map(|key| map.get(*key))
+                .find_map(|v| match v {
+                    Value::String(s) if !s.is_empty() && !message.contains(s.as_str()) => Some(s.clone()),
+                    Value::Number(n) => Some(n.to_string()),
+                    _ => None,
+                });
+            Some(match code {
+                Some(code) => format!("{} ({})", message, code),
+                None => message,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// HTML 错误页只保留标题
+fn summarize_html(text: &str) -> Option<String> {
+    let lower = text.to_ascii_lowercase();
+    let start = ["<!doctype", "<html"].iter().filter_map(|tag| lower.find(tag)).min()?;
+    let title = lower
+        .find("<title>")
+        .map(|i| i + "<title>".len())
+        .and_then(|i| lower[i..].find("</title>").map(|j| text[i..i + j].trim()))
+        .filter(|t| !t.is_empty())
+        .unwrap_or("HTML 错误页");
+    Some(format!("{}{}", &text[..start], title))
+}
+
+/// 折叠连续空白为单个空格
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 按字符数截断（不会截断在 UTF-8 字符中间）
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_provider_errors() {
+        assert_eq!(
+            summarize_error(
+                r#"HTTP 401: {"type":"error","error":{"type":"authentication_error","message":"invalid x-api-key"}}"#
+            ),
+            "HTTP 401: invalid x-api-key (authentication_error)"
+        );
+        assert_eq!(
+            summarize_error(r#"HTTP 422: {"detail":[{"loc":["body"],"msg":"字段缺失","type":"missing"}]}"#),
+            "HTTP 422: 字段缺失 (missing)"
+        );
+        assert_eq!(
+            summarize_error(r#"{"error":"invalid_grant","error_description":"Token expired"}"#),
+            "Token expired"
+        );
+        assert_eq!(
+            summarize_error("HTTP 502: <!DOCTYPE html><html><head><title> 502 Bad Gateway </title></head></html>"),
+            "HTTP 502: 502 Bad Gateway"
+        );
+        assert_eq!(
+            summarize_error(r#"[RATE_LIMIT] HTTP 429: {"message":"Too many requests"}"#),
+            "[RATE_LIMIT] HTTP 429: Too many requests"
+        );
+        assert_eq!(summarize_error("网络错误:\n  连接超时"), "网络错误: 连接超时");
+    }
+
+    #[test]
+    fn test_summarize_truncates_on_char_boundary() {
+        let long = "错".repeat(MAX_ERROR_SUMMARY_CHARS + 10);
+        let summary = summarize_error(&long);
+        assert_eq!(summary.chars().count(), MAX_ERROR_SUMMARY_CHARS + 1);
+        assert!(summary.ends_with('…'));
+    }
+}
@@ -2,12 +2,28 @@
 // 插件健康状态监控、调用统计、告警机制
 
 mod alert;
+mod alert_history;
+mod alert_queue;
+mod data_diff;
+mod error_summary;
 mod notification;
 mod sliding_window;
+mod system_health;
+mod timestamp;
+mod usage_alert;
+mod watchdog;
 
 pub use alert::{
-    Alert, AlertData, AlertManager, AlertSeverity, AlertStats, AlertThresholds, AlertType,
-    NotificationHandler,
+    Alert, AlertData, AlertManager, AlertSeverity, AlertSilences, AlertStats, AlertThresholds,
+    AlertType, NotificationHandler, ALL_PLUGINS,
 };
+pub use alert_history::{AlertHistory, AlertHistoryFilter, AlertRecord};
+pub use alert_queue::{AlertQueue, MissedAlertSummary};
+pub use data_diff::{DataChange, DataDiffTracker};
+pub use error_summary::summarize_error;
 pub use notification::{create_alert_manager_with_notifications, TauriNotificationHandler};
 pub use sliding_window::{SlidingWindow, WindowStats, DEFAULT_WINDOW_SIZE};
+pub use system_health::SystemHealth;
+pub use timestamp::{format_rfc3339, normalize_timestamp, RecordedAt};
+pub use usage_alert::{UsageAlert, UsageAlertSettings, UsageAlertTracker};
+pub use watchdog::{is_runaway_error, WatchdogPolicy, WatchdogTrip};
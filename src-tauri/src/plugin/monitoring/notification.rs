@@ -2,9 +2,9 @@
 // 实现系统通知发送功能
 
 use super::alert::NotificationHandler;
+use crate::commands::missed_alerts::deliver_notification;
 use std::sync::Arc;
 use tauri::AppHandle;
-use tauri_plugin_notification::NotificationExt;
 
 /// Tauri 通知处理器
 ///
@@ -27,17 +27,18 @@ impl NotificationHandler for TauriNotificationHandler {
     ///
     /// 使用 tauri-plugin-notification 发送系统级通知
     fn send_notification(&self, title: &str, body: &str, is_critical: bool) {
-        let builder = self.app_handle.notification().builder();
-
-        // 构建通知
-        let notification = builder
-            .title(title)
-            .body(body);
-
-        // 发送通知
-        if let Err(e) = notification.show() {
-            log::error!("发送系统通知失败: {}", e);
+        // 告警同时反映到托盘图标
+        let status = if is_critical {
+            crate::tray::TrayStatus::Error
         } else {
+            crate::tray::TrayStatus::Warning
+        };
+        if let Err(e) = crate::tray::update_tray_status(&self.app_handle, status) {
+            log::warn!("更新托盘告警状态失败: {}", e);
+        }
+
+        // 发送通知（失败时加入未送达队列等待重投）
+        if deliver_notification(&self.app_handle, title, body, is_critical) {
             if is_critical {
                 log::warn!("已发送严重告警通知: {}", title);
             } else {
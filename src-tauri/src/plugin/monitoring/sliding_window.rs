@@ -1,5 +1,6 @@
 // Phase 6.1.2: 滑动窗口成功率统计
 // 基于环形缓冲区实现最近 N 次调用的统计
+// 同时记录最近 N 次沙盒执行的资源占用（CPU 时间、内存）
 
 use std::collections::VecDeque;
 use std::time::Instant;
@@ -18,6 +19,15 @@ pub struct CallResult {
     pub timestamp: Instant,
 }
 
+/// 单次沙盒执行的资源占用
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceSample {
+    /// CPU 时间 (毫秒)
+    pub cpu_time_ms: f64,
+    /// 运行时内存 (字节)
+    pub memory_bytes: u64,
+}
+
 /// 滑动窗口统计
 ///
 /// 使用环形缓冲区存储最近 N 次调用结果，
@@ -28,6 +38,8 @@ pub struct CallResult {
 pub struct SlidingWindow {
     /// 调用结果队列
     results: VecDeque<CallResult>,
+    /// 资源占用样本队列（与调用结果共用窗口大小）
+    resources: VecDeque<ResourceSample>,
     /// 窗口大小（最小为 1）
     window_size: usize,
 }
@@ -42,6 +54,7 @@ impl SlidingWindow {
         let effective_size = window_size.max(1);
         Self {
             results: VecDeque::with_capacity(effective_size),
+            resources: VecDeque::new(),
             window_size: effective_size,
         }
     }
@@ -78,6 +91,47 @@ impl SlidingWindow {
         self.results.push_back(result);
     }
 
+    /// 记录一次沙盒执行的资源占用
+    ///
+    /// 一次调用可能包含多次沙盒执行（重试、生命周期钩子），因此与调用结果分开记录
+    pub fn record_resources(&mut self, cpu_time_ms: f64, memory_bytes: u64) {
+        if self.resources.len() >= self.window_size {
+            self.resources.pop_front();
+        }
+        let cpu_time_ms = if cpu_time_ms.is_finite() { cpu_time_ms.max(0.0) } else { 0.0 };
+        self.resources.push_back(ResourceSample { cpu_time_ms, memory_bytes });
+    }
+
+    /// 最近一次执行的资源占用
+    pub fn last_resources(&self) -> Option<ResourceSample> {
+        self.resources.back().copied()
+    }
+
+    /// 窗口内的资源样本数
+    pub fn resource_sample_count(&self) -> usize {
+        self.resources.len()
+    }
+
+    /// 平均 CPU 时间 (毫秒)，无样本时返回 0.0
+    pub fn avg_cpu_time_ms(&self) -> f64 {
+        if self.resources.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = self.resources.iter().map(|r| r.cpu_time_ms).sum();
+        let avg = sum / self.resources.len() as f64;
+        if avg.is_finite() { avg } else { 0.0 }
+    }
+
+    /// 最大 CPU 时间 (毫秒)
+    pub fn max_cpu_time_ms(&self) -> f64 {
+        self.resources.iter().map(|r| r.cpu_time_ms).fold(0.0, f64::max)
+    }
+
+    /// 峰值内存 (字节)
+    pub fn peak_memory_bytes(&self) -> u64 {
+        self.resources.iter().map(|r| r.memory_bytes).max().unwrap_or(0)
+    }
+
     /// 获取当前窗口中的调用次数
     pub fn count(&self) -> usize {
         self.results.len()
@@ -158,6 +212,7 @@ impl SlidingWindow {
     /// 清空窗口
     pub fn clear(&mut self) {
         self.results.clear();
+        self.resources.clear();
     }
 
     /// 获取统计快照
@@ -172,6 +227,8 @@ impl SlidingWindow {
             success_rate: self.success_rate(),
             avg_latency_ms: self.avg_latency_ms(),
             p99_latency_ms: self.p99_latency_ms(),
+            avg_cpu_time_ms: self.avg_cpu_time_ms(),
+            peak_memory_bytes: self.peak_memory_bytes(),
         }
     }
 
@@ -204,6 +261,10 @@ pub struct WindowStats {
     pub avg_latency_ms: f64,
     /// P99 延迟
     pub p99_latency_ms: f64,
+    /// 平均 CPU 时间 (毫秒)
+    pub avg_cpu_time_ms: f64,
+    /// 峰值内存 (字节)
+    pub peak_memory_bytes: u64,
 }
 
 #[cfg(test)]
@@ -345,6 +406,29 @@ mod tests {
         assert_eq!(stats.failure_count, 1);
     }
 
+    #[test]
+    fn test_resource_samples() {
+        let mut window = SlidingWindow::new(3);
+        assert_eq!(window.peak_memory_bytes(), 0);
+        assert_eq!(window.last_resources().map(|r| r.memory_bytes), None);
+
+        window.record_resources(10.0, 4096);
+        window.record_resources(f64::NAN, 1024);
+        window.record_resources(20.0, 2048);
+        assert!((window.avg_cpu_time_ms() - 10.0).abs() < 0.001);
+        assert_eq!(window.max_cpu_time_ms(), 20.0);
+        assert_eq!(window.peak_memory_bytes(), 4096);
+
+        // 超出窗口时旧样本被推出
+        window.record_resources(30.0, 512);
+        assert_eq!(window.resource_sample_count(), 3);
+        assert_eq!(window.peak_memory_bytes(), 2048);
+        assert_eq!(window.last_resources().unwrap().memory_bytes, 512);
+
+        window.clear();
+        assert_eq!(window.resource_sample_count(), 0);
+    }
+
     #[test]
     fn test_window_size_zero_protection() {
         // P3 修复：window_size=0 应该被自动调整为 1
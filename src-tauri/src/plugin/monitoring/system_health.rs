@@ -0,0 +1,39 @@
+// Phase 6.12: 系统健康状态
+// 汇总宿主层面的运行状态（插件数量、网络、生效的管理员策略），供设置页和企业管理员排查使用。
+
+use serde::Serialize;
+
+use crate::plugin::types::PluginInfo;
+use crate::reliability::NetworkStatus;
+use crate::security::policy::PolicyStatus;
+
+/// 系统健康状态（get_system_health 返回）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemHealth {
+    /// 应用版本
+    pub app_version: String,
+    /// 已加载插件数
+    pub plugin_count: usize,
+    /// 已启用插件数
+    pub enabled_count: usize,
+    /// 不健康插件数
+    pub unhealthy_count: usize,
+    /// 网络状态
+    pub network: NetworkStatus,
+    /// 管理员策略
+    pub policy: PolicyStatus,
+}
+
+impl SystemHealth {
+    pub fn new(plugins: &[PluginInfo], network: NetworkStatus, policy: PolicyStatus) -> Self {
+        Self {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            plugin_count: plugins.len(),
+            enabled_count: plugins.iter().filter(|p| p.enabled).count(),
+            unhealthy_count: plugins.iter().filter(|p| p.enabled && !p.healthy).count(),
+            network,
+            policy,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// Phase 6.1.4: 稳定时间戳
+// 记录时同时保存墙钟时间和单调时钟锚点，避免读取时由 Instant 反推墙钟导致的漂移
+//
+// 背景: macOS 睡眠期间单调时钟不计时，`Utc::now() - instant.elapsed()` 在唤醒后
+// 会把记录时间推后（睡眠 1 小时后 "3 分钟前" 变成 "刚刚"）。
+// - 序列化使用记录时的墙钟时间（RFC 3339，毫秒精度，UTC）
+// - 经过时长取单调时钟与墙钟差值中的较大者：
+//   睡眠后墙钟差值更准确，系统时间被回拨时单调时钟更准确
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+
+/// 记录时刻
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecordedAt {
+    /// 记录时的墙钟时间
+    wall: DateTime<Utc>,
+    /// 记录时的单调时钟锚点
+    anchor: Instant,
+}
+
+impl RecordedAt {
+    /// 记录当前时刻
+    pub fn now() -> Self {
+        Self {
+            wall: Utc::now(),
+            anchor: Instant::now(),
+        }
+    }
+
+    /// 稳定的 RFC 3339 表示（多次读取结果相同）
+    pub fn to_rfc3339(&self) -> String {
+        format_rfc3339(self.wall)
+    }
+
+    /// 距记录时刻经过的时长
+    pub fn age(&self) -> Duration {
+        let monotonic = self.anchor.elapsed();
+        let wall = (Utc::now() - self.wall).to_std().unwrap_or_default();
+        monotonic.max(wall)
+    }
+}
+
+/// 统一的 RFC 3339 格式（UTC，毫秒精度）
+pub fn format_rfc3339(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// 将插件返回的时间值规范化为 RFC 3339
+///
+/// 支持 RFC 3339 字符串（任意时区）和 Unix 时间戳（秒或毫秒），无法识别时返回 None。
+pub fn normalize_timestamp(value: &serde_json::Value) -> Option<String> {
+    let time = match value {
+        serde_json::Value::String(s) => DateTime::parse_from_rfc3339(s.trim())
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+        serde_json::Value::Number(n) => {
+            let n = n.as_f64()?;
+            // 大于 1e11 视为毫秒（秒级时间戳在 5138 年前都小于该值）
+            let millis = if n.abs() >= 1e11 { n } else { n * 1000.0 };
+            Utc.timestamp_millis_opt(millis as i64).single()
+        }
+        _ => None,
+    }?;
+    Some(format_rfc3339(time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_recorded_at_is_stable() {
+        let recorded = RecordedAt::now();
+        let first = recorded.to_rfc3339();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(recorded.to_rfc3339(), first);
+        assert!(recorded.age() >= Duration::from_millis(5));
+
+        // 模拟睡眠：墙钟已过去 1 小时，单调时钟几乎未走
+        let slept = RecordedAt {
+            wall: Utc::now() - chrono::Duration::hours(1),
+            anchor: Instant::now(),
+        };
+        assert!(slept.age() >= Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn test_normalize_timestamp() {
+        let expected = Some("2025-01-01T00:00:00.000Z".to_string());
+        assert_eq!(normalize_timestamp(&json!("2025-01-01T08:00:00+08:00")), expected);
+        assert_eq!(normalize_timestamp(&json!(1735689600)), expected);
+        assert_eq!(normalize_timestamp(&json!(1735689600000u64)), expected);
+        assert_eq!(normalize_timestamp(&json!("yesterday")), None);
+    }
+}
@@ -0,0 +1,293 @@
+// Phase 6.5: 用量阈值告警
+// 刷新后按用户配置的阈值（如 80%/95%）检查使用量，并去重
+//
+// 设计要点:
+// 1. 阈值按插件配置，未单独配置的插件使用默认阈值，空列表表示不提醒
+// 2. 同一阈值在一个重置周期内只触发一次（主用量和各维度分别计算）
+// 3. 没有重置时间的用量回落到阈值以下后重新计算
+// 4. 一次刷新同时越过多个阈值时只提醒最高的一个
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::types::PluginData;
+
+/// 默认提醒阈值（百分比）
+pub const DEFAULT_USAGE_THRESHOLDS: [f64; 2] = [80.0, 95.0];
+
+/// 重置时间比较容差（秒），插件按 "now + 剩余时长" 计算时会有抖动
+const RESET_TIME_TOLERANCE_SECS: i64 = 60;
+
+// ============================================================================
+// 阈值设置
+// ============================================================================
+
+/// 用量提醒设置（usage_alerts.json）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UsageAlertSettings {
+    /// 是否启用用量提醒
+    pub enabled: bool,
+    /// 默认阈值（百分比）
+    pub default_thresholds: Vec<f64>,
+    /// 按插件配置的阈值（覆盖默认阈值，空列表表示不提醒）
+    pub plugins: BTreeMap<String, Vec<f64>>,
+}
+
+impl Default for UsageAlertSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_thresholds: DEFAULT_USAGE_THRESHOLDS.to_vec(),
+            plugins: BTreeMap::new(),
+        }
+    }
+}
+
+impl UsageAlertSettings {
+    /// 默认配置文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("usage_alerts.json")
+    }
+
+    /// 从文件加载（文件不存在或无法解析时使用默认设置）
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析用量提醒设置失败，使用默认设置: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// 插件生效的阈值（过滤无效值，升序去重）
+    pub fn thresholds_for(&self, plugin_id: &str) -> Vec<f64> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        let mut thresholds: Vec<f64> = self
+            .plugins
+            .get(plugin_id)
+            .unwrap_or(&self.default_thresholds)
+            .iter()
+            .copied()
+            .filter(|t| t.is_finite() && *t > 0.0 && *t <= 100.0)
+            .collect();
+        thresholds.sort_by(|a, b| a.total_cmp(b));
+        thresholds.dedup();
+        thresholds
+    }
+}
+
+// ============================================================================
+// 阈值检查与去重
+// ============================================================================
+
+/// 用量提醒
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageAlert {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 维度标签（主用量为 None）
+    pub dimension: Option<String>,
+    /// 越过的阈值
+    pub threshold: f64,
+    /// 当前使用百分比
+    pub percentage: f64,
+    /// 重置时间
+    pub reset_time: Option<String>,
+}
+
+/// 单个用量计量的触发状态
+#[derive(Debug, Default)]
+struct FiredState {
+    /// 触发时的重置时间（标识重置周期）
+    period: Option<String>,
+    /// 本周期已触发的阈值
+    fired: Vec<f64>,
+}
+
+/// 用量计量（主用量或维度）
+struct Meter<'a> {
+    dimension_id: Option<&'a str>,
+    label: Option<&'a str>,
+    percentage: f64,
+    reset_time: Option<&'a str>,
+}
+
+/// 两个重置时间是否属于同一周期
+fn same_period(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            match (DateTime::parse_from_rfc3339(a.trim()), DateTime::parse_from_rfc3339(b.trim())) {
+                (Ok(a), Ok(b)) => (a - b).num_seconds().abs() <= RESET_TIME_TOLERANCE_SECS,
+                _ => a == b,
+            }
+        }
+        (a, b) => a == b,
+    }
+}
+
+/// 用量提醒跟踪器
+///
+/// 记录每个插件（及维度）在当前重置周期内已触发的阈值
+#[derive(Default)]
+pub struct UsageAlertTracker {
+    /// (plugin_id, dimension_id) -> 触发状态
+    states: Mutex<HashMap<(String, Option<String>), FiredState>>,
+}
+
+impl UsageAlertTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 检查插件数据，返回需要提醒的用量
+    ///
+    /// `thresholds` 需升序排列（见 [`UsageAlertSettings::thresholds_for`]）
+    pub fn evaluate(&self, data: &PluginData, thresholds: &[f64]) -> Vec<UsageAlert> {
+        let PluginData::Usage(usage) = data else {
+            return Vec::new();
+        };
+        if thresholds.is_empty() {
+            return Vec::new();
+        }
+
+        let meters = std::iter::once(Meter {
+            dimension_id: None,
+            label: None,
+            percentage: usage.percentage,
+            reset_time: usage.reset_time.as_deref(),
+        })
+        .chain(usage.dimensions.iter().flatten().map(|d| Meter {
+            dimension_id: Some(&d.id),
+            label: Some(&d.label),
+            percentage: d.percentage,
+            reset_time: d.reset_time.as_deref(),
+        }));
+
+        let plugin_id = &usage.base.plugin_id;
+        let mut states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        let mut alerts = Vec::new();
+
+        for meter in meters {
+            if !meter.percentage.is_finite() {
+                continue;
+            }
+            let state = states
+                .entry((plugin_id.clone(), meter.dimension_id.map(str::to_string)))
+                .or_default();
+
+            // 进入新的重置周期，所有阈值重新计算
+            if !same_period(state.period.as_deref(), meter.reset_time) {
+                state.fired.clear();
+            }
+            state.period = meter.reset_time.map(str::to_string);
+
+            // 没有重置时间时，回落到阈值以下即重新计算
+            if meter.reset_time.is_none() {
+                state.fired.retain(|t| meter.percentage >= *t);
+            }
+
+            let crossed: Vec<f64> = thresholds
+                .iter()
+                .copied()
+                .filter(|t| meter.percentage >= *t && !state.fired.contains(t))
+                .collect();
+            let Some(&highest) = crossed.last() else {
+                continue;
+            };
+            state.fired.extend(crossed);
+
+            alerts.push(UsageAlert {
+                plugin_id: plugin_id.clone(),
+                dimension: meter.label.map(str::to_string),
+                threshold: highest,
+                percentage: meter.percentage,
+                reset_time: meter.reset_time.map(str::to_string),
+            });
+        }
+
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn usage(percentage: f64, reset_time: Option<&str>, weekly: f64) -> PluginData {
+        serde_json::from_value(json!({
+            "dataType": "usage", "pluginId": "claude", "lastUpdated": "2025-01-01T00:00:00Z",
+            "percentage": percentage, "used": percentage, "limit": 100.0, "unit": "%",
+            "resetTime": reset_time,
+            "dimensions": [{
+                "id": "weekly", "label": "Weekly", "percentage": weekly, "used": weekly, "limit": 100.0
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_thresholds_for() {
+        let mut settings = UsageAlertSettings::default();
+        assert_eq!(settings.thresholds_for("a"), vec![80.0, 95.0]);
+
+        settings.plugins.insert("a".to_string(), vec![95.0, 50.0, 50.0, 150.0, -1.0]);
+        settings.plugins.insert("b".to_string(), vec![]);
+        assert_eq!(settings.thresholds_for("a"), vec![50.0, 95.0]);
+        assert!(settings.thresholds_for("b").is_empty());
+
+        settings.enabled = false;
+        assert!(settings.thresholds_for("a").is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_dedupes_per_reset_period() {
+        let tracker = UsageAlertTracker::new();
+        let thresholds = [80.0, 95.0];
+        let period = Some("2025-01-01T05:00:00Z");
+
+        assert!(tracker.evaluate(&usage(50.0, period, 10.0), &thresholds).is_empty());
+
+        // 一次越过两个阈值只提醒最高的一个
+        let alerts = tracker.evaluate(&usage(96.0, period, 10.0), &thresholds);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].threshold, alerts[0].dimension.as_deref()), (95.0, None));
+
+        // 同一周期内不重复提醒（重置时间的秒级抖动视为同一周期）
+        assert!(tracker
+            .evaluate(&usage(97.0, Some("2025-01-01T05:00:20Z"), 10.0), &thresholds)
+            .is_empty());
+
+        // 新周期重新计算
+        let alerts = tracker.evaluate(&usage(85.0, Some("2025-01-01T10:00:00Z"), 10.0), &thresholds);
+        assert_eq!(alerts[0].threshold, 80.0);
+
+        // 维度独立计算；没有重置时间时回落后重新提醒
+        let alerts = tracker.evaluate(&usage(0.0, None, 81.0), &thresholds);
+        assert_eq!((alerts[0].threshold, alerts[0].dimension.as_deref()), (80.0, Some("Weekly")));
+        assert!(tracker.evaluate(&usage(0.0, None, 82.0), &thresholds).is_empty());
+        assert!(tracker.evaluate(&usage(0.0, None, 40.0), &thresholds).is_empty());
+        assert_eq!(tracker.evaluate(&usage(0.0, None, 81.0), &thresholds).len(), 1);
+    }
+}
@@ -0,0 +1,101 @@
+// Phase 6.17: 失控插件看门狗
+// 每次执行都超时或内存超限的插件会一直占用沙盒运行时，连续达到阈值后自动禁用:
+// 1. 只统计沙盒执行超时（含生命周期钩子、onEvent 超时）和内存超限，网络超时等普通失败不计入
+// 2. 中间出现成功或其他类型的失败时重新计数
+// 3. 自动禁用后触发 Critical 告警，用户在插件列表中重新启用即可恢复（计数清零）
+// 4. 策略（开关、连续次数阈值）保存在应用设置的 watchdog 字段
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 默认连续失控次数阈值
+pub const DEFAULT_MAX_CONSECUTIVE_RUNAWAYS: u32 = 3;
+
+/// 连续失控次数阈值上限
+pub const MAX_CONSECUTIVE_RUNAWAYS_LIMIT: u32 = 20;
+
+/// 沙盒失控错误的消息特征（RuntimeError::ExecutionTimeout / MemoryExceeded 及钩子超时）
+const RUNAWAY_MARKERS: [&str; 2] = ["执行超时", "内存超限"];
+
+/// 看门狗策略（应用设置 watchdog 字段）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WatchdogPolicy {
+    /// 是否自动禁用失控插件
+    pub enabled: bool,
+    /// 连续多少次执行超时/内存超限后禁用
+    pub max_consecutive_runaways: u32,
+}
+
+impl Default for WatchdogPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_consecutive_runaways: DEFAULT_MAX_CONSECUTIVE_RUNAWAYS,
+        }
+    }
+}
+
+impl WatchdogPolicy {
+    /// 校验取值范围
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_CONSECUTIVE_RUNAWAYS_LIMIT).contains(&self.max_consecutive_runaways) {
+            return Err(format!(
+                "看门狗连续失控次数需在 1 到 {} 之间",
+                MAX_CONSECUTIVE_RUNAWAYS_LIMIT
+            ));
+        }
+        Ok(())
+    }
+
+    /// 连续失控次数是否达到禁用阈值
+    pub fn should_disable(&self, consecutive_runaways: u32) -> bool {
+        self.enabled && consecutive_runaways >= self.max_consecutive_runaways
+    }
+}
+
+/// 错误是否为沙盒失控（执行超时或内存超限）
+pub fn is_runaway_error(error: &str) -> bool {
+    RUNAWAY_MARKERS.iter().any(|marker| error.contains(marker))
+}
+
+/// 看门狗自动禁用记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchdogTrip {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 触发禁用时的连续失控次数
+    pub consecutive_runaways: u32,
+    /// 最后一次失控的错误
+    pub last_error: String,
+    /// 禁用时间
+    pub disabled_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchdog_policy_and_runaway_errors() {
+        assert!(is_runaway_error("执行插件失败: 执行超时: 超过 30s"));
+        assert!(is_runaway_error("执行插件失败: 内存超限: 超过 16777216 字节"));
+        assert!(is_runaway_error("onLoad 执行失败: 执行超时 (5s)"));
+        assert!(!is_runaway_error("HTTP 请求失败: operation timed out"));
+
+        let policy = WatchdogPolicy::default();
+        assert!(!policy.should_disable(2));
+        assert!(policy.should_disable(3));
+        let disabled = WatchdogPolicy { enabled: false, ..policy };
+        assert!(!disabled.should_disable(10));
+
+        assert!(policy.validate().is_ok());
+        assert!(WatchdogPolicy { max_consecutive_runaways: 0, ..policy }.validate().is_err());
+        assert!(WatchdogPolicy { max_consecutive_runaways: 21, ..policy }.validate().is_err());
+    }
+}
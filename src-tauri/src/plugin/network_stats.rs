@@ -0,0 +1,361 @@
+// Phase 6.7: 插件网络访问统计与隐私报告
+// 记录每个插件按天、按主机的请求数和传输字节数，生成"哪些数据离开了本机"报告
+//
+// 设计要点:
+// 1. 只记录主机名、计数和配置字段名，不记录 URL 路径、请求体或配置值
+// 2. 请求头中出现的配置值按字段名记录（仅匹配长度 >= 4 的字符串值，避免误报）
+// 3. 按天聚合，持久化到 network_stats.json（与插件目录同级），保留最近 30 天
+// 4. 卸载插件时删除其统计
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::monitoring::format_rfc3339;
+use crate::security::audit::AuditEvent;
+
+/// 统计保留天数（也是报告的最大天数）
+pub const NETWORK_STATS_RETENTION_DAYS: u32 = 30;
+
+/// 参与请求头匹配的配置值最小长度
+const MIN_MATCHED_VALUE_LEN: usize = 4;
+
+// ============================================================================
+// 统计数据
+// ============================================================================
+
+/// 单个主机的访问统计
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HostStats {
+    /// 请求数
+    pub requests: u64,
+    /// 失败数（未收到响应）
+    pub failures: u64,
+    /// 发送字节数（请求头 + 请求体）
+    pub bytes_sent: u64,
+    /// 接收字节数（响应体）
+    pub bytes_received: u64,
+    /// 作为请求头发送的配置字段名
+    pub header_fields: BTreeSet<String>,
+}
+
+impl HostStats {
+    fn merge(&mut self, other: &HostStats) {
+        self.requests += other.requests;
+        self.failures += other.failures;
+        self.bytes_sent += other.bytes_sent;
+        self.bytes_received += other.bytes_received;
+        self.header_fields.extend(other.header_fields.iter().cloned());
+    }
+}
+
+/// 插件 ID -> 日期 (YYYY-MM-DD) -> 主机 -> 统计
+type StatsData = BTreeMap<String, BTreeMap<NaiveDate, BTreeMap<String, HostStats>>>;
+
+/// 网络访问统计（network_stats.json）
+pub struct NetworkStats {
+    data: Mutex<StatsData>,
+    /// 持久化路径
+    path: PathBuf,
+}
+
+impl NetworkStats {
+    /// 默认文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("network_stats.json")
+    }
+
+    /// 从文件加载（文件不存在或无法解析时从空统计开始）
+    pub fn load(path: PathBuf) -> Self {
+        let data = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析网络访问统计失败，重新开始统计: {}", e);
+                StatsData::new()
+            }),
+            Err(_) => StatsData::new(),
+        };
+        Self {
+            data: Mutex::new(data),
+            path,
+        }
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    fn save(&self, data: &StatsData) {
+        let path = &self.path;
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_vec(data)?)?;
+            std::fs::rename(&tmp, path)
+        })();
+        if let Err(e) = result {
+            log::warn!("保存网络访问统计失败: {}", e);
+        }
+    }
+
+    /// 累加一次请求的统计
+    pub fn record(&self, plugin_id: &str, host: &str, request: HostStats, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        let cutoff = today - Duration::days(i64::from(NETWORK_STATS_RETENTION_DAYS));
+
+        let mut data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        for days in data.values_mut() {
+            days.retain(|day, _| *day > cutoff);
+        }
+        data.retain(|_, days| !days.is_empty());
+
+        data.entry(plugin_id.to_string())
+            .or_default()
+            .entry(today)
+            .or_default()
+            .entry(host.to_ascii_lowercase())
+            .or_default()
+            .merge(&request);
+        self.save(&data);
+    }
+
+    /// 删除插件的统计
+    pub fn remove(&self, plugin_id: &str) {
+        let mut data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        if data.remove(plugin_id).is_some() {
+            self.save(&data);
+        }
+    }
+
+    /// 汇总插件最近 `days` 天（含今天）的访问统计
+    pub fn host_reports(&self, plugin_id: &str, days: u32, now: DateTime<Utc>) -> Vec<HostReport> {
+        let since = now.date_naive() - Duration::days(i64::from(days.saturating_sub(1)));
+        let data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut hosts: BTreeMap<&str, HostReport> = BTreeMap::new();
+        for (day, day_hosts) in data.get(plugin_id).into_iter().flatten() {
+            if *day < since {
+                continue;
+            }
+            for (host, stats) in day_hosts {
+                let report = hosts.entry(host).or_insert_with(|| HostReport {
+                    host: host.clone(),
+                    stats: HostStats::default(),
+                    first_seen: *day,
+                    last_seen: *day,
+                });
+                report.stats.merge(stats);
+                report.last_seen = *day;
+            }
+        }
+
+        let mut reports: Vec<HostReport> = hosts.into_values().collect();
+        reports.sort_by(|a, b| b.stats.requests.cmp(&a.stats.requests).then(a.host.cmp(&b.host)));
+        reports
+    }
+}
+
+// ============================================================================
+// 请求记录器
+// ============================================================================
+
+/// 插件请求记录器（由 RequestManager 在每次请求完成后调用）
+#[derive(Clone)]
+pub struct NetworkRecorder {
+    plugin_id: String,
+    stats: Arc<NetworkStats>,
+    /// 配置字段名 -> 字符串值（用于识别作为请求头发送的配置）
+    config_values: Vec<(String, String)>,
+}
+
+impl NetworkRecorder {
+    pub fn new(
+        plugin_id: &str,
+        stats: Arc<NetworkStats>,
+        config: &HashMap<String, serde_json::Value>,
+    ) -> Self {
+        let config_values = config
+            .iter()
+            .filter_map(|(field, value)| {
+                let value = value.as_str()?.trim();
+                (value.len() >= MIN_MATCHED_VALUE_LEN).then(|| (field.clone(), value.to_string()))
+            })
+            .collect();
+        Self {
+            plugin_id: plugin_id.to_string(),
+            stats,
+            config_values,
+        }
+    }
+
+    /// 记录一次请求
+    ///
+    /// `received` 为响应体字节数，未收到响应时为 None
+    pub fn record(
+        &self,
+        host: &str,
+        headers: &HashMap<String, String>,
+        body: Option<&str>,
+        received: Option<usize>,
+    ) {
+        let header_bytes: usize = headers.iter().map(|(k, v)| k.len() + v.len()).sum();
+        let header_fields = self
+            .config_values
+            .iter()
+            .filter(|(_, value)| headers.values().any(|h| h.contains(value.as_str())))
+            .map(|(field, _)| field.clone())
+            .collect();
+
+        let request = HostStats {
+            requests: 1,
+            failures: u64::from(received.is_none()),
+            bytes_sent: (header_bytes + body.map_or(0, str::len)) as u64,
+            bytes_received: received.unwrap_or(0) as u64,
+            header_fields,
+        };
+        self.stats.record(&self.plugin_id, host, request, Utc::now());
+    }
+}
+
+// ============================================================================
+// 隐私报告
+// ============================================================================
+
+/// 单个主机的报告
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostReport {
+    /// 主机名
+    pub host: String,
+    #[serde(flatten)]
+    pub stats: HostStats,
+    /// 报告期内首次访问日期 (YYYY-MM-DD)
+    pub first_seen: NaiveDate,
+    /// 报告期内最后访问日期 (YYYY-MM-DD)
+    pub last_seen: NaiveDate,
+}
+
+/// 插件隐私报告：报告期内离开本机的数据
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivacyReport {
+    pub plugin_id: String,
+    /// 报告天数
+    pub days: u32,
+    /// 报告起始时间 (ISO 8601)
+    pub since: String,
+    /// 生成时间 (ISO 8601)
+    pub generated_at: String,
+    /// manifest 声明的主机白名单（None 表示未限制）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declared_hosts: Option<Vec<String>>,
+    /// 访问过的主机（按请求数降序）
+    pub hosts: Vec<HostReport>,
+    /// 总请求数
+    pub total_requests: u64,
+    /// 总发送字节数
+    pub total_bytes_sent: u64,
+    /// 总接收字节数
+    pub total_bytes_received: u64,
+    /// 作为请求头发送过的配置字段名（不含值）
+    pub header_fields: Vec<String>,
+    /// 报告期内与该插件相关的审计事件
+    pub audit_events: Vec<AuditEvent>,
+}
+
+impl PrivacyReport {
+    /// 汇总主机统计和审计事件
+    pub fn build(
+        plugin_id: &str,
+        days: u32,
+        now: DateTime<Utc>,
+        declared_hosts: Option<Vec<String>>,
+        hosts: Vec<HostReport>,
+        audit_events: Vec<AuditEvent>,
+    ) -> Self {
+        let since = now - Duration::days(i64::from(days));
+        let audit_events = audit_events
+            .into_iter()
+            .filter(|e| e.plugin_id.as_deref() == Some(plugin_id))
+            .filter(|e| {
+                DateTime::parse_from_rfc3339(&e.timestamp).is_ok_and(|t| t.with_timezone(&Utc) >= since)
+            })
+            .collect();
+
+        let header_fields: BTreeSet<&String> =
+            hosts.iter().flat_map(|h| h.stats.header_fields.iter()).collect();
+
+        Self {
+            plugin_id: plugin_id.to_string(),
+            days,
+            since: format_rfc3339(since),
+            generated_at: format_rfc3339(now),
+            declared_hosts,
+            total_requests: hosts.iter().map(|h| h.stats.requests).sum(),
+            total_bytes_sent: hosts.iter().map(|h| h.stats.bytes_sent).sum(),
+            total_bytes_received: hosts.iter().map(|h| h.stats.bytes_received).sum(),
+            header_fields: header_fields.into_iter().cloned().collect(),
+            hosts,
+            audit_events,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_record_and_report() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("network_stats.json");
+        let stats = Arc::new(NetworkStats::load(path.clone()));
+        let config = HashMap::from([
+            ("apiKey".to_string(), json!("sk-secret-123")),
+            ("region".to_string(), json!("us")),
+        ]);
+        let recorder = NetworkRecorder::new("demo", stats.clone(), &config);
+
+        let headers = HashMap::from([
+            ("Authorization".to_string(), "Bearer sk-secret-123".to_string()),
+            ("X-Region".to_string(), "us".to_string()),
+        ]);
+        recorder.record("API.example.com", &headers, Some("{}"), Some(100));
+        recorder.record("api.example.com", &HashMap::new(), None, None);
+        recorder.record("status.example.com", &HashMap::new(), None, Some(10));
+
+        // 超出保留期的统计被清理
+        let old = Utc::now() - Duration::days(40);
+        stats.record("demo", "old.example.com", HostStats { requests: 1, ..Default::default() }, old);
+        stats.record("demo", "status.example.com", HostStats { requests: 1, ..Default::default() }, Utc::now());
+
+        // 重启后从文件恢复
+        let stats = NetworkStats::load(path);
+        let hosts = stats.host_reports("demo", 7, Utc::now());
+        assert_eq!(hosts.len(), 2);
+        assert_eq!(hosts[0].host, "api.example.com");
+        assert_eq!((hosts[0].stats.requests, hosts[0].stats.failures), (2, 1));
+        assert_eq!(hosts[0].stats.bytes_received, 100);
+        // 短值 "us" 不参与匹配，避免误报
+        assert_eq!(hosts[0].stats.header_fields, BTreeSet::from(["apiKey".to_string()]));
+
+        let audit = AuditEvent::plugin(
+            crate::security::audit::AuditEventKind::IntegrityFailed,
+            "demo",
+            "tampered",
+        );
+        let report = PrivacyReport::build("demo", 7, Utc::now(), None, hosts, vec![audit]);
+        assert_eq!(report.total_requests, 4);
+        assert_eq!(report.header_fields, vec!["apiKey"]);
+        assert_eq!(report.audit_events.len(), 1);
+
+        stats.remove("demo");
+        assert!(stats.host_reports("demo", 30, Utc::now()).is_empty());
+    }
+}
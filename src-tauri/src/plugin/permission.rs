@@ -6,13 +6,20 @@
 // - 4.3.2 实现 context.call 权限检查 - 未授权调用被拒绝
 // - 4.3.3 实现调用深度限制 - 循环调用被阻止
 // - 4.3.4 实现 exposedMethods 注册 - 插件方法可被调用
+// - 4.3.5 主机级 fetch 权限 (fetch:<host-pattern>) - 每次请求检查，越权请求被拒绝并计入健康状态
+// - 4.3.6 数据读取权限 (read:<plugin_id>) - context.getData 读取其他插件的缓存数据
 
-use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
+use crate::plugin::sandbox::UrlSecurityChecker;
+
+/// 每个插件记录的越权主机数上限
+const MAX_BLOCKED_HOSTS: usize = 20;
+
 // ============================================================================
 // 权限类型定义
 // ============================================================================
@@ -27,12 +34,20 @@ pub enum Permission {
     },
     /// 网络权限
     Network,
+    /// 主机级网络权限: fetch:{host_pattern}（声明后只允许访问匹配的主机）
+    Fetch { host: String },
     /// 定时器权限
     Timer,
     /// 存储权限
     Storage,
     /// 缓存权限
     Cache,
+    /// 密钥读取权限: secrets:{name}
+    Secret { name: String },
+    /// 运行时请求用户输入权限
+    Input,
+    /// 读取其他插件缓存数据的权限: read:{plugin_id}
+    Read { plugin_id: String },
 }
 
 impl Permission {
@@ -41,10 +56,36 @@ impl Permission {
     /// 支持格式:
     /// - "call:{plugin_id}:{method}" -> Call 权限
     /// - "network" -> Network 权限
+    /// - "fetch:{host_pattern}" -> Fetch 权限（如 `fetch:api.openai.com`、`fetch:*.anthropic.com`）
     /// - "timer" -> Timer 权限
     /// - "storage" -> Storage 权限
     /// - "cache" -> Cache 权限
+    /// - "secrets:{name}" -> Secret 权限
+    /// - "input" -> Input 权限
+    /// - "read:{plugin_id}" -> Read 权限
     pub fn parse(s: &str) -> Option<Self> {
+        if let Some(name) = s.strip_prefix("secrets:") {
+            if crate::plugin::sandbox::secrets::is_valid_secret_name(name) {
+                return Some(Permission::Secret { name: name.to_string() });
+            }
+            return None;
+        }
+
+        if let Some(host) = s.strip_prefix("fetch:") {
+            let host = host.trim().trim_end_matches('.').to_lowercase();
+            if UrlSecurityChecker::is_valid_host_pattern(&host) {
+                return Some(Permission::Fetch { host });
+            }
+            return None;
+        }
+
+        if let Some(plugin_id) = s.strip_prefix("read:") {
+            if !plugin_id.is_empty() && !plugin_id.contains(':') {
+                return Some(Permission::Read { plugin_id: plugin_id.to_string() });
+            }
+            return None;
+        }
+
         if s.starts_with("call:") {
             let parts: Vec<&str> = s.splitn(3, ':').collect();
             if parts.len() == 3 && !parts[1].is_empty() && !parts[2].is_empty() {
@@ -61,6 +102,7 @@ impl Permission {
             "timer" | "settimeout" => Some(Permission::Timer),
             "storage" => Some(Permission::Storage),
             "cache" => Some(Permission::Cache),
+            "input" => Some(Permission::Input),
             _ => None,
         }
     }
@@ -72,9 +114,13 @@ impl Permission {
                 format!("call:{}:{}", target_plugin, method)
             }
             Permission::Network => "network".to_string(),
+            Permission::Fetch { host } => format!("fetch:{}", host),
             Permission::Timer => "timer".to_string(),
             Permission::Storage => "storage".to_string(),
             Permission::Cache => "cache".to_string(),
+            Permission::Secret { name } => format!("secrets:{}", name),
+            Permission::Input => "input".to_string(),
+            Permission::Read { plugin_id } => format!("read:{}", plugin_id),
         }
     }
 }
@@ -120,6 +166,18 @@ pub enum PermissionError {
         method: String,
     },
 
+    #[error("插件 {plugin_id} 未声明访问 {host} 的权限 (fetch:<host>)")]
+    FetchNotPermitted {
+        plugin_id: String,
+        host: String,
+    },
+
+    #[error("插件 {caller} 未声明读取 {target} 数据的权限 (read:{target})")]
+    ReadNotPermitted {
+        caller: String,
+        target: String,
+    },
+
     #[error("锁争用: {context}")]
     LockContention {
         context: String,
@@ -299,6 +357,16 @@ impl MethodRegistry {
         log::debug!("已取消注册插件 {} 的所有方法", plugin_id);
     }
 
+    /// 注册了方法的插件 ID
+    pub async fn registered_plugin_ids(&self) -> HashSet<String> {
+        self.methods
+            .read()
+            .await
+            .keys()
+            .map(|(pid, _)| pid.clone())
+            .collect()
+    }
+
     /// 检查方法是否已注册
     pub async fn is_registered(&self, plugin_id: &str, method_name: &str) -> bool {
         let key = (plugin_id.to_string(), method_name.to_string());
@@ -343,6 +411,8 @@ pub struct PermissionChecker {
     method_registry: Arc<MethodRegistry>,
     /// 调用深度限制
     max_call_depth: usize,
+    /// 被 fetch 权限拒绝的主机: plugin_id -> hosts（用于健康状态展示）
+    blocked_hosts: Mutex<HashMap<String, BTreeSet<String>>>,
 }
 
 impl PermissionChecker {
@@ -352,6 +422,7 @@ impl PermissionChecker {
             permissions: RwLock::new(HashMap::new()),
             method_registry,
             max_call_depth: CallStack::DEFAULT_MAX_DEPTH,
+            blocked_hosts: Mutex::new(HashMap::new()),
         }
     }
 
@@ -399,9 +470,18 @@ impl PermissionChecker {
     /// 取消注册插件的权限
     pub async fn unregister_permissions(&self, plugin_id: &str) {
         self.permissions.write().await.remove(plugin_id);
+        self.blocked_hosts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(plugin_id);
         log::debug!("已取消注册插件 {} 的权限", plugin_id);
     }
 
+    /// 注册了权限的插件 ID
+    pub async fn registered_plugin_ids(&self) -> HashSet<String> {
+        self.permissions.read().await.keys().cloned().collect()
+    }
+
     // ========================================================================
     // 权限检查 (4.3.2)
     // ========================================================================
@@ -562,6 +642,87 @@ impl PermissionChecker {
         Ok(())
     }
 
+    // ========================================================================
+    // 主机级 fetch 权限 (4.3.5)
+    // ========================================================================
+
+    /// 检查插件是否可以访问指定主机（在沙盒 fetch 的每次请求中同步调用）
+    ///
+    /// 未声明任何 `fetch:<host>` 的插件不受限制（仅 `network` 权限，由 allowedHosts 约束）；
+    /// 声明后只允许访问匹配的主机，越权请求记录到健康状态
+    pub fn check_fetch_host_sync(&self, plugin_id: &str, host: &str) -> Result<(), PermissionError> {
+        const MAX_RETRIES: u32 = 5;
+
+        let scopes = {
+            let mut result = None;
+            for _ in 0..MAX_RETRIES {
+                if let Ok(guard) = self.permissions.try_read() {
+                    result = Some(
+                        guard
+                            .get(plugin_id)
+                            .map(|perms| {
+                                perms
+                                    .iter()
+                                    .filter_map(|p| match p {
+                                        Permission::Fetch { host } => Some(host.clone()),
+                                        _ => None,
+                                    })
+                                    .collect::<Vec<_>>()
+                            })
+                            .unwrap_or_default(),
+                    );
+                    break;
+                }
+                std::thread::yield_now();
+            }
+            result.ok_or_else(|| PermissionError::LockContention {
+                context: format!("检查 {} 的 fetch 权限", plugin_id),
+            })?
+        };
+
+        if scopes.is_empty() || UrlSecurityChecker::is_host_allowed(host, &scopes) {
+            return Ok(());
+        }
+
+        let host = host.trim_end_matches('.').to_lowercase();
+        let mut blocked = self.blocked_hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let hosts = blocked.entry(plugin_id.to_string()).or_default();
+        if !hosts.contains(&host) {
+            log::warn!("[{}] 拒绝访问未声明的主机 {}（已声明: {:?}）", plugin_id, host, scopes);
+            if hosts.len() < MAX_BLOCKED_HOSTS {
+                hosts.insert(host.clone());
+            }
+        }
+        Err(PermissionError::FetchNotPermitted {
+            plugin_id: plugin_id.to_string(),
+            host,
+        })
+    }
+
+    /// 检查插件是否可以读取目标插件的缓存数据（context.getData）
+    pub async fn check_read_permission(&self, caller: &str, target: &str) -> Result<(), PermissionError> {
+        let required_permission = Permission::Read {
+            plugin_id: target.to_string(),
+        };
+        if !self.has_permission(caller, &required_permission).await {
+            return Err(PermissionError::ReadNotPermitted {
+                caller: caller.to_string(),
+                target: target.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// 被 fetch 权限拒绝过的主机
+    pub fn blocked_hosts(&self, plugin_id: &str) -> Vec<String> {
+        self.blocked_hosts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(plugin_id)
+            .map(|hosts| hosts.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
     // ========================================================================
     // 工具方法
     // ========================================================================
@@ -608,6 +769,7 @@ mod tests {
     fn test_permission_parse_network() {
         assert_eq!(Permission::parse("network"), Some(Permission::Network));
         assert_eq!(Permission::parse("fetch"), Some(Permission::Network));
+        assert_eq!(Permission::parse("input"), Some(Permission::Input));
     }
 
     #[test]
@@ -615,6 +777,15 @@ mod tests {
         assert!(Permission::parse("call:").is_none());
         assert!(Permission::parse("call:plugin:").is_none());
         assert!(Permission::parse("unknown").is_none());
+        assert!(Permission::parse("secrets:").is_none());
+        assert!(Permission::parse("secrets:a/b").is_none());
+    }
+
+    #[test]
+    fn test_permission_parse_secret() {
+        let perm = Permission::parse("secrets:api_key");
+        assert_eq!(perm, Some(Permission::Secret { name: "api_key".to_string() }));
+        assert_eq!(perm.unwrap().to_string(), "secrets:api_key");
     }
 
     #[test]
@@ -718,4 +889,50 @@ mod tests {
         let result = checker.validate_call("plugin-a", "plugin-b", "action", &stack).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_fetch_host_scopes() {
+        assert_eq!(
+            Permission::parse("fetch:API.OpenAI.com"),
+            Some(Permission::Fetch { host: "api.openai.com".to_string() })
+        );
+        assert!(Permission::parse("fetch:*").is_none());
+        assert!(Permission::parse("fetch:*.com").is_none());
+
+        let checker = PermissionChecker::new(Arc::new(MethodRegistry::new()));
+        checker
+            .register_permissions("scoped", &["fetch:api.openai.com".to_string(), "fetch:*.anthropic.com".to_string()])
+            .await;
+        checker.register_permissions("legacy", &["network".to_string()]).await;
+
+        assert!(checker.check_fetch_host_sync("scoped", "api.openai.com").is_ok());
+        assert!(checker.check_fetch_host_sync("scoped", "console.anthropic.com").is_ok());
+        assert!(matches!(
+            checker.check_fetch_host_sync("scoped", "evil.example.com"),
+            Err(PermissionError::FetchNotPermitted { .. })
+        ));
+        assert_eq!(checker.blocked_hosts("scoped"), vec!["evil.example.com".to_string()]);
+
+        // 只声明 network 的插件不受主机级限制
+        assert!(checker.check_fetch_host_sync("legacy", "evil.example.com").is_ok());
+        assert!(checker.blocked_hosts("legacy").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_permission() {
+        assert_eq!(
+            Permission::parse("read:claude-usage"),
+            Some(Permission::Read { plugin_id: "claude-usage".to_string() })
+        );
+        assert!(Permission::parse("read:").is_none());
+        assert!(Permission::parse("read:a:b").is_none());
+
+        let checker = PermissionChecker::new(Arc::new(MethodRegistry::new()));
+        checker.register_permissions("dashboard", &["read:claude-usage".to_string()]).await;
+        assert!(checker.check_read_permission("dashboard", "claude-usage").await.is_ok());
+        assert!(matches!(
+            checker.check_read_permission("dashboard", "openai-usage").await,
+            Err(PermissionError::ReadNotPermitted { .. })
+        ));
+    }
 }
@@ -0,0 +1,210 @@
+// Phase 6.12: 插件日志捕获
+// 插件沙盒中的 console 输出除写入应用日志外，按插件保存到内存环形缓冲区，供日志查看器使用:
+// - 每个插件最多保留 MAX_ENTRIES_PER_PLUGIN 条，超出时丢弃最旧的
+// - 单条消息沿用 console 的 10KB 截断
+// - 新日志同时经通道交给宿主，以 plugin:log 事件实时推送（队列满时丢弃）
+// - 不持久化；卸载插件时清除其日志
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+/// 每个插件保留的日志条数
+pub const MAX_ENTRIES_PER_PLUGIN: usize = 500;
+
+/// 实时推送通道容量
+const LIVE_CHANNEL_CAPACITY: usize = 256;
+
+/// 日志级别（按严重程度递增排序）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginLogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// 单条插件日志
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginLogEntry {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 插件内单调递增的序号（清除后继续递增）
+    pub seq: u64,
+    /// 记录时间
+    pub timestamp: DateTime<Utc>,
+    /// 日志级别
+    pub level: PluginLogLevel,
+    /// 日志内容
+    pub message: String,
+}
+
+/// 单个插件的日志缓冲区
+#[derive(Default)]
+struct PluginLogBuffer {
+    entries: VecDeque<PluginLogEntry>,
+    next_seq: u64,
+}
+
+/// 插件日志存储
+pub struct PluginLogStore {
+    buffers: Mutex<HashMap<String, PluginLogBuffer>>,
+    live_tx: mpsc::Sender<PluginLogEntry>,
+    live_rx: Mutex<Option<mpsc::Receiver<PluginLogEntry>>>,
+}
+
+impl PluginLogStore {
+    /// 创建日志存储
+    pub fn new() -> Self {
+        let (live_tx, live_rx) = mpsc::channel(LIVE_CHANNEL_CAPACITY);
+        Self {
+            buffers: Mutex::new(HashMap::new()),
+            live_tx,
+            live_rx: Mutex::new(Some(live_rx)),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, PluginLogBuffer>> {
+        self.buffers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 取出实时日志接收端（只能取出一次，由宿主推送 plugin:log 事件）
+    pub fn take_receiver(&self) -> Option<mpsc::Receiver<PluginLogEntry>> {
+        self.live_rx.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// 记录一条日志
+    pub fn record(&self, plugin_id: &str, level: PluginLogLevel, message: String) {
+        let entry = {
+            let mut buffers = self.lock();
+            let buffer = buffers.entry(plugin_id.to_string()).or_default();
+            let entry = PluginLogEntry {
+                plugin_id: plugin_id.to_string(),
+                seq: buffer.next_seq,
+                timestamp: Utc::now(),
+                level,
+                message,
+            };
+            buffer.next_seq += 1;
+            if buffer.entries.len() >= MAX_ENTRIES_PER_PLUGIN {
+                buffer.entries.pop_front();
+            }
+            buffer.entries.push_back(entry.clone());
+            entry
+        };
+        // 没有日志查看器消费时队列会满，直接丢弃
+        let _ = self.live_tx.try_send(entry);
+    }
+
+    /// 查询插件日志（按时间顺序）
+    ///
+    /// - `since`: 只返回该时间之后（不含）的日志
+    /// - `min_level`: 只返回不低于该级别的日志
+    pub fn query(
+        &self,
+        plugin_id: &str,
+        since: Option<DateTime<Utc>>,
+        min_level: Option<PluginLogLevel>,
+    ) -> Vec<PluginLogEntry> {
+        self.lock()
+            .get(plugin_id)
+            .map(|buffer| {
+                buffer
+                    .entries
+                    .iter()
+                    .filter(|e| since.map_or(true, |since| e.timestamp > since))
+                    .filter(|e| min_level.map_or(true, |level| e.level >= level))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 清除插件日志，返回清除的条数
+    pub fn clear(&self, plugin_id: &str) -> usize {
+        self.lock()
+            .get_mut(plugin_id)
+            .map(|buffer| {
+                let cleared = buffer.entries.len();
+                buffer.entries.clear();
+                cleared
+            })
+            .unwrap_or(0)
+    }
+
+    /// 移除插件的日志缓冲区（卸载插件时调用）
+    pub fn remove(&self, plugin_id: &str) {
+        self.lock().remove(plugin_id);
+    }
+}
+
+impl Default for PluginLogStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 插件日志记录器（由沙盒 console 在每次输出时调用）
+#[derive(Clone)]
+pub struct PluginLogRecorder {
+    plugin_id: String,
+    store: Arc<PluginLogStore>,
+}
+
+impl PluginLogRecorder {
+    pub fn new(plugin_id: &str, store: Arc<PluginLogStore>) -> Self {
+        Self {
+            plugin_id: plugin_id.to_string(),
+            store,
+        }
+    }
+
+    /// 插件 ID
+    pub fn plugin_id(&self) -> &str {
+        &self.plugin_id
+    }
+
+    /// 记录一条日志
+    pub fn record(&self, level: PluginLogLevel, message: String) {
+        self.store.record(&self.plugin_id, level, message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_query_and_clear() {
+        let store = Arc::new(PluginLogStore::new());
+        let mut live = store.take_receiver().unwrap();
+        assert!(store.take_receiver().is_none());
+
+        let recorder = PluginLogRecorder::new("demo", store.clone());
+        for i in 0..MAX_ENTRIES_PER_PLUGIN + 2 {
+            let level = if i % 2 == 0 { PluginLogLevel::Info } else { PluginLogLevel::Error };
+            recorder.record(level, format!("line {}", i));
+        }
+
+        // 超出容量丢弃最旧的，序号继续递增
+        let all = store.query("demo", None, None);
+        assert_eq!(all.len(), MAX_ENTRIES_PER_PLUGIN);
+        assert_eq!((all[0].seq, all[0].message.as_str()), (2, "line 2"));
+        let errors = store.query("demo", None, Some(PluginLogLevel::Warn));
+        assert!(errors.iter().all(|e| e.level == PluginLogLevel::Error));
+        assert_eq!(errors.len(), MAX_ENTRIES_PER_PLUGIN / 2);
+        assert!(store.query("demo", Some(all.last().unwrap().timestamp), None).is_empty());
+        assert!(store.query("other", None, None).is_empty());
+
+        assert_eq!(live.try_recv().unwrap().message, "line 0");
+        assert_eq!(store.clear("demo"), MAX_ENTRIES_PER_PLUGIN);
+        recorder.record(PluginLogLevel::Debug, "after".into());
+        assert_eq!(store.query("demo", None, None)[0].seq, MAX_ENTRIES_PER_PLUGIN as u64 + 2);
+    }
+}
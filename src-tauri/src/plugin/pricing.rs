@@ -0,0 +1,629 @@
+// Phase 6.14: 费用估算
+// 按模型价格表把插件上报的 token 用量换算为预估花费
+//
+// 设计要点:
+// 1. 价格表内置一份（USD / 百万 token），可从远程 JSON 更新；远程价格表保存到 pricing.json，
+//    按模型覆盖内置价格，未覆盖的模型仍使用内置价格
+// 2. 只有附带 token 明细的使用量维度参与估算，维度 ID 为模型名；
+//    模型名去掉提供商前缀和日期后缀后按最长前缀匹配价格（claude-sonnet-4-5-20250929 -> claude-sonnet-4-5）
+// 3. 插件上报的是周期累计用量，每次刷新记录与上一次的差值到按天（UTC）的账本，
+//    用量回落视为周期重置，回落后的用量全部计入；首次观测到的用量计入当天
+// 4. 账本持久化到 cost_ledger.json，保留最近 90 天；卸载插件时删除其账本
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, RwLock};
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::commands::error::codes;
+use crate::plugin::monitoring::format_rfc3339;
+use crate::plugin::types::{PluginData, TokenBreakdown};
+
+/// 默认远程价格表地址（随发布附带）
+pub const DEFAULT_PRICING_URL: &str =
+    "https://github.com/DDG0808/aibal/releases/latest/download/pricing.json";
+
+/// 内置价格表版本
+const BUNDLED_PRICING_VERSION: &str = "bundled-2025-11";
+
+/// 账本保留天数
+const LEDGER_RETENTION_DAYS: i64 = 90;
+
+/// 远程价格表请求超时
+const PRICING_FETCH_TIMEOUT: StdDuration = StdDuration::from_secs(30);
+
+/// 远程价格表最大字节数
+const MAX_PRICING_BYTES: usize = 1024 * 1024;
+
+/// 每百万 token
+const TOKENS_PER_UNIT: f64 = 1_000_000.0;
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+/// 价格表错误
+#[derive(Debug, Error)]
+pub enum PricingError {
+    #[error("获取价格表失败: {0}")]
+    Fetch(String),
+
+    #[error("价格表格式错误: {0}")]
+    Invalid(String),
+
+    #[error("保存价格表失败: {0}")]
+    Save(#[from] std::io::Error),
+}
+
+impl PricingError {
+    /// 错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            PricingError::Fetch(_) => codes::PRICING_FETCH_FAILED,
+            PricingError::Invalid(_) => codes::INVALID_PRICING_TABLE,
+            PricingError::Save(_) => codes::PRICING_SAVE_FAILED,
+        }
+    }
+}
+
+// ============================================================================
+// 价格表
+// ============================================================================
+
+/// 单个模型的价格（每百万 token）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPrice {
+    /// 输入
+    pub input: f64,
+    /// 输出
+    pub output: f64,
+    /// 写入缓存（缺省为输入价格的 1.25 倍）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+    /// 读取缓存（缺省为输入价格的 0.1 倍）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+}
+
+impl ModelPrice {
+    const fn new(input: f64, output: f64, cache_write: f64, cache_read: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_write: Some(cache_write),
+            cache_read: Some(cache_read),
+        }
+    }
+
+    /// 计算 token 用量的花费
+    pub fn cost(&self, tokens: &TokenBreakdown) -> f64 {
+        let cache_write = self.cache_write.unwrap_or(self.input * 1.25);
+        let cache_read = self.cache_read.unwrap_or(self.input * 0.1);
+        (tokens.input as f64 * self.input
+            + tokens.output as f64 * self.output
+            + tokens.cache_creation as f64 * cache_write
+            + tokens.cache_read as f64 * cache_read)
+            / TOKENS_PER_UNIT
+    }
+
+    fn is_valid(&self) -> bool {
+        [Some(self.input), Some(self.output), self.cache_write, self.cache_read]
+            .into_iter()
+            .flatten()
+            .all(|price| price.is_finite() && price >= 0.0)
+    }
+}
+
+fn default_currency() -> String {
+    "USD".to_string()
+}
+
+/// 价格表
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceTable {
+    /// 版本
+    pub version: String,
+    /// 货币（目前只支持 USD）
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// 模型名（不含日期后缀）-> 价格
+    pub models: BTreeMap<String, ModelPrice>,
+}
+
+impl PriceTable {
+    /// 内置价格表
+    pub fn bundled() -> Self {
+        let models = [
+            ("claude-opus-4-5", ModelPrice::new(5.0, 25.0, 6.25, 0.5)),
+            ("claude-opus-4-1", ModelPrice::new(15.0, 75.0, 18.75, 1.5)),
+            ("claude-opus-4", ModelPrice::new(15.0, 75.0, 18.75, 1.5)),
+            ("claude-sonnet-4-5", ModelPrice::new(3.0, 15.0, 3.75, 0.3)),
+            ("claude-sonnet-4", ModelPrice::new(3.0, 15.0, 3.75, 0.3)),
+            ("claude-3-7-sonnet", ModelPrice::new(3.0, 15.0, 3.75, 0.3)),
+            ("claude-3-5-sonnet", ModelPrice::new(3.0, 15.0, 3.75, 0.3)),
+            ("claude-haiku-4-5", ModelPrice::new(1.0, 5.0, 1.25, 0.1)),
+            ("claude-3-5-haiku", ModelPrice::new(0.8, 4.0, 1.0, 0.08)),
+            ("claude-3-opus", ModelPrice::new(15.0, 75.0, 18.75, 1.5)),
+            ("claude-3-haiku", ModelPrice::new(0.25, 1.25, 0.3, 0.03)),
+            ("gpt-4o", ModelPrice::new(2.5, 10.0, 2.5, 1.25)),
+            ("gpt-4o-mini", ModelPrice::new(0.15, 0.6, 0.15, 0.075)),
+            ("gpt-4.1", ModelPrice::new(2.0, 8.0, 2.0, 0.5)),
+            ("gpt-4.1-mini", ModelPrice::new(0.4, 1.6, 0.4, 0.1)),
+        ];
+        Self {
+            version: BUNDLED_PRICING_VERSION.to_string(),
+            currency: default_currency(),
+            models: models.into_iter().map(|(model, price)| (model.to_string(), price)).collect(),
+        }
+    }
+
+    /// 校验远程价格表
+    pub fn validate(&self) -> Result<(), PricingError> {
+        if self.version.trim().is_empty() {
+            return Err(PricingError::Invalid("缺少 version".to_string()));
+        }
+        if self.currency != "USD" {
+            return Err(PricingError::Invalid(format!("不支持的货币: {}", self.currency)));
+        }
+        if self.models.is_empty() {
+            return Err(PricingError::Invalid("models 为空".to_string()));
+        }
+        if let Some((model, _)) = self.models.iter().find(|(_, price)| !price.is_valid()) {
+            return Err(PricingError::Invalid(format!("模型 {} 的价格无效", model)));
+        }
+        Ok(())
+    }
+
+    /// 以远程价格表覆盖（按模型覆盖，版本取远程）
+    fn overlay(mut self, remote: &PriceTable) -> Self {
+        self.version = remote.version.clone();
+        for (model, price) in &remote.models {
+            self.models.insert(normalize_model(model), *price);
+        }
+        self
+    }
+
+    /// 查找模型价格，返回 (价格表中的模型名, 价格)
+    pub fn lookup(&self, model: &str) -> Option<(&str, &ModelPrice)> {
+        let name = normalize_model(model);
+        self.models
+            .iter()
+            .filter(|(key, _)| {
+                name == **key || name.strip_prefix(key.as_str()).is_some_and(|rest| rest.starts_with('-'))
+            })
+            .max_by_key(|(key, _)| key.len())
+            .map(|(key, price)| (key.as_str(), price))
+    }
+}
+
+/// 规范化模型名：小写，去掉提供商前缀（anthropic/、@ 版本）和日期后缀
+fn normalize_model(model: &str) -> String {
+    let name = model.trim().to_ascii_lowercase();
+    let name = name.rsplit('/').next().unwrap_or(&name);
+    let name = name.split('@').next().unwrap_or(name);
+    match name.rsplit_once('-') {
+        Some((base, date)) if date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()) => base.to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// 从远程地址获取价格表
+pub async fn fetch_price_table(url: &str) -> Result<PriceTable, PricingError> {
+    if !url.starts_with("https://") {
+        return Err(PricingError::Fetch("价格表地址必须使用 HTTPS".to_string()));
+    }
+    let client = reqwest::Client::builder()
+        .timeout(PRICING_FETCH_TIMEOUT)
+        .user_agent(concat!("AiBal/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| PricingError::Fetch(e.to_string()))?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| PricingError::Fetch(e.to_string()))?;
+    let body = response.bytes().await.map_err(|e| PricingError::Fetch(e.to_string()))?;
+    if body.len() > MAX_PRICING_BYTES {
+        return Err(PricingError::Invalid(format!("价格表超过 {} 字节", MAX_PRICING_BYTES)));
+    }
+    let table: PriceTable = serde_json::from_slice(&body).map_err(|e| PricingError::Invalid(e.to_string()))?;
+    table.validate()?;
+    Ok(table)
+}
+
+// ============================================================================
+// 费用估算结果
+// ============================================================================
+
+/// 估算区间（含今天，按 UTC 日期）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostRange {
+    /// 今天
+    Day,
+    /// 最近 7 天
+    Week,
+    /// 最近 30 天
+    #[default]
+    Month,
+}
+
+impl CostRange {
+    fn days(self) -> i64 {
+        match self {
+            CostRange::Day => 1,
+            CostRange::Week => 7,
+            CostRange::Month => 30,
+        }
+    }
+
+    /// 区间首日
+    fn start(self, now: DateTime<Utc>) -> NaiveDate {
+        now.date_naive() - Duration::days(self.days() - 1)
+    }
+}
+
+/// 单个模型的花费
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCost {
+    /// 模型名（插件上报）
+    pub model: String,
+    /// 匹配到的价格表模型名（无价格时为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priced_as: Option<String>,
+    /// 区间内 token 用量
+    pub tokens: TokenBreakdown,
+    /// 预估花费（无价格时为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+}
+
+/// 插件费用估算
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostEstimate {
+    pub plugin_id: String,
+    pub range: CostRange,
+    /// 区间起始时间 (ISO 8601)
+    pub start: String,
+    /// 区间结束时间 (ISO 8601)
+    pub end: String,
+    pub currency: String,
+    /// 预估总花费（不含无价格的模型）
+    pub total: f64,
+    /// 各模型花费（按花费降序）
+    pub models: Vec<ModelCost>,
+    /// 无价格模型的 token 数
+    pub unpriced_tokens: u64,
+    /// 使用的价格表版本
+    pub pricing_version: String,
+}
+
+/// 摘要中的预估花费
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimatedSpend {
+    pub range: CostRange,
+    pub currency: String,
+    /// 所有插件的预估花费之和
+    pub total: f64,
+}
+
+// ============================================================================
+// token 账本
+// ============================================================================
+
+/// 单个插件的 token 账本
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct PluginLedger {
+    /// 上次观测到的各模型周期累计用量
+    last: BTreeMap<String, TokenBreakdown>,
+    /// 日期 (YYYY-MM-DD, UTC) -> 模型 -> 当天用量
+    days: BTreeMap<NaiveDate, BTreeMap<String, TokenBreakdown>>,
+}
+
+/// 插件 ID -> 账本
+type LedgerData = BTreeMap<String, PluginLedger>;
+
+/// 与上次观测相比新增的用量（任一项回落视为周期重置，回落后的用量全部计入）
+fn token_delta(previous: Option<&TokenBreakdown>, current: &TokenBreakdown) -> TokenBreakdown {
+    let Some(previous) = previous else {
+        return *current;
+    };
+    let reset = current.input < previous.input
+        || current.output < previous.output
+        || current.cache_creation < previous.cache_creation
+        || current.cache_read < previous.cache_read;
+    if reset {
+        return *current;
+    }
+    TokenBreakdown {
+        input: current.input - previous.input,
+        output: current.output - previous.output,
+        cache_creation: current.cache_creation - previous.cache_creation,
+        cache_read: current.cache_read - previous.cache_read,
+    }
+}
+
+// ============================================================================
+// 费用估算器
+// ============================================================================
+
+/// 费用估算器（价格表 + token 账本）
+pub struct CostEstimator {
+    table: RwLock<PriceTable>,
+    /// 远程价格表保存路径
+    table_path: PathBuf,
+    ledger: Mutex<LedgerData>,
+    ledger_path: PathBuf,
+}
+
+impl CostEstimator {
+    /// 加载价格表和账本（文件与插件目录同级）
+    pub fn load(plugins_dir: &Path) -> Self {
+        let base = plugins_dir.parent().unwrap_or(plugins_dir);
+        let table_path = base.join("pricing.json");
+        let ledger_path = base.join("cost_ledger.json");
+
+        let remote = std::fs::read(&table_path)
+            .ok()
+            .and_then(|content| serde_json::from_slice::<PriceTable>(&content).ok())
+            .filter(|table| table.validate().is_ok());
+        let table = match remote {
+            Some(remote) => PriceTable::bundled().overlay(&remote),
+            None => PriceTable::bundled(),
+        };
+        let ledger = std::fs::read(&ledger_path)
+            .ok()
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            table: RwLock::new(table),
+            table_path,
+            ledger: Mutex::new(ledger),
+            ledger_path,
+        }
+    }
+
+    fn lock_ledger(&self) -> MutexGuard<'_, LedgerData> {
+        self.ledger.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 当前生效的价格表
+    pub fn price_table(&self) -> PriceTable {
+        self.table.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 应用远程价格表（保存后生效），返回合并后的价格表
+    pub fn update_price_table(&self, remote: PriceTable) -> Result<PriceTable, PricingError> {
+        remote.validate()?;
+        write_json(&self.table_path, &remote)?;
+        let table = PriceTable::bundled().overlay(&remote);
+        *self.table.write().unwrap_or_else(|e| e.into_inner()) = table.clone();
+        Ok(table)
+    }
+
+    /// 记录插件数据中带 token 明细的维度
+    pub fn record(&self, data: &PluginData, now: DateTime<Utc>) {
+        let PluginData::Usage(usage) = data else {
+            return;
+        };
+        let models: Vec<(&String, &TokenBreakdown)> = usage
+            .dimensions
+            .iter()
+            .flatten()
+            .filter_map(|d| d.tokens.as_ref().map(|tokens| (&d.id, tokens)))
+            .collect();
+        if models.is_empty() {
+            return;
+        }
+
+        let today = now.date_naive();
+        let cutoff = today - Duration::days(LEDGER_RETENTION_DAYS);
+        let mut ledger = self.lock_ledger();
+        for plugin in ledger.values_mut() {
+            plugin.days.retain(|day, _| *day > cutoff);
+        }
+
+        let plugin = ledger.entry(usage.base.plugin_id.clone()).or_default();
+        for (model, tokens) in models {
+            let delta = token_delta(plugin.last.get(model), tokens);
+            plugin.last.insert(model.clone(), *tokens);
+            if delta.total() > 0 {
+                plugin
+                    .days
+                    .entry(today)
+                    .or_default()
+                    .entry(model.clone())
+                    .or_default()
+                    .add(&delta);
+            }
+        }
+
+        if let Err(e) = write_json(&self.ledger_path, &*ledger) {
+            log::warn!("保存费用账本失败: {}", e);
+        }
+    }
+
+    /// 删除插件的账本
+    pub fn remove(&self, plugin_id: &str) {
+        let mut ledger = self.lock_ledger();
+        if ledger.remove(plugin_id).is_some() {
+            if let Err(e) = write_json(&self.ledger_path, &*ledger) {
+                log::warn!("保存费用账本失败: {}", e);
+            }
+        }
+    }
+
+    /// 估算插件在区间内的花费
+    pub fn estimate(&self, plugin_id: &str, range: CostRange, now: DateTime<Utc>) -> CostEstimate {
+        let start = range.start(now);
+        let mut tokens: BTreeMap<String, TokenBreakdown> = BTreeMap::new();
+        if let Some(plugin) = self.lock_ledger().get(plugin_id) {
+            for (_, models) in plugin.days.range(start..) {
+                for (model, counts) in models {
+                    tokens.entry(model.clone()).or_default().add(counts);
+                }
+            }
+        }
+
+        let table = self.price_table();
+        let mut models: Vec<ModelCost> = tokens
+            .into_iter()
+            .map(|(model, tokens)| {
+                let price = table.lookup(&model);
+                ModelCost {
+                    priced_as: price.map(|(key, _)| key.to_string()),
+                    cost: price.map(|(_, price)| price.cost(&tokens)),
+                    model,
+                    tokens,
+                }
+            })
+            .collect();
+        models.sort_by(|a, b| b.cost.unwrap_or(-1.0).total_cmp(&a.cost.unwrap_or(-1.0)));
+
+        let start_time = start.and_hms_opt(0, 0, 0).map_or(now, |t| t.and_utc());
+        CostEstimate {
+            plugin_id: plugin_id.to_string(),
+            range,
+            start: format_rfc3339(start_time),
+            end: format_rfc3339(now),
+            currency: table.currency.clone(),
+            total: models.iter().filter_map(|m| m.cost).sum(),
+            unpriced_tokens: models.iter().filter(|m| m.cost.is_none()).map(|m| m.tokens.total()).sum(),
+            models,
+            pricing_version: table.version,
+        }
+    }
+
+    /// 多个插件在区间内的预估花费之和（都没有 token 记录时为 None）
+    pub fn total_spend<'a>(
+        &self,
+        plugin_ids: impl IntoIterator<Item = &'a str>,
+        range: CostRange,
+        now: DateTime<Utc>,
+    ) -> Option<EstimatedSpend> {
+        let ids: Vec<&str> = {
+            let ledger = self.lock_ledger();
+            plugin_ids.into_iter().filter(|id| ledger.contains_key(*id)).collect()
+        };
+        if ids.is_empty() {
+            return None;
+        }
+        let estimates: Vec<CostEstimate> = ids.into_iter().map(|id| self.estimate(id, range, now)).collect();
+        Some(EstimatedSpend {
+            range,
+            currency: estimates[0].currency.clone(),
+            total: estimates.iter().map(|e| e.total).sum(),
+        })
+    }
+}
+
+/// 保存 JSON 文件（临时文件 + rename）
+fn write_json<T: Serialize>(path: &Path, value: &T) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(value)?)?;
+    std::fs::rename(&tmp, path)
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn usage(tokens: serde_json::Value) -> PluginData {
+        serde_json::from_value(json!({
+            "dataType": "usage", "pluginId": "local", "lastUpdated": "2025-06-02T00:00:00Z",
+            "percentage": 0.0, "used": 0.0, "limit": 0.0, "unit": "tokens",
+            "dimensions": [{
+                "id": "claude-sonnet-4-5-20250929", "label": "sonnet-4-5", "percentage": 100.0,
+                "used": 0.0, "limit": 0.0, "tokens": tokens
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_price_lookup_and_remote_overlay() {
+        let table = PriceTable::bundled();
+        assert_eq!(table.lookup("claude-opus-4-5-20251101").unwrap().0, "claude-opus-4-5");
+        assert_eq!(table.lookup("anthropic/claude-opus-4-20250514").unwrap().0, "claude-opus-4");
+        assert_eq!(table.lookup("claude-sonnet-4@20250514").unwrap().0, "claude-sonnet-4");
+        assert_eq!(table.lookup("gpt-4o-mini-2024-07-18").unwrap().0, "gpt-4o-mini");
+        assert!(table.lookup("claude-opusx").is_none());
+
+        let tokens = TokenBreakdown { input: 1_000_000, output: 100_000, cache_creation: 0, cache_read: 1_000_000 };
+        let (_, price) = table.lookup("claude-sonnet-4").unwrap();
+        assert!((price.cost(&tokens) - 4.8).abs() < 1e-9);
+
+        let dir = tempfile::tempdir().unwrap();
+        let estimator = CostEstimator::load(&dir.path().join("plugins"));
+        let remote: PriceTable = serde_json::from_value(json!({
+            "version": "2025-12-01",
+            "models": { "claude-sonnet-4-20250514": { "input": 2.0, "output": 10.0 } }
+        }))
+        .unwrap();
+        let merged = estimator.update_price_table(remote).unwrap();
+        assert_eq!(merged.version, "2025-12-01");
+        assert_eq!(merged.lookup("claude-sonnet-4").unwrap().1.input, 2.0);
+        assert_eq!(merged.lookup("claude-opus-4").unwrap().1.input, 15.0);
+
+        // 重新加载后仍使用远程价格
+        let reloaded = CostEstimator::load(&dir.path().join("plugins"));
+        assert_eq!(reloaded.price_table(), merged);
+
+        let invalid: PriceTable =
+            serde_json::from_value(json!({ "version": "x", "models": { "m": { "input": -1.0, "output": 1.0 } } })).unwrap();
+        assert!(estimator.update_price_table(invalid).is_err());
+    }
+
+    #[test]
+    fn test_cost_ledger_records_deltas_per_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let estimator = CostEstimator::load(&dir.path().join("plugins"));
+
+        estimator.record(&usage(json!({ "input": 1_000_000, "output": 0 })), at("2025-06-01T10:00:00Z"));
+        estimator.record(&usage(json!({ "input": 1_500_000, "output": 100_000 })), at("2025-06-02T10:00:00Z"));
+        // 周期重置：回落后的用量全部计入
+        estimator.record(&usage(json!({ "input": 200_000, "output": 0 })), at("2025-06-02T12:00:00Z"));
+
+        let day = estimator.estimate("local", CostRange::Day, at("2025-06-02T23:00:00Z"));
+        assert_eq!(day.models.len(), 1);
+        assert_eq!(day.models[0].priced_as.as_deref(), Some("claude-sonnet-4-5"));
+        assert_eq!(day.models[0].tokens.input, 700_000);
+        assert!((day.total - (0.7 * 3.0 + 0.1 * 15.0)).abs() < 1e-9);
+
+        let week = estimator.estimate("local", CostRange::Week, at("2025-06-02T23:00:00Z"));
+        assert!((week.total - (1.7 * 3.0 + 0.1 * 15.0)).abs() < 1e-9);
+
+        // 账本持久化，重启后不重复计入
+        let reloaded = CostEstimator::load(&dir.path().join("plugins"));
+        reloaded.record(&usage(json!({ "input": 200_000, "output": 0 })), at("2025-06-02T13:00:00Z"));
+        let spend = reloaded.total_spend(["local", "other"], CostRange::Week, at("2025-06-02T23:00:00Z")).unwrap();
+        assert!((spend.total - week.total).abs() < 1e-9);
+
+        reloaded.remove("local");
+        assert!(reloaded.total_spend(["local"], CostRange::Month, at("2025-06-02T23:00:00Z")).is_none());
+    }
+}
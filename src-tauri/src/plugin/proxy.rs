@@ -0,0 +1,187 @@
+// Phase 4.5: 插件请求代理
+// 企业网络通常只能经 HTTP 代理访问外网。RequestManager 默认禁用代理（防止通过
+// HTTP_PROXY 环境变量绕过 SSRF 检查），这里提供由用户在应用设置中显式开启的代理:
+// - 主机/端口/用户名保存在 app_settings.json 的 proxy 字段，密码保存在 Keychain
+//   （服务名 PROXY_KEYCHAIN_SERVICE，账户为用户名）
+// - 代理地址本身按私网规则校验（不允许 localhost、私有 IP、内部域名），请求时再检查解析结果
+// - 经代理的请求由代理解析目标主机，本地 DNS 重绑定检查不再适用（见 DNS_WARNING）
+
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::sandbox::fetch::{FetchError, UrlSecurityChecker};
+use crate::plugin::sandbox::secrets::SecretStore;
+
+/// 代理密码的 Keychain 服务名
+pub const PROXY_KEYCHAIN_SERVICE: &str = "cuk.proxy";
+
+/// 启用代理时的提示
+pub const DNS_WARNING: &str = "已启用代理：插件请求的目标主机由代理解析，本地 DNS 私网检查改为只校验代理地址";
+
+// ============================================================================
+// 代理设置
+// ============================================================================
+
+/// 代理设置（应用设置 proxy 字段）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProxySettings {
+    /// 是否对插件请求使用代理
+    pub enabled: bool,
+    /// 代理主机
+    pub host: String,
+    /// 代理端口
+    pub port: u16,
+    /// 代理用户名（密码保存在 Keychain）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+}
+
+impl ProxySettings {
+    /// 代理地址（http://host:port）
+    fn url(&self) -> String {
+        let host = self.host.trim();
+        if host.contains(':') && !host.starts_with('[') {
+            format!("http://[{}]:{}", host, self.port)
+        } else {
+            format!("http://{}:{}", host, self.port)
+        }
+    }
+
+    /// 校验设置（启用时代理地址需通过 SSRF 检查）
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.host.trim().is_empty() || self.port == 0 {
+            return Err("代理需要填写主机和端口".to_string());
+        }
+        if self.username.as_deref().is_some_and(|u| u.trim().is_empty()) {
+            return Err("代理用户名不能为空".to_string());
+        }
+        let url = UrlSecurityChecker::check_url(&self.url()).map_err(|e| format!("代理地址不可用: {}", e))?;
+        if url.path() != "/" || url.query().is_some() || !url.username().is_empty() {
+            return Err("代理主机格式无效".to_string());
+        }
+        Ok(())
+    }
+
+    /// 生成请求使用的代理配置（未启用或无效时返回 None，密码从 Keychain 读取）
+    pub fn resolve(&self, store: &dyn SecretStore) -> Option<ProxyConfig> {
+        if !self.enabled {
+            return None;
+        }
+        if let Err(e) = self.validate() {
+            log::warn!("代理设置无效，插件请求不使用代理: {}", e);
+            return None;
+        }
+        let credentials = self.username.as_ref().map(|username| {
+            let password = store
+                .get(PROXY_KEYCHAIN_SERVICE, username)
+                .unwrap_or_else(|e| {
+                    log::warn!("读取代理密码失败: {}", e);
+                    None
+                })
+                .unwrap_or_default();
+            (username.clone(), password)
+        });
+        Some(ProxyConfig {
+            url: self.url(),
+            credentials,
+        })
+    }
+}
+
+// ============================================================================
+// 代理配置
+// ============================================================================
+
+/// 插件请求使用的代理
+#[derive(Clone, PartialEq, Eq)]
+pub struct ProxyConfig {
+    /// 代理地址
+    url: String,
+    /// (用户名, 密码)
+    credentials: Option<(String, String)>,
+}
+
+impl std::fmt::Debug for ProxyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxyConfig")
+            .field("url", &self.url)
+            .field("username", &self.credentials.as_ref().map(|(u, _)| u))
+            .finish()
+    }
+}
+
+impl ProxyConfig {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 请求前检查代理主机的解析结果（防止代理域名解析到内网）
+    pub async fn check_resolved(&self) -> Result<(), FetchError> {
+        let url = UrlSecurityChecker::check_url(&self.url)?;
+        UrlSecurityChecker::check_resolved_ip(&url).await?;
+        Ok(())
+    }
+
+    /// 转换为 reqwest 代理（所有协议均经代理）
+    pub fn to_reqwest(&self) -> Result<reqwest::Proxy, FetchError> {
+        let proxy = reqwest::Proxy::all(&self.url)
+            .map_err(|e| FetchError::NetworkError(format!("Invalid proxy: {}", e)))?;
+        Ok(match &self.credentials {
+            Some((username, password)) => proxy.basic_auth(username, password),
+            None => proxy,
+        })
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct OneSecret;
+
+    impl SecretStore for OneSecret {
+        fn get(&self, service: &str, account: &str) -> Result<Option<String>, String> {
+            Ok((service == PROXY_KEYCHAIN_SERVICE && account == "alice").then(|| "s3cret".to_string()))
+        }
+
+        fn set(&self, _service: &str, _account: &str, _value: &str) -> Result<(), String> {
+            Err("read-only".to_string())
+        }
+    }
+
+    #[test]
+    fn test_proxy_settings_validate_and_resolve() {
+        let settings = ProxySettings {
+            enabled: true,
+            host: "proxy.example.com".to_string(),
+            port: 3128,
+            username: Some("alice".to_string()),
+        };
+        assert!(settings.validate().is_ok());
+        let config = settings.resolve(&OneSecret).unwrap();
+        assert_eq!(config.url(), "http://proxy.example.com:3128");
+        assert_eq!(config.credentials, Some(("alice".to_string(), "s3cret".to_string())));
+        assert!(!format!("{:?}", config).contains("s3cret"));
+        assert!(config.to_reqwest().is_ok());
+
+        // 代理地址同样受私网规则限制
+        for host in ["127.0.0.1", "localhost", "10.0.0.8", "proxy.internal", "proxy.example.com/path"] {
+            let invalid = ProxySettings { host: host.to_string(), ..settings.clone() };
+            assert!(invalid.validate().is_err(), "{}", host);
+            assert!(invalid.resolve(&OneSecret).is_none());
+        }
+        assert!(ProxySettings { port: 0, ..settings.clone() }.validate().is_err());
+
+        // 未启用时不校验、不使用
+        let disabled = ProxySettings { enabled: false, host: "127.0.0.1".to_string(), ..settings };
+        assert!(disabled.validate().is_ok());
+        assert!(disabled.resolve(&OneSecret).is_none());
+    }
+}
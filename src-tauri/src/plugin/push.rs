@@ -0,0 +1,238 @@
+// Phase 4.6: 外部数据推送
+// 插件在 manifest 中声明 `acceptsPush` 后，可由 CI 等外部系统经本地端点推送数据
+//
+// 设计要点:
+// 1. 端点默认关闭，开启后仅监听 127.0.0.1: `POST /v1/push/{plugin_id}`
+// 2. 每个插件独立的 Bearer token，只保存 SHA-256 哈希（明文仅在签发时返回一次）
+// 3. 推送内容与 fetchData 返回值格式相同，经相同的解析和 payloadSchema 校验后写入缓存
+// 4. 设置持久化到 push.json（与插件目录同级）
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::security::integrity::calculate_sha256_bytes;
+
+/// 默认监听端口
+pub const DEFAULT_PUSH_PORT: u16 = 47821;
+
+/// 推送路径前缀
+pub const PUSH_PATH_PREFIX: &str = "/v1/push/";
+
+/// 请求头最大长度
+pub const MAX_HEADER_SIZE: usize = 8 * 1024;
+
+/// 请求体最大长度
+pub const MAX_BODY_SIZE: usize = 256 * 1024;
+
+// ============================================================================
+// 推送设置
+// ============================================================================
+
+/// 推送端点设置（push.json）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PushSettings {
+    /// 是否启用本地推送端点
+    pub enabled: bool,
+    /// 监听端口（仅 127.0.0.1）
+    pub port: u16,
+    /// 插件 ID -> token 的 SHA-256 哈希
+    pub tokens: BTreeMap<String, String>,
+}
+
+impl Default for PushSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PUSH_PORT,
+            tokens: BTreeMap::new(),
+        }
+    }
+}
+
+impl PushSettings {
+    /// 默认配置文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("push.json")
+    }
+
+    /// 从文件加载（文件不存在或无法解析时使用默认设置）
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析推送设置失败，使用默认设置: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// 为插件签发新 token（覆盖旧 token），返回明文
+    pub fn issue_token(&mut self, plugin_id: &str) -> String {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        self.tokens
+            .insert(plugin_id.to_string(), calculate_sha256_bytes(token.as_bytes()));
+        token
+    }
+
+    /// 吊销插件的 token
+    pub fn revoke_token(&mut self, plugin_id: &str) -> bool {
+        self.tokens.remove(plugin_id).is_some()
+    }
+
+    /// 校验插件 token
+    pub fn verify_token(&self, plugin_id: &str, token: &str) -> bool {
+        self.tokens
+            .get(plugin_id)
+            .is_some_and(|expected| {
+                constant_time_eq(
+                    expected.as_bytes(),
+                    calculate_sha256_bytes(token.as_bytes()).as_bytes(),
+                )
+            })
+    }
+}
+
+/// 常量时间比较，避免通过响应时间猜测 token
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// ============================================================================
+// 请求解析
+// ============================================================================
+
+/// 推送请求（已解析请求头）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushRequest {
+    /// 目标插件 ID
+    pub plugin_id: String,
+    /// Bearer token
+    pub token: Option<String>,
+    /// 请求体长度
+    pub content_length: usize,
+}
+
+/// 请求解析错误（HTTP 状态码 + 描述）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushRejection {
+    pub status: u16,
+    pub message: String,
+}
+
+impl PushRejection {
+    pub fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// 解析推送请求头（不含结尾空行）
+pub fn parse_push_request(head: &str) -> Result<PushRequest, PushRejection> {
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (method, target),
+        _ => return Err(PushRejection::new(400, "无效的请求行")),
+    };
+
+    let path = target.split('?').next().unwrap_or(target);
+    let plugin_id = path
+        .strip_prefix(PUSH_PATH_PREFIX)
+        .filter(|id| !id.is_empty() && !id.contains('/'))
+        .ok_or_else(|| PushRejection::new(404, "未知路径"))?;
+    if method != "POST" {
+        return Err(PushRejection::new(405, "仅支持 POST"));
+    }
+
+    let mut token = None;
+    let mut content_length = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            token = value
+                .strip_prefix("Bearer ")
+                .map(|t| t.trim().to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = Some(
+                value
+                    .parse::<usize>()
+                    .map_err(|_| PushRejection::new(400, "无效的 Content-Length"))?,
+            );
+        }
+    }
+
+    let content_length =
+        content_length.ok_or_else(|| PushRejection::new(411, "缺少 Content-Length"))?;
+    if content_length > MAX_BODY_SIZE {
+        return Err(PushRejection::new(413, "请求体过大"));
+    }
+
+    Ok(PushRequest {
+        plugin_id: plugin_id.to_string(),
+        token,
+        content_length,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_tokens() {
+        let mut settings = PushSettings::default();
+        let token = settings.issue_token("ci-usage");
+        assert_eq!(token.len(), 64);
+        assert!(settings.verify_token("ci-usage", &token));
+        assert!(!settings.verify_token("ci-usage", "wrong"));
+        assert!(!settings.verify_token("other", &token));
+        // 只保存哈希
+        assert_ne!(settings.tokens["ci-usage"], token);
+
+        assert!(settings.revoke_token("ci-usage"));
+        assert!(!settings.verify_token("ci-usage", &token));
+    }
+
+    #[test]
+    fn test_parse_push_request() {
+        let request = parse_push_request(
+            "POST /v1/push/ci-usage HTTP/1.1\r\nHost: 127.0.0.1\r\nauthorization: Bearer abc\r\nContent-Length: 12",
+        )
+        .unwrap();
+        assert_eq!(request.plugin_id, "ci-usage");
+        assert_eq!(request.token.as_deref(), Some("abc"));
+        assert_eq!(request.content_length, 12);
+
+        let status = |head: &str| parse_push_request(head).unwrap_err().status;
+        assert_eq!(status("GET /v1/push/ci-usage HTTP/1.1"), 405);
+        assert_eq!(status("POST /v1/other HTTP/1.1\r\nContent-Length: 1"), 404);
+        assert_eq!(status("POST /v1/push/a/b HTTP/1.1\r\nContent-Length: 1"), 404);
+        assert_eq!(status("POST /v1/push/ci-usage HTTP/1.1"), 411);
+        assert_eq!(status("POST /v1/push/ci-usage HTTP/1.1\r\nContent-Length: 9999999"), 413);
+    }
+}
@@ -0,0 +1,455 @@
+// Phase 5A.6: 插件市场 Registry 客户端
+// 从可配置 URL 获取签名的插件索引，提供搜索、manifest 查询和安装来源解析
+//
+// 索引格式 (registry.json):
+// {
+//   "version": "...", "lastUpdated": "...", "featured": [...],
+//   "plugins": [{ "id", "name", "version", "downloadUrl", "manifestUrl"?, ... }],
+//   "signature": "ed25519:{key_id}:{base64}"
+// }
+//
+// 签名覆盖去除 signature 字段后的规范化 JSON（与 manifest 签名规则一致），
+// 未签名或签名无效的索引一律拒绝。
+//
+// 所有远程请求（索引、manifest、市场资源）只允许 HTTPS，并拒绝解析到内网地址的主机。
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::commands::error::codes;
+use crate::plugin::sandbox::fetch::UrlSecurityChecker;
+use crate::plugin::types::AppError;
+use crate::security::signature::SignatureVerifier;
+
+/// 默认 registry 索引地址
+pub const DEFAULT_REGISTRY_URL: &str =
+    "https://raw.githubusercontent.com/DDG0808/aibal-plugins/main/registry.json";
+
+/// 索引在内存中的有效期
+const INDEX_TTL: Duration = Duration::from_secs(600);
+
+/// 索引最大大小 (4MB)
+const MAX_INDEX_SIZE: usize = 4 * 1024 * 1024;
+
+/// manifest 最大大小 (256KB)
+const MAX_MANIFEST_SIZE: usize = 256 * 1024;
+
+/// 远程请求超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 最多跟随的重定向次数
+const MAX_REDIRECTS: usize = 5;
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("获取 registry 失败: {0}")]
+    Fetch(String),
+
+    #[error("registry 索引格式无效: {0}")]
+    InvalidIndex(String),
+
+    #[error("registry 签名验证失败: {0}")]
+    SignatureInvalid(String),
+
+    #[error("在 registry 中未找到插件: {0}")]
+    PluginNotFound(String),
+
+    #[error("获取 manifest 失败: {0}")]
+    Manifest(String),
+}
+
+impl From<RegistryError> for AppError {
+    fn from(e: RegistryError) -> Self {
+        let code = match &e {
+            RegistryError::Fetch(_) => codes::REGISTRY_FETCH_FAILED,
+            RegistryError::InvalidIndex(_) => codes::REGISTRY_INVALID,
+            RegistryError::SignatureInvalid(_) => codes::REGISTRY_SIGNATURE_INVALID,
+            RegistryError::PluginNotFound(_) => codes::PLUGIN_NOT_FOUND,
+            RegistryError::Manifest(_) => codes::MANIFEST_FETCH_FAILED,
+        };
+        AppError::new(code, e.to_string())
+    }
+}
+
+// ============================================================================
+// 索引类型
+// ============================================================================
+
+/// registry 中的插件条目
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    pub version: String,
+    #[serde(default)]
+    pub downloads: u64,
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// 截图地址（插件市场详情页展示）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub screenshots: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    /// manifest.json 地址（缺省时由 downloadUrl 目录推导）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_url: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// 更新说明
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_notes: Option<String>,
+    /// 插件包文件哈希
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// manifest.json 签名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl RegistryEntry {
+    /// 是否匹配搜索关键字（id/名称/描述/作者/标签，不区分大小写）
+    fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        [&self.id, &self.name, &self.description, &self.author]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&query))
+            || self.tags.iter().any(|tag| tag.to_lowercase().contains(&query))
+    }
+
+    /// manifest.json 地址
+    fn manifest_location(&self) -> Option<String> {
+        if let Some(url) = &self.manifest_url {
+            return Some(url.clone());
+        }
+        // ZIP 包无法直接读取 manifest，目录形式的 downloadUrl 可推导
+        let download = self.download_url.as_deref()?;
+        if download.ends_with(".zip") {
+            return None;
+        }
+        Some(format!("{}/manifest.json", download.trim_end_matches('/')))
+    }
+}
+
+/// registry 索引
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryIndex {
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub last_updated: String,
+    #[serde(default)]
+    pub featured: Vec<String>,
+    pub plugins: Vec<RegistryEntry>,
+}
+
+impl RegistryIndex {
+    /// 校验签名后解析索引
+    pub fn from_signed(raw: &Value, verifier: &SignatureVerifier) -> Result<Self, RegistryError> {
+        verifier
+            .verify_manifest(raw)
+            .map_err(|e| RegistryError::SignatureInvalid(e.to_string()))?;
+        serde_json::from_value(raw.clone()).map_err(|e| RegistryError::InvalidIndex(e.to_string()))
+    }
+
+    /// 搜索插件（空关键字返回全部）
+    pub fn search(&self, query: &str) -> Vec<RegistryEntry> {
+        let query = query.trim();
+        self.plugins
+            .iter()
+            .filter(|entry| query.is_empty() || entry.matches(query))
+            .cloned()
+            .collect()
+    }
+
+    /// 按 ID 查找插件
+    pub fn get(&self, id: &str) -> Option<&RegistryEntry> {
+        self.plugins.iter().find(|entry| entry.id == id)
+    }
+}
+
+/// registry 中插件的 manifest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryManifest {
+    /// registry 条目
+    pub entry: RegistryEntry,
+    /// manifest.json 内容
+    pub manifest: Value,
+    /// manifest 签名是否有效
+    pub signature_valid: bool,
+    /// 签名验证失败原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_error: Option<String>,
+}
+
+// ============================================================================
+// Registry 客户端
+// ============================================================================
+
+/// 内存中的索引快照
+struct CachedIndex {
+    url: String,
+    index: Arc<RegistryIndex>,
+    fetched_at: Instant,
+}
+
+/// Registry 客户端
+pub struct RegistryClient {
+    verifier: SignatureVerifier,
+    index: RwLock<Option<CachedIndex>>,
+}
+
+impl RegistryClient {
+    pub fn new() -> Self {
+        Self {
+            verifier: SignatureVerifier::default(),
+            index: RwLock::new(None),
+        }
+    }
+
+    /// 获取索引（内存缓存 + TTL，`url` 为空时使用默认地址）
+    pub async fn index(&self, url: Option<&str>) -> Result<Arc<RegistryIndex>, RegistryError> {
+        let url = url.unwrap_or(DEFAULT_REGISTRY_URL);
+        {
+            let cached = self.index.read().await;
+            if let Some(cached) = cached.as_ref() {
+                if cached.url == url && cached.fetched_at.elapsed() < INDEX_TTL {
+                    return Ok(cached.index.clone());
+                }
+            }
+        }
+
+        let raw = self.fetch_json(url, MAX_INDEX_SIZE).await.map_err(RegistryError::Fetch)?;
+        let index = Arc::new(RegistryIndex::from_signed(&raw, &self.verifier)?);
+        log::info!(
+            "registry 索引已更新: url={}, version={}, {} 个插件",
+            url,
+            index.version,
+            index.plugins.len()
+        );
+
+        *self.index.write().await = Some(CachedIndex {
+            url: url.to_string(),
+            index: index.clone(),
+            fetched_at: Instant::now(),
+        });
+        Ok(index)
+    }
+
+    /// 搜索插件
+    pub async fn search(
+        &self,
+        url: Option<&str>,
+        query: &str,
+    ) -> Result<Vec<RegistryEntry>, RegistryError> {
+        Ok(self.index(url).await?.search(query))
+    }
+
+    /// 查找插件条目
+    pub async fn entry(&self, url: Option<&str>, id: &str) -> Result<RegistryEntry, RegistryError> {
+        self.index(url)
+            .await?
+            .get(id)
+            .cloned()
+            .ok_or_else(|| RegistryError::PluginNotFound(id.to_string()))
+    }
+
+    /// 获取插件 manifest 并验证签名（签名无效时仍返回内容，供预览）
+    pub async fn manifest(
+        &self,
+        url: Option<&str>,
+        id: &str,
+    ) -> Result<RegistryManifest, RegistryError> {
+        let entry = self.entry(url, id).await?;
+        let location = entry
+            .manifest_location()
+            .ok_or_else(|| RegistryError::Manifest(format!("插件 {} 未提供 manifest 地址", id)))?;
+
+        let manifest = self
+            .fetch_json(&location, MAX_MANIFEST_SIZE)
+            .await
+            .map_err(RegistryError::Manifest)?;
+        if manifest.get("id").and_then(|v| v.as_str()) != Some(id) {
+            return Err(RegistryError::Manifest(format!(
+                "manifest id 与 registry 条目不一致: {}",
+                id
+            )));
+        }
+
+        let signature_error = self.verifier.verify_manifest(&manifest).err().map(|e| e.to_string());
+        Ok(RegistryManifest {
+            entry,
+            manifest,
+            signature_valid: signature_error.is_none(),
+            signature_error,
+        })
+    }
+
+    /// 获取并解析 JSON（超过 `max_size` 字节时拒绝）
+    async fn fetch_json(&self, url: &str, max_size: usize) -> Result<Value, String> {
+        let (client, url) = secure_client(url).await?;
+        let response = client.get(url).send().await.map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+
+        let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+        if bytes.len() > max_size {
+            return Err(format!("响应过大: {} bytes", bytes.len()));
+        }
+        serde_json::from_slice(&bytes).map_err(|e| format!("解析失败: {}", e))
+    }
+}
+
+impl Default for RegistryClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// 远程地址校验
+// ============================================================================
+
+/// 校验远程地址并创建请求客户端
+///
+/// 只允许 HTTPS，拒绝 localhost / 内网地址；域名的 DNS 解析结果同样检查，
+/// 并固定用于本次请求（防止 DNS 重绑定）。重定向目标同样只允许 HTTPS 且不能是内网地址。
+pub async fn secure_client(url: &str) -> Result<(reqwest::Client, url::Url), String> {
+    let parsed = UrlSecurityChecker::check_url(url).map_err(|e| e.to_string())?;
+    if parsed.scheme() != "https" {
+        return Err(format!("仅支持 HTTPS 地址: {}", url));
+    }
+    let resolved: Option<SocketAddr> = UrlSecurityChecker::check_resolved_ip(&parsed)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let redirect = reqwest::redirect::Policy::custom(|attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            return attempt.error(format!("重定向次数超过 {}", MAX_REDIRECTS));
+        }
+        let safe = attempt.url().scheme() == "https"
+            && UrlSecurityChecker::check_url(attempt.url().as_str()).is_ok();
+        if safe {
+            attempt.follow()
+        } else {
+            let target = attempt.url().to_string();
+            attempt.error(format!("拒绝重定向到不安全的地址: {}", target))
+        }
+    });
+    let mut builder = reqwest::Client::builder().timeout(REQUEST_TIMEOUT).redirect(redirect);
+    if let (Some(host), Some(addr)) = (parsed.host_str(), resolved) {
+        builder = builder.resolve(host, addr);
+    }
+    let client = builder.build().map_err(|e| e.to_string())?;
+    Ok((client, parsed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::canonical::canonicalize_for_signing;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use ed25519_dalek::{Signer, SigningKey};
+    use serde_json::json;
+
+    // RFC 8032 测试向量私钥（对应 signature 模块中的 "test" 公钥）
+    const TEST_PRIVATE_KEY: [u8; 32] = [
+        0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec, 0x2c,
+        0xc4, 0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03, 0x1c, 0xae,
+        0x7f, 0x60,
+    ];
+
+    fn signed_index() -> Value {
+        let mut index = json!({
+            "version": "3",
+            "lastUpdated": "2026-01-01T00:00:00Z",
+            "featured": ["claude-usage"],
+            "plugins": [
+                {
+                    "id": "claude-usage",
+                    "name": "Claude 使用量",
+                    "description": "Anthropic usage",
+                    "version": "1.2.0",
+                    "tags": ["anthropic"],
+                    "downloadUrl": "https://example.com/plugins/claude-usage"
+                },
+                {
+                    "id": "openai-balance",
+                    "name": "OpenAI 余额",
+                    "version": "0.3.0",
+                    "downloadUrl": "https://example.com/openai-balance.zip"
+                }
+            ]
+        });
+        let signature = SigningKey::from_bytes(&TEST_PRIVATE_KEY)
+            .sign(canonicalize_for_signing(&index).as_bytes());
+        index["signature"] = json!(format!("ed25519:test:{}", BASE64.encode(signature.to_bytes())));
+        index
+    }
+
+    #[test]
+    fn test_signed_index_search() {
+        let verifier = SignatureVerifier::default();
+        let index = RegistryIndex::from_signed(&signed_index(), &verifier).unwrap();
+
+        assert_eq!(index.search("").len(), 2);
+        let found = index.search("ANTHROPIC");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "claude-usage");
+
+        let entry = index.get("claude-usage").unwrap();
+        assert_eq!(
+            entry.manifest_location().as_deref(),
+            Some("https://example.com/plugins/claude-usage/manifest.json")
+        );
+        assert_eq!(index.get("openai-balance").unwrap().manifest_location(), None);
+    }
+
+    #[test]
+    fn test_tampered_or_unsigned_index_rejected() {
+        let verifier = SignatureVerifier::default();
+
+        let mut tampered = signed_index();
+        tampered["plugins"][0]["downloadUrl"] = json!("https://evil.example.com/payload");
+        assert!(matches!(
+            RegistryIndex::from_signed(&tampered, &verifier),
+            Err(RegistryError::SignatureInvalid(_))
+        ));
+
+        let mut unsigned = signed_index();
+        unsigned.as_object_mut().unwrap().remove("signature");
+        assert!(matches!(
+            RegistryIndex::from_signed(&unsigned, &verifier),
+            Err(RegistryError::SignatureInvalid(_))
+        ));
+    }
+}
@@ -9,16 +9,27 @@
 // - 2.1.5 栈大小限制 (512KB)
 // - 2.1.6 interrupt_handler (CPU 超时中断)
 // - 2.1.7 watchdog 任务
+// - 运行时池：按插件复用预热的 QuickJS 运行时，每次执行使用新的上下文
 
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use rquickjs::allocator::{Allocator, RawMemPtr, RustAllocator};
 use rquickjs::{AsyncContext, AsyncRuntime, Error as JsError};
 use thiserror::Error;
 use tokio::sync::oneshot;
 
-use crate::plugin::sandbox::{RequestManager, SandboxApiInitializer, TimerApi, TimerRegistry};
+use crate::plugin::plugin_logs::PluginLogRecorder;
+use crate::plugin::types::TrustLevel;
+use crate::plugin::sandbox::module::{PluginModuleLoader, PluginModuleResolver, PLUGIN_EXPORTS_GLOBAL};
+use crate::plugin::sandbox::{
+    CacheApi, ConsoleApi, InputApi, PluginCache, PluginDataApi, PluginDataReader, PluginInput, PluginModule,
+    PluginSecrets, PluginStorage, RequestManager, SandboxApiInitializer, SecretsApi, StorageApi, TimerApi,
+    TimerRegistry,
+};
 
 // ============================================================================
 // 常量定义
@@ -36,6 +47,15 @@ pub const DEFAULT_EXECUTION_TIMEOUT: Duration = Duration::from_secs(30);
 /// interrupt_handler 检查间隔 (每 N 次操作检查一次)
 const INTERRUPT_CHECK_INTERVAL: u64 = 10000;
 
+/// 运行时池空闲运行时的内存预算: 128MB（按每个运行时的内存上限计算）
+pub const DEFAULT_POOL_MEMORY_BUDGET: usize = 128 * 1024 * 1024;
+
+/// 每个插件最多保留的空闲运行时数
+const MAX_IDLE_RUNTIMES_PER_PLUGIN: usize = 2;
+
+/// 空闲运行时的最长保留时间
+const POOL_IDLE_TTL: Duration = Duration::from_secs(10 * 60);
+
 // ============================================================================
 // 错误类型
 // ============================================================================
@@ -166,7 +186,7 @@ impl Default for InterruptController {
 // ============================================================================
 
 /// 沙盒运行时配置
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SandboxConfig {
     /// 内存限制 (字节)
     pub memory_limit: usize,
@@ -186,6 +206,127 @@ impl Default for SandboxConfig {
     }
 }
 
+/// 按权限注入的可选沙盒 API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SandboxApis {
+    pub fetch: bool,
+    pub timer: bool,
+    pub storage: bool,
+    pub secrets: bool,
+    pub input: bool,
+    pub cache: bool,
+    /// context.getData（声明了 read:<pluginId>）
+    pub plugin_data: bool,
+}
+
+impl SandboxApis {
+    /// 始终注入的基础 API
+    pub const CORE: [&'static str; 3] = ["console", "encoding", "errors"];
+
+    /// 根据权限决定注入哪些 API
+    pub fn from_permissions(permissions: &[String]) -> Self {
+        let has = |pred: fn(&str) -> bool| permissions.iter().any(|p| pred(p));
+        Self {
+            fetch: has(|p| p == "fetch" || p == "network" || p.starts_with("fetch:")),
+            timer: has(|p| p == "timer" || p == "setTimeout"),
+            storage: has(|p| p == "storage"),
+            secrets: has(|p| p.starts_with("secrets:")),
+            input: has(|p| p == "input"),
+            cache: has(|p| p == "cache"),
+            plugin_data: has(|p| p.starts_with("read:")),
+        }
+    }
+
+    /// 注入的 API 名称（含基础 API）
+    pub fn names(&self) -> Vec<&'static str> {
+        let optional = [
+            (self.fetch, "fetch"),
+            (self.timer, "timer"),
+            (self.storage, "storage"),
+            (self.secrets, "secrets"),
+            (self.input, "input"),
+            (self.cache, "cache"),
+            (self.plugin_data, "pluginData"),
+        ];
+        Self::CORE
+            .into_iter()
+            .chain(optional.into_iter().filter(|(on, _)| *on).map(|(_, name)| name))
+            .collect()
+    }
+}
+
+/// 沙盒内存占用跟踪（当前分配量与峰值）
+#[derive(Debug, Default)]
+pub struct MemoryTracker {
+    current: AtomicU64,
+    peak: AtomicU64,
+}
+
+impl MemoryTracker {
+    fn add(&self, bytes: usize) {
+        let current = self.current.fetch_add(bytes as u64, Ordering::Relaxed) + bytes as u64;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn sub(&self, bytes: usize) {
+        self.current.fetch_sub(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// 当前已分配的内存（字节）
+    pub fn current(&self) -> u64 {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    /// 上次重置以来的峰值内存（字节）
+    pub fn peak(&self) -> u64 {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    /// 以当前分配量重置峰值（每次执行开始时调用）
+    pub fn reset_peak(&self) {
+        self.peak.store(self.current(), Ordering::Relaxed);
+    }
+}
+
+/// 统计分配量的 QuickJS 分配器
+///
+/// QuickJS 按引用计数立即释放对象，执行结束后查询内存无法反映执行期间的占用，
+/// 因此在分配器层面记录峰值。内存上限仍由 QuickJS 的 malloc_limit 检查
+struct TrackingAllocator {
+    inner: RustAllocator,
+    tracker: Arc<MemoryTracker>,
+}
+
+unsafe impl Allocator for TrackingAllocator {
+    fn alloc(&mut self, size: usize) -> RawMemPtr {
+        let ptr = self.inner.alloc(size);
+        if !ptr.is_null() {
+            // SAFETY: ptr 由 RustAllocator 刚刚分配
+            self.tracker.add(unsafe { RustAllocator::usable_size(ptr) });
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&mut self, ptr: RawMemPtr) {
+        self.tracker.sub(RustAllocator::usable_size(ptr));
+        self.inner.dealloc(ptr);
+    }
+
+    unsafe fn realloc(&mut self, ptr: RawMemPtr, new_size: usize) -> RawMemPtr {
+        let old_size = RustAllocator::usable_size(ptr);
+        let new_ptr = self.inner.realloc(ptr, new_size);
+        if !new_ptr.is_null() {
+            self.tracker.sub(old_size);
+            self.tracker.add(RustAllocator::usable_size(new_ptr));
+        }
+        new_ptr
+    }
+
+    unsafe fn usable_size(ptr: RawMemPtr) -> usize {
+        RustAllocator::usable_size(ptr)
+    }
+}
+
 /// 沙盒运行时
 /// 封装 QuickJS AsyncRuntime，提供资源限制和超时控制
 pub struct SandboxRuntime {
@@ -195,6 +336,8 @@ pub struct SandboxRuntime {
     interrupt_controller: Arc<InterruptController>,
     /// 配置
     config: SandboxConfig,
+    /// 内存占用跟踪
+    memory: Arc<MemoryTracker>,
 }
 
 impl SandboxRuntime {
@@ -204,9 +347,13 @@ impl SandboxRuntime {
         let interrupt_controller = Arc::new(InterruptController::new());
         interrupt_controller.set_timeout(config.execution_timeout);
 
-        // 创建 QuickJS 运行时
-        let runtime = AsyncRuntime::new()
-            .map_err(|e| RuntimeError::RuntimeCreation(e.to_string()))?;
+        // 创建 QuickJS 运行时（使用统计分配量的分配器）
+        let memory = Arc::new(MemoryTracker::default());
+        let runtime = AsyncRuntime::new_with_alloc(TrackingAllocator {
+            inner: RustAllocator,
+            tracker: memory.clone(),
+        })
+        .map_err(|e| RuntimeError::RuntimeCreation(e.to_string()))?;
 
         // 配置内存限制
         runtime.set_memory_limit(config.memory_limit).await;
@@ -231,6 +378,7 @@ impl SandboxRuntime {
             runtime,
             interrupt_controller,
             config,
+            memory,
         })
     }
 
@@ -273,6 +421,9 @@ impl SandboxRuntime {
     /// 此方法在基础沙盒上下文基础上，额外注入：
     /// - fetch API（受 SSRF 防护）
     /// - timer API（setTimeout/setInterval）
+    /// - storage API（插件键值存储）
+    /// - secrets API（Keychain 只读密钥）
+    /// - input API（运行时请求用户输入）
     ///
     /// # 参数
     /// - `permissions`: 允许的权限列表（如 ["fetch", "timer"]）
@@ -281,16 +432,18 @@ impl SandboxRuntime {
         permissions: &[String],
         request_manager: Option<Arc<RequestManager>>,
         timer_registry: Option<Arc<TimerRegistry>>,
+        storage: Option<Arc<PluginStorage>>,
+        secrets: Option<Arc<PluginSecrets>>,
+        input: Option<Arc<PluginInput>>,
     ) -> Result<AsyncContext, RuntimeError> {
         let ctx = AsyncContext::full(&self.runtime)
             .await
             .map_err(|e| RuntimeError::ContextCreation(e.to_string()))?;
 
         // 根据权限决定注入哪些 API
-        let has_fetch = permissions.iter().any(|p| p == "fetch" || p == "network");
-        let has_timer = permissions.iter().any(|p| p == "timer" || p == "setTimeout");
+        let apis = SandboxApis::from_permissions(permissions);
 
-        if has_fetch {
+        if apis.fetch {
             if let Some(rm) = request_manager {
                 SandboxApiInitializer::init_with_fetch(&ctx, rm)
                     .await
@@ -309,7 +462,7 @@ impl SandboxRuntime {
         }
 
         // 注入 timer API（如果有权限）
-        if has_timer {
+        if apis.timer {
             if let Some(tr) = timer_registry {
                 ctx.with(|ctx| TimerApi::inject(&ctx, tr))
                     .await
@@ -317,13 +470,47 @@ impl SandboxRuntime {
             }
         }
 
+        // 注入 storage API（如果有权限）
+        if apis.storage {
+            if let Some(storage) = storage {
+                ctx.with(|ctx| StorageApi::inject(&ctx, storage))
+                    .await
+                    .map_err(|e| RuntimeError::ContextCreation(format!("Storage API 注入失败: {}", e)))?;
+            }
+        }
+
+        // 注入 secrets API（如果声明了 secrets:<name> 权限）
+        if apis.secrets {
+            if let Some(secrets) = secrets {
+                ctx.with(|ctx| SecretsApi::inject(&ctx, secrets))
+                    .await
+                    .map_err(|e| RuntimeError::ContextCreation(format!("Secrets API 注入失败: {}", e)))?;
+            }
+        }
+
+        // 注入 input API（如果有权限）
+        if apis.input {
+            if let Some(input) = input {
+                ctx.with(|ctx| InputApi::inject(&ctx, input))
+                    .await
+                    .map_err(|e| RuntimeError::ContextCreation(format!("Input API 注入失败: {}", e)))?;
+            }
+        }
+
         log::info!(
-            "沙盒上下文已创建，权限: {:?}, fetch={}, timer={}",
-            permissions, has_fetch, has_timer
+            "沙盒上下文已创建，权限: {:?}, fetch={}, timer={}, storage={}, secrets={}, input={}",
+            permissions, apis.fetch, apis.timer, apis.storage, apis.secrets, apis.input
         );
         Ok(ctx)
     }
 
+    /// 设置 ES Module 加载器（相对导入从插件目录解析）
+    pub async fn set_module_root(&self, root: PathBuf) {
+        self.runtime
+            .set_loader(PluginModuleResolver, PluginModuleLoader::new(root))
+            .await;
+    }
+
     /// 获取中断控制器
     pub fn interrupt_controller(&self) -> Arc<InterruptController> {
         self.interrupt_controller.clone()
@@ -344,16 +531,29 @@ impl SandboxRuntime {
         self.interrupt_controller.reset();
     }
 
+    /// 是否还有未执行的 Promise 任务
+    pub async fn is_job_pending(&self) -> bool {
+        self.runtime.is_job_pending().await
+    }
+
+    /// 执行垃圾回收（回收已释放上下文占用的内存）
+    pub async fn run_gc(&self) {
+        self.runtime.run_gc().await;
+    }
+
     /// 获取配置
     pub fn config(&self) -> &SandboxConfig {
         &self.config
     }
 
-    /// 获取运行时内存使用情况
-    pub fn memory_usage(&self) -> usize {
-        // rquickjs 0.6 不直接提供内存使用查询
-        // 需要通过 Runtime::memory_usage() 方法，但在 async 版本中需要特殊处理
-        0 // TODO: 实现实际的内存使用查询
+    /// 获取运行时当前已分配的内存（字节）
+    pub fn memory_usage(&self) -> u64 {
+        self.memory.current()
+    }
+
+    /// 内存占用跟踪（峰值统计）
+    pub fn memory_tracker(&self) -> &MemoryTracker {
+        &self.memory
     }
 
     /// 带资源限制的执行入口（推荐使用）
@@ -452,6 +652,50 @@ impl SandboxRuntime {
         result.map_err(RuntimeError::JsExecution)
     }
 
+    /// 等待插件结果完成并序列化为 JSON
+    ///
+    /// 读取全局 `__rawResult`，是 Promise 时驱动 QuickJS 任务队列和宿主异步任务（fetch、定时器等）
+    /// 直到 Promise 完成。超过 `timeout` 仍未完成时返回超时错误；
+    /// Promise 回调中的同步代码同样受 Watchdog 中断保护。
+    pub async fn settle_result(
+        &self,
+        context: &AsyncContext,
+        timeout: Duration,
+    ) -> Result<String, RuntimeError> {
+        // 1. 启动 Watchdog（使用剩余执行时间）
+        let mut watchdog = Watchdog::new(self.interrupt_controller.clone());
+        watchdog.start(timeout);
+        let original_timeout = self.interrupt_controller.timeout_ms.load(Ordering::SeqCst);
+        self.interrupt_controller.set_timeout(timeout);
+        self.start_execution();
+
+        // 2. 等待 Promise 完成
+        let settled = tokio::time::timeout(
+            timeout,
+            rquickjs::async_with!(context => |ctx| { settle_raw_result(ctx).await }),
+        )
+        .await;
+
+        // 3. 停止 Watchdog，先缓存中断状态再重置
+        watchdog.stop();
+        let was_interrupted = self.interrupt_controller.interrupted.load(Ordering::SeqCst);
+        self.reset();
+        self.interrupt_controller
+            .timeout_ms
+            .store(original_timeout, Ordering::SeqCst);
+
+        match settled {
+            Err(_) => Err(RuntimeError::ExecutionTimeout(self.config.execution_timeout)),
+            Ok(_) if was_interrupted => Err(RuntimeError::ExecutionTimeout(self.config.execution_timeout)),
+            Ok(Err(e)) => Err(RuntimeError::JsExecution(e)),
+            Ok(Ok(Err(message))) => {
+                log::error!("插件异步执行错误: {}", message);
+                Err(RuntimeError::RuntimeCreation(message))
+            }
+            Ok(Ok(Ok(json))) => Ok(json),
+        }
+    }
+
     /// 带自定义超时的执行入口
     pub async fn run_with_timeout<F, R>(
         &self,
@@ -626,6 +870,65 @@ impl Executor {
     }
 }
 
+// ============================================================================
+// 执行资源统计
+// ============================================================================
+
+/// 单次沙盒执行的资源占用
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ExecutionStats {
+    /// JS 执行占用的 CPU 时间（只统计沙盒 Future 被轮询的时间，不含等待网络）
+    pub cpu_time: Duration,
+    /// 执行期间运行时分配内存的峰值（含运行时自身的基础占用）
+    pub memory_bytes: u64,
+}
+
+/// 当前线程已消耗的 CPU 时间
+#[cfg(unix)]
+fn thread_cpu_time() -> Duration {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: ts 是有效的可写 timespec
+    if unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut ts) } != 0 {
+        return Duration::ZERO;
+    }
+    Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+/// 非 Unix 平台没有线程 CPU 时钟，退化为轮询耗时
+#[cfg(not(unix))]
+fn thread_cpu_time() -> Duration {
+    static EPOCH: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+    EPOCH.get_or_init(Instant::now).elapsed()
+}
+
+/// 累计 Future 被轮询时消耗的线程 CPU 时间
+///
+/// 每次 poll 都在单个线程上同步执行，累加 poll 前后的线程 CPU 时间差即为 JS 实际执行时间，
+/// 不受 await 期间线程切换或执行其他任务的影响
+struct CpuTimed<'a, F> {
+    inner: std::pin::Pin<Box<F>>,
+    total_nanos: &'a AtomicU64,
+}
+
+fn cpu_timed<F: std::future::Future>(future: F, total_nanos: &AtomicU64) -> CpuTimed<'_, F> {
+    CpuTimed {
+        inner: Box::pin(future),
+        total_nanos,
+    }
+}
+
+impl<F: std::future::Future> std::future::Future for CpuTimed<'_, F> {
+    type Output = F::Output;
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<F::Output> {
+        let start = thread_cpu_time();
+        let result = self.inner.as_mut().poll(cx);
+        let elapsed = thread_cpu_time().saturating_sub(start);
+        self.total_nanos.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+        result
+    }
+}
+
 // ============================================================================
 // 插件执行器（安全入口）
 // ============================================================================
@@ -640,6 +943,17 @@ pub struct PluginExecutor {
     runtime: Arc<SandboxRuntime>,
     request_manager: Option<Arc<RequestManager>>,
     timer_registry: Option<Arc<TimerRegistry>>,
+    storage: Option<Arc<PluginStorage>>,
+    cache: Option<Arc<PluginCache>>,
+    secrets: Option<Arc<PluginSecrets>>,
+    input: Option<Arc<PluginInput>>,
+    plugin_data: Option<Arc<PluginDataReader>>,
+    /// 插件日志记录器（console 输出写入日志缓冲区）
+    log_recorder: Option<PluginLogRecorder>,
+    /// 插件信任级别（Phase 5A.10，None 表示不按信任级别过滤 API）
+    trust_level: Option<TrustLevel>,
+    /// 最近一次执行的资源占用
+    last_stats: Mutex<ExecutionStats>,
 }
 
 impl PluginExecutor {
@@ -649,9 +963,22 @@ impl PluginExecutor {
             runtime,
             request_manager: None,
             timer_registry: None,
+            storage: None,
+            cache: None,
+            secrets: None,
+            input: None,
+            plugin_data: None,
+            log_recorder: None,
+            trust_level: None,
+            last_stats: Mutex::new(ExecutionStats::default()),
         }
     }
 
+    /// 最近一次执行的资源占用（CPU 时间、内存）
+    pub fn last_stats(&self) -> ExecutionStats {
+        *self.last_stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// 设置 RequestManager（用于 fetch API）
     pub fn with_request_manager(mut self, rm: Arc<RequestManager>) -> Self {
         self.request_manager = Some(rm);
@@ -664,6 +991,48 @@ impl PluginExecutor {
         self
     }
 
+    /// 设置插件存储（用于 storage API）
+    pub fn with_storage(mut self, storage: Arc<PluginStorage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// 设置插件磁盘缓存（用于 cache API）
+    pub fn with_cache(mut self, cache: Arc<PluginCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// 设置插件密钥访问器（用于 secrets API）
+    pub fn with_secrets(mut self, secrets: Arc<PluginSecrets>) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// 设置插件输入句柄（用于 requestInput API）
+    pub fn with_input(mut self, input: Arc<PluginInput>) -> Self {
+        self.input = Some(input);
+        self
+    }
+
+    /// 设置其他插件的数据快照（用于 getData API）
+    pub fn with_plugin_data(mut self, reader: Arc<PluginDataReader>) -> Self {
+        self.plugin_data = Some(reader);
+        self
+    }
+
+    /// 设置插件日志记录器（捕获 console 输出）
+    pub fn with_log_recorder(mut self, recorder: PluginLogRecorder) -> Self {
+        self.log_recorder = Some(recorder);
+        self
+    }
+
+    /// 设置插件信任级别（创建上下文时只注入该级别允许的 API）
+    pub fn with_trust_level(mut self, trust_level: TrustLevel) -> Self {
+        self.trust_level = Some(trust_level);
+        self
+    }
+
     /// 执行插件代码（安全入口）
     ///
     /// 自动创建沙盒上下文并根据权限注入 API
@@ -676,8 +1045,8 @@ impl PluginExecutor {
     /// 4. 内存限制生效
     ///
     /// # 异步 Promise 支持
-    /// 当插件有 fetch/network 权限时，自动使用 `run_with_limits_async`
-    /// 等待所有异步 Promise 完成后再返回结果
+    /// 代码返回 Promise 时（如 async fetchData 中等待 fetch、storage、定时器），
+    /// 驱动 QuickJS 任务队列和宿主异步任务直到 Promise 完成，总耗时受执行超时限制
     ///
     /// # 参数
     /// - `code`: 要执行的 JS 代码
@@ -691,6 +1060,40 @@ impl PluginExecutor {
         code: &str,
         permissions: &[String],
     ) -> Result<serde_json::Value, RuntimeError> {
+        self.execute(None, code, permissions).await
+    }
+
+    /// 执行 ES Module 形式的插件
+    ///
+    /// 先按标准 ES Module 求值入口模块（相对导入从插件目录加载），
+    /// 模块导出对象放在全局 `__pluginExports` 上，再执行调用脚本 `invocation`
+    /// （如调用 `__pluginExports.fetchData(config, context)`），返回调用脚本的结果。
+    pub async fn execute_module(
+        &self,
+        module: &PluginModule,
+        invocation: &str,
+        permissions: &[String],
+    ) -> Result<serde_json::Value, RuntimeError> {
+        self.runtime.set_module_root(module.root.clone()).await;
+        self.execute(Some(module.clone()), invocation, permissions).await
+    }
+
+    async fn execute(
+        &self,
+        module: Option<PluginModule>,
+        code: &str,
+        permissions: &[String],
+    ) -> Result<serde_json::Value, RuntimeError> {
+        // 复用的运行时保留了上次执行的峰值，从当前占用重新统计
+        self.runtime.memory_tracker().reset_peak();
+
+        // 信任级别不允许的 API 不注入（Phase 5A.10）
+        let permissions = match self.trust_level {
+            Some(trust_level) => trust_level.filter_permissions(permissions),
+            None => permissions.to_vec(),
+        };
+        let permissions = permissions.as_slice();
+
         // 1. 创建安全的沙盒上下文（根据权限注入 API）
         let ctx = self
             .runtime
@@ -698,145 +1101,116 @@ impl PluginExecutor {
                 permissions,
                 self.request_manager.clone(),
                 self.timer_registry.clone(),
+                self.storage.clone(),
+                self.secrets.clone(),
+                self.input.clone(),
             )
             .await?;
 
-        // 2. 检查是否需要异步执行（有 fetch/network 权限时需要等待 Promise）
-        let needs_async = permissions.iter().any(|p| p == "fetch" || p == "network");
+        // 注入 cache API（如果有权限）
+        let apis = SandboxApis::from_permissions(permissions);
+        if apis.cache {
+            if let Some(cache) = self.cache.clone() {
+                ctx.with(|ctx| CacheApi::inject(&ctx, cache))
+                    .await
+                    .map_err(|e| RuntimeError::ContextCreation(format!("Cache API 注入失败: {}", e)))?;
+            }
+        }
+
+        // 注入 getData API（如果声明了 read:<pluginId> 权限）
+        if apis.plugin_data {
+            if let Some(reader) = self.plugin_data.clone() {
+                ctx.with(|ctx| PluginDataApi::inject(&ctx, reader))
+                    .await
+                    .map_err(|e| RuntimeError::ContextCreation(format!("Plugin Data API 注入失败: {}", e)))?;
+            }
+        }
+
+        // 捕获 console 输出（替换基础沙盒的 console）
+        if let Some(recorder) = self.log_recorder.clone() {
+            ctx.with(|ctx| ConsoleApi::inject_with_recorder(&ctx, Some(recorder)))
+                .await
+                .map_err(|e| RuntimeError::ContextCreation(format!("Console API 注入失败: {}", e)))?;
+        }
+
+        // 2-4. 执行调用脚本，记录 CPU 时间和内存峰值
+        let cpu_nanos = AtomicU64::new(0);
+        let result = self.run_invocation(&ctx, module, code, &cpu_nanos).await;
+
+        // 5. 执行期定时器随执行结束取消（持久定时器由宿主调度）
+        if let Some(tr) = &self.timer_registry {
+            tr.cancel_all().await;
+        }
+
+        let stats = ExecutionStats {
+            cpu_time: Duration::from_nanos(cpu_nanos.load(Ordering::Relaxed)),
+            memory_bytes: self.runtime.memory_tracker().peak(),
+        };
+        *self.last_stats.lock().unwrap_or_else(|e| e.into_inner()) = stats;
+        result
+    }
 
-        // 3. 根据是否需要异步执行选择执行方法
+    /// 在已创建的上下文中执行插件代码并等待结果（累计 JS 执行的 CPU 时间）
+    async fn run_invocation(
+        &self,
+        ctx: &AsyncContext,
+        module: Option<PluginModule>,
+        code: &str,
+        cpu_nanos: &AtomicU64,
+    ) -> Result<serde_json::Value, RuntimeError> {
+        // 2. 执行插件代码（受 Watchdog 保护），结果保存到全局变量
+        // 使用 Arc<Mutex> 来捕获同步执行期间的错误消息
+        let started = Instant::now();
+        if let Some(input) = &self.input {
+            // 输入对话框的等待时间不超过剩余执行时间
+            input.set_deadline(Some(started + self.runtime.config.execution_timeout));
+        }
         let code_owned = code.to_string();
-        let json_str: String = if needs_async {
-            // 使用异步执行方式：
-            // 1. 先初始化全局变量
-            // 2. 执行插件代码获取 Promise
-            // 3. 等待 Promise 完成
-            // 4. 获取结果
-
-            // 第一步：初始化全局变量
-            ctx.with(|js_ctx| -> rquickjs::Result<()> {
-                js_ctx.eval::<(), _>(b"globalThis.__asyncResult = null; globalThis.__asyncError = null; globalThis.__asyncDone = false;".to_vec())?;
-                Ok(())
-            }).await?;
-
-            // 第二步：执行插件代码获取 Promise
-            // 使用 Arc<Mutex> 来捕获同步执行期间的错误消息
-            let sync_error_msg: Arc<std::sync::Mutex<Option<String>>> = Arc::new(std::sync::Mutex::new(None));
-            let sync_error_msg_clone = sync_error_msg.clone();
-
-            let exec_result = self.runtime
-                .run_with_limits(&ctx, move |js_ctx| -> rquickjs::Result<()> {
-                    // 执行插件代码
-                    let result: rquickjs::Value = match js_ctx.eval(code_owned.as_bytes().to_vec()) {
-                        Ok(v) => v,
-                        Err(e) => {
-                            // 尝试从异常中提取更详细的错误消息
-                            let mut err_msg = String::new();
-                            if let Some(exc) = js_ctx.catch().as_exception() {
-                                let msg = exc.message().unwrap_or_default();
-                                log::error!("JS 异常消息: {}", msg);
-                                if !msg.is_empty() {
-                                    err_msg = msg;
-                                }
-                                if let Some(stack) = exc.stack() {
-                                    log::error!("JS 异常堆栈: {}", stack);
-                                }
+        let sync_error_msg: Arc<std::sync::Mutex<Option<String>>> = Arc::new(std::sync::Mutex::new(None));
+        let sync_error_msg_clone = sync_error_msg.clone();
+
+        let exec_result = cpu_timed(
+            self.runtime.run_with_limits(ctx, move |js_ctx| -> rquickjs::Result<()> {
+                let result: rquickjs::Value = match eval_plugin_code(&js_ctx, module.as_ref(), &code_owned) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        // 尝试从异常中提取更详细的错误消息
+                        if let Some(exc) = js_ctx.catch().as_exception() {
+                            let msg = exc.message().unwrap_or_default();
+                            log::error!("JS 异常消息: {}", msg);
+                            if let Some(stack) = exc.stack() {
+                                log::error!("JS 异常堆栈: {}", stack);
                             }
-                            // 保存错误消息
-                            if !err_msg.is_empty() {
+                            if !msg.is_empty() {
                                 if let Ok(mut guard) = sync_error_msg_clone.lock() {
-                                    *guard = Some(err_msg);
+                                    *guard = Some(msg);
                                 }
                             }
-                            return Err(e);
                         }
-                    };
-
-                    // 把结果保存到全局变量，让 JS 处理 Promise
-                    let globals = js_ctx.globals();
-                    globals.set("__rawResult", result)?;
-
-                    // 用 JS 代码检查并处理 Promise
-                    js_ctx.eval::<(), _>(br#"
-                        if (globalThis.__rawResult && typeof globalThis.__rawResult.then === 'function') {
-                            globalThis.__rawResult.then(
-                                function(v) { globalThis.__asyncResult = v; globalThis.__asyncDone = true; },
-                                function(e) { globalThis.__asyncError = e; globalThis.__asyncDone = true; }
-                            );
-                        } else {
-                            globalThis.__asyncResult = globalThis.__rawResult;
-                            globalThis.__asyncDone = true;
-                        }
-                    "#.to_vec())?;
-
-                    Ok(())
-                })
-                .await;
-
-            if let Err(e) = exec_result {
-                // 优先使用捕获的错误消息
-                if let Ok(guard) = sync_error_msg.lock() {
-                    if let Some(msg) = guard.as_ref() {
-                        log::error!("插件代码执行失败（详细）: {}", msg);
-                        return Err(RuntimeError::RuntimeCreation(msg.clone()));
+                        return Err(e);
                     }
-                }
-                log::error!("插件代码执行失败: {:?}", e);
-                return Err(e);
-            }
-
-            // 第三步：等待所有异步任务完成
-            self.runtime.runtime.idle().await;
-
-            // 第四步：获取异步结果
-            let async_result: Result<String, String> = ctx.with(|js_ctx| -> rquickjs::Result<Result<String, String>> {
-                // 检查是否有错误
-                let error: rquickjs::Value = js_ctx.eval(b"globalThis.__asyncError".to_vec())?;
-                if !error.is_null() && !error.is_undefined() {
-                    let err_str = if let Some(obj) = error.as_object() {
-                        obj.get::<_, String>("message")
-                            .or_else(|_| obj.get::<_, String>("msg"))
-                            .unwrap_or_else(|_| {
-                                js_ctx.json_stringify(error.clone())
-                                    .ok()
-                                    .flatten()
-                                    .and_then(|s| s.to_string().ok())
-                                    .unwrap_or_else(|| "Unknown error".to_string())
-                            })
-                    } else if let Some(s) = error.as_string() {
-                        s.to_string().unwrap_or_else(|_| "Unknown error".to_string())
-                    } else {
-                        format!("{:?}", error)
-                    };
-                    log::error!("插件异步执行错误: {}", err_str);
-                    return Ok(Err(err_str));
-                }
+                };
+                js_ctx.globals().set(RAW_RESULT_GLOBAL, result)
+            }),
+            cpu_nanos,
+        )
+        .await;
 
-                // 获取结果
-                let result: rquickjs::Value = js_ctx.eval(b"globalThis.__asyncResult".to_vec())?;
-                match js_ctx.json_stringify(result)? {
-                    Some(s) => Ok(Ok(s.to_string()?)),
-                    None => Ok(Ok("null".to_string())),
+        if let Err(e) = exec_result {
+            // 优先使用捕获的错误消息
+            if let Ok(guard) = sync_error_msg.lock() {
+                if let Some(msg) = guard.as_ref() {
+                    log::error!("插件代码执行失败（详细）: {}", msg);
+                    return Err(RuntimeError::RuntimeCreation(msg.clone()));
                 }
-            }).await?;
-
-            // 处理异步执行结果
-            match async_result {
-                Ok(json_str) => json_str,
-                Err(err_msg) => return Err(RuntimeError::RuntimeCreation(err_msg)),
             }
-        } else {
-            // 同步执行，不等待异步 Promise
-            self.runtime
-                .run_with_limits(&ctx, move |js_ctx| -> rquickjs::Result<String> {
-                    let result: rquickjs::Value = js_ctx.eval(code_owned.as_bytes().to_vec())?;
-                    match js_ctx.json_stringify(result)? {
-                        Some(s) => Ok(s.to_string()?),
-                        None => Ok("null".to_string()),
-                    }
-                })
-                .await?
-        };
+            log::error!("插件代码执行失败: {:?}", e);
+            return Err(e);
+        }
+
+        // 3. 结果是 Promise 时驱动任务队列直到完成（剩余执行时间内）
+        let remaining = self.runtime.config.execution_timeout.saturating_sub(started.elapsed());
+        let json_str = cpu_timed(self.runtime.settle_result(ctx, remaining), cpu_nanos).await?;
 
         // 4. 解析 JSON 字符串
         serde_json::from_str(&json_str)
@@ -870,6 +1244,58 @@ impl PluginExecutor {
     }
 }
 
+/// 插件代码执行结果在全局上的名称
+const RAW_RESULT_GLOBAL: &str = "__rawResult";
+
+/// 等待 `__rawResult`（可能是 Promise）完成，返回 JSON 字符串或错误消息
+async fn settle_raw_result(ctx: rquickjs::Ctx<'_>) -> rquickjs::Result<Result<String, String>> {
+    let value: rquickjs::Value = ctx.globals().get(RAW_RESULT_GLOBAL)?;
+    let value = match value.clone().into_promise() {
+        Some(promise) => match promise.into_future::<rquickjs::Value>().await {
+            Ok(value) => value,
+            Err(JsError::Exception) => return Ok(Err(js_error_message(&ctx, ctx.catch()))),
+            Err(e) => return Err(e),
+        },
+        None => value,
+    };
+    match ctx.json_stringify(value)? {
+        Some(s) => Ok(Ok(s.to_string()?)),
+        None => Ok(Ok("null".to_string())),
+    }
+}
+
+/// 提取 JS 错误值的消息（Error 对象、PluginError 或任意值）
+fn js_error_message<'js>(ctx: &rquickjs::Ctx<'js>, error: rquickjs::Value<'js>) -> String {
+    if let Some(obj) = error.as_object() {
+        obj.get::<_, String>("message")
+            .or_else(|_| obj.get::<_, String>("msg"))
+            .unwrap_or_else(|_| {
+                ctx.json_stringify(error.clone())
+                    .ok()
+                    .flatten()
+                    .and_then(|s| s.to_string().ok())
+                    .unwrap_or_else(|| "Unknown error".to_string())
+            })
+    } else if let Some(s) = error.as_string() {
+        s.to_string().unwrap_or_else(|_| "Unknown error".to_string())
+    } else {
+        format!("{:?}", error)
+    }
+}
+
+/// 执行插件代码：有插件模块时先求值模块并导出到全局 `__pluginExports`
+fn eval_plugin_code<'js>(
+    ctx: &rquickjs::Ctx<'js>,
+    module: Option<&PluginModule>,
+    code: &str,
+) -> rquickjs::Result<rquickjs::Value<'js>> {
+    if let Some(module) = module {
+        let exports = module.evaluate(ctx)?;
+        ctx.globals().set(PLUGIN_EXPORTS_GLOBAL, exports)?;
+    }
+    ctx.eval(code.as_bytes().to_vec())
+}
+
 /// 安全读取文件（使用 O_NOFOLLOW 防止符号链接跟踪）
 ///
 /// 这消除了路径检查与文件读取之间的 TOCTOU 窗口：
@@ -934,6 +1360,127 @@ async fn read_file_nofollow(path: &std::path::Path) -> Result<String, RuntimeErr
         .map_err(|e| RuntimeError::RuntimeCreation(format!("读取插件文件失败: {}", e)))
 }
 
+// ============================================================================
+// 运行时池
+// ============================================================================
+
+/// 空闲的运行时
+struct IdleRuntime {
+    runtime: Arc<SandboxRuntime>,
+    /// 归还时间（用于过期和 LRU 淘汰）
+    since: Instant,
+}
+
+/// 沙盒运行时池
+///
+/// 每次 fetchData 都创建 QuickJS 运行时开销较大。运行时池按插件保留执行完成的运行时，
+/// 下次执行时直接复用；每次执行仍创建新的上下文，插件之间、执行之间不共享全局状态。
+///
+/// 归还时：
+/// 1. 执行失败（超时、中断、异常）、仍被引用或有未完成的 Promise 任务的运行时直接丢弃
+/// 2. 其余运行时执行 GC、重置中断控制器后放回池中
+/// 3. 空闲运行时按内存上限累计，超过预算时淘汰最久未使用的运行时
+pub struct SandboxPool {
+    /// 空闲运行时的内存预算（字节）
+    memory_budget: usize,
+    /// plugin_id -> 空闲运行时（最近归还的在末尾）
+    idle: Mutex<HashMap<String, Vec<IdleRuntime>>>,
+}
+
+impl SandboxPool {
+    /// 创建运行时池
+    pub fn new(memory_budget: usize) -> Self {
+        Self {
+            memory_budget,
+            idle: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Vec<IdleRuntime>>> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 取出插件的空闲运行时，没有时按 `config` 新建
+    ///
+    /// 配置与 `config` 不同的空闲运行时（插件重载后资源限制变化）被丢弃
+    pub async fn acquire(&self, plugin_id: &str, config: &SandboxConfig) -> Result<Arc<SandboxRuntime>, RuntimeError> {
+        let reused = {
+            let mut idle = self.lock();
+            let runtimes = idle.get_mut(plugin_id);
+            let reused = runtimes.and_then(|runtimes| {
+                runtimes.retain(|r| r.since.elapsed() < POOL_IDLE_TTL && r.runtime.config() == config);
+                runtimes.pop()
+            });
+            if idle.get(plugin_id).is_some_and(Vec::is_empty) {
+                idle.remove(plugin_id);
+            }
+            reused
+        };
+
+        match reused {
+            Some(idle) => {
+                log::debug!("[{}] 复用沙盒运行时", plugin_id);
+                Ok(idle.runtime)
+            }
+            None => Ok(Arc::new(SandboxRuntime::new(config.clone()).await?)),
+        }
+    }
+
+    /// 归还运行时
+    ///
+    /// `reusable` 为 false（执行失败）时直接丢弃
+    pub async fn release(&self, plugin_id: &str, runtime: Arc<SandboxRuntime>, reusable: bool) {
+        // 仍被执行器等持有，或有未完成的任务（可能在下次执行时继续运行），不能复用
+        if !reusable || Arc::strong_count(&runtime) > 1 || runtime.is_job_pending().await {
+            log::debug!("[{}] 丢弃沙盒运行时", plugin_id);
+            return;
+        }
+        runtime.run_gc().await;
+        runtime.reset();
+
+        let runtime_memory = runtime.config().memory_limit.max(1);
+        let max_idle = self.memory_budget / runtime_memory;
+        let mut idle = self.lock();
+        let runtimes = idle.entry(plugin_id.to_string()).or_default();
+        if runtimes.len() >= MAX_IDLE_RUNTIMES_PER_PLUGIN {
+            runtimes.remove(0);
+        }
+        runtimes.push(IdleRuntime {
+            runtime,
+            since: Instant::now(),
+        });
+
+        // 超过内存预算时淘汰最久未使用的运行时
+        while idle.values().map(Vec::len).sum::<usize>() > max_idle {
+            let oldest = idle
+                .iter()
+                .filter_map(|(id, runtimes)| runtimes.first().map(|r| (id.clone(), r.since)))
+                .min_by_key(|(_, since)| *since)
+                .map(|(id, _)| id);
+            let Some(oldest) = oldest else {
+                break;
+            };
+            if let Some(runtimes) = idle.get_mut(&oldest) {
+                runtimes.remove(0);
+                if runtimes.is_empty() {
+                    idle.remove(&oldest);
+                }
+            }
+        }
+    }
+
+    /// 丢弃插件的所有空闲运行时（卸载插件时调用）
+    pub fn remove_plugin(&self, plugin_id: &str) {
+        self.lock().remove(plugin_id);
+    }
+}
+
+impl Default for SandboxPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_POOL_MEMORY_BUDGET)
+    }
+}
+
 // ============================================================================
 // 测试
 // ============================================================================
@@ -961,6 +1508,23 @@ mod tests {
         assert_eq!(runtime.config().stack_size, 256 * 1024);
     }
 
+    #[tokio::test]
+    async fn test_memory_limit_enforced_with_tracking_allocator() {
+        let config = SandboxConfig {
+            memory_limit: 8 * 1024 * 1024,
+            ..SandboxConfig::default()
+        };
+        let runtime = SandboxRuntime::new(config).await.unwrap();
+        let context = runtime.create_sandboxed_context().await.unwrap();
+
+        // 超过 malloc_limit 的分配失败，分配器统计到的占用不超过上限
+        let result = context
+            .with(|ctx| ctx.eval::<(), _>("const chunks = []; for (;;) chunks.push('x'.repeat(1 << 20));"))
+            .await;
+        assert!(result.is_err());
+        assert!(runtime.memory.peak() <= 8 * 1024 * 1024 + 1024 * 1024);
+    }
+
     #[tokio::test]
     async fn test_context_creation() {
         let runtime = SandboxRuntime::new_default().await.unwrap();
@@ -968,6 +1532,83 @@ mod tests {
         assert!(context.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_sandbox_pool_reuse() {
+        let config = SandboxConfig::default();
+        // 预算只够保留一个空闲运行时
+        let pool = SandboxPool::new(config.memory_limit);
+
+        let runtime = pool.acquire("a", &config).await.unwrap();
+        let executor = PluginExecutor::new(runtime.clone());
+        let result = executor.execute_plugin("globalThis.leaked = 1; 1 + 1", &[]).await.unwrap();
+        assert_eq!(result, serde_json::json!(2));
+        drop(executor);
+        let ptr = Arc::as_ptr(&runtime);
+        pool.release("a", runtime, true).await;
+
+        // 复用同一个运行时，但上下文是新的
+        let runtime = pool.acquire("a", &config).await.unwrap();
+        assert_eq!(Arc::as_ptr(&runtime), ptr);
+        let executor = PluginExecutor::new(runtime.clone());
+        let result = executor.execute_plugin("typeof globalThis.leaked", &[]).await.unwrap();
+        assert_eq!(result, serde_json::json!("undefined"));
+        drop(executor);
+        let pooled_a = Arc::downgrade(&runtime);
+        pool.release("a", runtime, true).await;
+
+        // 超出预算时淘汰最久未使用的；执行失败的运行时不放回
+        let other = pool.acquire("b", &config).await.unwrap();
+        pool.release("b", other, true).await;
+        assert!(pooled_a.upgrade().is_none());
+        let failed = pool.acquire("b", &config).await.unwrap();
+        let pooled_b = Arc::downgrade(&failed);
+        pool.release("b", failed, false).await;
+        assert!(pooled_b.upgrade().is_none());
+
+        // 资源限制变化后不复用旧配置的运行时
+        let runtime = pool.acquire("b", &config).await.unwrap();
+        let pooled_b = Arc::downgrade(&runtime);
+        pool.release("b", runtime, true).await;
+        let larger = SandboxConfig {
+            memory_limit: config.memory_limit * 2,
+            ..config.clone()
+        };
+        let runtime = pool.acquire("b", &larger).await.unwrap();
+        assert!(pooled_b.upgrade().is_none());
+        assert_eq!(runtime.config().memory_limit, larger.memory_limit);
+    }
+
+    #[tokio::test]
+    async fn test_execute_async_promise_resolution() {
+        let config = SandboxConfig {
+            execution_timeout: Duration::from_millis(500),
+            ..SandboxConfig::default()
+        };
+        let runtime = Arc::new(SandboxRuntime::new(config).await.unwrap());
+
+        // 等待 fetch 返回的 Promise（被 SSRF 检查拒绝，返回 ok=false 的响应）
+        let executor = PluginExecutor::new(runtime.clone())
+            .with_request_manager(Arc::new(RequestManager::new_with_fallback()));
+        let code = r#"(async function() {
+            var res = await fetch('http://127.0.0.1/usage');
+            await null;
+            return { ok: res.ok, status: res.status, steps: [1, 2].map(function(x) { return x * 2; }) };
+        })()"#;
+        let result = executor.execute_plugin(code, &["fetch".to_string()]).await.unwrap();
+        assert_eq!(result, serde_json::json!({ "ok": false, "status": 0, "steps": [2, 4] }));
+
+        // 无异步权限时同样等待 Promise；拒绝时返回错误消息
+        let executor = PluginExecutor::new(runtime.clone());
+        let result = executor.execute_plugin("Promise.resolve(1).then(function(v) { return v + 1; })", &[]).await;
+        assert_eq!(result.unwrap(), serde_json::json!(2));
+        let err = executor.execute_plugin("Promise.reject(new Error('boom'))", &[]).await.unwrap_err();
+        assert!(err.to_string().contains("boom"));
+
+        // 永不完成的 Promise 在执行超时后返回
+        let err = executor.execute_plugin("new Promise(function() {})", &[]).await.unwrap_err();
+        assert!(matches!(err, RuntimeError::ExecutionTimeout(_)));
+    }
+
     #[test]
     fn test_interrupt_controller() {
         let controller = InterruptController::new();
@@ -0,0 +1,565 @@
+// Cache API 实现
+// 插件私有的磁盘缓存 (context.cache)，按字节配额与 LRU 淘汰管理
+//
+// 提供给 JS 插件使用的磁盘缓存（需声明 `cache` 权限），用于缓存 Provider 响应:
+// - context.cache.put(key, value)      value 为 Uint8Array/ArrayBuffer（字节）或 JSON 值，不过期
+// - context.cache.set(key, value, ttl) 同 put，按契约默认 5 分钟过期
+// - context.cache.get(key)             返回写入时的类型，不存在或已过期时为 null
+// - context.cache.has(key) / delete(key) / keys()
+//
+// 设计要点：
+// 1. 每个插件独立缓存目录（插件 ID 校验防路径穿越），卸载插件时删除
+// 2. 按字节配额管理，写入超出配额时先清理过期条目，再按最近最少使用（LRU）淘汰
+// 3. 索引（类型、大小、访问顺序、过期时间）保存在 index.json，重启后不丢失
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rquickjs::{Ctx, Exception, Function, IntoJs, Object, Result as JsResult, Value};
+use serde::{Deserialize, Serialize};
+
+use super::storage::is_valid_plugin_id;
+
+// ============================================================================
+// 常量
+// ============================================================================
+
+/// 默认缓存配额: 10MB
+pub const DEFAULT_CACHE_QUOTA: u64 = 10 * 1024 * 1024;
+
+/// 最大键长度
+const MAX_KEY_LEN: usize = 256;
+
+/// 索引文件名
+const INDEX_FILE: &str = "index.json";
+
+/// set() 默认 TTL: 5 分钟（见 storage-cache-api.md）
+const DEFAULT_TTL_MS: u64 = 300_000;
+
+/// TTL 范围: 1 秒 - 1 小时
+const MIN_TTL_MS: u64 = 1_000;
+const MAX_TTL_MS: u64 = 3_600_000;
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+/// 缓存错误
+#[derive(Debug, thiserror::Error)]
+pub enum CacheError {
+    #[error("缓存条目过大: {size} 字节，配额 {quota} 字节")]
+    EntryTooLarge { size: u64, quota: u64 },
+
+    #[error("无效的键: {0}")]
+    InvalidKey(String),
+
+    #[error("无效的插件 ID: {0}")]
+    InvalidPluginId(String),
+
+    #[error("序列化失败: {0}")]
+    Serialize(#[from] serde_json::Error),
+
+    #[error("IO 错误: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl CacheError {
+    /// 对应的 PluginErrorType 代码
+    pub fn code(&self) -> &'static str {
+        match self {
+            CacheError::InvalidKey(_) | CacheError::InvalidPluginId(_) => "UNKNOWN",
+            _ => "CACHE_ERROR",
+        }
+    }
+}
+
+// ============================================================================
+// 插件缓存
+// ============================================================================
+
+/// 缓存条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheEntryKind {
+    /// 原始字节
+    Bytes,
+    /// JSON 文本
+    Json,
+}
+
+/// 缓存索引条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    kind: CacheEntryKind,
+    size: u64,
+    /// 最近访问序号（越大越新）
+    last_access: u64,
+    /// 过期时间（Unix 毫秒，None 表示不过期）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<u64>,
+}
+
+impl CacheEntry {
+    fn is_expired(&self, now_ms: u64) -> bool {
+        self.expires_at.is_some_and(|t| t <= now_ms)
+    }
+}
+
+/// 当前 Unix 毫秒
+fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// 缓存占用情况
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheUsage {
+    /// 条目数
+    pub entries: usize,
+    /// 已用字节数
+    pub bytes: u64,
+}
+
+/// 插件磁盘缓存（按插件隔离，LRU 淘汰）
+pub struct PluginCache {
+    /// 插件 ID
+    plugin_id: String,
+    /// 插件缓存目录
+    dir: PathBuf,
+    /// 配额（字节）
+    quota: u64,
+    /// 内存中的索引（变更时同步到磁盘）
+    index: Mutex<BTreeMap<String, CacheEntry>>,
+}
+
+impl PluginCache {
+    /// 默认缓存目录 (~/Library/Caches/cuk/plugins)
+    pub fn default_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("cuk")
+            .join("plugins")
+    }
+
+    /// 打开插件缓存（目录不存在时为空缓存，索引中缺失文件的条目被丢弃）
+    pub fn open(dir: &Path, plugin_id: &str, quota: u64) -> Result<Self, CacheError> {
+        let dir = Self::plugin_dir(dir, plugin_id)?;
+
+        let mut index: BTreeMap<String, CacheEntry> = match std::fs::read(dir.join(INDEX_FILE)) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                log::warn!("[{}] 缓存索引损坏，已忽略: {}", plugin_id, e);
+                BTreeMap::new()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        index.retain(|key, _| Self::entry_path(&dir, key).is_file());
+
+        Ok(Self {
+            plugin_id: plugin_id.to_string(),
+            dir,
+            quota,
+            index: Mutex::new(index),
+        })
+    }
+
+    /// 删除插件缓存目录（卸载插件时调用）
+    pub fn remove(dir: &Path, plugin_id: &str) -> Result<(), CacheError> {
+        let dir = Self::plugin_dir(dir, plugin_id)?;
+        match std::fs::remove_dir_all(&dir) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 计算插件缓存目录
+    fn plugin_dir(dir: &Path, plugin_id: &str) -> Result<PathBuf, CacheError> {
+        if !is_valid_plugin_id(plugin_id) {
+            return Err(CacheError::InvalidPluginId(plugin_id.to_string()));
+        }
+        Ok(dir.join(plugin_id))
+    }
+
+    /// 条目文件路径（键已校验，只含安全字符）
+    fn entry_path(dir: &Path, key: &str) -> PathBuf {
+        dir.join(format!("{}.entry", key))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, CacheEntry>> {
+        self.index.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 键名校验：1-256 字符，仅限 `[a-zA-Z0-9_-]`（与 storage 一致）
+    fn check_key(key: &str) -> Result<(), CacheError> {
+        if key.is_empty() || key.len() > MAX_KEY_LEN {
+            return Err(CacheError::InvalidKey(format!("长度须在 1-{} 之间", MAX_KEY_LEN)));
+        }
+        if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(CacheError::InvalidKey(format!(
+                "{} 只能包含字母、数字、下划线和连字符",
+                key
+            )));
+        }
+        Ok(())
+    }
+
+    /// 下一个访问序号
+    fn next_access(index: &BTreeMap<String, CacheEntry>) -> u64 {
+        index.values().map(|e| e.last_access).max().map_or(0, |n| n + 1)
+    }
+
+    /// 写入条目（`ttl_ms` 为 None 时不过期），空间不足时淘汰其他条目，返回被淘汰的键
+    pub fn put(
+        &self,
+        key: &str,
+        kind: CacheEntryKind,
+        data: &[u8],
+        ttl_ms: Option<u64>,
+    ) -> Result<Vec<String>, CacheError> {
+        Self::check_key(key)?;
+
+        let size = data.len() as u64;
+        if size > self.quota {
+            return Err(CacheError::EntryTooLarge { size, quota: self.quota });
+        }
+
+        let now = now_ms();
+        let mut index = self.lock();
+        index.remove(key);
+
+        // 先清理过期条目
+        let expired: Vec<String> = index
+            .iter()
+            .filter(|(_, e)| e.is_expired(now))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for k in &expired {
+            index.remove(k);
+            let _ = std::fs::remove_file(Self::entry_path(&self.dir, k));
+        }
+
+        // 淘汰最久未访问的条目直到放得下
+        let mut used: u64 = index.values().map(|e| e.size).sum();
+        let mut evicted = Vec::new();
+        while used + size > self.quota {
+            let Some(oldest) = index
+                .iter()
+                .min_by_key(|(_, e)| e.last_access)
+                .map(|(k, _)| k.clone())
+            else {
+                break;
+            };
+            if let Some(entry) = index.remove(&oldest) {
+                used -= entry.size;
+            }
+            let _ = std::fs::remove_file(Self::entry_path(&self.dir, &oldest));
+            evicted.push(oldest);
+        }
+
+        std::fs::create_dir_all(&self.dir)?;
+        let path = Self::entry_path(&self.dir, key);
+        let tmp = path.with_extension("entry.tmp");
+        std::fs::write(&tmp, data)?;
+        std::fs::rename(&tmp, &path)?;
+
+        let last_access = Self::next_access(&index);
+        let expires_at = ttl_ms.map(|ttl| now + ttl.clamp(MIN_TTL_MS, MAX_TTL_MS));
+        index.insert(
+            key.to_string(),
+            CacheEntry { kind, size, last_access, expires_at },
+        );
+        self.persist(&index)?;
+
+        if !evicted.is_empty() {
+            log::debug!("[{}] 缓存空间不足，已淘汰 {} 个条目", self.plugin_id, evicted.len());
+        }
+        Ok(evicted)
+    }
+
+    /// 读取条目（刷新访问顺序，过期条目被删除）
+    pub fn get(&self, key: &str) -> Result<Option<(CacheEntryKind, Vec<u8>)>, CacheError> {
+        let mut index = self.lock();
+        let Some(entry) = index.get(key) else {
+            return Ok(None);
+        };
+        let kind = entry.kind;
+        if entry.is_expired(now_ms()) {
+            index.remove(key);
+            let _ = std::fs::remove_file(Self::entry_path(&self.dir, key));
+            self.persist(&index)?;
+            return Ok(None);
+        }
+
+        let data = match std::fs::read(Self::entry_path(&self.dir, key)) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                // 文件被外部清理
+                index.remove(key);
+                self.persist(&index)?;
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let last_access = Self::next_access(&index);
+        if let Some(entry) = index.get_mut(key) {
+            entry.last_access = last_access;
+        }
+        self.persist(&index)?;
+        Ok(Some((kind, data)))
+    }
+
+    /// 条目是否存在且未过期
+    pub fn has(&self, key: &str) -> bool {
+        self.lock().get(key).is_some_and(|e| !e.is_expired(now_ms()))
+    }
+
+    /// 删除条目，返回键是否存在
+    pub fn delete(&self, key: &str) -> Result<bool, CacheError> {
+        let mut index = self.lock();
+        if index.remove(key).is_none() {
+            return Ok(false);
+        }
+        let _ = std::fs::remove_file(Self::entry_path(&self.dir, key));
+        self.persist(&index)?;
+        Ok(true)
+    }
+
+    /// 列出所有键
+    pub fn keys(&self) -> Vec<String> {
+        self.lock().keys().cloned().collect()
+    }
+
+    /// 当前占用
+    pub fn usage(&self) -> CacheUsage {
+        let index = self.lock();
+        CacheUsage {
+            entries: index.len(),
+            bytes: index.values().map(|e| e.size).sum(),
+        }
+    }
+
+    /// 原子写入索引
+    fn persist(&self, index: &BTreeMap<String, CacheEntry>) -> Result<(), CacheError> {
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(INDEX_FILE);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(index)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// JS API
+// ============================================================================
+
+/// Cache API
+pub struct CacheApi;
+
+impl CacheApi {
+    /// 向上下文注入 `__cukCache` 全局对象
+    ///
+    /// 原生函数以字节数组 / JSON 字符串交换数据，由 JS 包装层负责类型转换，
+    /// 并按契约返回 Promise、将错误转换为 PluginError。
+    pub fn inject<'js>(ctx: &Ctx<'js>, cache: Arc<PluginCache>) -> JsResult<()> {
+        let native = Object::new(ctx.clone())?;
+
+        let c = cache.clone();
+        native.set(
+            "putBytes",
+            Function::new(ctx.clone(), move |ctx: Ctx<'_>, key: String, data: Vec<u8>, ttl_ms: Option<f64>| -> JsResult<()> {
+                c.put(&key, CacheEntryKind::Bytes, &data, ttl_ms.map(|t| t.max(0.0) as u64))
+                    .map(|_| ())
+                    .map_err(|e| throw_cache_error(&ctx, &e))
+            })?,
+        )?;
+
+        let c = cache.clone();
+        native.set(
+            "putJson",
+            Function::new(ctx.clone(), move |ctx: Ctx<'_>, key: String, json: String, ttl_ms: Option<f64>| -> JsResult<()> {
+                c.put(&key, CacheEntryKind::Json, json.as_bytes(), ttl_ms.map(|t| t.max(0.0) as u64))
+                    .map(|_| ())
+                    .map_err(|e| throw_cache_error(&ctx, &e))
+            })?,
+        )?;
+
+        let c = cache.clone();
+        native.set(
+            "get",
+            Function::new(ctx.clone(), move |ctx: Ctx<'js>, key: String| -> JsResult<Value<'js>> {
+                let Some((kind, data)) = c.get(&key).map_err(|e| throw_cache_error(&ctx, &e))? else {
+                    return Ok(Value::new_null(ctx));
+                };
+                let entry = Object::new(ctx.clone())?;
+                match kind {
+                    CacheEntryKind::Bytes => {
+                        entry.set("kind", "bytes")?;
+                        entry.set("data", data)?;
+                    }
+                    CacheEntryKind::Json => {
+                        entry.set("kind", "json")?;
+                        entry.set("data", String::from_utf8_lossy(&data).into_owned())?;
+                    }
+                }
+                entry.into_js(&ctx)
+            })?,
+        )?;
+
+        let c = cache.clone();
+        native.set(
+            "has",
+            Function::new(ctx.clone(), move |key: String| -> bool { c.has(&key) })?,
+        )?;
+
+        let c = cache.clone();
+        native.set(
+            "delete",
+            Function::new(ctx.clone(), move |ctx: Ctx<'_>, key: String| -> JsResult<bool> {
+                c.delete(&key).map_err(|e| throw_cache_error(&ctx, &e))
+            })?,
+        )?;
+
+        let c = cache;
+        native.set(
+            "keys",
+            Function::new(ctx.clone(), move || -> Vec<String> { c.keys() })?,
+        )?;
+
+        // JS 包装层：字节/JSON 转换 + Promise 化 + 错误转换
+        let wrap: Function = ctx.eval(
+            r#"(function(native, defaultTtlMs) {
+                function toError(e) {
+                    var msg = String(e && e.message !== undefined ? e.message : e);
+                    var m = /^\[([A-Z_]+)\] ([\s\S]*)$/.exec(msg);
+                    if (m && typeof PluginError === 'function') return new PluginError(m[1], m[2], undefined);
+                    return e;
+                }
+                function run(fn) {
+                    try { return Promise.resolve(fn()); }
+                    catch (e) { return Promise.reject(toError(e)); }
+                }
+                function toBytes(value) {
+                    if (value instanceof ArrayBuffer) return new Uint8Array(value);
+                    if (ArrayBuffer.isView(value)) return new Uint8Array(value.buffer, value.byteOffset, value.byteLength);
+                    return null;
+                }
+                function put(key, value, ttlMs) {
+                    var bytes = toBytes(value);
+                    if (bytes !== null) {
+                        native.putBytes(String(key), Array.prototype.slice.call(bytes), ttlMs);
+                        return;
+                    }
+                    var json = JSON.stringify(value);
+                    if (json === undefined) throw new TypeError('cache: value must be bytes or JSON serializable');
+                    native.putJson(String(key), json, ttlMs);
+                }
+                return Object.freeze({
+                    put: function(key, value) {
+                        return run(function() { put(key, value, undefined); });
+                    },
+                    set: function(key, value, ttlMs) {
+                        return run(function() { put(key, value, ttlMs === undefined ? defaultTtlMs : Number(ttlMs)); });
+                    },
+                    get: function(key) {
+                        return run(function() {
+                            var entry = native.get(String(key));
+                            if (entry === undefined || entry === null) return null;
+                            return entry.kind === 'bytes' ? new Uint8Array(entry.data) : JSON.parse(entry.data);
+                        });
+                    },
+                    has: function(key) {
+                        return run(function() { return native.has(String(key)); });
+                    },
+                    delete: function(key) {
+                        return run(function() { return native.delete(String(key)); });
+                    },
+                    keys: function() {
+                        return run(function() { return native.keys(); });
+                    }
+                });
+            })"#,
+        )?;
+        let api: Object = wrap.call((native, DEFAULT_TTL_MS as f64))?;
+        ctx.globals().set("__cukCache", api)?;
+
+        log::debug!("Cache API 已注入");
+        Ok(())
+    }
+}
+
+/// 抛出带错误类型前缀的异常（由 JS 包装层转换为 PluginError）
+fn throw_cache_error(ctx: &Ctx<'_>, err: &CacheError) -> rquickjs::Error {
+    Exception::throw_message(ctx, &format!("[{}] {}", err.code(), err))
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cache_lru_eviction_and_persist() {
+        let dir = TempDir::new().unwrap();
+        let cache = PluginCache::open(dir.path(), "demo", 10).unwrap();
+
+        cache.put("a", CacheEntryKind::Bytes, &[1, 2, 3, 4], None).unwrap();
+        cache.put("b", CacheEntryKind::Json, b"\"xy\"", None).unwrap();
+        // 访问 a 后，b 成为最久未使用的条目
+        assert_eq!(cache.get("a").unwrap(), Some((CacheEntryKind::Bytes, vec![1, 2, 3, 4])));
+        assert_eq!(cache.put("c", CacheEntryKind::Bytes, &[0; 4], None).unwrap(), vec!["b".to_string()]);
+        assert_eq!(cache.usage(), CacheUsage { entries: 2, bytes: 8 });
+
+        // 超过配额的单个条目被拒绝
+        let err = cache.put("big", CacheEntryKind::Bytes, &[0; 11], None).unwrap_err();
+        assert_eq!(err.code(), "CACHE_ERROR");
+
+        // 重启后 LRU 顺序仍在：a 比 c 新
+        let reopened = PluginCache::open(dir.path(), "demo", 10).unwrap();
+        assert_eq!(reopened.keys(), vec!["a".to_string(), "c".to_string()]);
+        assert_eq!(reopened.put("d", CacheEntryKind::Bytes, &[0; 4], None).unwrap(), vec!["a".to_string()]);
+
+        assert!(PluginCache::open(dir.path(), "../evil", 10).is_err());
+        PluginCache::remove(dir.path(), "demo").unwrap();
+        assert!(!dir.path().join("demo").exists());
+    }
+
+    #[test]
+    fn test_cache_js_api() {
+        let dir = TempDir::new().unwrap();
+        let cache = Arc::new(PluginCache::open(dir.path(), "demo", 1024).unwrap());
+
+        let rt = rquickjs::Runtime::new().unwrap();
+        let ctx = rquickjs::Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            crate::plugin::sandbox::PluginErrorApi::inject(&ctx).unwrap();
+            CacheApi::inject(&ctx, cache.clone()).unwrap();
+            ctx.eval::<(), _>(
+                r#"
+                var out = [];
+                __cukCache.put('raw', new Uint8Array([1, 255]))
+                    .then(function() { return __cukCache.put('doc', {a: [1, 2]}); })
+                    .then(function() { return __cukCache.get('raw'); })
+                    .then(function(v) { out.push(v instanceof Uint8Array, Array.prototype.join.call(v)); return __cukCache.get('doc'); })
+                    .then(function(v) { out.push(JSON.stringify(v)); return __cukCache.get('none'); })
+                    .then(function(v) { out.push(v === null); return __cukCache.set('ttl', 1, 60000); })
+                    .then(function() { return __cukCache.has('ttl'); })
+                    .then(function(v) { out.push(v); return __cukCache.put('big', 'x'.repeat(2000)); })
+                    .catch(function(e) { out.push(e.type); });
+                "#,
+            )
+            .unwrap();
+            while ctx.execute_pending_job() {}
+            let result: String = ctx.eval("JSON.stringify(out)").unwrap();
+            assert_eq!(result, r#"[true,"1,255","{\"a\":[1,2]}",true,true,"CACHE_ERROR"]"#);
+        });
+
+        assert_eq!(cache.keys(), vec!["doc".to_string(), "raw".to_string(), "ttl".to_string()]);
+    }
+}
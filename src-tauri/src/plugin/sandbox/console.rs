@@ -1,11 +1,13 @@
 // Console API 实现
 // Phase 2.2.5: 实现 console API
 //
-// 将 JS console 日志桥接到 Rust tracing/log
+// 将 JS console 日志桥接到 Rust tracing/log，并可按插件捕获到日志缓冲区
 // 安全增强：深度限制防止循环引用导致栈溢出
 
 use rquickjs::{prelude::Rest, Ctx, Function, Object, Result as JsResult, Value};
 
+use crate::plugin::plugin_logs::{PluginLogLevel, PluginLogRecorder};
+
 /// 最大字符串化深度（防止循环引用导致栈溢出）
 const MAX_STRINGIFY_DEPTH: usize = 10;
 
@@ -18,67 +20,45 @@ const MAX_ARRAY_ELEMENTS: usize = 100;
 /// Console API
 pub struct ConsoleApi;
 
+/// console 方法及其日志级别
+const CONSOLE_METHODS: [(&str, PluginLogLevel); 6] = [
+    ("log", PluginLogLevel::Info),
+    ("info", PluginLogLevel::Info),
+    ("warn", PluginLogLevel::Warn),
+    ("error", PluginLogLevel::Error),
+    ("debug", PluginLogLevel::Debug),
+    ("trace", PluginLogLevel::Trace),
+];
+
 impl ConsoleApi {
     /// 向上下文注入 console 对象
     pub fn inject(ctx: &Ctx<'_>) -> JsResult<()> {
+        Self::inject_with_recorder(ctx, None)
+    }
+
+    /// 向上下文注入 console 对象，输出同时写入插件日志缓冲区（Phase 6.12）
+    pub fn inject_with_recorder(ctx: &Ctx<'_>, recorder: Option<PluginLogRecorder>) -> JsResult<()> {
         let globals = ctx.globals();
 
         // 创建 console 对象
         let console = Object::new(ctx.clone())?;
 
-        // console.log
-        console.set(
-            "log",
-            Function::new(ctx.clone(), |args: Rest<Value>| {
-                let message = format_args(&args.0);
-                log::info!("[plugin:console] {}", message);
-            })?,
-        )?;
-
-        // console.info
-        console.set(
-            "info",
-            Function::new(ctx.clone(), |args: Rest<Value>| {
-                let message = format_args(&args.0);
-                log::info!("[plugin:console] {}", message);
-            })?,
-        )?;
-
-        // console.warn
-        console.set(
-            "warn",
-            Function::new(ctx.clone(), |args: Rest<Value>| {
-                let message = format_args(&args.0);
-                log::warn!("[plugin:console] {}", message);
-            })?,
-        )?;
-
-        // console.error
-        console.set(
-            "error",
-            Function::new(ctx.clone(), |args: Rest<Value>| {
-                let message = format_args(&args.0);
-                log::error!("[plugin:console] {}", message);
-            })?,
-        )?;
-
-        // console.debug
-        console.set(
-            "debug",
-            Function::new(ctx.clone(), |args: Rest<Value>| {
-                let message = format_args(&args.0);
-                log::debug!("[plugin:console] {}", message);
-            })?,
-        )?;
-
-        // console.trace
-        console.set(
-            "trace",
-            Function::new(ctx.clone(), |args: Rest<Value>| {
-                let message = format_args(&args.0);
-                log::trace!("[plugin:console] {}", message);
-            })?,
-        )?;
+        for (name, level) in CONSOLE_METHODS {
+            let recorder = recorder.clone();
+            console.set(
+                name,
+                Function::new(ctx.clone(), move |args: Rest<Value>| {
+                    let message = format_args(&args.0);
+                    match &recorder {
+                        Some(recorder) => {
+                            log::log!(log_level(level), "[plugin:console] [{}] {}", recorder.plugin_id(), message);
+                            recorder.record(level, message);
+                        }
+                        None => log::log!(log_level(level), "[plugin:console] {}", message),
+                    }
+                })?,
+            )?;
+        }
 
         // 将 console 注入全局对象
         globals.set("console", console)?;
@@ -88,6 +68,17 @@ impl ConsoleApi {
     }
 }
 
+/// 插件日志级别对应的应用日志级别
+fn log_level(level: PluginLogLevel) -> log::Level {
+    match level {
+        PluginLogLevel::Trace => log::Level::Trace,
+        PluginLogLevel::Debug => log::Level::Debug,
+        PluginLogLevel::Info => log::Level::Info,
+        PluginLogLevel::Warn => log::Level::Warn,
+        PluginLogLevel::Error => log::Level::Error,
+    }
+}
+
 /// 格式化参数为字符串（带输出截断保护）
 fn format_args(args: &[Value]) -> String {
     let result: String = args
@@ -2,17 +2,120 @@
 // Phase 2.2.6-2.2.7: TextEncoder/TextDecoder, atob/btoa
 //
 // 使用 rquickjs class 宏实现，解决生命周期问题
-// TextEncoder/TextDecoder 遵循 WHATWG Encoding 标准
+// TextEncoder/TextDecoder 遵循 WHATWG Encoding 标准：
+// - encode 返回 Uint8Array，支持 encodeInto
+// - decode 接受 ArrayBuffer / 任意 ArrayBufferView，支持 fatal / ignoreBOM / stream
+// atob/btoa 遵循 HTML 标准（forgiving-base64，非法字符抛 InvalidCharacterError）
+// Base64 为非标准辅助对象，支持 URL 安全字母表（base64url）
+//
 // 安全增强：输入大小限制防止 Rust 堆分配绕过 QuickJS 内存限制
 
+use std::cell::RefCell;
+
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
 use rquickjs::{
-    class::Trace,
-    Class, Ctx, Exception, Function, Result as JsResult,
+    class::Trace, convert::Coerced, function::Opt, ArrayBuffer, Class, Ctx, Exception, Function,
+    Object, Result as JsResult, TypedArray, Value,
 };
 
 /// 最大编码输入大小: 1MB（防止 Rust 堆分配绕过 QuickJS 内存限制）
 const MAX_ENCODING_INPUT_SIZE: usize = 1024 * 1024;
 
+/// UTF-8 的 WHATWG 标签（其余编码不支持）
+const UTF8_LABELS: &[&str] = &[
+    "unicode-1-1-utf-8",
+    "unicode11utf8",
+    "unicode20utf8",
+    "utf-8",
+    "utf8",
+    "x-unicode20utf8",
+];
+
+/// forgiving-base64 解码引擎：填充可选，忽略末尾多余比特
+const FORGIVING_CONFIG: GeneralPurposeConfig = GeneralPurposeConfig::new()
+    .with_encode_padding(true)
+    .with_decode_allow_trailing_bits(true)
+    .with_decode_padding_mode(DecodePaddingMode::Indifferent);
+const FORGIVING_STANDARD: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, FORGIVING_CONFIG);
+const FORGIVING_URL_SAFE: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, FORGIVING_CONFIG);
+
+/// 输入大小检查
+fn check_input_size(ctx: &Ctx<'_>, len: usize) -> JsResult<()> {
+    if len > MAX_ENCODING_INPUT_SIZE {
+        return Err(Exception::throw_range(
+            ctx,
+            &format!(
+                "Input size {} exceeds maximum of {} bytes",
+                len, MAX_ENCODING_INPUT_SIZE
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// 抛出 InvalidCharacterError（沙盒中没有 DOMException，使用同名 Error）
+fn throw_invalid_character(ctx: &Ctx<'_>, message: &str) -> rquickjs::Error {
+    match Exception::from_message(ctx.clone(), message) {
+        Ok(exception) => {
+            if let Err(e) = exception.as_object().set("name", "InvalidCharacterError") {
+                return e;
+            }
+            exception.throw()
+        }
+        Err(e) => e,
+    }
+}
+
+/// 读取 BufferSource（ArrayBuffer 或 ArrayBufferView）的字节；兼容普通数字数组
+fn buffer_source_bytes<'js>(ctx: &Ctx<'js>, value: &Value<'js>) -> JsResult<Vec<u8>> {
+    if value.is_undefined() {
+        return Ok(Vec::new());
+    }
+    if let Some(buffer) = ArrayBuffer::from_value(value.clone()) {
+        let bytes = buffer
+            .as_bytes()
+            .ok_or_else(|| Exception::throw_type(ctx, "ArrayBuffer is detached"))?;
+        check_input_size(ctx, bytes.len())?;
+        return Ok(bytes.to_vec());
+    }
+    if let Some(object) = value.as_object() {
+        // TypedArray / DataView：按 byteOffset/byteLength 读取底层 ArrayBuffer
+        if let Ok(buffer) = object.get::<_, ArrayBuffer>("buffer") {
+            let offset: usize = object.get("byteOffset")?;
+            let len: usize = object.get("byteLength")?;
+            check_input_size(ctx, len)?;
+            return buffer
+                .as_bytes()
+                .and_then(|bytes| bytes.get(offset..offset + len))
+                .map(<[u8]>::to_vec)
+                .ok_or_else(|| Exception::throw_type(ctx, "ArrayBuffer is detached"));
+        }
+        if let Some(array) = object.as_array() {
+            check_input_size(ctx, array.len())?;
+            return array.iter::<u8>().collect();
+        }
+    }
+    Err(Exception::throw_type(
+        ctx,
+        "The provided value is not of type '(ArrayBuffer or ArrayBufferView)'",
+    ))
+}
+
+/// 读取可选的 options 对象中的布尔字段
+fn option_flag(options: &Opt<Object<'_>>, key: &str) -> JsResult<bool> {
+    match &options.0 {
+        Some(options) => Ok(options.get::<_, Option<Coerced<bool>>>(key)?.is_some_and(|v| v.0)),
+        None => Ok(false),
+    }
+}
+
+// ============================================================================
+// TextEncoder
+// ============================================================================
+
 /// TextEncoder - 将字符串编码为 UTF-8 字节数组
 #[derive(Trace)]
 #[rquickjs::class(rename = "TextEncoder")]
@@ -37,20 +140,108 @@ impl TextEncoder {
     }
 
     /// encode 方法 - 将字符串编码为 Uint8Array（带大小限制）
-    pub fn encode(&self, ctx: Ctx<'_>, input: Option<String>) -> JsResult<Vec<u8>> {
-        let data = input.unwrap_or_default();
-        if data.len() > MAX_ENCODING_INPUT_SIZE {
-            return Err(Exception::throw_range(
-                &ctx,
-                &format!(
-                    "Input size {} exceeds maximum of {} bytes",
-                    data.len(),
-                    MAX_ENCODING_INPUT_SIZE
-                ),
-            ));
+    pub fn encode<'js>(&self, ctx: Ctx<'js>, input: Opt<Coerced<String>>) -> JsResult<TypedArray<'js, u8>> {
+        let data = input.0.map(|s| s.0).unwrap_or_default();
+        check_input_size(&ctx, data.len())?;
+        TypedArray::new(ctx, data.into_bytes())
+    }
+
+    /// encodeInto 方法 - 编码到已有的 Uint8Array，返回 { read, written }
+    ///
+    /// read 为已读取的 UTF-16 码元数，不会写入不完整的字符。
+    #[qjs(rename = "encodeInto")]
+    pub fn encode_into<'js>(
+        &self,
+        ctx: Ctx<'js>,
+        source: Coerced<String>,
+        destination: TypedArray<'js, u8>,
+    ) -> JsResult<Object<'js>> {
+        check_input_size(&ctx, source.0.len())?;
+        let capacity = destination.len();
+        let target = destination.as_object();
+        let (mut read, mut written) = (0usize, 0usize);
+        let mut buf = [0u8; 4];
+        for c in source.0.chars() {
+            let encoded = c.encode_utf8(&mut buf).as_bytes();
+            if written + encoded.len() > capacity {
+                break;
+            }
+            for byte in encoded {
+                target.set(written as u32, *byte)?;
+                written += 1;
+            }
+            read += c.len_utf16();
+        }
+
+        let result = Object::new(ctx)?;
+        result.set("read", read)?;
+        result.set("written", written)?;
+        Ok(result)
+    }
+}
+
+// ============================================================================
+// TextDecoder
+// ============================================================================
+
+/// 流式解码状态
+#[derive(Default)]
+struct DecoderState {
+    /// 上次 stream 调用末尾不完整的字节序列
+    pending: Vec<u8>,
+    /// 当前流是否已输出过内容（BOM 只在流开头处理）
+    started: bool,
+}
+
+/// UTF-8 解码（WHATWG 语义）
+///
+/// `stream` 为 true 时，末尾不完整的字节序列保留到下一次调用；
+/// `fatal` 为 true 时遇到非法序列返回 Err，否则替换为 U+FFFD。
+fn decode_utf8(pending: &mut Vec<u8>, input: &[u8], stream: bool, fatal: bool) -> Result<String, ()> {
+    let buffer = if pending.is_empty() {
+        input.to_vec()
+    } else {
+        let mut buffer = std::mem::take(pending);
+        buffer.extend_from_slice(input);
+        buffer
+    };
+
+    let mut output = String::with_capacity(buffer.len());
+    let mut rest = buffer.as_slice();
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                output.push_str(valid);
+                break;
+            }
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                // valid_up_to 之前的字节已验证为合法 UTF-8
+                output.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                match e.error_len() {
+                    Some(len) => {
+                        if fatal {
+                            return Err(());
+                        }
+                        output.push('\u{FFFD}');
+                        rest = &after[len..];
+                    }
+                    // 末尾是不完整的序列
+                    None => {
+                        if stream {
+                            *pending = after.to_vec();
+                        } else if fatal {
+                            return Err(());
+                        } else {
+                            output.push('\u{FFFD}');
+                        }
+                        break;
+                    }
+                }
+            }
         }
-        Ok(data.into_bytes())
     }
+    Ok(output)
 }
 
 /// TextDecoder - 将字节数组解码为字符串
@@ -61,26 +252,30 @@ pub struct TextDecoder {
     fatal: bool,
     #[qjs(skip_trace)]
     ignore_bom: bool,
+    #[qjs(skip_trace)]
+    state: RefCell<DecoderState>,
 }
 
 #[rquickjs::methods]
 impl TextDecoder {
-    /// 构造函数
+    /// 构造函数: new TextDecoder(label = "utf-8", { fatal, ignoreBOM })
     #[qjs(constructor)]
-    pub fn new(ctx: Ctx<'_>, label: Option<String>) -> JsResult<Self> {
-        let encoding = label.unwrap_or_else(|| "utf-8".to_string()).to_lowercase();
+    pub fn new(ctx: Ctx<'_>, label: Opt<Coerced<String>>, options: Opt<Object<'_>>) -> JsResult<Self> {
+        let label = label.0.map(|s| s.0).unwrap_or_else(|| "utf-8".to_string());
+        let encoding = label.trim_matches(|c: char| c.is_ascii_whitespace()).to_ascii_lowercase();
 
         // 只支持 UTF-8
-        if encoding != "utf-8" && encoding != "utf8" {
-            return Err(Exception::throw_type(
+        if !UTF8_LABELS.contains(&encoding.as_str()) {
+            return Err(Exception::throw_range(
                 &ctx,
-                &format!("Unsupported encoding: {}", encoding),
+                &format!("Unsupported encoding: {}", label),
             ));
         }
 
         Ok(Self {
-            fatal: false,
-            ignore_bom: false,
+            fatal: option_flag(&options, "fatal")?,
+            ignore_bom: option_flag(&options, "ignoreBOM")?,
+            state: RefCell::new(DecoderState::default()),
         })
     }
 
@@ -102,20 +297,87 @@ impl TextDecoder {
         self.ignore_bom
     }
 
-    /// decode 方法 - 将字节数组解码为字符串（带大小限制）
-    pub fn decode(&self, ctx: Ctx<'_>, input: Option<Vec<u8>>) -> JsResult<String> {
-        let bytes = input.unwrap_or_default();
-        if bytes.len() > MAX_ENCODING_INPUT_SIZE {
-            return Err(Exception::throw_range(
-                &ctx,
-                &format!(
-                    "Input size {} exceeds maximum of {} bytes",
-                    bytes.len(),
-                    MAX_ENCODING_INPUT_SIZE
-                ),
-            ));
+    /// decode 方法 - decode(input?, { stream })（带大小限制）
+    pub fn decode<'js>(&self, ctx: Ctx<'js>, input: Opt<Value<'js>>, options: Opt<Object<'js>>) -> JsResult<String> {
+        let bytes = match &input.0 {
+            Some(value) => buffer_source_bytes(&ctx, value)?,
+            None => Vec::new(),
+        };
+        let stream = option_flag(&options, "stream")?;
+
+        let mut state = self.state.borrow_mut();
+        let result = decode_utf8(&mut state.pending, &bytes, stream, self.fatal);
+        let mut output = match result {
+            Ok(output) => output,
+            Err(()) => {
+                *state = DecoderState::default();
+                return Err(Exception::throw_type(&ctx, "The encoded data was not valid utf-8"));
+            }
+        };
+
+        // 流开头的 BOM 默认去除
+        if !state.started && !output.is_empty() {
+            state.started = true;
+            if !self.ignore_bom && output.starts_with('\u{FEFF}') {
+                output.remove(0);
+            }
+        }
+        // 非流式调用结束当前流
+        if !stream {
+            *state = DecoderState::default();
+        }
+        Ok(output)
+    }
+}
+
+// ============================================================================
+// Base64
+// ============================================================================
+
+/// forgiving-base64 解码（HTML 标准 atob 语义）
+fn forgiving_base64_decode(input: &str, url_safe: bool) -> Result<Vec<u8>, String> {
+    let mut data: String = input.chars().filter(|c| !matches!(c, '\t' | '\n' | '\x0C' | '\r' | ' ')).collect();
+    if data.len() % 4 == 0 {
+        let trimmed = data.trim_end_matches('=');
+        if data.len() - trimmed.len() <= 2 {
+            data.truncate(trimmed.len());
         }
-        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+    if data.len() % 4 == 1 {
+        return Err("Invalid base64 length".to_string());
+    }
+    let engine = if url_safe { &FORGIVING_URL_SAFE } else { &FORGIVING_STANDARD };
+    engine.decode(data.as_bytes()).map_err(|e| e.to_string())
+}
+
+/// Base64.encode(input, { urlSafe, padding }) - 字符串按 UTF-8 编码，其余按 BufferSource 处理
+fn base64_encode<'js>(ctx: Ctx<'js>, input: Value<'js>, options: Opt<Object<'js>>) -> JsResult<String> {
+    let bytes = match input.as_string() {
+        Some(s) => s.to_string()?.into_bytes(),
+        None => buffer_source_bytes(&ctx, &input)?,
+    };
+    check_input_size(&ctx, bytes.len())?;
+    let url_safe = option_flag(&options, "urlSafe")?;
+    // base64url 默认不带填充
+    let padding = match &options.0 {
+        Some(options) => options.get::<_, Option<bool>>("padding")?.unwrap_or(!url_safe),
+        None => true,
+    };
+    let alphabet = if url_safe { &alphabet::URL_SAFE } else { &alphabet::STANDARD };
+    let engine = GeneralPurpose::new(alphabet, FORGIVING_CONFIG.with_encode_padding(padding));
+    Ok(engine.encode(&bytes))
+}
+
+/// Base64.decode(text, { urlSafe }) - 返回 Uint8Array
+fn base64_decode<'js>(ctx: Ctx<'js>, encoded: Coerced<String>, options: Opt<Object<'js>>) -> JsResult<TypedArray<'js, u8>> {
+    check_input_size(&ctx, encoded.0.len())?;
+    let url_safe = option_flag(&options, "urlSafe")?;
+    match forgiving_base64_decode(&encoded.0, url_safe) {
+        Ok(bytes) => TypedArray::new(ctx, bytes),
+        Err(e) => Err(throw_invalid_character(
+            &ctx,
+            &format!("The string to be decoded is not correctly encoded: {}", e),
+        )),
     }
 }
 
@@ -136,30 +398,14 @@ impl EncodingApi {
         // 注入 atob 函数 (Base64 解码，带大小限制)
         globals.set(
             "atob",
-            Function::new(ctx.clone(), |ctx: Ctx<'_>, encoded: String| -> JsResult<String> {
-                use base64::Engine;
-
-                // 大小限制检查
-                if encoded.len() > MAX_ENCODING_INPUT_SIZE {
-                    return Err(Exception::throw_range(
+            Function::new(ctx.clone(), |ctx: Ctx<'_>, encoded: Coerced<String>| -> JsResult<String> {
+                check_input_size(&ctx, encoded.0.len())?;
+                match forgiving_base64_decode(&encoded.0, false) {
+                    // atob 返回 Latin1 字符串
+                    Ok(bytes) => Ok(bytes.iter().map(|&b| b as char).collect()),
+                    Err(e) => Err(throw_invalid_character(
                         &ctx,
-                        &format!(
-                            "Input size {} exceeds maximum of {} bytes",
-                            encoded.len(),
-                            MAX_ENCODING_INPUT_SIZE
-                        ),
-                    ));
-                }
-
-                match base64::engine::general_purpose::STANDARD.decode(&encoded) {
-                    Ok(bytes) => {
-                        // atob 返回 Latin1 字符串
-                        let result: String = bytes.iter().map(|&b| b as char).collect();
-                        Ok(result)
-                    }
-                    Err(e) => Err(Exception::throw_type(
-                        &ctx,
-                        &format!("Invalid base64 string: {}", e),
+                        &format!("The string to be decoded is not correctly encoded: {}", e),
                     )),
                 }
             })?,
@@ -168,36 +414,31 @@ impl EncodingApi {
         // 注入 btoa 函数 (Base64 编码，带大小限制)
         globals.set(
             "btoa",
-            Function::new(ctx.clone(), |ctx: Ctx<'_>, data: String| -> JsResult<String> {
-                use base64::Engine;
-
-                // 大小限制检查
-                if data.len() > MAX_ENCODING_INPUT_SIZE {
-                    return Err(Exception::throw_range(
-                        &ctx,
-                        &format!(
-                            "Input size {} exceeds maximum of {} bytes",
-                            data.len(),
-                            MAX_ENCODING_INPUT_SIZE
-                        ),
-                    ));
-                }
+            Function::new(ctx.clone(), |ctx: Ctx<'_>, data: Coerced<String>| -> JsResult<String> {
+                check_input_size(&ctx, data.0.len())?;
 
                 // btoa 只接受 Latin1 范围内的字符
-                for c in data.chars() {
-                    if c as u32 > 255 {
-                        return Err(Exception::throw_type(
-                            &ctx,
-                            "The string contains characters outside of the Latin1 range",
-                        ));
-                    }
+                let bytes: Option<Vec<u8>> = data.0.chars().map(|c| u8::try_from(c).ok()).collect();
+                match bytes {
+                    Some(bytes) => Ok(FORGIVING_STANDARD.encode(&bytes)),
+                    None => Err(throw_invalid_character(
+                        &ctx,
+                        "The string to be encoded contains characters outside of the Latin1 range",
+                    )),
                 }
-
-                let bytes: Vec<u8> = data.chars().map(|c| c as u8).collect();
-                Ok(base64::engine::general_purpose::STANDARD.encode(&bytes))
             })?,
         )?;
 
+        // 注入 Base64 辅助对象（字节 <-> base64/base64url）
+        let base64 = Object::new(ctx.clone())?;
+        base64.set("encode", Function::new(ctx.clone(), base64_encode)?)?;
+        base64.set("decode", Function::new(ctx.clone(), base64_decode)?)?;
+        // 调用 Object.freeze 冻结，防止插件篡改
+        let object_ctor: Object = globals.get("Object")?;
+        let freeze_fn: Function = object_ctor.get("freeze")?;
+        let frozen_base64: Value = freeze_fn.call((base64,))?;
+        globals.set("Base64", frozen_base64)?;
+
         log::debug!("Encoding API 已注入");
         Ok(())
     }
@@ -205,26 +446,75 @@ impl EncodingApi {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_btoa_atob_roundtrip() {
         let input = "Hello, World!";
         let bytes: Vec<u8> = input.chars().map(|c| c as u8).collect();
 
-        use base64::Engine;
-        let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
-        let decoded_bytes = base64::engine::general_purpose::STANDARD
-            .decode(&encoded)
-            .unwrap();
+        let encoded = FORGIVING_STANDARD.encode(&bytes);
+        let decoded_bytes = forgiving_base64_decode(&encoded, false).unwrap();
         let decoded: String = decoded_bytes.iter().map(|&b| b as char).collect();
 
         assert_eq!(decoded, input);
+
+        // forgiving-base64：忽略空白、填充可选
+        assert_eq!(forgiving_base64_decode(" SGk\n", false).unwrap(), b"Hi");
+        assert_eq!(forgiving_base64_decode("SGk=", false).unwrap(), b"Hi");
+        assert!(forgiving_base64_decode("SGk==", false).is_err());
+        assert!(forgiving_base64_decode("S", false).is_err());
+        assert_eq!(forgiving_base64_decode("-_8", true).unwrap(), vec![0xfb, 0xff]);
     }
 
     #[test]
     fn test_utf8_encoding() {
         let input = "你好世界";
         let bytes = input.as_bytes();
-        let decoded = String::from_utf8_lossy(bytes);
-        assert_eq!(decoded, input);
+
+        // 流式解码：多字节字符跨调用拆分
+        let mut pending = Vec::new();
+        let mut output = decode_utf8(&mut pending, &bytes[..4], true, true).unwrap();
+        assert_eq!(pending.len(), 1);
+        output.push_str(&decode_utf8(&mut pending, &bytes[4..], false, true).unwrap());
+        assert_eq!(output, input);
+
+        // 非法序列：替换或报错
+        assert_eq!(decode_utf8(&mut Vec::new(), b"a\xffb", false, false).unwrap(), "a\u{FFFD}b");
+        assert!(decode_utf8(&mut Vec::new(), b"a\xffb", false, true).is_err());
+        assert_eq!(decode_utf8(&mut Vec::new(), &bytes[..4], false, false).unwrap(), "你\u{FFFD}");
+    }
+
+    #[test]
+    fn test_encoding_js_api() {
+        let rt = rquickjs::Runtime::new().unwrap();
+        let ctx = rquickjs::Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            EncodingApi::inject(&ctx).unwrap();
+            let result: String = ctx
+                .eval(
+                    r#"
+                    var out = [];
+                    var bytes = new TextEncoder().encode('hé');
+                    out.push(bytes instanceof Uint8Array, bytes.length);
+                    var dest = new Uint8Array(3);
+                    var r = new TextEncoder().encodeInto('你a', dest);
+                    out.push(r.read, r.written);
+                    var view = new DataView(new Uint8Array([0xef, 0xbb, 0xbf, 0x68, 0x69]).buffer, 0);
+                    out.push(new TextDecoder().decode(view), new TextDecoder('utf-8', { ignoreBOM: true }).decode(view).length);
+                    try { new TextDecoder('utf-8', { fatal: true }).decode(new Uint8Array([0xff])); } catch (e) { out.push(e.name); }
+                    try { new TextDecoder('latin1'); } catch (e) { out.push(e.name); }
+                    try { atob('*'); } catch (e) { out.push(e.name); }
+                    out.push(atob(btoa('ÿ')) === 'ÿ');
+                    out.push(Base64.encode(new Uint8Array([0xfb, 0xff]), { urlSafe: true }), Base64.decode('-_8', { urlSafe: true }).length);
+                    JSON.stringify(out);
+                    "#,
+                )
+                .unwrap();
+            assert_eq!(
+                result,
+                r#"[true,3,1,3,"hi",3,"TypeError","RangeError","InvalidCharacterError",true,"-_8",2]"#
+            );
+        });
     }
 }
@@ -98,6 +98,8 @@ impl PluginErrorApi {
         error_types.set("STORAGE_LIMIT", "STORAGE_LIMIT")?;
         error_types.set("CACHE_ERROR", "CACHE_ERROR")?;
         error_types.set("INCOMPATIBLE_API_VERSION", "INCOMPATIBLE_API_VERSION")?;
+        error_types.set("INPUT_CANCELLED", "INPUT_CANCELLED")?;
+        error_types.set("INPUT_UNAVAILABLE", "INPUT_UNAVAILABLE")?;
         error_types.set("UNKNOWN", "UNKNOWN")?;
 
         globals.set("PluginErrorType", error_types)?;
@@ -3,20 +3,33 @@
 //
 // 提供给 JS 插件使用的安全 HTTP 请求功能
 // 包含 URL 模式拦截、DNS 级别拦截、响应大小限制
+// Phase 2.2.5: GET 请求按 ETag/Last-Modified 发送条件请求，304 时返回缓存的响应体
 
 use std::convert::TryFrom;
 use std::fmt;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 
 use std::collections::HashMap;
 
 use futures::StreamExt;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::plugin::gateway::{reject_gateway_reference, GatewayProfile};
+use crate::plugin::network_stats::NetworkRecorder;
+use crate::plugin::permission::{PermissionChecker, PermissionError};
+use crate::plugin::proxy::ProxyConfig;
+use crate::plugin::tls::TlsConfig;
+use crate::plugin::sandbox::http_cache::{has_conditional_header, HttpCache, Validators};
+use crate::plugin::types::PluginErrorType;
+use crate::reliability::retry::{RetryConfig, RetryExecutor, RetryableErrorWrapper};
+use crate::reliability::RateLimiter;
 use rquickjs::{
-    class::Trace, function::Opt, Class, Ctx, FromJs, Function, IntoJs, Object,
-    Result as JsResult, Value,
+    class::Trace, function::Opt, prelude::Async, Class, Ctx, Exception, FromJs, Function, IntoJs,
+    Object, Result as JsResult, Symbol, Value,
 };
 
 // ============================================================================
@@ -32,6 +45,71 @@ pub struct FetchOptions {
     pub headers: HashMap<String, String>,
     /// 请求体
     pub body: Option<String>,
+    /// 失败重试（可选，仅幂等请求）
+    pub retry: Option<FetchRetryOptions>,
+    /// URL 由 `{{gateway.base}}` 展开且指向网关主机（宿主设置，JS 无法指定）
+    pub(crate) via_gateway: bool,
+}
+
+/// Fetch 重试选项: `retry: { attempts, backoffMs }`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchRetryOptions {
+    /// 总尝试次数（含首次，1..=5）
+    pub attempts: u32,
+    /// 首次重试前的退避时间 (ms)，之后按 2 倍递增
+    pub backoff_ms: u64,
+}
+
+/// 最大尝试次数（重试总耗时仍计入单次 fetch 的 30 秒超时）
+const MAX_RETRY_ATTEMPTS: u32 = 5;
+
+/// 退避时间范围 (ms)
+const MIN_RETRY_BACKOFF_MS: u64 = 50;
+const MAX_RETRY_BACKOFF_MS: u64 = 10_000;
+
+impl Default for FetchRetryOptions {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            backoff_ms: 200,
+        }
+    }
+}
+
+impl FetchRetryOptions {
+    /// 转换为宿主重试执行器配置（超出范围的值被截断）
+    pub fn to_retry_config(self) -> RetryConfig {
+        let backoff_ms = self.backoff_ms.clamp(MIN_RETRY_BACKOFF_MS, MAX_RETRY_BACKOFF_MS);
+        RetryConfig {
+            max_retries: self.attempts.clamp(1, MAX_RETRY_ATTEMPTS) - 1,
+            initial_delay: Duration::from_millis(backoff_ms),
+            max_delay: Duration::from_millis(MAX_RETRY_BACKOFF_MS),
+            multiplier: 2.0,
+            ..Default::default()
+        }
+    }
+}
+
+impl FetchOptions {
+    /// 检查重试选项是否允许
+    ///
+    /// 只有 GET 请求默认可重试；其他方法必须携带 `Idempotency-Key` 请求头，
+    /// 避免重复提交产生副作用。
+    pub fn check_retry(&self) -> Result<(), FetchError> {
+        if self.retry.is_none() {
+            return Ok(());
+        }
+        let method = self.method.as_deref().unwrap_or("GET");
+        let has_idempotency_key = self
+            .headers
+            .iter()
+            .any(|(name, value)| name.eq_ignore_ascii_case("idempotency-key") && !value.trim().is_empty());
+        if method.eq_ignore_ascii_case("GET") || has_idempotency_key {
+            Ok(())
+        } else {
+            Err(FetchError::RetryNotAllowed(method.to_uppercase()))
+        }
+    }
 }
 
 impl<'js> FromJs<'js> for FetchOptions {
@@ -62,10 +140,24 @@ impl<'js> FromJs<'js> for FetchOptions {
         // 解析 body
         let body: Option<String> = obj.get("body").ok();
 
+        // 解析 retry: { attempts, backoffMs }
+        let mut retry = None;
+        if let Ok(retry_val) = obj.get::<_, Value>("retry") {
+            if let Ok(retry_obj) = Object::from_value(retry_val) {
+                let defaults = FetchRetryOptions::default();
+                retry = Some(FetchRetryOptions {
+                    attempts: retry_obj.get("attempts").unwrap_or(defaults.attempts),
+                    backoff_ms: retry_obj.get("backoffMs").unwrap_or(defaults.backoff_ms),
+                });
+            }
+        }
+
         Ok(Self {
             method,
             headers,
             body,
+            retry,
+            via_gateway: false,
         })
     }
 }
@@ -83,6 +175,8 @@ pub enum FetchError {
     DnsError(String),
     /// 并发请求数超限
     TooManyRequests,
+    /// 超出插件每分钟请求预算（manifest maxRequestsPerMinute）
+    RateLimited(String),
     /// 响应体过大
     ResponseTooLarge { size: usize, max: usize },
     /// Content-Length 超出平台限制
@@ -93,6 +187,18 @@ pub enum FetchError {
     ReadError(String),
     /// HTTP 客户端未初始化（创建失败）
     ClientNotInitialized,
+    /// 主机不在插件声明的 allowedHosts 中
+    HostNotAllowed(String),
+    /// 主机不在管理员策略的 networkAllowlist 中
+    HostBlockedByPolicy(String),
+    /// 主机不在插件声明的 fetch:<host> 权限中
+    HostNotPermitted(String),
+    /// 网关引用无法解析
+    Gateway(String),
+    /// 非幂等请求要求重试（需要 Idempotency-Key）
+    RetryNotAllowed(String),
+    /// 服务器证书与设置中固定的指纹不匹配（握手失败，请求未发送）
+    CertificatePinMismatch(String),
 }
 
 impl fmt::Display for FetchError {
@@ -101,6 +207,7 @@ impl fmt::Display for FetchError {
             Self::InvalidUrl(msg) => write!(f, "Invalid URL: {}", msg),
             Self::DnsError(msg) => write!(f, "DNS error: {}", msg),
             Self::TooManyRequests => write!(f, "Too many concurrent requests"),
+            Self::RateLimited(msg) => write!(f, "Request budget exceeded: {}", msg),
             Self::ResponseTooLarge { size, max } => {
                 write!(f, "Response size {} exceeds maximum of {} bytes", size, max)
             }
@@ -110,12 +217,35 @@ impl fmt::Display for FetchError {
             Self::NetworkError(msg) => write!(f, "Network error: {}", msg),
             Self::ReadError(msg) => write!(f, "Read error: {}", msg),
             Self::ClientNotInitialized => write!(f, "HTTP client not initialized (creation failed)"),
+            Self::HostNotAllowed(host) => write!(f, "Host not allowed by manifest: {}", host),
+            Self::HostBlockedByPolicy(host) => write!(f, "Host blocked by admin policy: {}", host),
+            Self::HostNotPermitted(host) => write!(f, "Host not permitted by fetch permissions: {}", host),
+            Self::Gateway(msg) => write!(f, "Gateway error: {}", msg),
+            Self::RetryNotAllowed(method) => write!(
+                f,
+                "Retry is only allowed for GET or requests with an Idempotency-Key header (got {})",
+                method
+            ),
+            Self::CertificatePinMismatch(host) => {
+                write!(f, "Certificate for {} does not match the pinned fingerprint", host)
+            }
         }
     }
 }
 
 impl std::error::Error for FetchError {}
 
+impl FetchError {
+    /// 对应的插件错误类型（决定是否可重试）
+    pub fn error_type(&self) -> PluginErrorType {
+        match self {
+            Self::NetworkError(_) | Self::ReadError(_) => PluginErrorType::NetworkError,
+            Self::TooManyRequests | Self::RateLimited(_) => PluginErrorType::RateLimit,
+            _ => PluginErrorType::Unknown,
+        }
+    }
+}
+
 /// 最大响应大小: 10MB
 const MAX_RESPONSE_SIZE: usize = 10 * 1024 * 1024;
 
@@ -142,6 +272,8 @@ pub struct FetchResult {
     status: u16,
     #[qjs(skip_trace)]
     body: String,
+    #[qjs(skip_trace)]
+    from_cache: bool,
 }
 
 #[rquickjs::methods]
@@ -155,9 +287,16 @@ impl FetchResult {
             ok,
             status,
             body,
+            from_cache: false,
         }
     }
 
+    /// fromCache 属性（服务器返回 304，响应体来自条件请求缓存）
+    #[qjs(get, rename = "fromCache")]
+    pub fn from_cache(&self) -> bool {
+        self.from_cache
+    }
+
     /// url 属性
     #[qjs(get)]
     pub fn url(&self) -> String {
@@ -193,6 +332,16 @@ impl FetchResult {
     }
 }
 
+impl FetchResult {
+    /// 用条件请求缓存的响应体合成的 200 响应
+    fn cached(url: String, method: String, body: String) -> Self {
+        Self {
+            from_cache: true,
+            ..Self::new(url, method, true, 200, body)
+        }
+    }
+}
+
 // ============================================================================
 // 异步返回的数据结构（用于 Async<T> Promise 转换）
 // ============================================================================
@@ -207,6 +356,7 @@ struct FetchResultData {
     ok: bool,
     status: u16,
     body: String,
+    from_cache: bool,
 }
 
 impl<'js> IntoJs<'js> for FetchResultData {
@@ -218,6 +368,7 @@ impl<'js> IntoJs<'js> for FetchResultData {
         obj.set("method", self.method.clone())?;
         obj.set("ok", self.ok)?;
         obj.set("status", self.status)?;
+        obj.set("fromCache", self.from_cache)?;
         obj.set("_body", self.body.clone())?;
 
         // text() 方法
@@ -289,6 +440,208 @@ fn serde_json_to_js<'js>(ctx: &Ctx<'js>, value: &serde_json::Value) -> JsResult<
     }
 }
 
+// ============================================================================
+// 流式响应 (fetchStream)
+// ============================================================================
+
+/// 流式响应通道容量（块数）
+///
+/// 通道满时读取任务暂停读取，JS 端消费速度决定下载速度（背压）。
+const STREAM_CHANNEL_CAPACITY: usize = 8;
+
+/// 读取任务发往 JS 端的流事件
+#[derive(Debug)]
+enum StreamEvent {
+    /// 响应头已到达
+    Head { ok: bool, status: u16 },
+    /// 响应体数据块（已解码为 UTF-8）
+    Chunk(String),
+    /// 请求或读取失败
+    Error(String),
+}
+
+/// 增量 UTF-8 解码器
+///
+/// 网络分块可能切断多字节字符，不完整的尾部字节保留到下一块再解码。
+#[derive(Debug, Default)]
+struct Utf8ChunkDecoder {
+    pending: Vec<u8>,
+}
+
+impl Utf8ChunkDecoder {
+    /// 追加字节，返回可完整解码的文本
+    fn push(&mut self, bytes: &[u8]) -> String {
+        self.pending.extend_from_slice(bytes);
+
+        let valid_up_to = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // 仅尾部不完整：保留尾部等待后续字节
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            // 中间存在非法序列：整体按 lossy 解码
+            Err(_) => self.pending.len(),
+        };
+
+        let rest = self.pending.split_off(valid_up_to);
+        let text = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending = rest;
+        text
+    }
+
+    /// 结束解码，输出剩余字节
+    fn finish(&mut self) -> String {
+        let text = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending.clear();
+        text
+    }
+}
+
+/// JS 端持有的流接收端
+///
+/// 截止时间从请求开始计算，覆盖整个流的生命周期（累计超时）。
+/// 接收端被丢弃后，读取任务发送失败并立即停止读取。
+/// 使用异步锁：并发的 next() 按调用顺序依次取块。
+struct StreamReceiver {
+    rx: tokio::sync::Mutex<Option<mpsc::Receiver<StreamEvent>>>,
+    deadline: Instant,
+}
+
+impl StreamReceiver {
+    fn new(rx: Option<mpsc::Receiver<StreamEvent>>, deadline: Instant) -> Self {
+        Self {
+            rx: tokio::sync::Mutex::new(rx),
+            deadline,
+        }
+    }
+
+    /// 等待下一个数据块
+    ///
+    /// - `Ok(Some(chunk))`: 数据块
+    /// - `Ok(None)`: 流已结束
+    /// - `Err(msg)`: 读取失败或超时（流随之关闭）
+    async fn next_chunk(&self) -> Result<Option<String>, String> {
+        let mut guard = self.rx.lock().await;
+        let Some(rx) = guard.as_mut() else {
+            return Ok(None);
+        };
+
+        loop {
+            match tokio::time::timeout_at(self.deadline, rx.recv()).await {
+                Ok(Some(StreamEvent::Chunk(chunk))) => return Ok(Some(chunk)),
+                Ok(Some(StreamEvent::Head { .. })) => continue,
+                Ok(Some(StreamEvent::Error(msg))) => {
+                    *guard = None;
+                    return Err(msg);
+                }
+                Ok(None) => {
+                    *guard = None;
+                    return Ok(None);
+                }
+                Err(_) => {
+                    *guard = None;
+                    return Err("Stream timeout".to_string());
+                }
+            }
+        }
+    }
+
+    /// 关闭流（丢弃接收端，通知读取任务停止）
+    async fn close(&self) {
+        *self.rx.lock().await = None;
+    }
+}
+
+/// 迭代器单步结果（`{ value, done }`）
+struct StreamStep(Option<String>);
+
+impl<'js> IntoJs<'js> for StreamStep {
+    fn into_js(self, ctx: &Ctx<'js>) -> JsResult<Value<'js>> {
+        let result = Object::new(ctx.clone())?;
+        result.set("done", self.0.is_none())?;
+        match self.0 {
+            Some(chunk) => result.set("value", chunk)?,
+            None => result.set("value", Value::new_undefined(ctx.clone()))?,
+        }
+        Ok(result.into_value())
+    }
+}
+
+/// fetchStream 返回给 JS 的数据结构
+///
+/// fetchStream 返回 Promise（响应头到达后 resolve），结果为异步迭代器：
+/// ```js
+/// const stream = await fetchStream(url, { method: 'POST', body });
+/// if (!stream.ok) throw new Error(stream.error);
+/// for await (const chunk of stream) { ... }
+/// ```
+struct FetchStreamData {
+    url: String,
+    method: String,
+    ok: bool,
+    status: u16,
+    /// 请求失败原因（响应头到达前失败时）
+    error: Option<String>,
+    receiver: Arc<StreamReceiver>,
+}
+
+impl<'js> IntoJs<'js> for FetchStreamData {
+    fn into_js(self, ctx: &Ctx<'js>) -> JsResult<Value<'js>> {
+        let obj = Object::new(ctx.clone())?;
+
+        obj.set("url", self.url)?;
+        obj.set("method", self.method)?;
+        obj.set("ok", self.ok)?;
+        obj.set("status", self.status)?;
+        if let Some(error) = self.error {
+            obj.set("error", error)?;
+        }
+
+        // next() - 返回 Promise<{ value, done }>，读取失败或超时时 reject
+        let receiver = self.receiver.clone();
+        obj.set(
+            "next",
+            Function::new(
+                ctx.clone(),
+                Async(move |ctx: Ctx<'js>| {
+                    let receiver = receiver.clone();
+                    async move {
+                        match receiver.next_chunk().await {
+                            Ok(chunk) => Ok(StreamStep(chunk)),
+                            Err(msg) => Err(Exception::throw_message(&ctx, &format!("Stream error: {}", msg))),
+                        }
+                    }
+                }),
+            )?,
+        )?;
+
+        // return() - for await 中途 break 时调用，提前关闭流
+        let receiver = self.receiver;
+        obj.set(
+            "return",
+            Function::new(
+                ctx.clone(),
+                Async(move || {
+                    let receiver = receiver.clone();
+                    async move {
+                        receiver.close().await;
+                        StreamStep(None)
+                    }
+                }),
+            )?,
+        )?;
+
+        // [Symbol.asyncIterator]() - 返回自身
+        obj.set(
+            Symbol::async_iterator(ctx.clone()),
+            Function::new(
+                ctx.clone(),
+                |this: rquickjs::function::This<Object<'js>>| -> Object<'js> { this.0 },
+            )?,
+        )?;
+
+        Ok(obj.into_value())
+    }
+}
+
 /// Fetch API
 pub struct FetchApi;
 
@@ -309,8 +662,8 @@ impl FetchApi {
     /// 使用 `Async<Fn>` 包装器：
     /// - 闭包返回 Future，rquickjs 自动转换为 JS Promise
     /// - Future 完成时 Promise resolve/reject
-    /// - 需要 runtime 正确处理 pending jobs（调用 idle()）
-    pub fn inject(ctx: &Ctx<'_>, manager: Arc<RequestManager>) -> JsResult<()> {
+    /// - 由 `PluginExecutor` 在等待插件结果时驱动（`SandboxRuntime::settle_result`）
+    pub fn inject<'js>(ctx: &Ctx<'js>, manager: Arc<RequestManager>) -> JsResult<()> {
         let globals = ctx.globals();
 
         // 注册 FetchResult 类（保留用于类型文档和向后兼容）
@@ -319,97 +672,298 @@ impl FetchApi {
         // 克隆 manager 用于闭包捕获
         let manager_for_fetch = manager.clone();
 
-        // 注入同步 fetch 函数
-        // 使用新线程 + channel 阻塞执行，避免 Promise 链问题
+        // 注入异步 fetch 函数：返回 Promise，请求在沙盒运行时的异步任务中执行，
+        // 等待期间不阻塞 JS 线程，由执行器驱动至完成（受插件执行超时限制）
         globals.set(
             "fetch",
             Function::new(
                 ctx.clone(),
-                move |url: String, options: Opt<FetchOptions>| {
-                    // 克隆 Arc 用于请求
+                Async(move |url: String, options: Opt<FetchOptions>| {
                     let manager = manager_for_fetch.clone();
-                    let url_owned = url;
                     let opts = options.0.unwrap_or_default();
+                    async move { Self::fetch_async(manager, url, opts).await }
+                }),
+            )?,
+        )?;
 
-                    // 1. URL 安全检查（同步）
-                    if let Err(e) = UrlSecurityChecker::check_url(&url_owned) {
-                        log::warn!("Fetch API URL 检查失败: {} -> {}", url_owned, e);
-                        return FetchResultData {
-                            url: url_owned,
-                            method: opts.method.clone().unwrap_or_else(|| "GET".to_string()),
-                            ok: false,
-                            status: 0,
-                            body: format!("URL validation failed: {}", e),
-                        };
-                    }
-
-                    let method = opts.method.clone().unwrap_or_else(|| "GET".to_string());
-                    log::debug!("Fetch API 开始同步请求: {} {}", method, url_owned);
-
-                    // 2. 使用新线程 + channel 同步执行异步 fetch
-                    let (tx, rx) = std::sync::mpsc::channel();
-                    let url_for_thread = url_owned.clone();
-                    let opts_for_thread = opts.clone();
-
-                    std::thread::spawn(move || {
-                        // 在新线程中创建 tokio runtime 执行异步请求
-                        let rt = tokio::runtime::Builder::new_current_thread()
-                            .enable_all()
-                            .build()
-                            .expect("Failed to create tokio runtime");
-
-                        let result = rt.block_on(async {
-                            Self::secure_fetch_with_options(&manager, &url_for_thread, &opts_for_thread).await
-                        });
-
-                        let _ = tx.send(result);
-                    });
-
-                    // 等待结果（30 秒超时）
-                    match rx.recv_timeout(std::time::Duration::from_secs(30)) {
-                        Ok(Ok(result)) => {
-                            log::debug!(
-                                "Fetch API 请求成功: {} -> status {}",
-                                url_owned,
-                                result.status
-                            );
-                            FetchResultData {
-                                url: result.url,
-                                method: result.method,
-                                ok: result.ok,
-                                status: result.status,
-                                body: result.body,
-                            }
-                        }
-                        Ok(Err(e)) => {
-                            log::warn!("Fetch API 请求失败: {} -> {}", url_owned, e);
-                            FetchResultData {
-                                url: url_owned,
-                                method,
-                                ok: false,
-                                status: 0,
-                                body: format!("Fetch error: {}", e),
-                            }
-                        }
-                        Err(_) => {
-                            log::warn!("Fetch API 请求超时: {}", url_owned);
-                            FetchResultData {
-                                url: url_owned,
-                                method,
-                                ok: false,
-                                status: 0,
-                                body: "Fetch timeout".to_string(),
-                            }
-                        }
-                    }
-                },
+        // 注入 fetchStream 函数：返回 Promise，响应头到达后 resolve 为异步迭代器，
+        // 响应体由沙盒运行时中的读取任务逐块转发，与 fetch 一样由执行器驱动
+        let manager_for_stream = manager.clone();
+        globals.set(
+            "fetchStream",
+            Function::new(
+                ctx.clone(),
+                Async(move |ctx: Ctx<'js>, url: String, options: Opt<FetchOptions>| {
+                    let manager = manager_for_stream.clone();
+                    let opts = options.0.unwrap_or_default();
+                    async move { Self::open_stream(&ctx, manager, url, opts).await }
+                }),
             )?,
         )?;
 
-        log::debug!("Fetch API 已注入（同步阻塞模式）");
+        log::debug!("Fetch API 已注入");
         Ok(())
     }
 
+    /// 执行一次 fetch 请求
+    ///
+    /// 网关或 URL 检查失败、请求失败和超时都返回 ok=false 的结果（body 为错误描述），不抛出异常
+    async fn fetch_async(manager: Arc<RequestManager>, url: String, mut opts: FetchOptions) -> FetchResultData {
+        let method = opts.method.clone().unwrap_or_else(|| "GET".to_string());
+        let failed = |url: String, method: String, body: String| FetchResultData {
+            url,
+            method,
+            ok: false,
+            status: 0,
+            body,
+            from_cache: false,
+        };
+
+        // 0. 展开网关引用（{{gateway.base}} / {{gateway.auth}}）
+        let url = match manager.apply_gateway(&url, &mut opts) {
+            Ok(expanded) => expanded,
+            Err(e) => {
+                log::warn!("Fetch API 网关引用解析失败: {} -> {}", url, e);
+                return failed(url, method, format!("URL validation failed: {}", e));
+            }
+        };
+
+        // 1. URL 安全检查
+        if let Err(e) = manager.check_request_url(&url, &opts) {
+            log::warn!("Fetch API URL 检查失败: {} -> {}", url, e);
+            return failed(url, method, format!("URL validation failed: {}", e));
+        }
+
+        // 1.1 重试只允许幂等请求
+        if let Err(e) = opts.check_retry() {
+            log::warn!("Fetch API 重试选项被拒绝: {} {} -> {}", method, url, e);
+            return failed(url, method, format!("Fetch error: {}", e));
+        }
+        log::debug!("Fetch API 开始请求: {} {}", method, url);
+
+        // 2. 执行请求
+        match tokio::time::timeout(DEFAULT_TIMEOUT, Self::secure_fetch_with_retry(&manager, &url, &opts)).await {
+            Ok(Ok(result)) => {
+                log::debug!("Fetch API 请求成功: {} -> status {}", url, result.status);
+                FetchResultData {
+                    url: result.url,
+                    method: result.method,
+                    ok: result.ok,
+                    status: result.status,
+                    body: result.body,
+                    from_cache: result.from_cache,
+                }
+            }
+            Ok(Err(e)) => {
+                log::warn!("Fetch API 请求失败: {} -> {}", url, e);
+                failed(url, method, format!("Fetch error: {}", e))
+            }
+            Err(_) => {
+                log::warn!("Fetch API 请求超时: {}", url);
+                failed(url, method, "Fetch timeout".to_string())
+            }
+        }
+    }
+
+    /// 打开流式请求
+    ///
+    /// 在沙盒运行时中派生读取任务，通过有界通道逐块发送响应体，
+    /// 响应头到达后返回。大小与超时限制对整个流累计生效。
+    async fn open_stream<'js>(
+        ctx: &Ctx<'js>,
+        manager: Arc<RequestManager>,
+        url: String,
+        mut opts: FetchOptions,
+    ) -> FetchStreamData {
+        let method = opts.method.clone().unwrap_or_else(|| "GET".to_string());
+        let deadline = Instant::now() + DEFAULT_TIMEOUT;
+        let (tx, mut rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        let failed = |url: String, method: String, error: String| FetchStreamData {
+            url,
+            method,
+            ok: false,
+            status: 0,
+            error: Some(error),
+            receiver: Arc::new(StreamReceiver::new(None, deadline)),
+        };
+
+        // 0. 展开网关引用
+        let url = match manager.apply_gateway(&url, &mut opts) {
+            Ok(expanded) => expanded,
+            Err(e) => {
+                log::warn!("fetchStream 网关引用解析失败: {} -> {}", url, e);
+                return failed(url, method, format!("URL validation failed: {}", e));
+            }
+        };
+
+        // 1. URL 安全检查
+        if let Err(e) = manager.check_request_url(&url, &opts) {
+            log::warn!("fetchStream URL 检查失败: {} -> {}", url, e);
+            return failed(url, method, format!("URL validation failed: {}", e));
+        }
+
+        log::debug!("fetchStream 开始请求: {} {}", method, url);
+
+        // 2. 读取任务在沙盒运行时中执行（与插件的其他异步任务一起被驱动）
+        let url_for_task = url.clone();
+        ctx.spawn(async move {
+            if let Err(e) = Self::stream_with_options(&manager, &url_for_task, &opts, &tx).await {
+                let _ = tx.send(StreamEvent::Error(e.to_string())).await;
+            }
+        });
+
+        // 3. 等待响应头
+        match tokio::time::timeout_at(deadline, rx.recv()).await {
+            Ok(Some(StreamEvent::Head { ok, status })) => FetchStreamData {
+                url,
+                method,
+                ok,
+                status,
+                error: None,
+                receiver: Arc::new(StreamReceiver::new(Some(rx), deadline)),
+            },
+            Ok(Some(StreamEvent::Error(e))) => {
+                log::warn!("fetchStream 请求失败: {} -> {}", url, e);
+                failed(url, method, format!("Fetch error: {}", e))
+            }
+            Ok(Some(StreamEvent::Chunk(_))) | Ok(None) => {
+                failed(url, method, "Stream closed before response head".to_string())
+            }
+            Err(_) => {
+                log::warn!("fetchStream 请求超时: {}", url);
+                failed(url, method, "Fetch timeout".to_string())
+            }
+        }
+    }
+
+    /// 流式 fetch 实现
+    ///
+    /// 与 `secure_fetch_with_options` 相同的安全检查（URL、DNS 固定、并发槽位），
+    /// 响应体按块发送到通道。接收端关闭时立即停止读取并释放槽位。
+    async fn stream_with_options(
+        manager: &RequestManager,
+        url_str: &str,
+        options: &FetchOptions,
+        tx: &mpsc::Sender<StreamEvent>,
+    ) -> Result<(), FetchError> {
+        let parsed_url = manager.check_request_url(url_str, options)?;
+        manager.check_budget().await?;
+        let _guard = RequestGuard::acquire(manager)?;
+        let resolved_ip = manager.check_resolved(&parsed_url).await?;
+        let max_size = manager.max_response_size();
+
+        let result = match Self::build_request(manager, &parsed_url, resolved_ip, options)?.send().await {
+            Ok(response) => Self::forward_stream(response, max_size, tx).await,
+            Err(e) => Err(manager.send_error(&parsed_url, &e)),
+        };
+        manager.record_request(&parsed_url, options, result.as_ref().ok().copied());
+        result.map(|_| ())
+    }
+
+    /// 将响应体按块转发到通道，返回已读取的字节数
+    async fn forward_stream(
+        response: reqwest::Response,
+        max_size: usize,
+        tx: &mpsc::Sender<StreamEvent>,
+    ) -> Result<usize, FetchError> {
+        // 检查 Content-Length
+        if let Some(content_length) = response.content_length() {
+            let len_usize = usize::try_from(content_length)
+                .map_err(|_| FetchError::ContentLengthOverflow(content_length))?;
+
+            if len_usize > max_size {
+                return Err(FetchError::ResponseTooLarge {
+                    size: len_usize,
+                    max: max_size,
+                });
+            }
+        }
+
+        let head = StreamEvent::Head {
+            ok: response.status().is_success(),
+            status: response.status().as_u16(),
+        };
+        if tx.send(head).await.is_err() {
+            return Ok(0);
+        }
+
+        // 流式读取，累计大小超限立即终止
+        let mut stream = response.bytes_stream();
+        let mut decoder = Utf8ChunkDecoder::default();
+        let mut total_size: usize = 0;
+
+        while let Some(chunk_result) = stream.next().await {
+            let chunk = chunk_result.map_err(|e| FetchError::ReadError(e.to_string()))?;
+
+            total_size = total_size.checked_add(chunk.len()).ok_or(FetchError::ResponseTooLarge {
+                size: usize::MAX,
+                max: max_size,
+            })?;
+
+            if total_size > max_size {
+                return Err(FetchError::ResponseTooLarge {
+                    size: total_size,
+                    max: max_size,
+                });
+            }
+
+            let text = decoder.push(&chunk);
+            if !text.is_empty() && tx.send(StreamEvent::Chunk(text)).await.is_err() {
+                // JS 端已关闭流
+                return Ok(total_size);
+            }
+        }
+
+        let rest = decoder.finish();
+        if !rest.is_empty() {
+            let _ = tx.send(StreamEvent::Chunk(rest)).await;
+        }
+
+        Ok(total_size)
+    }
+
+    /// 构建使用预解析 IP 的请求（禁用 redirect；未配置代理时禁用 proxy）
+    ///
+    /// 配置了用户代理（Phase 4.5）时经代理发送，目标主机由代理解析，不固定 IP；
+    /// 配置了自定义根证书（Phase 4.6）时额外信任这些证书
+    fn build_request(
+        manager: &RequestManager,
+        parsed_url: &url::Url,
+        resolved_addr: Option<std::net::SocketAddr>,
+        options: &FetchOptions,
+    ) -> Result<reqwest::RequestBuilder, FetchError> {
+        let host = parsed_url.host_str().unwrap_or_default();
+
+        // 取出共享客户端（按 TLS/代理配置复用连接池），未经代理时登记已验证的 IP
+        let pooled = PooledClient::get(manager.tls.as_ref(), manager.proxy())?;
+        if manager.proxy().is_none() {
+            let addr = resolved_addr
+                .ok_or_else(|| FetchError::DnsError("No resolved IP address available".to_string()))?;
+            pooled.pin(host, addr);
+        }
+        let client = pooled.client;
+
+        // 构建请求
+        let method_str = options.method.as_deref().unwrap_or("GET").to_uppercase();
+        let method = reqwest::Method::from_bytes(method_str.as_bytes())
+            .unwrap_or(reqwest::Method::GET);
+
+        let mut request = client.request(method, parsed_url.as_str());
+
+        // 添加请求头
+        for (key, value) in &options.headers {
+            request = request.header(key.as_str(), value.as_str());
+        }
+
+        // 添加请求体
+        if let Some(body) = &options.body {
+            request = request.body(body.clone());
+        }
+
+        Ok(request)
+    }
+
     /// 安全的异步 fetch 实现（带 DNS rebinding 防护）
     /// 这是供 Rust 层使用的安全 API，JS 层的 fetch 最终应调用此方法
     ///
@@ -436,14 +990,17 @@ impl FetchApi {
         url_str: &str,
     ) -> Result<FetchResult, FetchError> {
         // 1. URL 模式检查（同步，快速失败）
-        let parsed_url = UrlSecurityChecker::check_url(url_str)?;
+        let parsed_url = manager.check_url(url_str)?;
 
-        // 2. 使用 RAII 守卫获取请求槽位（在 DNS 解析前！）
+        // 2. 插件请求预算检查（被限流的请求不占用槽位、不发起 DNS 解析）
+        manager.check_budget().await?;
+
+        // 3. 使用 RAII 守卫获取请求槽位（在 DNS 解析前！）
         // 这样可以限制 DNS 阶段的并发数，防止 DNS DoS 攻击
         // Guard 在作用域结束或异步取消时自动释放槽位
         let _guard = RequestGuard::acquire(manager)?;
 
-        // 3. DNS 解析后检查（防止 DNS rebinding 攻击）
+        // 4. DNS 解析后检查（防止 DNS rebinding 攻击）
         // 返回预解析的 IP 用于后续 resolve API，消除 TOCTOU 窗口
         // 注意：此阶段也受并发限制保护
         let resolved_ip = UrlSecurityChecker::check_resolved_ip(&parsed_url).await?;
@@ -474,74 +1031,122 @@ impl FetchApi {
         options: &FetchOptions,
     ) -> Result<FetchResult, FetchError> {
         // 1. URL 模式检查（同步，快速失败）
-        let parsed_url = UrlSecurityChecker::check_url(url_str)?;
+        let parsed_url = manager.check_request_url(url_str, options)?;
+
+        // 2. 插件请求预算检查
+        manager.check_budget().await?;
 
-        // 2. 使用 RAII 守卫获取请求槽位
+        // 3. 使用 RAII 守卫获取请求槽位
         let _guard = RequestGuard::acquire(manager)?;
 
-        // 3. DNS 解析后检查
-        let resolved_ip = UrlSecurityChecker::check_resolved_ip(&parsed_url).await?;
+        // 4. DNS 解析后检查（经代理时检查代理地址）
+        let resolved_ip = manager.check_resolved(&parsed_url).await?;
 
-        // 4. 执行实际的 fetch 请求
+        // 5. 条件请求：已缓存的 GET 附加 If-None-Match / If-Modified-Since
         let method = options.method.clone().unwrap_or_else(|| "GET".to_string());
-        let (ok, status, body) = Self::do_fetch_with_options(
-            &parsed_url,
-            resolved_ip,
-            options,
-            manager.max_response_size(),
-        )
-        .await?;
+        let http_cache = manager.http_cache_for(&method, options);
+        let conditional;
+        let options = match http_cache.and_then(|scope| scope.cache.validators(&scope.plugin_id, url_str)) {
+            Some(validators) => {
+                let mut with_validators = options.clone();
+                for (name, value) in validators.conditional_headers() {
+                    with_validators.headers.insert(name.to_string(), value);
+                }
+                conditional = with_validators;
+                &conditional
+            }
+            None => options,
+        };
+
+        // 6. 执行实际的 fetch 请求
+        let result = Self::do_fetch_with_options(manager, &parsed_url, resolved_ip, options).await;
+        manager.record_request(&parsed_url, options, result.as_ref().ok().map(|(_, _, body, _)| body.len()));
+        let (ok, status, body, validators) = result?;
+
+        // 7. 304 时返回缓存的响应体，2xx 时更新缓存
+        if let Some(scope) = http_cache {
+            if status == 304 {
+                if let Some(cached) = scope.cache.cached_body(&scope.plugin_id, url_str) {
+                    log::debug!("[{}] 响应未修改，使用缓存: {}", scope.plugin_id, url_str);
+                    return Ok(FetchResult::cached(url_str.to_string(), method, cached));
+                }
+            } else if ok {
+                scope.cache.store(&scope.plugin_id, url_str, validators, &body);
+            }
+        }
 
         Ok(FetchResult::new(url_str.to_string(), method, ok, status, body))
     }
 
-    /// 使用 options 和预解析 IP 的 fetch 实现
+    /// 带重试的安全 fetch 实现
+    ///
+    /// 未设置 `retry` 时等同于 `secure_fetch_with_options`。设置后使用宿主的 RetryExecutor
+    /// 指数退避重试：网络错误和可重试状态码（408/429/5xx）触发重试，其余结果立即返回。
+    /// 重试耗尽时返回最后一次的响应（或错误），插件仍可读取最终状态码。
+    pub async fn secure_fetch_with_retry(
+        manager: &RequestManager,
+        url_str: &str,
+        options: &FetchOptions,
+    ) -> Result<FetchResult, FetchError> {
+        let Some(retry) = options.retry else {
+            return Self::secure_fetch_with_options(manager, url_str, options).await;
+        };
+        options.check_retry()?;
+
+        let executor = RetryExecutor::new(retry.to_retry_config())
+            .map_err(|e| FetchError::NetworkError(format!("Invalid retry options: {}", e)))?;
+        let last_failure: Mutex<Option<Result<FetchResult, FetchError>>> = Mutex::new(None);
+        let last_failure_ref = &last_failure;
+
+        let outcome = executor
+            .execute(|| async move {
+                let result = Self::secure_fetch_with_options(manager, url_str, options).await;
+                let (error_type, message) = match &result {
+                    Ok(response) => (
+                        PluginErrorType::from_http_status(response.status),
+                        format!("HTTP {}", response.status),
+                    ),
+                    Err(e) => (e.error_type(), e.to_string()),
+                };
+                match result {
+                    // 成功响应和不可重试的非 2xx 响应直接交给插件处理
+                    Ok(response) if response.ok || !error_type.is_retryable() => Ok(response),
+                    result => {
+                        *last_failure_ref.lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+                        Err(RetryableErrorWrapper { error_type, message })
+                    }
+                }
+            })
+            .await;
+
+        match outcome {
+            Ok(response) => Ok(response),
+            Err(e) => last_failure
+                .into_inner()
+                .unwrap_or_else(|e| e.into_inner())
+                .unwrap_or_else(|| Err(FetchError::NetworkError(e.to_string()))),
+        }
+    }
+
+    /// 使用 options 和预解析 IP 的 fetch 实现（同时返回响应的缓存验证器）
     async fn do_fetch_with_options(
+        manager: &RequestManager,
         parsed_url: &url::Url,
         resolved_addr: Option<std::net::SocketAddr>,
         options: &FetchOptions,
-        max_size: usize,
-    ) -> Result<(bool, u16, String), FetchError> {
-        let host = parsed_url.host_str().unwrap_or_default();
-
-        let addr = resolved_addr
-            .ok_or_else(|| FetchError::DnsError("No resolved IP address available".to_string()))?;
-
-        // 创建 Client
-        let client = reqwest::Client::builder()
-            .timeout(DEFAULT_TIMEOUT)
-            .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-            .redirect(reqwest::redirect::Policy::none())
-            .no_proxy()
-            .resolve(host, addr)
-            .build()
-            .map_err(|e| FetchError::NetworkError(format!("Failed to create client: {}", e)))?;
-
-        // 构建请求
-        let method_str = options.method.as_deref().unwrap_or("GET").to_uppercase();
-        let method = reqwest::Method::from_bytes(method_str.as_bytes())
-            .unwrap_or(reqwest::Method::GET);
-
-        let mut request = client.request(method, parsed_url.as_str());
-
-        // 添加请求头
-        for (key, value) in &options.headers {
-            request = request.header(key.as_str(), value.as_str());
-        }
-
-        // 添加请求体
-        if let Some(body) = &options.body {
-            request = request.body(body.clone());
-        }
+    ) -> Result<(bool, u16, String, Validators), FetchError> {
+        let max_size = manager.max_response_size();
+        let request = Self::build_request(manager, parsed_url, resolved_addr, options)?;
 
         // 发送请求
         let response = request
             .send()
             .await
-            .map_err(|e| FetchError::NetworkError(e.to_string()))?;
+            .map_err(|e| manager.send_error(parsed_url, &e))?;
 
         let status = response.status().as_u16();
         let ok = response.status().is_success();
+        let validators = Validators::from_headers(response.headers());
 
         // 检查 Content-Length
         if let Some(content_length) = response.content_length() {
@@ -582,7 +1187,7 @@ impl FetchApi {
         }
 
         let body = String::from_utf8_lossy(&body_bytes).into_owned();
-        Ok((ok, status, body))
+        Ok((ok, status, body, validators))
     }
 
     /// 使用预解析 IP 的 fetch 实现，消除 DNS TOCTOU 窗口
@@ -603,18 +1208,13 @@ impl FetchApi {
             FetchError::DnsError("No resolved IP address available".to_string())
         })?;
 
-        // 创建使用 resolve API 的临时 Client
+        // 共享客户端的解析器只返回登记过的地址
         // 这确保 reqwest 使用我们验证过的 IP，而不是重新解析 DNS
-        let client = reqwest::Client::builder()
-            .timeout(DEFAULT_TIMEOUT)
-            .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-            .redirect(reqwest::redirect::Policy::none())
-            .no_proxy()
-            .resolve(host, addr)
-            .build()
-            .map_err(|e| FetchError::NetworkError(format!("Failed to create client: {}", e)))?;
+        let pooled = PooledClient::get(None, None)?;
+        pooled.pin(host, addr);
 
-        let response = client
+        let response = pooled
+            .client
             .get(parsed_url.as_str())
             .send()
             .await
@@ -733,6 +1333,127 @@ impl FetchApi {
     }
 }
 
+// ============================================================================
+// 共享 HTTP 客户端
+// ============================================================================
+
+/// 最多缓存的客户端数（TLS/代理配置变更后，最早的客户端被淘汰）
+const MAX_POOLED_CLIENTS: usize = 4;
+
+/// 只返回已验证地址的 DNS 解析器
+///
+/// 发送请求前通过 `pin` 登记 SSRF 检查通过的 IP，解析时直接返回，
+/// 从不查询系统 DNS；未登记的主机解析失败，共享客户端也不会在检查后重新解析。
+#[derive(Debug, Default)]
+struct PinnedResolver {
+    pins: Mutex<HashMap<String, SocketAddr>>,
+}
+
+impl PinnedResolver {
+    /// 登记主机的已验证地址（覆盖旧地址）
+    fn pin(&self, host: &str, addr: SocketAddr) {
+        self.pins
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(host.to_ascii_lowercase(), addr);
+    }
+
+    fn lookup(&self, host: &str) -> Option<SocketAddr> {
+        self.pins
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&host.to_ascii_lowercase())
+            .copied()
+    }
+}
+
+impl reqwest::dns::Resolve for PinnedResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let result = self
+            .lookup(name.as_str())
+            .ok_or_else(|| format!("host not pinned: {}", name.as_str()));
+        Box::pin(async move {
+            let addr = result?;
+            Ok(Box::new(std::iter::once(addr)) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// 插件请求共享的 HTTP 客户端
+///
+/// 按 (TLS 配置, 代理) 缓存，相同配置的请求复用同一连接池。
+/// 未经代理时 DNS 固定由客户端的 `PinnedResolver` 按请求完成；
+/// 经代理时目标主机由代理解析，不固定 IP。
+#[derive(Clone)]
+struct PooledClient {
+    tls: Option<Arc<TlsConfig>>,
+    proxy: Option<ProxyConfig>,
+    client: reqwest::Client,
+    resolver: Arc<PinnedResolver>,
+}
+
+impl PooledClient {
+    /// 取出与配置对应的共享客户端（不存在时创建）
+    fn get(tls: Option<&Arc<TlsConfig>>, proxy: Option<&ProxyConfig>) -> Result<Self, FetchError> {
+        static POOL: OnceLock<Mutex<Vec<PooledClient>>> = OnceLock::new();
+        let mut pool = POOL
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        let same_tls = |other: Option<&Arc<TlsConfig>>| match (other, tls) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        if let Some(entry) = pool
+            .iter()
+            .find(|entry| same_tls(entry.tls.as_ref()) && entry.proxy.as_ref() == proxy)
+        {
+            return Ok(entry.clone());
+        }
+
+        let entry = Self::build(tls, proxy)?;
+        if pool.len() >= MAX_POOLED_CLIENTS {
+            pool.remove(0);
+        }
+        pool.push(entry.clone());
+        Ok(entry)
+    }
+
+    /// 创建客户端（禁用 redirect；未配置代理时禁用 proxy 并使用固定 IP 的解析器）
+    fn build(tls: Option<&Arc<TlsConfig>>, proxy: Option<&ProxyConfig>) -> Result<Self, FetchError> {
+        let resolver = Arc::new(PinnedResolver::default());
+        let builder = reqwest::Client::builder()
+            .timeout(DEFAULT_TIMEOUT)
+            .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+            .redirect(reqwest::redirect::Policy::none());
+        let builder = match tls {
+            Some(tls) => tls.apply(builder),
+            None => builder,
+        };
+        let builder = match proxy {
+            Some(proxy) => builder.proxy(proxy.to_reqwest()?),
+            None => builder.no_proxy().dns_resolver(resolver.clone()),
+        };
+        let client = builder
+            .build()
+            .map_err(|e| FetchError::NetworkError(format!("Failed to create client: {}", e)))?;
+
+        Ok(Self {
+            tls: tls.cloned(),
+            proxy: proxy.cloned(),
+            client,
+            resolver,
+        })
+    }
+
+    /// 登记本次请求的已验证地址
+    fn pin(&self, host: &str, addr: SocketAddr) {
+        self.resolver.pin(host, addr);
+    }
+}
+
 // ============================================================================
 // Request Manager
 // ============================================================================
@@ -745,6 +1466,45 @@ pub struct RequestManager {
     client: Option<reqwest::Client>,
     /// 活跃请求数（原子计数器，无锁操作）
     active_requests: AtomicUsize,
+    /// 最大并发请求数（按插件信任级别收紧，不超过 MAX_CONCURRENT_REQUESTS）
+    max_concurrent: usize,
+    /// 插件声明的主机白名单（None 表示不限制公网主机）
+    allowed_hosts: Option<Vec<String>>,
+    /// 管理员策略的主机白名单（None 表示未限制）
+    policy_hosts: Option<Vec<String>>,
+    /// 插件使用的宿主网关（None 表示未配置，网关引用将被拒绝）
+    gateway: Option<GatewayProfile>,
+    /// 网络访问记录器（None 表示不记录）
+    recorder: Option<NetworkRecorder>,
+    /// 插件请求预算（None 表示不限制）
+    budget: Option<RequestBudget>,
+    /// 主机级 fetch 权限检查（None 表示不检查）
+    fetch_scope: Option<FetchScope>,
+    /// 条件请求缓存（None 表示不发送条件请求）
+    http_cache: Option<HttpCacheScope>,
+    /// 用户配置的代理（None 表示直连）
+    proxy: Option<ProxyConfig>,
+    /// 自定义根证书与证书固定（None 表示只使用系统根证书）
+    tls: Option<Arc<TlsConfig>>,
+}
+
+/// 按插件隔离的条件请求缓存
+struct HttpCacheScope {
+    cache: Arc<HttpCache>,
+    plugin_id: String,
+}
+
+/// 按插件的 fetch:<host> 权限检查每次请求
+struct FetchScope {
+    checker: Arc<PermissionChecker>,
+    plugin_id: String,
+}
+
+/// 插件每分钟请求预算
+struct RequestBudget {
+    limiter: Arc<RateLimiter>,
+    plugin_id: String,
+    max_per_minute: u32,
 }
 
 impl RequestManager {
@@ -756,7 +1516,20 @@ impl RequestManager {
     /// - 禁用 proxy：防止通过 HTTP_PROXY 环境变量绕过 SSRF 检查
     /// - 设置超时：防止请求无限挂起
     pub fn new() -> Result<Self, reqwest::Error> {
-        let client = reqwest::Client::builder()
+        Ok(Self::from_client(Self::build_client()?))
+    }
+
+    /// 创建使用共享 HTTP 客户端的请求管理器
+    ///
+    /// 基础客户端取自共享客户端缓存（无 TLS/代理配置的条目），插件每次执行不再新建客户端；
+    /// 实际请求同样从缓存按 TLS/代理配置取客户端，复用连接池（见 `PooledClient`）
+    pub fn shared() -> Result<Self, FetchError> {
+        Ok(Self::from_client(PooledClient::get(None, None)?.client))
+    }
+
+    /// 构建安全配置的 HTTP 客户端
+    fn build_client() -> Result<reqwest::Client, reqwest::Error> {
+        reqwest::Client::builder()
             .timeout(DEFAULT_TIMEOUT)
             .user_agent("CUK-Plugin/1.0")
             // 禁用 redirect，防止 DNS rebinding 绕过
@@ -765,12 +1538,24 @@ impl RequestManager {
             // 禁用 proxy，防止通过 HTTP_PROXY/HTTPS_PROXY 环境变量绕过 SSRF 检查
             // 攻击者可能设置 proxy 指向本地，从而访问内网资源
             .no_proxy()
-            .build()?;
+            .build()
+    }
 
-        Ok(Self {
+    fn from_client(client: reqwest::Client) -> Self {
+        Self {
             client: Some(client),
             active_requests: AtomicUsize::new(0),
-        })
+            max_concurrent: MAX_CONCURRENT_REQUESTS,
+            allowed_hosts: None,
+            policy_hosts: None,
+            gateway: None,
+            recorder: None,
+            budget: None,
+            fetch_scope: None,
+            http_cache: None,
+            proxy: None,
+            tls: None,
+        }
     }
 
     /// 创建请求管理器，失败时使用最小化安全配置
@@ -804,6 +1589,16 @@ impl RequestManager {
                     Self {
                         client: Some(client),
                         active_requests: AtomicUsize::new(0),
+                        max_concurrent: MAX_CONCURRENT_REQUESTS,
+                        allowed_hosts: None,
+                        policy_hosts: None,
+                        gateway: None,
+                        recorder: None,
+                        budget: None,
+                        fetch_scope: None,
+                        http_cache: None,
+                        proxy: None,
+                        tls: None,
                     }
                 }
                 Err(e2) => {
@@ -817,12 +1612,215 @@ impl RequestManager {
                     Self {
                         client: None,
                         active_requests: AtomicUsize::new(0),
+                        max_concurrent: MAX_CONCURRENT_REQUESTS,
+                        allowed_hosts: None,
+                        policy_hosts: None,
+                        gateway: None,
+                        recorder: None,
+                        budget: None,
+                        fetch_scope: None,
+                        http_cache: None,
+                        proxy: None,
+                        tls: None,
                     }
                 }
             }
         })
     }
 
+    /// 设置主机白名单（来自 manifest 的 allowedHosts）
+    pub fn with_allowed_hosts(mut self, allowed_hosts: Option<Vec<String>>) -> Self {
+        self.allowed_hosts = allowed_hosts;
+        self
+    }
+
+    /// 设置管理员策略的主机白名单（policy.json 的 networkAllowlist）
+    ///
+    /// 与 manifest allowedHosts 同时生效，网关主机同样受其限制
+    pub fn with_policy_hosts(mut self, policy_hosts: Option<Vec<String>>) -> Self {
+        self.policy_hosts = policy_hosts;
+        self
+    }
+
+    /// 设置宿主网关
+    ///
+    /// 网关主机只对 `{{gateway.base}}` 展开的请求放行；`mapped` 为 true（插件在网关配置中
+    /// 显式映射）时网关主机加入白名单（需在 `with_allowed_hosts` 之后调用）。
+    pub fn with_gateway(mut self, gateway: Option<GatewayProfile>, mapped: bool) -> Self {
+        if let (true, Some(hosts), Some(host)) = (
+            mapped,
+            self.allowed_hosts.as_mut(),
+            gateway.as_ref().and_then(|g| g.host()),
+        ) {
+            if !hosts.contains(&host) {
+                hosts.push(host);
+            }
+        }
+        self.gateway = gateway;
+        self
+    }
+
+    /// 设置网络访问记录器（隐私报告统计）
+    pub fn with_recorder(mut self, recorder: Option<NetworkRecorder>) -> Self {
+        self.recorder = recorder;
+        self
+    }
+
+    /// 设置主机级 fetch 权限检查（每次请求前由 PermissionChecker 判定）
+    pub fn with_permission_checker(mut self, checker: Arc<PermissionChecker>, plugin_id: &str) -> Self {
+        self.fetch_scope = Some(FetchScope {
+            checker,
+            plugin_id: plugin_id.to_string(),
+        });
+        self
+    }
+
+    /// 设置条件请求缓存（Phase 2.2.5）
+    pub fn with_http_cache(mut self, cache: Arc<HttpCache>, plugin_id: &str) -> Self {
+        self.http_cache = Some(HttpCacheScope {
+            cache,
+            plugin_id: plugin_id.to_string(),
+        });
+        self
+    }
+
+    /// 可使用条件请求缓存的请求：无请求体的 GET，且插件未自行设置条件请求头
+    fn http_cache_for(&self, method: &str, options: &FetchOptions) -> Option<&HttpCacheScope> {
+        self.http_cache.as_ref().filter(|_| {
+            method.eq_ignore_ascii_case("GET") && options.body.is_none() && !has_conditional_header(&options.headers)
+        })
+    }
+
+    /// 设置用户代理（Phase 4.5，None 表示直连）
+    pub fn with_proxy(mut self, proxy: Option<ProxyConfig>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// 使用的代理
+    pub fn proxy(&self) -> Option<&ProxyConfig> {
+        self.proxy.as_ref()
+    }
+
+    /// 设置自定义根证书与证书固定（Phase 4.6）
+    pub fn with_tls(mut self, tls: Option<Arc<TlsConfig>>) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// 转换请求发送错误（握手阶段证书固定不匹配时返回 CertificatePinMismatch）
+    fn send_error(&self, url: &url::Url, error: &reqwest::Error) -> FetchError {
+        match (&self.tls, url.host_str()) {
+            (Some(tls), Some(host)) => tls.send_error(host, error),
+            _ => FetchError::NetworkError(error.to_string()),
+        }
+    }
+
+    /// DNS 解析后检查，返回用于固定 IP 的地址
+    ///
+    /// 经代理时目标主机由代理解析，只检查代理地址本身的解析结果，返回 None
+    async fn check_resolved(&self, url: &url::Url) -> Result<Option<std::net::SocketAddr>, FetchError> {
+        match &self.proxy {
+            Some(proxy) => proxy.check_resolved().await.map(|_| None),
+            None => UrlSecurityChecker::check_resolved_ip(url).await,
+        }
+    }
+
+    /// 设置最大并发请求数（插件信任级别，Phase 5A.10）
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = max_concurrent.clamp(1, MAX_CONCURRENT_REQUESTS);
+        self
+    }
+
+    /// 设置插件每分钟请求预算（manifest maxRequestsPerMinute）
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>, plugin_id: &str, max_per_minute: u32) -> Self {
+        self.budget = Some(RequestBudget {
+            limiter,
+            plugin_id: plugin_id.to_string(),
+            max_per_minute,
+        });
+        self
+    }
+
+    /// 检查插件请求预算，超出时返回 `FetchError::RateLimited`
+    ///
+    /// 被限流的请求计入限流统计，通过插件健康状态的 throttledRequests 暴露给监控。
+    pub async fn check_budget(&self) -> Result<(), FetchError> {
+        let Some(budget) = &self.budget else {
+            return Ok(());
+        };
+        budget
+            .limiter
+            .check_per_minute(&budget.plugin_id, budget.max_per_minute)
+            .await
+            .map_err(|e| {
+                log::warn!(
+                    "[{}] 请求被限流 (maxRequestsPerMinute={}): {}",
+                    budget.plugin_id, budget.max_per_minute, e
+                );
+                FetchError::RateLimited(e.to_string())
+            })
+    }
+
+    /// 记录一次已发出的请求（`received` 为响应体字节数，未收到响应时为 None）
+    fn record_request(&self, url: &url::Url, options: &FetchOptions, received: Option<usize>) {
+        if let (Some(recorder), Some(host)) = (&self.recorder, url.host_str()) {
+            recorder.record(host, &options.headers, options.body.as_deref(), received);
+        }
+    }
+
+    /// 展开请求中的网关引用，返回实际 URL
+    pub fn apply_gateway(&self, url: &str, options: &mut FetchOptions) -> Result<String, FetchError> {
+        let result = match &self.gateway {
+            Some(gateway) => gateway.apply(url, options),
+            None => reject_gateway_reference(url, options).map(|_| url.to_string()),
+        };
+        result.map_err(|e| FetchError::Gateway(e.to_string()))
+    }
+
+    /// 获取主机白名单
+    pub fn allowed_hosts(&self) -> Option<&[String]> {
+        self.allowed_hosts.as_deref()
+    }
+
+    /// 检查 URL 安全性，并依次校验插件白名单、管理员策略白名单和 fetch:<host> 权限
+    pub fn check_url(&self, url_str: &str) -> Result<url::Url, FetchError> {
+        self.check_url_with_gateway(url_str, false)
+    }
+
+    /// 检查插件请求的 URL（网关请求额外允许网关主机，其余检查不变）
+    pub fn check_request_url(&self, url_str: &str, options: &FetchOptions) -> Result<url::Url, FetchError> {
+        self.check_url_with_gateway(url_str, options.via_gateway)
+    }
+
+    fn check_url_with_gateway(&self, url_str: &str, via_gateway: bool) -> Result<url::Url, FetchError> {
+        let gateway_host = self.gateway.as_ref().filter(|_| via_gateway).and_then(|g| g.host());
+        let parsed = match (self.allowed_hosts(), gateway_host) {
+            (Some(hosts), Some(host)) => {
+                let mut hosts = hosts.to_vec();
+                hosts.push(host);
+                UrlSecurityChecker::check_url_with_allowlist(url_str, Some(&hosts))?
+            }
+            (hosts, _) => UrlSecurityChecker::check_url_with_allowlist(url_str, hosts)?,
+        };
+        let host = parsed.host_str().unwrap_or_default();
+        if let Some(patterns) = &self.policy_hosts {
+            if !UrlSecurityChecker::is_host_allowed(host, patterns) {
+                return Err(FetchError::HostBlockedByPolicy(host.to_string()));
+            }
+        }
+        if let Some(scope) = &self.fetch_scope {
+            scope
+                .checker
+                .check_fetch_host_sync(&scope.plugin_id, host)
+                .map_err(|e| match e {
+                    PermissionError::FetchNotPermitted { .. } => FetchError::HostNotPermitted(host.to_string()),
+                    other => FetchError::NetworkError(other.to_string()),
+                })?;
+        }
+        Ok(parsed)
+    }
+
     /// 检查客户端是否可用
     pub fn is_available(&self) -> bool {
         self.client.is_some()
@@ -854,7 +1852,7 @@ impl RequestManager {
     pub fn start_request(&self) -> Result<(), FetchError> {
         loop {
             let current = self.active_requests.load(Ordering::Acquire);
-            if current >= MAX_CONCURRENT_REQUESTS {
+            if current >= self.max_concurrent {
                 return Err(FetchError::TooManyRequests);
             }
             // CAS：如果当前值仍是 current，则加 1
@@ -1085,6 +2083,69 @@ impl UrlSecurityChecker {
         Ok(parsed)
     }
 
+    /// 检查 URL 是否安全，并校验主机是否在插件白名单内
+    ///
+    /// `allowed_hosts` 为 None 时仅执行 SSRF 检查（兼容未声明 allowedHosts 的插件）。
+    pub fn check_url_with_allowlist(
+        url_str: &str,
+        allowed_hosts: Option<&[String]>,
+    ) -> Result<url::Url, FetchError> {
+        let parsed = Self::check_url(url_str)?;
+
+        if let Some(patterns) = allowed_hosts {
+            let host = parsed.host_str().unwrap_or_default();
+            if !Self::is_host_allowed(host, patterns) {
+                return Err(FetchError::HostNotAllowed(host.to_string()));
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// 检查主机是否匹配白名单
+    ///
+    /// 支持两种模式（不区分大小写）：
+    /// - 精确匹配：`api.anthropic.com`
+    /// - 通配子域名：`*.anthropic.com` 匹配 `api.anthropic.com`、`a.b.anthropic.com`，
+    ///   不匹配 `anthropic.com` 本身
+    ///
+    /// 无效模式（如 `*`、`*.com`）永远不匹配。
+    pub fn is_host_allowed(host: &str, patterns: &[String]) -> bool {
+        let host = host.trim_end_matches('.').to_lowercase();
+        if host.is_empty() {
+            return false;
+        }
+
+        patterns.iter().any(|pattern| {
+            let pattern = pattern.trim().trim_end_matches('.').to_lowercase();
+            if !Self::is_valid_host_pattern(&pattern) {
+                log::warn!("忽略无效的 allowedHosts 模式: {}", pattern);
+                return false;
+            }
+
+            match pattern.strip_prefix("*.") {
+                Some(suffix) => host
+                    .strip_suffix(suffix)
+                    .is_some_and(|prefix| prefix.len() > 1 && prefix.ends_with('.')),
+                None => host == pattern,
+            }
+        })
+    }
+
+    /// 检查白名单模式是否有效
+    ///
+    /// 通配符只能出现在最左侧且必须覆盖至少二级域名，防止 `*` / `*.com` 这类等同于不限制的模式。
+    pub fn is_valid_host_pattern(pattern: &str) -> bool {
+        let base = pattern.strip_prefix("*.").unwrap_or(pattern);
+        !base.is_empty()
+            && !base.contains('*')
+            && !base.starts_with('.')
+            && base
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == ':' || c == '[' || c == ']')
+            && (pattern == base || base.contains('.'))
+    }
+
     /// 检查 IP 是否为非公网地址（完整的 IPv4/IPv6 私网/特殊地址检测）
     ///
     /// 注意：此函数等效于 `!IpAddr::is_global()`，但 `is_global()` 目前仅在 nightly Rust 中可用。
@@ -1260,4 +2321,231 @@ mod tests {
             8, 8, 8, 8
         ))));
     }
+
+    #[test]
+    fn test_utf8_chunk_decoder_split_char() {
+        let bytes = "额度".as_bytes();
+        let mut decoder = Utf8ChunkDecoder::default();
+
+        // 在多字节字符中间切分
+        assert_eq!(decoder.push(&bytes[..2]), "");
+        assert_eq!(decoder.push(&bytes[2..4]), "额");
+        assert_eq!(decoder.push(&bytes[4..]), "度");
+        assert_eq!(decoder.finish(), "");
+    }
+
+    #[test]
+    fn test_utf8_chunk_decoder_finish_flushes_tail() {
+        let mut decoder = Utf8ChunkDecoder::default();
+        assert_eq!(decoder.push(b"ok\n\xE9"), "ok\n");
+        assert_eq!(decoder.finish(), "\u{FFFD}");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_stream_blocked_url() {
+        let runtime = rquickjs::AsyncRuntime::new().unwrap();
+        let context = rquickjs::AsyncContext::full(&runtime).await.unwrap();
+
+        let result: Vec<bool> = rquickjs::async_with!(context => |ctx| {
+            let manager = Arc::new(RequestManager::new().unwrap());
+            FetchApi::inject(&ctx, manager).unwrap();
+
+            let promise: rquickjs::Promise = ctx
+                .eval(
+                    r#"
+                    (async () => {
+                        const pending = fetchStream('http://localhost/events');
+                        const s = await pending;
+                        const next = s.next();
+                        const step = await next;
+                        return [
+                            pending instanceof Promise,
+                            s.ok === false,
+                            s.status === 0,
+                            s.error.startsWith('URL validation failed'),
+                            s[Symbol.asyncIterator]() === s,
+                            next instanceof Promise,
+                            step.done === true,
+                            (await s.return()).done === true,
+                        ];
+                    })()
+                    "#,
+                )
+                .unwrap();
+            promise.into_future::<Vec<bool>>().await.unwrap()
+        })
+        .await;
+        assert!(result.iter().all(|v| *v), "{:?}", result);
+    }
+
+    #[tokio::test]
+    async fn test_stream_receiver_chunks_errors_and_deadline() {
+        // 数据块按顺序返回，发送端关闭后流结束
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let receiver = StreamReceiver::new(Some(rx), Instant::now() + Duration::from_secs(5));
+        tx.send(StreamEvent::Head { ok: true, status: 200 }).await.unwrap();
+        tx.send(StreamEvent::Chunk("a".to_string())).await.unwrap();
+        tx.send(StreamEvent::Chunk("b".to_string())).await.unwrap();
+        drop(tx);
+        assert_eq!(receiver.next_chunk().await, Ok(Some("a".to_string())));
+        assert_eq!(receiver.next_chunk().await, Ok(Some("b".to_string())));
+        assert_eq!(receiver.next_chunk().await, Ok(None));
+        assert_eq!(receiver.next_chunk().await, Ok(None));
+
+        // 读取错误关闭流
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let receiver = StreamReceiver::new(Some(rx), Instant::now() + Duration::from_secs(5));
+        tx.send(StreamEvent::Error("boom".to_string())).await.unwrap();
+        assert_eq!(receiver.next_chunk().await, Err("boom".to_string()));
+        assert!(tx.is_closed());
+
+        // 超过累计截止时间后超时，关闭后发送端感知
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let receiver = StreamReceiver::new(Some(rx), Instant::now() + Duration::from_millis(20));
+        assert_eq!(receiver.next_chunk().await, Err("Stream timeout".to_string()));
+        assert!(tx.send(StreamEvent::Chunk("late".to_string())).await.is_err());
+
+        // 提前关闭
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let receiver = StreamReceiver::new(Some(rx), Instant::now() + Duration::from_secs(5));
+        receiver.close().await;
+        assert!(tx.is_closed());
+        assert_eq!(receiver.next_chunk().await, Ok(None));
+    }
+
+    #[test]
+    fn test_fetch_retry_requires_idempotency() {
+        let retry = Some(FetchRetryOptions::default());
+        let get = FetchOptions {
+            retry,
+            ..Default::default()
+        };
+        assert!(get.check_retry().is_ok());
+
+        let mut post = FetchOptions {
+            method: Some("post".to_string()),
+            retry,
+            ..Default::default()
+        };
+        assert!(matches!(post.check_retry(), Err(FetchError::RetryNotAllowed(m)) if m == "POST"));
+        post.headers.insert("Idempotency-Key".to_string(), "order-42".to_string());
+        assert!(post.check_retry().is_ok());
+
+        // 未设置 retry 时不限制方法
+        post.retry = None;
+        post.headers.clear();
+        assert!(post.check_retry().is_ok());
+
+        // 超出范围的值被截断
+        let config = FetchRetryOptions { attempts: 99, backoff_ms: 1 }.to_retry_config();
+        assert_eq!(config.max_retries, MAX_RETRY_ATTEMPTS - 1);
+        assert_eq!(config.initial_delay, Duration::from_millis(MIN_RETRY_BACKOFF_MS));
+        assert!(config.validate().is_ok());
+        assert_eq!(FetchRetryOptions { attempts: 0, backoff_ms: 200 }.to_retry_config().max_retries, 0);
+    }
+
+    #[test]
+    fn test_host_allowlist_exact_and_wildcard() {
+        let patterns = vec!["api.anthropic.com".to_string(), "*.example.com".to_string()];
+
+        assert!(UrlSecurityChecker::is_host_allowed("api.anthropic.com", &patterns));
+        assert!(UrlSecurityChecker::is_host_allowed("API.Anthropic.com.", &patterns));
+        assert!(!UrlSecurityChecker::is_host_allowed("anthropic.com", &patterns));
+        assert!(!UrlSecurityChecker::is_host_allowed("evil-api.anthropic.com", &patterns));
+
+        assert!(UrlSecurityChecker::is_host_allowed("a.example.com", &patterns));
+        assert!(UrlSecurityChecker::is_host_allowed("a.b.example.com", &patterns));
+        assert!(!UrlSecurityChecker::is_host_allowed("example.com", &patterns));
+        assert!(!UrlSecurityChecker::is_host_allowed("badexample.com", &patterns));
+    }
+
+    #[test]
+    fn test_host_allowlist_rejects_broad_patterns() {
+        let patterns = vec!["*".to_string(), "*.com".to_string(), "api.*.com".to_string()];
+        assert!(!UrlSecurityChecker::is_host_allowed("api.anthropic.com", &patterns));
+    }
+
+    #[test]
+    fn test_check_url_with_allowlist() {
+        let allowed = vec!["api.anthropic.com".to_string()];
+
+        assert!(UrlSecurityChecker::check_url_with_allowlist(
+            "https://api.anthropic.com/v1/usage",
+            Some(&allowed)
+        )
+        .is_ok());
+        assert!(matches!(
+            UrlSecurityChecker::check_url_with_allowlist("https://example.com", Some(&allowed)),
+            Err(FetchError::HostNotAllowed(_))
+        ));
+        // 未声明 allowedHosts 时不限制公网主机
+        assert!(UrlSecurityChecker::check_url_with_allowlist("https://example.com", None).is_ok());
+        // 白名单不能绕过 SSRF 检查
+        let local = vec!["localhost".to_string()];
+        assert!(UrlSecurityChecker::check_url_with_allowlist("http://localhost", Some(&local)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pooled_client_reuses_client_and_pins_dns() {
+        use std::io::{Read, Write};
+
+        // 相同配置复用同一客户端（同一解析器）
+        let a = PooledClient::get(None, None).unwrap();
+        let b = PooledClient::get(None, None).unwrap();
+        assert!(Arc::ptr_eq(&a.resolver, &b.resolver));
+
+        // 登记的主机解析到已验证的地址
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .unwrap();
+        });
+        a.pin("pinned.invalid", ([127, 0, 0, 1], port).into());
+        let response = b
+            .client
+            .get(format!("http://pinned.invalid:{}/", port))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+        server.join().unwrap();
+
+        // 未登记的主机不回退到系统 DNS
+        assert!(a.client.get("http://unpinned.invalid/").send().await.is_err());
+    }
+
+    #[test]
+    fn test_default_gateway_does_not_widen_allowlist() {
+        use crate::plugin::gateway::GatewayConfig;
+
+        let config = GatewayConfig::default();
+        let manager = |mapped: bool| {
+            RequestManager::new_with_fallback()
+                .with_allowed_hosts(Some(vec!["example.com".to_string()]))
+                .with_gateway(config.resolve("demo").cloned(), mapped)
+        };
+
+        // 未映射的插件直接访问网关主机被拒绝
+        let implicit = manager(false);
+        assert!(matches!(
+            implicit.check_url("https://api.anthropic.com/v1/messages"),
+            Err(FetchError::HostNotAllowed(_))
+        ));
+
+        // 通过 {{gateway.base}} 引用时放行
+        let mut opts = FetchOptions::default();
+        let url = implicit.apply_gateway("{{gateway.base}}/v1/messages", &mut opts).unwrap();
+        assert!(implicit.check_request_url(&url, &opts).is_ok());
+        let mut opts = FetchOptions::default();
+        let url = implicit.apply_gateway("https://api.anthropic.com/v1/messages", &mut opts).unwrap();
+        assert!(implicit.check_request_url(&url, &opts).is_err());
+
+        // 显式映射的插件始终允许访问网关主机
+        assert!(manager(true).check_url("https://api.anthropic.com/v1/messages").is_ok());
+    }
 }
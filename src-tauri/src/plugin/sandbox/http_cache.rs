@@ -0,0 +1,218 @@
+// HTTP 条件请求缓存
+// Phase 2.2.5: fetch 条件请求
+//
+// Provider 接口大多支持 ETag / Last-Modified。fetch 层按 (插件, URL) 记录验证器和响应体:
+// - 再次 GET 同一 URL 时自动附加 If-None-Match / If-Modified-Since
+// - 服务器返回 304 时用缓存的响应体合成 200 响应交给插件（response.fromCache 为 true）
+//
+// 设计要点：
+// 1. 只缓存无条件 GET 的 2xx 响应；插件自己设置了条件请求头时不介入，由插件处理 304
+// 2. 仅保存在内存中，重启后第一次请求为完整请求
+// 3. 按条目数和单条大小限制内存占用，超出时淘汰最早写入的条目
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use reqwest::header::{HeaderMap, ETAG, LAST_MODIFIED};
+
+// ============================================================================
+// 常量
+// ============================================================================
+
+/// 最多缓存的响应数（所有插件合计）
+const MAX_ENTRIES: usize = 256;
+
+/// 单个响应体最大缓存字节数: 1MB
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// 条件请求头（插件已设置时不自动附加）
+const CONDITIONAL_HEADERS: [&str; 2] = ["if-none-match", "if-modified-since"];
+
+// ============================================================================
+// 验证器
+// ============================================================================
+
+/// 响应的缓存验证器
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    /// 从响应头提取 ETag / Last-Modified
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let get = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Self {
+            etag: get(ETAG),
+            last_modified: get(LAST_MODIFIED),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// 对应的条件请求头
+    pub fn conditional_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::new();
+        if let Some(etag) = &self.etag {
+            headers.push(("If-None-Match", etag.clone()));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.push(("If-Modified-Since", last_modified.clone()));
+        }
+        headers
+    }
+}
+
+/// 请求头中是否已包含条件请求头
+pub fn has_conditional_header(headers: &HashMap<String, String>) -> bool {
+    headers
+        .keys()
+        .any(|key| CONDITIONAL_HEADERS.contains(&key.to_ascii_lowercase().as_str()))
+}
+
+// ============================================================================
+// 缓存
+// ============================================================================
+
+/// 缓存的响应
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    validators: Validators,
+    body: String,
+    /// 写入序号（淘汰最早写入的条目）
+    seq: u64,
+}
+
+#[derive(Default)]
+struct CacheInner {
+    entries: HashMap<(String, String), CachedResponse>,
+    next_seq: u64,
+}
+
+/// 条件请求缓存（进程内共享，按插件和 URL 隔离）
+#[derive(Default)]
+pub struct HttpCache {
+    inner: Mutex<CacheInner>,
+}
+
+impl HttpCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 进程内共享的缓存
+    pub fn shared() -> Arc<HttpCache> {
+        static SHARED: OnceLock<Arc<HttpCache>> = OnceLock::new();
+        SHARED.get_or_init(|| Arc::new(HttpCache::new())).clone()
+    }
+
+    /// 已缓存响应的验证器
+    pub fn validators(&self, plugin_id: &str, url: &str) -> Option<Validators> {
+        self.lock()
+            .entries
+            .get(&(plugin_id.to_string(), url.to_string()))
+            .map(|entry| entry.validators.clone())
+    }
+
+    /// 304 时返回缓存的响应体
+    pub fn cached_body(&self, plugin_id: &str, url: &str) -> Option<String> {
+        self.lock()
+            .entries
+            .get(&(plugin_id.to_string(), url.to_string()))
+            .map(|entry| entry.body.clone())
+    }
+
+    /// 记录 2xx 响应（没有验证器或响应体过大时删除旧条目）
+    pub fn store(&self, plugin_id: &str, url: &str, validators: Validators, body: &str) {
+        let key = (plugin_id.to_string(), url.to_string());
+        let mut inner = self.lock();
+        if validators.is_empty() || body.len() > MAX_BODY_BYTES {
+            inner.entries.remove(&key);
+            return;
+        }
+
+        if !inner.entries.contains_key(&key) && inner.entries.len() >= MAX_ENTRIES {
+            let oldest = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.seq)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                inner.entries.remove(&oldest);
+            }
+        }
+
+        inner.next_seq += 1;
+        let seq = inner.next_seq;
+        inner.entries.insert(
+            key,
+            CachedResponse {
+                validators,
+                body: body.to_string(),
+                seq,
+            },
+        );
+    }
+
+    /// 删除插件的所有缓存（卸载插件时调用）
+    pub fn clear_plugin(&self, plugin_id: &str) {
+        self.lock().entries.retain(|(id, _), _| id != plugin_id);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_http_cache_validators_and_eviction() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        let validators = Validators::from_headers(&headers);
+        assert_eq!(validators.conditional_headers(), vec![("If-None-Match", "\"v1\"".to_string())]);
+
+        let cache = HttpCache::new();
+        let url = "https://api.example.com/usage";
+        cache.store("a", url, validators.clone(), r#"{"used":1}"#);
+        assert_eq!(cache.validators("a", url), Some(validators));
+        assert_eq!(cache.cached_body("a", url).as_deref(), Some(r#"{"used":1}"#));
+        // 按插件隔离
+        assert!(cache.validators("b", url).is_none());
+
+        // 新响应不再带验证器时删除旧条目
+        cache.store("a", url, Validators::default(), "{}");
+        assert!(cache.cached_body("a", url).is_none());
+
+        // 超出条目上限时淘汰最早写入的条目
+        let etag = Validators { etag: Some("\"x\"".into()), last_modified: None };
+        for i in 0..=MAX_ENTRIES {
+            cache.store("a", &format!("{}?page={}", url, i), etag.clone(), "{}");
+        }
+        assert!(cache.validators("a", &format!("{}?page=0", url)).is_none());
+        assert!(cache.validators("a", &format!("{}?page={}", url, MAX_ENTRIES)).is_some());
+
+        cache.clear_plugin("a");
+        assert!(cache.validators("a", &format!("{}?page=1", url)).is_none());
+
+        let plugin_headers = HashMap::from([("If-None-Match".to_string(), "\"v0\"".to_string())]);
+        assert!(has_conditional_header(&plugin_headers));
+    }
+}
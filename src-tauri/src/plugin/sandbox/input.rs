@@ -0,0 +1,616 @@
+// 用户输入请求 API 实现
+// Phase 4: 通信与配置
+//
+// 提供给 JS 插件使用的运行时输入请求（需声明 `input` 权限）:
+// - context.requestInput({ title, fields, timeoutMs })
+//
+// 流程：
+// 1. 插件调用 requestInput，沙盒通过 InputBroker 登记请求并等待响应
+// 2. 宿主消费请求通道，发送 ipc:input_requested 事件并打开输入对话框窗口
+// 3. 用户提交或取消后，submit_input 命令将结果交还给等待中的沙盒调用
+// 4. 超时未响应则以 TIMEOUT 错误结束；等待时间不超过插件剩余的执行时间
+//    （执行超时会中断整个调用，对话框不应在执行结束后仍然等待输入）
+//
+// 安全设计：
+// - 请求中的插件 ID 由宿主注入，插件无法伪造
+// - 每个插件同时只能有一个待处理请求，防止弹窗轰炸
+// - 等待中的调用被取消（执行超时）时由守卫移除待处理请求，不会让插件永久处于 Busy
+// - 输入值不写入日志
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use rquickjs::prelude::Async;
+use rquickjs::{Ctx, Function, Result as JsResult};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+
+// ============================================================================
+// 常量
+// ============================================================================
+
+/// 默认等待时间: 2 分钟
+const DEFAULT_INPUT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 最短等待时间: 5 秒
+const MIN_INPUT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 最长等待时间: 5 分钟
+const MAX_INPUT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// 单次请求最多字段数
+const MAX_INPUT_FIELDS: usize = 8;
+
+/// 标题最大长度（字符）
+const MAX_TITLE_LEN: usize = 100;
+
+/// 请求通知通道容量
+const INPUT_CHANNEL_CAPACITY: usize = 16;
+
+// ============================================================================
+// 请求类型
+// ============================================================================
+
+/// 输入字段类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputFieldKind {
+    /// 普通文本
+    #[default]
+    Text,
+    /// 密码（掩码显示）
+    Password,
+    /// 一次性验证码
+    Code,
+}
+
+/// 输入字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputField {
+    /// 字段名（作为返回对象的键）
+    pub name: String,
+    /// 显示标签
+    #[serde(default)]
+    pub label: Option<String>,
+    /// 字段类型
+    #[serde(default, rename = "type")]
+    pub kind: InputFieldKind,
+    /// 占位提示
+    #[serde(default)]
+    pub placeholder: Option<String>,
+    /// 是否必填
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// 插件传入的请求参数
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputOptions {
+    /// 对话框标题
+    pub title: String,
+    /// 说明文字
+    #[serde(default)]
+    pub message: Option<String>,
+    /// 字段列表
+    pub fields: Vec<InputField>,
+    /// 等待时间 (毫秒)
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// 输入请求（发送给前端）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputRequest {
+    /// 请求 ID
+    pub request_id: String,
+    /// 发起请求的插件 ID
+    pub plugin_id: String,
+    /// 对话框标题
+    pub title: String,
+    /// 说明文字
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// 字段列表
+    pub fields: Vec<InputField>,
+    /// 过期时间 (ISO 8601)
+    pub expires_at: String,
+}
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+/// 输入请求错误
+#[derive(Debug, thiserror::Error)]
+pub enum InputError {
+    #[error("无效的输入请求: {0}")]
+    Invalid(String),
+
+    #[error("插件已有待处理的输入请求")]
+    Busy,
+
+    #[error("输入对话框不可用")]
+    Unavailable,
+
+    #[error("等待用户输入超时")]
+    Timeout,
+
+    #[error("用户取消了输入")]
+    Cancelled,
+
+    #[error("输入请求不存在或已过期: {0}")]
+    NotFound(String),
+}
+
+impl InputError {
+    /// 对应的 PluginErrorType 代码
+    pub fn code(&self) -> &'static str {
+        match self {
+            InputError::Timeout => "TIMEOUT",
+            InputError::Busy => "RATE_LIMIT",
+            InputError::Cancelled => "INPUT_CANCELLED",
+            InputError::Unavailable => "INPUT_UNAVAILABLE",
+            InputError::Invalid(_) | InputError::NotFound(_) => "UNKNOWN",
+        }
+    }
+}
+
+// ============================================================================
+// 输入请求中介
+// ============================================================================
+
+/// 待处理请求
+struct PendingInput {
+    request: InputRequest,
+    response_tx: oneshot::Sender<Option<HashMap<String, String>>>,
+}
+
+/// 待处理请求守卫：请求结束（含等待中的调用被取消）时移除待处理请求
+struct PendingGuard<'a> {
+    broker: &'a InputBroker,
+    request_id: String,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        // 正常响应时已由 respond 移除
+        self.broker.lock().remove(&self.request_id);
+    }
+}
+
+/// 输入请求中介
+///
+/// 在沙盒和宿主 UI 之间转交请求与响应。
+pub struct InputBroker {
+    /// 待处理请求
+    pending: Mutex<HashMap<String, PendingInput>>,
+    /// 请求通知发送端
+    notify_tx: mpsc::Sender<InputRequest>,
+    /// 请求通知接收端（由宿主取出消费）
+    notify_rx: Mutex<Option<mpsc::Receiver<InputRequest>>>,
+    /// 请求 ID 计数器
+    next_id: AtomicU64,
+}
+
+impl InputBroker {
+    /// 创建中介
+    pub fn new() -> Self {
+        let (notify_tx, notify_rx) = mpsc::channel(INPUT_CHANNEL_CAPACITY);
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            notify_tx,
+            notify_rx: Mutex::new(Some(notify_rx)),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, PendingInput>> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 取出请求通知接收端（只能取出一次，供宿主消费）
+    pub fn take_receiver(&self) -> Option<mpsc::Receiver<InputRequest>> {
+        self.notify_rx.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// 获取所有待处理请求
+    pub fn pending_requests(&self) -> Vec<InputRequest> {
+        let mut requests: Vec<InputRequest> =
+            self.lock().values().map(|p| p.request.clone()).collect();
+        requests.sort_by(|a, b| a.request_id.cmp(&b.request_id));
+        requests
+    }
+
+    /// 发起输入请求并等待用户响应
+    ///
+    /// `max_wait` 为插件剩余的执行时间，等待时间不超过该值；不足最短等待时间时直接返回超时。
+    pub async fn request(
+        &self,
+        plugin_id: &str,
+        options: InputOptions,
+        max_wait: Option<Duration>,
+    ) -> Result<HashMap<String, String>, InputError> {
+        Self::validate(&options)?;
+
+        let timeout = Self::effective_timeout(options.timeout_ms, max_wait).ok_or_else(|| {
+            log::warn!("[{}] 剩余执行时间不足，不再请求用户输入", plugin_id);
+            InputError::Timeout
+        })?;
+
+        let request_id = format!(
+            "input-{:06}",
+            self.next_id.fetch_add(1, Ordering::Relaxed)
+        );
+        let expires_at = chrono::Utc::now()
+            + chrono::Duration::from_std(timeout).unwrap_or_else(|_| chrono::Duration::zero());
+        let request = InputRequest {
+            request_id: request_id.clone(),
+            plugin_id: plugin_id.to_string(),
+            title: options.title,
+            message: options.message,
+            fields: options.fields,
+            expires_at: expires_at.to_rfc3339(),
+        };
+
+        let (response_tx, response_rx) = oneshot::channel();
+        {
+            let mut pending = self.lock();
+            if pending.values().any(|p| p.request.plugin_id == plugin_id) {
+                return Err(InputError::Busy);
+            }
+            pending.insert(
+                request_id.clone(),
+                PendingInput {
+                    request: request.clone(),
+                    response_tx,
+                },
+            );
+        }
+
+        let _guard = PendingGuard {
+            broker: self,
+            request_id: request_id.clone(),
+        };
+
+        if self.notify_tx.try_send(request).is_err() {
+            return Err(InputError::Unavailable);
+        }
+        log::info!("[{}] 请求用户输入: {}", plugin_id, request_id);
+
+        let result = tokio::time::timeout(timeout, response_rx).await;
+
+        match result {
+            Ok(Ok(Some(values))) => Ok(values),
+            Ok(Ok(None)) | Ok(Err(_)) => Err(InputError::Cancelled),
+            Err(_) => {
+                log::warn!("[{}] 输入请求超时: {}", plugin_id, request_id);
+                Err(InputError::Timeout)
+            }
+        }
+    }
+
+    /// 提交用户响应（`None` 表示取消）
+    pub fn respond(
+        &self,
+        request_id: &str,
+        values: Option<HashMap<String, String>>,
+    ) -> Result<(), InputError> {
+        let mut pending = self.lock();
+        let entry = pending
+            .get(request_id)
+            .ok_or_else(|| InputError::NotFound(request_id.to_string()))?;
+
+        // 只保留声明过的字段，并检查必填项
+        let values = match values {
+            Some(mut values) => {
+                let names: HashSet<&str> =
+                    entry.request.fields.iter().map(|f| f.name.as_str()).collect();
+                values.retain(|k, _| names.contains(k.as_str()));
+                if let Some(missing) = entry.request.fields.iter().find(|f| {
+                    f.required && values.get(&f.name).map_or(true, |v| v.is_empty())
+                }) {
+                    return Err(InputError::Invalid(format!("缺少必填字段: {}", missing.name)));
+                }
+                Some(values)
+            }
+            None => None,
+        };
+
+        let entry = pending
+            .remove(request_id)
+            .ok_or_else(|| InputError::NotFound(request_id.to_string()))?;
+        if entry.response_tx.send(values).is_err() {
+            return Err(InputError::NotFound(request_id.to_string()));
+        }
+        Ok(())
+    }
+
+    /// 计算实际等待时间（限制在 5 秒到 5 分钟之间，且不超过剩余执行时间）
+    ///
+    /// 剩余执行时间不足 5 秒时返回 None。
+    fn effective_timeout(timeout_ms: Option<u64>, max_wait: Option<Duration>) -> Option<Duration> {
+        let timeout = timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_INPUT_TIMEOUT)
+            .clamp(MIN_INPUT_TIMEOUT, MAX_INPUT_TIMEOUT);
+        match max_wait {
+            Some(max_wait) if max_wait < MIN_INPUT_TIMEOUT => None,
+            Some(max_wait) => Some(timeout.min(max_wait)),
+            None => Some(timeout),
+        }
+    }
+
+    /// 校验请求参数
+    fn validate(options: &InputOptions) -> Result<(), InputError> {
+        let title_len = options.title.chars().count();
+        if title_len == 0 || title_len > MAX_TITLE_LEN {
+            return Err(InputError::Invalid(format!(
+                "title 长度须在 1-{} 之间",
+                MAX_TITLE_LEN
+            )));
+        }
+        if options.fields.is_empty() || options.fields.len() > MAX_INPUT_FIELDS {
+            return Err(InputError::Invalid(format!(
+                "fields 数量须在 1-{} 之间",
+                MAX_INPUT_FIELDS
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        for field in &options.fields {
+            let valid_name = !field.name.is_empty()
+                && field.name.len() <= 64
+                && field
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            if !valid_name {
+                return Err(InputError::Invalid(format!("无效的字段名: {}", field.name)));
+            }
+            if !seen.insert(field.name.as_str()) {
+                return Err(InputError::Invalid(format!("重复的字段名: {}", field.name)));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for InputBroker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// 插件输入句柄
+// ============================================================================
+
+/// 插件输入句柄（绑定插件 ID）
+pub struct PluginInput {
+    /// 插件 ID
+    plugin_id: String,
+    /// 中介
+    broker: Arc<InputBroker>,
+    /// 本次执行的截止时间（由执行器在开始执行时设置）
+    deadline: Mutex<Option<Instant>>,
+}
+
+impl PluginInput {
+    /// 创建句柄
+    pub fn new(plugin_id: &str, broker: Arc<InputBroker>) -> Self {
+        Self {
+            plugin_id: plugin_id.to_string(),
+            broker,
+            deadline: Mutex::new(None),
+        }
+    }
+
+    /// 设置执行截止时间，输入等待时间不超过剩余执行时间
+    pub fn set_deadline(&self, deadline: Option<Instant>) {
+        *self.deadline.lock().unwrap_or_else(|e| e.into_inner()) = deadline;
+    }
+
+    /// 剩余执行时间（未设置截止时间时为 None）
+    fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// 发起请求，返回 JSON 文本 `{"ok": {...}}` 或 `{"error": {code, message}}`
+    async fn request_json(&self, options_json: String) -> String {
+        let result = match serde_json::from_str::<InputOptions>(&options_json) {
+            Ok(options) => self.broker.request(&self.plugin_id, options, self.remaining()).await,
+            Err(e) => Err(InputError::Invalid(e.to_string())),
+        };
+
+        match result {
+            Ok(values) => serde_json::json!({ "ok": values }).to_string(),
+            Err(e) => serde_json::json!({
+                "error": { "code": e.code(), "message": e.to_string() }
+            })
+            .to_string(),
+        }
+    }
+}
+
+// ============================================================================
+// JS API
+// ============================================================================
+
+/// Input API
+pub struct InputApi;
+
+impl InputApi {
+    /// 向上下文注入 `__cukRequestInput` 全局函数
+    pub fn inject(ctx: &Ctx<'_>, input: Arc<PluginInput>) -> JsResult<()> {
+        let native = Function::new(
+            ctx.clone(),
+            Async(move |options_json: String| {
+                let input = input.clone();
+                async move { input.request_json(options_json).await }
+            }),
+        )?;
+
+        // JS 包装层：序列化参数，将错误转换为 PluginError
+        let wrap: Function = ctx.eval(
+            r#"(function(native) {
+                function fail(code, message) {
+                    return typeof PluginError === 'function'
+                        ? new PluginError(code, message, undefined)
+                        : new Error('[' + code + '] ' + message);
+                }
+                return function requestInput(options) {
+                    var json;
+                    try { json = JSON.stringify(options || {}); }
+                    catch (e) { return Promise.reject(fail('UNKNOWN', String(e))); }
+                    return native(json).then(function(s) {
+                        var r = JSON.parse(s);
+                        if (r.error) throw fail(r.error.code, r.error.message);
+                        return r.ok;
+                    });
+                };
+            })"#,
+        )?;
+        let api: Function = wrap.call((native,))?;
+        ctx.globals().set("__cukRequestInput", api)?;
+
+        log::debug!("Input API 已注入");
+        Ok(())
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(timeout_ms: Option<u64>) -> InputOptions {
+        serde_json::from_value(serde_json::json!({
+            "title": "输入验证码",
+            "fields": [{ "name": "code", "type": "code" }, { "name": "note", "required": false }],
+            "timeoutMs": timeout_ms,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_input_request_and_respond() {
+        let broker = Arc::new(InputBroker::new());
+        let mut rx = broker.take_receiver().unwrap();
+        assert!(broker.take_receiver().is_none());
+
+        let b = broker.clone();
+        let task = tokio::spawn(async move { b.request("demo", options(None), None).await });
+
+        let request = rx.recv().await.unwrap();
+        assert_eq!(request.plugin_id, "demo");
+        assert_eq!(request.fields[0].kind, InputFieldKind::Code);
+        assert_eq!(broker.pending_requests().len(), 1);
+
+        // 同一插件不能并发请求
+        assert!(matches!(
+            broker.request("demo", options(None), None).await,
+            Err(InputError::Busy)
+        ));
+
+        // 缺少必填字段
+        assert!(matches!(
+            broker.respond(&request.request_id, Some(HashMap::new())),
+            Err(InputError::Invalid(_))
+        ));
+
+        let mut values = HashMap::new();
+        values.insert("code".to_string(), "123456".to_string());
+        values.insert("extra".to_string(), "ignored".to_string());
+        broker.respond(&request.request_id, Some(values)).unwrap();
+
+        let result = task.await.unwrap().unwrap();
+        assert_eq!(result.get("code").map(String::as_str), Some("123456"));
+        assert!(!result.contains_key("extra"));
+        assert!(broker.pending_requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_input_cancel() {
+        let broker = Arc::new(InputBroker::new());
+        let mut rx = broker.take_receiver().unwrap();
+
+        let b = broker.clone();
+        let task = tokio::spawn(async move { b.request("demo", options(None), None).await });
+        let request = rx.recv().await.unwrap();
+        broker.respond(&request.request_id, None).unwrap();
+        assert!(matches!(task.await.unwrap(), Err(InputError::Cancelled)));
+        assert!(broker.pending_requests().is_empty());
+
+        // 已完成的请求不能再次响应
+        assert!(matches!(
+            broker.respond(&request.request_id, None),
+            Err(InputError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_input_cancelled_call_clears_pending() {
+        let broker = Arc::new(InputBroker::new());
+        let mut rx = broker.take_receiver().unwrap();
+
+        // 等待中的调用被执行超时取消后，插件可以再次请求输入
+        let b = broker.clone();
+        let task = tokio::spawn(async move { b.request("demo", options(None), None).await });
+        rx.recv().await.unwrap();
+        task.abort();
+        assert!(task.await.unwrap_err().is_cancelled());
+        assert!(broker.pending_requests().is_empty());
+
+        // 剩余执行时间不足时直接超时，不打开对话框
+        assert!(matches!(
+            broker.request("demo", options(None), Some(Duration::from_secs(1))).await,
+            Err(InputError::Timeout)
+        ));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(InputError::Cancelled.code(), "INPUT_CANCELLED");
+        assert_eq!(InputError::Unavailable.code(), "INPUT_UNAVAILABLE");
+    }
+
+    #[test]
+    fn test_input_timeout_bounds() {
+        let timeout = |ms, max_wait| InputBroker::effective_timeout(ms, max_wait);
+        assert_eq!(timeout(None, None), Some(DEFAULT_INPUT_TIMEOUT));
+        assert_eq!(timeout(Some(1), None), Some(MIN_INPUT_TIMEOUT));
+        assert_eq!(timeout(Some(u64::MAX), None), Some(MAX_INPUT_TIMEOUT));
+        assert_eq!(timeout(Some(30_000), None), Some(Duration::from_secs(30)));
+
+        // 不超过剩余执行时间
+        let remaining = Some(Duration::from_secs(20));
+        assert_eq!(timeout(None, remaining), Some(Duration::from_secs(20)));
+        assert_eq!(timeout(Some(10_000), remaining), Some(Duration::from_secs(10)));
+        assert_eq!(timeout(None, Some(Duration::from_secs(4))), None);
+    }
+
+    #[test]
+    fn test_input_validate() {
+        let invalid = |v: serde_json::Value| {
+            let options: InputOptions = serde_json::from_value(v).unwrap();
+            InputBroker::validate(&options).is_err()
+        };
+        assert!(invalid(serde_json::json!({ "title": "", "fields": [{ "name": "a" }] })));
+        assert!(invalid(serde_json::json!({ "title": "t", "fields": [] })));
+        assert!(invalid(serde_json::json!({ "title": "t", "fields": [{ "name": "a b" }] })));
+        assert!(invalid(serde_json::json!({ "title": "t", "fields": [{ "name": "a" }, { "name": "a" }] })));
+        assert!(!invalid(serde_json::json!({ "title": "t", "fields": [{ "name": "a" }] })));
+    }
+}
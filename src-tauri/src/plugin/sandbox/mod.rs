@@ -4,20 +4,34 @@
 //
 // 提供给 JS 插件使用的安全 API
 
+pub mod cache;
 pub mod console;
 pub mod context;
 pub mod encoding;
 pub mod error;
 pub mod fetch;
+pub mod http_cache;
+pub mod input;
+pub mod module;
+pub mod plugin_data;
+pub mod secrets;
+pub mod storage;
 pub mod timer;
 
 // 导出所有沙盒 API
+pub use cache::{CacheApi, PluginCache};
 pub use console::ConsoleApi;
 pub use context::{EmitRequest, PluginCallRequest, PluginContextApi, PluginContextConfig};
 pub use encoding::EncodingApi;
 pub use error::PluginErrorApi;
 pub use fetch::{FetchApi, RequestManager, UrlSecurityChecker};
-pub use timer::{TimerApi, TimerRegistry};
+pub use http_cache::HttpCache;
+pub use input::{InputApi, InputBroker, InputRequest, PluginInput};
+pub use module::PluginModule;
+pub use plugin_data::{PluginDataApi, PluginDataReader};
+pub use secrets::{PluginSecrets, SecretsApi};
+pub use storage::{PluginStorage, StorageApi};
+pub use timer::{HostTimerRequest, TimerApi, TimerRegistry};
 
 use std::sync::Arc;
 use rquickjs::{AsyncContext, Result as JsResult};
@@ -0,0 +1,183 @@
+// Phase 2.3: ES Module 加载
+// 插件入口按标准 ES Module 求值（Module::declare + eval），不再对源码做字符串改写，
+// 支持 re-export、default export 以及插件目录内的相对导入。
+//
+// 模块解析规则:
+// - 模块名为相对插件目录的路径（如 `index.js`、`lib/util.js`）
+// - 只允许相对导入（`./`、`../`），解析结果不得超出插件目录
+// - 不支持裸模块名（npm 包）和 URL 导入
+// - 只加载 .js/.mjs 文件，读取时拒绝符号链接（与入口文件相同的 openat 校验）
+
+use std::path::PathBuf;
+
+use rquickjs::loader::{Loader, Resolver};
+use rquickjs::module::Declared;
+use rquickjs::{Ctx, Error as JsError, Exception, Module, Object, Promise, Result as JsResult};
+
+use crate::plugin::lifecycle::openat_verifier;
+
+/// 插件模块的导出对象在全局上的名称（调用脚本通过它访问 fetchData 等导出）
+pub const PLUGIN_EXPORTS_GLOBAL: &str = "__pluginExports";
+
+/// 允许加载的模块扩展名
+const MODULE_EXTENSIONS: &[&str] = &["js", "mjs"];
+
+/// ES Module 形式的插件代码
+#[derive(Debug, Clone)]
+pub struct PluginModule {
+    /// 模块名（入口文件相对插件目录的路径）
+    pub name: String,
+    /// 入口模块源码
+    pub source: String,
+    /// 插件目录（相对导入的根目录）
+    pub root: PathBuf,
+}
+
+impl PluginModule {
+    /// 创建插件模块（`entry` 为 manifest 中的入口路径）
+    pub fn new(root: PathBuf, entry: &str, source: String) -> Self {
+        Self {
+            name: normalize_module_path(entry).unwrap_or_else(|| entry.to_string()),
+            source,
+            root,
+        }
+    }
+
+    /// 声明并求值入口模块，返回模块导出对象
+    ///
+    /// 模块顶层抛出的异常作为求值错误返回；顶层 await 只驱动 QuickJS 任务队列，
+    /// 等待宿主异步 API（fetch 等）的顶层 await 会返回错误。
+    pub fn evaluate<'js>(&self, ctx: &Ctx<'js>) -> JsResult<Object<'js>> {
+        let (module, promise) =
+            Module::declare(ctx.clone(), self.name.as_str(), self.source.as_str())?.eval()?;
+        finish_evaluation(ctx, &self.name, promise)?;
+        module.namespace()
+    }
+}
+
+/// 等待模块求值完成
+fn finish_evaluation<'js>(ctx: &Ctx<'js>, name: &str, promise: Promise<'js>) -> JsResult<()> {
+    match promise.finish::<rquickjs::Value>() {
+        Ok(_) => Ok(()),
+        Err(JsError::WouldBlock) => Err(Exception::throw_message(
+            ctx,
+            &format!("模块 {} 的顶层 await 依赖异步宿主 API，请将异步逻辑移到 fetchData 中", name),
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+/// 规范化模块路径（去掉 `.` 组件、折叠 `..`），超出插件目录时返回 None
+fn normalize_module_path(path: &str) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            part => parts.push(part),
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// 插件模块解析器
+///
+/// 把相对导入解析为相对插件目录的模块名
+pub struct PluginModuleResolver;
+
+impl Resolver for PluginModuleResolver {
+    fn resolve<'js>(&mut self, _ctx: &Ctx<'js>, base: &str, name: &str) -> JsResult<String> {
+        if !name.starts_with("./") && !name.starts_with("../") {
+            return Err(JsError::new_resolving_message(
+                base,
+                name,
+                "插件只能导入插件目录内的相对路径模块",
+            ));
+        }
+        let dir = base.rsplit_once('/').map_or("", |(dir, _)| dir);
+        normalize_module_path(&format!("{}/{}", dir, name))
+            .ok_or_else(|| JsError::new_resolving_message(base, name, "模块路径超出插件目录"))
+    }
+}
+
+/// 插件模块加载器
+///
+/// 从插件目录读取模块源码（拒绝符号链接）
+pub struct PluginModuleLoader {
+    root: PathBuf,
+}
+
+impl PluginModuleLoader {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+}
+
+impl Loader for PluginModuleLoader {
+    fn load<'js>(&mut self, ctx: &Ctx<'js>, name: &str) -> JsResult<Module<'js, Declared>> {
+        let allowed = name
+            .rsplit_once('.')
+            .is_some_and(|(_, ext)| MODULE_EXTENSIONS.contains(&ext));
+        if !allowed {
+            return Err(JsError::new_loading_message(name, "只能导入 .js/.mjs 模块"));
+        }
+        let source = openat_verifier::read_entry_file(&self.root, name)
+            .map_err(|e| JsError::new_loading_message(name, e.to_string()))?;
+        Module::declare(ctx.clone(), name, source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_relative_imports() {
+        let mut resolver = PluginModuleResolver;
+        let runtime = rquickjs::Runtime::new().unwrap();
+        let context = rquickjs::Context::full(&runtime).unwrap();
+        context.with(|ctx| {
+            assert_eq!(resolver.resolve(&ctx, "index.js", "./lib/util.js").unwrap(), "lib/util.js");
+            assert_eq!(resolver.resolve(&ctx, "lib/a.js", "../b.mjs").unwrap(), "b.mjs");
+            assert_eq!(resolver.resolve(&ctx, "lib/a.js", "./c/../d.js").unwrap(), "lib/d.js");
+            assert!(resolver.resolve(&ctx, "index.js", "../outside.js").is_err());
+            assert!(resolver.resolve(&ctx, "index.js", "lodash").is_err());
+            assert!(resolver.resolve(&ctx, "index.js", "https://example.com/x.js").is_err());
+        });
+    }
+
+    #[tokio::test]
+    async fn test_execute_module_with_imports() {
+        use crate::plugin::runtime::{PluginExecutor, SandboxRuntime};
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("lib")).unwrap();
+        std::fs::write(
+            dir.path().join("lib/util.js"),
+            "export const label = 'export const in a string';\nexport default function double(x) { return x * 2; }",
+        )
+        .unwrap();
+        let source = r#"
+            import double, { label } from './lib/util.js';
+            export { label as name } from './lib/util.js';
+            export default { fetchData: async (config) => ({ value: double(config.n), label }) };
+        "#;
+        let module = PluginModule::new(dir.path().to_path_buf(), "./index.js", source.to_string());
+        assert_eq!(module.name, "index.js");
+
+        let runtime = Arc::new(SandboxRuntime::new_default().await.unwrap());
+        let executor = PluginExecutor::new(runtime);
+        let result = executor
+            .execute_module(&module, "__pluginExports.default.fetchData({ n: 21 })", &["storage".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(result, serde_json::json!({ "value": 42, "label": "export const in a string" }));
+
+        // 导入插件目录之外的模块失败
+        let escape = PluginModule::new(dir.path().to_path_buf(), "index.js", "import '../x.js';".to_string());
+        assert!(executor.execute_module(&escape, "1", &[]).await.is_err());
+    }
+}
@@ -0,0 +1,162 @@
+// Plugin Data API 实现
+// Phase 4.3: 权限控制
+//
+// 提供给 JS 插件使用的只读跨插件数据访问:
+// - context.getData(pluginId)  返回目标插件最近一次缓存的 PluginData，没有数据时为 null
+//
+// 设计要点：
+// 1. 调用方需在 manifest 中声明 `read:<pluginId>`，由 PermissionChecker 在执行前逐个校验
+// 2. 执行前对允许读取的插件数据做快照，执行期间不访问插件管理器（无需跨插件调用和常驻沙盒）
+// 3. 数据以 JSON 复制进沙盒，插件修改返回值不影响宿主缓存
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rquickjs::{Ctx, Exception, Function, Object, Result as JsResult};
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+/// 数据读取错误
+#[derive(Debug, thiserror::Error)]
+pub enum PluginDataError {
+    #[error("未声明读取权限: read:{0}")]
+    NotDeclared(String),
+}
+
+impl PluginDataError {
+    /// 对应的 PluginErrorType 代码
+    pub fn code(&self) -> &'static str {
+        match self {
+            PluginDataError::NotDeclared(_) => "PERMISSION_DENIED",
+        }
+    }
+}
+
+// ============================================================================
+// 数据快照
+// ============================================================================
+
+/// 插件可读取的其他插件数据（执行前的快照）
+pub struct PluginDataReader {
+    /// 调用方插件 ID
+    plugin_id: String,
+    /// 允许读取的插件 ID -> 缓存数据（JSON，没有数据时为 None）
+    snapshot: HashMap<String, Option<String>>,
+}
+
+impl PluginDataReader {
+    /// 创建快照（只应包含已通过 read:<pluginId> 权限校验的插件）
+    pub fn new(plugin_id: &str, data: HashMap<String, Option<serde_json::Value>>) -> Self {
+        let snapshot = data
+            .into_iter()
+            .map(|(id, value)| (id, value.map(|v| v.to_string())))
+            .collect();
+        Self {
+            plugin_id: plugin_id.to_string(),
+            snapshot,
+        }
+    }
+
+    /// 读取目标插件的缓存数据（JSON）
+    pub fn get(&self, target: &str) -> Result<Option<&str>, PluginDataError> {
+        match self.snapshot.get(target) {
+            Some(data) => Ok(data.as_deref()),
+            None => {
+                log::warn!("[{}] 尝试读取未声明的插件数据: {}", self.plugin_id, target);
+                Err(PluginDataError::NotDeclared(target.to_string()))
+            }
+        }
+    }
+}
+
+// ============================================================================
+// JS API
+// ============================================================================
+
+/// Plugin Data API
+pub struct PluginDataApi;
+
+impl PluginDataApi {
+    /// 向上下文注入 `__cukPluginData` 全局对象
+    pub fn inject(ctx: &Ctx<'_>, reader: Arc<PluginDataReader>) -> JsResult<()> {
+        let native = Object::new(ctx.clone())?;
+
+        native.set(
+            "get",
+            Function::new(ctx.clone(), move |ctx: Ctx<'_>, target: String| -> JsResult<Option<String>> {
+                reader
+                    .get(&target)
+                    .map(|data| data.map(String::from))
+                    .map_err(|e| Exception::throw_message(&ctx, &format!("[{}] {}", e.code(), e)))
+            })?,
+        )?;
+
+        // JS 包装层：Promise 化 + JSON 解析 + 错误转换
+        let wrap: Function = ctx.eval(
+            r#"(function(native) {
+                function toError(e) {
+                    var msg = String(e && e.message !== undefined ? e.message : e);
+                    var m = /^\[([A-Z_]+)\] ([\s\S]*)$/.exec(msg);
+                    if (m && typeof PluginError === 'function') return new PluginError(m[1], m[2], undefined);
+                    return e;
+                }
+                return Object.freeze({
+                    getData: function(pluginId) {
+                        try {
+                            var v = native.get(String(pluginId));
+                            return Promise.resolve(v === null || v === undefined ? null : JSON.parse(v));
+                        } catch (e) {
+                            return Promise.reject(toError(e));
+                        }
+                    }
+                });
+            })"#,
+        )?;
+        let api: Object = wrap.call((native,))?;
+        ctx.globals().set("__cukPluginData", api)?;
+
+        log::debug!("Plugin Data API 已注入");
+        Ok(())
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_plugin_data_js_api() {
+        let mut data = HashMap::new();
+        data.insert("usage".to_string(), Some(json!({"dataType": "usage", "percentage": 75})));
+        data.insert("empty".to_string(), None);
+        let reader = Arc::new(PluginDataReader::new("dashboard", data));
+        assert_eq!(reader.get("missing").unwrap_err().code(), "PERMISSION_DENIED");
+
+        let rt = rquickjs::Runtime::new().unwrap();
+        let ctx = rquickjs::Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            crate::plugin::sandbox::PluginErrorApi::inject(&ctx).unwrap();
+            PluginDataApi::inject(&ctx, reader).unwrap();
+            ctx.eval::<(), _>(
+                r#"
+                var out = [];
+                __cukPluginData.getData('usage')
+                    .then(function(v) { out.push(v.percentage); return __cukPluginData.getData('empty'); })
+                    .then(function(v) { out.push(v === null); return __cukPluginData.getData('missing'); })
+                    .catch(function(e) { out.push(e.type); });
+                "#,
+            )
+            .unwrap();
+            while ctx.execute_pending_job() {}
+            let result: String = ctx.eval("JSON.stringify(out)").unwrap();
+            assert_eq!(result, r#"[75,true,"PERMISSION_DENIED"]"#);
+        });
+    }
+}
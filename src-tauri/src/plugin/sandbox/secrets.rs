@@ -0,0 +1,312 @@
+// Secrets API 实现
+// 插件按名称读取宿主保管的密钥 (context.secrets)，密钥本身保存在 macOS Keychain
+//
+// 提供给 JS 插件使用的只读密钥访问:
+// - context.secrets.get(name)
+//
+// 安全设计：
+// 1. 密钥存储在 macOS Keychain，服务名按插件 ID 隔离（cuk.plugin.{pluginId}）
+// 2. 插件只能读取在 manifest 中以 `secrets:<name>` 声明的名称
+// 3. 插件无法写入或枚举密钥，写入由宿主（设置界面 keychain_set / .env 导入）完成
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use rquickjs::{Ctx, Exception, Function, Object, Result as JsResult};
+
+// ============================================================================
+// 常量
+// ============================================================================
+
+/// Keychain 服务名前缀
+pub const SECRETS_SERVICE_PREFIX: &str = "cuk.plugin.";
+
+/// 权限前缀
+const SECRETS_PERMISSION_PREFIX: &str = "secrets:";
+
+/// 获取插件密钥对应的 Keychain 服务名
+pub fn secrets_service(plugin_id: &str) -> String {
+    format!("{}{}", SECRETS_SERVICE_PREFIX, plugin_id)
+}
+
+/// 从权限列表中提取声明的密钥名称（`secrets:<name>`）
+pub fn declared_secrets(permissions: &[String]) -> HashSet<String> {
+    permissions
+        .iter()
+        .filter_map(|p| p.strip_prefix(SECRETS_PERMISSION_PREFIX))
+        .filter(|name| is_valid_secret_name(name))
+        .map(String::from)
+        .collect()
+}
+
+/// 密钥名称校验：1-64 字符，仅限 `[a-zA-Z0-9_-]`
+pub fn is_valid_secret_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+/// 密钥访问错误
+#[derive(Debug, thiserror::Error)]
+pub enum SecretsError {
+    #[error("未声明密钥权限: secrets:{0}")]
+    NotDeclared(String),
+
+    #[error("读取密钥失败: {0}")]
+    Store(String),
+}
+
+impl SecretsError {
+    /// 对应的 PluginErrorType 代码
+    pub fn code(&self) -> &'static str {
+        match self {
+            SecretsError::NotDeclared(_) => "PERMISSION_DENIED",
+            SecretsError::Store(_) => "UNKNOWN",
+        }
+    }
+}
+
+// ============================================================================
+// 密钥存储后端
+// ============================================================================
+
+/// 密钥存储后端
+pub trait SecretStore: Send + Sync {
+    /// 读取密钥，不存在时返回 None
+    fn get(&self, service: &str, account: &str) -> Result<Option<String>, String>;
+
+    /// 写入密钥（仅宿主调用，插件侧无写入接口）
+    fn set(&self, service: &str, account: &str, value: &str) -> Result<(), String>;
+
+    /// 删除密钥，不存在时视为成功（仅宿主调用）
+    fn delete(&self, _service: &str, _account: &str) -> Result<(), String> {
+        Err("该密钥存储不支持删除".to_string())
+    }
+}
+
+/// macOS Keychain 后端
+pub struct KeychainStore;
+
+#[cfg(target_os = "macos")]
+impl SecretStore for KeychainStore {
+    fn get(&self, service: &str, account: &str) -> Result<Option<String>, String> {
+        use security_framework::passwords::get_generic_password;
+
+        match get_generic_password(service, account) {
+            Ok(password) => String::from_utf8(password.to_vec())
+                .map(Some)
+                .map_err(|e| format!("Failed to decode password: {}", e)),
+            Err(e) if e.code() == -25300 => Ok(None), // errSecItemNotFound
+            Err(e) => Err(format!("Failed to get keychain item: {}", e)),
+        }
+    }
+
+    fn set(&self, service: &str, account: &str, value: &str) -> Result<(), String> {
+        use security_framework::passwords::set_generic_password;
+
+        set_generic_password(service, account, value.as_bytes())
+            .map_err(|e| format!("Failed to set keychain item: {}", e))
+    }
+
+    fn delete(&self, service: &str, account: &str) -> Result<(), String> {
+        use security_framework::passwords::delete_generic_password;
+
+        match delete_generic_password(service, account) {
+            Ok(()) => Ok(()),
+            Err(e) if e.code() == -25300 => Ok(()), // errSecItemNotFound
+            Err(e) => Err(format!("Failed to delete keychain item: {}", e)),
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+impl SecretStore for KeychainStore {
+    fn get(&self, _service: &str, _account: &str) -> Result<Option<String>, String> {
+        Err("Keychain is only available on macOS".to_string())
+    }
+
+    fn set(&self, _service: &str, _account: &str, _value: &str) -> Result<(), String> {
+        Err("Keychain is only available on macOS".to_string())
+    }
+
+    fn delete(&self, _service: &str, _account: &str) -> Result<(), String> {
+        Err("Keychain is only available on macOS".to_string())
+    }
+}
+
+// ============================================================================
+// 插件密钥访问器
+// ============================================================================
+
+/// 插件密钥访问器（按插件 ID 和声明的名称限定）
+pub struct PluginSecrets {
+    /// 插件 ID
+    plugin_id: String,
+    /// 允许读取的密钥名称
+    allowed: HashSet<String>,
+    /// 存储后端
+    store: Arc<dyn SecretStore>,
+}
+
+impl PluginSecrets {
+    /// 创建访问器
+    pub fn new(plugin_id: &str, allowed: HashSet<String>, store: Arc<dyn SecretStore>) -> Self {
+        Self {
+            plugin_id: plugin_id.to_string(),
+            allowed,
+            store,
+        }
+    }
+
+    /// 使用 Keychain 后端，根据权限列表创建访问器
+    pub fn from_permissions(plugin_id: &str, permissions: &[String]) -> Self {
+        Self::new(plugin_id, declared_secrets(permissions), Arc::new(KeychainStore))
+    }
+
+    /// 读取密钥
+    pub fn get(&self, name: &str) -> Result<Option<String>, SecretsError> {
+        if !self.allowed.contains(name) {
+            log::warn!("[{}] 尝试读取未声明的密钥: {}", self.plugin_id, name);
+            return Err(SecretsError::NotDeclared(name.to_string()));
+        }
+        self.store
+            .get(&secrets_service(&self.plugin_id), name)
+            .map_err(SecretsError::Store)
+    }
+}
+
+// ============================================================================
+// JS API
+// ============================================================================
+
+/// Secrets API
+pub struct SecretsApi;
+
+impl SecretsApi {
+    /// 向上下文注入 `__cukSecrets` 全局对象
+    pub fn inject(ctx: &Ctx<'_>, secrets: Arc<PluginSecrets>) -> JsResult<()> {
+        let native = Object::new(ctx.clone())?;
+
+        native.set(
+            "get",
+            Function::new(ctx.clone(), move |ctx: Ctx<'_>, name: String| -> JsResult<Option<String>> {
+                secrets.get(&name).map_err(|e| {
+                    Exception::throw_message(&ctx, &format!("[{}] {}", e.code(), e))
+                })
+            })?,
+        )?;
+
+        // JS 包装层：Promise 化 + 错误转换
+        let wrap: Function = ctx.eval(
+            r#"(function(native) {
+                function toError(e) {
+                    var msg = String(e && e.message !== undefined ? e.message : e);
+                    var m = /^\[([A-Z_]+)\] ([\s\S]*)$/.exec(msg);
+                    if (m && typeof PluginError === 'function') return new PluginError(m[1], m[2], undefined);
+                    return e;
+                }
+                return Object.freeze({
+                    get: function(name) {
+                        try {
+                            var v = native.get(String(name));
+                            return Promise.resolve(v === null ? undefined : v);
+                        } catch (e) {
+                            return Promise.reject(toError(e));
+                        }
+                    }
+                });
+            })"#,
+        )?;
+        let api: Object = wrap.call((native,))?;
+        ctx.globals().set("__cukSecrets", api)?;
+
+        log::debug!("Secrets API 已注入");
+        Ok(())
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// 内存存储后端
+    struct MemoryStore(HashMap<(String, String), String>);
+
+    impl SecretStore for MemoryStore {
+        fn get(&self, service: &str, account: &str) -> Result<Option<String>, String> {
+            Ok(self.0.get(&(service.to_string(), account.to_string())).cloned())
+        }
+
+        fn set(&self, _service: &str, _account: &str, _value: &str) -> Result<(), String> {
+            Err("read-only".to_string())
+        }
+    }
+
+    fn test_secrets() -> PluginSecrets {
+        let mut items = HashMap::new();
+        items.insert(("cuk.plugin.demo".to_string(), "token".to_string()), "sk-1".to_string());
+        items.insert(("cuk.plugin.other".to_string(), "other".to_string()), "sk-2".to_string());
+        let permissions = vec![
+            "network".to_string(),
+            "secrets:token".to_string(),
+            "secrets:other".to_string(),
+            "secrets:bad name".to_string(),
+        ];
+        PluginSecrets::new("demo", declared_secrets(&permissions), Arc::new(MemoryStore(items)))
+    }
+
+    #[test]
+    fn test_declared_secrets() {
+        let declared = declared_secrets(&[
+            "secrets:token".to_string(),
+            "secrets:".to_string(),
+            "secrets:a/b".to_string(),
+            "storage".to_string(),
+        ]);
+        assert_eq!(declared.len(), 1);
+        assert!(declared.contains("token"));
+    }
+
+    #[test]
+    fn test_secrets_scoped_to_plugin() {
+        let secrets = test_secrets();
+        assert_eq!(secrets.get("token").unwrap(), Some("sk-1".to_string()));
+        // 已声明但属于其他插件命名空间
+        assert_eq!(secrets.get("other").unwrap(), None);
+        // 未声明
+        let err = secrets.get("missing").unwrap_err();
+        assert_eq!(err.code(), "PERMISSION_DENIED");
+    }
+
+    #[test]
+    fn test_secrets_js_api() {
+        let rt = rquickjs::Runtime::new().unwrap();
+        let ctx = rquickjs::Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            crate::plugin::sandbox::PluginErrorApi::inject(&ctx).unwrap();
+            SecretsApi::inject(&ctx, Arc::new(test_secrets())).unwrap();
+            ctx.eval::<(), _>(
+                r#"
+                var out = [];
+                __cukSecrets.get('token')
+                    .then(function(v) { out.push(v); return __cukSecrets.get('other'); })
+                    .then(function(v) { out.push(v === undefined); return __cukSecrets.get('missing'); })
+                    .catch(function(e) { out.push(e.type); });
+                "#,
+            )
+            .unwrap();
+            while ctx.execute_pending_job() {}
+            let result: String = ctx.eval("JSON.stringify(out)").unwrap();
+            assert_eq!(result, r#"["sk-1",true,"PERMISSION_DENIED"]"#);
+        });
+    }
+}
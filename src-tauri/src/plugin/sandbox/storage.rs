@@ -0,0 +1,512 @@
+// Storage API 实现
+// 插件私有的持久化键值存储 (context.storage)，每个插件一个 JSON 文件
+//
+// 提供给 JS 插件使用的键值存储（需声明 `storage` 权限），语义见
+// contracts/storage-cache-api.md:
+// - context.storage.get(key)
+// - context.storage.set(key, value)
+// - context.storage.delete(key)
+// - context.storage.keys()
+// - context.storage.clear()
+//
+// 安全设计：
+// 1. 每个插件独立命名空间（单独文件，插件 ID 校验防路径穿越）
+// 2. 总大小 / 单值大小 / 键数量配额，超限抛出 STORAGE_LIMIT
+// 3. 写入使用临时文件 + rename，单次 set 原子；写盘失败时回滚内存数据，与磁盘保持一致
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rquickjs::{Ctx, Exception, Function, Object, Result as JsResult};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+// ============================================================================
+// 常量
+// ============================================================================
+
+/// 默认存储配额: 1MB
+pub const DEFAULT_STORAGE_QUOTA: usize = 1024 * 1024;
+
+/// 单个值最大大小: 100KB
+const MAX_VALUE_SIZE: usize = 100 * 1024;
+
+/// 最大键数量
+const MAX_KEYS: usize = 1000;
+
+/// 最大键长度
+const MAX_KEY_LEN: usize = 256;
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+/// 存储错误
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("存储配额超限: {used} 字节，上限 {quota} 字节")]
+    QuotaExceeded { used: usize, quota: usize },
+
+    #[error("单个值过大: {size} 字节，上限 {max} 字节")]
+    ValueTooLarge { size: usize, max: usize },
+
+    #[error("键数量超限: 上限 {0}")]
+    TooManyKeys(usize),
+
+    #[error("无效的键: {0}")]
+    InvalidKey(String),
+
+    #[error("无效的插件 ID: {0}")]
+    InvalidPluginId(String),
+
+    #[error("序列化失败: {0}")]
+    Serialize(#[from] serde_json::Error),
+
+    #[error("IO 错误: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl StorageError {
+    /// 对应的 PluginErrorType 代码
+    pub fn code(&self) -> &'static str {
+        match self {
+            StorageError::QuotaExceeded { .. }
+            | StorageError::ValueTooLarge { .. }
+            | StorageError::TooManyKeys(_) => "STORAGE_LIMIT",
+            _ => "UNKNOWN",
+        }
+    }
+}
+
+// ============================================================================
+// 插件存储
+// ============================================================================
+
+/// 插件 ID 能否安全用作文件/目录名（防路径穿越）
+pub(crate) fn is_valid_plugin_id(plugin_id: &str) -> bool {
+    !plugin_id.is_empty()
+        && plugin_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !plugin_id.starts_with('.')
+}
+
+/// 插件存储占用统计（storage 文件 + cache 目录）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginStorageStats {
+    pub plugin_id: String,
+    /// storage 已用字节数
+    pub storage_bytes: u64,
+    /// storage 配额（字节）
+    pub storage_quota: u64,
+    /// cache 条目数
+    pub cache_entries: usize,
+    /// cache 已用字节数
+    pub cache_bytes: u64,
+    /// cache 配额（字节）
+    pub cache_quota: u64,
+}
+
+/// 插件键值存储（按插件隔离）
+pub struct PluginStorage {
+    /// 插件 ID
+    plugin_id: String,
+    /// 存储文件路径
+    path: PathBuf,
+    /// 配额（字节）
+    quota: usize,
+    /// 内存中的数据（写入时同步到磁盘）
+    data: Mutex<BTreeMap<String, JsonValue>>,
+}
+
+impl PluginStorage {
+    /// 默认存储目录 (~/.config/cuk/storage)
+    pub fn default_dir() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("cuk")
+            .join("storage")
+    }
+
+    /// 打开插件存储（文件不存在时为空存储）
+    pub fn open(dir: &Path, plugin_id: &str, quota: usize) -> Result<Self, StorageError> {
+        let path = Self::file_path(dir, plugin_id)?;
+
+        let data = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                log::warn!("[{}] 存储文件损坏，已忽略: {}", plugin_id, e);
+                BTreeMap::new()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self {
+            plugin_id: plugin_id.to_string(),
+            path,
+            quota,
+            data: Mutex::new(data),
+        })
+    }
+
+    /// 删除插件存储文件（卸载插件时调用）
+    pub fn remove(dir: &Path, plugin_id: &str) -> Result<(), StorageError> {
+        let path = Self::file_path(dir, plugin_id)?;
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 插件存储文件大小（文件不存在时为 0）
+    pub fn disk_usage(dir: &Path, plugin_id: &str) -> Result<u64, StorageError> {
+        let path = Self::file_path(dir, plugin_id)?;
+        match std::fs::metadata(&path) {
+            Ok(meta) => Ok(meta.len()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 计算插件存储文件路径
+    fn file_path(dir: &Path, plugin_id: &str) -> Result<PathBuf, StorageError> {
+        if !is_valid_plugin_id(plugin_id) {
+            return Err(StorageError::InvalidPluginId(plugin_id.to_string()));
+        }
+        Ok(dir.join(format!("{}.json", plugin_id)))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, JsonValue>> {
+        self.data.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 键名校验：1-256 字符，仅限 `[a-zA-Z0-9_-]`
+    fn check_key(key: &str) -> Result<(), StorageError> {
+        if key.is_empty() || key.len() > MAX_KEY_LEN {
+            return Err(StorageError::InvalidKey(format!(
+                "长度须在 1-{} 之间",
+                MAX_KEY_LEN
+            )));
+        }
+        if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(StorageError::InvalidKey(format!(
+                "{} 只能包含字母、数字、下划线和连字符",
+                key
+            )));
+        }
+        Ok(())
+    }
+
+    /// 读取值
+    pub fn get(&self, key: &str) -> Option<JsonValue> {
+        self.lock().get(key).cloned()
+    }
+
+    /// 写入值（超出配额或写盘失败时拒绝且不修改）
+    pub fn set(&self, key: &str, value: JsonValue) -> Result<(), StorageError> {
+        Self::check_key(key)?;
+
+        let size = serde_json::to_vec(&value)?.len();
+        if size > MAX_VALUE_SIZE {
+            return Err(StorageError::ValueTooLarge {
+                size,
+                max: MAX_VALUE_SIZE,
+            });
+        }
+
+        let mut data = self.lock();
+        if !data.contains_key(key) && data.len() >= MAX_KEYS {
+            return Err(StorageError::TooManyKeys(MAX_KEYS));
+        }
+        let previous = data.insert(key.to_string(), value);
+        let result = serde_json::to_vec(&*data)
+            .map_err(StorageError::from)
+            .and_then(|bytes| {
+                if bytes.len() > self.quota {
+                    return Err(StorageError::QuotaExceeded {
+                        used: bytes.len(),
+                        quota: self.quota,
+                    });
+                }
+                self.persist(&bytes)
+            });
+
+        if result.is_err() {
+            // 回滚
+            match previous {
+                Some(v) => data.insert(key.to_string(), v),
+                None => data.remove(key),
+            };
+        }
+        result
+    }
+
+    /// 删除值，返回键是否存在（写盘失败时恢复该键）
+    pub fn delete(&self, key: &str) -> Result<bool, StorageError> {
+        let mut data = self.lock();
+        let Some(previous) = data.remove(key) else {
+            return Ok(false);
+        };
+        let result = serde_json::to_vec(&*data)
+            .map_err(StorageError::from)
+            .and_then(|bytes| self.persist(&bytes));
+        if let Err(e) = result {
+            data.insert(key.to_string(), previous);
+            return Err(e);
+        }
+        Ok(true)
+    }
+
+    /// 列出所有键
+    pub fn keys(&self) -> Vec<String> {
+        self.lock().keys().cloned().collect()
+    }
+
+    /// 清空存储（写盘失败时保留原数据）
+    pub fn clear(&self) -> Result<(), StorageError> {
+        let mut data = self.lock();
+        let bytes = serde_json::to_vec(&BTreeMap::<String, JsonValue>::new())?;
+        self.persist(&bytes)?;
+        data.clear();
+        Ok(())
+    }
+
+    /// 原子写入磁盘
+    fn persist(&self, bytes: &[u8]) -> Result<(), StorageError> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, bytes)?;
+        std::fs::rename(&tmp, &self.path)?;
+        log::debug!("[{}] 存储已写入 ({} bytes)", self.plugin_id, bytes.len());
+        Ok(())
+    }
+}
+
+// ============================================================================
+// JS API
+// ============================================================================
+
+/// Storage API
+pub struct StorageApi;
+
+impl StorageApi {
+    /// 向上下文注入 `__cukStorage` 全局对象
+    ///
+    /// 原生函数以 JSON 字符串交换数据，由 JS 包装层负责序列化，
+    /// 并按契约返回 Promise、将错误转换为 PluginError。
+    pub fn inject(ctx: &Ctx<'_>, storage: Arc<PluginStorage>) -> JsResult<()> {
+        let native = Object::new(ctx.clone())?;
+
+        let s = storage.clone();
+        native.set(
+            "get",
+            Function::new(ctx.clone(), move |key: String| -> Option<String> {
+                s.get(&key).map(|v| v.to_string())
+            })?,
+        )?;
+
+        let s = storage.clone();
+        native.set(
+            "set",
+            Function::new(ctx.clone(), move |ctx: Ctx<'_>, key: String, json: String| -> JsResult<()> {
+                let value: JsonValue = serde_json::from_str(&json)
+                    .map_err(|e| throw_storage_error(&ctx, &StorageError::Serialize(e)))?;
+                s.set(&key, value).map_err(|e| throw_storage_error(&ctx, &e))
+            })?,
+        )?;
+
+        let s = storage.clone();
+        native.set(
+            "delete",
+            Function::new(ctx.clone(), move |ctx: Ctx<'_>, key: String| -> JsResult<bool> {
+                s.delete(&key).map_err(|e| throw_storage_error(&ctx, &e))
+            })?,
+        )?;
+
+        let s = storage.clone();
+        native.set(
+            "keys",
+            Function::new(ctx.clone(), move || -> Vec<String> { s.keys() })?,
+        )?;
+
+        let s = storage;
+        native.set(
+            "clear",
+            Function::new(ctx.clone(), move |ctx: Ctx<'_>| -> JsResult<()> {
+                s.clear().map_err(|e| throw_storage_error(&ctx, &e))
+            })?,
+        )?;
+
+        // JS 包装层：JSON 序列化 + Promise 化 + 错误转换
+        let wrap: Function = ctx.eval(
+            r#"(function(native) {
+                function toError(e) {
+                    var msg = String(e && e.message !== undefined ? e.message : e);
+                    var m = /^\[([A-Z_]+)\] ([\s\S]*)$/.exec(msg);
+                    if (m && typeof PluginError === 'function') return new PluginError(m[1], m[2], undefined);
+                    return e;
+                }
+                function run(fn) {
+                    try { return Promise.resolve(fn()); }
+                    catch (e) { return Promise.reject(toError(e)); }
+                }
+                return Object.freeze({
+                    get: function(key) {
+                        return run(function() {
+                            var s = native.get(String(key));
+                            return s === undefined || s === null ? undefined : JSON.parse(s);
+                        });
+                    },
+                    set: function(key, value) {
+                        return run(function() {
+                            var json = JSON.stringify(value);
+                            if (json === undefined) throw new TypeError('storage.set: value must be JSON serializable');
+                            native.set(String(key), json);
+                        });
+                    },
+                    delete: function(key) {
+                        return run(function() { return native.delete(String(key)); });
+                    },
+                    keys: function() {
+                        return run(function() { return native.keys(); });
+                    },
+                    clear: function() {
+                        return run(function() { native.clear(); });
+                    }
+                });
+            })"#,
+        )?;
+        let api: Object = wrap.call((native,))?;
+        ctx.globals().set("__cukStorage", api)?;
+
+        log::debug!("Storage API 已注入");
+        Ok(())
+    }
+}
+
+/// 抛出带错误类型前缀的异常（由 JS 包装层转换为 PluginError）
+fn throw_storage_error(ctx: &Ctx<'_>, err: &StorageError) -> rquickjs::Error {
+    Exception::throw_message(ctx, &format!("[{}] {}", err.code(), err))
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_storage_roundtrip_and_persist() {
+        let dir = TempDir::new().unwrap();
+        let storage = PluginStorage::open(dir.path(), "demo", DEFAULT_STORAGE_QUOTA).unwrap();
+
+        storage.set("token", json!({"v": 1})).unwrap();
+        storage.set("count", json!(3)).unwrap();
+        assert_eq!(storage.get("token"), Some(json!({"v": 1})));
+        assert_eq!(storage.keys(), vec!["count".to_string(), "token".to_string()]);
+        assert!(storage.delete("count").unwrap());
+        assert!(!storage.delete("count").unwrap());
+
+        // 重新打开后数据仍在
+        let reopened = PluginStorage::open(dir.path(), "demo", DEFAULT_STORAGE_QUOTA).unwrap();
+        assert_eq!(reopened.keys(), vec!["token".to_string()]);
+
+        // 命名空间隔离
+        let other = PluginStorage::open(dir.path(), "other", DEFAULT_STORAGE_QUOTA).unwrap();
+        assert!(other.get("token").is_none());
+
+        PluginStorage::remove(dir.path(), "demo").unwrap();
+        let removed = PluginStorage::open(dir.path(), "demo", DEFAULT_STORAGE_QUOTA).unwrap();
+        assert!(removed.keys().is_empty());
+    }
+
+    #[test]
+    fn test_storage_quota() {
+        let dir = TempDir::new().unwrap();
+        let storage = PluginStorage::open(dir.path(), "demo", 64).unwrap();
+
+        storage.set("a", json!("small")).unwrap();
+        let err = storage.set("b", json!("x".repeat(100))).unwrap_err();
+        assert!(matches!(err, StorageError::QuotaExceeded { .. }));
+        assert_eq!(err.code(), "STORAGE_LIMIT");
+        // 超额写入被回滚
+        assert!(storage.get("b").is_none());
+        assert_eq!(storage.get("a"), Some(json!("small")));
+    }
+
+    #[test]
+    fn test_storage_rolls_back_when_persist_fails() {
+        let dir = TempDir::new().unwrap();
+        let storage = PluginStorage::open(dir.path(), "demo", DEFAULT_STORAGE_QUOTA).unwrap();
+        storage.set("a", json!(1)).unwrap();
+
+        // 临时文件路径被目录占用，写盘失败
+        let tmp = dir.path().join("demo.json.tmp");
+        std::fs::create_dir(&tmp).unwrap();
+        assert!(matches!(storage.set("a", json!(2)), Err(StorageError::Io(_))));
+        assert!(matches!(storage.set("b", json!(3)), Err(StorageError::Io(_))));
+        assert!(storage.delete("a").is_err());
+        assert!(storage.clear().is_err());
+
+        // 内存数据与磁盘一致
+        assert_eq!(storage.get("a"), Some(json!(1)));
+        assert!(storage.get("b").is_none());
+        let reopened = PluginStorage::open(dir.path(), "demo", DEFAULT_STORAGE_QUOTA).unwrap();
+        assert_eq!(reopened.keys(), storage.keys());
+
+        std::fs::remove_dir(&tmp).unwrap();
+        storage.set("b", json!(3)).unwrap();
+        assert_eq!(storage.keys(), vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_storage_rejects_bad_ids_and_keys() {
+        let dir = TempDir::new().unwrap();
+        assert!(PluginStorage::open(dir.path(), "../evil", 1024).is_err());
+        assert!(PluginStorage::open(dir.path(), ".hidden", 1024).is_err());
+        assert!(PluginStorage::open(dir.path(), "", 1024).is_err());
+
+        let storage = PluginStorage::open(dir.path(), "demo", 1024).unwrap();
+        assert!(matches!(storage.set("a:b", json!(1)), Err(StorageError::InvalidKey(_))));
+        assert!(matches!(storage.set("", json!(1)), Err(StorageError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn test_storage_js_api() {
+        let dir = TempDir::new().unwrap();
+        let storage = Arc::new(PluginStorage::open(dir.path(), "demo", 64).unwrap());
+
+        let rt = rquickjs::Runtime::new().unwrap();
+        let ctx = rquickjs::Context::full(&rt).unwrap();
+        ctx.with(|ctx| {
+            crate::plugin::sandbox::PluginErrorApi::inject(&ctx).unwrap();
+            StorageApi::inject(&ctx, storage.clone()).unwrap();
+            ctx.eval::<(), _>(
+                r#"
+                var out = [];
+                __cukStorage.set('k', {a: [1, 2]})
+                    .then(function() { return __cukStorage.get('k'); })
+                    .then(function(v) { out.push(JSON.stringify(v)); return __cukStorage.get('none'); })
+                    .then(function(v) { out.push(v === undefined); return __cukStorage.set('big', 'x'.repeat(100)); })
+                    .catch(function(e) { out.push(e.type); return __cukStorage.keys(); })
+                    .then(function(keys) { out.push(keys.join(',')); });
+                "#,
+            )
+            .unwrap();
+            while ctx.execute_pending_job() {}
+            let result: String = ctx.eval("JSON.stringify(out)").unwrap();
+            assert_eq!(result, r#"["{\"a\":[1,2]}",true,"STORAGE_LIMIT","k"]"#);
+        });
+
+        assert_eq!(storage.get("k"), Some(json!({"a": [1, 2]})));
+    }
+}
@@ -1,8 +1,15 @@
 // Timer API 实现
 // Phase 2.2.8: setTimeout/clearTimeout
+// Phase 2.2.9: 宿主调度的持久定时器 (context.timers)
 //
 // 提供给 JS 插件使用的定时器功能，带数量限制
 //
+// 两类定时器:
+// 1. setTimeout/setInterval: 执行期定时器，回调在沙盒的 JS 任务队列中执行，
+//    执行结束（结果返回或超时）时全部取消，不会在沙盒回收后触发
+// 2. context.timers.schedule(name, ...): 持久定时器，只在本次执行中记录请求，
+//    执行结束后由宿主 (plugin::host_timer) 调度，到期时在新的沙盒中调用插件的 onTimer
+//
 // 安全设计（基于最佳实践）：
 // 1. 使用 Semaphore 限制并发定时器数量（防止 spawn flooding DoS）
 // 2. Permit 在 spawn 前同步获取，确保真正占位
@@ -15,7 +22,8 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, MutexGuard, PoisonError};
 
-use rquickjs::{Ctx, Exception, Function, Result as JsResult};
+use rquickjs::function::Opt;
+use rquickjs::{Ctx, Exception, Function, Object, Result as JsResult};
 use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
 use tokio_util::sync::CancellationToken;
 
@@ -44,20 +52,56 @@ const MAX_TIMERS: usize = 100;
 /// 最大延时 (60 秒)
 const MAX_DELAY_MS: u64 = 60_000;
 
+/// 持久定时器最小间隔 (1 秒)
+pub const MIN_HOST_TIMER_INTERVAL_MS: u64 = 1_000;
+
+/// 持久定时器最大延时/间隔 (24 小时)
+pub const MAX_HOST_TIMER_DELAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// 单次执行最多登记的持久定时器请求数
+const MAX_HOST_TIMER_REQUESTS: usize = 32;
+
+/// 持久定时器名称最大长度
+const MAX_HOST_TIMER_NAME_LEN: usize = 64;
+
+/// 持久定时器附带数据的最大长度（JSON 序列化后）
+const MAX_HOST_TIMER_DATA_SIZE: usize = 16 * 1024;
+
+/// 持久定时器请求（执行结束后由宿主处理）
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostTimerRequest {
+    /// 登记定时器，同名定时器会被替换
+    Schedule {
+        /// 定时器名称（插件内唯一）
+        name: String,
+        /// 首次触发延时 (ms)
+        delay_ms: u64,
+        /// 重复间隔 (ms)，None 表示只触发一次
+        interval_ms: Option<u64>,
+        /// 触发时传给 onTimer 的数据
+        data: serde_json::Value,
+    },
+    /// 取消定时器
+    Cancel {
+        /// 定时器名称
+        name: String,
+    },
+}
+
 /// Timer API
 pub struct TimerApi;
 
 impl TimerApi {
     /// 向上下文注入 timer 相关函数
     /// 注意：需要传入 TimerRegistry 以跟踪定时器
-    pub fn inject(ctx: &Ctx<'_>, registry: Arc<TimerRegistry>) -> JsResult<()> {
+    pub fn inject<'js>(ctx: &Ctx<'js>, registry: Arc<TimerRegistry>) -> JsResult<()> {
         let globals = ctx.globals();
 
         // setTimeout
         let registry_clone = registry.clone();
         globals.set(
             "setTimeout",
-            Function::new(ctx.clone(), move |ctx: Ctx<'_>, callback: Function<'_>, delay: Option<u64>| {
+            Function::new(ctx.clone(), move |ctx: Ctx<'js>, callback: Function<'js>, delay: Option<u64>| {
                 set_timeout(&ctx, callback, delay, registry_clone.clone())
             })?,
         )?;
@@ -75,7 +119,7 @@ impl TimerApi {
         let registry_clone = registry.clone();
         globals.set(
             "setInterval",
-            Function::new(ctx.clone(), move |ctx: Ctx<'_>, callback: Function<'_>, delay: Option<u64>| {
+            Function::new(ctx.clone(), move |ctx: Ctx<'js>, callback: Function<'js>, delay: Option<u64>| {
                 set_interval(&ctx, callback, delay, registry_clone.clone())
             })?,
         )?;
@@ -89,6 +133,25 @@ impl TimerApi {
             })?,
         )?;
 
+        // __cukTimers: context.timers 的底层实现（持久定时器）
+        let timers = Object::new(ctx.clone())?;
+        let registry_clone = registry.clone();
+        timers.set(
+            "schedule",
+            Function::new(ctx.clone(), move |ctx: Ctx<'js>, name: String, options: Opt<Object<'js>>| {
+                schedule_host_timer(&ctx, name, options.0, &registry_clone)
+            })?,
+        )?;
+        let registry_clone = registry.clone();
+        timers.set(
+            "cancel",
+            Function::new(ctx.clone(), move |ctx: Ctx<'_>, name: String| {
+                validate_host_timer_name(&ctx, &name)?;
+                push_host_request(&ctx, &registry_clone, HostTimerRequest::Cancel { name })
+            })?,
+        )?;
+        globals.set("__cukTimers", timers)?;
+
         log::debug!("Timer API 已注入");
         Ok(())
     }
@@ -128,6 +191,8 @@ pub struct TimerRegistry {
     pending_tokens: std::sync::Mutex<HashMap<u64, CancellationToken>>,
     /// 活跃的定时器（用于取消操作）
     timers: Mutex<HashMap<u64, TimerEntry>>,
+    /// 本次执行登记的持久定时器请求
+    host_requests: std::sync::Mutex<Vec<HostTimerRequest>>,
 }
 
 impl TimerRegistry {
@@ -138,9 +203,15 @@ impl TimerRegistry {
             semaphore: Arc::new(Semaphore::new(MAX_TIMERS)),
             pending_tokens: std::sync::Mutex::new(HashMap::new()),
             timers: Mutex::new(HashMap::new()),
+            host_requests: std::sync::Mutex::new(Vec::new()),
         }
     }
 
+    /// 取出本次执行登记的持久定时器请求（按登记顺序）
+    pub fn take_host_requests(&self) -> Vec<HostTimerRequest> {
+        std::mem::take(&mut *recover_lock(self.host_requests.lock()))
+    }
+
     /// 尝试获取定时器槽位（同步版本，在 spawn 前调用）
     ///
     /// 返回 (id, permit, cancel_token)，permit 持有期间占用槽位
@@ -311,9 +382,11 @@ impl Default for TimerRegistry {
 // ============================================================================
 
 /// setTimeout 实现
-fn set_timeout(
-    ctx: &Ctx<'_>,
-    _callback: Function<'_>,
+///
+/// 回调通过 `ctx.spawn` 在沙盒的 JS 任务队列中执行，只在等待执行结果期间被驱动
+fn set_timeout<'js>(
+    ctx: &Ctx<'js>,
+    callback: Function<'js>,
     delay: Option<u64>,
     registry: Arc<TimerRegistry>,
 ) -> JsResult<u64> {
@@ -324,17 +397,16 @@ fn set_timeout(
         Some(tuple) => tuple,
         None => {
             log::warn!("定时器数量超限 ({}), 拒绝创建新定时器", MAX_TIMERS);
-            let msg = format!("Timer limit exceeded (max {})", MAX_TIMERS);
-            let exception = Exception::from_message(ctx.clone(), &msg)?;
-            return Err(ctx.throw(exception.into_object().into_value()));
+            return Err(throw_error(ctx, &format!("Timer limit exceeded (max {})", MAX_TIMERS)));
         }
     };
 
     let registry_clone = registry.clone();
     let cancel_token_clone = cancel_token.clone();
+    let js_ctx = ctx.clone();
 
     // 启动定时器任务（permit 已获取，槽位已占用）
-    tokio::spawn(async move {
+    ctx.spawn(async move {
         // 先检查是否在 spawn 前已被取消（解决竞态）
         if cancel_token.is_cancelled() {
             log::trace!("定时器 {} 在启动前已被取消", id);
@@ -358,10 +430,9 @@ fn set_timeout(
                 registry_clone.complete(id).await;
             }
             _ = tokio::time::sleep(std::time::Duration::from_millis(delay)) => {
-                // 执行回调
-                // 注意：实际实现中需要通过消息队列在 JS 线程中执行
                 log::trace!("定时器 {} 触发", id);
                 registry_clone.complete(id).await;
+                invoke_callback(&js_ctx, &callback, id);
             }
         }
     });
@@ -378,9 +449,9 @@ fn clear_timeout(id: u64, registry: Arc<TimerRegistry>) {
 }
 
 /// setInterval 实现
-fn set_interval(
-    ctx: &Ctx<'_>,
-    _callback: Function<'_>,
+fn set_interval<'js>(
+    ctx: &Ctx<'js>,
+    callback: Function<'js>,
     delay: Option<u64>,
     registry: Arc<TimerRegistry>,
 ) -> JsResult<u64> {
@@ -391,17 +462,16 @@ fn set_interval(
         Some(tuple) => tuple,
         None => {
             log::warn!("定时器数量超限 ({}), 拒绝创建 interval", MAX_TIMERS);
-            let msg = format!("Timer limit exceeded (max {})", MAX_TIMERS);
-            let exception = Exception::from_message(ctx.clone(), &msg)?;
-            return Err(ctx.throw(exception.into_object().into_value()));
+            return Err(throw_error(ctx, &format!("Timer limit exceeded (max {})", MAX_TIMERS)));
         }
     };
 
     let registry_clone = registry.clone();
     let cancel_token_clone = cancel_token.clone();
+    let js_ctx = ctx.clone();
 
     // 启动定时器任务
-    tokio::spawn(async move {
+    ctx.spawn(async move {
         // 先检查是否在 spawn 前已被取消（解决竞态）
         if cancel_token.is_cancelled() {
             log::trace!("Interval {} 在启动前已被取消", id);
@@ -416,7 +486,9 @@ fn set_interval(
             return;
         }
 
-        let mut interval = tokio::time::interval(std::time::Duration::from_millis(delay));
+        // 首次触发在一个间隔之后（与浏览器语义一致）
+        let period = std::time::Duration::from_millis(delay);
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
 
         loop {
             tokio::select! {
@@ -426,8 +498,8 @@ fn set_interval(
                     break;
                 }
                 _ = interval.tick() => {
-                    // 触发回调 (通过消息队列)
                     log::trace!("Interval {} 触发", id);
+                    invoke_callback(&js_ctx, &callback, id);
                 }
             }
         }
@@ -438,6 +510,138 @@ fn set_interval(
     Ok(id)
 }
 
+/// 执行定时器回调，回调抛出的异常只记录日志
+fn invoke_callback<'js>(ctx: &Ctx<'js>, callback: &Function<'js>, id: u64) {
+    if let Err(e) = callback.call::<_, ()>(()) {
+        let detail = match e {
+            rquickjs::Error::Exception => format!("{:?}", ctx.catch()),
+            e => e.to_string(),
+        };
+        log::warn!("定时器 {} 回调执行失败: {}", id, detail);
+    }
+}
+
+/// 构造并抛出 JS 异常
+fn throw_error(ctx: &Ctx<'_>, message: &str) -> rquickjs::Error {
+    match Exception::from_message(ctx.clone(), message) {
+        Ok(exception) => ctx.throw(exception.into_object().into_value()),
+        Err(e) => e,
+    }
+}
+
+// ============================================================================
+// 持久定时器 (context.timers)
+// ============================================================================
+
+/// 校验持久定时器名称
+fn validate_host_timer_name(ctx: &Ctx<'_>, name: &str) -> JsResult<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_HOST_TIMER_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+    if valid {
+        Ok(())
+    } else {
+        Err(throw_error(
+            ctx,
+            &format!(
+                "Invalid timer name '{}': 1-{} characters of [A-Za-z0-9-_.:]",
+                name, MAX_HOST_TIMER_NAME_LEN
+            ),
+        ))
+    }
+}
+
+/// 读取可选的毫秒数选项
+fn read_millis(ctx: &Ctx<'_>, options: Option<&Object<'_>>, key: &str) -> JsResult<Option<u64>> {
+    let Some(value) = options.map(|o| o.get::<_, Option<f64>>(key)).transpose()?.flatten() else {
+        return Ok(None);
+    };
+    if !value.is_finite() || value < 0.0 {
+        return Err(throw_error(ctx, &format!("timers.schedule: {} must be a non-negative number", key)));
+    }
+    Ok(Some(value as u64))
+}
+
+/// context.timers.schedule(name, { delayMs?, intervalMs?, data? })
+///
+/// 至少指定 delayMs 或 intervalMs 之一；只指定 intervalMs 时首次触发在一个间隔之后
+fn schedule_host_timer<'js>(
+    ctx: &Ctx<'js>,
+    name: String,
+    options: Option<Object<'js>>,
+    registry: &TimerRegistry,
+) -> JsResult<()> {
+    validate_host_timer_name(ctx, &name)?;
+
+    let delay_ms = read_millis(ctx, options.as_ref(), "delayMs")?;
+    let interval_ms = read_millis(ctx, options.as_ref(), "intervalMs")?;
+    if let Some(interval) = interval_ms {
+        if !(MIN_HOST_TIMER_INTERVAL_MS..=MAX_HOST_TIMER_DELAY_MS).contains(&interval) {
+            return Err(throw_error(
+                ctx,
+                &format!(
+                    "timers.schedule: intervalMs must be between {} and {}",
+                    MIN_HOST_TIMER_INTERVAL_MS, MAX_HOST_TIMER_DELAY_MS
+                ),
+            ));
+        }
+    }
+    let delay_ms = match delay_ms.or(interval_ms) {
+        Some(delay) if delay <= MAX_HOST_TIMER_DELAY_MS => delay,
+        Some(_) => {
+            return Err(throw_error(
+                ctx,
+                &format!("timers.schedule: delayMs must not exceed {}", MAX_HOST_TIMER_DELAY_MS),
+            ))
+        }
+        None => return Err(throw_error(ctx, "timers.schedule: delayMs or intervalMs is required")),
+    };
+
+    let data = match options.map(|o| o.get::<_, rquickjs::Value>("data")).transpose()? {
+        Some(value) if !value.is_undefined() => {
+            let json = ctx
+                .json_stringify(value)?
+                .map(|s| s.to_string())
+                .transpose()?
+                .unwrap_or_else(|| "null".to_string());
+            if json.len() > MAX_HOST_TIMER_DATA_SIZE {
+                return Err(throw_error(
+                    ctx,
+                    &format!("timers.schedule: data exceeds {} bytes", MAX_HOST_TIMER_DATA_SIZE),
+                ));
+            }
+            serde_json::from_str(&json).unwrap_or(serde_json::Value::Null)
+        }
+        _ => serde_json::Value::Null,
+    };
+
+    push_host_request(
+        ctx,
+        registry,
+        HostTimerRequest::Schedule {
+            name,
+            delay_ms,
+            interval_ms,
+            data,
+        },
+    )
+}
+
+/// 记录持久定时器请求（单次执行数量受限）
+fn push_host_request(ctx: &Ctx<'_>, registry: &TimerRegistry, request: HostTimerRequest) -> JsResult<()> {
+    let mut requests = recover_lock(registry.host_requests.lock());
+    if requests.len() >= MAX_HOST_TIMER_REQUESTS {
+        return Err(throw_error(
+            ctx,
+            &format!("Too many timer requests in one execution (max {})", MAX_HOST_TIMER_REQUESTS),
+        ));
+    }
+    requests.push(request);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,263 @@
+// Phase 2.3.8: 插件状态持久化
+// 插件的启用状态和配置保存到 state.json（与插件目录同级，即 ~/.config/cuk/state.json），
+// 启动发现插件时恢复，避免每次启动插件全部变为禁用、配置丢失
+//
+// 设计要点:
+// 1. 启用/禁用/配置变更/卸载时立即写入（临时文件 + rename）
+// 2. 文件带 version 字段，加载时逐级迁移到当前版本
+// 3. 版本高于当前程序（降级运行）时不覆盖原文件，先备份再从空状态开始
+// 4. 数据源失效跟踪（gone_since / possibly_defunct）随状态保存，重启后继续计时
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 当前状态文件版本
+pub const STATE_VERSION: u32 = 1;
+
+/// 单个插件的持久化状态
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PersistedPluginState {
+    /// 是否启用
+    pub enabled: bool,
+    /// 插件配置
+    pub config: HashMap<String, serde_json::Value>,
+    /// 同一插件的其他账户（键为实例 ID，默认账户为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<PersistedAccount>,
+    /// 首次出现"服务已不存在"错误的时间（期间无成功调用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gone_since: Option<DateTime<Utc>>,
+    /// 数据源可能已失效（停止调度）
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub possibly_defunct: bool,
+}
+
+/// 插件账户（Phase 7.14）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedAccount {
+    /// 所属插件 ID
+    pub plugin_id: String,
+    /// 账户名称
+    pub label: String,
+}
+
+/// 状态文件内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginStateFile {
+    /// 文件格式版本
+    pub version: u32,
+    /// 插件 ID -> 持久化状态
+    #[serde(default)]
+    pub plugins: BTreeMap<String, PersistedPluginState>,
+}
+
+impl Default for PluginStateFile {
+    fn default() -> Self {
+        Self {
+            version: STATE_VERSION,
+            plugins: BTreeMap::new(),
+        }
+    }
+}
+
+/// 将任意版本的状态文件迁移到当前版本
+///
+/// - v0: 无 version 字段，顶层直接为 `{ 插件 ID: { enabled, config } }`
+/// - v1: `{ version: 1, plugins: { 插件 ID: { enabled, config } } }`
+pub fn migrate(mut value: serde_json::Value) -> Result<PluginStateFile, String> {
+    let mut version = match value.get("version") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| format!("无效的状态文件版本: {}", v))?,
+    };
+    if version > STATE_VERSION {
+        return Err(format!("状态文件版本 {} 高于当前支持的版本 {}", version, STATE_VERSION));
+    }
+
+    while version < STATE_VERSION {
+        value = match version {
+            0 => serde_json::json!({ "version": 1, "plugins": value }),
+            _ => unreachable!("缺少状态文件 v{} 的迁移步骤", version),
+        };
+        version += 1;
+    }
+
+    serde_json::from_value(value).map_err(|e| format!("状态文件格式错误: {}", e))
+}
+
+/// 插件状态存储
+pub struct PluginStateStore {
+    state: Mutex<PluginStateFile>,
+    /// 持久化路径
+    path: PathBuf,
+}
+
+impl PluginStateStore {
+    /// 默认文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir.parent().unwrap_or(plugins_dir).join("state.json")
+    }
+
+    /// 从文件加载并迁移到当前版本（文件不存在或无法解析时从空状态开始）
+    pub fn load(path: PathBuf) -> Self {
+        let state = match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                let migrated = serde_json::from_str(&content)
+                    .map_err(|e| e.to_string())
+                    .and_then(migrate);
+                match migrated {
+                    Ok(state) => state,
+                    Err(e) => {
+                        // 保留原文件，避免后续写入覆盖无法识别的状态
+                        let backup = path.with_extension("json.bak");
+                        log::warn!("加载插件状态失败，已备份到 {:?} 并从空状态开始: {}", backup, e);
+                        if let Err(e) = std::fs::rename(&path, &backup) {
+                            log::warn!("备份插件状态文件失败: {}", e);
+                        }
+                        PluginStateFile::default()
+                    }
+                }
+            }
+            Err(_) => PluginStateFile::default(),
+        };
+        Self {
+            state: Mutex::new(state),
+            path,
+        }
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    fn save(&self, state: &PluginStateFile) {
+        let path = &self.path;
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_vec_pretty(state)?)?;
+            std::fs::rename(&tmp, path)
+        })();
+        if let Err(e) = result {
+            log::warn!("保存插件状态失败: {}", e);
+        }
+    }
+
+    /// 插件的持久化状态
+    pub fn get(&self, plugin_id: &str) -> Option<PersistedPluginState> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.plugins.get(plugin_id).cloned()
+    }
+
+    /// 记录启用状态
+    pub fn set_enabled(&self, plugin_id: &str, enabled: bool) {
+        self.update(plugin_id, |plugin| plugin.enabled = enabled);
+    }
+
+    /// 记录插件配置
+    pub fn set_config(&self, plugin_id: &str, config: &HashMap<String, serde_json::Value>) {
+        self.update(plugin_id, |plugin| plugin.config = config.clone());
+    }
+
+    /// 记录账户实例（创建账户时）
+    pub fn set_account(&self, instance_id: &str, account: PersistedAccount) {
+        self.update(instance_id, |plugin| plugin.account = Some(account));
+    }
+
+    /// 记录数据源失效跟踪状态（状态未变化时不写文件）
+    pub fn set_defunct(&self, plugin_id: &str, gone_since: Option<DateTime<Utc>>, possibly_defunct: bool) {
+        {
+            let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let current = state.plugins.get(plugin_id);
+            let unchanged = match current {
+                Some(plugin) => plugin.gone_since == gone_since && plugin.possibly_defunct == possibly_defunct,
+                None => gone_since.is_none() && !possibly_defunct,
+            };
+            if unchanged {
+                return;
+            }
+        }
+        self.update(plugin_id, |plugin| {
+            plugin.gone_since = gone_since;
+            plugin.possibly_defunct = possibly_defunct;
+        });
+    }
+
+    /// 所有已保存的账户实例（实例 ID 与账户信息）
+    pub fn accounts(&self) -> Vec<(String, PersistedAccount)> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .plugins
+            .iter()
+            .filter_map(|(id, plugin)| plugin.account.clone().map(|account| (id.clone(), account)))
+            .collect()
+    }
+
+    /// 删除插件的状态（卸载时）
+    pub fn remove(&self, plugin_id: &str) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.plugins.remove(plugin_id).is_some() {
+            self.save(&state);
+        }
+    }
+
+    fn update(&self, plugin_id: &str, apply: impl FnOnce(&mut PersistedPluginState)) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let plugin = state.plugins.entry(plugin_id.to_string()).or_default();
+        let before = plugin.clone();
+        apply(plugin);
+        if *plugin != before {
+            self.save(&state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_state_persistence_and_migration() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        let store = PluginStateStore::load(path.clone());
+        store.set_enabled("claude", true);
+        store.set_config("claude", &HashMap::from([("plan".to_string(), serde_json::json!("pro"))]));
+        store.set_enabled("openai", true);
+        store.remove("openai");
+        let work = PersistedAccount { plugin_id: "claude".to_string(), label: "Work".to_string() };
+        store.set_account("claude@2", work.clone());
+        let gone_since = chrono::Utc::now() - chrono::Duration::days(3);
+        store.set_defunct("claude", Some(gone_since), true);
+        // 未跟踪的插件不创建状态
+        store.set_defunct("gemini", None, false);
+
+        let reloaded = PluginStateStore::load(path.clone());
+        let claude = reloaded.get("claude").unwrap();
+        assert!(claude.enabled);
+        assert_eq!(claude.config["plan"], "pro");
+        assert_eq!(claude.gone_since, Some(gone_since));
+        assert!(claude.possibly_defunct);
+        assert_eq!(reloaded.get("gemini"), None);
+        assert_eq!(reloaded.get("openai"), None);
+        assert_eq!(reloaded.accounts(), vec![("claude@2".to_string(), work)]);
+
+        // v0（无版本字段）迁移到当前版本
+        std::fs::write(&path, r#"{"claude": {"enabled": true}}"#).unwrap();
+        assert!(PluginStateStore::load(path.clone()).get("claude").unwrap().enabled);
+
+        // 更高版本的文件不被覆盖
+        std::fs::write(&path, r#"{"version": 99, "plugins": {}}"#).unwrap();
+        assert_eq!(PluginStateStore::load(path.clone()).get("claude"), None);
+        assert!(dir.path().join("state.json.bak").exists());
+    }
+}
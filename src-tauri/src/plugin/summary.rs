@@ -0,0 +1,252 @@
+// Phase 7.4.1: 多插件数据摘要
+// 将所有已启用插件的缓存数据聚合为一份摘要，在 Rust 端计算以保证各窗口展示一致
+//
+// 聚合规则:
+// - 最高使用率: 取所有使用量数据（含多维度）中的最大百分比
+// - 最低余额: 取所有余额数据中的最小余额（不做货币换算）
+// - 最差状态: critical > major > minor > unknown > none
+// - 最近重置: 取所有使用量数据（含多维度）中最早的未来重置时间
+// - 预估花费: 按模型价格估算的最近 30 天花费（Phase 6.14，由 PluginManager 填入）
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::plugin::monitoring::format_rfc3339;
+use crate::plugin::pricing::EstimatedSpend;
+use crate::plugin::types::{PluginData, StatusIndicator};
+
+/// 最高使用率
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageHighlight {
+    pub plugin_id: String,
+    /// 使用百分比 (0-100)
+    pub percentage: f64,
+    /// 维度标签（来自多维度使用量时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimension: Option<String>,
+}
+
+/// 最低余额
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceHighlight {
+    pub plugin_id: String,
+    pub balance: f64,
+    pub currency: String,
+}
+
+/// 最差状态
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusHighlight {
+    pub plugin_id: String,
+    pub indicator: StatusIndicator,
+    pub description: String,
+}
+
+/// 最近重置时间
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetHighlight {
+    pub plugin_id: String,
+    /// 重置时间 (ISO 8601)
+    pub reset_time: String,
+    /// 重置标签或维度标签
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// 多插件数据摘要
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataSummary {
+    /// 参与聚合的插件数
+    pub plugin_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_usage: Option<UsageHighlight>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lowest_balance: Option<BalanceHighlight>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worst_status: Option<StatusHighlight>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soonest_reset: Option<ResetHighlight>,
+    /// 预估花费（没有 token 用量记录时为 None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_spend: Option<EstimatedSpend>,
+    /// 生成时间 (ISO 8601)
+    pub generated_at: String,
+}
+
+/// 状态严重程度（越大越差）
+fn status_severity(indicator: StatusIndicator) -> u8 {
+    match indicator {
+        StatusIndicator::None => 0,
+        StatusIndicator::Unknown => 1,
+        StatusIndicator::Minor => 2,
+        StatusIndicator::Major => 3,
+        StatusIndicator::Critical => 4,
+    }
+}
+
+/// 聚合插件数据
+pub fn summarize(data: &[PluginData], now: DateTime<Utc>) -> DataSummary {
+    let mut max_usage: Option<UsageHighlight> = None;
+    let mut lowest_balance: Option<BalanceHighlight> = None;
+    let mut worst_status: Option<StatusHighlight> = None;
+    let mut soonest_reset: Option<(DateTime<Utc>, ResetHighlight)> = None;
+
+    let mut consider_reset = |plugin_id: &str, reset_time: Option<&String>, label: Option<&String>| {
+        let Some(time) = reset_time
+            .and_then(|t| DateTime::parse_from_rfc3339(t.trim()).ok())
+            .map(|t| t.with_timezone(&Utc))
+        else {
+            return;
+        };
+        if time < now || soonest_reset.as_ref().is_some_and(|(best, _)| *best <= time) {
+            return;
+        }
+        soonest_reset = Some((
+            time,
+            ResetHighlight {
+                plugin_id: plugin_id.to_string(),
+                reset_time: format_rfc3339(time),
+                label: label.cloned(),
+            },
+        ));
+    };
+
+    for item in data {
+        match item {
+            PluginData::Usage(usage) => {
+                let plugin_id = &usage.base.plugin_id;
+                let candidates = std::iter::once((usage.percentage, None)).chain(
+                    usage
+                        .dimensions
+                        .iter()
+                        .flatten()
+                        .map(|d| (d.percentage, Some(&d.label))),
+                );
+                for (percentage, dimension) in candidates {
+                    if percentage.is_finite()
+                        && max_usage.as_ref().map_or(true, |m| percentage > m.percentage)
+                    {
+                        max_usage = Some(UsageHighlight {
+                            plugin_id: plugin_id.clone(),
+                            percentage,
+                            dimension: dimension.cloned(),
+                        });
+                    }
+                }
+
+                consider_reset(plugin_id, usage.reset_time.as_ref(), usage.reset_label.as_ref());
+                for dimension in usage.dimensions.iter().flatten() {
+                    consider_reset(plugin_id, dimension.reset_time.as_ref(), Some(&dimension.label));
+                }
+            }
+            PluginData::Balance(balance) => {
+                if balance.balance.is_finite()
+                    && lowest_balance.as_ref().map_or(true, |b| balance.balance < b.balance)
+                {
+                    lowest_balance = Some(BalanceHighlight {
+                        plugin_id: balance.base.plugin_id.clone(),
+                        balance: balance.balance,
+                        currency: balance.currency.clone(),
+                    });
+                }
+            }
+            PluginData::Status(status) => {
+                if worst_status.as_ref().map_or(true, |w| {
+                    status_severity(status.indicator) > status_severity(w.indicator)
+                }) {
+                    worst_status = Some(StatusHighlight {
+                        plugin_id: status.base.plugin_id.clone(),
+                        indicator: status.indicator,
+                        description: status.description.clone(),
+                    });
+                }
+            }
+            PluginData::Custom(_) => {}
+        }
+    }
+
+    DataSummary {
+        plugin_count: data.len(),
+        max_usage,
+        lowest_balance,
+        worst_status,
+        soonest_reset: soonest_reset.map(|(_, reset)| reset),
+        estimated_spend: None,
+        generated_at: format_rfc3339(now),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn data(value: serde_json::Value) -> PluginData {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_summarize() {
+        let now = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let items = vec![
+            data(json!({
+                "dataType": "usage", "pluginId": "a", "lastUpdated": "2025-01-01T00:00:00Z",
+                "percentage": 40.0, "used": 40.0, "limit": 100.0, "unit": "req",
+                "resetTime": "2025-01-02T00:00:00Z",
+                "dimensions": [{
+                    "id": "weekly", "label": "Weekly", "percentage": 85.0, "used": 85.0,
+                    "limit": 100.0, "resetTime": "2025-01-01T06:00:00+02:00"
+                }]
+            })),
+            data(json!({
+                "dataType": "usage", "pluginId": "b", "lastUpdated": "2025-01-01T00:00:00Z",
+                "percentage": 60.0, "used": 60.0, "limit": 100.0, "unit": "req",
+                // 已过去的重置时间不参与
+                "resetTime": "2024-12-31T00:00:00Z"
+            })),
+            data(json!({
+                "dataType": "balance", "pluginId": "c", "lastUpdated": "2025-01-01T00:00:00Z",
+                "balance": 12.5, "currency": "USD"
+            })),
+            data(json!({
+                "dataType": "balance", "pluginId": "d", "lastUpdated": "2025-01-01T00:00:00Z",
+                "balance": 3.0, "currency": "CNY"
+            })),
+            data(json!({
+                "dataType": "status", "pluginId": "e", "lastUpdated": "2025-01-01T00:00:00Z",
+                "indicator": "minor", "description": "Degraded"
+            })),
+            data(json!({
+                "dataType": "status", "pluginId": "f", "lastUpdated": "2025-01-01T00:00:00Z",
+                "indicator": "unknown", "description": "?"
+            })),
+        ];
+
+        let summary = summarize(&items, now);
+        assert_eq!(summary.plugin_count, 6);
+
+        let usage = summary.max_usage.unwrap();
+        assert_eq!((usage.plugin_id.as_str(), usage.percentage), ("a", 85.0));
+        assert_eq!(usage.dimension.as_deref(), Some("Weekly"));
+
+        let balance = summary.lowest_balance.unwrap();
+        assert_eq!((balance.plugin_id.as_str(), balance.currency.as_str()), ("d", "CNY"));
+
+        let status = summary.worst_status.unwrap();
+        assert_eq!(status.indicator, StatusIndicator::Minor);
+
+        let reset = summary.soonest_reset.unwrap();
+        assert_eq!(reset.reset_time, "2025-01-01T04:00:00.000Z");
+        assert_eq!(reset.label.as_deref(), Some("Weekly"));
+
+        let empty = summarize(&[], now);
+        assert!(empty.max_usage.is_none() && empty.soonest_reset.is_none());
+    }
+}
@@ -0,0 +1,107 @@
+// Phase 5A.10: 插件信任级别与资源限制
+// 按插件来源划分信任级别（builtin > signed > installed > developer），级别决定:
+// - 沙盒资源: 在 manifest `resources` 和管理员上限之内，低信任插件只能使用上限的一部分
+// - fetch 并发: 单次执行内同时进行的请求数
+// - 可用 API: secrets:<name>（Keychain 只读访问）仅对内置和已签名插件开放
+//
+// 信任级别在插件加载时确定（installed 插件的 manifest 签名通过校验即为 signed），
+// 由 PluginExecutor 在创建沙盒上下文时统一执行，未授予的 API 不会注入。
+
+use std::time::Duration;
+
+use crate::plugin::runtime::SandboxConfig;
+use crate::plugin::types::TrustLevel;
+use crate::security::policy::SandboxCaps;
+
+/// 信任级别对应的限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustLimits {
+    /// 可使用的沙盒资源上限比例（百分比，相对管理员上限）
+    pub sandbox_share_percent: u32,
+    /// 单次执行内最大并发 fetch 请求数
+    pub max_concurrent_fetches: usize,
+    /// 是否允许 secrets:<name> API
+    pub allow_secrets: bool,
+}
+
+impl TrustLevel {
+    /// 信任级别对应的限制
+    pub fn limits(&self) -> TrustLimits {
+        match self {
+            TrustLevel::Builtin | TrustLevel::Signed => TrustLimits {
+                sandbox_share_percent: 100,
+                max_concurrent_fetches: 10,
+                allow_secrets: true,
+            },
+            TrustLevel::Installed => TrustLimits {
+                sandbox_share_percent: 50,
+                max_concurrent_fetches: 4,
+                allow_secrets: false,
+            },
+            TrustLevel::Developer => TrustLimits {
+                sandbox_share_percent: 50,
+                max_concurrent_fetches: 2,
+                allow_secrets: false,
+            },
+        }
+    }
+
+    /// 是否允许使用该权限对应的 API
+    pub fn permits(&self, permission: &str) -> bool {
+        !permission.starts_with("secrets:") || self.limits().allow_secrets
+    }
+
+    /// 按信任级别过滤权限（未授予的 API 不注入沙盒）
+    pub fn filter_permissions(&self, permissions: &[String]) -> Vec<String> {
+        permissions.iter().filter(|p| self.permits(p)).cloned().collect()
+    }
+}
+
+impl TrustLimits {
+    /// 将沙盒配置收紧到该信任级别可使用的上限之内
+    pub fn clamp_sandbox(&self, config: SandboxConfig, caps: &SandboxCaps) -> SandboxConfig {
+        let share = |value: u64| value * u64::from(self.sandbox_share_percent) / 100;
+        let max_memory = share(u64::from(caps.memory_limit_mb) * 1024 * 1024) as usize;
+        let max_timeout = Duration::from_millis(share(caps.timeout_ms));
+        SandboxConfig {
+            memory_limit: config.memory_limit.min(max_memory),
+            execution_timeout: config.execution_timeout.min(max_timeout),
+            ..config
+        }
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trust_limits_scale_sandbox_and_apis() {
+        let caps = SandboxCaps {
+            memory_limit_mb: 64,
+            timeout_ms: 30_000,
+        };
+        let config = SandboxConfig {
+            memory_limit: 48 * 1024 * 1024,
+            execution_timeout: Duration::from_secs(20),
+            ..SandboxConfig::default()
+        };
+
+        let signed = TrustLevel::Signed.limits().clamp_sandbox(config.clone(), &caps);
+        assert_eq!(signed.memory_limit, 48 * 1024 * 1024);
+        assert_eq!(signed.execution_timeout, Duration::from_secs(20));
+
+        let unsigned = TrustLevel::Installed.limits().clamp_sandbox(config, &caps);
+        assert_eq!(unsigned.memory_limit, 32 * 1024 * 1024);
+        assert_eq!(unsigned.execution_timeout, Duration::from_secs(15));
+
+        let permissions = vec!["network".to_string(), "secrets:api_key".to_string()];
+        assert_eq!(TrustLevel::Signed.filter_permissions(&permissions), permissions);
+        assert_eq!(TrustLevel::Developer.filter_permissions(&permissions), ["network"]);
+        assert!(TrustLevel::Installed.limits().max_concurrent_fetches < TrustLevel::Signed.limits().max_concurrent_fetches);
+    }
+}
@@ -90,12 +90,33 @@ pub enum DataType {
     Custom,
 }
 
+/// 插件信任级别（builtin > signed > installed > developer，限制见 trust.rs）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrustLevel {
+    /// 已安装但 manifest 未签名（或签名无法校验）的插件
+    #[default]
+    Installed,
+    /// 已安装且 manifest 签名校验通过的插件
+    Signed,
+    /// 开发者模式下从 dev-plugins 目录加载的未签名插件
+    Developer,
+    /// 随应用发布的内置数据源（Phase 7.13）
+    Builtin,
+}
+
 /// 插件信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PluginInfo {
     /// 插件 ID
     pub id: String,
+    /// 插件实例 ID（IPC 调用和数据均以此区分；默认账户与插件 ID 相同）
+    #[serde(default)]
+    pub instance_id: String,
+    /// 账户名称（同一插件添加的其他账户，默认账户为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_label: Option<String>,
     /// 显示名称
     pub name: String,
     /// 版本号
@@ -121,6 +142,55 @@ pub struct PluginInfo {
     /// 配置 Schema
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config_schema: Option<serde_json::Value>,
+    /// 数据源可能已失效（连续多天 404/410 或域名不存在）
+    #[serde(default)]
+    pub possibly_defunct: bool,
+    /// 开始持续失效的时间 (ISO 8601)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defunct_since: Option<String>,
+    /// 已因完整性校验失败被隔离
+    #[serde(default)]
+    pub quarantined: bool,
+    /// 信任级别
+    #[serde(default)]
+    pub trust_level: TrustLevel,
+    /// 已声明但未授予的权限（未受信任的开发者插件）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub restricted_permissions: Vec<String>,
+    /// 接受外部推送数据（本地推送端点）
+    #[serde(default)]
+    pub accepts_push: bool,
+    /// 服务商计划维护截止时间 (ISO 8601)，维护期间暂停刷新，数据停留在维护前
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance_until: Option<String>,
+    /// 被看门狗自动禁用的时间 (ISO 8601)，重新启用后清除
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_disabled_at: Option<String>,
+}
+
+/// 插件完整性校验报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 校验时间 (ISO 8601)
+    pub checked_at: String,
+    /// manifest 中是否声明了文件哈希（未声明时无法校验）
+    pub has_hashes: bool,
+    /// 校验的文件数
+    pub files_checked: usize,
+    /// 校验失败的文件描述
+    pub issues: Vec<String>,
+    /// 校验后是否处于隔离状态
+    pub quarantined: bool,
+}
+
+impl IntegrityReport {
+    /// 是否通过校验
+    pub fn passed(&self) -> bool {
+        self.issues.is_empty()
+    }
 }
 
 /// 更新信息
@@ -158,6 +228,21 @@ pub struct ValidationResult {
     pub field_errors: Option<std::collections::HashMap<String, String>>,
 }
 
+/// 解析回放结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayParseResult {
+    /// 是否无错误
+    pub valid: bool,
+    /// 解析得到的数据
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<PluginData>,
+    /// 错误（字段类型不符、解析失败等）
+    pub errors: Vec<String>,
+    /// 警告（字段缺失使用默认值、dataType 不一致等）
+    pub warnings: Vec<String>,
+}
+
 /// 健康状态
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -165,6 +250,24 @@ pub enum HealthStatus {
     Healthy,
     Degraded,
     Unhealthy,
+    /// 网络离线，数据暂停刷新（Stale (offline)，不视为插件故障）
+    Stale,
+}
+
+/// 插件单个账户的数据（Phase 7.14）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginAccountData {
+    /// 插件实例 ID
+    pub instance_id: String,
+    /// 账户名称（默认账户为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_label: Option<String>,
+    /// 是否启用
+    pub enabled: bool,
+    /// 最近一次数据
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<PluginData>,
 }
 
 /// 插件健康信息
@@ -178,6 +281,9 @@ pub struct PluginHealth {
     /// 最后成功时间 (ISO 8601)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_success: Option<String>,
+    /// 距最后成功的时长 (ms)，基于单调时钟，不受睡眠/唤醒影响
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_success_age_ms: Option<u64>,
     /// 最后错误信息
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_error: Option<String>,
@@ -193,6 +299,118 @@ pub struct PluginHealth {
     pub total_calls: u64,
     /// 连续失败次数
     pub consecutive_failures: u32,
+    /// fetchData 结果缓存命中次数
+    #[serde(default)]
+    pub cache_hits: u64,
+    /// fetchData 结果缓存未命中次数（含强制刷新）
+    #[serde(default)]
+    pub cache_misses: u64,
+    /// 超出请求预算（maxRequestsPerMinute）被拒绝的请求数
+    #[serde(default)]
+    pub throttled_requests: u64,
+    /// 因未声明 fetch:<host> 权限被拒绝访问的主机
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_hosts: Vec<String>,
+    /// 沙盒执行平均 CPU 时间 (ms) - 基于滑动窗口
+    #[serde(default)]
+    pub avg_cpu_time_ms: f64,
+    /// 沙盒运行时峰值内存 (字节) - 基于滑动窗口
+    #[serde(default)]
+    pub peak_memory_bytes: u64,
+    /// 建议的恢复操作（根据最后错误类型生成）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_action: Option<String>,
+    /// 告警是否已静音
+    #[serde(default)]
+    pub alerts_muted: bool,
+    /// 告警暂停截止时间 (ISO 8601)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alerts_snoozed_until: Option<String>,
+}
+
+/// 插件健康状态变化（Phase 6.15，plugin:health_changed 事件 payload）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthTransition {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 变化前的状态
+    pub previous: HealthStatus,
+    /// 变化后的状态
+    pub current: HealthStatus,
+    /// 变化时间 (ISO 8601)
+    pub changed_at: String,
+    /// 变化前的状态开始时间 (ISO 8601)，插件加载后首次变化时为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_since: Option<String>,
+    /// 变化后的健康快照
+    pub health: PluginHealth,
+}
+
+/// 插件沙盒资源占用统计（基于最近 N 次沙盒执行）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginResourceStats {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 统计窗口内的沙盒执行次数（含重试和生命周期钩子）
+    pub executions: usize,
+    /// 最近一次执行的 CPU 时间 (ms)
+    pub last_cpu_time_ms: f64,
+    /// 平均 CPU 时间 (ms)
+    pub avg_cpu_time_ms: f64,
+    /// 最大 CPU 时间 (ms)
+    pub max_cpu_time_ms: f64,
+    /// 最近一次执行的运行时内存 (字节)
+    pub last_memory_bytes: u64,
+    /// 峰值内存 (字节)
+    pub peak_memory_bytes: u64,
+    /// 沙盒内存上限 (字节)
+    pub memory_limit_bytes: u64,
+}
+
+/// 插件能力描述（设置界面"插件能做什么"面板）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDescription {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 插件实例 ID
+    pub instance_id: String,
+    /// 解析后的完整 manifest（旧版本格式已迁移到当前格式）
+    pub manifest: serde_json::Value,
+    /// 信任级别
+    pub trust_level: TrustLevel,
+    /// 实际授予的权限（已去除开发者插件未信任和信任级别不允许的权限）
+    pub granted_permissions: Vec<String>,
+    /// 已声明但未授予的权限
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub restricted_permissions: Vec<String>,
+    /// 沙盒中注入的 API（内置数据源不在沙盒中执行，为空）
+    pub injected_apis: Vec<String>,
+    /// 暴露给其他插件调用的方法
+    pub exposed_methods: Vec<String>,
+    /// 事件订阅（事件名、通配符或带过滤器的订阅）
+    pub subscribed_events: Vec<crate::plugin::event_bus::EventSubscription>,
+    /// 允许访问的主机白名单（未声明时可访问任意公网主机）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_hosts: Option<Vec<String>>,
+    /// 沙盒与网络限制
+    pub limits: PluginSandboxLimits,
+}
+
+/// 插件运行时实际生效的限制（manifest 声明、管理员上限和信任级别共同决定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSandboxLimits {
+    /// 沙盒内存上限 (字节)
+    pub memory_limit_bytes: u64,
+    /// 单次执行超时 (ms)
+    pub timeout_ms: u64,
+    /// 单次执行内最大并发 fetch 请求数
+    pub max_concurrent_fetches: usize,
+    /// 每分钟最多发起的 fetch 请求数
+    pub max_requests_per_minute: u32,
 }
 
 // ============================================================================
@@ -226,6 +444,38 @@ pub struct UsageDimension {
     /// 重置时间
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reset_time: Option<String>,
+    /// token 用量明细（维度 ID 为模型名时用于费用估算）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<TokenBreakdown>,
+}
+
+/// token 用量明细
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TokenBreakdown {
+    /// 输入 token
+    pub input: u64,
+    /// 输出 token
+    pub output: u64,
+    /// 写入缓存的 token
+    pub cache_creation: u64,
+    /// 读取缓存的 token
+    pub cache_read: u64,
+}
+
+impl TokenBreakdown {
+    /// 总 token 数
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_creation + self.cache_read
+    }
+
+    /// 累加
+    pub fn add(&mut self, other: &TokenBreakdown) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_creation += other.cache_creation;
+        self.cache_read += other.cache_read;
+    }
 }
 
 /// 使用量数据
@@ -379,6 +629,18 @@ pub enum PluginData {
     Custom(CustomData),
 }
 
+impl PluginData {
+    /// 数据来源插件 ID
+    pub fn plugin_id(&self) -> &str {
+        match self {
+            PluginData::Usage(u) => &u.base.plugin_id,
+            PluginData::Balance(b) => &b.base.plugin_id,
+            PluginData::Status(s) => &s.base.plugin_id,
+            PluginData::Custom(c) => &c.base.plugin_id,
+        }
+    }
+}
+
 // ============================================================================
 // 错误类型
 // ============================================================================
@@ -398,6 +660,8 @@ pub enum PluginErrorType {
     StorageLimit,
     CacheError,
     IncompatibleApiVersion,
+    InputCancelled,
+    InputUnavailable,
     Unknown,
 }
 
@@ -425,6 +689,142 @@ impl PluginErrorType {
             _ => PluginErrorType::Unknown,
         }
     }
+
+    /// 从错误消息推断错误类型
+    ///
+    /// 优先识别消息中显式的类型标记（如 PluginError 的 `[AUTH_ERROR] ...`），
+    /// 其次按 HTTP 状态码和关键字推断。
+    pub fn classify(message: &str) -> Self {
+        for error_type in ALL_ERROR_TYPES {
+            if message.contains(error_type.as_str())
+                || message.contains(&format!("[{:?}]", error_type))
+            {
+                return error_type;
+            }
+        }
+
+        let lower = message.to_lowercase();
+        if let Some(status) = extract_http_status(&lower) {
+            let error_type = Self::from_http_status(status);
+            if error_type != PluginErrorType::Unknown {
+                return error_type;
+            }
+        }
+
+        const KEYWORD_RULES: &[(&[&str], PluginErrorType)] = &[
+            (&["unauthorized", "forbidden", "api key", "认证", "凭证"], PluginErrorType::AuthError),
+            (&["rate limit", "too many requests", "限流"], PluginErrorType::RateLimit),
+            (&["timeout", "timed out", "超时"], PluginErrorType::Timeout),
+            (&["内存超限", "执行被中断", "too many concurrent", "exceeds maximum"], PluginErrorType::SandboxLimit),
+            (&["permission", "权限"], PluginErrorType::PermissionDenied),
+            (&["dns error", "network error", "connection", "网络"], PluginErrorType::NetworkError),
+            (&["parse", "json", "解析", "datatype"], PluginErrorType::ParseError),
+        ];
+
+        KEYWORD_RULES
+            .iter()
+            .find(|(keywords, _)| keywords.iter().any(|k| lower.contains(k)))
+            .map(|(_, error_type)| *error_type)
+            .unwrap_or(PluginErrorType::Unknown)
+    }
+
+    /// 错误类型的字符串形式（与序列化格式一致）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluginErrorType::NetworkError => "NETWORK_ERROR",
+            PluginErrorType::AuthError => "AUTH_ERROR",
+            PluginErrorType::RateLimit => "RATE_LIMIT",
+            PluginErrorType::Timeout => "TIMEOUT",
+            PluginErrorType::ParseError => "PARSE_ERROR",
+            PluginErrorType::ProviderError => "PROVIDER_ERROR",
+            PluginErrorType::SandboxLimit => "SANDBOX_LIMIT",
+            PluginErrorType::PermissionDenied => "PERMISSION_DENIED",
+            PluginErrorType::StorageLimit => "STORAGE_LIMIT",
+            PluginErrorType::CacheError => "CACHE_ERROR",
+            PluginErrorType::IncompatibleApiVersion => "INCOMPATIBLE_API_VERSION",
+            PluginErrorType::InputCancelled => "INPUT_CANCELLED",
+            PluginErrorType::InputUnavailable => "INPUT_UNAVAILABLE",
+            PluginErrorType::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// 获取该错误类型的建议恢复操作
+    pub fn suggested_action(&self) -> Option<&'static str> {
+        SUGGESTED_ACTIONS
+            .iter()
+            .find(|(error_type, _)| error_type == self)
+            .map(|(_, action)| *action)
+    }
+}
+
+/// 所有错误类型（按识别优先级排列，Unknown 不参与显式匹配）
+const ALL_ERROR_TYPES: [PluginErrorType; 13] = [
+    PluginErrorType::IncompatibleApiVersion,
+    PluginErrorType::InputCancelled,
+    PluginErrorType::InputUnavailable,
+    PluginErrorType::PermissionDenied,
+    PluginErrorType::NetworkError,
+    PluginErrorType::AuthError,
+    PluginErrorType::RateLimit,
+    PluginErrorType::Timeout,
+    PluginErrorType::ParseError,
+    PluginErrorType::ProviderError,
+    PluginErrorType::SandboxLimit,
+    PluginErrorType::StorageLimit,
+    PluginErrorType::CacheError,
+];
+
+/// 错误恢复建议规则表
+///
+/// 所有展示健康状态的界面（托盘、仪表盘、通知）共用此表，保证提示一致。
+const SUGGESTED_ACTIONS: &[(PluginErrorType, &str)] = &[
+    (PluginErrorType::AuthError, "请在设置中更新 API Key"),
+    (PluginErrorType::RateLimit, "请求过于频繁，请增大刷新间隔"),
+    (PluginErrorType::Timeout, "请求超时，请检查网络或增大刷新间隔"),
+    (PluginErrorType::NetworkError, "请检查网络连接或代理设置"),
+    (PluginErrorType::ParseError, "服务返回格式已变化，请尝试更新插件"),
+    (PluginErrorType::ProviderError, "服务商暂时不可用，请稍后重试"),
+    (PluginErrorType::SandboxLimit, "插件超出资源限制，请联系插件作者"),
+    (PluginErrorType::PermissionDenied, "插件缺少所需权限，请检查插件权限声明"),
+    (PluginErrorType::StorageLimit, "插件存储空间已满，请清理插件数据"),
+    (PluginErrorType::CacheError, "缓存异常，请尝试重新加载插件"),
+    (PluginErrorType::IncompatibleApiVersion, "插件与当前版本不兼容，请更新插件或应用"),
+    (PluginErrorType::InputCancelled, "插件需要用户输入，请手动刷新后在对话框中填写"),
+    (PluginErrorType::InputUnavailable, "输入对话框无法打开，请重启应用后重试"),
+];
+
+/// 从错误消息中提取 HTTP 状态码（匹配 `http 401` / `status 429` / `status: 503` 形式）
+fn extract_http_status(lower: &str) -> Option<u16> {
+    for marker in ["http ", "status ", "status: ", "status="] {
+        if let Some(pos) = lower.find(marker) {
+            let digits: String = lower[pos + marker.len()..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            if digits.len() == 3 {
+                return digits.parse().ok();
+            }
+        }
+    }
+    None
+}
+
+/// 判断错误是否表明服务商 API 已不存在
+///
+/// 匹配 HTTP 404/410，或 DNS 解析返回域名不存在 (NXDOMAIN)。
+pub fn is_provider_gone(message: &str) -> bool {
+    const NXDOMAIN_MARKERS: &[&str] = &[
+        "nxdomain",
+        "name or service not known",
+        "nodename nor servname provided",
+        "no such host",
+    ];
+
+    let lower = message.to_lowercase();
+    if matches!(extract_http_status(&lower), Some(404) | Some(410)) {
+        return true;
+    }
+    lower.contains("dns") && NXDOMAIN_MARKERS.iter().any(|m| lower.contains(m))
 }
 
 /// 插件错误
@@ -0,0 +1,212 @@
+// Phase 5A.7: 插件自动更新
+// 将已安装插件的版本与 registry 索引比对，生成更新列表
+//
+// 设计要点:
+// 1. 版本按点分数字比较，带预发布后缀（-beta 等）的版本低于同号正式版
+// 2. 更新设置持久化到 updates.json（与插件目录同级）
+// 3. 仅标记为信任的插件允许自动安装更新，其余只通知
+
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::registry::RegistryIndex;
+use crate::plugin::types::{PluginInfo, UpdateInfo};
+
+/// 默认检查间隔（小时）
+pub const DEFAULT_CHECK_INTERVAL_HOURS: u64 = 6;
+
+// ============================================================================
+// 更新设置
+// ============================================================================
+
+/// 自动更新设置（updates.json）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UpdateSettings {
+    /// 是否启用后台检查
+    pub enabled: bool,
+    /// 检查间隔（小时，最小 1）
+    pub interval_hours: u64,
+    /// 允许自动安装更新的插件 ID
+    pub trusted: BTreeSet<String>,
+    /// registry.json URL（为空时使用默认地址）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry_url: Option<String>,
+}
+
+impl Default for UpdateSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_hours: DEFAULT_CHECK_INTERVAL_HOURS,
+            trusted: BTreeSet::new(),
+            registry_url: None,
+        }
+    }
+}
+
+impl UpdateSettings {
+    /// 默认配置文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("updates.json")
+    }
+
+    /// 从文件加载（文件不存在或无法解析时使用默认设置）
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析更新设置失败，使用默认设置: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// 检查间隔
+    pub fn interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.interval_hours.max(1) * 60 * 60)
+    }
+
+    /// 插件是否允许自动更新
+    pub fn is_trusted(&self, id: &str) -> bool {
+        self.trusted.contains(id)
+    }
+}
+
+// ============================================================================
+// 版本比较
+// ============================================================================
+
+/// 比较两个版本号（`1.2.10` > `1.2.9`，`1.0.0` > `1.0.0-beta`）
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a_core, a_pre) = split_prerelease(a);
+    let (b_core, b_pre) = split_prerelease(b);
+
+    let a_parts: Vec<u64> = a_core.split('.').map(|p| p.parse().unwrap_or(0)).collect();
+    let b_parts: Vec<u64> = b_core.split('.').map(|p| p.parse().unwrap_or(0)).collect();
+    for i in 0..a_parts.len().max(b_parts.len()) {
+        let ord = a_parts
+            .get(i)
+            .unwrap_or(&0)
+            .cmp(b_parts.get(i).unwrap_or(&0));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.cmp(b),
+    }
+}
+
+fn split_prerelease(version: &str) -> (&str, Option<&str>) {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split('+').next().unwrap_or(version);
+    match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    }
+}
+
+/// 找出 registry 中有新版本的已安装插件（同一插件的其他账户不重复列出）
+pub fn find_updates(installed: &[PluginInfo], index: &RegistryIndex) -> Vec<UpdateInfo> {
+    installed
+        .iter()
+        .filter(|plugin| plugin.account_label.is_none())
+        .filter_map(|plugin| {
+            let entry = index.get(&plugin.id)?;
+            if compare_versions(&entry.version, &plugin.version) != Ordering::Greater {
+                return None;
+            }
+            Some(UpdateInfo {
+                id: plugin.id.clone(),
+                current_version: plugin.version.clone(),
+                latest_version: entry.version.clone(),
+                release_notes: entry.release_notes.clone(),
+                download_url: entry.download_url.clone()?,
+                sha256: entry.sha256.clone().unwrap_or_default(),
+                signature: entry.signature.clone().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::types::PluginType;
+    use serde_json::json;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.2.10", "1.2.9"), Ordering::Greater);
+        assert_eq!(compare_versions("v1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0.0", "1.0.0-beta.1"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.0-alpha", "1.0.0-beta"), Ordering::Less);
+        assert_eq!(compare_versions("0.9.9", "1.0.0"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_find_updates() {
+        let index: RegistryIndex = serde_json::from_value(json!({
+            "plugins": [
+                { "id": "a", "name": "A", "version": "1.1.0", "downloadUrl": "https://example.com/a.zip", "releaseNotes": "fixes" },
+                { "id": "b", "name": "B", "version": "2.0.0", "downloadUrl": "https://example.com/b.zip" },
+                { "id": "c", "name": "C", "version": "3.0.0" }
+            ]
+        }))
+        .unwrap();
+
+        let installed: Vec<PluginInfo> = [("a", "1.0.0"), ("b", "2.0.0"), ("c", "1.0.0"), ("d", "1.0.0")]
+            .iter()
+            .map(|(id, version)| PluginInfo {
+                id: id.to_string(),
+                instance_id: id.to_string(),
+                account_label: None,
+                name: id.to_uppercase(),
+                version: version.to_string(),
+                plugin_type: PluginType::Data,
+                data_type: None,
+                enabled: true,
+                healthy: true,
+                author: None,
+                description: None,
+                icon: None,
+                config_schema: None,
+                possibly_defunct: false,
+                defunct_since: None,
+                quarantined: false,
+                trust_level: Default::default(),
+                restricted_permissions: Vec::new(),
+                accepts_push: false,
+                maintenance_until: None,
+                auto_disabled_at: None,
+            })
+            .collect();
+
+        let updates = find_updates(&installed, &index);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].id, "a");
+        assert_eq!(updates[0].latest_version, "1.1.0");
+        assert_eq!(updates[0].release_notes.as_deref(), Some("fixes"));
+    }
+}
@@ -0,0 +1,319 @@
+// Phase 6.9: 使用率趋势（弹窗迷你折线图）
+// 记录 usage 类型插件最近 24 小时的使用百分比，按需降采样为固定数量的点
+//
+// 设计要点:
+// 1. 每次刷新成功后由 PluginManager 记录一个采样点，超出窗口的采样自动清理
+// 2. 持久化到 usage_history.json（与插件目录同级），重启后趋势不中断
+// 3. 降采样在宿主侧完成，webview 只拿到 N 个点，不接触原始历史
+// 4. 卸载插件时删除其历史
+//
+// Phase 6.13: 用量异常检测
+// 按小时统计使用百分比的增长量（每小时消耗），以前 23 小时为基线计算最近一小时的 z-score，
+// 明显偏离基线的突增（可能是密钥泄露或失控的自动化任务）触发 AnomalyDetected 告警
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::monitoring::format_rfc3339;
+use crate::plugin::types::PluginData;
+
+/// 趋势窗口（小时）
+pub const USAGE_TREND_WINDOW_HOURS: i64 = 24;
+
+/// 默认点数
+pub const DEFAULT_TREND_POINTS: u32 = 24;
+
+/// 最大点数（每 15 分钟一个点）
+pub const MAX_TREND_POINTS: u32 = 96;
+
+/// 触发用量异常的 z-score 阈值
+pub const ANOMALY_Z_SCORE_THRESHOLD: f64 = 3.0;
+
+/// 检测用量异常所需的最少基线小时数
+const ANOMALY_MIN_BASELINE_HOURS: usize = 6;
+
+/// 触发用量异常的最小每小时消耗（百分点），避免基线平稳时的微小波动误报
+const ANOMALY_MIN_BURN: f64 = 5.0;
+
+/// 计算 z-score 时的最小标准差（百分点）
+const ANOMALY_MIN_STD_DEV: f64 = 1.0;
+
+// ============================================================================
+// 采样数据
+// ============================================================================
+
+/// 单个采样点
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct UsageSample {
+    /// 采样时间（Unix 毫秒）
+    t: i64,
+    /// 使用百分比
+    p: f64,
+}
+
+/// 插件 ID -> 按时间升序的采样点
+type HistoryData = BTreeMap<String, Vec<UsageSample>>;
+
+/// 使用率趋势
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTrend {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 窗口起始时间 (ISO 8601)
+    pub start: String,
+    /// 窗口结束时间 (ISO 8601)
+    pub end: String,
+    /// 每个点覆盖的时长（毫秒）
+    pub bucket_ms: i64,
+    /// 各时间段内的峰值使用百分比，无采样的时间段为 null
+    pub points: Vec<Option<f64>>,
+}
+
+/// 用量异常（最近一小时的消耗明显高于基线）
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageAnomaly {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 最近一小时的消耗（百分点）
+    pub observed: f64,
+    /// 基线每小时消耗均值（百分点）
+    pub baseline_mean: f64,
+    /// 基线每小时消耗标准差（百分点）
+    pub baseline_std_dev: f64,
+    /// 基线覆盖的小时数（有采样的小时）
+    pub baseline_hours: usize,
+    /// 最近一小时相对基线的 z-score
+    pub z_score: f64,
+}
+
+/// 使用率历史（usage_history.json）
+pub struct UsageHistory {
+    data: Mutex<HistoryData>,
+    /// 持久化路径
+    path: PathBuf,
+}
+
+impl UsageHistory {
+    /// 默认文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("usage_history.json")
+    }
+
+    /// 从文件加载（文件不存在或无法解析时从空历史开始）
+    pub fn load(path: PathBuf) -> Self {
+        let data = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析使用率历史失败，重新开始记录: {}", e);
+                HistoryData::new()
+            }),
+            Err(_) => HistoryData::new(),
+        };
+        Self {
+            data: Mutex::new(data),
+            path,
+        }
+    }
+
+    /// 保存到文件（临时文件 + rename）
+    fn save(&self, data: &HistoryData) {
+        let path = &self.path;
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, serde_json::to_vec(data)?)?;
+            std::fs::rename(&tmp, path)
+        })();
+        if let Err(e) = result {
+            log::warn!("保存使用率历史失败: {}", e);
+        }
+    }
+
+    /// 记录一次刷新结果（仅 usage 类型）
+    pub fn record(&self, data: &PluginData, now: DateTime<Utc>) {
+        let PluginData::Usage(usage) = data else {
+            return;
+        };
+        if !usage.percentage.is_finite() {
+            return;
+        }
+        let cutoff = (now - Duration::hours(USAGE_TREND_WINDOW_HOURS)).timestamp_millis();
+
+        let mut data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        for samples in data.values_mut() {
+            samples.retain(|s| s.t >= cutoff);
+        }
+        data.retain(|_, samples| !samples.is_empty());
+
+        data.entry(usage.base.plugin_id.clone())
+            .or_default()
+            .push(UsageSample {
+                t: now.timestamp_millis(),
+                p: usage.percentage.clamp(0.0, 100.0),
+            });
+        self.save(&data);
+    }
+
+    /// 删除插件的历史
+    pub fn remove(&self, plugin_id: &str) {
+        let mut data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        if data.remove(plugin_id).is_some() {
+            self.save(&data);
+        }
+    }
+
+    /// 将最近 24 小时的采样降采样为 `points` 个时间段（取每段峰值）
+    pub fn trend(&self, plugin_id: &str, points: u32, now: DateTime<Utc>) -> UsageTrend {
+        let points = points.clamp(1, MAX_TREND_POINTS) as usize;
+        let start = now - Duration::hours(USAGE_TREND_WINDOW_HOURS);
+        let start_ms = start.timestamp_millis();
+        let end_ms = now.timestamp_millis();
+        let bucket_ms = (end_ms - start_ms) / points as i64;
+
+        let mut buckets: Vec<Option<f64>> = vec![None; points];
+        let data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        for sample in data.get(plugin_id).into_iter().flatten() {
+            if sample.t < start_ms || sample.t > end_ms {
+                continue;
+            }
+            let index = (((sample.t - start_ms) / bucket_ms) as usize).min(points - 1);
+            let peak = buckets[index].get_or_insert(sample.p);
+            *peak = peak.max(sample.p);
+        }
+
+        UsageTrend {
+            plugin_id: plugin_id.to_string(),
+            start: format_rfc3339(start),
+            end: format_rfc3339(now),
+            bucket_ms,
+            points: buckets,
+        }
+    }
+
+    /// 检测最近一小时的用量异常
+    ///
+    /// 每小时消耗为该小时内相邻采样的百分比增量之和（百分比下降视为额度重置，
+    /// 重置后的百分比计为增量）；没有采样的小时（如应用未运行）不计入基线。
+    pub fn detect_anomaly(&self, plugin_id: &str, now: DateTime<Utc>) -> Option<UsageAnomaly> {
+        let hour_ms = Duration::hours(1).num_milliseconds();
+        let now_ms = now.timestamp_millis();
+        let mut hourly: Vec<Option<f64>> = vec![None; USAGE_TREND_WINDOW_HOURS as usize];
+
+        let data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        let samples = data.get(plugin_id)?;
+        for pair in samples.windows(2) {
+            let (prev, cur) = (pair[0], pair[1]);
+            if cur.t > now_ms {
+                continue;
+            }
+            let index = ((now_ms - cur.t) / hour_ms) as usize;
+            let Some(slot) = hourly.get_mut(index) else {
+                continue;
+            };
+            let burn = if cur.p >= prev.p { cur.p - prev.p } else { cur.p };
+            *slot.get_or_insert(0.0) += burn;
+        }
+        drop(data);
+
+        let observed = hourly[0]?;
+        let baseline: Vec<f64> = hourly[1..].iter().flatten().copied().collect();
+        if baseline.len() < ANOMALY_MIN_BASELINE_HOURS || observed < ANOMALY_MIN_BURN {
+            return None;
+        }
+        let mean = baseline.iter().sum::<f64>() / baseline.len() as f64;
+        let variance = baseline.iter().map(|b| (b - mean).powi(2)).sum::<f64>() / baseline.len() as f64;
+        let std_dev = variance.sqrt();
+        let z_score = (observed - mean) / std_dev.max(ANOMALY_MIN_STD_DEV);
+        (z_score >= ANOMALY_Z_SCORE_THRESHOLD).then(|| UsageAnomaly {
+            plugin_id: plugin_id.to_string(),
+            observed,
+            baseline_mean: mean,
+            baseline_std_dev: std_dev,
+            baseline_hours: baseline.len(),
+            z_score,
+        })
+    }
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::types::{PluginDataBase, UsageData};
+
+    fn usage(plugin_id: &str, percentage: f64) -> PluginData {
+        PluginData::Usage(UsageData {
+            base: PluginDataBase {
+                plugin_id: plugin_id.to_string(),
+                last_updated: String::new(),
+            },
+            percentage,
+            used: percentage,
+            limit: 100.0,
+            unit: "%".to_string(),
+            reset_time: None,
+            reset_label: None,
+            dimensions: None,
+        })
+    }
+
+    #[test]
+    fn test_usage_trend_downsampling() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage_history.json");
+        let history = UsageHistory::load(path.clone());
+        let now = Utc::now();
+
+        // 超出窗口的采样被清理
+        history.record(&usage("demo", 90.0), now - Duration::hours(30));
+        history.record(&usage("demo", 10.0), now - Duration::hours(23));
+        history.record(&usage("demo", 40.0), now - Duration::hours(2));
+        history.record(&usage("demo", 30.0), now - Duration::hours(1));
+        history.record(&usage("demo", 55.0), now);
+
+        let trend = UsageHistory::load(path).trend("demo", 4, now);
+        assert_eq!(trend.bucket_ms, 6 * 3600 * 1000);
+        assert_eq!(trend.points, vec![Some(10.0), None, None, Some(55.0)]);
+
+        history.remove("demo");
+        assert!(history.trend("demo", 4, now).points.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_detect_usage_anomaly() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = UsageHistory::load(dir.path().join("usage_history.json"));
+        let now = Utc::now();
+
+        // 基线: 过去 12 小时每小时稳定消耗 2 个百分点
+        for hour in (1..=12).rev() {
+            let percentage = 2.0 * (12 - hour) as f64;
+            history.record(&usage("demo", percentage), now - Duration::hours(hour) - Duration::minutes(30));
+        }
+        history.record(&usage("demo", 25.0), now - Duration::minutes(20));
+        assert_eq!(history.detect_anomaly("demo", now), None);
+
+        // 最近一小时突增 30 个百分点
+        history.record(&usage("demo", 55.0), now);
+        let anomaly = history.detect_anomaly("demo", now).unwrap();
+        assert_eq!(anomaly.observed, 33.0);
+        assert_eq!(anomaly.baseline_mean, 2.0);
+        assert_eq!(anomaly.baseline_hours, 11);
+        assert!(anomaly.z_score >= ANOMALY_Z_SCORE_THRESHOLD);
+
+        // 基线不足时不检测
+        assert_eq!(history.detect_anomaly("other", now), None);
+    }
+}
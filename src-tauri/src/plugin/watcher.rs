@@ -3,8 +3,9 @@
 //
 // 监听插件文件变化，触发 unload → load 热重载
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
 use tokio::sync::mpsc;
@@ -153,7 +154,6 @@ impl PluginWatcher {
 /// 热重载管理器
 pub struct HotReloadManager {
     plugins_dir: PathBuf,
-    #[allow(dead_code)]
     debounce_ms: u64,
 }
 
@@ -177,6 +177,56 @@ impl HotReloadManager {
     pub fn plugins_dir(&self) -> &Path {
         &self.plugins_dir
     }
+
+    /// 防抖间隔
+    pub fn debounce(&self) -> Duration {
+        Duration::from_millis(self.debounce_ms)
+    }
+}
+
+// ============================================================================
+// 重载防抖
+// ============================================================================
+
+/// 按插件合并短时间内的多次文件变更（编辑器保存常产生多个事件）
+#[derive(Debug)]
+pub struct ReloadDebouncer {
+    delay: Duration,
+    /// 插件 ID -> 计划重载时间
+    pending: HashMap<String, Instant>,
+}
+
+impl ReloadDebouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// 记录一次变更，重载时间顺延到 `now + delay`
+    pub fn touch(&mut self, plugin_id: &str, now: Instant) {
+        self.pending.insert(plugin_id.to_string(), now + self.delay);
+    }
+
+    /// 最早的计划重载时间
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.values().min().copied()
+    }
+
+    /// 取出已到期的插件 ID
+    pub fn take_due(&mut self, now: Instant) -> Vec<String> {
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &due {
+            self.pending.remove(id);
+        }
+        due
+    }
 }
 
 #[cfg(test)]
@@ -192,4 +242,20 @@ mod tests {
         let debug_str = format!("{:?}", event);
         assert!(debug_str.contains("Modified"));
     }
+
+    #[test]
+    fn test_reload_debouncer() {
+        let start = Instant::now();
+        let mut debouncer = ReloadDebouncer::new(Duration::from_millis(500));
+        debouncer.touch("a", start);
+        debouncer.touch("a", start + Duration::from_millis(300));
+        debouncer.touch("b", start + Duration::from_millis(100));
+
+        assert_eq!(debouncer.next_deadline(), Some(start + Duration::from_millis(600)));
+        assert!(debouncer.take_due(start + Duration::from_millis(599)).is_empty());
+        // 连续保存顺延了 a 的重载时间
+        assert_eq!(debouncer.take_due(start + Duration::from_millis(600)), vec!["b"]);
+        assert_eq!(debouncer.take_due(start + Duration::from_millis(800)), vec!["a"]);
+        assert_eq!(debouncer.next_deadline(), None);
+    }
 }
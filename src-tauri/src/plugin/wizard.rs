@@ -0,0 +1,383 @@
+// Phase 7.2: 首次设置向导
+// 首次启动时显示向导窗口，按顺序完成以下步骤后才结束:
+// 1. welcome          欢迎页
+// 2. plugins          选择要启用的插件
+// 3. apiKeys          填写 API Key（写入 Keychain，可跳过）
+// 4. refreshInterval  选择刷新间隔
+//
+// - 只能重做已完成的步骤或完成当前步骤，不能跳步
+// - 进度保存在 wizard.json，中途退出后下次启动从当前步骤继续
+// - 完成后写入标记文件 setup_complete（均与插件目录同级），之后不再显示向导
+// - 状态中不保存 API Key 明文，只记录已写入的密钥名称
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::error::codes;
+use crate::plugin::monitoring::format_rfc3339;
+use crate::settings::{DEFAULT_REFRESH_INTERVAL_MS, MAX_REFRESH_INTERVAL_MS, MIN_REFRESH_INTERVAL_MS};
+
+/// 进度文件名
+const WIZARD_FILE_NAME: &str = "wizard.json";
+
+/// 首次设置完成标记文件名
+const SETUP_COMPLETE_MARKER: &str = "setup_complete";
+
+// ============================================================================
+// 步骤
+// ============================================================================
+
+/// 向导步骤（按顺序排列）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WizardStep {
+    Welcome,
+    Plugins,
+    ApiKeys,
+    RefreshInterval,
+}
+
+impl WizardStep {
+    /// 全部步骤
+    pub const ALL: [WizardStep; 4] = [
+        WizardStep::Welcome,
+        WizardStep::Plugins,
+        WizardStep::ApiKeys,
+        WizardStep::RefreshInterval,
+    ];
+}
+
+impl std::fmt::Display for WizardStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            WizardStep::Welcome => "welcome",
+            WizardStep::Plugins => "plugins",
+            WizardStep::ApiKeys => "apiKeys",
+            WizardStep::RefreshInterval => "refreshInterval",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 单个 API Key 输入
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WizardApiKey {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 密钥名称（`secrets:<name>` 或 configSchema 中的 secret 字段）
+    pub name: String,
+    /// 明文值（只写入 Keychain）
+    pub value: String,
+}
+
+/// 完成步骤时提交的内容
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "step", rename_all = "camelCase")]
+pub enum WizardStepInput {
+    Welcome,
+    #[serde(rename_all = "camelCase")]
+    Plugins { plugin_ids: Vec<String> },
+    ApiKeys { keys: Vec<WizardApiKey> },
+    #[serde(rename_all = "camelCase")]
+    RefreshInterval { interval_ms: u64 },
+}
+
+impl WizardStepInput {
+    /// 对应的步骤
+    pub fn step(&self) -> WizardStep {
+        match self {
+            WizardStepInput::Welcome => WizardStep::Welcome,
+            WizardStepInput::Plugins { .. } => WizardStep::Plugins,
+            WizardStepInput::ApiKeys { .. } => WizardStep::ApiKeys,
+            WizardStepInput::RefreshInterval { .. } => WizardStep::RefreshInterval,
+        }
+    }
+}
+
+/// 已写入 Keychain 的密钥（不含值）
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfiguredSecret {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 密钥名称
+    pub name: String,
+}
+
+// ============================================================================
+// 状态
+// ============================================================================
+
+/// 向导进度（wizard.json）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WizardProgress {
+    /// 已完成的步骤（按顺序）
+    pub completed_steps: Vec<WizardStep>,
+    /// 选择启用的插件
+    pub selected_plugins: Vec<String>,
+    /// 已写入 Keychain 的密钥
+    pub configured_secrets: Vec<ConfiguredSecret>,
+    /// 选择的刷新间隔 (毫秒)
+    pub refresh_interval_ms: u64,
+    /// 完成时间 (ISO 8601)
+    pub finished_at: Option<String>,
+}
+
+impl Default for WizardProgress {
+    fn default() -> Self {
+        Self {
+            completed_steps: Vec::new(),
+            selected_plugins: Vec::new(),
+            configured_secrets: Vec::new(),
+            refresh_interval_ms: DEFAULT_REFRESH_INTERVAL_MS,
+            finished_at: None,
+        }
+    }
+}
+
+impl WizardProgress {
+    /// 当前步骤（第一个未完成的步骤，全部完成时为 None）
+    pub fn current_step(&self) -> Option<WizardStep> {
+        WizardStep::ALL
+            .into_iter()
+            .find(|step| !self.completed_steps.contains(step))
+    }
+}
+
+/// 可在向导中选择的插件
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WizardPluginOption {
+    /// 插件 ID
+    pub id: String,
+    /// 插件名称
+    pub name: String,
+    /// 当前是否启用
+    pub enabled: bool,
+    /// 可在向导中填写的密钥名称
+    pub secret_names: Vec<String>,
+}
+
+/// 向导状态（wizard_get_state 返回值）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WizardState {
+    /// 首次设置是否已完成
+    pub completed: bool,
+    /// 当前步骤（全部步骤完成、等待 wizard_finish 时为 None）
+    pub current_step: Option<WizardStep>,
+    /// 进度
+    #[serde(flatten)]
+    pub progress: WizardProgress,
+    /// 可选择的插件
+    pub plugins: Vec<WizardPluginOption>,
+}
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+/// 向导错误
+#[derive(Debug, thiserror::Error)]
+pub enum WizardError {
+    #[error("首次设置已完成")]
+    AlreadyFinished,
+
+    #[error("请先完成步骤 {expected}，不能跳到 {requested}")]
+    StepOutOfOrder {
+        expected: WizardStep,
+        requested: WizardStep,
+    },
+
+    #[error("尚未完成步骤 {0}")]
+    Incomplete(WizardStep),
+
+    #[error("{0}")]
+    InvalidInput(String),
+
+    #[error("保存向导进度失败: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl WizardError {
+    /// IPC 错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            WizardError::AlreadyFinished => codes::WIZARD_FINISHED,
+            WizardError::StepOutOfOrder { .. } => codes::WIZARD_STEP_OUT_OF_ORDER,
+            WizardError::Incomplete(_) => codes::WIZARD_INCOMPLETE,
+            WizardError::InvalidInput(_) => codes::INVALID_ARGUMENT,
+            WizardError::Io(_) => codes::WIZARD_SAVE_FAILED,
+        }
+    }
+}
+
+/// 校验刷新间隔
+pub fn validate_refresh_interval(interval_ms: u64) -> Result<(), WizardError> {
+    if (MIN_REFRESH_INTERVAL_MS..=MAX_REFRESH_INTERVAL_MS).contains(&interval_ms) {
+        Ok(())
+    } else {
+        Err(WizardError::InvalidInput(format!(
+            "刷新间隔需在 {} 分钟到 {} 小时之间",
+            MIN_REFRESH_INTERVAL_MS / 60_000,
+            MAX_REFRESH_INTERVAL_MS / 3_600_000
+        )))
+    }
+}
+
+// ============================================================================
+// 向导
+// ============================================================================
+
+/// 首次设置向导
+pub struct SetupWizard {
+    progress: Mutex<WizardProgress>,
+    /// 进度文件路径
+    path: PathBuf,
+    /// 完成标记文件路径
+    marker: PathBuf,
+}
+
+impl SetupWizard {
+    /// 从配置目录（插件目录的上级）加载
+    pub fn load(plugins_dir: &Path) -> Self {
+        let dir = plugins_dir.parent().unwrap_or(plugins_dir);
+        let path = dir.join(WIZARD_FILE_NAME);
+        let progress = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("解析向导进度失败，从头开始: {}", e);
+                WizardProgress::default()
+            }),
+            Err(_) => WizardProgress::default(),
+        };
+        Self {
+            progress: Mutex::new(progress),
+            path,
+            marker: dir.join(SETUP_COMPLETE_MARKER),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, WizardProgress> {
+        self.progress.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 保存进度（临时文件 + rename）
+    fn save(&self, progress: &WizardProgress) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(progress)?)?;
+        std::fs::rename(&tmp, &self.path)
+    }
+
+    /// 首次设置是否已完成（以标记文件为准）
+    pub fn is_completed(&self) -> bool {
+        self.marker.exists()
+    }
+
+    /// 当前进度
+    pub fn progress(&self) -> WizardProgress {
+        self.lock().clone()
+    }
+
+    /// 检查步骤是否可以提交（只能重做已完成的步骤或完成当前步骤）
+    pub fn check_step(&self, step: WizardStep) -> Result<(), WizardError> {
+        if self.is_completed() {
+            return Err(WizardError::AlreadyFinished);
+        }
+        match self.lock().current_step() {
+            Some(expected) if step > expected => Err(WizardError::StepOutOfOrder {
+                expected,
+                requested: step,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// 记录步骤已完成（副作用已由调用方应用）
+    pub fn complete_step(
+        &self,
+        step: WizardStep,
+        update: impl FnOnce(&mut WizardProgress),
+    ) -> Result<WizardProgress, WizardError> {
+        self.check_step(step)?;
+        let mut progress = self.lock();
+        update(&mut progress);
+        if !progress.completed_steps.contains(&step) {
+            progress.completed_steps.push(step);
+            progress.completed_steps.sort();
+        }
+        self.save(&progress)?;
+        log::info!("首次设置向导: 已完成步骤 {}", step);
+        Ok(progress.clone())
+    }
+
+    /// 结束向导并写入完成标记
+    pub fn finish(&self) -> Result<WizardProgress, WizardError> {
+        if self.is_completed() {
+            return Err(WizardError::AlreadyFinished);
+        }
+        let mut progress = self.lock();
+        if let Some(step) = progress.current_step() {
+            return Err(WizardError::Incomplete(step));
+        }
+        let finished_at = format_rfc3339(Utc::now());
+        progress.finished_at = Some(finished_at.clone());
+        self.save(&progress)?;
+        std::fs::write(&self.marker, finished_at)?;
+        log::info!("首次设置向导已完成");
+        Ok(progress.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wizard_steps_in_order_and_finish() {
+        let dir = tempfile::tempdir().unwrap();
+        let plugins_dir = dir.path().join("plugins");
+        let wizard = SetupWizard::load(&plugins_dir);
+        assert!(!wizard.is_completed());
+
+        // 不能跳步，也不能提前结束
+        assert!(matches!(
+            wizard.complete_step(WizardStep::Plugins, |_| {}),
+            Err(WizardError::StepOutOfOrder { expected: WizardStep::Welcome, .. })
+        ));
+        assert!(matches!(wizard.finish(), Err(WizardError::Incomplete(WizardStep::Welcome))));
+
+        wizard.complete_step(WizardStep::Welcome, |_| {}).unwrap();
+        wizard
+            .complete_step(WizardStep::Plugins, |p| p.selected_plugins = vec!["claude".into()])
+            .unwrap();
+        // 重做已完成的步骤
+        wizard.complete_step(WizardStep::Welcome, |_| {}).unwrap();
+        wizard.complete_step(WizardStep::ApiKeys, |_| {}).unwrap();
+
+        // 进度持久化，重新加载后从当前步骤继续
+        let wizard = SetupWizard::load(&plugins_dir);
+        assert_eq!(wizard.progress().current_step(), Some(WizardStep::RefreshInterval));
+        assert_eq!(wizard.progress().selected_plugins, ["claude"]);
+        assert!(validate_refresh_interval(1000).is_err());
+        wizard
+            .complete_step(WizardStep::RefreshInterval, |p| p.refresh_interval_ms = 600_000)
+            .unwrap();
+
+        let progress = wizard.finish().unwrap();
+        assert!(progress.finished_at.is_some());
+        assert!(SetupWizard::load(&plugins_dir).is_completed());
+        assert!(matches!(wizard.finish(), Err(WizardError::AlreadyFinished)));
+        assert!(matches!(
+            wizard.check_step(WizardStep::Welcome),
+            Err(WizardError::AlreadyFinished)
+        ));
+    }
+}
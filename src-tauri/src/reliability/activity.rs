@@ -0,0 +1,174 @@
+// Phase 3.6: App Nap 规避
+// 在批量刷新期间持有 macOS 活动断言，防止 App Nap 拖慢后台刷新
+//
+// 设计要点:
+// 1. 断言通过 RAII guard 管理，批量刷新结束（guard drop）立即释放
+// 2. 断言不阻止系统空闲睡眠，仅阻止 App Nap 对定时器和网络的节流
+// 3. 可通过设置关闭（电池敏感用户），关闭后 begin 返回 None
+// 4. 非 macOS 平台不执行系统调用，仅维护计数
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// NSActivityUserInitiatedAllowingIdleSystemSleep
+///
+/// = NSActivityUserInitiated & ~NSActivityIdleSystemSleepDisabled
+#[cfg(target_os = "macos")]
+const NS_ACTIVITY_USER_INITIATED_ALLOWING_IDLE_SYSTEM_SLEEP: u64 = 0x00FF_FFFF & !(1 << 20);
+
+// ============================================================================
+// 活动断言管理
+// ============================================================================
+
+/// 活动断言管理器
+pub struct ActivityAssertions {
+    /// 是否启用（关闭后不再创建断言）
+    enabled: AtomicBool,
+    /// 当前持有的断言数
+    active: Arc<AtomicUsize>,
+}
+
+impl ActivityAssertions {
+    /// 创建（默认启用）
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(true),
+            active: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// 是否启用
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// 设置是否启用（已持有的断言在 drop 时正常释放）
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// 当前持有的断言数
+    pub fn active_count(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// 开始一次活动（禁用时返回 None）
+    ///
+    /// 返回的 guard 需持有到批量刷新结束。
+    pub fn begin(&self, reason: &str) -> Option<ActivityAssertion> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let token = platform::begin_activity(reason);
+        self.active.fetch_add(1, Ordering::Relaxed);
+        log::debug!("开始活动断言: {} (当前 {} 个)", reason, self.active_count());
+        Some(ActivityAssertion {
+            token,
+            active: self.active.clone(),
+        })
+    }
+}
+
+impl Default for ActivityAssertions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 活动断言 guard（drop 时释放）
+pub struct ActivityAssertion {
+    token: platform::Token,
+    active: Arc<AtomicUsize>,
+}
+
+impl Drop for ActivityAssertion {
+    fn drop(&mut self) {
+        platform::end_activity(&self.token);
+        self.active.fetch_sub(1, Ordering::Relaxed);
+        log::debug!("已释放活动断言");
+    }
+}
+
+// ============================================================================
+// 平台实现
+// ============================================================================
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use cocoa::base::{id, nil};
+    use cocoa::foundation::NSString;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    use super::NS_ACTIVITY_USER_INITIATED_ALLOWING_IDLE_SYSTEM_SLEEP;
+
+    /// beginActivityWithOptions:reason: 返回的活动对象（已 retain）
+    pub struct Token(id);
+
+    // SAFETY: NSProcessInfo 的 beginActivity/endActivity 是线程安全的，
+    // 活动对象仅作为不透明 token 传回 endActivity:
+    unsafe impl Send for Token {}
+    unsafe impl Sync for Token {}
+
+    pub fn begin_activity(reason: &str) -> Token {
+        unsafe {
+            let process_info: id = msg_send![class!(NSProcessInfo), processInfo];
+            let reason = NSString::alloc(nil).init_str(reason);
+            let activity: id = msg_send![
+                process_info,
+                beginActivityWithOptions: NS_ACTIVITY_USER_INITIATED_ALLOWING_IDLE_SYSTEM_SLEEP
+                reason: reason
+            ];
+            let _: () = msg_send![reason, release];
+            let activity: id = msg_send![activity, retain];
+            Token(activity)
+        }
+    }
+
+    pub fn end_activity(token: &Token) {
+        if token.0 == nil {
+            return;
+        }
+        unsafe {
+            let process_info: id = msg_send![class!(NSProcessInfo), processInfo];
+            let _: () = msg_send![process_info, endActivity: token.0];
+            let _: () = msg_send![token.0, release];
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    /// 非 macOS 平台无系统断言
+    pub struct Token;
+
+    pub fn begin_activity(_reason: &str) -> Token {
+        Token
+    }
+
+    pub fn end_activity(_token: &Token) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assertion_scoped_to_guard() {
+        let assertions = ActivityAssertions::new();
+        assert!(assertions.is_enabled());
+
+        let first = assertions.begin("refresh batch");
+        let second = assertions.begin("queued refresh");
+        assert!(first.is_some() && second.is_some());
+        assert_eq!(assertions.active_count(), 2);
+
+        drop(first);
+        assert_eq!(assertions.active_count(), 1);
+        drop(second);
+        assert_eq!(assertions.active_count(), 0);
+
+        assertions.set_enabled(false);
+        assert!(assertions.begin("refresh batch").is_none());
+        assert_eq!(assertions.active_count(), 0);
+    }
+}
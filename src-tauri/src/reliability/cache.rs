@@ -7,6 +7,7 @@
 // - 3.3.3 实现 TTI 空闲过期 ✓
 // - 3.3.4 实现强制刷新 bypass ✓
 // - 3.3.5 实现缓存命中率统计 ✓
+// - 3.3.6 实现按条目 TTL（插件 fetchData 结果按刷新间隔缓存） ✓
 
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashSet;
@@ -14,10 +15,11 @@ use std::future::Future;
 use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use dashmap::DashMap;
 use moka::future::Cache;
+use moka::Expiry;
 use thiserror::Error;
 
 // ============================================================================
@@ -179,6 +181,38 @@ pub struct CacheStatsSnapshot {
     pub hit_rate: f64,
 }
 
+// ============================================================================
+// 缓存条目
+// ============================================================================
+
+/// 缓存条目（可带独立 TTL）
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    value: serde_json::Value,
+    /// 条目 TTL，None 时仅受全局 TTL/TTI 约束
+    ttl: Option<Duration>,
+}
+
+/// 按条目 TTL 的过期策略（与全局 TTL/TTI 取最早者）
+struct EntryExpiry;
+
+impl Expiry<String, CacheEntry> for EntryExpiry {
+    fn expire_after_create(&self, _key: &String, entry: &CacheEntry, _created_at: Instant) -> Option<Duration> {
+        entry.ttl
+    }
+
+    fn expire_after_update(
+        &self,
+        _key: &String,
+        entry: &CacheEntry,
+        _updated_at: Instant,
+        _duration_until_expiry: Option<Duration>,
+    ) -> Option<Duration> {
+        // 覆盖写入时按新条目的 TTL 重新计时
+        entry.ttl
+    }
+}
+
 // ============================================================================
 // 缓存层
 // ============================================================================
@@ -190,8 +224,9 @@ pub struct CacheStatsSnapshot {
 /// - 强制刷新绕过缓存
 /// - 缓存命中率统计
 /// - 按插件 ID 批量失效缓存
+/// - 按条目 TTL（set_with_ttl）
 pub struct CacheLayer {
-    cache: Cache<String, serde_json::Value>,
+    cache: Cache<String, CacheEntry>,
     stats: Arc<CacheStats>,
     config: CacheConfig,
     /// 插件 ID -> 缓存键集合 的反向索引，用于按插件批量失效
@@ -205,6 +240,7 @@ impl CacheLayer {
             .max_capacity(config.max_capacity)
             .time_to_live(config.time_to_live)
             .time_to_idle(config.time_to_idle)
+            .expire_after(EntryExpiry)
             .build();
 
         Self {
@@ -240,10 +276,10 @@ impl CacheLayer {
         let key_str = key.to_string_key();
 
         match self.cache.get(&key_str).await {
-            Some(value) => {
+            Some(entry) => {
                 self.stats.record_hit();
                 log::trace!("缓存命中: {}", key);
-                Some(value)
+                Some(entry.value)
             }
             None => {
                 self.stats.record_miss();
@@ -270,10 +306,10 @@ impl CacheLayer {
 
         // 如果不强制刷新，先尝试获取缓存
         if !force {
-            if let Some(value) = self.cache.get(&key_str).await {
+            if let Some(entry) = self.cache.get(&key_str).await {
                 self.stats.record_hit();
                 log::trace!("缓存命中: {}", key);
-                return Ok(value);
+                return Ok(entry.value);
             }
         } else {
             self.stats.record_force_refresh();
@@ -288,7 +324,9 @@ impl CacheLayer {
             .map_err(|e| CacheError::SerializationError(e))?;
 
         // 插入缓存并注册到索引
-        self.cache.insert(key_str, value.clone()).await;
+        self.cache
+            .insert(key_str, CacheEntry { value: value.clone(), ttl: None })
+            .await;
         self.register_key(key);
         self.stats.record_insert();
         log::trace!("缓存插入: {}", key);
@@ -296,10 +334,29 @@ impl CacheLayer {
         Ok(value)
     }
 
+    /// 查询缓存（force 时绕过缓存并计入强制刷新次数）
+    pub async fn lookup(&self, key: &CacheKey, force: bool) -> Option<serde_json::Value> {
+        if force {
+            self.stats.record_force_refresh();
+            log::trace!("强制刷新: {}", key);
+            return None;
+        }
+        self.get(key).await
+    }
+
     /// 设置缓存值
     pub async fn set(&self, key: &CacheKey, value: serde_json::Value) {
+        self.insert_entry(key, CacheEntry { value, ttl: None }).await;
+    }
+
+    /// 设置缓存值并指定该条目的 TTL（不超过全局 TTL）
+    pub async fn set_with_ttl(&self, key: &CacheKey, value: serde_json::Value, ttl: Duration) {
+        self.insert_entry(key, CacheEntry { value, ttl: Some(ttl) }).await;
+    }
+
+    async fn insert_entry(&self, key: &CacheKey, entry: CacheEntry) {
         let key_str = key.to_string_key();
-        self.cache.insert(key_str, value).await;
+        self.cache.insert(key_str, entry).await;
         self.register_key(key);
         self.stats.record_insert();
         log::trace!("缓存设置: {}", key);
@@ -654,4 +711,22 @@ mod tests {
         cache.set(&key, json!({"ok": true})).await;
         assert!(cache.get(&key).await.is_some());
     }
+
+    /// 测试按条目 TTL 过期与 lookup 的强制刷新
+    #[tokio::test]
+    async fn test_entry_ttl_and_lookup() {
+        let cache = CacheLayer::with_default_config();
+        let short = CacheKey::new("plugin-a", "fetchData", &json!(null));
+        let long = CacheKey::new("plugin-b", "fetchData", &json!(null));
+
+        cache.set_with_ttl(&short, json!(1), Duration::from_millis(50)).await;
+        cache.set_with_ttl(&long, json!(2), Duration::from_secs(60)).await;
+        assert_eq!(cache.lookup(&short, false).await, Some(json!(1)));
+        assert_eq!(cache.lookup(&long, true).await, None);
+        assert_eq!(cache.stats().snapshot().force_refreshes, 1);
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(cache.lookup(&short, false).await.is_none());
+        assert_eq!(cache.lookup(&long, false).await, Some(json!(2)));
+    }
 }
@@ -0,0 +1,217 @@
+// Phase 3.7: 延迟预算
+// 为弹窗等交互路径设定端到端延迟预算，逐阶段计时，超预算时降级为快照模式
+//
+// 设计要点:
+// 1. StageTimer 按顺序记录各阶段耗时，生成 LatencyReport
+// 2. 超出预算时输出结构化慢路径报告（JSON），并进入快照模式
+// 3. 快照模式下调用方直接返回上次结果，同时在后台探测实时路径
+// 4. 连续若干次探测都在预算内，视为竞争消退，恢复实时模式
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+/// 弹窗路径默认延迟预算
+pub const DEFAULT_POPUP_BUDGET: Duration = Duration::from_millis(150);
+
+/// 退出快照模式所需的连续达标次数
+const RECOVERY_STREAK: u32 = 3;
+
+// ============================================================================
+// 阶段计时
+// ============================================================================
+
+/// 单个阶段耗时
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageTiming {
+    /// 阶段名称
+    pub stage: &'static str,
+    /// 耗时（毫秒）
+    pub elapsed_ms: f64,
+}
+
+/// 一次调用的延迟报告
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyReport {
+    /// 路径名称
+    pub path: &'static str,
+    /// 预算（毫秒）
+    pub budget_ms: f64,
+    /// 总耗时（毫秒）
+    pub total_ms: f64,
+    /// 各阶段耗时
+    pub stages: Vec<StageTiming>,
+}
+
+impl LatencyReport {
+    /// 是否超出预算
+    pub fn exceeded(&self) -> bool {
+        self.total_ms > self.budget_ms
+    }
+}
+
+/// 阶段计时器
+pub struct StageTimer {
+    path: &'static str,
+    started: Instant,
+    last: Instant,
+    stages: Vec<StageTiming>,
+}
+
+impl StageTimer {
+    /// 开始计时
+    pub fn start(path: &'static str) -> Self {
+        let now = Instant::now();
+        Self {
+            path,
+            started: now,
+            last: now,
+            stages: Vec::new(),
+        }
+    }
+
+    /// 结束当前阶段（耗时从上一阶段结束时算起）
+    pub fn stage(&mut self, stage: &'static str) {
+        let now = Instant::now();
+        self.stages.push(StageTiming {
+            stage,
+            elapsed_ms: as_ms(now - self.last),
+        });
+        self.last = now;
+    }
+
+    /// 生成报告
+    pub fn finish(self, budget: Duration) -> LatencyReport {
+        LatencyReport {
+            path: self.path,
+            budget_ms: as_ms(budget),
+            total_ms: as_ms(self.started.elapsed()),
+            stages: self.stages,
+        }
+    }
+}
+
+fn as_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+// ============================================================================
+// 延迟预算
+// ============================================================================
+
+/// 延迟预算（超预算时进入快照模式）
+pub struct LatencyBudget {
+    budget: Duration,
+    /// 是否处于快照模式
+    snapshot_only: AtomicBool,
+    /// 快照模式下连续达标次数
+    recovery_streak: AtomicU32,
+}
+
+impl LatencyBudget {
+    /// 使用指定预算创建
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            snapshot_only: AtomicBool::new(false),
+            recovery_streak: AtomicU32::new(0),
+        }
+    }
+
+    /// 预算
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// 是否处于快照模式
+    pub fn is_snapshot_only(&self) -> bool {
+        self.snapshot_only.load(Ordering::Relaxed)
+    }
+
+    /// 记录一次实时路径的耗时，返回记录后是否处于快照模式
+    pub fn record(&self, report: &LatencyReport) -> bool {
+        if report.exceeded() {
+            self.recovery_streak.store(0, Ordering::Relaxed);
+            let entered = !self.snapshot_only.swap(true, Ordering::Relaxed);
+            log::warn!(
+                "慢路径报告{}: {}",
+                if entered { "（进入快照模式）" } else { "" },
+                serde_json::to_string(report).unwrap_or_default()
+            );
+            return true;
+        }
+
+        if self.is_snapshot_only() {
+            let streak = self.recovery_streak.fetch_add(1, Ordering::Relaxed) + 1;
+            if streak < RECOVERY_STREAK {
+                return true;
+            }
+            self.snapshot_only.store(false, Ordering::Relaxed);
+            self.recovery_streak.store(0, Ordering::Relaxed);
+            log::info!(
+                "{} 连续 {} 次在预算内 ({:.0}ms)，退出快照模式",
+                report.path,
+                streak,
+                report.budget_ms
+            );
+        }
+        false
+    }
+}
+
+impl Default for LatencyBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_POPUP_BUDGET)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(total_ms: f64) -> LatencyReport {
+        LatencyReport {
+            path: "get_popup_summary",
+            budget_ms: 150.0,
+            total_ms,
+            stages: vec![StageTiming {
+                stage: "plugins",
+                elapsed_ms: total_ms,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_stage_timer_records_stages_in_order() {
+        let mut timer = StageTimer::start("get_popup_summary");
+        timer.stage("plugins");
+        timer.stage("health");
+        let report = timer.finish(Duration::from_secs(10));
+
+        let names: Vec<_> = report.stages.iter().map(|s| s.stage).collect();
+        assert_eq!(names, vec!["plugins", "health"]);
+        assert_eq!(report.budget_ms, 10_000.0);
+        assert!(!report.exceeded());
+    }
+
+    #[test]
+    fn test_snapshot_mode_until_contention_subsides() {
+        let budget = LatencyBudget::default();
+        assert!(!budget.record(&report(20.0)));
+
+        // 超预算进入快照模式
+        assert!(budget.record(&report(400.0)));
+        assert!(budget.is_snapshot_only());
+
+        // 达标次数不足时保持快照模式，中途再次超预算则重新计数
+        assert!(budget.record(&report(20.0)));
+        assert!(budget.record(&report(300.0)));
+        assert!(budget.record(&report(20.0)));
+        assert!(budget.record(&report(20.0)));
+        assert!(!budget.record(&report(20.0)));
+        assert!(!budget.is_snapshot_only());
+    }
+}
@@ -1,8 +1,12 @@
 // Phase 3: 可靠性层
 // 提供并发调度、限流、缓存和重试机制
 
+pub mod activity;
 pub mod cache;
+pub mod latency;
+pub mod network;
 pub mod rate_limiter;
+pub mod reachability;
 pub mod retry;
 pub mod scheduler;
 
@@ -10,7 +14,9 @@ pub mod scheduler;
 mod tests;
 
 // 导出核心类型
+pub use activity::ActivityAssertions;
 pub use cache::{CacheConfig, CacheKey, CacheLayer, CacheStats};
+pub use network::{NetworkMonitor, NetworkStatus};
 pub use rate_limiter::{RateLimitConfig, RateLimiter, RateLimiterStats};
 pub use retry::{RetryConfig, RetryExecutor, RetryStats};
 pub use scheduler::{SchedulerConfig, TaskScheduler, TaskHandle, TaskPriority};
@@ -0,0 +1,219 @@
+// Phase 3.5: 离线模式
+// 检测网络可达性，离线时暂停刷新并在恢复连接后补刷
+//
+// 任务:
+// - 3.5.1 通过 TCP 探测判断网络可达性 ✓
+// - 3.5.2 显式离线状态（含离线起始时间） ✓
+// - 3.5.3 离线期间每个插件只排队一次刷新 ✓
+// - 3.5.4 macOS 系统可达性变化时立即探测（见 reachability） ✓
+
+use std::collections::BTreeSet;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+// ============================================================================
+// 配置
+// ============================================================================
+
+/// 默认探测目标（任一可连接即视为在线）
+const DEFAULT_PROBE_TARGETS: &[&str] = &["captive.apple.com:80", "1.1.1.1:443", "8.8.8.8:53"];
+
+/// 单个目标的连接超时
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 离线时的探测间隔
+pub const OFFLINE_PROBE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 在线时的探测间隔
+pub const ONLINE_PROBE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 系统可达性轮询间隔（查询不产生网络请求）
+pub const REACHABILITY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// 网络状态
+// ============================================================================
+
+/// 网络状态（发送给前端）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStatus {
+    /// 是否在线
+    pub online: bool,
+    /// 离线起始时间 (ISO 8601)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offline_since: Option<String>,
+    /// 等待恢复连接后刷新的插件
+    pub queued_refreshes: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct NetworkState {
+    /// 离线起始时间（None 表示在线）
+    offline_since: Option<DateTime<Utc>>,
+    /// 排队刷新的插件 ID
+    queued: BTreeSet<String>,
+    /// 最近一次读取的系统可达性
+    system_reachable: Option<bool>,
+}
+
+/// 网络监视器
+///
+/// 维护显式的在线/离线状态。状态切换由调用方根据探测结果驱动，
+/// 便于在刷新失败时立即确认，也便于后台定时探测。
+pub struct NetworkMonitor {
+    state: RwLock<NetworkState>,
+    probe_targets: Vec<String>,
+}
+
+impl NetworkMonitor {
+    /// 使用默认探测目标创建
+    pub fn new() -> Self {
+        Self::with_probe_targets(DEFAULT_PROBE_TARGETS.iter().map(|t| t.to_string()).collect())
+    }
+
+    /// 使用指定探测目标创建（`host:port` 格式）
+    pub fn with_probe_targets(probe_targets: Vec<String>) -> Self {
+        Self {
+            state: RwLock::new(NetworkState::default()),
+            probe_targets,
+        }
+    }
+
+    /// 是否在线
+    pub fn is_online(&self) -> bool {
+        self.read().offline_since.is_none()
+    }
+
+    /// 当前状态快照
+    pub fn status(&self) -> NetworkStatus {
+        let state = self.read();
+        NetworkStatus {
+            online: state.offline_since.is_none(),
+            offline_since: state.offline_since.map(|t| t.to_rfc3339()),
+            queued_refreshes: state.queued.iter().cloned().collect(),
+        }
+    }
+
+    /// 标记离线，返回是否发生了状态切换
+    pub fn mark_offline(&self, now: DateTime<Utc>) -> bool {
+        let mut state = self.write();
+        if state.offline_since.is_some() {
+            return false;
+        }
+        state.offline_since = Some(now);
+        log::warn!("网络不可达，进入离线模式");
+        true
+    }
+
+    /// 标记在线
+    ///
+    /// 从离线切换到在线时返回排队刷新的插件 ID（并清空队列），否则返回 None。
+    pub fn mark_online(&self) -> Option<Vec<String>> {
+        let mut state = self.write();
+        let since = state.offline_since.take()?;
+        let queued: Vec<String> = std::mem::take(&mut state.queued).into_iter().collect();
+        log::info!(
+            "网络已恢复 (离线自 {}), 待刷新插件: {}",
+            since.to_rfc3339(),
+            queued.len()
+        );
+        Some(queued)
+    }
+
+    /// 记录系统可达性，返回是否与上次不同（首次记录不视为变化）
+    pub fn observe_reachability(&self, reachable: bool) -> bool {
+        let mut state = self.write();
+        let previous = state.system_reachable.replace(reachable);
+        previous.is_some_and(|previous| previous != reachable)
+    }
+
+    /// 系统是否报告没有可用的网络路由
+    pub fn system_unreachable(&self) -> bool {
+        self.read().system_reachable == Some(false)
+    }
+
+    /// 离线期间排队刷新，返回是否为新排队（同一插件只排队一次）
+    pub fn queue_refresh(&self, plugin_id: &str) -> bool {
+        self.write().queued.insert(plugin_id.to_string())
+    }
+
+    /// 探测网络可达性（阻塞调用在 blocking 线程执行）
+    pub async fn probe(&self) -> bool {
+        let targets = self.probe_targets.clone();
+        tokio::task::spawn_blocking(move || targets.iter().any(|t| probe_target(t)))
+            .await
+            .unwrap_or(false)
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, NetworkState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, NetworkState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for NetworkMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 尝试连接单个目标（DNS 解析失败同样视为不可达）
+fn probe_target(target: &str) -> bool {
+    let addrs: Vec<SocketAddr> = match target.to_socket_addrs() {
+        Ok(addrs) => addrs.collect(),
+        Err(_) => return false,
+    };
+    addrs
+        .iter()
+        .any(|addr| TcpStream::connect_timeout(addr, PROBE_TIMEOUT).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_transitions_and_queue() {
+        let monitor = NetworkMonitor::with_probe_targets(vec![]);
+        assert!(monitor.is_online());
+        assert!(monitor.mark_online().is_none());
+
+        let now = Utc::now();
+        assert!(monitor.mark_offline(now));
+        assert!(!monitor.mark_offline(Utc::now()));
+        assert!(monitor.queue_refresh("a"));
+        assert!(!monitor.queue_refresh("a"));
+        assert!(monitor.queue_refresh("b"));
+
+        let status = monitor.status();
+        assert!(!status.online);
+        assert_eq!(status.offline_since, Some(now.to_rfc3339()));
+        assert_eq!(status.queued_refreshes, vec!["a", "b"]);
+
+        assert_eq!(monitor.mark_online(), Some(vec!["a".to_string(), "b".to_string()]));
+        assert!(monitor.status().online);
+        assert!(monitor.status().queued_refreshes.is_empty());
+
+        // 系统可达性只在变化时触发探测
+        assert!(!monitor.observe_reachability(true));
+        assert!(!monitor.observe_reachability(true));
+        assert!(monitor.observe_reachability(false));
+        assert!(monitor.system_unreachable());
+        assert!(monitor.observe_reachability(true));
+        assert!(!monitor.system_unreachable());
+    }
+
+    #[tokio::test]
+    async fn test_probe_without_reachable_targets() {
+        let monitor = NetworkMonitor::with_probe_targets(vec!["invalid host".to_string()]);
+        assert!(!monitor.probe().await);
+    }
+}
@@ -7,6 +7,7 @@
 // - 3.2.3 实现插件级限流 ✓
 // - 3.2.4 实现 until_ready_with_jitter ✓
 // - 3.2.5 实现限流统计 ✓
+// - 3.2.6 实现插件每分钟请求预算（manifest maxRequestsPerMinute） ✓
 
 use std::collections::HashMap;
 use std::num::NonZeroU32;
@@ -154,6 +155,8 @@ pub struct RateLimiter {
 struct PluginLimiter {
     limiter: InnerRateLimiter,
     stats: PluginRateLimiterStats,
+    /// 每分钟请求预算（None 表示使用配置的每秒速率）
+    per_minute: Option<u32>,
 }
 
 impl RateLimiter {
@@ -211,6 +214,19 @@ impl RateLimiter {
 
     /// 检查请求是否被允许（非阻塞）
     pub async fn check(&self, plugin_id: &str) -> Result<(), RateLimitError> {
+        let plugin_limiter = self.get_or_create_plugin_limiter(plugin_id).await;
+        self.check_with(plugin_id, &plugin_limiter)
+    }
+
+    /// 按每分钟请求预算检查插件请求（非阻塞）
+    ///
+    /// 预算变化（插件更新后 manifest 修改）时重建该插件的令牌桶。
+    pub async fn check_per_minute(&self, plugin_id: &str, max_per_minute: u32) -> Result<(), RateLimitError> {
+        let plugin_limiter = self.get_or_create_budget_limiter(plugin_id, max_per_minute).await;
+        self.check_with(plugin_id, &plugin_limiter)
+    }
+
+    fn check_with(&self, plugin_id: &str, plugin_limiter: &PluginLimiter) -> Result<(), RateLimitError> {
         self.stats.total_requests.fetch_add(1, Ordering::Relaxed);
 
         // 全局限流检查
@@ -220,7 +236,6 @@ impl RateLimiter {
         }
 
         // 插件级限流检查
-        let plugin_limiter = self.get_or_create_plugin_limiter(plugin_id).await;
         if plugin_limiter.limiter.check().is_err() {
             self.stats.throttled_requests.fetch_add(1, Ordering::Relaxed);
             plugin_limiter
@@ -329,6 +344,7 @@ impl RateLimiter {
         let plugin_limiter = Arc::new(PluginLimiter {
             limiter: GovernorRateLimiter::direct(quota),
             stats: PluginRateLimiterStats::default(),
+            per_minute: None,
         });
 
         limiters.insert(plugin_id.to_string(), plugin_limiter.clone());
@@ -337,6 +353,39 @@ impl RateLimiter {
         plugin_limiter
     }
 
+    /// 获取或创建按每分钟预算限流的插件限流器
+    ///
+    /// 突发容量等于每分钟预算，之后按 60s / 预算 的间隔补充令牌。
+    /// 预算为 0 时使用安全默认值 1 并记录警告。
+    async fn get_or_create_budget_limiter(&self, plugin_id: &str, max_per_minute: u32) -> Arc<PluginLimiter> {
+        {
+            let limiters = self.plugin_limiters.read().await;
+            if let Some(limiter) = limiters.get(plugin_id).filter(|l| l.per_minute == Some(max_per_minute)) {
+                return limiter.clone();
+            }
+        }
+
+        let mut limiters = self.plugin_limiters.write().await;
+        if let Some(limiter) = limiters.get(plugin_id).filter(|l| l.per_minute == Some(max_per_minute)) {
+            return limiter.clone();
+        }
+
+        let budget = NonZeroU32::new(max_per_minute).unwrap_or_else(|| {
+            log::warn!("插件 {} 请求预算无效: maxRequestsPerMinute=0，使用默认值 1", plugin_id);
+            NonZeroU32::MIN
+        });
+        let plugin_limiter = Arc::new(PluginLimiter {
+            limiter: GovernorRateLimiter::direct(Quota::per_minute(budget)),
+            stats: PluginRateLimiterStats::default(),
+            per_minute: Some(max_per_minute),
+        });
+
+        limiters.insert(plugin_id.to_string(), plugin_limiter.clone());
+        log::debug!("为插件 {} 创建请求预算限流器 ({}/min)", plugin_id, budget);
+
+        plugin_limiter
+    }
+
     /// 获取全局统计
     pub fn stats(&self) -> &RateLimiterStats {
         &self.stats
@@ -506,6 +555,24 @@ mod tests {
         assert!(limiter.plugin_stats("test-plugin").await.is_none());
     }
 
+    #[tokio::test]
+    async fn test_per_minute_budget() {
+        let limiter = RateLimiter::new(RateLimitConfig::default());
+
+        // 突发容量等于每分钟预算
+        for _ in 0..3 {
+            assert!(limiter.check_per_minute("test-plugin", 3).await.is_ok());
+        }
+        assert!(matches!(
+            limiter.check_per_minute("test-plugin", 3).await,
+            Err(RateLimitError::RateLimited { .. })
+        ));
+        assert_eq!(limiter.plugin_stats("test-plugin").await, Some((3, 1)));
+
+        // 预算变化后重建令牌桶
+        assert!(limiter.check_per_minute("test-plugin", 5).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_zero_config_fallback() {
         // 零配置应该不会 panic，而是使用默认值 1
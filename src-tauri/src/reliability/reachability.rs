@@ -0,0 +1,65 @@
+// Phase 3.5.4: 系统网络可达性
+// 通过 macOS SystemConfiguration 读取系统路由层面的可达性，作为网络变化的快速信号
+//
+// 设计要点:
+// 1. 使用 0.0.0.0 地址创建 SCNetworkReachability（默认路由），查询不触发 DNS 或网络请求，可高频轮询
+// 2. 只作为变化信号：系统报告不可达时直接进入离线，报告恢复时仍需 TCP 探测确认（门户网络、DNS 未就绪）
+// 3. 非 macOS 平台返回 None，由调用方退回定时 TCP 探测
+
+/// 系统当前是否有可用的网络路由（None 表示平台不支持或查询失败）
+pub fn system_reachable() -> Option<bool> {
+    #[cfg(target_os = "macos")]
+    {
+        macos::default_route_reachable()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::ffi::c_void;
+
+    /// kSCNetworkReachabilityFlagsReachable
+    const FLAG_REACHABLE: u32 = 1 << 1;
+    /// kSCNetworkReachabilityFlagsConnectionRequired（需要先建立连接，如拨号/VPN 按需连接）
+    const FLAG_CONNECTION_REQUIRED: u32 = 1 << 2;
+
+    #[link(name = "SystemConfiguration", kind = "framework")]
+    extern "C" {
+        fn SCNetworkReachabilityCreateWithAddress(
+            allocator: *const c_void,
+            address: *const libc::sockaddr,
+        ) -> *const c_void;
+        fn SCNetworkReachabilityGetFlags(target: *const c_void, flags: *mut u32) -> u8;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFRelease(cf: *const c_void);
+    }
+
+    /// 查询默认路由（0.0.0.0）的可达性标志
+    pub fn default_route_reachable() -> Option<bool> {
+        // SAFETY: sockaddr_in 全零是合法值；创建的 reachability 对象在本函数内释放
+        unsafe {
+            let mut address: libc::sockaddr_in = std::mem::zeroed();
+            address.sin_len = std::mem::size_of::<libc::sockaddr_in>() as u8;
+            address.sin_family = libc::AF_INET as libc::sa_family_t;
+
+            let target = SCNetworkReachabilityCreateWithAddress(
+                std::ptr::null(),
+                &address as *const libc::sockaddr_in as *const libc::sockaddr,
+            );
+            if target.is_null() {
+                return None;
+            }
+            let mut flags = 0u32;
+            let ok = SCNetworkReachabilityGetFlags(target, &mut flags) != 0;
+            CFRelease(target);
+            ok.then_some(flags & FLAG_REACHABLE != 0 && flags & FLAG_CONNECTION_REQUIRED == 0)
+        }
+    }
+}
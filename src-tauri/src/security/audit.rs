@@ -0,0 +1,188 @@
+// Phase 5A.5: 安全审计日志
+// 记录安全相关事件（完整性校验失败、插件隔离等），追加写入 JSON Lines 文件
+//
+// 设计要点:
+// 1. 每行一条事件，便于追加写入和按行读取
+// 2. 超过大小上限时轮转为 audit.log.1（仅保留一个历史文件）
+// 3. 写入失败不影响调用方主流程，由调用方决定是否记录日志
+
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::security::Result;
+
+/// 审计日志文件大小上限: 1MB
+pub const MAX_AUDIT_LOG_SIZE: u64 = 1024 * 1024;
+
+// ============================================================================
+// 审计事件
+// ============================================================================
+
+/// 审计事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventKind {
+    /// 完整性校验失败
+    IntegrityFailed,
+    /// 插件被隔离
+    PluginQuarantined,
+    /// 插件解除隔离（重新校验通过）
+    QuarantineReleased,
+}
+
+/// 审计事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEvent {
+    /// 事件时间 (ISO 8601)
+    pub timestamp: String,
+    /// 事件类型
+    pub kind: AuditEventKind,
+    /// 相关插件 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_id: Option<String>,
+    /// 事件描述
+    pub message: String,
+    /// 详细信息
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+}
+
+impl AuditEvent {
+    /// 创建插件相关的审计事件（时间为当前时间）
+    pub fn plugin(kind: AuditEventKind, plugin_id: &str, message: impl Into<String>) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339(),
+            kind,
+            plugin_id: Some(plugin_id.to_string()),
+            message: message.into(),
+            details: Vec::new(),
+        }
+    }
+
+    /// 附加详细信息
+    pub fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+// ============================================================================
+// 审计日志
+// ============================================================================
+
+/// 审计日志（追加写入 JSON Lines）
+pub struct AuditLog {
+    path: PathBuf,
+    /// 串行化写入与轮转
+    lock: Mutex<()>,
+}
+
+impl AuditLog {
+    /// 使用指定文件路径创建
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// 追加一条事件
+    pub fn record(&self, event: &AuditEvent) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.rotate_if_needed()?;
+
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// 读取最近的事件（按时间顺序，最多 `limit` 条；无法解析的行会被跳过）
+    pub fn recent(&self, limit: usize) -> Vec<AuditEvent> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(_) => return Vec::new(),
+        };
+        let events: Vec<AuditEvent> = BufReader::new(file)
+            .lines()
+            .map_while(|line| line.ok())
+            .filter_map(|line| serde_json::from_str(&line).ok())
+            .collect();
+        let skip = events.len().saturating_sub(limit);
+        events.into_iter().skip(skip).collect()
+    }
+
+    /// 超过大小上限时轮转
+    fn rotate_if_needed(&self) -> Result<()> {
+        let size = match fs::metadata(&self.path) {
+            Ok(meta) => meta.len(),
+            Err(_) => return Ok(()),
+        };
+        if size >= MAX_AUDIT_LOG_SIZE {
+            let rotated = self.path.with_extension("log.1");
+            fs::rename(&self.path, rotated)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_recent() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = AuditLog::new(temp_dir.path().join("nested").join("audit.log"));
+        assert!(log.recent(10).is_empty());
+
+        for i in 0..3 {
+            let event = AuditEvent::plugin(
+                AuditEventKind::IntegrityFailed,
+                "demo",
+                format!("event {}", i),
+            )
+            .with_details(vec!["plugin.js".to_string()]);
+            log.record(&event).unwrap();
+        }
+
+        let recent = log.recent(2);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].message, "event 1");
+        assert_eq!(recent[1].message, "event 2");
+        assert_eq!(recent[1].plugin_id.as_deref(), Some("demo"));
+        assert_eq!(recent[1].details, vec!["plugin.js"]);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("audit.log");
+        fs::write(&path, vec![b'x'; MAX_AUDIT_LOG_SIZE as usize]).unwrap();
+
+        let log = AuditLog::new(&path);
+        let event = AuditEvent::plugin(AuditEventKind::PluginQuarantined, "demo", "quarantined");
+        log.record(&event).unwrap();
+
+        assert!(temp_dir.path().join("audit.log.1").exists());
+        assert_eq!(log.recent(10), vec![event]);
+    }
+}
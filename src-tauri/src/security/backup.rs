@@ -0,0 +1,323 @@
+// Phase 5A.11: 插件版本备份
+// 每次更新或覆盖安装前保存旧版本，保留最近 N 个版本（BACKUP_VERSIONS），可回滚到任一版本
+//
+// 目录布局（与插件目录同级的 plugin_backups/）:
+//   plugin_backups/<plugin_id>/<version>/backup.json   备份元数据
+//   plugin_backups/<plugin_id>/<version>/files/...     插件文件副本
+//
+// 设计要点:
+// 1. 同一版本重复备份时覆盖旧副本，超出保留数量时按备份时间删除最旧的版本
+// 2. 复制时计算内容哈希（按相对路径排序的各文件 SHA-256 再求 SHA-256），恢复前校验
+// 3. 拒绝符号链接；恢复先复制到暂存目录，再替换插件目录
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::security::integrity::calculate_sha256_bytes;
+use crate::security::{Result, SecurityError, BACKUP_VERSIONS};
+
+/// 备份元数据文件名
+const METADATA_FILE: &str = "backup.json";
+
+/// 备份文件副本目录名
+const FILES_DIR: &str = "files";
+
+// ============================================================================
+// 备份元数据
+// ============================================================================
+
+/// 插件版本备份
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginBackup {
+    /// 插件 ID（插件目录名）
+    pub plugin_id: String,
+    /// 备份的版本号（manifest.version）
+    pub version: String,
+    /// 该版本的安装时间（manifest.json 的修改时间）
+    pub installed_at: DateTime<Utc>,
+    /// 备份时间
+    pub backed_up_at: DateTime<Utc>,
+    /// 内容哈希
+    pub hash: String,
+    /// 文件数
+    pub files: usize,
+    /// 总字节数
+    pub size_bytes: u64,
+}
+
+// ============================================================================
+// 备份存储
+// ============================================================================
+
+/// 插件版本备份存储
+pub struct BackupStore {
+    root: PathBuf,
+}
+
+impl BackupStore {
+    /// 创建备份存储
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// 与插件目录同级的备份目录
+    pub fn dir_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("plugin_backups")
+    }
+
+    /// 备份插件目录（读取 manifest.json 中的 id 和 version）
+    pub fn backup(&self, plugin_dir: &Path) -> Result<PluginBackup> {
+        let manifest_path = plugin_dir.join("manifest.json");
+        let manifest: serde_json::Value = serde_json::from_slice(&fs::read(&manifest_path)?)?;
+        let plugin_id = plugin_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let version = manifest
+            .get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let installed_at = fs::metadata(&manifest_path)?
+            .modified()
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+
+        let backup_dir = self.version_dir(&plugin_id, &version);
+        let staging = sibling(&backup_dir, ".", ".tmp");
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        let mut hashes = Vec::new();
+        let size_bytes = copy_and_hash(plugin_dir, &staging.join(FILES_DIR), Path::new(""), &mut hashes)?;
+
+        let backup = PluginBackup {
+            plugin_id,
+            version,
+            installed_at,
+            backed_up_at: Utc::now(),
+            hash: content_hash(&mut hashes),
+            files: hashes.len(),
+            size_bytes,
+        };
+        fs::write(staging.join(METADATA_FILE), serde_json::to_vec_pretty(&backup)?)?;
+
+        if backup_dir.exists() {
+            fs::remove_dir_all(&backup_dir)?;
+        }
+        fs::rename(&staging, &backup_dir)?;
+        log::info!("已备份插件 {} v{} ({} 个文件)", backup.plugin_id, backup.version, backup.files);
+
+        self.prune(&backup.plugin_id)?;
+        Ok(backup)
+    }
+
+    /// 列出插件的备份（最新在前）
+    pub fn list(&self, plugin_id: &str) -> Result<Vec<PluginBackup>> {
+        let plugin_dir = self.root.join(safe_name(plugin_id));
+        if !plugin_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut backups = Vec::new();
+        for entry in fs::read_dir(&plugin_dir)? {
+            let entry = entry?;
+            // 跳过未完成的暂存目录
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let metadata_path = entry.path().join(METADATA_FILE);
+            if let Ok(bytes) = fs::read(&metadata_path) {
+                match serde_json::from_slice::<PluginBackup>(&bytes) {
+                    Ok(backup) => backups.push(backup),
+                    Err(e) => log::warn!("备份元数据无效: {:?}: {}", metadata_path, e),
+                }
+            }
+        }
+        backups.sort_by(|a, b| b.backed_up_at.cmp(&a.backed_up_at));
+        Ok(backups)
+    }
+
+    /// 列出所有插件的备份（按插件 ID 排序，同一插件最新在前）
+    pub fn list_all(&self) -> Result<Vec<PluginBackup>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut plugin_ids = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                plugin_ids.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        plugin_ids.sort();
+
+        let mut backups = Vec::new();
+        for plugin_id in plugin_ids {
+            backups.extend(self.list(&plugin_id)?);
+        }
+        Ok(backups)
+    }
+
+    /// 查找备份（`version` 为空时返回最新的备份）
+    pub fn find(&self, plugin_id: &str, version: Option<&str>) -> Result<Option<PluginBackup>> {
+        let backups = self.list(plugin_id)?;
+        Ok(match version {
+            Some(version) => backups.into_iter().find(|b| b.version == version),
+            None => backups.into_iter().next(),
+        })
+    }
+
+    /// 将备份恢复到插件目录（校验内容哈希后替换）
+    pub fn restore(&self, backup: &PluginBackup, target_dir: &Path) -> Result<()> {
+        let files_dir = self.version_dir(&backup.plugin_id, &backup.version).join(FILES_DIR);
+        let staging = sibling(target_dir, "", ".restoring");
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+
+        let mut hashes = Vec::new();
+        copy_and_hash(&files_dir, &staging, Path::new(""), &mut hashes)?;
+        let actual = content_hash(&mut hashes);
+        if actual != backup.hash {
+            fs::remove_dir_all(&staging)?;
+            return Err(SecurityError::HashMismatch {
+                file: format!("{} v{} 备份", backup.plugin_id, backup.version),
+                expected: backup.hash.clone(),
+                actual,
+            });
+        }
+
+        if target_dir.exists() {
+            fs::remove_dir_all(target_dir)?;
+        }
+        fs::rename(&staging, target_dir)?;
+        log::info!("已恢复插件 {} v{} 的备份", backup.plugin_id, backup.version);
+        Ok(())
+    }
+
+    /// 删除超出保留数量（BACKUP_VERSIONS）的旧备份
+    fn prune(&self, plugin_id: &str) -> Result<()> {
+        for stale in self.list(plugin_id)?.into_iter().skip(BACKUP_VERSIONS) {
+            fs::remove_dir_all(self.version_dir(&stale.plugin_id, &stale.version))?;
+            log::info!("删除旧备份: {} v{}", stale.plugin_id, stale.version);
+        }
+        Ok(())
+    }
+
+    /// 某个版本的备份目录
+    fn version_dir(&self, plugin_id: &str, version: &str) -> PathBuf {
+        self.root.join(safe_name(plugin_id)).join(safe_name(version))
+    }
+}
+
+/// 同级暂存目录（目录名加前后缀，版本号中的 `.` 不受影响）
+fn sibling(dir: &Path, prefix: &str, suffix: &str) -> PathBuf {
+    let name = dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    dir.with_file_name(format!("{}{}{}", prefix, name, suffix))
+}
+
+/// 目录名中只保留字母、数字和 `.-_+`
+fn safe_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || "._-+".contains(c) { c } else { '_' })
+        .collect()
+}
+
+/// 递归复制目录并记录每个文件的 (相对路径, SHA-256)，返回复制的字节数
+fn copy_and_hash(src: &Path, dst: &Path, relative: &Path, hashes: &mut Vec<(String, String)>) -> Result<u64> {
+    fs::create_dir_all(dst)?;
+    let mut total = 0;
+
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let metadata = src_path.symlink_metadata()?;
+        if metadata.is_symlink() {
+            return Err(SecurityError::SymlinkRejected {
+                path: src_path.to_string_lossy().to_string(),
+            });
+        }
+
+        let relative = relative.join(entry.file_name());
+        let dst_path = dst.join(entry.file_name());
+        if metadata.is_dir() {
+            total += copy_and_hash(&src_path, &dst_path, &relative, hashes)?;
+        } else {
+            let bytes = fs::read(&src_path)?;
+            fs::write(&dst_path, &bytes)?;
+            total += bytes.len() as u64;
+            hashes.push((relative.to_string_lossy().replace('\\', "/"), calculate_sha256_bytes(&bytes)));
+        }
+    }
+
+    Ok(total)
+}
+
+/// 按相对路径排序后计算整体内容哈希
+fn content_hash(hashes: &mut [(String, String)]) -> String {
+    hashes.sort();
+    let joined: String = hashes
+        .iter()
+        .map(|(path, hash)| format!("{}:{}\n", path, hash))
+        .collect();
+    calculate_sha256_bytes(joined.as_bytes())
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_plugin(dir: &Path, version: &str) {
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        fs::write(
+            dir.join("manifest.json"),
+            format!(r#"{{"id": "demo", "version": "{}"}}"#, version),
+        )
+        .unwrap();
+        fs::write(dir.join("lib/plugin.js"), format!("// v{}", version)).unwrap();
+    }
+
+    #[test]
+    fn test_backup_rotation_and_restore() {
+        let temp = TempDir::new().unwrap();
+        let plugin_dir = temp.path().join("plugins/demo");
+        let store = BackupStore::new(BackupStore::dir_path(&temp.path().join("plugins")));
+
+        for version in ["0.9.0", "1.0.0", "1.1.0", "1.2.0"] {
+            write_plugin(&plugin_dir, version);
+            let backup = store.backup(&plugin_dir).unwrap();
+            assert_eq!((backup.version.as_str(), backup.files), (version, 2));
+        }
+
+        // 只保留最近 BACKUP_VERSIONS 个版本，最新在前
+        let versions: Vec<_> = store.list("demo").unwrap().into_iter().map(|b| b.version).collect();
+        assert_eq!(versions, ["1.2.0", "1.1.0", "1.0.0"]);
+        assert!(store.find("demo", Some("0.9.0")).unwrap().is_none());
+        assert_eq!(store.list_all().unwrap().len(), 3);
+
+        let backup = store.find("demo", Some("1.1.0")).unwrap().unwrap();
+        store.restore(&backup, &plugin_dir).unwrap();
+        assert_eq!(fs::read_to_string(plugin_dir.join("lib/plugin.js")).unwrap(), "// v1.1.0");
+
+        // 备份内容被篡改时拒绝恢复，插件目录保持不变
+        let tampered = store.root.join("demo/1.2.0/files/lib/plugin.js");
+        fs::write(tampered, "tampered").unwrap();
+        let latest = store.find("demo", None).unwrap().unwrap();
+        assert!(matches!(store.restore(&latest, &plugin_dir), Err(SecurityError::HashMismatch { .. })));
+        assert_eq!(fs::read_to_string(plugin_dir.join("lib/plugin.js")).unwrap(), "// v1.1.0");
+    }
+}
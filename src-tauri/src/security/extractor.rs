@@ -1,16 +1,75 @@
 // Phase 5A.3: 安全解压器
 // 提供安全的 ZIP 解压功能，防止路径穿越、符号链接等攻击
 
+use crate::security::integrity::{calculate_sha256_bytes, HashStats};
 use crate::security::{
     Result, SecurityError, ALLOWED_EXTENSIONS, BACKUP_VERSIONS, MAX_ENTRIES, MAX_FILE_SIZE,
     MAX_TOTAL_SIZE,
 };
+use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 use tempfile::TempDir;
 use zip::ZipArchive;
 
+// ============================================================================
+// 解压结果
+// ============================================================================
+
+/// 解压结果：写出文件的同时计算的 SHA-256，完整性校验时无需再次读盘
+#[derive(Debug, Clone, Default)]
+pub struct ExtractReport {
+    /// 相对路径（`/` 分隔）-> 十六进制哈希
+    pub file_hashes: HashMap<String, String>,
+    /// 哈希计算统计
+    pub hash_stats: HashStats,
+}
+
+impl ExtractReport {
+    /// 以子目录为根重新计算相对路径（压缩包只包含一个插件子目录时）
+    pub fn rebase(self, prefix: &Path) -> Self {
+        if prefix.as_os_str().is_empty() {
+            return self;
+        }
+        let prefix = format!("{}/", relative_key(prefix));
+        let file_hashes = self
+            .file_hashes
+            .into_iter()
+            .filter_map(|(name, hash)| name.strip_prefix(&prefix).map(|rest| (rest.to_string(), hash)))
+            .collect();
+        Self {
+            file_hashes,
+            hash_stats: self.hash_stats,
+        }
+    }
+}
+
+/// 解压进度（每写出一个条目报告一次）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractProgress {
+    /// 已处理的条目数（含目录）
+    pub entries_processed: usize,
+    /// 条目总数
+    pub total_entries: usize,
+    /// 已写出的字节数
+    pub bytes_written: u64,
+}
+
+/// 解压进度回调
+pub type ExtractProgressCallback = Arc<dyn Fn(&ExtractProgress) + Send + Sync>;
+
+/// 相对路径统一使用 `/` 分隔（与 manifest.files 的键一致）
+fn relative_key(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 // ============================================================================
 // 安全解压器
 // ============================================================================
@@ -36,6 +95,10 @@ pub struct SecureExtractor {
     allowed_extensions: Vec<String>,
     /// 备份保留版本数
     backup_versions: usize,
+    /// 解压进度回调
+    progress: Option<ExtractProgressCallback>,
+    /// 取消标记（置位后在下一个条目前中止）
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl SecureExtractor {
@@ -47,6 +110,8 @@ impl SecureExtractor {
             max_entries: MAX_ENTRIES,
             allowed_extensions: ALLOWED_EXTENSIONS.iter().map(|s| s.to_string()).collect(),
             backup_versions: BACKUP_VERSIONS,
+            progress: None,
+            cancel: None,
         }
     }
 
@@ -69,6 +134,28 @@ impl SecureExtractor {
         self
     }
 
+    /// 设置解压进度回调
+    pub fn with_progress(mut self, progress: Option<ExtractProgressCallback>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// 设置取消标记
+    ///
+    /// 标记置位后解压在处理下一个条目前返回 `SecurityError::Cancelled`，
+    /// 已写出的部分文件位于临时目录中，随临时目录一起删除，目标目录保持不变。
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// 是否已请求取消
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
     /// 安全解压 ZIP 文件到目标目录
     ///
     /// # 参数
@@ -76,7 +163,7 @@ impl SecureExtractor {
     /// - `target_dir`: 目标目录
     ///
     /// # 返回
-    /// - `Ok(())`: 解压成功
+    /// - `Ok(ExtractReport)`: 解压成功，包含解压时计算的文件哈希
     /// - `Err(SecurityError)`: 解压失败
     ///
     /// # 安全检查
@@ -85,11 +172,13 @@ impl SecureExtractor {
     /// 3. 文件大小限制
     /// 4. 文件类型白名单
     /// 5. 原子替换
+    ///
+    /// 取消或失败时临时目录被删除，目标目录保持不变。
     pub fn extract<P: AsRef<Path>, Q: AsRef<Path>>(
         &self,
         zip_path: P,
         target_dir: Q,
-    ) -> Result<()> {
+    ) -> Result<ExtractReport> {
         let zip_path = zip_path.as_ref();
         let target_dir = target_dir.as_ref();
 
@@ -103,8 +192,11 @@ impl SecureExtractor {
         // 3. 创建临时目录
         let temp_dir = TempDir::new()?;
 
-        // 4. 解压到临时目录
-        self.extract_to_temp(&mut archive, temp_dir.path())?;
+        // 4. 解压到临时目录（同时计算文件哈希），出错或取消时临时目录随 drop 删除
+        let report = self.extract_to_temp(&mut archive, temp_dir.path())?;
+        if self.is_cancelled() {
+            return Err(SecurityError::Cancelled);
+        }
 
         // 5. 备份现有目录
         if target_dir.exists() {
@@ -115,7 +207,7 @@ impl SecureExtractor {
         self.atomic_replace(temp_dir.path(), target_dir)?;
 
         log::info!("安全解压完成: {:?} -> {:?}", zip_path, target_dir);
-        Ok(())
+        Ok(report)
     }
 
     /// 预验证 ZIP 内容
@@ -221,12 +313,19 @@ impl SecureExtractor {
         self.allowed_extensions.iter().any(|allowed| allowed == ext)
     }
 
-    /// 解压到临时目录 (按实际写出字节累计总大小)
-    fn extract_to_temp(&self, archive: &mut ZipArchive<File>, temp_dir: &Path) -> Result<()> {
+    /// 解压到临时目录 (按实际写出字节累计总大小，写出时计算哈希)
+    fn extract_to_temp(&self, archive: &mut ZipArchive<File>, temp_dir: &Path) -> Result<ExtractReport> {
         // 按实际写出字节累计总大小 (不依赖 ZIP 元数据)
         let mut actual_total_size: u64 = 0;
+        let mut report = ExtractReport::default();
+        let total_entries = archive.len();
+
+        for i in 0..total_entries {
+            if self.is_cancelled() {
+                log::info!("解压已取消: 已处理 {}/{} 个条目", i, total_entries);
+                return Err(SecurityError::Cancelled);
+            }
 
-        for i in 0..archive.len() {
             let mut file = archive.by_index(i)?;
 
             // 使用 enclosed_name() 安全获取路径 (已在 pre_validate 中验证，这里再次检查以确保安全)
@@ -281,11 +380,25 @@ impl SecureExtractor {
 
                 outfile.write_all(&buffer)?;
 
+                // 文件内容已在内存中，直接计算哈希，省去校验时的再次读盘
+                let started = Instant::now();
+                let hash = calculate_sha256_bytes(&buffer);
+                report.hash_stats.record(bytes_written, started.elapsed());
+                report.file_hashes.insert(relative_key(&safe_name), hash);
+
                 log::debug!("解压文件: {} ({} bytes, 累计 {} bytes)", name, bytes_written, actual_total_size);
             }
+
+            if let Some(progress) = &self.progress {
+                progress(&ExtractProgress {
+                    entries_processed: i + 1,
+                    total_entries,
+                    bytes_written: actual_total_size,
+                });
+            }
         }
 
-        Ok(())
+        Ok(report)
     }
 
     /// 创建备份
@@ -512,8 +625,62 @@ mod tests {
         let target_dir = temp_dir.path().join("plugin");
         let extractor = SecureExtractor::new();
 
-        assert!(extractor.extract(&zip_path, &target_dir).is_ok());
+        let report = extractor.extract(&zip_path, &target_dir).unwrap();
         assert!(target_dir.join("plugin.js").exists());
+        assert_eq!(
+            report.file_hashes.get("plugin.js"),
+            Some(&calculate_sha256_bytes(b"console.log('test');"))
+        );
+        assert_eq!((report.hash_stats.files, report.hash_stats.bytes), (1, 20));
+    }
+
+    #[test]
+    fn test_extract_progress_and_cancel() {
+        let temp_dir = TempDir::new().unwrap();
+        let zip_path = write_test_zip(
+            &temp_dir,
+            &[("manifest.json", b"{}"), ("plugin.js", b"run();"), ("icon.svg", b"<svg/>")],
+        );
+        let target_dir = temp_dir.path().join("plugin");
+
+        // 完整解压：每个条目报告一次进度
+        let reports = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        SecureExtractor::new()
+            .with_progress(Some(Arc::new(move |p: &ExtractProgress| sink.lock().unwrap().push(*p))))
+            .extract(&zip_path, &target_dir)
+            .unwrap();
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 3);
+        assert_eq!(
+            reports[2],
+            ExtractProgress { entries_processed: 3, total_entries: 3, bytes_written: 14 }
+        );
+
+        // 处理第一个条目后取消：返回 Cancelled，不会创建目标目录
+        let cancel = Arc::new(AtomicBool::new(false));
+        let flag = cancel.clone();
+        let result = SecureExtractor::new()
+            .with_progress(Some(Arc::new(move |_: &ExtractProgress| flag.store(true, Ordering::Relaxed))))
+            .with_cancel(cancel)
+            .extract(&zip_path, temp_dir.path().join("cancelled"));
+        assert!(matches!(result, Err(SecurityError::Cancelled)));
+        assert!(!temp_dir.path().join("cancelled").exists());
+    }
+
+    #[test]
+    fn test_extract_report_rebase() {
+        let report = ExtractReport {
+            file_hashes: HashMap::from([
+                ("my-plugin/plugin.js".to_string(), "a".to_string()),
+                ("my-plugin/icons/logo.svg".to_string(), "b".to_string()),
+                ("README.json".to_string(), "c".to_string()),
+            ]),
+            hash_stats: HashStats::default(),
+        };
+        let rebased = report.rebase(Path::new("my-plugin"));
+        assert_eq!(rebased.file_hashes.len(), 2);
+        assert_eq!(rebased.file_hashes["icons/logo.svg"], "b");
     }
 
     #[test]
@@ -6,13 +6,17 @@ use serde_json::Value;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::Read;
 use std::path::{Component, Path};
+use std::time::{Duration, Instant};
 
 // ============================================================================
 // SHA256 计算
 // ============================================================================
 
+/// 读取缓冲区大小（较大的块让硬件 SHA-2 / 汇编实现保持满载）
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+
 /// 计算文件的 SHA256 哈希值
 ///
 /// # 参数
@@ -22,21 +26,27 @@ use std::path::{Component, Path};
 /// - `Ok(String)`: 十六进制格式的 SHA256 哈希值
 /// - `Err(SecurityError)`: 读取文件失败
 pub fn calculate_sha256<P: AsRef<Path>>(path: P) -> Result<String> {
-    let file = File::open(path.as_ref())?;
-    let mut reader = BufReader::new(file);
+    hash_file(path.as_ref()).map(|(hash, _)| hash)
+}
+
+/// 计算文件哈希，同时返回读取的字节数
+fn hash_file(path: &Path) -> Result<(String, u64)> {
+    let mut file = File::open(path)?;
     let mut hasher = Sha256::new();
+    let mut total: u64 = 0;
 
-    let mut buffer = [0u8; 8192];
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
     loop {
-        let bytes_read = reader.read(&mut buffer)?;
+        let bytes_read = file.read(&mut buffer)?;
         if bytes_read == 0 {
             break;
         }
         hasher.update(&buffer[..bytes_read]);
+        total += bytes_read as u64;
     }
 
     let hash = hasher.finalize();
-    Ok(format!("{:x}", hash))
+    Ok((format!("{:x}", hash), total))
 }
 
 /// 计算字节数据的 SHA256 哈希值
@@ -53,6 +63,39 @@ pub fn calculate_sha256_bytes(data: &[u8]) -> String {
     format!("{:x}", hash)
 }
 
+/// 哈希计算统计（用于报告校验吞吐量）
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HashStats {
+    /// 已计算哈希的文件数
+    pub files: usize,
+    /// 已计算哈希的字节数
+    pub bytes: u64,
+    /// 哈希计算耗时
+    pub elapsed: Duration,
+}
+
+impl HashStats {
+    /// 记录一个文件
+    pub fn record(&mut self, bytes: u64, elapsed: Duration) {
+        self.files += 1;
+        self.bytes += bytes;
+        self.elapsed += elapsed;
+    }
+
+    /// 合并另一段统计
+    pub fn merge(&mut self, other: HashStats) {
+        self.files += other.files;
+        self.bytes += other.bytes;
+        self.elapsed += other.elapsed;
+    }
+
+    /// 吞吐量 (MB/s)，没有可计量的耗时时返回 None
+    pub fn throughput_mb_per_sec(&self) -> Option<f64> {
+        let secs = self.elapsed.as_secs_f64();
+        (self.bytes > 0 && secs > 0.0).then(|| self.bytes as f64 / (1024.0 * 1024.0) / secs)
+    }
+}
+
 // ============================================================================
 // 路径安全校验
 // ============================================================================
@@ -108,12 +151,14 @@ fn is_safe_filename(filename: &str) -> bool {
 pub fn verify_file_hash<P: AsRef<Path>>(path: P, expected_hash: &str) -> Result<()> {
     let path = path.as_ref();
 
-    // 解析期望哈希 (支持 "sha256:{hex}" 或纯 hex 格式)
-    let expected = expected_hash.strip_prefix("sha256:").unwrap_or(expected_hash);
-
     // 计算实际哈希
     let actual = calculate_sha256(path)?;
+    compare_hash(path, expected_hash, actual)
+}
 
+/// 比较实际哈希与期望哈希 (支持 "sha256:{hex}" 或纯 hex 格式)
+fn compare_hash(path: &Path, expected_hash: &str, actual: String) -> Result<()> {
+    let expected = expected_hash.strip_prefix("sha256:").unwrap_or(expected_hash);
     if actual != expected {
         return Err(SecurityError::HashMismatch {
             file: path.display().to_string(),
@@ -121,21 +166,26 @@ pub fn verify_file_hash<P: AsRef<Path>>(path: P, expected_hash: &str) -> Result<
             actual,
         });
     }
-
     Ok(())
 }
 
-/// 验证 manifest.files 中的所有文件哈希
+/// 验证 manifest.files 中的所有文件哈希，优先使用解压时已计算的哈希
 ///
 /// # 参数
 /// - `manifest`: manifest.json 内容
 /// - `plugin_dir`: 插件目录路径
+/// - `known`: 相对路径 -> 十六进制哈希（解压时边写边算），未命中的文件从磁盘读取计算
 ///
 /// # 返回
-/// - `Ok(())`: 所有文件哈希匹配
+/// - `Ok(HashStats)`: 所有文件哈希匹配，返回本次从磁盘计算哈希的统计
 /// - `Err(SecurityError)`: 哈希不匹配或文件缺失
-pub fn verify_manifest_files<P: AsRef<Path>>(manifest: &Value, plugin_dir: P) -> Result<()> {
+pub fn verify_manifest_files<P: AsRef<Path>>(
+    manifest: &Value,
+    plugin_dir: P,
+    known: &HashMap<String, String>,
+) -> Result<HashStats> {
     let plugin_dir = plugin_dir.as_ref();
+    let mut stats = HashStats::default();
 
     // 获取 files 字段
     let files = match manifest.get("files") {
@@ -149,7 +199,7 @@ pub fn verify_manifest_files<P: AsRef<Path>>(manifest: &Value, plugin_dir: P) ->
         None => {
             // 没有 files 字段，跳过验证
             log::debug!("manifest 没有 files 字段，跳过文件哈希验证");
-            return Ok(());
+            return Ok(stats);
         }
     };
 
@@ -176,13 +226,74 @@ pub fn verify_manifest_files<P: AsRef<Path>>(manifest: &Value, plugin_dir: P) ->
             });
         }
 
-        // 验证哈希
-        verify_file_hash(&file_path, hash)?;
+        // 验证哈希（解压时已计算的直接比较，避免再次读盘）
+        let actual = match known.get(filename) {
+            Some(actual) => actual.clone(),
+            None => {
+                let started = Instant::now();
+                let (actual, bytes) = hash_file(&file_path)?;
+                stats.record(bytes, started.elapsed());
+                actual
+            }
+        };
+        compare_hash(&file_path, hash, actual)?;
 
         log::debug!("文件验证通过: {}", filename);
     }
 
-    Ok(())
+    Ok(stats)
+}
+
+/// 逐个校验文件哈希并收集所有问题（不在首个错误处中止）
+///
+/// 用于安装后的完整性自检，便于一次性报告所有被篡改或缺失的文件。
+///
+/// # 参数
+/// - `files`: 文件名 -> 期望哈希 的映射 (manifest.files)
+/// - `plugin_dir`: 插件目录路径
+///
+/// # 返回
+/// - 所有校验失败的文件错误（为空表示全部通过）
+pub fn check_files<P: AsRef<Path>>(
+    files: &HashMap<String, String>,
+    plugin_dir: P,
+) -> Vec<SecurityError> {
+    let plugin_dir = plugin_dir.as_ref();
+    let mut names: Vec<&String> = files.keys().collect();
+    names.sort();
+
+    let mut issues = Vec::new();
+    for filename in names {
+        if !is_safe_filename(filename) {
+            issues.push(SecurityError::PathTraversal {
+                path: filename.clone(),
+            });
+            continue;
+        }
+
+        let file_path = plugin_dir.join(filename);
+        if !file_path.is_file() {
+            issues.push(SecurityError::FileMissing {
+                file: filename.clone(),
+            });
+            continue;
+        }
+
+        let expected = &files[filename];
+        match verify_file_hash(&file_path, expected) {
+            Ok(()) => {}
+            Err(SecurityError::HashMismatch {
+                expected, actual, ..
+            }) => issues.push(SecurityError::HashMismatch {
+                file: filename.clone(),
+                expected,
+                actual,
+            }),
+            Err(e) => issues.push(e),
+        }
+    }
+
+    issues
 }
 
 /// 生成目录中所有文件的哈希映射
@@ -311,7 +422,34 @@ mod tests {
             }
         });
 
-        assert!(verify_manifest_files(&manifest, temp_dir.path()).is_ok());
+        assert!(verify_manifest_files(&manifest, temp_dir.path(), &HashMap::new()).is_ok());
+    }
+
+    #[test]
+    fn test_verify_manifest_files_known_hashes() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("plugin.js"), b"console.log('hello');").unwrap();
+        std::fs::write(temp_dir.path().join("icon.svg"), b"<svg/>").unwrap();
+        let js_hash = calculate_sha256(temp_dir.path().join("plugin.js")).unwrap();
+        let svg_hash = calculate_sha256(temp_dir.path().join("icon.svg")).unwrap();
+
+        let manifest = json!({
+            "id": "test-plugin",
+            "files": {
+                "plugin.js": format!("sha256:{}", js_hash),
+                "icon.svg": svg_hash
+            }
+        });
+
+        // 解压时已计算的哈希直接比较，只有未命中的文件从磁盘读取
+        let known = HashMap::from([("plugin.js".to_string(), js_hash.clone())]);
+        let stats = verify_manifest_files(&manifest, temp_dir.path(), &known).unwrap();
+        assert_eq!((stats.files, stats.bytes), (1, 6));
+
+        // 已知哈希与 manifest 不符时拒绝
+        let tampered = HashMap::from([("plugin.js".to_string(), svg_hash)]);
+        let result = verify_manifest_files(&manifest, temp_dir.path(), &tampered);
+        assert!(matches!(result, Err(SecurityError::HashMismatch { .. })));
     }
 
     #[test]
@@ -325,7 +463,7 @@ mod tests {
             }
         });
 
-        let result = verify_manifest_files(&manifest, temp_dir.path());
+        let result = verify_manifest_files(&manifest, temp_dir.path(), &HashMap::new());
         assert!(matches!(result, Err(SecurityError::FileMissing { .. })));
     }
 
@@ -338,7 +476,35 @@ mod tests {
         });
 
         // 没有 files 字段时应该通过
-        assert!(verify_manifest_files(&manifest, temp_dir.path()).is_ok());
+        assert!(verify_manifest_files(&manifest, temp_dir.path(), &HashMap::new()).is_ok());
+    }
+
+    #[test]
+    fn test_check_files_collects_all_issues() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("plugin.js"), b"tampered").unwrap();
+        std::fs::write(temp_dir.path().join("ok.js"), b"ok").unwrap();
+
+        let mut files = HashMap::new();
+        files.insert(
+            "plugin.js".to_string(),
+            format!("sha256:{}", calculate_sha256_bytes(b"original")),
+        );
+        files.insert("ok.js".to_string(), calculate_sha256_bytes(b"ok"));
+        files.insert("missing.js".to_string(), calculate_sha256_bytes(b""));
+        files.insert("../escape.js".to_string(), calculate_sha256_bytes(b""));
+
+        let issues = check_files(&files, temp_dir.path());
+        assert_eq!(issues.len(), 3);
+        assert!(issues
+            .iter()
+            .any(|e| matches!(e, SecurityError::PathTraversal { path } if path == "../escape.js")));
+        assert!(issues
+            .iter()
+            .any(|e| matches!(e, SecurityError::FileMissing { file } if file == "missing.js")));
+        assert!(issues
+            .iter()
+            .any(|e| matches!(e, SecurityError::HashMismatch { file, .. } if file == "plugin.js")));
     }
 
     #[test]
@@ -0,0 +1,416 @@
+// Phase 5A.12: 发布者密钥信任库
+// 第三方插件作者可以用自己的密钥签名插件，公钥通过根密钥签名的密钥列表分发:
+// 1. 根密钥为编译时嵌入的公钥（signature.rs），密钥列表必须由根密钥签名
+// 2. 密钥列表包含发布者公钥（可设置过期时间）和吊销列表，按 version 单调递增，拒绝回退到旧版本
+// 3. 列表原文保存在 publisher_keys.json，启动时重新校验签名；校验失败时只使用嵌入的根密钥
+// 4. 根密钥不能被列表覆盖或吊销，更换根密钥需要发布新版本应用
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{VerifyingKey, PUBLIC_KEY_LENGTH};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::commands::error::codes;
+use crate::security::signature::SignatureVerifier;
+
+/// 默认密钥列表地址
+pub const DEFAULT_PUBLISHER_KEYS_URL: &str =
+    "https://raw.githubusercontent.com/DDG0808/aibal-plugins/main/publisher-keys.json";
+
+/// 密钥列表下载超时
+const KEY_LIST_FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 密钥列表最大大小 (256KB)
+const MAX_KEY_LIST_BYTES: usize = 256 * 1024;
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+/// 密钥信任库错误
+#[derive(Debug, Error)]
+pub enum KeyStoreError {
+    #[error("下载密钥列表失败: {0}")]
+    Fetch(String),
+
+    #[error("密钥列表签名无效: {0}")]
+    SignatureInvalid(String),
+
+    #[error("密钥列表格式无效: {0}")]
+    Invalid(String),
+
+    #[error("密钥列表版本 {got} 不高于当前版本 {current}")]
+    Outdated { current: u64, got: u64 },
+
+    #[error("保存密钥列表失败: {0}")]
+    Save(String),
+}
+
+impl KeyStoreError {
+    /// 错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            KeyStoreError::Fetch(_) => codes::KEY_LIST_FETCH_FAILED,
+            KeyStoreError::SignatureInvalid(_) => codes::SIGNATURE_INVALID,
+            KeyStoreError::Invalid(_) => codes::INVALID_KEY_LIST,
+            KeyStoreError::Outdated { .. } => codes::KEY_LIST_OUTDATED,
+            KeyStoreError::Save(_) => codes::KEY_LIST_SAVE_FAILED,
+        }
+    }
+}
+
+// ============================================================================
+// 密钥列表
+// ============================================================================
+
+/// 发布者公钥
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublisherKey {
+    /// 密钥 ID（manifest 签名 `ed25519:{key_id}:{base64}` 中的 key_id）
+    pub key_id: String,
+    /// Ed25519 公钥（Base64，32 字节）
+    pub public_key: String,
+    /// 发布者名称
+    pub publisher: String,
+    /// 过期时间，过期后不再接受该密钥的签名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl PublisherKey {
+    /// 解码公钥
+    fn verifying_key(&self) -> Result<VerifyingKey, KeyStoreError> {
+        let bytes = BASE64
+            .decode(&self.public_key)
+            .map_err(|e| KeyStoreError::Invalid(format!("{} 公钥 Base64 解码失败: {}", self.key_id, e)))?;
+        let bytes: [u8; PUBLIC_KEY_LENGTH] = bytes
+            .try_into()
+            .map_err(|_| KeyStoreError::Invalid(format!("{} 公钥长度应为 {} 字节", self.key_id, PUBLIC_KEY_LENGTH)))?;
+        VerifyingKey::from_bytes(&bytes).map_err(|e| KeyStoreError::Invalid(format!("{} 公钥无效: {}", self.key_id, e)))
+    }
+}
+
+/// 根密钥签名的发布者密钥列表
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublisherKeyList {
+    /// 列表版本（单调递增）
+    pub version: u64,
+    /// 签发时间
+    pub issued_at: DateTime<Utc>,
+    /// 发布者公钥
+    #[serde(default)]
+    pub keys: Vec<PublisherKey>,
+    /// 已吊销的密钥 ID
+    #[serde(default)]
+    pub revoked: Vec<String>,
+}
+
+impl PublisherKeyList {
+    /// 用根密钥校验签名后解析密钥列表
+    pub fn from_signed(raw: &Value, root: &SignatureVerifier) -> Result<Self, KeyStoreError> {
+        root.verify_manifest(raw)
+            .map_err(|e| KeyStoreError::SignatureInvalid(e.to_string()))?;
+        let list: Self = serde_json::from_value(raw.clone()).map_err(|e| KeyStoreError::Invalid(e.to_string()))?;
+
+        for key in &list.keys {
+            let valid_id = !key.key_id.is_empty()
+                && key
+                    .key_id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "._-".contains(c));
+            if !valid_id {
+                return Err(KeyStoreError::Invalid(format!("密钥 ID 无效: {:?}", key.key_id)));
+            }
+            if root.has_key(&key.key_id) {
+                return Err(KeyStoreError::Invalid(format!("不能覆盖根密钥: {}", key.key_id)));
+            }
+            key.verifying_key()?;
+        }
+        Ok(list)
+    }
+
+    /// 当前可用的发布者密钥（未吊销、未过期）
+    fn active_keys(&self, now: DateTime<Utc>) -> impl Iterator<Item = &PublisherKey> {
+        self.keys.iter().filter(move |key| {
+            !self.revoked.contains(&key.key_id) && key.expires_at.map_or(true, |expires| expires > now)
+        })
+    }
+}
+
+// ============================================================================
+// 信任库状态
+// ============================================================================
+
+/// 密钥状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyState {
+    Active,
+    Expired,
+    Revoked,
+}
+
+/// 信任库中的密钥
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedKeyInfo {
+    pub key_id: String,
+    /// 发布者名称（根密钥为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    /// 是否为嵌入的根密钥
+    pub root: bool,
+    pub state: KeyState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// 信任库状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublisherKeyStatus {
+    /// 当前密钥列表版本（未加载列表时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+    /// 当前密钥列表签发时间
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issued_at: Option<DateTime<Utc>>,
+    /// 根密钥和发布者密钥
+    pub keys: Vec<TrustedKeyInfo>,
+}
+
+// ============================================================================
+// 信任库
+// ============================================================================
+
+/// 发布者密钥信任库
+pub struct PublisherKeyStore {
+    path: PathBuf,
+    list: RwLock<Option<PublisherKeyList>>,
+}
+
+impl PublisherKeyStore {
+    /// 与插件目录同级的密钥列表文件
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join("publisher_keys.json")
+    }
+
+    /// 加载已保存的密钥列表（重新校验签名，失败时只使用根密钥）
+    pub fn load(path: PathBuf) -> Self {
+        let list = match std::fs::read(&path) {
+            Ok(bytes) => match serde_json::from_slice::<Value>(&bytes)
+                .map_err(|e| KeyStoreError::Invalid(e.to_string()))
+                .and_then(|raw| PublisherKeyList::from_signed(&raw, &SignatureVerifier::default()))
+            {
+                Ok(list) => {
+                    log::info!("已加载发布者密钥列表: version={}, keys={}", list.version, list.keys.len());
+                    Some(list)
+                }
+                Err(e) => {
+                    log::warn!("发布者密钥列表无效，仅使用根密钥: {}", e);
+                    None
+                }
+            },
+            Err(_) => None,
+        };
+        Self {
+            path,
+            list: RwLock::new(list),
+        }
+    }
+
+    /// 签名验证器（根密钥 + 未吊销、未过期的发布者密钥）
+    pub fn verifier(&self) -> SignatureVerifier {
+        let mut verifier = SignatureVerifier::default();
+        if let Some(list) = self.list.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            for key in list.active_keys(Utc::now()) {
+                if let Ok(verifying_key) = key.verifying_key() {
+                    verifier.add_key(&key.key_id, verifying_key);
+                }
+            }
+        }
+        verifier
+    }
+
+    /// 应用新的密钥列表（校验根密钥签名，版本必须高于当前版本），原文持久化
+    pub fn apply(&self, raw: &Value) -> Result<PublisherKeyStatus, KeyStoreError> {
+        let list = PublisherKeyList::from_signed(raw, &SignatureVerifier::default())?;
+        {
+            let mut current = self.list.write().unwrap_or_else(|e| e.into_inner());
+            if let Some(existing) = current.as_ref() {
+                if list.version <= existing.version {
+                    return Err(KeyStoreError::Outdated {
+                        current: existing.version,
+                        got: list.version,
+                    });
+                }
+            }
+
+            let content = serde_json::to_vec_pretty(raw).map_err(|e| KeyStoreError::Save(e.to_string()))?;
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| KeyStoreError::Save(e.to_string()))?;
+            }
+            let tmp = self.path.with_extension("json.tmp");
+            std::fs::write(&tmp, content)
+                .and_then(|_| std::fs::rename(&tmp, &self.path))
+                .map_err(|e| KeyStoreError::Save(e.to_string()))?;
+
+            log::info!(
+                "发布者密钥列表已更新: version={}, keys={}, revoked={}",
+                list.version,
+                list.keys.len(),
+                list.revoked.len()
+            );
+            *current = Some(list);
+        }
+        Ok(self.status())
+    }
+
+    /// 信任库状态
+    pub fn status(&self) -> PublisherKeyStatus {
+        let root = SignatureVerifier::default();
+        let mut root_ids: Vec<_> = root.available_keys().into_iter().cloned().collect();
+        root_ids.sort();
+        let mut keys: Vec<TrustedKeyInfo> = root_ids
+            .into_iter()
+            .map(|key_id| TrustedKeyInfo {
+                key_id,
+                publisher: None,
+                root: true,
+                state: KeyState::Active,
+                expires_at: None,
+            })
+            .collect();
+
+        let list = self.list.read().unwrap_or_else(|e| e.into_inner());
+        let now = Utc::now();
+        if let Some(list) = list.as_ref() {
+            keys.extend(list.keys.iter().map(|key| TrustedKeyInfo {
+                key_id: key.key_id.clone(),
+                publisher: Some(key.publisher.clone()),
+                root: false,
+                state: if list.revoked.contains(&key.key_id) {
+                    KeyState::Revoked
+                } else if key.expires_at.is_some_and(|expires| expires <= now) {
+                    KeyState::Expired
+                } else {
+                    KeyState::Active
+                },
+                expires_at: key.expires_at,
+            }));
+        }
+
+        PublisherKeyStatus {
+            version: list.as_ref().map(|l| l.version),
+            issued_at: list.as_ref().map(|l| l.issued_at),
+            keys,
+        }
+    }
+}
+
+/// 下载密钥列表（仅 HTTPS）
+pub async fn fetch_key_list(url: &str) -> Result<Value, KeyStoreError> {
+    if !url.starts_with("https://") {
+        return Err(KeyStoreError::Fetch("密钥列表地址必须使用 HTTPS".to_string()));
+    }
+    let client = reqwest::Client::builder()
+        .timeout(KEY_LIST_FETCH_TIMEOUT)
+        .user_agent(concat!("AiBal/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| KeyStoreError::Fetch(e.to_string()))?;
+    let body = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| KeyStoreError::Fetch(e.to_string()))?
+        .bytes()
+        .await
+        .map_err(|e| KeyStoreError::Fetch(e.to_string()))?;
+    if body.len() > MAX_KEY_LIST_BYTES {
+        return Err(KeyStoreError::Invalid(format!("密钥列表超过 {} 字节", MAX_KEY_LIST_BYTES)));
+    }
+    serde_json::from_slice(&body).map_err(|e| KeyStoreError::Invalid(e.to_string()))
+}
+
+// ============================================================================
+// 测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::canonical::canonicalize_for_signing;
+    use ed25519_dalek::{Signer, SigningKey};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    /// 根密钥（嵌入的 "test" 公钥对应的 RFC 8032 测试私钥）
+    const ROOT_PRIVATE_KEY: [u8; 32] = [
+        0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec, 0x2c,
+        0xc4, 0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03, 0x1c, 0xae,
+        0x7f, 0x60,
+    ];
+
+    fn sign(mut doc: Value, key: &SigningKey, key_id: &str) -> Value {
+        let signature = key.sign(canonicalize_for_signing(&doc).as_bytes());
+        doc["signature"] = json!(format!("ed25519:{}:{}", key_id, BASE64.encode(signature.to_bytes())));
+        doc
+    }
+
+    #[test]
+    fn test_publisher_keys_rotation_and_revocation() {
+        let temp = TempDir::new().unwrap();
+        let root = SigningKey::from_bytes(&ROOT_PRIVATE_KEY);
+        let publisher = SigningKey::from_bytes(&[7u8; 32]);
+        let publisher_key = BASE64.encode(publisher.verifying_key().to_bytes());
+        let store = PublisherKeyStore::load(temp.path().join("publisher_keys.json"));
+
+        let manifest = sign(json!({"id": "acme-usage", "version": "1.0.0"}), &publisher, "acme-2026");
+        assert!(store.verifier().verify_manifest(&manifest).is_err());
+
+        // 根密钥签名的列表加入发布者密钥后，发布者签名的插件通过校验
+        let list = json!({
+            "version": 1,
+            "issuedAt": "2026-01-01T00:00:00Z",
+            "keys": [{"keyId": "acme-2026", "publicKey": publisher_key, "publisher": "Acme"}]
+        });
+        store.apply(&sign(list.clone(), &root, "test")).unwrap();
+        assert!(store.verifier().verify_manifest(&manifest).is_ok());
+
+        // 发布者密钥不能签发密钥列表，旧版本列表被拒绝
+        let forged = sign(json!({"version": 5, "issuedAt": "2026-01-01T00:00:00Z"}), &publisher, "acme-2026");
+        assert!(matches!(store.apply(&forged), Err(KeyStoreError::SignatureInvalid(_))));
+        assert!(matches!(
+            store.apply(&sign(list, &root, "test")),
+            Err(KeyStoreError::Outdated { current: 1, got: 1 })
+        ));
+
+        // 吊销后不再接受该密钥的签名，重启后从磁盘恢复
+        let revoked = json!({
+            "version": 2,
+            "issuedAt": "2026-02-01T00:00:00Z",
+            "keys": [{"keyId": "acme-2026", "publicKey": publisher_key, "publisher": "Acme"}],
+            "revoked": ["acme-2026"]
+        });
+        let status = store.apply(&sign(revoked, &root, "test")).unwrap();
+        assert_eq!(status.version, Some(2));
+        assert!(status.keys.iter().any(|k| k.key_id == "acme-2026" && k.state == KeyState::Revoked));
+
+        let reloaded = PublisherKeyStore::load(temp.path().join("publisher_keys.json"));
+        assert_eq!(reloaded.status().version, Some(2));
+        assert!(reloaded.verifier().verify_manifest(&manifest).is_err());
+    }
+}
@@ -1,9 +1,13 @@
 // Phase 5A: 安全工具链
-// 提供签名验证、完整性校验、安全解压功能
+// 提供签名验证、发布者密钥管理、完整性校验、安全解压、版本备份、审计日志功能
 
+pub mod audit;
+pub mod backup;
 pub mod canonical;
 pub mod extractor;
 pub mod integrity;
+pub mod keys;
+pub mod policy;
 pub mod signature;
 
 use std::io;
@@ -82,6 +86,8 @@ pub enum SecurityError {
         target: PathBuf,
         reason: String,
     },
+    /// 解压被取消
+    Cancelled,
 }
 
 impl std::fmt::Display for SecurityError {
@@ -147,6 +153,7 @@ impl std::fmt::Display for SecurityError {
                     source, target, reason
                 )
             }
+            Self::Cancelled => write!(f, "解压已取消"),
         }
     }
 }
@@ -205,6 +212,6 @@ pub const BACKUP_VERSIONS: usize = 3;
 // ============================================================================
 
 pub use canonical::canonicalize;
-pub use extractor::SecureExtractor;
-pub use integrity::{calculate_sha256, verify_file_hash, verify_manifest_files};
-pub use signature::{verify_manifest_signature, SignatureVerifier};
+pub use extractor::{ExtractProgress, ExtractReport, SecureExtractor};
+pub use integrity::{calculate_sha256, verify_file_hash, verify_manifest_files, HashStats};
+pub use signature::SignatureVerifier;
@@ -0,0 +1,249 @@
+// Phase 5A.8: 管理员策略
+// 企业部署时由 MDM 下发机器级策略文件，宿主只读加载：
+// - registryUrl: 固定插件市场地址，拒绝其他 registry
+// - disablePluginInstalls: 禁止安装新插件（已安装插件的更新不受影响）
+// - requireSignatures: 强制验证插件签名（禁止跳过签名、不加载开发者插件）
+// - networkAllowlist: 插件网络访问白名单，与 manifest allowedHosts 同时生效
+// - maxPluginMemoryMb / maxPluginTimeoutMs: manifest `resources` 可声明的沙盒内存/超时上限
+//
+// 策略文件位置:
+// - macOS: /Library/Application Support/CUK/policy.json
+// - 其他平台: /etc/cuk/policy.json（Windows 为 %ProgramData%\CUK\policy.json）
+//
+// 文件不存在时不启用任何策略；文件存在但无法解析时按最严格的策略处理（禁止安装、强制签名），
+// 防止篡改或写错的策略文件解除管控。
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::error::codes;
+use crate::plugin::types::AppError;
+
+/// 插件可声明的沙盒内存上限默认值 (MB)
+pub const DEFAULT_MAX_PLUGIN_MEMORY_MB: u32 = 64;
+
+/// 插件可声明的单次执行超时上限默认值 (ms)
+pub const DEFAULT_MAX_PLUGIN_TIMEOUT_MS: u64 = 120_000;
+
+/// 操作被管理员策略拒绝
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{0}")]
+pub struct PolicyDenied(pub String);
+
+impl From<PolicyDenied> for AppError {
+    fn from(e: PolicyDenied) -> Self {
+        AppError::new(codes::POLICY_DENIED, e.0)
+    }
+}
+
+/// 管理员策略（policy.json）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AdminPolicy {
+    /// 固定的插件市场 registry.json 地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry_url: Option<String>,
+    /// 禁止安装新插件
+    pub disable_plugin_installs: bool,
+    /// 强制验证插件签名
+    pub require_signatures: bool,
+    /// 插件网络访问白名单（主机模式与 manifest allowedHosts 相同）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_allowlist: Option<Vec<String>>,
+    /// 插件可声明的沙盒内存上限 (MB)，未设置时为 DEFAULT_MAX_PLUGIN_MEMORY_MB
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_plugin_memory_mb: Option<u32>,
+    /// 插件可声明的单次执行超时上限 (ms)，未设置时为 DEFAULT_MAX_PLUGIN_TIMEOUT_MS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_plugin_timeout_ms: Option<u64>,
+}
+
+/// 插件沙盒资源上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SandboxCaps {
+    /// 内存上限 (MB)
+    pub memory_limit_mb: u32,
+    /// 单次执行超时上限 (ms)
+    pub timeout_ms: u64,
+}
+
+impl AdminPolicy {
+    /// 生效的沙盒资源上限
+    pub fn sandbox_caps(&self) -> SandboxCaps {
+        SandboxCaps {
+            memory_limit_mb: self.max_plugin_memory_mb.unwrap_or(DEFAULT_MAX_PLUGIN_MEMORY_MB),
+            timeout_ms: self.max_plugin_timeout_ms.unwrap_or(DEFAULT_MAX_PLUGIN_TIMEOUT_MS),
+        }
+    }
+}
+
+/// 策略状态（get_system_health 返回）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyStatus {
+    /// 策略文件路径
+    pub path: String,
+    /// 是否已加载策略文件
+    pub loaded: bool,
+    /// 策略文件解析错误（按最严格的策略处理）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 生效的策略
+    pub policy: AdminPolicy,
+    /// 生效策略的名称（如 registryUrl、requireSignatures）
+    pub active: Vec<String>,
+}
+
+/// 已加载的管理员策略
+pub struct PolicyManager {
+    path: PathBuf,
+    policy: AdminPolicy,
+    loaded: bool,
+    error: Option<String>,
+}
+
+impl PolicyManager {
+    /// 机器级策略文件路径
+    pub fn system_path() -> PathBuf {
+        if cfg!(target_os = "macos") {
+            PathBuf::from("/Library/Application Support/CUK/policy.json")
+        } else if cfg!(windows) {
+            std::env::var_os("ProgramData")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"))
+                .join("CUK")
+                .join("policy.json")
+        } else {
+            PathBuf::from("/etc/cuk/policy.json")
+        }
+    }
+
+    /// 加载策略文件（只读）
+    pub fn load(path: &Path) -> Self {
+        let (policy, loaded, error) = match std::fs::read_to_string(path) {
+            Ok(content) => match serde_json::from_str::<AdminPolicy>(&content) {
+                Ok(policy) => {
+                    log::info!("已加载管理员策略: {:?}", path);
+                    (policy, true, None)
+                }
+                Err(e) => {
+                    log::error!("管理员策略文件无效，按最严格的策略处理: {:?}, {}", path, e);
+                    let strict = AdminPolicy {
+                        disable_plugin_installs: true,
+                        require_signatures: true,
+                        ..AdminPolicy::default()
+                    };
+                    (strict, true, Some(e.to_string()))
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (AdminPolicy::default(), false, None),
+            Err(e) => {
+                log::warn!("读取管理员策略失败，不启用策略: {:?}, {}", path, e);
+                (AdminPolicy::default(), false, Some(e.to_string()))
+            }
+        };
+        Self {
+            path: path.to_path_buf(),
+            policy,
+            loaded,
+            error,
+        }
+    }
+
+    /// 当前策略
+    pub fn policy(&self) -> &AdminPolicy {
+        &self.policy
+    }
+
+    /// 策略状态
+    pub fn status(&self) -> PolicyStatus {
+        let policy = &self.policy;
+        let active = [
+            ("registryUrl", policy.registry_url.is_some()),
+            ("disablePluginInstalls", policy.disable_plugin_installs),
+            ("requireSignatures", policy.require_signatures),
+            ("networkAllowlist", policy.network_allowlist.is_some()),
+            ("maxPluginMemoryMb", policy.max_plugin_memory_mb.is_some()),
+            ("maxPluginTimeoutMs", policy.max_plugin_timeout_ms.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, active)| *active)
+        .map(|(name, _)| name.to_string())
+        .collect();
+        PolicyStatus {
+            path: self.path.display().to_string(),
+            loaded: self.loaded,
+            error: self.error.clone(),
+            policy: policy.clone(),
+            active,
+        }
+    }
+
+    /// 解析要使用的 registry 地址
+    ///
+    /// 策略固定了地址时，未指定返回固定地址，指定其他地址返回错误
+    pub fn registry_url(&self, requested: Option<&str>) -> Result<Option<String>, PolicyDenied> {
+        match (&self.policy.registry_url, requested) {
+            (Some(pinned), Some(url)) if pinned.trim_end_matches('/') != url.trim_end_matches('/') => {
+                Err(PolicyDenied(format!("管理员策略仅允许使用 registry: {}", pinned)))
+            }
+            (Some(pinned), _) => Ok(Some(pinned.clone())),
+            (None, requested) => Ok(requested.map(str::to_string)),
+        }
+    }
+
+    /// 检查是否允许安装插件
+    ///
+    /// `is_update` 为 true 表示更新已安装的插件；`skip_signature` 为 true 表示请求跳过签名验证
+    pub fn check_install(&self, plugin_id: &str, is_update: bool, skip_signature: bool) -> Result<(), PolicyDenied> {
+        if self.policy.disable_plugin_installs && !is_update {
+            return Err(PolicyDenied(format!("管理员策略禁止安装新插件: {}", plugin_id)));
+        }
+        if self.policy.require_signatures && skip_signature {
+            return Err(PolicyDenied(format!("管理员策略要求验证插件签名: {}", plugin_id)));
+        }
+        Ok(())
+    }
+}
+
+impl Default for PolicyManager {
+    fn default() -> Self {
+        Self::load(&Self::system_path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_load_and_enforce() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.json");
+
+        // 文件不存在：不启用策略
+        let manager = PolicyManager::load(&path);
+        assert!(!manager.status().loaded && manager.status().active.is_empty());
+        assert_eq!(manager.registry_url(Some("https://a/registry.json")).unwrap().as_deref(), Some("https://a/registry.json"));
+        assert!(manager.check_install("demo", false, true).is_ok());
+
+        std::fs::write(
+            &path,
+            r#"{"registryUrl": "https://corp/registry.json", "disablePluginInstalls": true, "networkAllowlist": ["*.corp.com"]}"#,
+        )
+        .unwrap();
+        let manager = PolicyManager::load(&path);
+        assert_eq!(manager.status().active, vec!["registryUrl", "disablePluginInstalls", "networkAllowlist"]);
+        assert_eq!(manager.registry_url(None).unwrap().as_deref(), Some("https://corp/registry.json"));
+        assert!(manager.registry_url(Some("https://other/registry.json")).is_err());
+        assert!(manager.check_install("demo", false, false).is_err());
+        assert!(manager.check_install("demo", true, true).is_ok());
+
+        // 无法解析：按最严格的策略处理
+        std::fs::write(&path, "{not json").unwrap();
+        let manager = PolicyManager::load(&path);
+        assert!(manager.status().error.is_some());
+        assert!(manager.check_install("demo", true, true).is_err());
+    }
+}
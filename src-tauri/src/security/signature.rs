@@ -168,6 +168,11 @@ impl SignatureVerifier {
         Ok((key_id, signature))
     }
 
+    /// 添加公钥（发布者密钥，已存在的 key_id 不会被覆盖）
+    pub fn add_key(&mut self, key_id: &str, key: VerifyingKey) {
+        self.keys.entry(key_id.to_string()).or_insert(key);
+    }
+
     /// 检查是否有指定的公钥
     pub fn has_key(&self, key_id: &str) -> bool {
         self.keys.contains_key(key_id)
@@ -185,23 +190,6 @@ impl Default for SignatureVerifier {
     }
 }
 
-// ============================================================================
-// 便捷函数
-// ============================================================================
-
-/// 验证 manifest 签名 (便捷函数)
-///
-/// # 参数
-/// - `manifest`: manifest.json 内容
-///
-/// # 返回
-/// - `Ok(())`: 签名验证通过
-/// - `Err(SecurityError)`: 签名验证失败
-pub fn verify_manifest_signature(manifest: &Value) -> Result<()> {
-    let verifier = SignatureVerifier::new()?;
-    verifier.verify_manifest(manifest)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
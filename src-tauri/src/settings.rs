@@ -0,0 +1,391 @@
+// Phase 7.6: 应用设置
+// 集中管理应用级偏好（主题、托盘点击、刷新节奏、通知、启动行为、看门狗策略），插件自身的配置不在此列:
+// - 设置文件 app_settings.json（与插件目录同级），带 version 字段，加载时按版本迁移
+// - 写入先落到临时文件并 fsync，再 rename 覆盖，避免崩溃时留下半个文件
+// - 修改通过 JSON merge patch 提交，校验通过后才保存；字段为 null 表示恢复默认值
+// - 变更由 IPC 层通过 window::broadcast_state("settings", ...) 同步到所有窗口
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::error::codes;
+use crate::plugin::monitoring::WatchdogPolicy;
+use crate::plugin::proxy::ProxySettings;
+use crate::plugin::tls::TlsSettings;
+use crate::tray::TrayClickConfig;
+
+/// 当前设置版本
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// 设置文件名
+const SETTINGS_FILE_NAME: &str = "app_settings.json";
+
+/// 默认刷新间隔 (5 分钟，与前端 DEFAULT_APP_SETTINGS 一致)
+pub const DEFAULT_REFRESH_INTERVAL_MS: u64 = 5 * 60 * 1000;
+
+/// 刷新间隔下限 (1 分钟)
+pub const MIN_REFRESH_INTERVAL_MS: u64 = 60 * 1000;
+
+/// 刷新间隔上限 (24 小时)
+pub const MAX_REFRESH_INTERVAL_MS: u64 = 24 * 60 * 60 * 1000;
+
+// ============================================================================
+// 设置模型
+// ============================================================================
+
+/// 界面主题
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Light,
+    Dark,
+    #[default]
+    System,
+}
+
+impl Theme {
+    /// 前端使用的主题名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::System => "system",
+        }
+    }
+}
+
+/// 左键点击托盘时的显示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrayDisplayMode {
+    /// 托盘弹窗
+    #[default]
+    Popup,
+    /// 紧凑 HUD（只显示百分比的置顶小窗）
+    Hud,
+}
+
+/// 刷新节奏
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RefreshSettings {
+    /// 自动刷新间隔 (毫秒)
+    pub interval_ms: u64,
+    /// 启动时立即刷新
+    pub on_launch: bool,
+}
+
+impl Default for RefreshSettings {
+    fn default() -> Self {
+        Self {
+            interval_ms: DEFAULT_REFRESH_INTERVAL_MS,
+            on_launch: true,
+        }
+    }
+}
+
+/// 通知偏好
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    /// 是否发送系统通知
+    pub enabled: bool,
+    /// 通知是否播放提示音
+    pub sound: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sound: true,
+        }
+    }
+}
+
+/// 启动行为
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StartupSettings {
+    /// 开机自启动
+    pub launch_at_login: bool,
+    /// 显示菜单栏图标
+    pub show_in_menu_bar: bool,
+}
+
+impl Default for StartupSettings {
+    fn default() -> Self {
+        Self {
+            launch_at_login: false,
+            show_in_menu_bar: true,
+        }
+    }
+}
+
+/// 应用设置（app_settings.json）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    /// 设置版本
+    pub version: u32,
+    /// 界面主题
+    pub theme: Theme,
+    /// 左键点击托盘时的显示方式
+    pub tray_display: TrayDisplayMode,
+    /// 托盘点击动作（按修饰键路由）
+    pub tray_click: TrayClickConfig,
+    /// 刷新节奏
+    pub refresh: RefreshSettings,
+    /// 通知偏好
+    pub notifications: NotificationSettings,
+    /// 启动行为
+    pub startup: StartupSettings,
+    /// 失控插件看门狗策略（Phase 6.17）
+    pub watchdog: WatchdogPolicy,
+    /// 插件请求代理（Phase 4.5，密码保存在 Keychain）
+    pub proxy: ProxySettings,
+    /// 自定义根证书与证书固定（Phase 4.6）
+    pub tls: TlsSettings,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            theme: Theme::default(),
+            tray_display: TrayDisplayMode::default(),
+            tray_click: TrayClickConfig::default(),
+            refresh: RefreshSettings::default(),
+            notifications: NotificationSettings::default(),
+            startup: StartupSettings::default(),
+            watchdog: WatchdogPolicy::default(),
+            proxy: ProxySettings::default(),
+            tls: TlsSettings::default(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// 校验取值范围
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        if !(MIN_REFRESH_INTERVAL_MS..=MAX_REFRESH_INTERVAL_MS).contains(&self.refresh.interval_ms) {
+            return Err(SettingsError::Invalid(format!(
+                "刷新间隔需在 {} 分钟到 {} 小时之间",
+                MIN_REFRESH_INTERVAL_MS / 60_000,
+                MAX_REFRESH_INTERVAL_MS / 3_600_000
+            )));
+        }
+        self.watchdog.validate().map_err(SettingsError::Invalid)?;
+        self.proxy.validate().map_err(SettingsError::Invalid)?;
+        self.tls.validate().map_err(SettingsError::Invalid)
+    }
+}
+
+// ============================================================================
+// 错误类型
+// ============================================================================
+
+/// 设置错误
+#[derive(Debug, thiserror::Error)]
+pub enum SettingsError {
+    #[error("无效的设置: {0}")]
+    Invalid(String),
+
+    #[error("保存设置失败: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl SettingsError {
+    /// IPC 错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            SettingsError::Invalid(_) => codes::INVALID_ARGUMENT,
+            SettingsError::Io(_) => codes::SETTINGS_SAVE_FAILED,
+        }
+    }
+}
+
+// ============================================================================
+// 版本迁移
+// ============================================================================
+
+/// 将旧版本的设置 JSON 迁移到当前版本
+///
+/// 没有 version 字段的文件视为版本 0（尚无需要改名的字段，只补上版本号）
+fn migrate(mut value: serde_json::Value) -> serde_json::Value {
+    let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+    if version > SETTINGS_VERSION {
+        log::warn!("设置文件版本 {} 高于当前版本 {}，未知字段将被忽略", version, SETTINGS_VERSION);
+    }
+    if version < SETTINGS_VERSION {
+        log::info!("迁移应用设置: v{} -> v{}", version, SETTINGS_VERSION);
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".to_string(), SETTINGS_VERSION.into());
+    }
+    value
+}
+
+/// 按 JSON merge patch 合并（对象递归合并，null 删除字段以恢复默认值）
+fn merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    let target = target.as_object_mut().expect("target is object");
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge_patch(target.entry(key.clone()).or_insert(serde_json::Value::Null), value);
+        }
+    }
+}
+
+// ============================================================================
+// 设置存储
+// ============================================================================
+
+/// 应用设置存储
+pub struct SettingsStore {
+    settings: Mutex<AppSettings>,
+    /// 持久化路径
+    path: PathBuf,
+}
+
+impl SettingsStore {
+    /// 默认文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join(SETTINGS_FILE_NAME)
+    }
+
+    /// 从文件加载（文件不存在或无法解析时使用默认设置）
+    pub fn load(path: PathBuf) -> Self {
+        let settings = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map(migrate)
+                .and_then(serde_json::from_value::<AppSettings>)
+                .unwrap_or_else(|e| {
+                    log::warn!("解析应用设置失败，使用默认设置: {}", e);
+                    AppSettings::default()
+                }),
+            Err(_) => AppSettings::default(),
+        };
+        Self {
+            settings: Mutex::new(settings),
+            path,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, AppSettings> {
+        self.settings.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 原子写入（临时文件 + fsync + rename）
+    fn save(&self, settings: &AppSettings) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec_pretty(settings)?)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.path)
+    }
+
+    /// 当前设置
+    pub fn get(&self) -> AppSettings {
+        self.lock().clone()
+    }
+
+    /// 按 merge patch 修改设置，校验并保存后返回新设置
+    pub fn update(&self, patch: &serde_json::Value) -> Result<AppSettings, SettingsError> {
+        if !patch.is_object() {
+            return Err(SettingsError::Invalid("设置修改必须是对象".to_string()));
+        }
+        let mut settings = self.lock();
+        let mut value = serde_json::to_value(&*settings).map_err(|e| SettingsError::Invalid(e.to_string()))?;
+        merge_patch(&mut value, patch);
+        let mut updated: AppSettings =
+            serde_json::from_value(value).map_err(|e| SettingsError::Invalid(e.to_string()))?;
+        updated.version = SETTINGS_VERSION;
+        updated.validate()?;
+
+        self.save(&updated)?;
+        *settings = updated.clone();
+        log::info!("已更新应用设置");
+        Ok(updated)
+    }
+
+    /// 恢复默认设置
+    pub fn reset(&self) -> Result<AppSettings, SettingsError> {
+        let mut settings = self.lock();
+        let defaults = AppSettings::default();
+        self.save(&defaults)?;
+        *settings = defaults.clone();
+        log::info!("已恢复默认应用设置");
+        Ok(defaults)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_patch_validate_and_migrate() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SETTINGS_FILE_NAME);
+
+        // 版本 0 的旧文件: 保留已有值，补齐默认值
+        std::fs::write(&path, r#"{"theme": "dark", "refresh": {"intervalMs": 600000}}"#).unwrap();
+        let store = SettingsStore::load(path.clone());
+        let settings = store.get();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!((settings.theme, settings.refresh.interval_ms), (Theme::Dark, 600_000));
+        assert!(settings.refresh.on_launch);
+        assert_eq!(settings.tray_display, TrayDisplayMode::Popup);
+
+        // 部分修改只影响提交的字段，null 恢复默认值
+        let updated = store
+            .update(&serde_json::json!({ "notifications": { "sound": false }, "theme": null }))
+            .unwrap();
+        assert!(!updated.notifications.sound && updated.notifications.enabled);
+        assert_eq!(updated.theme, Theme::System);
+        assert_eq!(updated.refresh.interval_ms, 600_000);
+
+        // 校验失败不修改
+        let err = store.update(&serde_json::json!({ "refresh": { "intervalMs": 1000 } })).unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+        assert!(store.update(&serde_json::json!({ "theme": "neon" })).is_err());
+        let err = store.update(&serde_json::json!({ "watchdog": { "maxConsecutiveRunaways": 0 } })).unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+        let err = store
+            .update(&serde_json::json!({ "proxy": { "enabled": true, "host": "192.168.1.1", "port": 8080 } }))
+            .unwrap_err();
+        assert_eq!(err.code(), "INVALID_ARGUMENT");
+        assert_eq!(SettingsStore::load(path.clone()).get(), updated);
+
+        // 托盘点击配置随设置持久化，重新加载后保留
+        assert_eq!(updated.tray_click, TrayClickConfig::default());
+        let updated = store
+            .update(&serde_json::json!({ "trayClick": { "shiftClick": "open_dashboard" } }))
+            .unwrap();
+        assert_eq!(updated.tray_click.shift_click, crate::tray::TrayClickAction::OpenDashboard);
+        assert_eq!(updated.tray_click.click, TrayClickConfig::default().click);
+        assert!(store.update(&serde_json::json!({ "trayClick": { "click": "explode" } })).is_err());
+        assert_eq!(SettingsStore::load(path.clone()).get(), updated);
+
+        assert_eq!(store.reset().unwrap(), AppSettings::default());
+        assert_eq!(SettingsStore::load(path).get(), AppSettings::default());
+    }
+}
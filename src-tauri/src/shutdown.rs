@@ -0,0 +1,106 @@
+// Phase 7.10: 优雅退出
+// 托盘菜单退出、Cmd+Q、最后一个窗口关闭、app.restart 都会触发 RunEvent::ExitRequested:
+// - 第一次退出请求时阻止退出，在后台执行 PluginManager::shutdown
+//   （等待事件队列分发完成、停止分发器、调用各插件 onUnload 保存状态）
+// - 整个关闭流程受 SHUTDOWN_TIMEOUT 限制，超时后不再等待，直接退出
+// - 关闭完成后以原退出码再次请求退出（重启请求的标记由 Tauri 保留，退出后照常重启）
+// - 关闭期间的重复退出请求被忽略
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, RunEvent, Runtime};
+
+use crate::commands::PluginManagerState;
+
+/// 关闭流程的总超时
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(8);
+
+const PHASE_IDLE: u8 = 0;
+const PHASE_RUNNING: u8 = 1;
+const PHASE_FINISHED: u8 = 2;
+
+/// 退出流程状态
+#[derive(Debug, Default)]
+pub struct ShutdownState {
+    phase: AtomicU8,
+}
+
+impl ShutdownState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 开始关闭流程，返回是否由本次调用开始（已在关闭或已完成时返回 false）
+    fn begin(&self) -> bool {
+        self.phase
+            .compare_exchange(PHASE_IDLE, PHASE_RUNNING, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    /// 标记关闭流程完成，之后的退出请求直接放行
+    fn finish(&self) {
+        self.phase.store(PHASE_FINISHED, Ordering::SeqCst);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.phase.load(Ordering::SeqCst) == PHASE_FINISHED
+    }
+}
+
+/// 处理应用运行事件（传给 App::run）
+pub fn handle_run_event<R: Runtime>(app: &AppHandle<R>, event: RunEvent) {
+    let RunEvent::ExitRequested { code, api, .. } = event else {
+        return;
+    };
+    let Some(state) = app.try_state::<ShutdownState>() else {
+        return;
+    };
+    if state.is_finished() {
+        return;
+    }
+
+    api.prevent_exit();
+    if !state.begin() {
+        log::debug!("正在关闭，忽略重复的退出请求");
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        shutdown(&app).await;
+        if let Some(state) = app.try_state::<ShutdownState>() {
+            state.finish();
+        }
+        app.exit(code.unwrap_or(0));
+    });
+}
+
+/// 关闭插件系统（受 SHUTDOWN_TIMEOUT 限制）
+async fn shutdown<R: Runtime>(app: &AppHandle<R>) {
+    let Some(manager) = app.try_state::<PluginManagerState>().map(|state| state.0.clone()) else {
+        return;
+    };
+    log::info!("应用退出中，正在关闭插件系统...");
+    if tokio::time::timeout(SHUTDOWN_TIMEOUT, manager.shutdown()).await.is_err() {
+        log::warn!("插件系统关闭超时 ({}s)，直接退出", SHUTDOWN_TIMEOUT.as_secs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shutdown_runs_once() {
+        let state = ShutdownState::new();
+        assert!(!state.is_finished());
+        assert!(state.begin());
+        // 关闭期间的重复请求不会再次开始
+        assert!(!state.begin());
+        assert!(!state.is_finished());
+        state.finish();
+        assert!(state.is_finished());
+        assert!(!state.begin());
+    }
+}
@@ -0,0 +1,255 @@
+// Phase 7.11: 单实例
+// 重复启动时唤起已运行实例的托盘弹窗，新进程直接退出（避免两个托盘图标和重复刷新）:
+// - 启动时先对 instance.lock（与插件目录同级）加排他 flock，持有锁的进程才是主实例；
+//   锁由主实例持有到退出，进程异常退出时由系统释放，不存在需要人工清理的旧锁
+// - 主实例在本机回环地址监听随机端口，并将 {pid, port} 原地写入锁文件
+// - 加锁失败说明已有实例在运行：读取锁文件并连接该端口发送唤起请求，收到回应即退出；
+//   主实例可能尚未写入端口，短暂重试后仍无回应也直接退出
+// - 同时启动的多个进程由 flock 串行化，只有一个能成为主实例（先读后写的检查存在竞争）
+// - 非 Unix 平台没有 flock，退回到探测锁文件中的端口
+// - 检查在创建 Tauri 应用之前进行，此时日志尚未初始化，重复启动的提示输出到 stderr
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// 锁文件名
+const LOCK_FILE_NAME: &str = "instance.lock";
+
+/// 唤起请求
+const ACTIVATE_REQUEST: &[u8] = b"cuk:activate\n";
+
+/// 唤起回应
+const ACTIVATE_RESPONSE: &[u8] = b"cuk:ok\n";
+
+/// 连接和读写已运行实例的超时
+const IO_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// 已运行实例尚未写入端口时的唤起重试次数
+const ACTIVATE_ATTEMPTS: u32 = 10;
+
+/// 唤起重试间隔
+const ACTIVATE_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 锁文件内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct LockInfo {
+    pid: u32,
+    port: u16,
+}
+
+/// 主实例：唤起请求监听器和实例锁（锁随文件句柄释放，需持有到进程退出）
+pub struct PrimaryInstance {
+    listener: TcpListener,
+    _lock: File,
+}
+
+/// 单实例检查结果
+pub enum Instance {
+    /// 当前进程是唯一实例
+    Primary(PrimaryInstance),
+    /// 已有实例在运行（已尝试唤起其托盘弹窗）
+    Secondary,
+}
+
+/// 默认锁文件路径（与插件目录同级）
+pub fn lock_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("cuk")
+        .join(LOCK_FILE_NAME)
+}
+
+/// 检查是否已有实例在运行，没有时获取锁
+pub fn acquire(path: &Path) -> std::io::Result<Instance> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+
+    if !try_lock(&file)? {
+        for attempt in 0..ACTIVATE_ATTEMPTS {
+            if read_lock(path).is_some_and(|info| activate_running(info.port)) {
+                return Ok(Instance::Secondary);
+            }
+            if attempt + 1 < ACTIVATE_ATTEMPTS {
+                std::thread::sleep(ACTIVATE_RETRY_INTERVAL);
+            }
+        }
+        eprintln!("已有实例持有实例锁，但未回应唤起请求");
+        return Ok(Instance::Secondary);
+    }
+    if let Some(info) = read_lock(path) {
+        // 没有 flock 时锁文件中的端口有回应说明实例仍在运行
+        #[cfg(not(unix))]
+        if activate_running(info.port) {
+            return Ok(Instance::Secondary);
+        }
+        log::info!("实例锁已失效 (pid={}, port={})，接管", info.pid, info.port);
+    }
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let info = LockInfo {
+        pid: std::process::id(),
+        port: listener.local_addr()?.port(),
+    };
+    write_lock(&mut file, &info)?;
+    Ok(Instance::Primary(PrimaryInstance { listener, _lock: file }))
+}
+
+/// 在后台线程处理唤起请求（线程持有实例锁直到进程退出）
+pub fn serve<F>(primary: PrimaryInstance, on_activate: F)
+where
+    F: Fn() + Send + 'static,
+{
+    let result = std::thread::Builder::new()
+        .name("single-instance".into())
+        .spawn(move || {
+            // 整体移入线程，锁随线程持有（只用 listener 字段时闭包不会捕获锁）
+            let PrimaryInstance { listener, _lock } = primary;
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(mut stream) => {
+                        if !read_request(&mut stream) {
+                            continue;
+                        }
+                        log::info!("检测到重复启动，唤起托盘弹窗");
+                        on_activate();
+                        let _ = stream.write_all(ACTIVATE_RESPONSE);
+                    }
+                    Err(e) => log::warn!("接受唤起请求失败: {}", e),
+                }
+            }
+        });
+    if let Err(e) = result {
+        log::error!("启动单实例监听线程失败: {}", e);
+    }
+}
+
+fn read_lock(path: &Path) -> Option<LockInfo> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// 原地写入锁文件（不能用临时文件 + rename，替换文件会让后启动的进程锁住另一个文件）
+fn write_lock(file: &mut File, info: &LockInfo) -> std::io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(&serde_json::to_vec(info)?)?;
+    file.flush()
+}
+
+/// 非阻塞获取排他 flock，已被其他进程持有时返回 false
+#[cfg(unix)]
+fn try_lock(file: &File) -> std::io::Result<bool> {
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: fd 在 file 的生命周期内有效
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(true);
+    }
+    let error = std::io::Error::last_os_error();
+    if error.raw_os_error() == Some(libc::EWOULDBLOCK) {
+        Ok(false)
+    } else {
+        Err(error)
+    }
+}
+
+/// 没有 flock 时总是成功，由调用方探测锁文件中的端口
+#[cfg(not(unix))]
+fn try_lock(_file: &File) -> std::io::Result<bool> {
+    Ok(true)
+}
+
+/// 向已运行的实例发送唤起请求，返回对方是否回应
+fn activate_running(port: u16) -> bool {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, IO_TIMEOUT) else {
+        return false;
+    };
+    let _ = stream.set_read_timeout(Some(IO_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+    if stream.write_all(ACTIVATE_REQUEST).is_err() {
+        return false;
+    }
+    let mut response = [0u8; ACTIVATE_RESPONSE.len()];
+    stream.read_exact(&mut response).is_ok() && response == ACTIVATE_RESPONSE
+}
+
+/// 读取唤起请求，返回是否为有效请求
+fn read_request(stream: &mut TcpStream) -> bool {
+    let _ = stream.set_read_timeout(Some(IO_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+    let mut request = [0u8; ACTIVATE_REQUEST.len()];
+    stream.read_exact(&mut request).is_ok() && request == ACTIVATE_REQUEST
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_second_instance_activates_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE_NAME);
+
+        let Instance::Primary(primary) = acquire(&path).unwrap() else {
+            panic!("首个实例应获取锁");
+        };
+        let activations = Arc::new(AtomicUsize::new(0));
+        let counter = activations.clone();
+        serve(primary, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert!(matches!(acquire(&path).unwrap(), Instance::Secondary));
+        assert_eq!(activations.load(Ordering::SeqCst), 1);
+
+        // 上次异常退出留下的锁文件（锁已释放）直接接管
+        let stale_path = dir.path().join("stale.lock");
+        let closed = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let stale = LockInfo { pid: 1, port: closed.local_addr().unwrap().port() };
+        drop(closed);
+        std::fs::write(&stale_path, serde_json::to_vec(&stale).unwrap()).unwrap();
+        assert!(matches!(acquire(&stale_path).unwrap(), Instance::Primary(_)));
+        assert_eq!(read_lock(&stale_path).unwrap().pid, std::process::id());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_concurrent_launches_elect_one_primary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Arc::new(dir.path().join(LOCK_FILE_NAME));
+        let barrier = Arc::new(std::sync::Barrier::new(6));
+
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let path = path.clone();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    acquire(&path).unwrap()
+                })
+            })
+            .collect();
+        let results: Vec<Instance> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        let primaries = results.iter().filter(|r| matches!(r, Instance::Primary(_))).count();
+        assert_eq!(primaries, 1);
+
+        // 主实例退出后锁被释放，下次启动成为主实例
+        drop(results);
+        assert!(matches!(acquire(&path).unwrap(), Instance::Primary(_)));
+    }
+}
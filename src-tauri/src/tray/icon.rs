@@ -0,0 +1,246 @@
+// Phase 7.1: 动态托盘图标
+// 在基础托盘图标上叠加使用量进度环和状态圆点
+//
+// - 进度环：显示所有插件中最高的使用百分比，颜色随阈值变化
+// - 状态圆点：右下角，警告为橙色、错误为红色、加载中为蓝色，正常时不显示
+
+use std::f64::consts::PI;
+
+use tauri::image::Image;
+
+use super::TrayStatus;
+
+// ============================================================================
+// 阈值与颜色
+// ============================================================================
+
+/// 使用量警告阈值 (%)
+pub const USAGE_WARNING_PERCENT: f64 = 75.0;
+
+/// 使用量严重阈值 (%)
+pub const USAGE_CRITICAL_PERCENT: f64 = 90.0;
+
+/// RGB 颜色
+type Rgb = (u8, u8, u8);
+
+const COLOR_OK: Rgb = (16, 185, 129);
+const COLOR_WARNING: Rgb = (245, 158, 11);
+const COLOR_ERROR: Rgb = (239, 68, 68);
+const COLOR_LOADING: Rgb = (59, 130, 246);
+const COLOR_TRACK: Rgb = (128, 128, 128);
+
+/// 进度环底色透明度
+const TRACK_ALPHA: f64 = 0.35;
+
+/// 根据使用百分比推导托盘状态
+pub fn usage_status(percent: f64) -> TrayStatus {
+    if percent >= USAGE_CRITICAL_PERCENT {
+        TrayStatus::Error
+    } else if percent >= USAGE_WARNING_PERCENT {
+        TrayStatus::Warning
+    } else {
+        TrayStatus::Normal
+    }
+}
+
+/// 状态圆点颜色（正常状态不显示圆点）
+fn status_color(status: TrayStatus) -> Option<Rgb> {
+    match status {
+        TrayStatus::Normal => None,
+        TrayStatus::Warning => Some(COLOR_WARNING),
+        TrayStatus::Error => Some(COLOR_ERROR),
+        TrayStatus::Loading => Some(COLOR_LOADING),
+    }
+}
+
+/// 进度环颜色
+fn usage_color(percent: f64) -> Rgb {
+    match usage_status(percent) {
+        TrayStatus::Error => COLOR_ERROR,
+        TrayStatus::Warning => COLOR_WARNING,
+        _ => COLOR_OK,
+    }
+}
+
+// ============================================================================
+// 渲染
+// ============================================================================
+
+/// 基础托盘图标（编译期从 icons/tray-icon.png 解码）
+fn base_icon() -> Image<'static> {
+    tauri::include_image!("icons/tray-icon.png")
+}
+
+/// 生成指定状态和使用量的托盘图标
+pub fn tray_icon_image(status: TrayStatus, usage: Option<f64>) -> Image<'static> {
+    let base = base_icon();
+    let rgba = render_tray_icon(base.rgba(), base.width(), base.height(), status, usage);
+    Image::new_owned(rgba, base.width(), base.height())
+}
+
+/// 渲染托盘图标 RGBA 数据
+///
+/// 有使用量时将基础图标缩小放入进度环内侧；状态非正常时在右下角绘制圆点，
+/// 圆点周围挖出透明间隙以便在任意菜单栏背景下可辨识。
+pub fn render_tray_icon(
+    base: &[u8],
+    width: u32,
+    height: u32,
+    status: TrayStatus,
+    usage: Option<f64>,
+) -> Vec<u8> {
+    let w = width as usize;
+    let h = height as usize;
+    let size = w.min(h) as f64;
+
+    let mut out = match usage {
+        Some(_) => {
+            let inset = (size / 11.0).max(2.0).round() as usize + 2;
+            shrink_into(base, w, h, inset)
+        }
+        None => base.to_vec(),
+    };
+
+    // 进度环
+    if let Some(percent) = usage {
+        let percent = percent.clamp(0.0, 100.0);
+        let thickness = (size / 11.0).max(2.0);
+        let outer = size / 2.0 - 0.5;
+        let inner = outer - thickness;
+        let (cx, cy) = (w as f64 / 2.0, h as f64 / 2.0);
+        let filled = percent / 100.0 * 2.0 * PI;
+        let color = usage_color(percent);
+
+        for y in 0..h {
+            for x in 0..w {
+                let dx = x as f64 + 0.5 - cx;
+                let dy = y as f64 + 0.5 - cy;
+                let d = (dx * dx + dy * dy).sqrt();
+                let coverage = (outer - d + 0.5).clamp(0.0, 1.0) * (d - inner + 0.5).clamp(0.0, 1.0);
+                if coverage <= 0.0 {
+                    continue;
+                }
+                // 从 12 点方向顺时针计算角度
+                let mut angle = dx.atan2(-dy);
+                if angle < 0.0 {
+                    angle += 2.0 * PI;
+                }
+                if angle <= filled {
+                    blend(&mut out, w, x, y, color, coverage);
+                } else {
+                    blend(&mut out, w, x, y, COLOR_TRACK, coverage * TRACK_ALPHA);
+                }
+            }
+        }
+    }
+
+    // 状态圆点
+    if let Some(color) = status_color(status) {
+        let radius = (size * 0.18).max(2.0);
+        let gap = (size / 22.0).max(1.0);
+        let cx = w as f64 - radius - 0.5;
+        let cy = h as f64 - radius - 0.5;
+
+        for y in 0..h {
+            for x in 0..w {
+                let dx = x as f64 + 0.5 - cx;
+                let dy = y as f64 + 0.5 - cy;
+                let d = (dx * dx + dy * dy).sqrt();
+                // 挖出间隙
+                let clear = (radius + gap - d + 0.5).clamp(0.0, 1.0);
+                if clear > 0.0 {
+                    let idx = (y * w + x) * 4 + 3;
+                    out[idx] = (out[idx] as f64 * (1.0 - clear)).round() as u8;
+                }
+                let coverage = (radius - d + 0.5).clamp(0.0, 1.0);
+                if coverage > 0.0 {
+                    blend(&mut out, w, x, y, color, coverage);
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// 将图标等比缩小到内缩 `inset` 像素的区域（最近邻采样）
+fn shrink_into(base: &[u8], w: usize, h: usize, inset: usize) -> Vec<u8> {
+    let mut out = vec![0u8; w * h * 4];
+    if inset * 2 >= w || inset * 2 >= h {
+        return out;
+    }
+    let (iw, ih) = (w - inset * 2, h - inset * 2);
+    for y in 0..ih {
+        for x in 0..iw {
+            let sx = x * w / iw;
+            let sy = y * h / ih;
+            let src = (sy * w + sx) * 4;
+            let dst = ((y + inset) * w + x + inset) * 4;
+            out[dst..dst + 4].copy_from_slice(&base[src..src + 4]);
+        }
+    }
+    out
+}
+
+/// 将颜色以指定覆盖率叠加到像素上（source-over）
+fn blend(buf: &mut [u8], w: usize, x: usize, y: usize, color: Rgb, alpha: f64) {
+    let idx = (y * w + x) * 4;
+    let dst_a = buf[idx + 3] as f64 / 255.0;
+    let out_a = alpha + dst_a * (1.0 - alpha);
+    if out_a <= 0.0 {
+        return;
+    }
+    let mix = |src: u8, dst: u8| -> u8 {
+        ((src as f64 * alpha + dst as f64 * dst_a * (1.0 - alpha)) / out_a).round() as u8
+    };
+    buf[idx] = mix(color.0, buf[idx]);
+    buf[idx + 1] = mix(color.1, buf[idx + 1]);
+    buf[idx + 2] = mix(color.2, buf[idx + 2]);
+    buf[idx + 3] = (out_a * 255.0).round() as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(buf: &[u8], w: usize, x: usize, y: usize) -> [u8; 4] {
+        let idx = (y * w + x) * 4;
+        [buf[idx], buf[idx + 1], buf[idx + 2], buf[idx + 3]]
+    }
+
+    #[test]
+    fn test_usage_status() {
+        assert_eq!(usage_status(10.0), TrayStatus::Normal);
+        assert_eq!(usage_status(75.0), TrayStatus::Warning);
+        assert_eq!(usage_status(95.0), TrayStatus::Error);
+    }
+
+    #[test]
+    fn test_render_tray_icon() {
+        let (w, h) = (44usize, 44usize);
+        let base = vec![255u8; w * h * 4];
+
+        // 正常且无使用量：保持原图
+        let plain = render_tray_icon(&base, 44, 44, TrayStatus::Normal, None);
+        assert_eq!(plain, base);
+
+        // 错误状态：右下角为红色圆点
+        let error = render_tray_icon(&base, 44, 44, TrayStatus::Error, None);
+        let [r, g, b, a] = pixel(&error, w, 36, 36);
+        assert_eq!((r, g, b, a), (COLOR_ERROR.0, COLOR_ERROR.1, COLOR_ERROR.2, 255));
+
+        // 50% 使用量：右侧环（3 点方向）已填充，左侧环（9 点方向）为底色
+        let ring = render_tray_icon(&base, 44, 44, TrayStatus::Normal, Some(50.0));
+        let right = pixel(&ring, w, 42, 22);
+        assert_eq!((right[0], right[1], right[2]), COLOR_OK);
+        let left = pixel(&ring, w, 1, 21);
+        assert_ne!((left[0], left[1], left[2]), COLOR_OK);
+        assert!(left[3] < 255);
+    }
+
+    #[test]
+    fn test_base_icon_loads() {
+        let image = tray_icon_image(TrayStatus::Warning, Some(80.0));
+        assert_eq!(image.rgba().len(), (image.width() * image.height() * 4) as usize);
+    }
+}
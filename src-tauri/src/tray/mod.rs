@@ -1,14 +1,30 @@
 // Phase 7.1: 系统托盘
 // 实现 macOS 菜单栏应用的托盘功能
 
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
 use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem},
+    menu::{Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Manager, Runtime, Wry,
 };
 use tauri_plugin_positioner::{Position, WindowExt};
 
-use crate::window::{WindowManager, WindowType};
+use crate::commands::ipc::refresh_all_plugins;
+use crate::commands::network::NetworkMonitorState;
+use crate::commands::settings::SettingsState;
+use crate::commands::{AlertManagerState, PluginManagerState};
+use crate::plugin::types::{HealthStatus, PluginData, PluginInfo, StatusIndicator};
+use crate::plugin::ALL_PLUGINS;
+use crate::reliability::ActivityAssertions;
+use crate::updater::AppUpdaterState;
+use crate::window::{hide_animated, hud, show_animated, PopupState, WindowManager, WindowType};
+
+mod icon;
+
+pub use icon::{tray_icon_image, usage_status};
 
 // ============================================================================
 // 托盘状态
@@ -31,10 +47,21 @@ pub enum TrayStatus {
 // 托盘管理器
 // ============================================================================
 
+/// 图标缓存键：(显示状态, 取整后的使用百分比)
+type IconKey = (TrayStatus, Option<u8>);
+
 /// 托盘管理器
 pub struct TrayManager {
     /// 当前状态
     status: TrayStatus,
+    /// 最高使用百分比 (0-100)
+    usage: Option<f64>,
+    /// 上次渲染的图标（避免重复设置相同图标）
+    rendered: Option<IconKey>,
+    /// 菜单中显示的已启用插件 (id, 名称)，按名称排序
+    menu_plugins: Vec<(String, String)>,
+    /// 各插件的用量摘要文本
+    summaries: BTreeMap<String, String>,
 }
 
 impl TrayManager {
@@ -42,6 +69,10 @@ impl TrayManager {
     pub fn new() -> Self {
         Self {
             status: TrayStatus::Normal,
+            usage: None,
+            rendered: None,
+            menu_plugins: Vec::new(),
+            summaries: BTreeMap::new(),
         }
     }
 
@@ -54,6 +85,88 @@ impl TrayManager {
     pub fn set_status(&mut self, status: TrayStatus) {
         self.status = status;
     }
+
+    /// 获取最高使用百分比
+    pub fn usage(&self) -> Option<f64> {
+        self.usage
+    }
+
+    /// 设置最高使用百分比
+    pub fn set_usage(&mut self, usage: Option<f64>) {
+        self.usage = usage.map(|p| p.clamp(0.0, 100.0));
+    }
+
+    /// 显示状态：取显式状态和使用量阈值中更严重的一个
+    pub fn effective_status(&self) -> TrayStatus {
+        let from_usage = self.usage.map(usage_status).unwrap_or(TrayStatus::Normal);
+        match (self.status, from_usage) {
+            (TrayStatus::Error, _) | (_, TrayStatus::Error) => TrayStatus::Error,
+            (TrayStatus::Warning, _) | (_, TrayStatus::Warning) => TrayStatus::Warning,
+            (status, _) => status,
+        }
+    }
+
+    /// 若图标需要更新，返回新的渲染参数并记录
+    fn take_icon_update(&mut self) -> Option<(TrayStatus, Option<f64>)> {
+        let status = self.effective_status();
+        let key = (status, self.usage.map(|p| p.round() as u8));
+        if self.rendered == Some(key) {
+            return None;
+        }
+        self.rendered = Some(key);
+        Some((status, self.usage))
+    }
+
+    /// 设置菜单中的插件（只保留已启用的插件，其他账户显示为"名称 · 账户"）
+    pub fn set_menu_plugins(&mut self, plugins: &[PluginInfo]) {
+        let mut menu_plugins: Vec<(String, String)> = plugins
+            .iter()
+            .filter(|p| p.enabled)
+            .map(|p| {
+                let name = match &p.account_label {
+                    Some(label) => format!("{} · {}", p.name, label),
+                    None => p.name.clone(),
+                };
+                (p.instance_id.clone(), name)
+            })
+            .collect();
+        menu_plugins.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        self.menu_plugins = menu_plugins;
+    }
+
+    /// 按插件数据更新用量摘要，返回摘要是否变化
+    pub fn set_summaries(&mut self, data: &[PluginData]) -> bool {
+        let summaries: BTreeMap<String, String> = data
+            .iter()
+            .filter_map(|d| Some((d.plugin_id().to_string(), plugin_summary(d)?)))
+            .collect();
+        let changed = summaries != self.summaries;
+        self.summaries = summaries;
+        changed
+    }
+
+    /// 插件子菜单标题（名称 + 用量摘要）
+    fn plugin_title(&self, id: &str, name: &str) -> String {
+        match self.summaries.get(id) {
+            Some(summary) => format!("{}  {}", name, summary),
+            None => name.to_string(),
+        }
+    }
+
+    /// 菜单顶部的用量摘要
+    pub fn summary_text(&self) -> String {
+        let parts: Vec<String> = self
+            .menu_plugins
+            .iter()
+            .filter_map(|(id, name)| Some(format!("{} {}", name, self.summaries.get(id)?)))
+            .take(MAX_SUMMARY_PLUGINS)
+            .collect();
+        if parts.is_empty() {
+            "暂无用量数据".to_string()
+        } else {
+            parts.join(" · ")
+        }
+    }
 }
 
 impl Default for TrayManager {
@@ -62,6 +175,162 @@ impl Default for TrayManager {
     }
 }
 
+/// 托盘管理器状态
+pub struct TrayManagerState(pub RwLock<TrayManager>);
+
+impl TrayManagerState {
+    pub fn new() -> Self {
+        Self(RwLock::new(TrayManager::new()))
+    }
+}
+
+impl Default for TrayManagerState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// 托盘点击修饰键
+// ============================================================================
+
+/// 默认工具提示
+const DEFAULT_TOOLTIP: &str = "CUK - Claude Usage Tracker";
+
+/// 点击托盘时按下的修饰键
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClickModifiers {
+    /// ⌥ Option (Alt)
+    pub option: bool,
+    /// ⇧ Shift
+    pub shift: bool,
+}
+
+/// 托盘点击动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayClickAction {
+    /// 切换托盘弹窗
+    TogglePopup,
+    /// 强制刷新所有插件
+    RefreshAll,
+    /// 打开仪表盘
+    OpenDashboard,
+    /// 不执行任何操作
+    None,
+}
+
+impl TrayClickAction {
+    /// 工具提示中的动作描述
+    fn label(&self) -> Option<&'static str> {
+        match self {
+            TrayClickAction::TogglePopup => Some("打开面板"),
+            TrayClickAction::RefreshAll => Some("刷新全部"),
+            TrayClickAction::OpenDashboard => Some("打开仪表盘"),
+            TrayClickAction::None => None,
+        }
+    }
+}
+
+/// 托盘点击配置（按修饰键路由，保存在应用设置的 trayClick 中）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TrayClickConfig {
+    /// 普通点击
+    pub click: TrayClickAction,
+    /// ⌥ 点击
+    pub option_click: TrayClickAction,
+    /// ⇧ 点击
+    pub shift_click: TrayClickAction,
+}
+
+impl Default for TrayClickConfig {
+    fn default() -> Self {
+        Self {
+            click: TrayClickAction::TogglePopup,
+            option_click: TrayClickAction::RefreshAll,
+            shift_click: TrayClickAction::OpenDashboard,
+        }
+    }
+}
+
+impl TrayClickConfig {
+    /// 根据修饰键选择动作（⌥ 优先于 ⇧）
+    pub fn resolve(&self, modifiers: ClickModifiers) -> TrayClickAction {
+        if modifiers.option {
+            self.option_click
+        } else if modifiers.shift {
+            self.shift_click
+        } else {
+            self.click
+        }
+    }
+
+    /// 生成修饰键提示（用于工具提示，便于用户发现）
+    pub fn tooltip_hints(&self) -> Option<String> {
+        let hints: Vec<String> = [("⌥", self.option_click), ("⇧", self.shift_click)]
+            .iter()
+            .filter_map(|(key, action)| action.label().map(|label| format!("{} 点击: {}", key, label)))
+            .collect();
+
+        if hints.is_empty() {
+            None
+        } else {
+            Some(hints.join(" · "))
+        }
+    }
+
+    /// 拼接基础提示和修饰键提示
+    pub fn tooltip(&self, base: &str) -> String {
+        match self.tooltip_hints() {
+            Some(hints) => format!("{}\n{}", base, hints),
+            None => base.to_string(),
+        }
+    }
+}
+
+/// 读取当前键盘修饰键状态
+///
+/// 托盘点击事件不携带修饰键，通过 `[NSEvent modifierFlags]` 查询点击瞬间的按键状态。
+#[cfg(target_os = "macos")]
+fn current_modifiers() -> ClickModifiers {
+    use objc::{class, msg_send, sel, sel_impl};
+
+    // NSEventModifierFlags
+    const NS_EVENT_MODIFIER_FLAG_SHIFT: usize = 1 << 17;
+    const NS_EVENT_MODIFIER_FLAG_OPTION: usize = 1 << 19;
+
+    let flags: usize = unsafe { msg_send![class!(NSEvent), modifierFlags] };
+    ClickModifiers {
+        option: flags & NS_EVENT_MODIFIER_FLAG_OPTION != 0,
+        shift: flags & NS_EVENT_MODIFIER_FLAG_SHIFT != 0,
+    }
+}
+
+/// 非 macOS 平台不支持修饰键检测
+#[cfg(not(target_os = "macos"))]
+fn current_modifiers() -> ClickModifiers {
+    ClickModifiers::default()
+}
+
+/// 获取托盘点击配置（应用设置未加载时使用默认配置）
+fn click_config<R: Runtime>(app: &AppHandle<R>) -> TrayClickConfig {
+    app.try_state::<SettingsState>()
+        .map(|state| state.0.get().tray_click)
+        .unwrap_or_default()
+}
+
+/// 应用托盘点击配置（刷新工具提示中的修饰键提示，配置本身由应用设置保存）
+pub fn apply_click_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: TrayClickConfig,
+) -> Result<(), tauri::Error> {
+    if let Some(tray) = app.tray_by_id("main") {
+        tray.set_tooltip(Some(config.tooltip(DEFAULT_TOOLTIP)))?;
+    }
+    Ok(())
+}
+
 // ============================================================================
 // 托盘设置函数
 // ============================================================================
@@ -70,7 +339,7 @@ impl Default for TrayManager {
 ///
 /// 注意: tauri.conf.json 已配置 trayIcon，Tauri 会自动创建托盘。
 /// 此函数获取已有托盘并设置菜单和事件处理器。
-pub fn setup_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
+pub fn setup_tray(app: &AppHandle) -> Result<(), tauri::Error> {
     // 创建托盘菜单
     let menu = create_tray_menu(app)?;
 
@@ -80,8 +349,8 @@ pub fn setup_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
         tray.set_menu(Some(menu))?;
         // 设置左键点击不显示菜单
         tray.set_show_menu_on_left_click(false)?;
-        // 设置工具提示
-        tray.set_tooltip(Some("CUK - Claude Usage Tracker"))?;
+        // 设置工具提示（包含修饰键提示）
+        tray.set_tooltip(Some(click_config(app).tooltip(DEFAULT_TOOLTIP)))?;
 
         // 注册菜单事件处理器
         tray.on_menu_event(|app, event| {
@@ -108,7 +377,7 @@ pub fn setup_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
         .icon(icon)
         .menu(&menu)
         .show_menu_on_left_click(false)
-        .tooltip("CUK - Claude Usage Tracker")
+        .tooltip(click_config(app).tooltip(DEFAULT_TOOLTIP))
         .on_menu_event(|app, event| {
             handle_menu_event(app, &event.id.0);
         })
@@ -123,33 +392,201 @@ pub fn setup_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
     Ok(())
 }
 
+/// 托盘菜单暂停提醒的时长
+const TRAY_SNOOZE_DURATION: Duration = Duration::from_secs(3600);
+
+/// 菜单顶部摘要最多列出的插件数
+const MAX_SUMMARY_PLUGINS: usize = 3;
+
+/// 插件子菜单项 ID 前缀（格式: plugin:<动作>:<插件 ID>）
+const PLUGIN_MENU_PREFIX: &str = "plugin:";
+
+/// 插件子菜单动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PluginMenuAction {
+    /// 立即刷新
+    Refresh,
+    /// 在仪表盘中查看详情
+    OpenDetails,
+    /// 停用插件
+    Disable,
+}
+
+impl PluginMenuAction {
+    const ALL: [PluginMenuAction; 3] = [
+        PluginMenuAction::Refresh,
+        PluginMenuAction::OpenDetails,
+        PluginMenuAction::Disable,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            PluginMenuAction::Refresh => "refresh",
+            PluginMenuAction::OpenDetails => "details",
+            PluginMenuAction::Disable => "disable",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            PluginMenuAction::Refresh => "立即刷新",
+            PluginMenuAction::OpenDetails => "查看详情",
+            PluginMenuAction::Disable => "停用",
+        }
+    }
+
+    fn menu_id(&self, plugin_id: &str) -> String {
+        format!("{}{}:{}", PLUGIN_MENU_PREFIX, self.as_str(), plugin_id)
+    }
+
+    /// 解析插件子菜单项 ID
+    fn parse(menu_id: &str) -> Option<(PluginMenuAction, &str)> {
+        let (action, plugin_id) = menu_id.strip_prefix(PLUGIN_MENU_PREFIX)?.split_once(':')?;
+        let action = Self::ALL.into_iter().find(|a| a.as_str() == action)?;
+        (!plugin_id.is_empty()).then_some((action, plugin_id))
+    }
+}
+
+/// 插件用量摘要（使用量取最高百分比，余额显示金额，状态显示级别）
+pub fn plugin_summary(data: &PluginData) -> Option<String> {
+    match data {
+        PluginData::Usage(_) => {
+            max_usage_percent(std::slice::from_ref(data)).map(|p| format!("{:.0}%", p))
+        }
+        PluginData::Balance(b) => Some(format!("{:.2} {}", b.balance, b.currency)),
+        PluginData::Status(s) => Some(
+            match s.indicator {
+                StatusIndicator::None => "正常",
+                StatusIndicator::Minor => "轻微异常",
+                StatusIndicator::Major => "严重异常",
+                StatusIndicator::Critical => "故障",
+                StatusIndicator::Unknown => "未知",
+            }
+            .to_string(),
+        ),
+        PluginData::Custom(_) => None,
+    }
+}
+
 /// 创建托盘菜单
+///
+/// 顶部为用量摘要（不可点击），每个已启用插件一个子菜单，其后为固定菜单项
 fn create_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<Menu<R>, tauri::Error> {
-    let menu = Menu::with_items(
-        app,
-        &[
-            &MenuItem::with_id(app, "open", "打开主面板", true, None::<&str>)?,
-            &MenuItem::with_id(app, "refresh", "刷新数据", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "settings", "设置...", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "about", "关于 AiBal", true, None::<&str>)?,
-            &MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?,
-        ],
-    )?;
+    let (summary, plugins) = match app.try_state::<TrayManagerState>() {
+        Some(state) => {
+            let manager = state.0.read().unwrap_or_else(|e| e.into_inner());
+            let plugins: Vec<(String, String)> = manager
+                .menu_plugins
+                .iter()
+                .map(|(id, name)| (id.clone(), manager.plugin_title(id, name)))
+                .collect();
+            (manager.summary_text(), plugins)
+        }
+        None => ("暂无用量数据".to_string(), Vec::new()),
+    };
+
+    let menu = Menu::new(app)?;
+    menu.append(&MenuItem::with_id(app, "usage_summary", summary, false, None::<&str>)?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    for (id, title) in &plugins {
+        let submenu = Submenu::new(app, title, true)?;
+        for action in PluginMenuAction::ALL {
+            submenu.append(&MenuItem::with_id(app, action.menu_id(id), action.label(), true, None::<&str>)?)?;
+        }
+        menu.append(&submenu)?;
+    }
+    if !plugins.is_empty() {
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+
+    menu.append_items(&[
+        &MenuItem::with_id(app, "open", "打开主面板", true, None::<&str>)?,
+        &MenuItem::with_id(app, "refresh", "刷新数据", true, None::<&str>)?,
+        &PredefinedMenuItem::separator(app)?,
+        &MenuItem::with_id(app, "alerts_snooze", "暂停提醒 1 小时", true, None::<&str>)?,
+        &MenuItem::with_id(app, "alerts_resume", "恢复提醒", true, None::<&str>)?,
+        &PredefinedMenuItem::separator(app)?,
+        &MenuItem::with_id(app, "settings", "设置...", true, None::<&str>)?,
+        &MenuItem::with_id(app, "open_log_folder", "打开日志文件夹", true, None::<&str>)?,
+        &PredefinedMenuItem::separator(app)?,
+        &MenuItem::with_id(app, "check_updates", "检查更新…", true, None::<&str>)?,
+        &MenuItem::with_id(app, "about", "关于 AiBal", true, None::<&str>)?,
+        &MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?,
+    ])?;
 
     Ok(menu)
 }
 
+/// 按当前状态重新生成托盘菜单
+fn apply_tray_menu<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
+    if let Some(tray) = app.tray_by_id("main") {
+        tray.set_menu(Some(create_tray_menu(app)?))?;
+    }
+    Ok(())
+}
+
+/// 插件列表变化后重建托盘菜单（每个已启用插件一个子菜单）
+pub fn rebuild_menu<R: Runtime>(app: &AppHandle<R>, plugins: &[PluginInfo]) -> Result<(), tauri::Error> {
+    if let Some(state) = app.try_state::<TrayManagerState>() {
+        state.0.write().unwrap_or_else(|e| e.into_inner()).set_menu_plugins(plugins);
+    }
+    apply_tray_menu(app)?;
+    log::debug!("托盘菜单已重建 ({} 个插件)", plugins.iter().filter(|p| p.enabled).count());
+    Ok(())
+}
+
+/// 处理插件子菜单动作
+fn handle_plugin_menu_action<R: Runtime>(app: &AppHandle<R>, action: PluginMenuAction, plugin_id: &str) {
+    match action {
+        PluginMenuAction::Refresh => {
+            // 与"刷新数据"一致，交给托盘弹窗执行，刷新结果经 refresh_plugin 同步回托盘
+            if let Err(e) = app.emit("tray:refresh_plugin", plugin_id) {
+                log::warn!("发送插件刷新事件失败: plugin={}, error={}", plugin_id, e);
+            }
+        }
+        PluginMenuAction::OpenDetails => {
+            let route = format!("/dashboard?plugin={}", plugin_id);
+            WindowManager::open_dashboard_with_route(app, Some(&route));
+        }
+        PluginMenuAction::Disable => {
+            let Some(state) = app.try_state::<PluginManagerState>() else {
+                return;
+            };
+            let manager = state.0.clone();
+            let app = app.clone();
+            let plugin_id = plugin_id.to_string();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = manager.disable_plugin(&plugin_id).await {
+                    log::warn!("托盘菜单停用插件失败: plugin={}, error={}", plugin_id, e);
+                    return;
+                }
+                log::info!("已通过托盘菜单停用插件: {}", plugin_id);
+                // 与前端停用插件时的广播一致，各窗口据此刷新插件列表
+                if let Err(e) = app.emit("window:plugin_disabled", &plugin_id) {
+                    log::warn!("发送插件停用事件失败: plugin={}, error={}", plugin_id, e);
+                }
+                if let Err(e) = rebuild_menu(&app, &manager.list_plugins().await) {
+                    log::warn!("重建托盘菜单失败: {}", e);
+                }
+            });
+        }
+    }
+}
+
 /// 处理菜单事件
 fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, menu_id: &str) {
     log::debug!("托盘菜单点击: {}", menu_id);
 
+    if let Some((action, plugin_id)) = PluginMenuAction::parse(menu_id) {
+        handle_plugin_menu_action(app, action, plugin_id);
+        return;
+    }
+
     match menu_id {
         "open" => {
             // 打开主窗口
             if let Some(window) = app.get_webview_window("main") {
-                if let Err(e) = window.show() {
+                if let Err(e) = show_animated(&window.as_ref().window()) {
                     log::warn!("显示主窗口失败: {}", e);
                 }
                 if let Err(e) = window.set_focus() {
@@ -165,10 +602,40 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, menu_id: &str) {
                 log::info!("触发数据刷新");
             }
         }
+        "alerts_snooze" => {
+            // 暂停全部插件的提醒（静音的插件保持静音）
+            if let Some(alerts) = app.try_state::<AlertManagerState>() {
+                if let Err(e) = alerts.0.snooze(ALL_PLUGINS, TRAY_SNOOZE_DURATION) {
+                    log::warn!("暂停提醒失败: {}", e);
+                }
+            }
+        }
+        "alerts_resume" => {
+            if let Some(alerts) = app.try_state::<AlertManagerState>() {
+                if let Err(e) = alerts.0.resume_all() {
+                    log::warn!("恢复提醒失败: {}", e);
+                }
+            }
+        }
         "settings" => {
             // 打开设置窗口 (使用 WindowManager 统一管理)
             WindowManager::open(app, WindowType::Settings);
         }
+        "open_log_folder" => {
+            // 在系统文件管理器中打开应用日志目录
+            open_log_folder();
+        }
+        "check_updates" => {
+            // 打开关于窗口显示检查结果，检查完成后通过 app_update:status 通知
+            WindowManager::open(app, WindowType::About);
+            if let Some(updater) = app.try_state::<AppUpdaterState>() {
+                let updater = updater.0.clone();
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    crate::updater::check_and_notify(&app, &updater).await;
+                });
+            }
+        }
         "about" => {
             // 打开关于窗口 (使用 WindowManager 统一管理)
             WindowManager::open(app, WindowType::About);
@@ -184,8 +651,27 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, menu_id: &str) {
     }
 }
 
+/// 在系统文件管理器中打开应用日志目录
+fn open_log_folder() {
+    let dir = crate::logging::log_dir();
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        log::warn!("创建日志目录失败: {:?}, {}", dir, e);
+        return;
+    }
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    if let Err(e) = std::process::Command::new(opener).arg(&dir).spawn() {
+        log::warn!("打开日志目录失败: {:?}, {}", dir, e);
+    }
+}
+
 /// 处理托盘图标事件
-fn handle_tray_event<R: Runtime>(tray: &TrayIcon<R>, event: TrayIconEvent) {
+fn handle_tray_event(tray: &TrayIcon, event: TrayIconEvent) {
     match event {
         TrayIconEvent::Click {
             button: MouseButton::Left,
@@ -194,10 +680,15 @@ fn handle_tray_event<R: Runtime>(tray: &TrayIcon<R>, event: TrayIconEvent) {
             rect,
             ..
         } => {
-            // 左键点击 - 切换主窗口显示
+            // 左键点击 - 按修饰键路由动作
             // 传递托盘图标的位置和尺寸用于精确定位
-            log::debug!("托盘左键点击: position={:?}, rect={:?}", position, rect);
-            toggle_main_window(tray.app_handle(), Some((position, rect)));
+            let modifiers = current_modifiers();
+            let action = click_config(tray.app_handle()).resolve(modifiers);
+            log::debug!(
+                "托盘左键点击: position={:?}, rect={:?}, modifiers={:?}, action={:?}",
+                position, rect, modifiers, action
+            );
+            handle_click_action(tray.app_handle(), action, Some((position, rect)));
         }
         TrayIconEvent::DoubleClick {
             button: MouseButton::Left,
@@ -209,7 +700,7 @@ fn handle_tray_event<R: Runtime>(tray: &TrayIcon<R>, event: TrayIconEvent) {
             log::debug!("托盘左键双击");
             if let Some(window) = tray.app_handle().get_webview_window("main") {
                 position_window_near_tray(&window, Some((position, rect)));
-                if let Err(e) = window.show() {
+                if let Err(e) = show_animated(&window.as_ref().window()) {
                     log::warn!("显示主窗口失败: {}", e);
                 }
                 if let Err(e) = window.set_focus() {
@@ -224,31 +715,59 @@ fn handle_tray_event<R: Runtime>(tray: &TrayIcon<R>, event: TrayIconEvent) {
 /// 托盘位置信息类型
 type TrayPosition = (tauri::PhysicalPosition<f64>, tauri::Rect);
 
+/// 执行托盘点击动作
+fn handle_click_action(
+    app: &AppHandle,
+    action: TrayClickAction,
+    tray_info: Option<TrayPosition>,
+) {
+    match action {
+        // 紧凑 HUD 模式下左键点击只显示百分比小窗
+        TrayClickAction::TogglePopup if hud::hud_enabled(app) => toggle_hud(app, tray_info),
+        TrayClickAction::TogglePopup => toggle_main_window(app, tray_info),
+        TrayClickAction::RefreshAll => {
+            log::info!("托盘点击触发全部刷新");
+            refresh_all(app);
+        }
+        TrayClickAction::OpenDashboard => {
+            WindowManager::open_dashboard_with_route(app, None);
+        }
+        TrayClickAction::None => {}
+    }
+}
+
+/// 强制刷新所有插件
+///
+/// 直接经 PluginManager 执行，与 refresh_all 命令相同（发布数据、用量提醒、同步托盘图标），
+/// 不依赖前端窗口是否已加载
+fn refresh_all(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let (Some(manager), Some(network), Some(activity)) = (
+            app.try_state::<PluginManagerState>(),
+            app.try_state::<NetworkMonitorState>(),
+            app.try_state::<ActivityAssertions>(),
+        ) else {
+            log::warn!("插件管理器尚未初始化，跳过托盘刷新");
+            return;
+        };
+        let data = refresh_all_plugins(&app, &manager.0, &network.0, &activity, true).await;
+        log::info!("托盘刷新完成，{} 个插件返回数据", data.len());
+    });
+}
+
 /// 切换主窗口显示状态
 fn toggle_main_window<R: Runtime>(app: &AppHandle<R>, tray_info: Option<TrayPosition>) {
     if let Some(window) = app.get_webview_window("main") {
         match window.is_visible() {
             Ok(true) => {
-                if let Err(e) = window.hide() {
+                if let Err(e) = hide_animated(&window.as_ref().window()) {
                     log::warn!("隐藏主窗口失败: {}", e);
                 }
             }
-            Ok(false) => {
-                // 定位窗口到托盘图标下方
-                position_window_near_tray(&window, tray_info);
-                if let Err(e) = window.show() {
-                    log::warn!("显示主窗口失败: {}", e);
-                }
-                if let Err(e) = window.set_focus() {
-                    log::warn!("设置窗口焦点失败: {}", e);
-                }
-                // 发送刷新事件，确保托盘弹框显示最新数据
-                if let Err(e) = app.emit("tray:refresh", ()) {
-                    log::warn!("发送托盘刷新事件失败: {}", e);
-                } else {
-                    log::debug!("托盘弹框显示，触发数据刷新");
-                }
-            }
+            // 本次点击先触发了失焦自动隐藏，保持关闭
+            Ok(false) if recently_auto_hidden(app) => {}
+            Ok(false) => show_main_window(app, &window, tray_info),
             Err(e) => {
                 log::error!("获取窗口可见性失败: {}", e);
             }
@@ -256,6 +775,70 @@ fn toggle_main_window<R: Runtime>(app: &AppHandle<R>, tray_info: Option<TrayPosi
     }
 }
 
+/// 在托盘图标下方显示主窗口并触发刷新
+fn show_main_window<R: Runtime>(
+    app: &AppHandle<R>,
+    window: &tauri::WebviewWindow<R>,
+    tray_info: Option<TrayPosition>,
+) {
+    // 定位窗口到托盘图标下方
+    position_window_near_tray(window, tray_info);
+    if let Err(e) = show_animated(&window.as_ref().window()) {
+        log::warn!("显示主窗口失败: {}", e);
+    }
+    if let Err(e) = window.set_focus() {
+        log::warn!("设置窗口焦点失败: {}", e);
+    }
+    // 发送刷新事件，确保托盘弹框显示最新数据
+    if let Err(e) = app.emit("tray:refresh", ()) {
+        log::warn!("发送托盘刷新事件失败: {}", e);
+    } else {
+        log::debug!("托盘弹框显示，触发数据刷新");
+    }
+}
+
+/// 显示托盘弹窗（已显示时只获取焦点）
+///
+/// 重复启动应用时由已运行的实例调用
+pub fn show_popup<R: Runtime>(app: &AppHandle<R>) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    if window.is_visible().unwrap_or(false) {
+        if let Err(e) = window.set_focus() {
+            log::warn!("设置窗口焦点失败: {}", e);
+        }
+        return;
+    }
+    show_main_window(app, &window, None);
+}
+
+/// 切换紧凑 HUD 显示状态
+fn toggle_hud<R: Runtime>(app: &AppHandle<R>, tray_info: Option<TrayPosition>) {
+    if hud::is_visible(app) {
+        hud::hide(app);
+        return;
+    }
+    let Some(window) = hud::prepare(app) else {
+        return;
+    };
+    position_window_near_tray(&window, tray_info);
+    if let Err(e) = hud::show(&window) {
+        log::warn!("显示 HUD 失败: {}", e);
+        return;
+    }
+    // 与弹窗一致，显示时触发刷新，HUD 随 ipc:plugin_data_updated 更新
+    if let Err(e) = app.emit("tray:refresh", ()) {
+        log::warn!("发送托盘刷新事件失败: {}", e);
+    }
+}
+
+/// 弹窗是否刚因失焦被自动隐藏
+fn recently_auto_hidden<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.try_state::<PopupState>()
+        .is_some_and(|state| state.take_recent_auto_hide(Instant::now()))
+}
+
 /// 窗口与菜单栏的间距
 const WINDOW_PADDING: f64 = 4.0;
 
@@ -330,23 +913,106 @@ fn fallback_position<R: Runtime>(window: &tauri::WebviewWindow<R>) {
 // ============================================================================
 
 /// 更新托盘图标状态
-///
-/// 注意: 当前使用默认图标。如需动态图标，需要准备不同状态的图标资源。
 pub fn update_tray_status(app: &AppHandle<Wry>, status: TrayStatus) -> Result<(), tauri::Error> {
-    // 获取托盘图标
+    with_tray_manager(app, |manager| manager.set_status(status));
+    refresh_tray_icon(app)
+}
+
+/// 更新托盘使用量进度环
+pub fn update_tray_usage(app: &AppHandle<Wry>, usage: Option<f64>) -> Result<(), tauri::Error> {
+    with_tray_manager(app, |manager| manager.set_usage(usage));
+    refresh_tray_icon(app)
+}
+
+/// 按所有已启用插件中最差的健康状态确定托盘状态（Phase 6.15）
+pub fn tray_status_for_health(worst: Option<HealthStatus>) -> TrayStatus {
+    match worst {
+        Some(HealthStatus::Unhealthy) => TrayStatus::Error,
+        Some(HealthStatus::Degraded) | Some(HealthStatus::Stale) => TrayStatus::Warning,
+        Some(HealthStatus::Healthy) | None => TrayStatus::Normal,
+    }
+}
+
+/// 根据插件数据同步托盘图标和菜单用量摘要
+///
+/// 使用量取所有插件（含多维度）中的最高百分比；状态取最差健康状态，有插件刷新失败时至少显示警告。
+pub fn sync_tray_with_data(
+    app: &AppHandle<Wry>,
+    data: &[PluginData],
+    failed: usize,
+    worst_health: Option<HealthStatus>,
+) -> Result<(), tauri::Error> {
+    let status = match tray_status_for_health(worst_health) {
+        TrayStatus::Normal if failed > 0 => TrayStatus::Warning,
+        status => status,
+    };
+    let mut summaries_changed = false;
+    with_tray_manager(app, |manager| {
+        manager.set_status(status);
+        manager.set_usage(max_usage_percent(data));
+        summaries_changed = manager.set_summaries(data);
+    });
+    if summaries_changed {
+        apply_tray_menu(app)?;
+    }
+    refresh_tray_icon(app)
+}
+
+/// 计算所有使用量数据中的最高百分比
+pub fn max_usage_percent(data: &[PluginData]) -> Option<f64> {
+    data.iter()
+        .filter_map(|d| match d {
+            PluginData::Usage(u) => Some(u),
+            _ => None,
+        })
+        .flat_map(|u| {
+            std::iter::once(u.percentage).chain(
+                u.dimensions
+                    .iter()
+                    .flatten()
+                    .map(|dimension| dimension.percentage),
+            )
+        })
+        .filter(|p| p.is_finite())
+        .reduce(f64::max)
+}
+
+/// 修改托盘管理器状态
+fn with_tray_manager<F: FnOnce(&mut TrayManager)>(app: &AppHandle<Wry>, f: F) {
+    if let Some(state) = app.try_state::<TrayManagerState>() {
+        f(&mut state.0.write().unwrap_or_else(|e| e.into_inner()));
+    }
+}
+
+/// 按当前状态重新渲染托盘图标和工具提示
+fn refresh_tray_icon(app: &AppHandle<Wry>) -> Result<(), tauri::Error> {
+    let Some(state) = app.try_state::<TrayManagerState>() else {
+        return Ok(());
+    };
+    let (status, usage, update) = {
+        let mut manager = state.0.write().unwrap_or_else(|e| e.into_inner());
+        let update = manager.take_icon_update();
+        (manager.effective_status(), manager.usage(), update)
+    };
+
     if let Some(tray) = app.tray_by_id("main") {
         // 根据状态更新工具提示
-        let tooltip = match status {
+        let label = match status {
             TrayStatus::Normal => "CUK - 正常",
             TrayStatus::Warning => "CUK - 警告",
             TrayStatus::Error => "CUK - 错误",
             TrayStatus::Loading => "CUK - 加载中...",
         };
-        tray.set_tooltip(Some(tooltip))?;
+        let tooltip = match usage {
+            Some(percent) => format!("{} · 最高使用率 {:.0}%", label, percent),
+            None => label.to_string(),
+        };
+        tray.set_tooltip(Some(click_config(app).tooltip(&tooltip)))?;
 
-        // TODO: 动态图标需要在 icons/ 目录准备不同状态的图标
-        // 然后使用 tauri::image::Image 加载
-        log::debug!("托盘状态更新: {:?}", status);
+        if let Some((status, usage)) = update {
+            tray.set_icon(Some(tray_icon_image(status, usage)))?;
+            log::debug!("托盘图标更新: {:?}, usage={:?}", status, usage);
+        }
     }
 
     Ok(())
@@ -372,4 +1038,122 @@ mod tests {
         manager.set_status(TrayStatus::Warning);
         assert_eq!(manager.status(), TrayStatus::Warning);
     }
+
+    #[test]
+    fn test_tray_manager_effective_status() {
+        let mut manager = TrayManager::new();
+        assert!(manager.take_icon_update().is_some());
+        // 状态未变化时不重复渲染
+        assert!(manager.take_icon_update().is_none());
+
+        manager.set_usage(Some(92.0));
+        assert_eq!(manager.effective_status(), TrayStatus::Error);
+        assert_eq!(manager.take_icon_update(), Some((TrayStatus::Error, Some(92.0))));
+
+        manager.set_usage(Some(50.0));
+        manager.set_status(TrayStatus::Warning);
+        assert_eq!(manager.effective_status(), TrayStatus::Warning);
+
+        manager.set_status(TrayStatus::Loading);
+        assert_eq!(manager.effective_status(), TrayStatus::Loading);
+    }
+
+    #[test]
+    fn test_tray_status_for_health() {
+        assert_eq!(tray_status_for_health(None), TrayStatus::Normal);
+        assert_eq!(tray_status_for_health(Some(HealthStatus::Healthy)), TrayStatus::Normal);
+        assert_eq!(tray_status_for_health(Some(HealthStatus::Degraded)), TrayStatus::Warning);
+        assert_eq!(tray_status_for_health(Some(HealthStatus::Unhealthy)), TrayStatus::Error);
+        assert_eq!(tray_status_for_health(Some(HealthStatus::Stale)), TrayStatus::Warning);
+    }
+
+    #[test]
+    fn test_max_usage_percent() {
+        let data: Vec<PluginData> = serde_json::from_value(serde_json::json!([
+            {
+                "dataType": "usage", "pluginId": "a", "lastUpdated": "2025-01-01T00:00:00Z",
+                "percentage": 40.0, "used": 40.0, "limit": 100.0, "unit": "%",
+                "dimensions": [
+                    { "id": "week", "label": "周", "percentage": 81.5, "used": 0.0, "limit": 0.0 }
+                ]
+            },
+            {
+                "dataType": "usage", "pluginId": "b", "lastUpdated": "2025-01-01T00:00:00Z",
+                "percentage": 60.0, "used": 60.0, "limit": 100.0, "unit": "%"
+            }
+        ]))
+        .unwrap();
+        assert_eq!(max_usage_percent(&data), Some(81.5));
+        assert_eq!(max_usage_percent(&[]), None);
+    }
+
+    #[test]
+    fn test_tray_menu_plugins_and_summary() {
+        let plugins: Vec<PluginInfo> = serde_json::from_value(serde_json::json!([
+            { "id": "openai", "instanceId": "openai", "name": "OpenAI", "version": "1.0.0", "pluginType": "data", "enabled": true, "healthy": true },
+            { "id": "claude", "instanceId": "claude", "name": "Claude", "version": "1.0.0", "pluginType": "data", "enabled": true, "healthy": true },
+            { "id": "off", "instanceId": "off", "name": "Off", "version": "1.0.0", "pluginType": "data", "enabled": false, "healthy": true }
+        ]))
+        .unwrap();
+        let data: Vec<PluginData> = serde_json::from_value(serde_json::json!([
+            {
+                "dataType": "usage", "pluginId": "claude", "lastUpdated": "2025-01-01T00:00:00Z",
+                "percentage": 45.2, "used": 45.0, "limit": 100.0, "unit": "%"
+            },
+            {
+                "dataType": "balance", "pluginId": "openai", "lastUpdated": "2025-01-01T00:00:00Z",
+                "balance": 12.3, "currency": "USD"
+            }
+        ]))
+        .unwrap();
+
+        let mut manager = TrayManager::new();
+        manager.set_menu_plugins(&plugins);
+        assert_eq!(manager.summary_text(), "暂无用量数据");
+        assert!(manager.set_summaries(&data));
+        assert!(!manager.set_summaries(&data));
+        // 只列出已启用插件，按名称排序
+        assert_eq!(manager.summary_text(), "Claude 45% · OpenAI 12.30 USD");
+        assert_eq!(manager.plugin_title("claude", "Claude"), "Claude  45%");
+
+        let id = PluginMenuAction::Disable.menu_id("claude");
+        assert_eq!(PluginMenuAction::parse(&id), Some((PluginMenuAction::Disable, "claude")));
+        assert_eq!(PluginMenuAction::parse("plugin:unknown:claude"), None);
+        assert_eq!(PluginMenuAction::parse("refresh"), None);
+    }
+
+    #[test]
+    fn test_click_config_resolve() {
+        let config = TrayClickConfig::default();
+        assert_eq!(config.resolve(ClickModifiers::default()), TrayClickAction::TogglePopup);
+        assert_eq!(
+            config.resolve(ClickModifiers { option: true, shift: false }),
+            TrayClickAction::RefreshAll
+        );
+        assert_eq!(
+            config.resolve(ClickModifiers { option: false, shift: true }),
+            TrayClickAction::OpenDashboard
+        );
+        // ⌥ 优先
+        assert_eq!(
+            config.resolve(ClickModifiers { option: true, shift: true }),
+            TrayClickAction::RefreshAll
+        );
+    }
+
+    #[test]
+    fn test_click_config_tooltip() {
+        let config = TrayClickConfig::default();
+        assert_eq!(
+            config.tooltip("CUK"),
+            "CUK\n⌥ 点击: 刷新全部 · ⇧ 点击: 打开仪表盘"
+        );
+
+        let config = TrayClickConfig {
+            option_click: TrayClickAction::None,
+            shift_click: TrayClickAction::None,
+            ..TrayClickConfig::default()
+        };
+        assert_eq!(config.tooltip("CUK"), "CUK");
+    }
 }
@@ -0,0 +1,774 @@
+// Phase 7.12: 应用自动更新
+// 定期获取发布地址的 latest.json，发现新版本时通知前端，下载和安装都由用户在关于窗口中确认:
+// - latest.json 使用与插件 registry 相同的 Ed25519 签名（signature 字段），签名无效的发布信息直接丢弃
+// - 同一密钥也签署插件 manifest 和 registry 索引，latest.json 必须带已签名的 `type: "app-release"`，
+//   防止其他已签名的 JSON 被当作发布信息
+// - 各平台安装包（.app 的 zip 压缩包）的 sha256 记录在已签名的 latest.json 中，
+//   下载时边写入边计算哈希，不一致时删除已下载的文件
+// - 安装包先暂存在缓存目录（staged），用户确认安装后才替换当前 .app，替换失败时恢复原 bundle
+// - 安装完成后请求重启，经 RunEvent::ExitRequested 走优雅退出流程（Phase 7.10）
+// - 托盘菜单"检查更新…"立即检查并打开关于窗口显示结果
+// - 目前仅支持替换 macOS .app，其他平台只提示新版本
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter, Runtime};
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use zip::ZipArchive;
+
+use crate::commands::error::codes;
+use crate::commands::event_names;
+use crate::plugin::updates::compare_versions;
+use crate::security::SignatureVerifier;
+
+/// 默认发布信息地址
+pub const DEFAULT_RELEASE_ENDPOINT: &str =
+    "https://github.com/DDG0808/aibal/releases/latest/download/latest.json";
+
+/// 首次检查延迟（避开启动时的插件初始化和数据刷新）
+const INITIAL_CHECK_DELAY: Duration = Duration::from_secs(60);
+
+/// 后台检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// latest.json 大小上限
+const MAX_MANIFEST_SIZE: usize = 64 * 1024;
+
+/// 安装包大小上限
+const MAX_ARTIFACT_SIZE: u64 = 200 * 1024 * 1024;
+
+/// latest.json 的 type 字段（签名覆盖，用于区分同一密钥签署的其他文档）
+const RELEASE_MANIFEST_TYPE: &str = "app-release";
+
+/// 获取发布信息的超时
+const CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 下载安装包的超时
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// 下载进度事件的最小间隔字节数
+const PROGRESS_STEP: u64 = 512 * 1024;
+
+// ============================================================================
+// 错误
+// ============================================================================
+
+/// 应用更新错误
+#[derive(Debug, Error)]
+pub enum UpdaterError {
+    #[error("获取发布信息失败: {0}")]
+    Fetch(String),
+
+    #[error("发布信息签名无效: {0}")]
+    SignatureInvalid(String),
+
+    #[error("发布信息格式错误: {0}")]
+    InvalidManifest(String),
+
+    #[error("没有可用的更新")]
+    NoUpdate,
+
+    #[error("正在{0}，请稍后再试")]
+    Busy(&'static str),
+
+    #[error("下载更新失败: {0}")]
+    Download(String),
+
+    #[error("安装包哈希不匹配: 期望 {expected}, 实际 {actual}")]
+    HashMismatch { expected: String, actual: String },
+
+    #[error("更新尚未下载完成")]
+    NotReady,
+
+    #[error("当前平台不支持自动安装更新")]
+    Unsupported,
+
+    #[error("安装更新失败: {0}")]
+    Install(String),
+}
+
+impl UpdaterError {
+    /// 错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            UpdaterError::Fetch(_) | UpdaterError::InvalidManifest(_) => codes::APP_UPDATE_CHECK_FAILED,
+            UpdaterError::SignatureInvalid(_) => codes::SIGNATURE_INVALID,
+            UpdaterError::NoUpdate => codes::APP_UPDATE_NOT_AVAILABLE,
+            UpdaterError::Busy(_) => codes::APP_UPDATE_BUSY,
+            UpdaterError::Download(_) => codes::APP_UPDATE_DOWNLOAD_FAILED,
+            UpdaterError::HashMismatch { .. } => codes::INTEGRITY_FAILED,
+            UpdaterError::NotReady => codes::APP_UPDATE_NOT_READY,
+            UpdaterError::Unsupported => codes::NOT_SUPPORTED,
+            UpdaterError::Install(_) => codes::APP_UPDATE_INSTALL_FAILED,
+        }
+    }
+}
+
+fn install_error(e: impl std::fmt::Display) -> UpdaterError {
+    UpdaterError::Install(e.to_string())
+}
+
+// ============================================================================
+// 发布信息
+// ============================================================================
+
+/// 发布信息（latest.json，签名校验通过后解析）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseManifest {
+    /// 版本号
+    pub version: String,
+    /// 更新说明
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// 发布时间 (ISO 8601)
+    #[serde(default)]
+    pub pub_date: Option<String>,
+    /// 各平台安装包（键为 darwin-aarch64 / darwin-x86_64 等）
+    #[serde(default)]
+    pub platforms: BTreeMap<String, ReleaseArtifact>,
+}
+
+/// 单个平台的安装包
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseArtifact {
+    /// 下载地址
+    pub url: String,
+    /// 安装包 SHA-256（十六进制）
+    pub sha256: String,
+    /// 安装包大小（字节）
+    #[serde(default)]
+    pub size: Option<u64>,
+}
+
+impl ReleaseManifest {
+    /// 校验签名和文档类型后解析
+    pub fn from_signed(raw: &Value, verifier: &SignatureVerifier) -> Result<Self, UpdaterError> {
+        verifier
+            .verify_manifest(raw)
+            .map_err(|e| UpdaterError::SignatureInvalid(e.to_string()))?;
+        let kind = raw.get("type").and_then(Value::as_str);
+        if kind != Some(RELEASE_MANIFEST_TYPE) {
+            return Err(UpdaterError::InvalidManifest(format!(
+                "不是应用发布信息 (type: {:?})",
+                kind
+            )));
+        }
+        serde_json::from_value(raw.clone()).map_err(|e| UpdaterError::InvalidManifest(e.to_string()))
+    }
+
+    /// 当前平台的安装包
+    pub fn artifact(&self, platform: &str) -> Option<&ReleaseArtifact> {
+        self.platforms.get(platform)
+    }
+}
+
+/// 当前平台标识（与 latest.json 的 platforms 键一致）
+fn platform_key() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        other => other,
+    };
+    format!("{}-{}", os, std::env::consts::ARCH)
+}
+
+// ============================================================================
+// 更新状态
+// ============================================================================
+
+/// 更新阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AppUpdatePhase {
+    /// 尚未检查
+    Idle,
+    /// 已是最新版本
+    UpToDate,
+    /// 有新版本，尚未下载
+    Available,
+    /// 正在下载
+    Downloading,
+    /// 已下载并校验，等待用户确认安装
+    Ready,
+    /// 正在安装
+    Installing,
+}
+
+/// 可用的新版本
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppRelease {
+    /// 版本号
+    pub version: String,
+    /// 更新说明
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// 发布时间
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pub_date: Option<String>,
+    /// 安装包大小（字节）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// 当前平台是否可以自动安装（否则只能手动下载）
+    pub installable: bool,
+}
+
+/// 应用更新状态（app_update:status 事件和更新命令的返回值）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppUpdateStatus {
+    /// 当前版本
+    pub current_version: String,
+    /// 更新阶段
+    pub phase: AppUpdatePhase,
+    /// 新版本信息（available / downloading / ready / installing 时存在）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release: Option<AppRelease>,
+    /// 已下载字节数
+    pub downloaded_bytes: u64,
+    /// 最近一次检查时间 (ISO 8601)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_checked: Option<String>,
+    /// 最近一次检查、下载或安装的错误
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// 更新器内部状态
+struct UpdaterInner {
+    phase: AppUpdatePhase,
+    release: Option<ReleaseManifest>,
+    staged: Option<PathBuf>,
+    downloaded_bytes: u64,
+    last_checked: Option<String>,
+    last_error: Option<String>,
+}
+
+// ============================================================================
+// 更新器
+// ============================================================================
+
+/// 应用更新器状态
+pub struct AppUpdaterState(pub Arc<AppUpdater>);
+
+/// 创建应用更新器（版本取自 tauri.conf.json，安装包暂存在缓存目录）
+pub fn create_app_updater<R: Runtime>(app: &AppHandle<R>) -> AppUpdaterState {
+    let staging_dir = dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("cuk")
+        .join("updates");
+    AppUpdaterState(Arc::new(AppUpdater::new(
+        app.package_info().version.to_string(),
+        DEFAULT_RELEASE_ENDPOINT,
+        staging_dir,
+    )))
+}
+
+/// 应用更新器
+pub struct AppUpdater {
+    http_client: reqwest::Client,
+    verifier: SignatureVerifier,
+    endpoint: String,
+    current_version: String,
+    platform: String,
+    staging_dir: PathBuf,
+    inner: Mutex<UpdaterInner>,
+}
+
+impl AppUpdater {
+    pub fn new(current_version: String, endpoint: &str, staging_dir: PathBuf) -> Self {
+        let http_client = reqwest::Client::builder()
+            .user_agent(format!("AiBal/{}", current_version))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            http_client,
+            verifier: SignatureVerifier::default(),
+            endpoint: endpoint.to_string(),
+            current_version,
+            platform: platform_key(),
+            staging_dir,
+            inner: Mutex::new(UpdaterInner {
+                phase: AppUpdatePhase::Idle,
+                release: None,
+                staged: None,
+                downloaded_bytes: 0,
+                last_checked: None,
+                last_error: None,
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, UpdaterInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 当前状态
+    pub fn status(&self) -> AppUpdateStatus {
+        let inner = self.lock();
+        self.snapshot(&inner)
+    }
+
+    fn snapshot(&self, inner: &UpdaterInner) -> AppUpdateStatus {
+        let release = inner.release.as_ref().map(|release| {
+            let artifact = release.artifact(&self.platform);
+            AppRelease {
+                version: release.version.clone(),
+                notes: release.notes.clone(),
+                pub_date: release.pub_date.clone(),
+                size: artifact.and_then(|a| a.size),
+                installable: artifact.is_some() && cfg!(target_os = "macos"),
+            }
+        });
+        AppUpdateStatus {
+            current_version: self.current_version.clone(),
+            phase: inner.phase,
+            release,
+            downloaded_bytes: inner.downloaded_bytes,
+            last_checked: inner.last_checked.clone(),
+            last_error: inner.last_error.clone(),
+        }
+    }
+
+    /// 检查新版本（下载或安装进行中时只返回当前状态）
+    pub async fn check(&self) -> Result<AppUpdateStatus, UpdaterError> {
+        {
+            let inner = self.lock();
+            if matches!(inner.phase, AppUpdatePhase::Downloading | AppUpdatePhase::Installing) {
+                return Ok(self.snapshot(&inner));
+            }
+        }
+
+        let result = self.fetch_release().await;
+        let mut inner = self.lock();
+        inner.last_checked = Some(Utc::now().to_rfc3339());
+        let release = match result {
+            Ok(release) => release,
+            Err(e) => {
+                inner.last_error = Some(e.to_string());
+                return Err(e);
+            }
+        };
+        inner.last_error = None;
+
+        if compare_versions(&release.version, &self.current_version).is_gt() {
+            let same_release = inner.release.as_ref().map(|r| r.version.as_str()) == Some(&release.version);
+            if !(same_release && inner.phase == AppUpdatePhase::Ready) {
+                inner.phase = AppUpdatePhase::Available;
+                inner.staged = None;
+                inner.downloaded_bytes = 0;
+            }
+            log::info!("发现新版本: {} -> {}", self.current_version, release.version);
+            inner.release = Some(release);
+        } else {
+            inner.phase = AppUpdatePhase::UpToDate;
+            inner.release = None;
+            inner.staged = None;
+            inner.downloaded_bytes = 0;
+        }
+        Ok(self.snapshot(&inner))
+    }
+
+    /// 获取并校验 latest.json
+    async fn fetch_release(&self) -> Result<ReleaseManifest, UpdaterError> {
+        let response = self
+            .http_client
+            .get(&self.endpoint)
+            .timeout(CHECK_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| UpdaterError::Fetch(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(UpdaterError::Fetch(format!("HTTP {}", response.status())));
+        }
+
+        let bytes = response.bytes().await.map_err(|e| UpdaterError::Fetch(e.to_string()))?;
+        if bytes.len() > MAX_MANIFEST_SIZE {
+            return Err(UpdaterError::Fetch(format!("响应过大: {} bytes", bytes.len())));
+        }
+        let raw: Value =
+            serde_json::from_slice(&bytes).map_err(|e| UpdaterError::InvalidManifest(e.to_string()))?;
+        ReleaseManifest::from_signed(&raw, &self.verifier)
+    }
+
+    /// 下载当前平台的安装包到暂存目录（校验 sha256），每下载 PROGRESS_STEP 字节回调一次进度
+    pub async fn download<F>(&self, on_progress: F) -> Result<AppUpdateStatus, UpdaterError>
+    where
+        F: Fn(&AppUpdateStatus),
+    {
+        let (version, artifact) = {
+            let mut inner = self.lock();
+            match inner.phase {
+                AppUpdatePhase::Ready => return Ok(self.snapshot(&inner)),
+                AppUpdatePhase::Downloading => return Err(UpdaterError::Busy("下载更新")),
+                AppUpdatePhase::Installing => return Err(UpdaterError::Busy("安装更新")),
+                AppUpdatePhase::Available => {}
+                AppUpdatePhase::Idle | AppUpdatePhase::UpToDate => return Err(UpdaterError::NoUpdate),
+            }
+            let release = inner.release.as_ref().ok_or(UpdaterError::NoUpdate)?;
+            let artifact = release.artifact(&self.platform).cloned().ok_or(UpdaterError::Unsupported)?;
+            let version = release.version.clone();
+            inner.phase = AppUpdatePhase::Downloading;
+            inner.downloaded_bytes = 0;
+            inner.last_error = None;
+            (version, artifact)
+        };
+
+        let dest = self.staging_dir.join(format!("AiBal-{}-{}.zip", version, self.platform));
+        let result = self.fetch_artifact(&artifact, &dest, &on_progress).await;
+
+        let mut inner = self.lock();
+        match result {
+            Ok(()) => {
+                log::info!("更新 {} 已下载: {:?}", version, dest);
+                inner.phase = AppUpdatePhase::Ready;
+                inner.staged = Some(dest);
+                Ok(self.snapshot(&inner))
+            }
+            Err(e) => {
+                log::warn!("下载更新 {} 失败: {}", version, e);
+                inner.phase = AppUpdatePhase::Available;
+                inner.downloaded_bytes = 0;
+                inner.last_error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// 流式下载安装包（先写入 .part，哈希校验通过后 rename）
+    async fn fetch_artifact<F>(
+        &self,
+        artifact: &ReleaseArtifact,
+        dest: &Path,
+        on_progress: &F,
+    ) -> Result<(), UpdaterError>
+    where
+        F: Fn(&AppUpdateStatus),
+    {
+        let download_error = |e: &dyn std::fmt::Display| UpdaterError::Download(e.to_string());
+        tokio::fs::create_dir_all(&self.staging_dir).await.map_err(|e| download_error(&e))?;
+
+        let mut response = self
+            .http_client
+            .get(&artifact.url)
+            .timeout(DOWNLOAD_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| download_error(&e))?;
+        if !response.status().is_success() {
+            return Err(UpdaterError::Download(format!("HTTP {}", response.status())));
+        }
+
+        let part = dest.with_extension("zip.part");
+        let mut file = tokio::fs::File::create(&part).await.map_err(|e| download_error(&e))?;
+        let mut hasher = Sha256::new();
+        let mut downloaded: u64 = 0;
+        let mut reported: u64 = 0;
+
+        let result = async {
+            while let Some(chunk) = response.chunk().await.map_err(|e| download_error(&e))? {
+                downloaded += chunk.len() as u64;
+                if downloaded > MAX_ARTIFACT_SIZE {
+                    return Err(UpdaterError::Download(format!("安装包超过 {} bytes", MAX_ARTIFACT_SIZE)));
+                }
+                hasher.update(&chunk);
+                file.write_all(&chunk).await.map_err(|e| download_error(&e))?;
+
+                if downloaded - reported >= PROGRESS_STEP {
+                    reported = downloaded;
+                    let status = {
+                        let mut inner = self.lock();
+                        inner.downloaded_bytes = downloaded;
+                        self.snapshot(&inner)
+                    };
+                    on_progress(&status);
+                }
+            }
+            file.flush().await.map_err(|e| download_error(&e))?;
+
+            let actual = format!("{:x}", hasher.finalize());
+            if !actual.eq_ignore_ascii_case(&artifact.sha256) {
+                return Err(UpdaterError::HashMismatch {
+                    expected: artifact.sha256.clone(),
+                    actual,
+                });
+            }
+            tokio::fs::rename(&part, dest).await.map_err(|e| download_error(&e))
+        }
+        .await;
+
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(&part).await;
+        } else {
+            self.lock().downloaded_bytes = downloaded;
+        }
+        result
+    }
+
+    /// 安装已下载的更新（用户确认后调用，成功后由调用方请求重启）
+    pub fn install(&self) -> Result<String, UpdaterError> {
+        let (version, archive) = {
+            let mut inner = self.lock();
+            match inner.phase {
+                AppUpdatePhase::Ready => {}
+                AppUpdatePhase::Installing => return Err(UpdaterError::Busy("安装更新")),
+                _ => return Err(UpdaterError::NotReady),
+            }
+            let archive = inner.staged.clone().ok_or(UpdaterError::NotReady)?;
+            let version = inner.release.as_ref().map(|r| r.version.clone()).unwrap_or_default();
+            inner.phase = AppUpdatePhase::Installing;
+            (version, archive)
+        };
+
+        let result = current_bundle().and_then(|bundle| {
+            log::info!("安装更新 {}: {:?} -> {:?}", version, archive, bundle);
+            install_bundle(&archive, &bundle)
+        });
+
+        let mut inner = self.lock();
+        match result {
+            Ok(()) => {
+                let _ = std::fs::remove_file(&archive);
+                inner.staged = None;
+                Ok(version)
+            }
+            Err(e) => {
+                log::error!("安装更新 {} 失败: {}", version, e);
+                inner.phase = AppUpdatePhase::Ready;
+                inner.last_error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 后台检查
+// ============================================================================
+
+/// 检查新版本并通过 app_update:status 通知前端
+pub async fn check_and_notify<R: Runtime>(app: &AppHandle<R>, updater: &AppUpdater) {
+    let status = match updater.check().await {
+        Ok(status) => status,
+        Err(e) => {
+            log::warn!("应用更新检查失败: {}", e);
+            updater.status()
+        }
+    };
+    notify_status(app, &status);
+}
+
+/// 发送更新状态事件
+pub fn notify_status<R: Runtime>(app: &AppHandle<R>, status: &AppUpdateStatus) {
+    if let Err(e) = app.emit(event_names::APP_UPDATE_STATUS, status) {
+        log::warn!("发送应用更新状态事件失败: {}", e);
+    }
+}
+
+/// 启动后台定期检查
+pub fn spawn_app_update_checker<R: Runtime>(app: AppHandle<R>, updater: Arc<AppUpdater>) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(INITIAL_CHECK_DELAY).await;
+        loop {
+            check_and_notify(&app, &updater).await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+// ============================================================================
+// 安装
+// ============================================================================
+
+/// 当前运行的 .app（可执行文件位于 <bundle>.app/Contents/MacOS/）
+fn current_bundle() -> Result<PathBuf, UpdaterError> {
+    if !cfg!(target_os = "macos") {
+        return Err(UpdaterError::Unsupported);
+    }
+    let exe = std::env::current_exe().map_err(install_error)?;
+    exe.ancestors()
+        .nth(3)
+        .filter(|bundle| bundle.extension().is_some_and(|ext| ext == "app"))
+        .map(Path::to_path_buf)
+        .ok_or(UpdaterError::Unsupported)
+}
+
+/// 解压安装包并替换 bundle（解压到同一目录再 rename，替换失败时恢复原 bundle）
+fn install_bundle(archive: &Path, bundle: &Path) -> Result<(), UpdaterError> {
+    let parent = bundle.parent().ok_or_else(|| install_error("无效的安装路径"))?;
+    let name = bundle
+        .file_name()
+        .ok_or_else(|| install_error("无效的安装路径"))?
+        .to_string_lossy()
+        .to_string();
+    let unpack_dir = parent.join(format!(".{}.update", name));
+    let backup = parent.join(format!(".{}.old", name));
+    let _ = std::fs::remove_dir_all(&unpack_dir);
+    let _ = std::fs::remove_dir_all(&backup);
+
+    let result = unpack(archive, &unpack_dir)
+        .and_then(|()| find_bundle(&unpack_dir))
+        .and_then(|new_bundle| {
+            std::fs::rename(bundle, &backup).map_err(install_error)?;
+            if let Err(e) = std::fs::rename(&new_bundle, bundle) {
+                let _ = std::fs::rename(&backup, bundle);
+                return Err(install_error(e));
+            }
+            if let Err(e) = std::fs::remove_dir_all(&backup) {
+                log::warn!("清理旧版本失败: {:?}, {}", backup, e);
+            }
+            Ok(())
+        });
+    let _ = std::fs::remove_dir_all(&unpack_dir);
+    result
+}
+
+/// 解压安装包（保留可执行权限和 bundle 内的符号链接）
+fn unpack(archive: &Path, dest: &Path) -> Result<(), UpdaterError> {
+    let file = File::open(archive).map_err(install_error)?;
+    let mut zip = ZipArchive::new(file).map_err(install_error)?;
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(install_error)?;
+        let relative = entry
+            .enclosed_name()
+            .ok_or_else(|| install_error(format!("安装包包含不安全的路径: {}", entry.name())))?;
+        let target = dest.join(relative);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&target).map_err(install_error)?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(install_error)?;
+        }
+
+        #[cfg(unix)]
+        if entry.is_symlink() {
+            let mut link = String::new();
+            entry.read_to_string(&mut link).map_err(install_error)?;
+            std::os::unix::fs::symlink(link, &target).map_err(install_error)?;
+            continue;
+        }
+
+        let mut out = File::create(&target).map_err(install_error)?;
+        std::io::copy(&mut entry, &mut out).map_err(install_error)?;
+
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&target, std::fs::Permissions::from_mode(mode & 0o777))
+                .map_err(install_error)?;
+        }
+    }
+    Ok(())
+}
+
+/// 在解压目录中查找 .app
+fn find_bundle(dir: &Path) -> Result<PathBuf, UpdaterError> {
+    std::fs::read_dir(dir)
+        .map_err(install_error)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .find(|path| path.is_dir() && path.extension().is_some_and(|ext| ext == "app"))
+        .ok_or_else(|| install_error("安装包中没有 .app"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::canonical::canonicalize_for_signing;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use ed25519_dalek::{Signer, SigningKey};
+    use serde_json::json;
+    use std::io::Write;
+
+    // RFC 8032 测试向量私钥（对应 signature 模块中的 "test" 公钥）
+    const TEST_PRIVATE_KEY: [u8; 32] = [
+        0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec, 0x2c,
+        0xc4, 0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03, 0x1c, 0xae,
+        0x7f, 0x60,
+    ];
+
+    fn sign(raw: &mut Value) {
+        let signature = SigningKey::from_bytes(&TEST_PRIVATE_KEY)
+            .sign(canonicalize_for_signing(raw).as_bytes());
+        raw["signature"] = json!(format!("ed25519:test:{}", BASE64.encode(signature.to_bytes())));
+    }
+
+    #[test]
+    fn test_signed_release_manifest() {
+        let mut raw = json!({
+            "type": "app-release",
+            "version": "0.2.0",
+            "notes": "修复若干问题",
+            "platforms": {
+                "darwin-aarch64": { "url": "https://example.com/AiBal.zip", "sha256": "ab", "size": 10 }
+            }
+        });
+        sign(&mut raw);
+
+        let verifier = SignatureVerifier::default();
+        let release = ReleaseManifest::from_signed(&raw, &verifier).unwrap();
+        assert_eq!(release.version, "0.2.0");
+        assert_eq!(release.artifact("darwin-aarch64").unwrap().size, Some(10));
+        assert!(release.artifact("windows-x86_64").is_none());
+
+        raw["version"] = json!("9.9.9");
+        let err = ReleaseManifest::from_signed(&raw, &verifier).unwrap_err();
+        assert_eq!(err.code(), codes::SIGNATURE_INVALID);
+    }
+
+    #[test]
+    fn test_other_signed_documents_rejected_as_release() {
+        let verifier = SignatureVerifier::default();
+
+        // 同一密钥签署的插件 manifest 带有 version 字段，不能被当作发布信息
+        let mut plugin_manifest = json!({ "id": "claude-usage", "name": "Claude", "version": "9.9.9" });
+        sign(&mut plugin_manifest);
+        assert!(matches!(
+            ReleaseManifest::from_signed(&plugin_manifest, &verifier),
+            Err(UpdaterError::InvalidManifest(_))
+        ));
+
+        let mut wrong_type = json!({ "type": "registry", "version": "9.9.9" });
+        sign(&mut wrong_type);
+        assert!(ReleaseManifest::from_signed(&wrong_type, &verifier).is_err());
+    }
+
+    #[test]
+    fn test_install_bundle_replaces_app() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("AiBal.app");
+        std::fs::create_dir_all(bundle.join("Contents/MacOS")).unwrap();
+        std::fs::write(bundle.join("Contents/MacOS/cuk"), b"old").unwrap();
+
+        let archive = dir.path().join("update.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default().unix_permissions(0o755);
+        zip.start_file("AiBal.app/Contents/MacOS/cuk", options).unwrap();
+        zip.write_all(b"new").unwrap();
+        zip.finish().unwrap();
+
+        install_bundle(&archive, &bundle).unwrap();
+        assert_eq!(std::fs::read(bundle.join("Contents/MacOS/cuk")).unwrap(), b"new");
+        assert!(!dir.path().join(".AiBal.app.old").exists());
+        assert!(!dir.path().join(".AiBal.app.update").exists());
+
+        // 安装包中没有 .app 时保留原 bundle
+        let empty = dir.path().join("empty.zip");
+        zip::ZipWriter::new(File::create(&empty).unwrap()).finish().unwrap();
+        assert!(install_bundle(&empty, &bundle).is_err());
+        assert_eq!(std::fs::read(bundle.join("Contents/MacOS/cuk")).unwrap(), b"new");
+    }
+}
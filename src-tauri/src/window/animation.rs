@@ -0,0 +1,142 @@
+// Phase 7.2: 托盘弹窗显示/隐藏动画
+// 由 Rust 编排弹窗淡入淡出，避免弹窗突然出现/消失
+//
+// - macOS: 通过 NSAnimationContext 驱动 NSWindow 的 alphaValue（Core Animation），
+//   隐藏时先淡出，动画结束后再真正 hide 并恢复不透明度
+// - 系统开启"减弱动态效果"（辅助功能）时直接显示/隐藏
+// - 其他平台无动画
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tauri::{Runtime, Window};
+
+/// 淡入时长
+const FADE_IN_DURATION: Duration = Duration::from_millis(140);
+
+/// 淡出时长
+const FADE_OUT_DURATION: Duration = Duration::from_millis(100);
+
+/// 显示/隐藏序号：淡出期间重新显示时，取消延迟的 hide
+static TRANSITION_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 是否播放动画（平台支持且系统未开启减弱动态效果）
+fn should_animate() -> bool {
+    platform::SUPPORTED && !platform::reduce_motion()
+}
+
+/// 淡入显示窗口
+pub fn show_animated<R: Runtime>(window: &Window<R>) -> tauri::Result<()> {
+    TRANSITION_GENERATION.fetch_add(1, Ordering::SeqCst);
+    if !should_animate() {
+        platform::set_alpha(window, 1.0)?;
+        return window.show();
+    }
+
+    platform::set_alpha(window, 0.0)?;
+    window.show()?;
+    platform::animate_alpha(window, 1.0, FADE_IN_DURATION)
+}
+
+/// 淡出后隐藏窗口
+pub fn hide_animated<R: Runtime>(window: &Window<R>) -> tauri::Result<()> {
+    let generation = TRANSITION_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if !should_animate() {
+        return window.hide();
+    }
+
+    platform::animate_alpha(window, 0.0, FADE_OUT_DURATION)?;
+    let window = window.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FADE_OUT_DURATION).await;
+        // 淡出期间窗口被重新显示
+        if TRANSITION_GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        if let Err(e) = window.hide() {
+            log::warn!("淡出后隐藏窗口失败: label={}, error={}", window.label(), e);
+        }
+        // 恢复不透明度，下次显示时由 show_animated 重新淡入
+        if let Err(e) = platform::set_alpha(&window, 1.0) {
+            log::debug!("恢复窗口不透明度失败: {}", e);
+        }
+    });
+    Ok(())
+}
+
+// ============================================================================
+// 平台实现
+// ============================================================================
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::time::Duration;
+
+    use cocoa::base::{id, BOOL, NO};
+    use objc::{class, msg_send, sel, sel_impl};
+    use tauri::{Runtime, Window};
+
+    pub const SUPPORTED: bool = true;
+
+    /// 系统辅助功能中是否开启了"减弱动态效果"
+    pub fn reduce_motion() -> bool {
+        unsafe {
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let reduce: BOOL = msg_send![workspace, accessibilityDisplayShouldReduceMotion];
+            reduce != NO
+        }
+    }
+
+    /// 立即设置窗口不透明度（主线程执行）
+    pub fn set_alpha<R: Runtime>(window: &Window<R>, alpha: f64) -> tauri::Result<()> {
+        // 裸指针不是 Send，按地址传入主线程闭包
+        let ns_window = window.ns_window()? as usize;
+        window.run_on_main_thread(move || unsafe {
+            let _: () = msg_send![ns_window as id, setAlphaValue: alpha];
+        })
+    }
+
+    /// 以 Core Animation 动画过渡到目标不透明度（主线程执行）
+    pub fn animate_alpha<R: Runtime>(
+        window: &Window<R>,
+        alpha: f64,
+        duration: Duration,
+    ) -> tauri::Result<()> {
+        let ns_window = window.ns_window()? as usize;
+        let seconds = duration.as_secs_f64();
+        window.run_on_main_thread(move || unsafe {
+            let _: () = msg_send![class!(NSAnimationContext), beginGrouping];
+            let context: id = msg_send![class!(NSAnimationContext), currentContext];
+            let _: () = msg_send![context, setDuration: seconds];
+            let animator: id = msg_send![ns_window as id, animator];
+            let _: () = msg_send![animator, setAlphaValue: alpha];
+            let _: () = msg_send![class!(NSAnimationContext), endGrouping];
+        })
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use std::time::Duration;
+
+    use tauri::{Runtime, Window};
+
+    /// 非 macOS 平台无窗口动画
+    pub const SUPPORTED: bool = false;
+
+    pub fn reduce_motion() -> bool {
+        false
+    }
+
+    pub fn set_alpha<R: Runtime>(_window: &Window<R>, _alpha: f64) -> tauri::Result<()> {
+        Ok(())
+    }
+
+    pub fn animate_alpha<R: Runtime>(
+        _window: &Window<R>,
+        _alpha: f64,
+        _duration: Duration,
+    ) -> tauri::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+// Phase 7.8: 窗口位置与尺寸记忆
+// 仪表盘和设置窗口记住上次的位置与尺寸:
+// - 窗口移动/缩放时立即更新内存，延迟写入 window_state.json（与插件目录同级），连续拖动只写一次
+// - 窗口销毁时立即写入，避免退出前最后一次调整丢失
+// - WindowManager::open 创建窗口时恢复；显示器布局变化（拔掉外接屏等）时收回到可见区域，
+//   与任何显示器都几乎不重叠则回退为默认尺寸居中
+// - 坐标统一使用逻辑像素，最小化/最大化/全屏状态下的变化不记录
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Monitor, Runtime, Window, WindowEvent};
+
+use super::WindowType;
+
+/// 状态文件名
+const WINDOW_STATE_FILE_NAME: &str = "window_state.json";
+
+/// 写入延迟（连续拖动/缩放期间只在停止后写一次）
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 恢复时窗口与显示器至少重叠的宽度 (逻辑像素)
+const MIN_VISIBLE_WIDTH: f64 = 100.0;
+
+/// 恢复时窗口与显示器至少重叠的高度 (逻辑像素)
+const MIN_VISIBLE_HEIGHT: f64 = 50.0;
+
+/// 可恢复的最小窗口尺寸 (逻辑像素)
+const MIN_WINDOW_SIZE: f64 = 200.0;
+
+/// 记住位置与尺寸的窗口
+const PERSISTED_WINDOWS: [WindowType; 2] = [WindowType::Dashboard, WindowType::Settings];
+
+/// 窗口位置与尺寸（逻辑像素，位置为窗口外框左上角）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// 显示器可用区域（逻辑像素，不含菜单栏和 Dock）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorArea {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl From<&Monitor> for MonitorArea {
+    fn from(monitor: &Monitor) -> Self {
+        let scale = monitor.scale_factor();
+        let area = monitor.work_area();
+        Self {
+            x: area.position.x as f64 / scale,
+            y: area.position.y as f64 / scale,
+            width: area.size.width as f64 / scale,
+            height: area.size.height as f64 / scale,
+        }
+    }
+}
+
+/// 两个区间的重叠长度
+fn overlap(start_a: f64, len_a: f64, start_b: f64, len_b: f64) -> f64 {
+    ((start_a + len_a).min(start_b + len_b) - start_a.max(start_b)).max(0.0)
+}
+
+/// 将保存的位置收回到显示器可见区域
+///
+/// 选择重叠面积最大的显示器，尺寸不超过其可用区域并整体移入；
+/// 与所有显示器都几乎不重叠（显示器已断开）时返回 None
+pub fn fit_to_monitors(saved: WindowGeometry, monitors: &[MonitorArea]) -> Option<WindowGeometry> {
+    if !(saved.width >= MIN_WINDOW_SIZE && saved.height >= MIN_WINDOW_SIZE && saved.x.is_finite() && saved.y.is_finite()) {
+        return None;
+    }
+
+    let (area, visible_width, visible_height) = monitors
+        .iter()
+        .map(|area| {
+            (
+                area,
+                overlap(saved.x, saved.width, area.x, area.width),
+                overlap(saved.y, saved.height, area.y, area.height),
+            )
+        })
+        .max_by(|a, b| (a.1 * a.2).total_cmp(&(b.1 * b.2)))?;
+    if visible_width < MIN_VISIBLE_WIDTH || visible_height < MIN_VISIBLE_HEIGHT {
+        return None;
+    }
+
+    let width = saved.width.min(area.width);
+    let height = saved.height.min(area.height);
+    Some(WindowGeometry {
+        x: saved.x.clamp(area.x, area.x + area.width - width),
+        y: saved.y.clamp(area.y, area.y + area.height - height),
+        width,
+        height,
+    })
+}
+
+/// 是否记住该窗口的位置与尺寸
+fn is_persisted(label: &str) -> bool {
+    PERSISTED_WINDOWS.iter().any(|window_type| window_type.config().label == label)
+}
+
+// ============================================================================
+// 状态存储
+// ============================================================================
+
+/// 窗口位置与尺寸存储
+pub struct WindowGeometryStore {
+    geometries: Mutex<BTreeMap<String, WindowGeometry>>,
+    /// 修改序号：延迟写入时只有最后一次修改真正落盘
+    generation: AtomicU64,
+    /// 持久化路径
+    path: PathBuf,
+}
+
+impl WindowGeometryStore {
+    /// 默认文件路径（与插件目录同级）
+    pub fn file_path(plugins_dir: &Path) -> PathBuf {
+        plugins_dir
+            .parent()
+            .unwrap_or(plugins_dir)
+            .join(WINDOW_STATE_FILE_NAME)
+    }
+
+    /// 从文件加载（文件不存在或无法解析时为空）
+    pub fn load(path: PathBuf) -> Self {
+        let geometries = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| {
+                serde_json::from_str(&content)
+                    .map_err(|e| log::warn!("解析窗口状态失败，忽略: {}", e))
+                    .ok()
+            })
+            .unwrap_or_default();
+        Self {
+            geometries: Mutex::new(geometries),
+            generation: AtomicU64::new(0),
+            path,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, WindowGeometry>> {
+        self.geometries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 保存的窗口位置与尺寸
+    pub fn get(&self, label: &str) -> Option<WindowGeometry> {
+        self.lock().get(label).copied()
+    }
+
+    /// 记录窗口位置与尺寸（仅内存），返回本次修改序号
+    pub fn record(&self, label: &str, geometry: WindowGeometry) -> Option<u64> {
+        let mut geometries = self.lock();
+        if geometries.get(label) == Some(&geometry) {
+            return None;
+        }
+        geometries.insert(label.to_string(), geometry);
+        Some(self.generation.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    /// 写入文件（临时文件 + rename）
+    pub fn save(&self) -> std::io::Result<()> {
+        let content = serde_json::to_vec_pretty(&*self.lock())?;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(&content)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.path)
+    }
+
+    /// 延迟期间没有新的修改时写入
+    fn save_if_current(&self, generation: u64) {
+        if self.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        if let Err(e) = self.save() {
+            log::warn!("保存窗口状态失败: {}", e);
+        }
+    }
+}
+
+// ============================================================================
+// 恢复与记录
+// ============================================================================
+
+/// 读取创建窗口时应恢复的位置与尺寸（已按当前显示器布局校正）
+pub fn restore<R: Runtime>(app: &AppHandle<R>, label: &str) -> Option<WindowGeometry> {
+    if !is_persisted(label) {
+        return None;
+    }
+    let saved = app.try_state::<WindowGeometryStore>()?.get(label)?;
+    let monitors: Vec<MonitorArea> = app
+        .available_monitors()
+        .map_err(|e| log::warn!("获取显示器列表失败: {}", e))
+        .ok()?
+        .iter()
+        .map(MonitorArea::from)
+        .collect();
+
+    let fitted = fit_to_monitors(saved, &monitors);
+    match fitted {
+        Some(geometry) if geometry != saved => log::info!("窗口 {} 的保存位置超出可见区域，已校正", label),
+        None => log::info!("窗口 {} 的保存位置不在任何显示器上，使用默认位置", label),
+        _ => {}
+    }
+    fitted
+}
+
+/// 读取窗口当前位置与尺寸（最小化/最大化/全屏时返回 None）
+fn current_geometry<R: Runtime>(window: &Window<R>) -> Option<WindowGeometry> {
+    if window.is_minimized().unwrap_or(true)
+        || window.is_maximized().unwrap_or(true)
+        || window.is_fullscreen().unwrap_or(true)
+    {
+        return None;
+    }
+    let scale = window.scale_factor().ok()?;
+    let position = window.outer_position().ok()?.to_logical::<f64>(scale);
+    let size = window.inner_size().ok()?.to_logical::<f64>(scale);
+    Some(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// 处理窗口移动/缩放/销毁（在 handle_window_event 中调用）
+pub fn handle_geometry_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if !is_persisted(window.label()) {
+        return;
+    }
+    let Some(store) = window.try_state::<WindowGeometryStore>() else {
+        return;
+    };
+
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+            let Some(geometry) = current_geometry(window) else {
+                return;
+            };
+            let Some(generation) = store.record(window.label(), geometry) else {
+                return;
+            };
+            let app = window.app_handle().clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(SAVE_DEBOUNCE).await;
+                if let Some(store) = app.try_state::<WindowGeometryStore>() {
+                    store.save_if_current(generation);
+                }
+            });
+        }
+        WindowEvent::Destroyed => {
+            if let Err(e) = store.save() {
+                log::warn!("保存窗口状态失败: {}", e);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_to_monitors_and_persist() {
+        let builtin = MonitorArea { x: 0.0, y: 25.0, width: 1440.0, height: 875.0 };
+        let external = MonitorArea { x: 1440.0, y: 0.0, width: 1920.0, height: 1080.0 };
+        let saved = WindowGeometry { x: 1600.0, y: 100.0, width: 900.0, height: 650.0 };
+
+        // 外接屏仍在: 原样恢复
+        assert_eq!(fit_to_monitors(saved, &[builtin, external]), Some(saved));
+        // 外接屏已断开: 回退默认位置
+        assert_eq!(fit_to_monitors(saved, &[builtin]), None);
+        // 部分超出: 尺寸收窄并整体移入
+        let partial = WindowGeometry { x: 1000.0, y: 0.0, width: 1600.0, height: 650.0 };
+        assert_eq!(
+            fit_to_monitors(partial, &[builtin]),
+            Some(WindowGeometry { x: 0.0, y: 25.0, width: 1440.0, height: 650.0 })
+        );
+        assert_eq!(fit_to_monitors(WindowGeometry { width: 10.0, ..saved }, &[external]), None);
+
+        // 只有最后一次修改触发写入
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(WINDOW_STATE_FILE_NAME);
+        let store = WindowGeometryStore::load(path.clone());
+        let first = store.record("dashboard", saved).unwrap();
+        assert_eq!(store.record("dashboard", saved), None);
+        let second = store.record("dashboard", partial).unwrap();
+        store.save_if_current(first);
+        assert!(!path.exists());
+        store.save_if_current(second);
+        assert_eq!(WindowGeometryStore::load(path).get("dashboard"), Some(partial));
+        assert!(is_persisted("settings") && !is_persisted("main"));
+    }
+}
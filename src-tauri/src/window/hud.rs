@@ -0,0 +1,91 @@
+// Phase 7.9: 紧凑 HUD
+// 托盘显示方式设为 hud 时，左键点击托盘不打开弹窗，而是在托盘旁显示一个只有百分比的置顶透明小窗:
+// - 窗口不抢占焦点、鼠标事件穿透，不影响下方应用的操作；再次点击托盘图标时隐藏
+// - 首次显示时以隐藏状态创建，由托盘定位后再显示，之后复用同一个窗口
+// - 切回弹窗模式时立即隐藏
+// - 显示后发送 hud:shown，前端据此重新读取摘要数据
+
+use tauri::{AppHandle, Emitter, Manager, Runtime, WebviewWindow};
+
+use crate::commands::settings::SettingsState;
+use crate::settings::TrayDisplayMode;
+
+use super::WindowType;
+
+/// HUD 窗口标签
+pub const HUD_LABEL: &str = "hud";
+
+/// HUD 显示事件
+pub const HUD_SHOWN: &str = "hud:shown";
+
+/// 是否使用紧凑 HUD 代替托盘弹窗
+pub fn hud_enabled<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.try_state::<SettingsState>()
+        .is_some_and(|settings| settings.0.get().tray_display == TrayDisplayMode::Hud)
+}
+
+/// HUD 是否正在显示
+pub fn is_visible<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.get_webview_window(HUD_LABEL)
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false)
+}
+
+/// 获取 HUD 窗口（不存在时以隐藏状态创建）
+pub fn prepare<R: Runtime>(app: &AppHandle<R>) -> Option<WebviewWindow<R>> {
+    if let Some(window) = app.get_webview_window(HUD_LABEL) {
+        return Some(window);
+    }
+
+    let config = WindowType::Hud.config();
+    let result = tauri::WebviewWindowBuilder::new(
+        app,
+        config.label,
+        tauri::WebviewUrl::App(config.url.into()),
+    )
+    .title(config.title)
+    .inner_size(config.width, config.height)
+    .resizable(config.resizable)
+    .decorations(config.decorations)
+    .transparent(config.transparent)
+    .always_on_top(config.always_on_top)
+    .skip_taskbar(config.skip_taskbar)
+    .visible_on_all_workspaces(true)
+    .shadow(false)
+    .focused(false)
+    .visible(false)
+    .build();
+
+    match result {
+        Ok(window) => {
+            log::info!("HUD 窗口已创建");
+            Some(window)
+        }
+        Err(e) => {
+            log::error!("创建 HUD 窗口失败: {}", e);
+            None
+        }
+    }
+}
+
+/// 显示 HUD（不获取焦点，鼠标事件穿透）
+pub fn show<R: Runtime>(window: &WebviewWindow<R>) -> tauri::Result<()> {
+    window.set_ignore_cursor_events(true)?;
+    window.show()?;
+    window.emit(HUD_SHOWN, ())?;
+    Ok(())
+}
+
+/// 隐藏 HUD，返回是否隐藏了窗口
+pub fn hide<R: Runtime>(app: &AppHandle<R>) -> bool {
+    let Some(window) = app.get_webview_window(HUD_LABEL) else {
+        return false;
+    };
+    match window.hide() {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("隐藏 HUD 失败: {}", e);
+            false
+        }
+    }
+}
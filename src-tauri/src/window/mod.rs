@@ -1,8 +1,22 @@
 // Phase 7.2: 窗口管理
 // 实现多窗口创建和状态同步
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, Manager, Runtime, WebviewWindow};
+use tauri::{AppHandle, Emitter, Manager, Runtime, WebviewWindow, Window, WindowEvent};
+
+use crate::commands::SetupWizardState;
+
+mod animation;
+pub mod geometry;
+pub mod hud;
+pub mod theme;
+
+pub use animation::{hide_animated, show_animated};
+pub use theme::ThemeChangedPayload;
 
 // ============================================================================
 // 窗口类型
@@ -22,6 +36,10 @@ pub enum WindowType {
     Wizard,
     /// 关于窗口
     About,
+    /// 插件输入请求对话框
+    Input,
+    /// 紧凑 HUD（托盘旁的置顶百分比小窗）
+    Hud,
 }
 
 /// 窗口配置
@@ -126,6 +144,36 @@ impl WindowType {
                 title_bar_style: None,
                 hidden_title: false,
             },
+            WindowType::Input => WindowConfig {
+                label: "input",
+                title: "插件请求输入",
+                url: "/input",
+                width: 380.0,
+                height: 320.0,
+                resizable: false,
+                decorations: true,
+                transparent: false,
+                always_on_top: true,
+                center: true,
+                skip_taskbar: true,
+                title_bar_style: None,
+                hidden_title: false,
+            },
+            WindowType::Hud => WindowConfig {
+                label: hud::HUD_LABEL,
+                title: "CUK HUD",
+                url: "/hud",
+                width: 160.0,
+                height: 72.0,
+                resizable: false,
+                decorations: false,
+                transparent: true,
+                always_on_top: true,
+                center: false,
+                skip_taskbar: true,
+                title_bar_style: None,
+                hidden_title: true,
+            },
         }
     }
 }
@@ -174,8 +222,15 @@ impl WindowManager {
             builder = builder.title_bar_style(style);
         }
 
-        if config.center {
-            builder = builder.center();
+        // 恢复上次的位置与尺寸（仅仪表盘/设置窗口）
+        match geometry::restore(app, config.label) {
+            Some(saved) => {
+                builder = builder
+                    .inner_size(saved.width, saved.height)
+                    .position(saved.x, saved.y);
+            }
+            None if config.center => builder = builder.center(),
+            None => {}
         }
 
         match builder.build() {
@@ -283,14 +338,21 @@ impl WindowManager {
         .always_on_top(config.always_on_top)
         .skip_taskbar(config.skip_taskbar)
         .hidden_title(config.hidden_title)
-        .visible(true)
-        .center();
+        .visible(true);
 
         // macOS 标题栏样式
         if let Some(style) = config.title_bar_style {
             builder = builder.title_bar_style(style);
         }
 
+        // 恢复上次的位置与尺寸
+        builder = match geometry::restore(app, config.label) {
+            Some(saved) => builder
+                .inner_size(saved.width, saved.height)
+                .position(saved.x, saved.y),
+            None => builder.center(),
+        };
+
         match builder.build() {
             Ok(window) => {
                 log::info!("仪表盘窗口已创建: route={}", url);
@@ -345,13 +407,13 @@ pub fn broadcast_state<R: Runtime>(
     Ok(())
 }
 
-/// 广播主题变化
+/// 广播主题变化（模式 + 实际外观）
 pub fn broadcast_theme<R: Runtime>(
     app: &AppHandle<R>,
-    theme: &str,
+    theme: &ThemeChangedPayload,
 ) -> Result<(), tauri::Error> {
     app.emit(sync_events::THEME_CHANGED, theme)?;
-    log::debug!("主题变化广播: {}", theme);
+    log::debug!("主题变化广播: {:?}", theme);
     Ok(())
 }
 
@@ -365,20 +427,113 @@ pub fn broadcast_data_update<R: Runtime>(
     Ok(())
 }
 
+// ============================================================================
+// 托盘弹窗失焦自动隐藏
+// ============================================================================
+
+/// 托盘弹窗窗口标签
+pub const POPUP_LABEL: &str = "main";
+
+/// 自动隐藏后的点击抑制时间
+///
+/// 点击托盘图标会先使弹窗失焦（自动隐藏），随后才触发托盘点击事件；
+/// 在此时间内的托盘切换视为"关闭"，避免弹窗被立即重新打开。
+const AUTO_HIDE_CLICK_GRACE: Duration = Duration::from_millis(300);
+
+/// 托盘弹窗状态
+pub struct PopupState {
+    /// 是否固定（固定时失焦不自动隐藏）
+    pinned: AtomicBool,
+    /// 最近一次自动隐藏的时间
+    auto_hidden_at: Mutex<Option<Instant>>,
+}
+
+impl PopupState {
+    pub fn new() -> Self {
+        Self {
+            pinned: AtomicBool::new(false),
+            auto_hidden_at: Mutex::new(None),
+        }
+    }
+
+    /// 是否固定
+    pub fn is_pinned(&self) -> bool {
+        self.pinned.load(Ordering::Relaxed)
+    }
+
+    /// 设置是否固定
+    pub fn set_pinned(&self, pinned: bool) {
+        self.pinned.store(pinned, Ordering::Relaxed);
+    }
+
+    /// 记录自动隐藏
+    fn mark_auto_hidden(&self, at: Instant) {
+        *self.auto_hidden_at.lock().unwrap_or_else(|e| e.into_inner()) = Some(at);
+    }
+
+    /// 是否刚刚自动隐藏（读取后清除记录）
+    pub fn take_recent_auto_hide(&self, now: Instant) -> bool {
+        self.auto_hidden_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .is_some_and(|at| now.duration_since(at) < AUTO_HIDE_CLICK_GRACE)
+    }
+}
+
+impl Default for PopupState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 处理窗口事件（在 Builder::on_window_event 中注册）
+///
+/// 托盘弹窗失去焦点时自动隐藏，用户固定弹窗时除外；系统外观变化交给主题管理器，
+/// 仪表盘/设置窗口的移动和缩放交给位置记忆。
+pub fn handle_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    geometry::handle_geometry_event(window, event);
+    if let WindowEvent::ThemeChanged(theme) = event {
+        theme::handle_theme_changed(window, *theme);
+        return;
+    }
+    if window.label() != POPUP_LABEL {
+        return;
+    }
+    let WindowEvent::Focused(false) = event else {
+        return;
+    };
+
+    let Some(state) = window.try_state::<PopupState>() else {
+        return;
+    };
+    if state.is_pinned() || !window.is_visible().unwrap_or(false) {
+        return;
+    }
+
+    match hide_animated(window) {
+        Ok(()) => {
+            state.mark_auto_hidden(Instant::now());
+            log::debug!("托盘弹窗失焦，已自动隐藏");
+        }
+        Err(e) => log::warn!("自动隐藏托盘弹窗失败: {}", e),
+    }
+}
+
 // ============================================================================
 // 首次设置向导
 // ============================================================================
 
-/// 检查是否需要显示首次设置向导
-pub fn should_show_wizard() -> bool {
-    // TODO: 检查配置文件是否存在，或者用户是否已完成首次设置
-    // 目前暂时返回 false
-    false
+/// 检查是否需要显示首次设置向导（未写入完成标记时显示）
+pub fn should_show_wizard<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.try_state::<SetupWizardState>()
+        .is_some_and(|wizard| !wizard.0.is_completed())
 }
 
 /// 显示首次设置向导
 pub fn show_wizard<R: Runtime>(app: &AppHandle<R>) {
-    if should_show_wizard() {
+    if should_show_wizard(app) {
+        log::info!("首次启动，显示设置向导");
         WindowManager::open(app, WindowType::Wizard);
     }
 }
@@ -398,5 +553,34 @@ mod tests {
         assert_eq!(settings.label, "settings");
         assert!(settings.decorations);
         assert!(settings.resizable);
+
+        let input = WindowType::Input.config();
+        assert_eq!(input.label, "input");
+        assert!(input.always_on_top);
+        assert!(!input.resizable);
+
+        let hud = WindowType::Hud.config();
+        assert_eq!(hud.label, "hud");
+        assert!(hud.transparent && hud.always_on_top && hud.skip_taskbar);
+        assert!(!hud.decorations && !hud.center);
+    }
+
+    #[test]
+    fn test_popup_auto_hide_grace() {
+        let state = PopupState::new();
+        assert!(!state.is_pinned());
+        state.set_pinned(true);
+        assert!(state.is_pinned());
+
+        let now = Instant::now();
+        assert!(!state.take_recent_auto_hide(now));
+
+        state.mark_auto_hidden(now);
+        assert!(state.take_recent_auto_hide(now + Duration::from_millis(50)));
+        // 记录读取后清除
+        assert!(!state.take_recent_auto_hide(now + Duration::from_millis(60)));
+
+        state.mark_auto_hidden(now);
+        assert!(!state.take_recent_auto_hide(now + AUTO_HIDE_CLICK_GRACE));
     }
 }
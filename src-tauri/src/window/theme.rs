@@ -0,0 +1,176 @@
+// Phase 7.7: 主题管理
+// 主题模式保存在应用设置中（system/light/dark），这里负责:
+// - light/dark 时通过 AppHandle::set_theme 强制所有窗口的原生外观，system 时跟随系统
+// - 跟随系统时监听窗口的 ThemeChanged 事件（macOS 外观切换会触发），
+//   系统外观变化后重新广播 window:theme_changed 到所有窗口（含隐藏的托盘弹窗）
+// - 事件 payload 同时包含模式和解析后的实际外观，隐藏窗口无需自行查询系统外观
+
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Runtime, Window};
+
+use crate::commands::settings::SettingsState;
+use crate::settings::Theme;
+
+use super::{broadcast_theme, POPUP_LABEL};
+
+/// 实际外观
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+
+impl From<tauri::Theme> for Appearance {
+    fn from(theme: tauri::Theme) -> Self {
+        match theme {
+            tauri::Theme::Dark => Appearance::Dark,
+            _ => Appearance::Light,
+        }
+    }
+}
+
+/// window:theme_changed 事件 Payload
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeChangedPayload {
+    /// 主题模式
+    pub theme: Theme,
+    /// 解析后的实际外观
+    pub resolved: Appearance,
+}
+
+/// 主题管理器（记录最近观察到的系统外观）
+pub struct ThemeManager {
+    system: Mutex<Appearance>,
+}
+
+impl ThemeManager {
+    pub fn new(system: Appearance) -> Self {
+        Self {
+            system: Mutex::new(system),
+        }
+    }
+
+    /// 当前系统外观
+    pub fn system(&self) -> Appearance {
+        *self.system.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 记录系统外观，返回是否发生变化
+    pub fn observe_system(&self, appearance: Appearance) -> bool {
+        let mut system = self.system.lock().unwrap_or_else(|e| e.into_inner());
+        let changed = *system != appearance;
+        *system = appearance;
+        changed
+    }
+
+    /// 按模式解析实际外观
+    pub fn resolve(&self, mode: Theme) -> ThemeChangedPayload {
+        let resolved = match mode {
+            Theme::Light => Appearance::Light,
+            Theme::Dark => Appearance::Dark,
+            Theme::System => self.system(),
+        };
+        ThemeChangedPayload { theme: mode, resolved }
+    }
+}
+
+/// 当前主题模式（未加载设置时跟随系统）
+fn current_mode<R: Runtime>(app: &AppHandle<R>) -> Theme {
+    app.try_state::<SettingsState>()
+        .map(|settings| settings.0.get().theme)
+        .unwrap_or_default()
+}
+
+/// 当前主题（模式 + 实际外观）
+pub fn current_theme<R: Runtime>(app: &AppHandle<R>) -> ThemeChangedPayload {
+    let mode = current_mode(app);
+    match app.try_state::<ThemeManager>() {
+        Some(manager) => manager.resolve(mode),
+        None => ThemeManager::new(Appearance::Light).resolve(mode),
+    }
+}
+
+/// 创建主题管理器（以托盘弹窗的当前外观作为初始系统外观）
+pub fn create_theme_manager<R: Runtime>(app: &AppHandle<R>) -> ThemeManager {
+    let system = app
+        .get_webview_window(POPUP_LABEL)
+        .and_then(|window| window.theme().ok())
+        .map(Appearance::from)
+        .unwrap_or(Appearance::Light);
+    ThemeManager::new(system)
+}
+
+/// 应用主题模式到原生窗口
+///
+/// 切回 system 时窗口恢复跟随系统，重新读取一次系统外观（强制外观期间的事件不代表系统外观）
+pub fn apply_native_theme<R: Runtime>(app: &AppHandle<R>, mode: Theme) {
+    let native = match mode {
+        Theme::Light => Some(tauri::Theme::Light),
+        Theme::Dark => Some(tauri::Theme::Dark),
+        Theme::System => None,
+    };
+    app.set_theme(native);
+
+    if mode == Theme::System {
+        if let (Some(manager), Some(window)) = (app.try_state::<ThemeManager>(), app.get_webview_window(POPUP_LABEL)) {
+            if let Ok(theme) = window.theme() {
+                manager.observe_system(theme.into());
+            }
+        }
+    }
+}
+
+/// 应用主题模式并广播到所有窗口
+pub fn apply_theme<R: Runtime>(app: &AppHandle<R>, mode: Theme) {
+    apply_native_theme(app, mode);
+    let payload = current_theme(app);
+    if let Err(e) = broadcast_theme(app, &payload) {
+        log::warn!("广播主题变化失败: {}", e);
+    }
+}
+
+/// 处理窗口外观变化（在 handle_window_event 中调用）
+///
+/// 只在跟随系统时记录：强制 light/dark 期间窗口报告的是强制外观
+pub fn handle_theme_changed<R: Runtime>(window: &Window<R>, theme: tauri::Theme) {
+    let app = window.app_handle();
+    if current_mode(app) != Theme::System {
+        return;
+    }
+    let Some(manager) = app.try_state::<ThemeManager>() else {
+        return;
+    };
+    // 每个窗口都会收到同一次系统外观变化，只广播第一次
+    if !manager.observe_system(theme.into()) {
+        return;
+    }
+    log::info!("系统外观已切换: {:?}", Appearance::from(theme));
+    if let Err(e) = broadcast_theme(app, &manager.resolve(Theme::System)) {
+        log::warn!("广播主题变化失败: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_and_observe_system() {
+        let manager = ThemeManager::new(Appearance::Light);
+        assert_eq!(manager.resolve(Theme::Dark).resolved, Appearance::Dark);
+        assert_eq!(manager.resolve(Theme::System).resolved, Appearance::Light);
+
+        // 同一次变化只报告一次
+        assert!(manager.observe_system(Appearance::Dark));
+        assert!(!manager.observe_system(Appearance::Dark));
+        assert_eq!(manager.resolve(Theme::System).resolved, Appearance::Dark);
+        assert_eq!(manager.resolve(Theme::Light).resolved, Appearance::Light);
+
+        let payload = serde_json::to_value(manager.resolve(Theme::System)).unwrap();
+        assert_eq!(payload, serde_json::json!({ "theme": "system", "resolved": "dark" }));
+    }
+}
@@ -48,6 +48,20 @@ const routes: RouteRecordRaw[] = [
     component: () => import('../views/AboutView.vue'),
     meta: { title: '关于 CUK' },
   },
+  // 插件输入请求对话框
+  {
+    path: '/input',
+    name: 'input',
+    component: () => import('../views/InputView.vue'),
+    meta: { title: '插件请求输入 - CUK' },
+  },
+  // 紧凑 HUD（托盘旁的置顶百分比小窗）
+  {
+    path: '/hud',
+    name: 'hud',
+    component: () => import('../views/HudView.vue'),
+    meta: { title: 'HUD - CUK' },
+  },
   // 托盘弹窗页面
   {
     path: '/home',
@@ -35,6 +35,7 @@ function getMockResult(cmd: string): unknown {
         data: [
           {
             id: 'claude-usage',
+            instanceId: 'claude-usage',
             name: 'Claude Usage',
             version: '1.0.0',
             description: 'Claude API 使用量监控',
@@ -46,6 +47,7 @@ function getMockResult(cmd: string): unknown {
           },
           {
             id: 'openai-api',
+            instanceId: 'openai-api',
             name: 'OpenAI API',
             version: '1.0.0',
             description: 'OpenAI API 余额监控',
@@ -57,6 +59,7 @@ function getMockResult(cmd: string): unknown {
           },
           {
             id: 'deepseek',
+            instanceId: 'deepseek',
             name: 'DeepSeek',
             version: '1.0.0',
             description: 'DeepSeek API 余额监控',
@@ -116,7 +119,13 @@ function getMockResult(cmd: string): unknown {
             errorCount: 0,
             totalCalls: 1250,
             consecutiveFailures: 0,
+            cacheHits: 0,
+            cacheMisses: 0,
+            throttledRequests: 0,
+            avgCpuTimeMs: 0,
+            peakMemoryBytes: 0,
             lastSuccess: new Date().toISOString(),
+            alertsMuted: false,
           },
           {
             pluginId: 'openai-api',
@@ -127,7 +136,13 @@ function getMockResult(cmd: string): unknown {
             errorCount: 2,
             totalCalls: 890,
             consecutiveFailures: 0,
+            cacheHits: 0,
+            cacheMisses: 0,
+            throttledRequests: 0,
+            avgCpuTimeMs: 0,
+            peakMemoryBytes: 0,
             lastSuccess: new Date().toISOString(),
+            alertsMuted: false,
           },
           {
             pluginId: 'deepseek',
@@ -138,7 +153,13 @@ function getMockResult(cmd: string): unknown {
             errorCount: 1,
             totalCalls: 560,
             consecutiveFailures: 0,
+            cacheHits: 0,
+            cacheMisses: 0,
+            throttledRequests: 0,
+            avgCpuTimeMs: 0,
+            peakMemoryBytes: 0,
             lastSuccess: new Date().toISOString(),
+            alertsMuted: false,
           },
         ] as PluginHealth[],
       } as Result<PluginHealth[]>;
@@ -1,7 +1,8 @@
 // KeychainService - 敏感数据存储服务
-// 使用 Tauri IPC 调用 Rust 后端的 Keychain 功能
+// 使用 Tauri IPC 调用 Rust 后端的 Keychain 功能（仅设置/仪表盘窗口可调用）
 
 import { invoke } from '@tauri-apps/api/core';
+import type { Result } from '@/types';
 
 // ============================================================================
 // Keychain 服务名称
@@ -24,11 +25,14 @@ class KeychainService {
    * @param value 密钥值
    */
   async set(key: string, value: string): Promise<void> {
-    await invoke('keychain_set', {
+    const result = await invoke<Result<void>>('keychain_set', {
       service: KEYCHAIN_SERVICE,
       key,
       value,
     });
+    if (!result.success) {
+      throw new Error(result.error?.message ?? '写入 Keychain 失败');
+    }
   }
 
   /**
@@ -38,11 +42,11 @@ class KeychainService {
    */
   async get(key: string): Promise<string | null> {
     try {
-      const result = await invoke<string | null>('keychain_get', {
+      const result = await invoke<Result<string | null>>('keychain_get', {
         service: KEYCHAIN_SERVICE,
         key,
       });
-      return result;
+      return result.success ? result.data ?? null : null;
     } catch {
       return null;
     }
@@ -53,10 +57,13 @@ class KeychainService {
    * @param key 密钥名称
    */
   async delete(key: string): Promise<void> {
-    await invoke('keychain_delete', {
+    const result = await invoke<Result<void>>('keychain_delete', {
       service: KEYCHAIN_SERVICE,
       key,
     });
+    if (!result.success) {
+      throw new Error(result.error?.message ?? '删除 Keychain 项失败');
+    }
   }
 
   /**
@@ -339,6 +339,35 @@ class MarketplaceService {
     }
   }
 
+  /**
+   * 从本地 .zip 文件安装插件（拖拽安装）
+   *
+   * @param path 本地文件路径
+   * @param skipSignature 是否跳过签名验证（用户确认后传 true）
+   */
+  async installPluginFromFile(path: string, skipSignature = false): Promise<Result<PluginInfo>> {
+    if (!isTauri) {
+      return {
+        success: false,
+        error: { code: 'NOT_SUPPORTED', message: '浏览器环境不支持本地安装' },
+      };
+    }
+
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      return await invoke<Result<PluginInfo>>('plugin_install_from_file', {
+        path,
+        skipSignature,
+      });
+    } catch (e) {
+      const message = e instanceof Error ? e.message : '安装失败';
+      return {
+        success: false,
+        error: { code: 'INSTALL_ERROR', message },
+      };
+    }
+  }
+
   /**
    * 检查插件是否需要跳过签名验证
    * 用于判断是否需要显示确认对话框
@@ -5,7 +5,14 @@
 import { defineStore } from 'pinia';
 import { ref, computed } from 'vue';
 import { storageService } from '@/services/storage';
-import type { AppSettings, Theme } from '@/types';
+import type {
+  AppSettings,
+  AppSettingsPatch,
+  BackendAppSettings,
+  Result,
+  Theme,
+  ThemeChangedPayload,
+} from '@/types';
 import { DEFAULT_APP_SETTINGS } from '@/types';
 
 // Tauri 环境检测
@@ -14,6 +21,38 @@ const isTauri = typeof window !== 'undefined' && '__TAURI_INTERNALS__' in window
 // 主题同步事件名称（与 Rust 端 window/mod.rs 中的 sync_events::THEME_CHANGED 保持一致）
 const THEME_CHANGED_EVENT = 'window:theme_changed';
 
+// 窗口状态同步事件名称（与 Rust 端 window/mod.rs 中的 sync_events::STATE_CHANGED 保持一致）
+const STATE_CHANGED_EVENT = 'window:state_changed';
+
+// 后端应用设置的状态类型（与 Rust 端 commands/settings.rs 中的 SETTINGS_STATE_TYPE 保持一致）
+const SETTINGS_STATE_TYPE = 'settings';
+
+// 后端应用设置 → 前端设置
+function fromBackendSettings(backend: BackendAppSettings): Partial<AppSettings> {
+  return {
+    theme: backend.theme,
+    trayDisplayMode: backend.trayDisplay,
+    refreshInterval: backend.refresh.intervalMs,
+    refreshOnLaunch: backend.refresh.onLaunch,
+    launchAtLogin: backend.startup.launchAtLogin,
+    showInMenuBar: backend.startup.showInMenuBar,
+  };
+}
+
+// 前端设置修改 → 后端设置 patch（只包含后端管理的字段）
+function toBackendPatch(updates: Partial<AppSettings>): AppSettingsPatch | null {
+  const patch: AppSettingsPatch = {};
+  if (updates.theme !== undefined) patch.theme = updates.theme;
+  if (updates.trayDisplayMode !== undefined) patch.trayDisplay = updates.trayDisplayMode;
+  if (updates.refreshInterval !== undefined || updates.refreshOnLaunch !== undefined) {
+    patch.refresh = { intervalMs: updates.refreshInterval, onLaunch: updates.refreshOnLaunch };
+  }
+  if (updates.launchAtLogin !== undefined || updates.showInMenuBar !== undefined) {
+    patch.startup = { launchAtLogin: updates.launchAtLogin, showInMenuBar: updates.showInMenuBar };
+  }
+  return Object.keys(patch).length > 0 ? patch : null;
+}
+
 export const useAppStore = defineStore('app', () => {
   // 状态
   const settings = ref<AppSettings>(DEFAULT_APP_SETTINGS);
@@ -28,8 +67,8 @@ export const useAppStore = defineStore('app', () => {
   const refreshIntervalSeconds = computed(() => settings.value.refreshInterval / 1000);
   const theme = computed(() => settings.value.theme);
 
-  // 应用主题到 DOM
-  function applyTheme(themeValue: Theme): void {
+  // 应用主题到 DOM（resolved 为后端解析的实际外观，缺省时按媒体查询判断）
+  function applyTheme(themeValue: Theme, resolved?: 'light' | 'dark'): void {
     const root = document.documentElement;
 
     // 移除现有主题类
@@ -38,7 +77,7 @@ export const useAppStore = defineStore('app', () => {
     if (themeValue === 'system') {
       // 跟随系统，不添加类让 CSS 媒体查询生效
       const prefersDark = window.matchMedia('(prefers-color-scheme: dark)').matches;
-      resolvedTheme.value = prefersDark ? 'dark' : 'light';
+      resolvedTheme.value = resolved ?? (prefersDark ? 'dark' : 'light');
     } else {
       // 手动设置主题
       root.classList.add(`theme-${themeValue}`);
@@ -46,40 +85,39 @@ export const useAppStore = defineStore('app', () => {
     }
   }
 
-  // 设置主题
+  // 设置主题（后端保存后会向所有窗口广播 window:theme_changed）
   async function setTheme(newTheme: Theme): Promise<void> {
     await saveSettings({ theme: newTheme });
     applyTheme(newTheme);
-    // 广播主题变化到其他窗口
-    await broadcastThemeChange(newTheme);
   }
 
-  // 广播主题变化到其他窗口
-  async function broadcastThemeChange(theme: Theme): Promise<void> {
+  // 从后端读取当前主题（含 system 模式下的系统外观）
+  async function syncTheme(): Promise<void> {
     if (!isTauri) return;
     try {
-      const { emit } = await import('@tauri-apps/api/event');
-      console.log('[Theme] 广播主题变化:', theme);
-      await emit(THEME_CHANGED_EVENT, theme);
+      const { invoke } = await import('@tauri-apps/api/core');
+      const result = await invoke<Result<ThemeChangedPayload>>('get_theme');
+      if (result.success && result.data) {
+        settings.value = { ...settings.value, theme: result.data.theme };
+        applyTheme(result.data.theme, result.data.resolved);
+      }
     } catch (e) {
-      console.warn('[Theme] 广播主题变化失败:', e);
+      console.warn('[Theme] 获取当前主题失败:', e);
     }
   }
 
-  // 监听其他窗口的主题变化
+  // 监听后端广播的主题变化（设置修改或系统外观切换）
   async function setupThemeSyncListener(): Promise<() => void> {
     if (!isTauri) return () => {};
     try {
       const { listen } = await import('@tauri-apps/api/event');
       console.log('[Theme] 开始监听主题变化事件');
-      const unlisten = await listen<Theme>(THEME_CHANGED_EVENT, async (event) => {
-        const newTheme = event.payload;
-        console.log('[Theme] 收到主题变化事件:', newTheme, '当前:', settings.value.theme);
-        // 仅当主题与当前不同时更新（避免循环）
+      const unlisten = await listen<ThemeChangedPayload>(THEME_CHANGED_EVENT, async (event) => {
+        const { theme: newTheme, resolved } = event.payload;
+        console.log('[Theme] 收到主题变化事件:', newTheme, resolved);
+        applyTheme(newTheme, resolved);
         if (settings.value.theme !== newTheme) {
-          console.log('[Theme] 应用新主题:', newTheme);
           settings.value = { ...settings.value, theme: newTheme };
-          applyTheme(newTheme);
           // 同步到存储
           await storageService.setAppSettings(settings.value);
         }
@@ -91,6 +129,22 @@ export const useAppStore = defineStore('app', () => {
     }
   }
 
+  // 监听后端应用设置变更（其他窗口修改或首次设置向导完成）
+  async function setupSettingsSyncListener(): Promise<() => void> {
+    if (!isTauri) return () => {};
+    try {
+      const { listen } = await import('@tauri-apps/api/event');
+      return await listen<{ stateType: string; data: BackendAppSettings }>(STATE_CHANGED_EVENT, async (event) => {
+        if (event.payload.stateType !== SETTINGS_STATE_TYPE) return;
+        settings.value = { ...settings.value, ...fromBackendSettings(event.payload.data) };
+        await storageService.setAppSettings(settings.value);
+      });
+    } catch (e) {
+      console.warn('[AppStore] 监听应用设置变更失败:', e);
+      return () => {};
+    }
+  }
+
   // 监听系统主题变化
   function setupSystemThemeListener(): void {
     const mediaQuery = window.matchMedia('(prefers-color-scheme: dark)');
@@ -101,12 +155,23 @@ export const useAppStore = defineStore('app', () => {
     });
   }
 
-  // 加载设置
+  // 加载设置（主题、刷新节奏、启动行为以后端为准）
   async function loadSettings(): Promise<void> {
     const saved = await storageService.getAppSettings();
     if (saved) {
       settings.value = { ...DEFAULT_APP_SETTINGS, ...saved };
     }
+    if (isTauri) {
+      try {
+        const { invoke } = await import('@tauri-apps/api/core');
+        const result = await invoke<Result<BackendAppSettings>>('get_app_settings');
+        if (result.success && result.data) {
+          settings.value = { ...settings.value, ...fromBackendSettings(result.data) };
+        }
+      } catch (e) {
+        console.warn('[AppStore] 加载后端应用设置失败:', e);
+      }
+    }
     isInitialized.value = true;
   }
 
@@ -114,6 +179,53 @@ export const useAppStore = defineStore('app', () => {
   async function saveSettings(newSettings: Partial<AppSettings>): Promise<void> {
     settings.value = { ...settings.value, ...newSettings };
     await storageService.setAppSettings(settings.value);
+    const patch = toBackendPatch(newSettings);
+    if (isTauri && patch) {
+      try {
+        const { invoke } = await import('@tauri-apps/api/core');
+        const result = await invoke<Result<BackendAppSettings>>('set_app_settings', { patch });
+        if (!result.success) {
+          console.warn('[AppStore] 保存后端应用设置失败:', result.error?.message);
+        }
+      } catch (e) {
+        console.warn('[AppStore] 保存后端应用设置失败:', e);
+      }
+    }
+  }
+
+  // 同步弹窗固定状态到后端（固定时失焦不自动隐藏）
+  async function syncPopupPinned(): Promise<void> {
+    if (!isTauri) return;
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      await invoke('set_popup_pinned', { pinned: settings.value.pinPopup });
+    } catch (e) {
+      console.warn('[AppStore] 同步弹窗固定状态失败:', e);
+    }
+  }
+
+  // 切换弹窗固定状态
+  async function setPopupPinned(pinned: boolean): Promise<void> {
+    await saveSettings({ pinPopup: pinned });
+    await syncPopupPinned();
+    await syncAppNapPrevention();
+  }
+
+  // 同步 App Nap 规避设置到后端
+  async function syncAppNapPrevention(): Promise<void> {
+    if (!isTauri) return;
+    try {
+      const { invoke } = await import('@tauri-apps/api/core');
+      await invoke('set_app_nap_prevention', { enabled: settings.value.preventAppNap });
+    } catch (e) {
+      console.warn('[AppStore] 同步 App Nap 设置失败:', e);
+    }
+  }
+
+  // 切换 App Nap 规避（电池敏感用户可关闭）
+  async function setPreventAppNap(enabled: boolean): Promise<void> {
+    await saveSettings({ preventAppNap: enabled });
+    await syncAppNapPrevention();
   }
 
   // 设置当前路由
@@ -127,9 +239,12 @@ export const useAppStore = defineStore('app', () => {
     await loadSettings();
     console.log('[AppStore] 加载设置完成, 主题:', settings.value.theme);
     applyTheme(settings.value.theme);
+    await syncTheme();
+    await syncPopupPinned();
     setupSystemThemeListener();
-    // 监听其他窗口的主题变化
+    // 监听后端广播的主题变化
     await setupThemeSyncListener();
+    await setupSettingsSyncListener();
     console.log('[AppStore] 初始化完成');
   }
 
@@ -148,6 +263,8 @@ export const useAppStore = defineStore('app', () => {
     saveSettings,
     setCurrentRoute,
     setTheme,
+    setPopupPinned,
+    setPreventAppNap,
     applyTheme,
     init,
   };
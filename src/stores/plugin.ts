@@ -6,7 +6,16 @@
  */
 import { defineStore } from 'pinia';
 import { ref, computed } from 'vue';
-import type { PluginInfo, PluginData, PluginHealth, Result, InstallStatus, UpdateInfo } from '@/types';
+import type {
+  PluginInfo,
+  PluginData,
+  PluginHealth,
+  Result,
+  InstallStatus,
+  UpdateInfo,
+  DataSummary,
+  BatchResponse,
+} from '@/types';
 import { marketplaceService, storageService, STORAGE_KEYS } from '@/services';
 
 // Tauri 环境检测
@@ -39,6 +48,7 @@ function getMockResult(cmd: string): Result<unknown> {
         data: [
           {
             id: 'claude-usage',
+            instanceId: 'claude-usage',
             name: 'Claude Usage',
             version: '1.0.0',
             description: 'Claude API 使用量监控',
@@ -91,6 +101,8 @@ export const usePluginStore = defineStore('plugin', () => {
   const plugins = ref<PluginInfo[]>([]);
   const pluginData = ref<Map<string, PluginData>>(new Map());
   const pluginHealth = ref<Map<string, PluginHealth>>(new Map());
+  // 多插件数据摘要（由后端聚合）
+  const summary = ref<DataSummary | null>(null);
   const isLoading = ref(false);
   const isRefreshing = ref(false);
   const error = ref<string | null>(null);
@@ -199,6 +211,18 @@ export const usePluginStore = defineStore('plugin', () => {
     }
   }
 
+  // 获取已启用插件数据的聚合摘要（最高使用率、最低余额、最差状态、最近重置）
+  async function fetchSummary(): Promise<void> {
+    try {
+      const result = await safeInvoke<Result<DataSummary>>('get_summary');
+      if (result.success && result.data) {
+        summary.value = result.data;
+      }
+    } catch (e) {
+      console.warn('[Plugin] fetchSummary 失败:', e);
+    }
+  }
+
   // 刷新所有插件数据（执行所有插件的 fetchData）
   async function fetchAllData(force = false): Promise<void> {
     isRefreshing.value = true;
@@ -242,6 +266,31 @@ export const usePluginStore = defineStore('plugin', () => {
     }
   }
 
+  // 在同一快照上获取插件列表、缓存数据和健康状态（避免三次调用之间插件表变化）
+  async function fetchSnapshot(): Promise<void> {
+    try {
+      const result = await safeInvoke<Result<BatchResponse>>('batch_query', {
+        requests: [{ command: 'plugin_list' }, { command: 'get_all_data' }, { command: 'get_all_health' }],
+      });
+      if (!result.success || !result.data) {
+        // 浏览器调试或旧版后端：退回到独立调用
+        await Promise.all([fetchPlugins(), fetchAllHealth(), fetchCachedData()]);
+        return;
+      }
+      for (const item of result.data.results) {
+        if (item.command === 'plugin_list') {
+          plugins.value = item.result;
+        } else if (item.command === 'get_all_data') {
+          pluginData.value = new Map(item.result.map(d => [d.pluginId, d]));
+        } else if (item.command === 'get_all_health') {
+          pluginHealth.value = new Map(item.result.map(h => [h.pluginId, h]));
+        }
+      }
+    } catch (e) {
+      error.value = e instanceof Error ? e.message : '获取插件快照失败';
+    }
+  }
+
   // 检查插件是否正在操作中
   function isOperating(id: string): boolean {
     return operatingPlugins.value.has(id);
@@ -254,7 +303,7 @@ export const usePluginStore = defineStore('plugin', () => {
     try {
       const result = await safeInvoke<Result>('plugin_enable', { id });
       if (result.success) {
-        const plugin = plugins.value.find(p => p.id === id);
+        const plugin = plugins.value.find(p => p.instanceId === id);
         if (plugin) plugin.enabled = true;
         // 持久化启用状态
         await persistEnabledPlugins();
@@ -279,7 +328,7 @@ export const usePluginStore = defineStore('plugin', () => {
     try {
       const result = await safeInvoke<Result>('plugin_disable', { id });
       if (result.success) {
-        const plugin = plugins.value.find(p => p.id === id);
+        const plugin = plugins.value.find(p => p.instanceId === id);
         if (plugin) plugin.enabled = false;
         // 持久化禁用状态
         await persistEnabledPlugins();
@@ -287,9 +336,9 @@ export const usePluginStore = defineStore('plugin', () => {
         await broadcastPluginDisabled(id);
         // 如果当前选中的插件被禁用，自动切换到下一个可用插件
         if (selectedPluginId.value === id) {
-          const nextPlugin = plugins.value.find(p => p.enabled && p.dataType && p.id !== id);
+          const nextPlugin = plugins.value.find(p => p.enabled && p.dataType && p.instanceId !== id);
           if (nextPlugin) {
-            await selectPlugin(nextPlugin.id);
+            await selectPlugin(nextPlugin.instanceId);
           } else {
             // 没有可用插件，清空选择
             selectedPluginId.value = '';
@@ -427,15 +476,18 @@ export const usePluginStore = defineStore('plugin', () => {
   // 保存插件配置
   async function savePluginConfig(id: string, config: Record<string, unknown>): Promise<boolean> {
     try {
-      // 1. 先持久化到本地存储（确保重启后配置不丢失）
-      await storageService.setPluginConfig(id, config);
-
-      // 2. 同步到后端内存
+      // 1. 同步到后端内存（后端按 configSchema 校验，失败时不保存）
       const result = await safeInvoke<Result>('set_plugin_config', { id, config });
       if (!result.success) {
         error.value = result.error?.message ?? '保存插件配置失败';
+        return false;
       }
-      return result.success;
+
+      // 2. 校验通过后持久化到本地存储（确保重启后配置不丢失）
+      // 敏感字段已由后端移入钥匙串，只保存后端返回的引用，不落盘明文
+      const saved = await safeInvoke<Result<Record<string, unknown>>>('get_plugin_config', { id });
+      await storageService.setPluginConfig(id, saved.success && saved.data ? saved.data : {});
+      return true;
     } catch (e) {
       error.value = e instanceof Error ? e.message : '保存插件配置失败';
       return false;
@@ -449,8 +501,8 @@ export const usePluginStore = defineStore('plugin', () => {
     try {
       const result = await safeInvoke<Result>('plugin_uninstall', { id });
       if (result.success) {
-        // 从本地列表移除
-        plugins.value = plugins.value.filter(p => p.id !== id);
+        // 从本地列表移除（卸载插件时其他账户一并移除）
+        plugins.value = plugins.value.filter(p => p.instanceId !== id && p.id !== id);
         pluginData.value.delete(id);
         pluginHealth.value.delete(id);
         return true;
@@ -490,6 +542,22 @@ export const usePluginStore = defineStore('plugin', () => {
     }
   }
 
+  // 为数据插件添加账户（新账户默认禁用，配置凭证后启用）
+  async function addPluginAccount(pluginId: string, label: string): Promise<PluginInfo | null> {
+    try {
+      const result = await safeInvoke<Result<PluginInfo>>('add_plugin_account', { pluginId, label });
+      if (result.success && result.data) {
+        plugins.value.push(result.data);
+        return result.data;
+      }
+      error.value = result.error?.message ?? '添加账户失败';
+      return null;
+    } catch (e) {
+      error.value = e instanceof Error ? e.message : '添加账户失败';
+      return null;
+    }
+  }
+
   // 检查插件更新（返回类型与契约 UpdateInfo[] 对齐）
   async function checkUpdates(): Promise<UpdateInfo[]> {
     try {
@@ -511,7 +579,7 @@ export const usePluginStore = defineStore('plugin', () => {
       const result = await safeInvoke<Result<PluginInfo>>('plugin_update', { id });
       if (result.success && result.data) {
         // 更新本地插件信息
-        const index = plugins.value.findIndex(p => p.id === id);
+        const index = plugins.value.findIndex(p => p.instanceId === id);
         if (index !== -1) {
           plugins.value[index] = result.data;
         }
@@ -529,7 +597,7 @@ export const usePluginStore = defineStore('plugin', () => {
 
   // 持久化启用的插件列表
   async function persistEnabledPlugins(): Promise<void> {
-    const enabledIds = plugins.value.filter(p => p.enabled).map(p => p.id);
+    const enabledIds = plugins.value.filter(p => p.enabled).map(p => p.instanceId);
     await storageService.updateAppSettings({ enabledPlugins: enabledIds });
   }
 
@@ -637,6 +705,28 @@ export const usePluginStore = defineStore('plugin', () => {
     }
   }
 
+  // 监听后端推送的插件数据更新（返回 cleanup 函数）
+  // 任何来源的数据更新都会推送到所有窗口，无需轮询 get_all_data
+  async function setupDataUpdatedListener(): Promise<() => void> {
+    if (!isTauri) return () => {};
+    try {
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = await listen<{ id: string; data: PluginData }>('plugin:data_updated', (event) => {
+        const { id, data } = event.payload;
+        const existing = pluginData.value.get(id);
+        // 乱序到达的旧数据不覆盖新数据
+        if (existing && new Date(data.lastUpdated) < new Date(existing.lastUpdated)) return;
+        const newMap = new Map(pluginData.value);
+        newMap.set(id, data);
+        pluginData.value = newMap;
+      });
+      return unlisten;
+    } catch (e) {
+      console.warn('[Plugin] 监听插件数据更新失败:', e);
+      return () => {};
+    }
+  }
+
   // 设置插件错误（用于触发响应式更新）
   function setPluginError(id: string, pluginError: { code: string; message: string }): void {
     const newErrors = new Map(pluginErrors.value);
@@ -674,8 +764,15 @@ export const usePluginStore = defineStore('plugin', () => {
       errorCount: currentHealth?.errorCount ?? 0,
       totalCalls: currentHealth?.totalCalls ?? 0,
       consecutiveFailures: (currentHealth?.consecutiveFailures ?? 0) + 1,
+      cacheHits: currentHealth?.cacheHits ?? 0,
+      cacheMisses: currentHealth?.cacheMisses ?? 0,
+      throttledRequests: currentHealth?.throttledRequests ?? 0,
+      avgCpuTimeMs: currentHealth?.avgCpuTimeMs ?? 0,
+      peakMemoryBytes: currentHealth?.peakMemoryBytes ?? 0,
       lastSuccess: currentHealth?.lastSuccess,
       lastError: new Date().toISOString(),
+      alertsMuted: currentHealth?.alertsMuted ?? false,
+      alertsSnoozedUntil: currentHealth?.alertsSnoozedUntil,
     };
 
     const newHealthMap = new Map(pluginHealth.value);
@@ -742,8 +839,15 @@ export const usePluginStore = defineStore('plugin', () => {
       errorCount: stats.errorCount,
       totalCalls: (currentHealth?.totalCalls ?? 0) + 1,
       consecutiveFailures: success ? 0 : (currentHealth?.consecutiveFailures ?? 0) + 1,
+      cacheHits: currentHealth?.cacheHits ?? 0,
+      cacheMisses: currentHealth?.cacheMisses ?? 0,
+      throttledRequests: currentHealth?.throttledRequests ?? 0,
+      avgCpuTimeMs: currentHealth?.avgCpuTimeMs ?? 0,
+      peakMemoryBytes: currentHealth?.peakMemoryBytes ?? 0,
       lastSuccess: success ? new Date().toISOString() : currentHealth?.lastSuccess,
       lastError: !success ? new Date().toISOString() : currentHealth?.lastError,
+      alertsMuted: currentHealth?.alertsMuted ?? false,
+      alertsSnoozedUntil: currentHealth?.alertsSnoozedUntil,
     };
 
     // 触发响应式更新
@@ -776,32 +880,33 @@ export const usePluginStore = defineStore('plugin', () => {
   }
 
   // 恢复插件启用状态
+  // 后端启动时已从 state.json 恢复，此处与前端存储对齐（兼容升级前仅保存在前端的状态）
   async function restoreEnabledPlugins(): Promise<void> {
     const settings = await storageService.getAppSettings();
     const savedEnabledIds = settings.enabledPlugins || [];
 
     // 根据持久化的状态恢复插件启用
     for (const plugin of plugins.value) {
-      const shouldBeEnabled = savedEnabledIds.includes(plugin.id);
+      const shouldBeEnabled = savedEnabledIds.includes(plugin.instanceId);
       if (shouldBeEnabled && !plugin.enabled) {
         // 需要启用
         try {
-          const result = await safeInvoke<Result>('plugin_enable', { id: plugin.id });
+          const result = await safeInvoke<Result>('plugin_enable', { id: plugin.instanceId });
           if (result.success) {
             plugin.enabled = true;
           }
         } catch (e) {
-          console.warn(`恢复插件启用状态失败: ${plugin.id}`, e);
+          console.warn(`恢复插件启用状态失败: ${plugin.instanceId}`, e);
         }
       } else if (!shouldBeEnabled && plugin.enabled) {
         // 需要禁用
         try {
-          const result = await safeInvoke<Result>('plugin_disable', { id: plugin.id });
+          const result = await safeInvoke<Result>('plugin_disable', { id: plugin.instanceId });
           if (result.success) {
             plugin.enabled = false;
           }
         } catch (e) {
-          console.warn(`恢复插件禁用状态失败: ${plugin.id}`, e);
+          console.warn(`恢复插件禁用状态失败: ${plugin.instanceId}`, e);
         }
       }
     }
@@ -811,14 +916,21 @@ export const usePluginStore = defineStore('plugin', () => {
   async function restorePluginConfigs(): Promise<void> {
     for (const plugin of plugins.value) {
       try {
-        const savedConfig = await storageService.getPluginConfig<Record<string, unknown>>(plugin.id);
+        const savedConfig = await storageService.getPluginConfig<Record<string, unknown>>(plugin.instanceId);
         if (savedConfig && Object.keys(savedConfig).length > 0) {
           // 同步配置到后端内存
-          await safeInvoke<Result>('set_plugin_config', { id: plugin.id, config: savedConfig });
-          console.info(`[Plugin] 已恢复插件配置: ${plugin.id}`);
+          const result = await safeInvoke<Result>('set_plugin_config', { id: plugin.instanceId, config: savedConfig });
+          if (result.success) {
+            // 旧版本可能在本地存储中保存了敏感字段明文，改存后端返回的钥匙串引用
+            const saved = await safeInvoke<Result<Record<string, unknown>>>('get_plugin_config', { id: plugin.instanceId });
+            if (saved.success && saved.data) {
+              await storageService.setPluginConfig(plugin.instanceId, saved.data);
+            }
+          }
+          console.info(`[Plugin] 已恢复插件配置: ${plugin.instanceId}`);
         }
       } catch (e) {
-        console.warn(`恢复插件配置失败: ${plugin.id}`, e);
+        console.warn(`恢复插件配置失败: ${plugin.instanceId}`, e);
       }
     }
   }
@@ -834,22 +946,24 @@ export const usePluginStore = defineStore('plugin', () => {
     // 4. 恢复持久化的选中插件 ID
     const savedPluginId = await storageService.get<string>(STORAGE_KEYS.SELECTED_PLUGIN_ID);
     const enabledDataPlugins = plugins.value.filter(p => p.enabled && p.dataType);
-    if (savedPluginId && enabledDataPlugins.some(p => p.id === savedPluginId)) {
+    if (savedPluginId && enabledDataPlugins.some(p => p.instanceId === savedPluginId)) {
       // 恢复持久化的选择
       selectedPluginId.value = savedPluginId;
     } else if (!selectedPluginId.value) {
       // 默认选中第一个有数据类型的启用插件
       const firstPlugin = enabledDataPlugins[0];
       if (firstPlugin) {
-        selectedPluginId.value = firstPlugin.id;
+        selectedPluginId.value = firstPlugin.instanceId;
       }
     }
-    // 5. 获取健康状态和缓存数据（不执行插件，快速初始化）
-    await Promise.all([fetchAllHealth(), fetchCachedData()]);
+    // 5. 同一快照获取插件列表、健康状态和缓存数据（不执行插件，快速初始化）
+    await fetchSnapshot();
     // 6. 监听其他窗口的插件选择
     await setupPluginSelectionListener();
     // 7. 监听插件错误事件（用于 UI 显示错误状态）
     await setupPluginErrorListener();
+    // 8. 监听插件数据更新（所有窗口实时更新）
+    await setupDataUpdatedListener();
   }
 
   // ============================================================================
@@ -933,7 +1047,7 @@ export const usePluginStore = defineStore('plugin', () => {
 
         // 使用返回的 PluginInfo 更新本地列表（避免额外 fetchPlugins）
         const newPlugin = result.data;
-        const existingIndex = plugins.value.findIndex(p => p.id === newPlugin.id);
+        const existingIndex = plugins.value.findIndex(p => p.instanceId === newPlugin.instanceId);
         if (existingIndex !== -1) {
           // 更新已有插件
           plugins.value[existingIndex] = newPlugin;
@@ -1021,6 +1135,7 @@ export const usePluginStore = defineStore('plugin', () => {
     plugins,
     pluginData,
     pluginHealth,
+    summary,
     isLoading,
     isRefreshing,
     error,
@@ -1045,8 +1160,10 @@ export const usePluginStore = defineStore('plugin', () => {
     // 方法
     fetchPlugins,
     fetchCachedData,
+    fetchSummary,
     fetchAllData,
     fetchAllHealth,
+    fetchSnapshot,
     isOperating,
     enablePlugin,
     disablePlugin,
@@ -1056,6 +1173,7 @@ export const usePluginStore = defineStore('plugin', () => {
     savePluginConfig,
     uninstallPlugin,
     reloadPlugin,
+    addPluginAccount,
     checkUpdates,
     updatePlugin,
     init,
@@ -1063,6 +1181,7 @@ export const usePluginStore = defineStore('plugin', () => {
     setupPluginSelectionListener,
     setupPluginDisabledListener,
     setupPluginEnabledListener,
+    setupDataUpdatedListener,
     // 安装方法
     isInstalled,
     getInstalledVersion,
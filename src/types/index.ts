@@ -19,15 +19,20 @@ export type {
   // IPC Commands
   Result,
   AppError,
+  IpcErrorCode,
   PluginType,
   DataType,
   PluginInfo,
   UpdateInfo,
   ValidationResult,
+  FieldValidationError,
   HealthStatus,
   PluginHealth,
+  HealthTransition,
+  PluginResourceStats,
   PluginDataBase,
   UsageDimension,
+  TokenBreakdown,
   UsageData,
   BalanceItem,
   BalanceData,
@@ -35,6 +40,83 @@ export type {
   StatusData,
   CustomData,
   PluginData,
+  InputField,
+  InputRequest,
+  NetworkStatus,
+  AdminPolicy,
+  PolicyStatus,
+  SystemHealth,
+  IntegrityReport,
+  AuditEventKind,
+  AuditEvent,
+  RegistryEntry,
+  RegistryManifest,
+  PluginBackup,
+  Incompatibility,
+  IncompatiblePlugin,
+  PluginSandboxLimits,
+  PluginDescription,
+  BulkOp,
+  BulkItemResult,
+  BulkResult,
+  KeyState,
+  TrustedKeyInfo,
+  PublisherKeyStatus,
+  UpdateSettings,
+  UsageAlertSettings,
+  MissedAlert,
+  MissedAlertSummary,
+  AlertType,
+  AlertSeverity,
+  AlertRecord,
+  AlertHistoryFilter,
+  WatchdogPolicy,
+  ProxySettings,
+  TlsSettings,
+  CustomRootCertificate,
+  HostReport,
+  PrivacyReport,
+  PluginStorageStats,
+  Budget,
+  BudgetLine,
+  BudgetStatus,
+  ModelPrice,
+  PriceTable,
+  CostRange,
+  ModelCost,
+  CostEstimate,
+  EstimatedSpend,
+  TrustLevel,
+  DeveloperSettings,
+  ImportedKey,
+  SkippedKey,
+  EnvImportReport,
+  PushSettings,
+  PopupSummary,
+  UsageTrend,
+
+  // 数据摘要
+  DataSummary,
+  BatchQuery,
+  BatchResult,
+  BatchResponse,
+  GatewayAuth,
+  GatewayProfile,
+  GatewayConfig,
+  DeprecatedCommand,
+  LegacyCommandReport,
+  AppSettings as BackendAppSettings,
+  AppSettingsPatch,
+  ThemeChangedPayload,
+  WizardStep,
+  WizardStepInput,
+  WizardProgress,
+  WizardPluginOption,
+  WizardState,
+  AppUpdatePhase,
+  AppRelease,
+  AppUpdateStatus,
+  PluginAccountData,
   PluginManagementCommands,
   DataCommands,
   ConfigCommands,
@@ -46,10 +128,28 @@ export type {
   IPCEventName,
   PluginInstalledEvent,
   PluginUninstalledEvent,
+  PluginBulkCompletedEvent,
   PluginUpdatedEvent,
   PluginDataUpdatedEvent,
+  CrossingDirection,
+  BoundaryCrossing,
+  PercentageChange,
+  PluginDataChange,
+  PluginDataChangedEvent,
   PluginErrorEvent,
   PluginHealthChangedEvent,
+  PluginHealthTransitionEvent,
+  InputRequestedEvent,
+  NetworkStatusChangedEvent,
+  UpdateAvailableEvent,
+  PluginReloadedEvent,
+  InstallStage,
+  InstallProgress,
+  InstallProgressEvent,
+  BudgetWarningEvent,
+  DeprecatedCommandEvent,
+  WizardFinishedEvent,
+  AppUpdateStatusEvent,
   IPCEvent,
   IPCEventHandlers,
   IPCEventListener,
@@ -85,6 +185,8 @@ export enum PluginErrorType {
   STORAGE_LIMIT = 'STORAGE_LIMIT',
   CACHE_ERROR = 'CACHE_ERROR',
   INCOMPATIBLE_API_VERSION = 'INCOMPATIBLE_API_VERSION',
+  INPUT_CANCELLED = 'INPUT_CANCELLED',
+  INPUT_UNAVAILABLE = 'INPUT_UNAVAILABLE',
   UNKNOWN = 'UNKNOWN',
 }
 
@@ -153,6 +255,12 @@ export interface AppSettings {
   showInMenuBar: boolean;
   /** 主题设置 */
   theme: Theme;
+  /** 左键点击托盘时的显示方式（popup: 弹窗，hud: 紧凑 HUD） */
+  trayDisplayMode: 'popup' | 'hud';
+  /** 固定托盘弹窗（失焦时不自动隐藏） */
+  pinPopup: boolean;
+  /** 批量刷新期间阻止 App Nap（macOS） */
+  preventAppNap: boolean;
   /** 插件市场仓库 URL（可选，默认使用内置地址） */
   marketplaceUrl?: string;
 }
@@ -167,6 +275,9 @@ export const DEFAULT_APP_SETTINGS: AppSettings = {
   launchAtLogin: false,
   showInMenuBar: true,
   theme: 'system',
+  trayDisplayMode: 'popup',
+  pinPopup: false,
+  preventAppNap: true,
 };
 
 /**
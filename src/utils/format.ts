@@ -56,3 +56,10 @@ export function formatUsedQuota(used: number, total: number, currency?: string):
   // 包月模式
   return `已用 ${formatLargeNumber(used)}/${formatLargeNumber(total)}`;
 }
+
+/**
+ * 插件显示名称（同一插件的其他账户显示为 "名称 · 账户"）
+ */
+export function pluginDisplayName(plugin: { name: string; accountLabel?: string }): string {
+  return plugin.accountLabel ? `${plugin.name} · ${plugin.accountLabel}` : plugin.name;
+}